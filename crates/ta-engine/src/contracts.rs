@@ -37,6 +37,127 @@ impl TaSeriesF64 {
     pub fn is_empty(&self) -> bool {
         self.values.is_empty()
     }
+
+    /// Element-wise addition. A slot is available in the result only where
+    /// it was available in both operands -- unavailable slots propagate
+    /// like a `None` would, regardless of what garbage value sits under
+    /// them.
+    pub fn add(&self, other: &Self) -> Result<Self, TaStatusCode> {
+        self.zip_with(other, |a, b| a + b)
+    }
+
+    /// Element-wise subtraction; see [`Self::add`] for mask propagation.
+    pub fn sub(&self, other: &Self) -> Result<Self, TaStatusCode> {
+        self.zip_with(other, |a, b| a - b)
+    }
+
+    /// Element-wise multiplication; see [`Self::add`] for mask propagation.
+    pub fn mul(&self, other: &Self) -> Result<Self, TaStatusCode> {
+        self.zip_with(other, |a, b| a * b)
+    }
+
+    /// Element-wise division; see [`Self::add`] for mask propagation. A
+    /// zero divisor yields `inf`/`NaN` in the value, same as plain `f64`
+    /// division -- it does not by itself make the slot unavailable.
+    pub fn div(&self, other: &Self) -> Result<Self, TaStatusCode> {
+        self.zip_with(other, |a, b| a / b)
+    }
+
+    fn zip_with(&self, other: &Self, op: impl Fn(f64, f64) -> f64) -> Result<Self, TaStatusCode> {
+        if self.len() != other.len() {
+            return Err(TaStatusCode::ShapeMismatch);
+        }
+
+        let values = self
+            .values
+            .iter()
+            .zip(&other.values)
+            .map(|(a, b)| op(*a, *b))
+            .collect();
+        let availability_mask = self
+            .availability_mask
+            .iter()
+            .zip(&other.availability_mask)
+            .map(|(a, b)| *a && *b)
+            .collect();
+
+        Ok(Self {
+            values,
+            availability_mask,
+        })
+    }
+
+    /// Adds a scalar to every value, leaving `availability_mask` untouched.
+    pub fn add_scalar(&self, scalar: f64) -> Self {
+        self.map_scalar(|v| v + scalar)
+    }
+
+    /// Subtracts a scalar from every value, leaving `availability_mask`
+    /// untouched.
+    pub fn sub_scalar(&self, scalar: f64) -> Self {
+        self.map_scalar(|v| v - scalar)
+    }
+
+    /// Multiplies every value by a scalar, leaving `availability_mask`
+    /// untouched.
+    pub fn mul_scalar(&self, scalar: f64) -> Self {
+        self.map_scalar(|v| v * scalar)
+    }
+
+    /// Divides every value by a scalar, leaving `availability_mask`
+    /// untouched.
+    pub fn div_scalar(&self, scalar: f64) -> Self {
+        self.map_scalar(|v| v / scalar)
+    }
+
+    fn map_scalar(&self, op: impl Fn(f64) -> f64) -> Self {
+        Self {
+            values: self.values.iter().map(|v| op(*v)).collect(),
+            availability_mask: self.availability_mask.clone(),
+        }
+    }
+
+    /// Carries the last available value forward into each unavailable slot
+    /// that follows it, marking every slot it fills as available. Leading
+    /// unavailable slots with nothing to carry forward from are left
+    /// untouched.
+    pub fn fill_forward(&self) -> Self {
+        let mut values = self.values.clone();
+        let mut availability_mask = self.availability_mask.clone();
+        let mut last_available: Option<f64> = None;
+
+        for i in 0..values.len() {
+            if availability_mask[i] {
+                last_available = Some(values[i]);
+            } else if let Some(carry) = last_available {
+                values[i] = carry;
+                availability_mask[i] = true;
+            }
+        }
+
+        Self {
+            values,
+            availability_mask,
+        }
+    }
+
+    /// Fills every unavailable slot with `value` and marks it available.
+    pub fn fill_value(&self, value: f64) -> Self {
+        let mut values = self.values.clone();
+        let mut availability_mask = self.availability_mask.clone();
+
+        for i in 0..values.len() {
+            if !availability_mask[i] {
+                values[i] = value;
+                availability_mask[i] = true;
+            }
+        }
+
+        Self {
+            values,
+            availability_mask,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -66,20 +187,41 @@ pub struct RustExecutionRequest {
 pub struct RustExecutionPayload {
     pub dataset_id: u64,
     pub partition: RustExecutionPartition,
+    /// Extra partitions to run the same `graph`/`requests` against, e.g. a
+    /// basket of symbols on the same timeframe/source. Empty for the
+    /// ordinary single-partition payload; when non-empty,
+    /// `payload_parse::parse_execute_plan_payloads` expands this payload
+    /// into one `ExecutePlanPayload` per entry here, resolving `requests`'
+    /// kernel ids once and sharing
+    /// them read-only across every partition instead of re-resolving per
+    /// partition. `partition` is ignored when this is non-empty, matching
+    /// `execute_plan_graph_prefix`'s convention for multi-partition fan-out.
+    pub partitions: Vec<RustExecutionPartition>,
     pub graph: RustExecutionGraph,
     pub requests: Vec<RustExecutionRequest>,
 }
 
+fn validate_partition(partition: &RustExecutionPartition) -> Result<(), String> {
+    if partition.symbol.trim().is_empty() {
+        return Err("partition.symbol must be non-empty".to_string());
+    }
+    if partition.timeframe.trim().is_empty() {
+        return Err("partition.timeframe must be non-empty".to_string());
+    }
+    if partition.source.trim().is_empty() {
+        return Err("partition.source must be non-empty".to_string());
+    }
+    Ok(())
+}
+
 impl RustExecutionPayload {
     pub fn validate(&self) -> Result<(), String> {
-        if self.partition.symbol.trim().is_empty() {
-            return Err("partition.symbol must be non-empty".to_string());
-        }
-        if self.partition.timeframe.trim().is_empty() {
-            return Err("partition.timeframe must be non-empty".to_string());
-        }
-        if self.partition.source.trim().is_empty() {
-            return Err("partition.source must be non-empty".to_string());
+        if self.partitions.is_empty() {
+            validate_partition(&self.partition)?;
+        } else {
+            for (i, partition) in self.partitions.iter().enumerate() {
+                validate_partition(partition).map_err(|e| format!("partitions[{i}]: {e}"))?;
+            }
         }
         if self.graph.node_order.is_empty() {
             return Err("graph.node_order must be non-empty".to_string());
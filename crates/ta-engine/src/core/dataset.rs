@@ -0,0 +1,802 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+use crate::indicators::fill_policy::FillPolicy;
+
+pub type DatasetId = u64;
+
+/// Ordered `symbol -> timeframe -> source` so that storing partitions in a
+/// `BTreeMap<DatasetPartitionKey, _>` makes fixing a leading prefix of the
+/// tuple (just `symbol`, or `symbol` + `timeframe`) a contiguous range --
+/// see [`list_partitions`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DatasetPartitionKey {
+    pub symbol: String,
+    pub timeframe: String,
+    pub source: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OhlcvColumns {
+    pub timestamps: Vec<i64>,
+    pub open: Vec<f64>,
+    pub high: Vec<f64>,
+    pub low: Vec<f64>,
+    pub close: Vec<f64>,
+    pub volume: Vec<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeriesColumn {
+    pub timestamps: Vec<i64>,
+    pub values: Vec<f64>,
+}
+
+/// A dense, merge-joined view over OHLCV columns and/or named series that
+/// otherwise each carry their own (possibly sparse) timestamp vector --
+/// see [`DatasetRegistry::get_aligned`]. `columns[i]` holds the resolved
+/// values for the field requested at index `i`, one entry per timestamp in
+/// `timestamps`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlignedColumns {
+    pub timestamps: Vec<i64>,
+    pub columns: Vec<Vec<f64>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatasetPartition {
+    pub ohlcv: Option<OhlcvColumns>,
+    pub series: HashMap<String, SeriesColumn>,
+}
+
+impl DatasetPartition {
+    fn new() -> Self {
+        Self {
+            ohlcv: None,
+            series: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatasetRecord {
+    pub id: DatasetId,
+    pub partitions: BTreeMap<DatasetPartitionKey, DatasetPartition>,
+    /// Secondary indices mirroring `partitions`, keyed by one field of the
+    /// composite key, so [`DatasetRegistry::find_partitions`] can answer a
+    /// symbol-only or timeframe-only filter without scanning every
+    /// partition -- the same "address by a prefix of the composite key"
+    /// idea subxt uses for its storage addresses, just generalized to any
+    /// single field rather than only a leading prefix.
+    by_symbol: HashMap<String, HashSet<DatasetPartitionKey>>,
+    by_timeframe: HashMap<String, HashSet<DatasetPartitionKey>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatasetInfo {
+    pub id: DatasetId,
+    pub partition_count: usize,
+    pub ohlcv_row_count: usize,
+    pub series_row_count: usize,
+    pub series_count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DatasetRegistryError {
+    UnknownDatasetId(DatasetId),
+    LengthMismatch {
+        field: &'static str,
+        expected: usize,
+        got: usize,
+    },
+    NonMonotonicTimestamps {
+        field: &'static str,
+    },
+    EmptyField {
+        field: &'static str,
+    },
+    /// `list_partitions`/`execute_plan_prefix` were given a `timeframe`
+    /// filter without a `symbol` filter -- `timeframe` isn't a leading
+    /// field of the ordered key, so it can't be scanned as a prefix on its
+    /// own.
+    InvalidPartitionFilter {
+        message: &'static str,
+    },
+    /// `get_ohlcv_range`/`get_series_range` were given a `start_ts` after
+    /// `end_ts` -- there's no well-defined `[start_ts, end_ts]` window to
+    /// slice.
+    InvalidTimeRange {
+        message: &'static str,
+    },
+    /// A `dataset_load_mmap` layout/file problem -- missing column, offset
+    /// past the end of the file, or the file couldn't be opened/mapped.
+    MmapLayout {
+        message: String,
+    },
+}
+
+impl std::fmt::Display for DatasetRegistryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownDatasetId(id) => write!(f, "unknown dataset id: {id}"),
+            Self::LengthMismatch {
+                field,
+                expected,
+                got,
+            } => write!(
+                f,
+                "length mismatch for {field}: expected {expected}, got {got}"
+            ),
+            Self::NonMonotonicTimestamps { field } => {
+                write!(f, "timestamps must be non-decreasing for {field}")
+            }
+            Self::EmptyField { field } => write!(f, "empty field not allowed: {field}"),
+            Self::InvalidPartitionFilter { message } => write!(f, "{message}"),
+            Self::InvalidTimeRange { message } => write!(f, "{message}"),
+            Self::MmapLayout { message } => write!(f, "mmap layout error: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for DatasetRegistryError {}
+
+/// An injectable handle for a process's dataset store, so tests and
+/// embedders aren't forced to share the single process-wide default
+/// instance `registry()` exposes -- the same reason moonfire-nvr routes
+/// wall-clock reads through an injectable `Clocks` trait rather than
+/// calling `SystemTime::now()` directly. Each `DatasetRegistry` owns its
+/// own id counter, so ids from two separate registries can collide; they're
+/// only unique within the registry that issued them.
+pub struct DatasetRegistry {
+    next_id: AtomicU64,
+    datasets: Mutex<HashMap<DatasetId, DatasetRecord>>,
+}
+
+impl DatasetRegistry {
+    pub fn new() -> Self {
+        Self {
+            next_id: AtomicU64::new(1),
+            datasets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn create_dataset(&self) -> DatasetId {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let mut map = self.datasets.lock().expect("dataset registry lock poisoned");
+        map.insert(
+            id,
+            DatasetRecord {
+                id,
+                partitions: BTreeMap::new(),
+                by_symbol: HashMap::new(),
+                by_timeframe: HashMap::new(),
+            },
+        );
+        id
+    }
+
+    pub fn drop_dataset(&self, id: DatasetId) -> Result<(), DatasetRegistryError> {
+        let mut map = self.datasets.lock().expect("dataset registry lock poisoned");
+        if map.remove(&id).is_some() {
+            Ok(())
+        } else {
+            Err(DatasetRegistryError::UnknownDatasetId(id))
+        }
+    }
+
+    pub fn dataset_exists(&self, id: DatasetId) -> bool {
+        let map = self.datasets.lock().expect("dataset registry lock poisoned");
+        map.contains_key(&id)
+    }
+
+    pub fn dataset_count(&self) -> usize {
+        let map = self.datasets.lock().expect("dataset registry lock poisoned");
+        map.len()
+    }
+
+    pub fn dataset_info(&self, id: DatasetId) -> Result<DatasetInfo, DatasetRegistryError> {
+        let map = self.datasets.lock().expect("dataset registry lock poisoned");
+        let record = map
+            .get(&id)
+            .ok_or(DatasetRegistryError::UnknownDatasetId(id))?;
+
+        let mut ohlcv_rows = 0_usize;
+        let mut series_rows = 0_usize;
+        let mut series_count = 0_usize;
+        for partition in record.partitions.values() {
+            if let Some(ohlcv) = &partition.ohlcv {
+                ohlcv_rows += ohlcv.timestamps.len();
+            }
+            for series in partition.series.values() {
+                series_rows += series.timestamps.len();
+                series_count += 1;
+            }
+        }
+
+        Ok(DatasetInfo {
+            id,
+            partition_count: record.partitions.len(),
+            ohlcv_row_count: ohlcv_rows,
+            series_row_count: series_rows,
+            series_count,
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn append_ohlcv(
+        &self,
+        id: DatasetId,
+        key: DatasetPartitionKey,
+        timestamps: &[i64],
+        open: &[f64],
+        high: &[f64],
+        low: &[f64],
+        close: &[f64],
+        volume: &[f64],
+    ) -> Result<usize, DatasetRegistryError> {
+        if key.source.trim().is_empty() {
+            return Err(DatasetRegistryError::EmptyField { field: "source" });
+        }
+        if key.symbol.trim().is_empty() {
+            return Err(DatasetRegistryError::EmptyField { field: "symbol" });
+        }
+        if key.timeframe.trim().is_empty() {
+            return Err(DatasetRegistryError::EmptyField { field: "timeframe" });
+        }
+
+        let expected = timestamps.len();
+        ensure_same_len("open", expected, open.len())?;
+        ensure_same_len("high", expected, high.len())?;
+        ensure_same_len("low", expected, low.len())?;
+        ensure_same_len("close", expected, close.len())?;
+        ensure_same_len("volume", expected, volume.len())?;
+        ensure_non_decreasing_timestamps("timestamps", timestamps)?;
+
+        let mut map = self.datasets.lock().expect("dataset registry lock poisoned");
+        let record = map
+            .get_mut(&id)
+            .ok_or(DatasetRegistryError::UnknownDatasetId(id))?;
+        index_partition_key(record, &key);
+        let partition = record
+            .partitions
+            .entry(key)
+            .or_insert_with(DatasetPartition::new);
+        let columns = partition.ohlcv.get_or_insert_with(|| OhlcvColumns {
+            timestamps: Vec::new(),
+            open: Vec::new(),
+            high: Vec::new(),
+            low: Vec::new(),
+            close: Vec::new(),
+            volume: Vec::new(),
+        });
+
+        if let (Some(last), Some(first)) = (columns.timestamps.last(), timestamps.first()) {
+            if first < last {
+                return Err(DatasetRegistryError::NonMonotonicTimestamps {
+                    field: "timestamps",
+                });
+            }
+        }
+
+        columns.timestamps.extend_from_slice(timestamps);
+        columns.open.extend_from_slice(open);
+        columns.high.extend_from_slice(high);
+        columns.low.extend_from_slice(low);
+        columns.close.extend_from_slice(close);
+        columns.volume.extend_from_slice(volume);
+        Ok(columns.timestamps.len())
+    }
+
+    pub fn append_series(
+        &self,
+        id: DatasetId,
+        key: DatasetPartitionKey,
+        field: String,
+        timestamps: &[i64],
+        values: &[f64],
+    ) -> Result<usize, DatasetRegistryError> {
+        if key.source.trim().is_empty() {
+            return Err(DatasetRegistryError::EmptyField { field: "source" });
+        }
+        if key.symbol.trim().is_empty() {
+            return Err(DatasetRegistryError::EmptyField { field: "symbol" });
+        }
+        if key.timeframe.trim().is_empty() {
+            return Err(DatasetRegistryError::EmptyField { field: "timeframe" });
+        }
+        if field.trim().is_empty() {
+            return Err(DatasetRegistryError::EmptyField { field: "field" });
+        }
+
+        let expected = timestamps.len();
+        ensure_same_len("values", expected, values.len())?;
+        ensure_non_decreasing_timestamps("timestamps", timestamps)?;
+
+        let mut map = self.datasets.lock().expect("dataset registry lock poisoned");
+        let record = map
+            .get_mut(&id)
+            .ok_or(DatasetRegistryError::UnknownDatasetId(id))?;
+        index_partition_key(record, &key);
+        let partition = record
+            .partitions
+            .entry(key)
+            .or_insert_with(DatasetPartition::new);
+
+        let series = partition
+            .series
+            .entry(field)
+            .or_insert_with(|| SeriesColumn {
+                timestamps: Vec::new(),
+                values: Vec::new(),
+            });
+
+        if let (Some(last), Some(first)) = (series.timestamps.last(), timestamps.first()) {
+            if first < last {
+                return Err(DatasetRegistryError::NonMonotonicTimestamps {
+                    field: "timestamps",
+                });
+            }
+        }
+
+        series.timestamps.extend_from_slice(timestamps);
+        series.values.extend_from_slice(values);
+        Ok(series.timestamps.len())
+    }
+
+    pub fn get_dataset(&self, id: DatasetId) -> Result<DatasetRecord, DatasetRegistryError> {
+        let map = self.datasets.lock().expect("dataset registry lock poisoned");
+        map.get(&id)
+            .cloned()
+            .ok_or(DatasetRegistryError::UnknownDatasetId(id))
+    }
+
+    /// Returns every partition key stored under `id` whose leading fields
+    /// match `symbol`/`timeframe` (either filter may be omitted, but
+    /// `timeframe` cannot be given without `symbol` -- that wouldn't be a
+    /// contiguous range of the `symbol -> timeframe -> source`-ordered
+    /// keys). Keys are returned in their natural `BTreeMap` order.
+    pub fn list_partitions(
+        &self,
+        id: DatasetId,
+        symbol: Option<&str>,
+        timeframe: Option<&str>,
+    ) -> Result<Vec<DatasetPartitionKey>, DatasetRegistryError> {
+        if symbol.is_none() && timeframe.is_some() {
+            return Err(DatasetRegistryError::InvalidPartitionFilter {
+                message: "timeframe filter requires a symbol filter",
+            });
+        }
+
+        let map = self.datasets.lock().expect("dataset registry lock poisoned");
+        let record = map
+            .get(&id)
+            .ok_or(DatasetRegistryError::UnknownDatasetId(id))?;
+
+        let lower = DatasetPartitionKey {
+            symbol: symbol.unwrap_or("").to_string(),
+            timeframe: timeframe.unwrap_or("").to_string(),
+            source: String::new(),
+        };
+
+        Ok(record
+            .partitions
+            .range(lower..)
+            .take_while(|(key, _)| {
+                symbol.map_or(true, |s| key.symbol == s)
+                    && timeframe.map_or(true, |t| key.timeframe == t)
+            })
+            .map(|(key, _)| key.clone())
+            .collect())
+    }
+
+    pub fn count_partitions(&self, id: DatasetId) -> Result<usize, DatasetRegistryError> {
+        let map = self.datasets.lock().expect("dataset registry lock poisoned");
+        let record = map
+            .get(&id)
+            .ok_or(DatasetRegistryError::UnknownDatasetId(id))?;
+        Ok(record.partitions.len())
+    }
+
+    /// Like [`DatasetRegistry::list_partitions`], but accepts an arbitrary
+    /// combination of `symbol`/`timeframe`/`source` filters instead of only
+    /// a leading prefix of the composite key. A `symbol` or `timeframe`
+    /// filter is answered from the matching secondary index without
+    /// scanning every partition; `source` (which has no secondary index)
+    /// is then applied as a linear filter over that narrowed candidate set.
+    /// All filters omitted returns every partition, same as
+    /// `list_partitions(id, None, None)`.
+    pub fn find_partitions(
+        &self,
+        id: DatasetId,
+        symbol: Option<&str>,
+        timeframe: Option<&str>,
+        source: Option<&str>,
+    ) -> Result<Vec<DatasetPartitionKey>, DatasetRegistryError> {
+        let map = self.datasets.lock().expect("dataset registry lock poisoned");
+        let record = map
+            .get(&id)
+            .ok_or(DatasetRegistryError::UnknownDatasetId(id))?;
+
+        let mut candidates: Vec<DatasetPartitionKey> = match (symbol, timeframe) {
+            (Some(s), Some(t)) => match (record.by_symbol.get(s), record.by_timeframe.get(t)) {
+                (Some(by_s), Some(by_t)) => by_s.intersection(by_t).cloned().collect(),
+                _ => Vec::new(),
+            },
+            (Some(s), None) => record
+                .by_symbol
+                .get(s)
+                .map(|set| set.iter().cloned().collect())
+                .unwrap_or_default(),
+            (None, Some(t)) => record
+                .by_timeframe
+                .get(t)
+                .map(|set| set.iter().cloned().collect())
+                .unwrap_or_default(),
+            (None, None) => record.partitions.keys().cloned().collect(),
+        };
+
+        if let Some(source) = source {
+            candidates.retain(|key| key.source == source);
+        }
+
+        candidates.sort();
+        Ok(candidates)
+    }
+
+    /// Returns the `open/high/low/close/volume` rows of `key`'s OHLCV
+    /// columns whose timestamps fall in `[start_ts, end_ts]`. A missing
+    /// partition, or one with no OHLCV columns at all, yields empty
+    /// columns rather than an error -- only a malformed range is an error.
+    pub fn get_ohlcv_range(
+        &self,
+        id: DatasetId,
+        key: &DatasetPartitionKey,
+        start_ts: i64,
+        end_ts: i64,
+    ) -> Result<OhlcvColumns, DatasetRegistryError> {
+        if start_ts > end_ts {
+            return Err(DatasetRegistryError::InvalidTimeRange {
+                message: "start_ts must be <= end_ts",
+            });
+        }
+
+        let map = self.datasets.lock().expect("dataset registry lock poisoned");
+        let record = map
+            .get(&id)
+            .ok_or(DatasetRegistryError::UnknownDatasetId(id))?;
+
+        let Some(columns) = record.partitions.get(key).and_then(|p| p.ohlcv.as_ref()) else {
+            return Ok(OhlcvColumns {
+                timestamps: Vec::new(),
+                open: Vec::new(),
+                high: Vec::new(),
+                low: Vec::new(),
+                close: Vec::new(),
+                volume: Vec::new(),
+            });
+        };
+
+        let (lo, hi) = time_range_bounds(&columns.timestamps, start_ts, end_ts);
+        Ok(OhlcvColumns {
+            timestamps: columns.timestamps[lo..hi].to_vec(),
+            open: columns.open[lo..hi].to_vec(),
+            high: columns.high[lo..hi].to_vec(),
+            low: columns.low[lo..hi].to_vec(),
+            close: columns.close[lo..hi].to_vec(),
+            volume: columns.volume[lo..hi].to_vec(),
+        })
+    }
+
+    /// Returns the rows of `key`'s `field` series whose timestamps fall in
+    /// `[start_ts, end_ts]`. A missing partition, or one with no such
+    /// series, yields an empty series rather than an error -- only a
+    /// malformed range is an error.
+    pub fn get_series_range(
+        &self,
+        id: DatasetId,
+        key: &DatasetPartitionKey,
+        field: &str,
+        start_ts: i64,
+        end_ts: i64,
+    ) -> Result<SeriesColumn, DatasetRegistryError> {
+        if start_ts > end_ts {
+            return Err(DatasetRegistryError::InvalidTimeRange {
+                message: "start_ts must be <= end_ts",
+            });
+        }
+
+        let map = self.datasets.lock().expect("dataset registry lock poisoned");
+        let record = map
+            .get(&id)
+            .ok_or(DatasetRegistryError::UnknownDatasetId(id))?;
+
+        let Some(series) = record.partitions.get(key).and_then(|p| p.series.get(field)) else {
+            return Ok(SeriesColumn {
+                timestamps: Vec::new(),
+                values: Vec::new(),
+            });
+        };
+
+        let (lo, hi) = time_range_bounds(&series.timestamps, start_ts, end_ts);
+        Ok(SeriesColumn {
+            timestamps: series.timestamps[lo..hi].to_vec(),
+            values: series.values[lo..hi].to_vec(),
+        })
+    }
+
+    /// Merge-joins `fields` (any of `open`/`high`/`low`/`close`/`volume`,
+    /// or a named series) onto the union of their timestamps, via a k-way
+    /// merge over the already-sorted per-field timestamp vectors: at each
+    /// step every field at the smallest remaining timestamp contributes
+    /// its real value and advances its cursor, and every other field fills
+    /// that row per `fill`. A missing partition, or a field with no data
+    /// at all, contributes an all-filled column rather than an error.
+    pub fn get_aligned(
+        &self,
+        id: DatasetId,
+        key: &DatasetPartitionKey,
+        fields: &[&str],
+        fill: FillPolicy,
+    ) -> Result<AlignedColumns, DatasetRegistryError> {
+        let map = self.datasets.lock().expect("dataset registry lock poisoned");
+        let record = map
+            .get(&id)
+            .ok_or(DatasetRegistryError::UnknownDatasetId(id))?;
+        let partition = record.partitions.get(key);
+
+        let series: Vec<(Vec<i64>, Vec<f64>)> = fields
+            .iter()
+            .map(|field| resolve_aligned_field(partition, field))
+            .collect();
+
+        let mut cursors = vec![0_usize; series.len()];
+        let mut last_values = vec![f64::NAN; series.len()];
+        let mut timestamps = Vec::new();
+        let mut columns: Vec<Vec<f64>> = vec![Vec::new(); series.len()];
+
+        loop {
+            let next_ts = series
+                .iter()
+                .enumerate()
+                .filter_map(|(i, (ts, _))| ts.get(cursors[i]).copied())
+                .min();
+            let Some(ts) = next_ts else { break };
+            timestamps.push(ts);
+
+            for (i, (ts_i, values_i)) in series.iter().enumerate() {
+                if cursors[i] < ts_i.len() && ts_i[cursors[i]] == ts {
+                    let value = values_i[cursors[i]];
+                    last_values[i] = value;
+                    columns[i].push(value);
+                    cursors[i] += 1;
+                } else {
+                    columns[i].push(match fill {
+                        FillPolicy::Ffill => last_values[i],
+                        FillPolicy::Zero => 0.0,
+                        FillPolicy::Constant(value) => value,
+                        FillPolicy::Nan | FillPolicy::Drop => f64::NAN,
+                    });
+                }
+            }
+        }
+
+        Ok(AlignedColumns { timestamps, columns })
+    }
+}
+
+impl Default for DatasetRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+static DEFAULT_REGISTRY: OnceLock<DatasetRegistry> = OnceLock::new();
+
+fn default_registry() -> &'static DatasetRegistry {
+    DEFAULT_REGISTRY.get_or_init(DatasetRegistry::new)
+}
+
+pub fn create_dataset() -> DatasetId {
+    default_registry().create_dataset()
+}
+
+pub fn drop_dataset(id: DatasetId) -> Result<(), DatasetRegistryError> {
+    default_registry().drop_dataset(id)
+}
+
+pub fn dataset_exists(id: DatasetId) -> bool {
+    default_registry().dataset_exists(id)
+}
+
+pub fn dataset_count() -> usize {
+    default_registry().dataset_count()
+}
+
+pub fn dataset_info(id: DatasetId) -> Result<DatasetInfo, DatasetRegistryError> {
+    default_registry().dataset_info(id)
+}
+
+pub fn append_ohlcv(
+    id: DatasetId,
+    key: DatasetPartitionKey,
+    timestamps: &[i64],
+    open: &[f64],
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    volume: &[f64],
+) -> Result<usize, DatasetRegistryError> {
+    default_registry().append_ohlcv(id, key, timestamps, open, high, low, close, volume)
+}
+
+pub fn append_series(
+    id: DatasetId,
+    key: DatasetPartitionKey,
+    field: String,
+    timestamps: &[i64],
+    values: &[f64],
+) -> Result<usize, DatasetRegistryError> {
+    default_registry().append_series(id, key, field, timestamps, values)
+}
+
+pub fn get_dataset(id: DatasetId) -> Result<DatasetRecord, DatasetRegistryError> {
+    default_registry().get_dataset(id)
+}
+
+/// Returns every partition key stored under `id` whose leading fields match
+/// `symbol`/`timeframe` (either filter may be omitted, but `timeframe`
+/// cannot be given without `symbol` -- that wouldn't be a contiguous range
+/// of the `symbol -> timeframe -> source`-ordered keys). Keys are returned
+/// in their natural `BTreeMap` order.
+pub fn list_partitions(
+    id: DatasetId,
+    symbol: Option<&str>,
+    timeframe: Option<&str>,
+) -> Result<Vec<DatasetPartitionKey>, DatasetRegistryError> {
+    default_registry().list_partitions(id, symbol, timeframe)
+}
+
+pub fn count_partitions(id: DatasetId) -> Result<usize, DatasetRegistryError> {
+    default_registry().count_partitions(id)
+}
+
+/// Like [`list_partitions`], but accepts an arbitrary combination of
+/// `symbol`/`timeframe`/`source` filters instead of only a leading prefix
+/// of the composite key, backed by `DatasetRegistry`'s secondary indices.
+pub fn find_partitions(
+    id: DatasetId,
+    symbol: Option<&str>,
+    timeframe: Option<&str>,
+    source: Option<&str>,
+) -> Result<Vec<DatasetPartitionKey>, DatasetRegistryError> {
+    default_registry().find_partitions(id, symbol, timeframe, source)
+}
+
+pub fn get_ohlcv_range(
+    id: DatasetId,
+    key: &DatasetPartitionKey,
+    start_ts: i64,
+    end_ts: i64,
+) -> Result<OhlcvColumns, DatasetRegistryError> {
+    default_registry().get_ohlcv_range(id, key, start_ts, end_ts)
+}
+
+pub fn get_series_range(
+    id: DatasetId,
+    key: &DatasetPartitionKey,
+    field: &str,
+    start_ts: i64,
+    end_ts: i64,
+) -> Result<SeriesColumn, DatasetRegistryError> {
+    default_registry().get_series_range(id, key, field, start_ts, end_ts)
+}
+
+pub fn get_aligned(
+    id: DatasetId,
+    key: &DatasetPartitionKey,
+    fields: &[&str],
+    fill: FillPolicy,
+) -> Result<AlignedColumns, DatasetRegistryError> {
+    default_registry().get_aligned(id, key, fields, fill)
+}
+
+/// Resolves `field`'s `(timestamps, values)` for [`DatasetRegistry::get_aligned`]:
+/// one of the fixed OHLCV column names, or a named series. A missing
+/// partition, or a field with no data under it, resolves to an empty pair.
+fn resolve_aligned_field(partition: Option<&DatasetPartition>, field: &str) -> (Vec<i64>, Vec<f64>) {
+    let Some(partition) = partition else {
+        return (Vec::new(), Vec::new());
+    };
+    match field {
+        "open" => partition
+            .ohlcv
+            .as_ref()
+            .map(|o| (o.timestamps.clone(), o.open.clone()))
+            .unwrap_or_default(),
+        "high" => partition
+            .ohlcv
+            .as_ref()
+            .map(|o| (o.timestamps.clone(), o.high.clone()))
+            .unwrap_or_default(),
+        "low" => partition
+            .ohlcv
+            .as_ref()
+            .map(|o| (o.timestamps.clone(), o.low.clone()))
+            .unwrap_or_default(),
+        "close" => partition
+            .ohlcv
+            .as_ref()
+            .map(|o| (o.timestamps.clone(), o.close.clone()))
+            .unwrap_or_default(),
+        "volume" => partition
+            .ohlcv
+            .as_ref()
+            .map(|o| (o.timestamps.clone(), o.volume.clone()))
+            .unwrap_or_default(),
+        other => partition
+            .series
+            .get(other)
+            .map(|s| (s.timestamps.clone(), s.values.clone()))
+            .unwrap_or_default(),
+    }
+}
+
+/// Adds `key` to `record`'s `by_symbol`/`by_timeframe` secondary indices if
+/// it isn't already a known partition. Must run before `key` is moved into
+/// `record.partitions.entry(key)`.
+fn index_partition_key(record: &mut DatasetRecord, key: &DatasetPartitionKey) {
+    if record.partitions.contains_key(key) {
+        return;
+    }
+    record
+        .by_symbol
+        .entry(key.symbol.clone())
+        .or_default()
+        .insert(key.clone());
+    record
+        .by_timeframe
+        .entry(key.timeframe.clone())
+        .or_default()
+        .insert(key.clone());
+}
+
+/// Binary-searches a non-decreasing `timestamps` slice for the `[lo, hi)`
+/// index range covering `[start_ts, end_ts]`: `lo` is the first index with
+/// `ts >= start_ts`, `hi` is the first index with `ts > end_ts`. A window
+/// entirely outside the data collapses to an empty (but in-bounds) range.
+fn time_range_bounds(timestamps: &[i64], start_ts: i64, end_ts: i64) -> (usize, usize) {
+    let lo = timestamps.partition_point(|&ts| ts < start_ts);
+    let hi = timestamps.partition_point(|&ts| ts <= end_ts);
+    (lo, hi)
+}
+
+fn ensure_same_len(
+    field: &'static str,
+    expected: usize,
+    got: usize,
+) -> Result<(), DatasetRegistryError> {
+    if expected == got {
+        Ok(())
+    } else {
+        Err(DatasetRegistryError::LengthMismatch {
+            field,
+            expected,
+            got,
+        })
+    }
+}
+
+fn ensure_non_decreasing_timestamps(
+    field: &'static str,
+    timestamps: &[i64],
+) -> Result<(), DatasetRegistryError> {
+    if timestamps
+        .windows(2)
+        .all(|w| matches!(w, [a, b] if b >= a))
+    {
+        Ok(())
+    } else {
+        Err(DatasetRegistryError::NonMonotonicTimestamps { field })
+    }
+}
@@ -0,0 +1,125 @@
+//! Memory-mapped columnar ingestion for [`super::dataset`].
+//!
+//! `dataset_load_mmap` registers an OHLCV partition straight from an
+//! on-disk fixed-width column file instead of requiring the caller to push
+//! every row through Python first. The file is mapped read-only and each
+//! column is decoded directly from the mapped pages with the same
+//! fixed-width little-endian primitive layout
+//! [`crate::execution::incremental::codec`] uses for kernel state --
+//! `f64`s via `to_bits`/`from_bits`, just column-major instead of one
+//! struct per row -- so a multi-gigabyte history never needs a full copy
+//! into a staging buffer before `mmap` hands back borrowed pages.
+
+use std::fs::File;
+
+use memmap2::Mmap;
+
+use super::dataset::{self, DatasetId, DatasetPartitionKey, DatasetRegistryError};
+
+/// Byte layout of one column within the mapped file: where its first row
+/// starts. Every column is 8 bytes wide per row (see [`MmapOhlcvLayout`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MmapColumnSpec {
+    pub byte_offset: usize,
+}
+
+/// Describes where each OHLCV column lives inside a mapped file. All
+/// columns are `f64` except `timestamp`, which is `i64`; both are 8 bytes
+/// wide, stored contiguously for `row_count` rows starting at their
+/// `byte_offset` (column-major, not interleaved).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MmapOhlcvLayout {
+    pub row_count: usize,
+    pub timestamp: MmapColumnSpec,
+    pub open: MmapColumnSpec,
+    pub high: MmapColumnSpec,
+    pub low: MmapColumnSpec,
+    pub close: MmapColumnSpec,
+    pub volume: MmapColumnSpec,
+}
+
+/// Maps `path` and registers its columns as the OHLCV partition `key` of
+/// dataset `dataset_id`, per `layout`. Returns the partition's new row
+/// count (same contract as `dataset::append_ohlcv`).
+pub fn load_mmap_partition(
+    dataset_id: DatasetId,
+    key: DatasetPartitionKey,
+    path: &str,
+    layout: &MmapOhlcvLayout,
+) -> Result<usize, DatasetRegistryError> {
+    let file = File::open(path).map_err(|err| DatasetRegistryError::MmapLayout {
+        message: format!("could not open '{path}': {err}"),
+    })?;
+    let mmap = unsafe { Mmap::map(&file) }.map_err(|err| DatasetRegistryError::MmapLayout {
+        message: format!("could not mmap '{path}': {err}"),
+    })?;
+
+    let timestamps = read_i64_column(&mmap, layout.timestamp, layout.row_count, "timestamp")?;
+    let open = read_f64_column(&mmap, layout.open, layout.row_count, "open")?;
+    let high = read_f64_column(&mmap, layout.high, layout.row_count, "high")?;
+    let low = read_f64_column(&mmap, layout.low, layout.row_count, "low")?;
+    let close = read_f64_column(&mmap, layout.close, layout.row_count, "close")?;
+    let volume = read_f64_column(&mmap, layout.volume, layout.row_count, "volume")?;
+
+    dataset::append_ohlcv(
+        dataset_id,
+        key,
+        &timestamps,
+        &open,
+        &high,
+        &low,
+        &close,
+        &volume,
+    )
+}
+
+fn read_f64_column(
+    mmap: &Mmap,
+    spec: MmapColumnSpec,
+    row_count: usize,
+    field: &'static str,
+) -> Result<Vec<f64>, DatasetRegistryError> {
+    read_column(mmap, spec, row_count, field, |bytes| {
+        f64::from_le_bytes(bytes)
+    })
+}
+
+fn read_i64_column(
+    mmap: &Mmap,
+    spec: MmapColumnSpec,
+    row_count: usize,
+    field: &'static str,
+) -> Result<Vec<i64>, DatasetRegistryError> {
+    read_column(mmap, spec, row_count, field, |bytes| {
+        i64::from_le_bytes(bytes)
+    })
+}
+
+fn read_column<T>(
+    mmap: &Mmap,
+    spec: MmapColumnSpec,
+    row_count: usize,
+    field: &'static str,
+    decode: impl Fn([u8; 8]) -> T,
+) -> Result<Vec<T>, DatasetRegistryError> {
+    let byte_len = row_count * 8;
+    let end = spec.byte_offset.checked_add(byte_len).ok_or_else(|| {
+        DatasetRegistryError::MmapLayout {
+            message: format!("column '{field}' byte range overflows"),
+        }
+    })?;
+    let slice = mmap
+        .get(spec.byte_offset..end)
+        .ok_or_else(|| DatasetRegistryError::MmapLayout {
+            message: format!(
+                "column '{field}' range {}..{end} is out of bounds for a {}-byte file",
+                spec.byte_offset,
+                mmap.len()
+            ),
+        })?;
+
+    Ok(slice
+        .chunks_exact(8)
+        .map(|chunk| decode(chunk.try_into().expect("chunks_exact(8) yields 8-byte slices")))
+        .collect())
+}
@@ -0,0 +1,630 @@
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+use thiserror::Error;
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum DatasetOpsError {
+    #[error("timestamps and values must have identical lengths")]
+    LengthMismatch,
+    #[error("factor must be positive")]
+    InvalidFactor,
+    #[error("unsupported aggregation: {0}")]
+    UnsupportedAggregation(String),
+    #[error("unsupported sync fill mode: {0}")]
+    UnsupportedFillMode(String),
+    #[error("interval_ms must be positive")]
+    InvalidInterval,
+    #[error("unsupported empty-window policy: {0}")]
+    UnsupportedEmptyPolicy(String),
+}
+
+/// Per-bucket accumulator state threaded through a [`BucketAggregator`]:
+/// `init` produces one of these per bucket, every value in the bucket is
+/// folded in with `accumulate`, and `finalize` reduces it to the bucket's
+/// output. Carries every field a built-in aggregator might need so the trait
+/// stays dyn-safe without an associated type; an aggregator that only needs
+/// `sum`/`count` simply leaves the rest at their default.
+#[derive(Debug, Clone, Default)]
+pub struct BucketState {
+    pub count: u64,
+    pub nonzero_count: u64,
+    pub sum: f64,
+    pub sumsq: f64,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub first: Option<f64>,
+    pub last: Option<f64>,
+    pub values: Vec<f64>,
+    pub weighted_sum: f64,
+    pub weight_total: f64,
+}
+
+/// A named bucket-reduction strategy for [`downsample`], resolved by name at
+/// call time through [`register_aggregator`] rather than a closed `match`.
+/// `weight` is carried through for aggregators that want it (e.g. a
+/// time-weighted mean); of the built-ins below only `vwap` uses it.
+pub trait BucketAggregator: Send + Sync {
+    fn init(&self) -> BucketState {
+        BucketState::default()
+    }
+    fn accumulate(&self, state: &mut BucketState, value: f64, weight: f64);
+    fn finalize(&self, state: &BucketState) -> f64;
+}
+
+struct First;
+impl BucketAggregator for First {
+    fn accumulate(&self, state: &mut BucketState, value: f64, _weight: f64) {
+        state.first.get_or_insert(value);
+    }
+    fn finalize(&self, state: &BucketState) -> f64 {
+        state.first.unwrap_or(f64::NAN)
+    }
+}
+
+struct Last;
+impl BucketAggregator for Last {
+    fn accumulate(&self, state: &mut BucketState, value: f64, _weight: f64) {
+        state.last = Some(value);
+    }
+    fn finalize(&self, state: &BucketState) -> f64 {
+        state.last.unwrap_or(f64::NAN)
+    }
+}
+
+struct Mean;
+impl BucketAggregator for Mean {
+    fn accumulate(&self, state: &mut BucketState, value: f64, _weight: f64) {
+        state.count += 1;
+        state.sum += value;
+    }
+    fn finalize(&self, state: &BucketState) -> f64 {
+        if state.count == 0 {
+            f64::NAN
+        } else {
+            state.sum / state.count as f64
+        }
+    }
+}
+
+struct Sum;
+impl BucketAggregator for Sum {
+    fn accumulate(&self, state: &mut BucketState, value: f64, _weight: f64) {
+        state.sum += value;
+    }
+    fn finalize(&self, state: &BucketState) -> f64 {
+        state.sum
+    }
+}
+
+struct Max;
+impl BucketAggregator for Max {
+    fn accumulate(&self, state: &mut BucketState, value: f64, _weight: f64) {
+        state.max = Some(state.max.map_or(value, |m| m.max(value)));
+    }
+    fn finalize(&self, state: &BucketState) -> f64 {
+        state.max.unwrap_or(f64::NAN)
+    }
+}
+
+struct Min;
+impl BucketAggregator for Min {
+    fn accumulate(&self, state: &mut BucketState, value: f64, _weight: f64) {
+        state.min = Some(state.min.map_or(value, |m| m.min(value)));
+    }
+    fn finalize(&self, state: &BucketState) -> f64 {
+        state.min.unwrap_or(f64::NAN)
+    }
+}
+
+struct Median;
+impl BucketAggregator for Median {
+    fn accumulate(&self, state: &mut BucketState, value: f64, _weight: f64) {
+        state.values.push(value);
+    }
+    fn finalize(&self, state: &BucketState) -> f64 {
+        let mut sorted: Vec<f64> = state.values.iter().copied().filter(|v| !v.is_nan()).collect();
+        if sorted.is_empty() {
+            return f64::NAN;
+        }
+        sorted.sort_by(|a, b| a.partial_cmp(b).expect("NaN filtered out above"));
+        let mid = sorted.len() / 2;
+        if sorted.len() % 2 == 0 {
+            (sorted[mid - 1] + sorted[mid]) / 2.0
+        } else {
+            sorted[mid]
+        }
+    }
+}
+
+struct Std;
+impl BucketAggregator for Std {
+    fn accumulate(&self, state: &mut BucketState, value: f64, _weight: f64) {
+        state.count += 1;
+        state.sum += value;
+        state.sumsq += value * value;
+    }
+    fn finalize(&self, state: &BucketState) -> f64 {
+        if state.count == 0 {
+            return f64::NAN;
+        }
+        let n = state.count as f64;
+        let mean = state.sum / n;
+        let variance = (state.sumsq / n - mean * mean).max(0.0);
+        variance.sqrt()
+    }
+}
+
+struct Range;
+impl BucketAggregator for Range {
+    fn accumulate(&self, state: &mut BucketState, value: f64, _weight: f64) {
+        state.max = Some(state.max.map_or(value, |m| m.max(value)));
+        state.min = Some(state.min.map_or(value, |m| m.min(value)));
+    }
+    fn finalize(&self, state: &BucketState) -> f64 {
+        match (state.max, state.min) {
+            (Some(max), Some(min)) => max - min,
+            _ => f64::NAN,
+        }
+    }
+}
+
+struct CountNonzero;
+impl BucketAggregator for CountNonzero {
+    fn accumulate(&self, state: &mut BucketState, value: f64, _weight: f64) {
+        state.count += 1;
+        if value != 0.0 {
+            state.nonzero_count += 1;
+        }
+    }
+    fn finalize(&self, state: &BucketState) -> f64 {
+        state.nonzero_count as f64
+    }
+}
+
+/// Volume-weighted average: `sum(value * weight) / sum(weight)`, falling
+/// back to the plain (unweighted) mean when the bucket's total weight is
+/// zero. `downsample` always calls aggregators with `weight = 1.0`, so this
+/// only differs from `"mean"` when driven directly with real weights, as
+/// [`resample_ohlcv`] does with `(close, volume)`.
+struct Vwap;
+impl BucketAggregator for Vwap {
+    fn accumulate(&self, state: &mut BucketState, value: f64, weight: f64) {
+        state.count += 1;
+        state.sum += value;
+        state.weighted_sum += value * weight;
+        state.weight_total += weight;
+    }
+    fn finalize(&self, state: &BucketState) -> f64 {
+        if state.weight_total != 0.0 {
+            state.weighted_sum / state.weight_total
+        } else if state.count > 0 {
+            state.sum / state.count as f64
+        } else {
+            f64::NAN
+        }
+    }
+}
+
+type AggregatorRegistry = HashMap<String, Box<dyn BucketAggregator>>;
+
+static AGGREGATORS: OnceLock<RwLock<AggregatorRegistry>> = OnceLock::new();
+
+fn aggregators() -> &'static RwLock<AggregatorRegistry> {
+    AGGREGATORS.get_or_init(|| {
+        let mut map: AggregatorRegistry = HashMap::new();
+        map.insert("first".to_string(), Box::new(First));
+        map.insert("last".to_string(), Box::new(Last));
+        map.insert("mean".to_string(), Box::new(Mean));
+        map.insert("sum".to_string(), Box::new(Sum));
+        map.insert("max".to_string(), Box::new(Max));
+        map.insert("min".to_string(), Box::new(Min));
+        map.insert("median".to_string(), Box::new(Median));
+        map.insert("std".to_string(), Box::new(Std));
+        map.insert("range".to_string(), Box::new(Range));
+        map.insert("count_nonzero".to_string(), Box::new(CountNonzero));
+        map.insert("vwap".to_string(), Box::new(Vwap));
+        RwLock::new(map)
+    })
+}
+
+/// Registers `aggregator` under `name`, for `downsample` to resolve by
+/// string. Registering a name that is already registered (built-in or not)
+/// replaces it, the same "overlay over defaults" shape the indicator overlay
+/// registry uses.
+pub fn register_aggregator(name: impl Into<String>, aggregator: Box<dyn BucketAggregator>) {
+    aggregators()
+        .write()
+        .expect("aggregator registry lock poisoned")
+        .insert(name.into(), aggregator);
+}
+
+/// Removes `name` from the aggregator registry, including a built-in. A
+/// no-op when `name` was never registered.
+pub fn deregister_aggregator(name: &str) {
+    aggregators()
+        .write()
+        .expect("aggregator registry lock poisoned")
+        .remove(name);
+}
+
+pub fn downsample(
+    timestamps: &[i64],
+    values: &[f64],
+    factor: usize,
+    agg: &str,
+) -> Result<(Vec<i64>, Vec<f64>), DatasetOpsError> {
+    if timestamps.len() != values.len() {
+        return Err(DatasetOpsError::LengthMismatch);
+    }
+    if factor == 0 {
+        return Err(DatasetOpsError::InvalidFactor);
+    }
+    if factor <= 1 || timestamps.is_empty() {
+        return Ok((timestamps.to_vec(), values.to_vec()));
+    }
+
+    let registry = aggregators()
+        .read()
+        .expect("aggregator registry lock poisoned");
+    let aggregator = registry
+        .get(agg)
+        .ok_or_else(|| DatasetOpsError::UnsupportedAggregation(agg.to_string()))?;
+
+    let mut out_ts = Vec::with_capacity(timestamps.len().div_ceil(factor));
+    let mut out_values = Vec::with_capacity(values.len().div_ceil(factor));
+
+    let mut i = 0_usize;
+    while i < timestamps.len() {
+        let end = (i + factor).min(timestamps.len());
+        out_ts.push(timestamps[end - 1]);
+
+        let mut state = aggregator.init();
+        for &value in &values[i..end] {
+            aggregator.accumulate(&mut state, value, 1.0);
+        }
+        out_values.push(aggregator.finalize(&state));
+
+        i = end;
+    }
+
+    Ok((out_ts, out_values))
+}
+
+/// Groups samples into fixed-width calendar windows anchored to the epoch
+/// (`bucket = ts - ts.rem_euclid(interval_ms)`), the right shape for
+/// irregular or gappy series where `downsample`'s fixed sample-count buckets
+/// would drift away from real bar boundaries. The output timestamp is each
+/// window's lower boundary, not its last sample's timestamp. Windows between
+/// the first and last non-empty window that have no samples are handled per
+/// `empty_policy`: `"skip"` omits them from the output, and `"zero"`,
+/// `"ffill"`, `"linear"` fill the gap the same way [`sync_timeframe`] does
+/// for its matching fill modes.
+pub fn downsample_interval(
+    timestamps: &[i64],
+    values: &[f64],
+    interval_ms: i64,
+    agg: &str,
+    empty_policy: &str,
+) -> Result<(Vec<i64>, Vec<f64>), DatasetOpsError> {
+    if timestamps.len() != values.len() {
+        return Err(DatasetOpsError::LengthMismatch);
+    }
+    if interval_ms <= 0 {
+        return Err(DatasetOpsError::InvalidInterval);
+    }
+    if timestamps.is_empty() {
+        return Ok((Vec::new(), Vec::new()));
+    }
+
+    let registry = aggregators()
+        .read()
+        .expect("aggregator registry lock poisoned");
+    let aggregator = registry
+        .get(agg)
+        .ok_or_else(|| DatasetOpsError::UnsupportedAggregation(agg.to_string()))?;
+
+    let mut sparse_ts: Vec<i64> = Vec::new();
+    let mut sparse_states: Vec<BucketState> = Vec::new();
+    for (&ts, &value) in timestamps.iter().zip(values.iter()) {
+        let bucket = ts - ts.rem_euclid(interval_ms);
+        match sparse_ts.last() {
+            Some(&last_bucket) if last_bucket == bucket => {
+                aggregator.accumulate(sparse_states.last_mut().unwrap(), value, 1.0);
+            }
+            _ => {
+                let mut state = aggregator.init();
+                aggregator.accumulate(&mut state, value, 1.0);
+                sparse_ts.push(bucket);
+                sparse_states.push(state);
+            }
+        }
+    }
+    let sparse_values: Vec<f64> = sparse_states.iter().map(|s| aggregator.finalize(s)).collect();
+
+    fill_empty_windows(&sparse_ts, &sparse_values, interval_ms, empty_policy)
+}
+
+fn fill_empty_windows(
+    sparse_ts: &[i64],
+    sparse_values: &[f64],
+    interval_ms: i64,
+    empty_policy: &str,
+) -> Result<(Vec<i64>, Vec<f64>), DatasetOpsError> {
+    if empty_policy == "skip" {
+        return Ok((sparse_ts.to_vec(), sparse_values.to_vec()));
+    }
+    if !matches!(empty_policy, "zero" | "ffill" | "linear") {
+        return Err(DatasetOpsError::UnsupportedEmptyPolicy(
+            empty_policy.to_string(),
+        ));
+    }
+
+    let first = *sparse_ts.first().unwrap();
+    let last = *sparse_ts.last().unwrap();
+    let mut full_ts = Vec::new();
+    let mut ts = first;
+    while ts <= last {
+        full_ts.push(ts);
+        ts += interval_ms;
+    }
+
+    let out_values = match empty_policy {
+        "zero" => sync_zero(sparse_ts, sparse_values, &full_ts),
+        "ffill" => sync_ffill(sparse_ts, sparse_values, &full_ts),
+        "linear" => sync_linear(sparse_ts, sparse_values, &full_ts),
+        _ => unreachable!(),
+    };
+
+    Ok((full_ts, out_values))
+}
+
+/// Aggregates OHLCV bars into coarser candles using proper candle semantics
+/// (`open` = first, `high` = max, `low` = min, `close` = last, `volume` =
+/// sum), emitting bucket timestamps the same way `downsample` does. Unlike
+/// `downsample`, empty input is rejected rather than passed through: there is
+/// no complete bar to aggregate into, so it is reported the same as any other
+/// column-length mismatch.
+#[allow(clippy::too_many_arguments)]
+pub fn downsample_ohlcv(
+    timestamps: &[i64],
+    open: &[f64],
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    volume: &[f64],
+    factor: usize,
+) -> Result<(Vec<i64>, Vec<f64>, Vec<f64>, Vec<f64>, Vec<f64>, Vec<f64>), DatasetOpsError> {
+    let expected = timestamps.len();
+    if open.len() != expected
+        || high.len() != expected
+        || low.len() != expected
+        || close.len() != expected
+        || volume.len() != expected
+    {
+        return Err(DatasetOpsError::LengthMismatch);
+    }
+    if factor == 0 {
+        return Err(DatasetOpsError::InvalidFactor);
+    }
+    if timestamps.is_empty() {
+        return Err(DatasetOpsError::LengthMismatch);
+    }
+    if factor == 1 {
+        return Ok((
+            timestamps.to_vec(),
+            open.to_vec(),
+            high.to_vec(),
+            low.to_vec(),
+            close.to_vec(),
+            volume.to_vec(),
+        ));
+    }
+
+    let n_buckets = timestamps.len().div_ceil(factor);
+    let mut out_ts = Vec::with_capacity(n_buckets);
+    let mut out_open = Vec::with_capacity(n_buckets);
+    let mut out_high = Vec::with_capacity(n_buckets);
+    let mut out_low = Vec::with_capacity(n_buckets);
+    let mut out_close = Vec::with_capacity(n_buckets);
+    let mut out_volume = Vec::with_capacity(n_buckets);
+
+    let mut i = 0_usize;
+    while i < timestamps.len() {
+        let end = (i + factor).min(timestamps.len());
+        out_ts.push(timestamps[end - 1]);
+        out_open.push(open[i]);
+        out_high.push(high[i..end].iter().copied().fold(f64::NEG_INFINITY, f64::max));
+        out_low.push(low[i..end].iter().copied().fold(f64::INFINITY, f64::min));
+        out_close.push(close[end - 1]);
+        out_volume.push(volume[i..end].iter().sum());
+        i = end;
+    }
+
+    Ok((out_ts, out_open, out_high, out_low, out_close, out_volume))
+}
+
+/// Resamples a bar series to a coarser timeframe in one pass: the canonical
+/// OHLCV rule set from [`downsample_ohlcv`] (open=first, high=max, low=min,
+/// close=last, volume=sum) plus a `vwap` column (`sum(close*volume) /
+/// sum(volume)` per bucket, via the same [`Vwap`] accumulator `"vwap"`
+/// resolves to in the aggregation registry), all keyed off the same bucket
+/// boundaries so the seven output columns can't drift out of alignment the
+/// way six separate `downsample`/`series_downsample` calls could.
+#[allow(clippy::too_many_arguments)]
+pub fn resample_ohlcv(
+    timestamps: &[i64],
+    open: &[f64],
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    volume: &[f64],
+    factor: usize,
+) -> Result<
+    (
+        Vec<i64>,
+        Vec<f64>,
+        Vec<f64>,
+        Vec<f64>,
+        Vec<f64>,
+        Vec<f64>,
+        Vec<f64>,
+    ),
+    DatasetOpsError,
+> {
+    let (out_ts, out_open, out_high, out_low, out_close, out_volume) =
+        downsample_ohlcv(timestamps, open, high, low, close, volume, factor)?;
+
+    let vwap = Vwap;
+    let n = timestamps.len();
+    let bucket_factor = factor.max(1);
+    let mut out_vwap = Vec::with_capacity(out_ts.len());
+    let mut i = 0_usize;
+    while i < n {
+        let end = (i + bucket_factor).min(n);
+        let mut state = vwap.init();
+        for idx in i..end {
+            vwap.accumulate(&mut state, close[idx], volume[idx]);
+        }
+        out_vwap.push(vwap.finalize(&state));
+        i = end;
+    }
+
+    Ok((
+        out_ts, out_open, out_high, out_low, out_close, out_volume, out_vwap,
+    ))
+}
+
+pub fn upsample_ffill(
+    timestamps: &[i64],
+    values: &[f64],
+    factor: usize,
+) -> Result<(Vec<i64>, Vec<f64>), DatasetOpsError> {
+    if timestamps.len() != values.len() {
+        return Err(DatasetOpsError::LengthMismatch);
+    }
+    if factor == 0 {
+        return Err(DatasetOpsError::InvalidFactor);
+    }
+    if factor <= 1 || timestamps.is_empty() {
+        return Ok((timestamps.to_vec(), values.to_vec()));
+    }
+
+    let mut out_ts = Vec::with_capacity((timestamps.len() - 1) * factor + 1);
+    let mut out_values = Vec::with_capacity((values.len() - 1) * factor + 1);
+
+    for idx in 0..timestamps.len() {
+        out_ts.push(timestamps[idx]);
+        out_values.push(values[idx]);
+        if idx < timestamps.len() - 1 {
+            for _ in 0..(factor - 1) {
+                out_ts.push(timestamps[idx]);
+                out_values.push(values[idx]);
+            }
+        }
+    }
+    Ok((out_ts, out_values))
+}
+
+pub fn sync_timeframe(
+    source_timestamps: &[i64],
+    source_values: &[f64],
+    reference_timestamps: &[i64],
+    fill: &str,
+) -> Result<Vec<f64>, DatasetOpsError> {
+    if source_timestamps.len() != source_values.len() {
+        return Err(DatasetOpsError::LengthMismatch);
+    }
+    if reference_timestamps.is_empty() {
+        return Ok(Vec::new());
+    }
+    if source_timestamps.is_empty() {
+        return Ok(vec![0.0; reference_timestamps.len()]);
+    }
+
+    match fill {
+        "ffill" => Ok(sync_ffill(
+            source_timestamps,
+            source_values,
+            reference_timestamps,
+        )),
+        "linear" => Ok(sync_linear(
+            source_timestamps,
+            source_values,
+            reference_timestamps,
+        )),
+        "zero" => Ok(sync_zero(
+            source_timestamps,
+            source_values,
+            reference_timestamps,
+        )),
+        other => Err(DatasetOpsError::UnsupportedFillMode(other.to_string())),
+    }
+}
+
+fn sync_zero(
+    source_timestamps: &[i64],
+    source_values: &[f64],
+    reference_timestamps: &[i64],
+) -> Vec<f64> {
+    reference_timestamps
+        .iter()
+        .map(|ts| match source_timestamps.binary_search(ts) {
+            Ok(i) => source_values[i],
+            Err(_) => 0.0,
+        })
+        .collect()
+}
+
+fn sync_ffill(
+    source_timestamps: &[i64],
+    source_values: &[f64],
+    reference_timestamps: &[i64],
+) -> Vec<f64> {
+    let mut out = Vec::with_capacity(reference_timestamps.len());
+    let mut pos = 0_usize;
+    let mut last = source_values[0];
+    for &ts in reference_timestamps {
+        while pos < source_timestamps.len() && source_timestamps[pos] <= ts {
+            last = source_values[pos];
+            pos += 1;
+        }
+        out.push(last);
+    }
+    out
+}
+
+fn sync_linear(
+    source_timestamps: &[i64],
+    source_values: &[f64],
+    reference_timestamps: &[i64],
+) -> Vec<f64> {
+    let mut out = Vec::with_capacity(reference_timestamps.len());
+    for &ts in reference_timestamps {
+        match source_timestamps.binary_search(&ts) {
+            Ok(i) => out.push(source_values[i]),
+            Err(i) => {
+                if i == 0 {
+                    out.push(source_values[0]);
+                    continue;
+                }
+                if i >= source_timestamps.len() {
+                    out.push(source_values[source_values.len() - 1]);
+                    continue;
+                }
+                let t0 = source_timestamps[i - 1];
+                let t1 = source_timestamps[i];
+                let v0 = source_values[i - 1];
+                let v1 = source_values[i];
+                let denom = (t1 - t0) as f64;
+                if denom == 0.0 {
+                    out.push(v0);
+                    continue;
+                }
+                let w = (ts - t0) as f64 / denom;
+                out.push(v0 + (v1 - v0) * w);
+            }
+        }
+    }
+    out
+}
@@ -126,3 +126,110 @@ pub fn exit_channel(price: &[f64], upper: &[f64], lower: &[f64]) -> Vec<bool> {
     }
     out
 }
+
+/// A local high: `values[i]` strictly exceeds every neighbor within
+/// `lookback` bars on both sides ([`pivot_lows`] is the mirror: strictly
+/// below every neighbor). Returns the confirmed pivot bar indices in
+/// ascending order.
+fn pivot_highs(values: &[f64], lookback: usize) -> Vec<usize> {
+    let n = values.len();
+    if lookback == 0 || n <= 2 * lookback {
+        return Vec::new();
+    }
+    (lookback..n - lookback)
+        .filter(|&i| {
+            let v = values[i];
+            (i - lookback..i).all(|j| values[j] < v) && (i + 1..=i + lookback).all(|j| values[j] < v)
+        })
+        .collect()
+}
+
+fn pivot_lows(values: &[f64], lookback: usize) -> Vec<usize> {
+    let n = values.len();
+    if lookback == 0 || n <= 2 * lookback {
+        return Vec::new();
+    }
+    (lookback..n - lookback)
+        .filter(|&i| {
+            let v = values[i];
+            (i - lookback..i).all(|j| values[j] > v) && (i + 1..=i + lookback).all(|j| values[j] > v)
+        })
+        .collect()
+}
+
+/// The pivot in `pivots` closest to `target`, if one lies within
+/// `tolerance` bars -- the "matching oscillator pivot" for a given price
+/// pivot, since the two series' local extremes don't land on exactly the
+/// same bar.
+fn nearest_pivot(pivots: &[usize], target: usize, tolerance: usize) -> Option<usize> {
+    pivots
+        .iter()
+        .copied()
+        .min_by_key(|&p| p.abs_diff(target))
+        .filter(|&p| p.abs_diff(target) <= tolerance)
+}
+
+/// Regular/hidden bullish/bearish divergence between `price` and
+/// `oscillator`, as four boolean signal series aligned to `price`'s index:
+/// `(bearish, bullish, hidden_bearish, hidden_bullish)`. A signal fires at
+/// the bar of the later pivot in each confirmed pair.
+///
+/// Pivots are detected independently in both series with [`pivot_highs`]/
+/// [`pivot_lows`] over `lookback` bars, then consecutive price pivots are
+/// compared against the oscillator pivot nearest each one (within
+/// `lookback` bars -- beyond that the series aren't considered to be
+/// pivoting together). Regular bearish is a price higher-high paired with
+/// an oscillator lower-high; regular bullish is a price lower-low paired
+/// with an oscillator higher-low; the hidden variants invert the price
+/// comparison.
+pub fn divergence(
+    price: &[f64],
+    oscillator: &[f64],
+    lookback: usize,
+) -> (Vec<bool>, Vec<bool>, Vec<bool>, Vec<bool>) {
+    let n = price.len().min(oscillator.len());
+    let mut bearish = vec![false; n];
+    let mut bullish = vec![false; n];
+    let mut hidden_bearish = vec![false; n];
+    let mut hidden_bullish = vec![false; n];
+    if n == 0 {
+        return (bearish, bullish, hidden_bearish, hidden_bullish);
+    }
+
+    let price_highs = pivot_highs(&price[..n], lookback);
+    let price_lows = pivot_lows(&price[..n], lookback);
+    let osc_highs = pivot_highs(&oscillator[..n], lookback);
+    let osc_lows = pivot_lows(&oscillator[..n], lookback);
+
+    for pair in price_highs.windows(2) {
+        let (prev, curr) = (pair[0], pair[1]);
+        let (Some(osc_prev), Some(osc_curr)) = (
+            nearest_pivot(&osc_highs, prev, lookback),
+            nearest_pivot(&osc_highs, curr, lookback),
+        ) else {
+            continue;
+        };
+        if price[curr] > price[prev] && oscillator[osc_curr] < oscillator[osc_prev] {
+            bearish[curr] = true;
+        } else if price[curr] < price[prev] && oscillator[osc_curr] > oscillator[osc_prev] {
+            hidden_bearish[curr] = true;
+        }
+    }
+
+    for pair in price_lows.windows(2) {
+        let (prev, curr) = (pair[0], pair[1]);
+        let (Some(osc_prev), Some(osc_curr)) = (
+            nearest_pivot(&osc_lows, prev, lookback),
+            nearest_pivot(&osc_lows, curr, lookback),
+        ) else {
+            continue;
+        };
+        if price[curr] < price[prev] && oscillator[osc_curr] > oscillator[osc_prev] {
+            bullish[curr] = true;
+        } else if price[curr] > price[prev] && oscillator[osc_curr] < oscillator[osc_prev] {
+            hidden_bullish[curr] = true;
+        }
+    }
+
+    (bearish, bullish, hidden_bearish, hidden_bullish)
+}
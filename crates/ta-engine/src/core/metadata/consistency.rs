@@ -0,0 +1,215 @@
+//! Cross-checks every [`IndicatorMeta`]'s [`IndicatorVisualMeta`] against
+//! its own outputs and style slots, so a typo'd `style_slot` or a
+//! mismatched z-index ships as a [`Diagnostic`] instead of a silently
+//! broken chart. Modeled on codespan-reporting: a [`Severity`], a short
+//! message, and one or more [`Label`]s naming the indicator, output, and
+//! slot the finding is about.
+
+use std::collections::BTreeMap;
+
+use super::{IndicatorMeta, OutputVisualPrimitive, StyleSlotType};
+
+/// How serious a [`Diagnostic`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// Points a [`Diagnostic`] at the indicator, and optionally the output
+/// and/or style slot, it was raised against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Label {
+    pub indicator: &'static str,
+    pub output: Option<&'static str>,
+    pub slot: Option<&'static str>,
+    pub message: String,
+}
+
+impl Label {
+    fn new(indicator: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            indicator,
+            output: None,
+            slot: None,
+            message: message.into(),
+        }
+    }
+
+    fn with_output(mut self, output: &'static str) -> Self {
+        self.output = Some(output);
+        self
+    }
+
+    fn with_slot(mut self, slot: &'static str) -> Self {
+        self.slot = Some(slot);
+        self
+    }
+}
+
+impl std::fmt::Display for Label {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "  --> {}", self.indicator)?;
+        if let Some(output) = self.output {
+            write!(f, ": output \"{output}\"")?;
+        }
+        if let Some(slot) = self.slot {
+            write!(f, " -> slot \"{slot}\"")?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// A single catalog-consistency finding: a severity, a message, and the
+/// labeled spans that pin down where it occurred.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    pub labels: Vec<Label>,
+}
+
+impl Diagnostic {
+    fn error(message: impl Into<String>, labels: Vec<Label>) -> Self {
+        Self {
+            severity: Severity::Error,
+            message: message.into(),
+            labels,
+        }
+    }
+
+    fn warning(message: impl Into<String>, labels: Vec<Label>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            message: message.into(),
+            labels,
+        }
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        writeln!(f, "{severity}: {}", self.message)?;
+        for (i, label) in self.labels.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{label}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Renders a batch of [`Diagnostic`]s as a human-readable report, one
+/// blank-line-separated entry per diagnostic.
+pub fn render_diagnostics(diagnostics: &[Diagnostic]) -> String {
+    diagnostics
+        .iter()
+        .map(Diagnostic::to_string)
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Walks [`super::indicator_catalog`] and checks every indicator's visual
+/// metadata is internally coherent: `style_slot`s resolve, compound
+/// (`"a|b"`) output names exist, the style slot kind fits the primitive
+/// drawn through it, and no two differently-styled outputs silently
+/// collide on `z_index`.
+pub fn validate_catalog() -> Vec<Diagnostic> {
+    validate_indicators(super::indicator_catalog())
+}
+
+/// Runs the same checks [`validate_catalog`] runs over the real catalog,
+/// but against an arbitrary slice of indicators.
+pub fn validate_indicators(indicators: &[IndicatorMeta]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    for meta in indicators {
+        validate_one(meta, &mut diagnostics);
+    }
+    diagnostics
+}
+
+fn validate_one(meta: &IndicatorMeta, diagnostics: &mut Vec<Diagnostic>) {
+    let visual = &meta.visual;
+
+    for output_visual in visual.output_visuals {
+        let slot = visual
+            .style_slots
+            .iter()
+            .find(|slot| slot.slot == output_visual.style_slot);
+
+        match slot {
+            None => diagnostics.push(Diagnostic::error(
+                "output_visual references a style_slot not declared on this indicator",
+                vec![Label::new(meta.id, "undeclared style_slot")
+                    .with_output(output_visual.output)
+                    .with_slot(output_visual.style_slot)],
+            )),
+            Some(slot) => {
+                let allowed = expected_slot_kinds(output_visual.primitive);
+                if !allowed.contains(&slot.kind) {
+                    diagnostics.push(Diagnostic::warning(
+                        format!(
+                            "{:?} output drawn through a {:?} style_slot",
+                            output_visual.primitive, slot.kind
+                        ),
+                        vec![Label::new(meta.id, "primitive/slot kind mismatch")
+                            .with_output(output_visual.output)
+                            .with_slot(slot.slot)],
+                    ));
+                }
+            }
+        }
+
+        for part in output_visual.output.split('|') {
+            if !meta.outputs.iter().any(|output| output.name == part) {
+                diagnostics.push(Diagnostic::error(
+                    "output_visual references an output name not in this indicator's output list",
+                    vec![Label::new(meta.id, format!("unknown output \"{part}\""))
+                        .with_output(output_visual.output)],
+                ));
+            }
+        }
+    }
+
+    let mut by_z_index: BTreeMap<i32, Vec<&super::OutputVisualMeta>> = BTreeMap::new();
+    for output_visual in visual.output_visuals {
+        by_z_index
+            .entry(output_visual.z_index)
+            .or_default()
+            .push(output_visual);
+    }
+    for (z_index, group) in by_z_index {
+        let mut slots: Vec<&str> = group.iter().map(|ov| ov.style_slot).collect();
+        slots.sort_unstable();
+        slots.dedup();
+        if slots.len() > 1 {
+            let labels = group
+                .iter()
+                .map(|ov| {
+                    Label::new(meta.id, format!("shares z_index {z_index}"))
+                        .with_output(ov.output)
+                        .with_slot(ov.style_slot)
+                })
+                .collect();
+            diagnostics.push(Diagnostic::warning(
+                format!("{} outputs share z_index {z_index} across different style slots", group.len()),
+                labels,
+            ));
+        }
+    }
+}
+
+fn expected_slot_kinds(primitive: OutputVisualPrimitive) -> &'static [StyleSlotType] {
+    match primitive {
+        OutputVisualPrimitive::Line => &[StyleSlotType::Stroke],
+        OutputVisualPrimitive::Histogram => &[StyleSlotType::Stroke, StyleSlotType::Fill],
+        OutputVisualPrimitive::BandFill => &[StyleSlotType::Fill],
+        OutputVisualPrimitive::Markers => &[StyleSlotType::Stroke],
+        OutputVisualPrimitive::SignalFlag => &[StyleSlotType::Stroke],
+    }
+}
@@ -15,4 +15,5 @@ pub const META: IndicatorMeta = IndicatorMeta {
     semantics: SEM_CLOSE_PAIR,
     visual: VIS_SIGNAL_FLAG,
     runtime_binding: "cross",
+    constraints: &[],
 };
@@ -0,0 +1,54 @@
+use super::*;
+
+const P_DIVERGENCE_SOURCE: IndicatorParamMeta = IndicatorParamMeta {
+    name: "source",
+    kind: IndicatorParamKind::String,
+    required: false,
+    default: Some(IndicatorParamDefault::String("rsi")),
+    description: "Catalog oscillator id to check against price for divergence",
+    min: None,
+    max: None,
+    allowed: Some(&["rsi", "cci", "cmo", "mfi", "williams_r"]),
+};
+
+pub const META: IndicatorMeta = IndicatorMeta {
+    id: "divergence",
+    display_name: "Price/Oscillator Divergence",
+    category: "event",
+    aliases: &[],
+    param_aliases: &[],
+    params: &[P_DIVERGENCE_SOURCE, P_PERIOD_14, P_PIVOT_LOOKBACK_2],
+    outputs: &[
+        IndicatorOutputMeta {
+            name: "bearish",
+            kind: "signal",
+            description: "Regular bearish: price higher high, oscillator lower high",
+        },
+        IndicatorOutputMeta {
+            name: "bullish",
+            kind: "signal",
+            description: "Regular bullish: price lower low, oscillator higher low",
+        },
+        IndicatorOutputMeta {
+            name: "hidden_bearish",
+            kind: "signal",
+            description: "Hidden bearish: price lower high, oscillator higher high",
+        },
+        IndicatorOutputMeta {
+            name: "hidden_bullish",
+            kind: "signal",
+            description: "Hidden bullish: price higher low, oscillator lower low",
+        },
+    ],
+    semantics: IndicatorSemanticsMeta {
+        required_fields: &[],
+        optional_fields: &[],
+        lookback_params: &["period", "pivot_lookback"],
+        default_lookback: None,
+        warmup_policy: "window",
+        source_param: Some("source"),
+    },
+    visual: VIS_DIVERGENCE,
+    runtime_binding: "divergence",
+    constraints: &[],
+};
@@ -18,7 +18,9 @@ pub const META: IndicatorMeta = IndicatorMeta {
         lookback_params: &[],
         default_lookback: Some(2),
         warmup_policy: "none",
+        source_param: None,
     },
     visual: VIS_SIGNAL_FLAG,
     runtime_binding: "enter",
+    constraints: &[],
 };
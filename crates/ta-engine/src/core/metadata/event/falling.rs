@@ -15,4 +15,5 @@ pub const META: IndicatorMeta = IndicatorMeta {
     semantics: SEM_CLOSE_NO_LOOKBACK,
     visual: VIS_SIGNAL_FLAG,
     runtime_binding: "falling",
+    constraints: &[],
 };
@@ -18,7 +18,9 @@ pub const META: IndicatorMeta = IndicatorMeta {
         lookback_params: &[],
         default_lookback: Some(1),
         warmup_policy: "none",
+        source_param: None,
     },
     visual: VIS_SIGNAL_FLAG,
     runtime_binding: "in_channel",
+    constraints: &[],
 };
@@ -3,6 +3,7 @@ use super::*;
 mod cross;
 mod crossdown;
 mod crossup;
+mod divergence;
 mod enter;
 mod exit;
 mod falling;
@@ -16,6 +17,7 @@ pub const ENTRIES: &[IndicatorMeta] = &[
     cross::META,
     crossdown::META,
     crossup::META,
+    divergence::META,
     enter::META,
     exit::META,
     falling::META,
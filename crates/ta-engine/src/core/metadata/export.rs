@@ -0,0 +1,101 @@
+//! JSON export of the indicator catalog for non-Rust consumers (a charting
+//! frontend in particular): [`catalog_to_json`] hands over the same data
+//! [`super::indicator_catalog`] returns, [`catalog_json_schema`] derives
+//! a JSON-Schema for each indicator's parameters straight from the same
+//! `IndicatorParamMeta` consts, and [`catalog_snapshot`] wraps the same
+//! data in a versioned envelope meant to be golden-tested, so the data
+//! document and the schema that validates it can never drift apart.
+
+use serde_json::{json, Map, Value};
+
+use super::{indicator_catalog, IndicatorMeta, IndicatorParamKind, IndicatorParamMeta};
+
+/// Bump this whenever a change to the catalog's *shape* (not its data --
+/// adding an indicator is fine) would require frontend consumers to
+/// update how they parse [`catalog_snapshot`]'s document.
+pub const CATALOG_SCHEMA_VERSION: u32 = 1;
+
+/// Serializes the full indicator catalog into a JSON document.
+pub fn catalog_to_json() -> Value {
+    serde_json::to_value(indicator_catalog()).expect("catalog types are always serializable")
+}
+
+/// Wraps [`catalog_to_json`] in a versioned envelope intended for golden
+/// snapshot testing: every enum (pane hint, scale group, primitive, style
+/// slot kind, stroke pattern) serializes to a stable string tag rather
+/// than a discriminant, so the document's shape survives reordering and
+/// any accidental change to a pane hint, z-index, or default style shows
+/// up as a diff in review.
+pub fn catalog_snapshot() -> Value {
+    json!({
+        "schema_version": CATALOG_SCHEMA_VERSION,
+        "indicators": catalog_to_json(),
+    })
+}
+
+/// Emits a JSON-Schema document (draft-07) with one definition per
+/// indicator, describing its parameters' `kind`, `required`, `default`,
+/// and `min`/`max` so a web client can build and validate input forms
+/// without recompiling Rust.
+pub fn catalog_json_schema() -> Value {
+    let mut definitions = Map::new();
+    for meta in indicator_catalog() {
+        definitions.insert(meta.id.to_string(), indicator_schema(meta));
+    }
+
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "ta-engine indicator catalog",
+        "definitions": definitions,
+    })
+}
+
+fn indicator_schema(meta: &IndicatorMeta) -> Value {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    for param in meta.params {
+        properties.insert(param.name.to_string(), param_schema(param));
+        if param.required {
+            required.push(Value::String(param.name.to_string()));
+        }
+    }
+
+    json!({
+        "type": "object",
+        "title": meta.display_name,
+        "properties": properties,
+        "required": required,
+    })
+}
+
+fn param_schema(param: &IndicatorParamMeta) -> Value {
+    let mut schema = Map::new();
+    schema.insert("type".to_string(), json!(json_type(param.kind)));
+    schema.insert(
+        "description".to_string(),
+        Value::String(param.description.to_string()),
+    );
+    if let Some(default) = param.default {
+        schema.insert("default".to_string(), serde_json::to_value(default).unwrap());
+    }
+    if let Some(min) = param.min {
+        schema.insert("minimum".to_string(), json!(min));
+    }
+    if let Some(max) = param.max {
+        schema.insert("maximum".to_string(), json!(max));
+    }
+    if let Some(allowed) = param.allowed {
+        schema.insert("enum".to_string(), json!(allowed));
+    }
+    Value::Object(schema)
+}
+
+fn json_type(kind: IndicatorParamKind) -> &'static str {
+    match kind {
+        IndicatorParamKind::Integer => "integer",
+        IndicatorParamKind::Float => "number",
+        IndicatorParamKind::Boolean => "boolean",
+        IndicatorParamKind::String => "string",
+        IndicatorParamKind::MaType => "string",
+    }
+}
@@ -1,25 +1,72 @@
 //! Canonical indicator metadata catalog for Rust-first compute/runtime ownership.
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum IndicatorParamKind {
     Integer,
     Float,
     Boolean,
     String,
+    /// A moving-average kernel selector, e.g. `ma_type`/`signal_ma_type`
+    /// params whose `allowed` choices are one of [`crate::moving_averages::MovingAverageType::parse`]'s
+    /// names. Coerces/serializes like `String`, but keeps matype params
+    /// self-describing in exported schemas instead of looking like any
+    /// other free-text string field.
+    MaType,
 }
 
+/// A parameter default with its native Rust type preserved, so consumers
+/// don't have to re-parse a string at runtime (and can't construct a
+/// default that doesn't match its [`IndicatorParamKind`]).
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum IndicatorParamDefault {
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    String(&'static str),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct IndicatorParamMeta {
     pub name: &'static str,
     pub kind: IndicatorParamKind,
     pub required: bool,
-    pub default: Option<&'static str>,
+    pub default: Option<IndicatorParamDefault>,
     pub description: &'static str,
     pub min: Option<f64>,
     pub max: Option<f64>,
+    /// For a closed-choice `String` or `MaType` param, the legal values;
+    /// `None` means any string is accepted. Unused for other `kind`s.
+    pub allowed: Option<&'static [&'static str]>,
+}
+
+/// A relational comparison between two of an indicator's own param values,
+/// checked after both have been coerced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum ConstraintOp {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// A cross-field invariant between two params of the same indicator, e.g.
+/// `fast_period < slow_period`. Checked once both sides have been coerced
+/// and range-checked individually.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ParamConstraint {
+    pub left: &'static str,
+    pub op: ConstraintOp,
+    pub right: &'static str,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct IndicatorOutputMeta {
     pub name: &'static str,
     pub kind: &'static str,
@@ -27,6 +74,7 @@ pub struct IndicatorOutputMeta {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum IndicatorPaneHint {
     PriceOverlay,
     SeparatePane,
@@ -35,6 +83,7 @@ pub enum IndicatorPaneHint {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum IndicatorScaleGroup {
     Price,
     Oscillator,
@@ -44,6 +93,7 @@ pub enum IndicatorScaleGroup {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum OutputVisualPrimitive {
     Line,
     Histogram,
@@ -53,6 +103,7 @@ pub enum OutputVisualPrimitive {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum StrokePattern {
     Solid,
     Dashed,
@@ -60,12 +111,14 @@ pub enum StrokePattern {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum StyleSlotType {
     Stroke,
     Fill,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct StyleDefaultMeta {
     pub color: &'static str,
     pub width: Option<f64>,
@@ -74,13 +127,18 @@ pub struct StyleDefaultMeta {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct StyleSlotMeta {
     pub slot: &'static str,
     pub kind: StyleSlotType,
+    /// The semantic color role this slot resolves through a [`Theme`] when
+    /// present; `None` means it always uses `default.color` as-is.
+    pub token: Option<ColorToken>,
     pub default: StyleDefaultMeta,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct OutputVisualMeta {
     pub output: &'static str,
     pub primitive: OutputVisualPrimitive,
@@ -89,6 +147,7 @@ pub struct OutputVisualMeta {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct IndicatorVisualMeta {
     pub pane_hint: IndicatorPaneHint,
     pub scale_group: IndicatorScaleGroup,
@@ -97,21 +156,29 @@ pub struct IndicatorVisualMeta {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct IndicatorSemanticsMeta {
     pub required_fields: &'static [&'static str],
     pub optional_fields: &'static [&'static str],
     pub lookback_params: &'static [&'static str],
     pub default_lookback: Option<usize>,
     pub warmup_policy: &'static str,
+    /// When set, this indicator's required input isn't a raw OHLCV field but
+    /// the output series of another catalog indicator, named by the param
+    /// with this key (e.g. `"source"`). `required_fields`/`optional_fields`
+    /// are ignored for indicators that set this.
+    pub source_param: Option<&'static str>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct IndicatorAliasMeta {
     pub alias: &'static str,
     pub target: &'static str,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct IndicatorMeta {
     pub id: &'static str,
     pub display_name: &'static str,
@@ -123,106 +190,264 @@ pub struct IndicatorMeta {
     pub semantics: IndicatorSemanticsMeta,
     pub visual: IndicatorVisualMeta,
     pub runtime_binding: &'static str,
+    /// Cross-field invariants between this indicator's own params, e.g.
+    /// `fast_period < slow_period`; evaluated after every param has been
+    /// individually coerced and range-checked. Empty for indicators with no
+    /// relational constraints.
+    pub constraints: &'static [ParamConstraint],
 }
 
 const P_PERIOD_14: IndicatorParamMeta = IndicatorParamMeta {
     name: "period",
     kind: IndicatorParamKind::Integer,
     required: false,
-    default: Some("14"),
+    default: Some(IndicatorParamDefault::Integer(14)),
     description: "Lookback period",
     min: Some(1.0),
     max: None,
+    allowed: None,
 };
 
 const P_PERIOD_12: IndicatorParamMeta = IndicatorParamMeta {
     name: "period",
     kind: IndicatorParamKind::Integer,
     required: false,
-    default: Some("12"),
+    default: Some(IndicatorParamDefault::Integer(12)),
     description: "Lookback period",
     min: Some(1.0),
     max: None,
+    allowed: None,
 };
 
 const P_PERIOD_20: IndicatorParamMeta = IndicatorParamMeta {
     name: "period",
     kind: IndicatorParamKind::Integer,
     required: false,
-    default: Some("20"),
+    default: Some(IndicatorParamDefault::Integer(20)),
     description: "Lookback period",
     min: Some(1.0),
     max: None,
+    allowed: None,
+};
+
+const P_PERIOD_10: IndicatorParamMeta = IndicatorParamMeta {
+    name: "period",
+    kind: IndicatorParamKind::Integer,
+    required: false,
+    default: Some(IndicatorParamDefault::Integer(10)),
+    description: "ATR lookback period",
+    min: Some(1.0),
+    max: None,
+    allowed: None,
+};
+
+const P_PERIOD_100: IndicatorParamMeta = IndicatorParamMeta {
+    name: "period",
+    kind: IndicatorParamKind::Integer,
+    required: false,
+    default: Some(IndicatorParamDefault::Integer(100)),
+    description: "Lookback period",
+    min: Some(16.0),
+    max: None,
+    allowed: None,
+};
+
+const P_MA_PERIOD_50: IndicatorParamMeta = IndicatorParamMeta {
+    name: "ma_period",
+    kind: IndicatorParamKind::Integer,
+    required: false,
+    default: Some(IndicatorParamDefault::Integer(50)),
+    description: "Moving-average period for the SMA/EMA cross contributors",
+    min: Some(1.0),
+    max: None,
+    allowed: None,
 };
 
 const P_FAST_PERIOD_12: IndicatorParamMeta = IndicatorParamMeta {
     name: "fast_period",
     kind: IndicatorParamKind::Integer,
     required: false,
-    default: Some("12"),
+    default: Some(IndicatorParamDefault::Integer(12)),
     description: "Fast moving average period",
     min: Some(1.0),
     max: None,
+    allowed: None,
 };
 
 const P_FAST_PERIOD_5: IndicatorParamMeta = IndicatorParamMeta {
     name: "fast_period",
     kind: IndicatorParamKind::Integer,
     required: false,
-    default: Some("5"),
+    default: Some(IndicatorParamDefault::Integer(5)),
     description: "Fast moving average period",
     min: Some(1.0),
     max: None,
+    allowed: None,
+};
+
+const P_FAST_PERIOD_2: IndicatorParamMeta = IndicatorParamMeta {
+    name: "fast_period",
+    kind: IndicatorParamKind::Integer,
+    required: false,
+    default: Some(IndicatorParamDefault::Integer(2)),
+    description: "Fastest smoothing constant period for the efficiency-ratio blend",
+    min: Some(1.0),
+    max: None,
+    allowed: None,
 };
 
 const P_SLOW_PERIOD_26: IndicatorParamMeta = IndicatorParamMeta {
     name: "slow_period",
     kind: IndicatorParamKind::Integer,
     required: false,
-    default: Some("26"),
+    default: Some(IndicatorParamDefault::Integer(26)),
     description: "Slow moving average period",
     min: Some(1.0),
     max: None,
+    allowed: None,
 };
 
 const P_SLOW_PERIOD_34: IndicatorParamMeta = IndicatorParamMeta {
     name: "slow_period",
     kind: IndicatorParamKind::Integer,
     required: false,
-    default: Some("34"),
+    default: Some(IndicatorParamDefault::Integer(34)),
     description: "Slow moving average period",
     min: Some(1.0),
     max: None,
+    allowed: None,
+};
+
+const P_SLOW_PERIOD_30: IndicatorParamMeta = IndicatorParamMeta {
+    name: "slow_period",
+    kind: IndicatorParamKind::Integer,
+    required: false,
+    default: Some(IndicatorParamDefault::Integer(30)),
+    description: "Slowest smoothing constant period for the efficiency-ratio blend",
+    min: Some(1.0),
+    max: None,
+    allowed: None,
+};
+
+const P_FAST_PERIOD_3: IndicatorParamMeta = IndicatorParamMeta {
+    name: "fast_period",
+    kind: IndicatorParamKind::Integer,
+    required: false,
+    default: Some(IndicatorParamDefault::Integer(3)),
+    description: "Fast moving average period",
+    min: Some(1.0),
+    max: None,
+    allowed: None,
+};
+
+const P_SLOW_PERIOD_10: IndicatorParamMeta = IndicatorParamMeta {
+    name: "slow_period",
+    kind: IndicatorParamKind::Integer,
+    required: false,
+    default: Some(IndicatorParamDefault::Integer(10)),
+    description: "Slow moving average period",
+    min: Some(1.0),
+    max: None,
+    allowed: None,
+};
+
+const P_SIGNAL_PERIOD_5: IndicatorParamMeta = IndicatorParamMeta {
+    name: "signal_period",
+    kind: IndicatorParamKind::Integer,
+    required: false,
+    default: Some(IndicatorParamDefault::Integer(5)),
+    description: "Signal smoothing period",
+    min: Some(1.0),
+    max: None,
+    allowed: None,
+};
+
+const P_ER_PERIOD_10: IndicatorParamMeta = IndicatorParamMeta {
+    name: "er_period",
+    kind: IndicatorParamKind::Integer,
+    required: false,
+    default: Some(IndicatorParamDefault::Integer(10)),
+    description: "Efficiency ratio lookback period",
+    min: Some(1.0),
+    max: None,
+    allowed: None,
 };
 
 const P_SIGNAL_PERIOD_9: IndicatorParamMeta = IndicatorParamMeta {
     name: "signal_period",
     kind: IndicatorParamKind::Integer,
     required: false,
-    default: Some("9"),
+    default: Some(IndicatorParamDefault::Integer(9)),
     description: "Signal period",
     min: Some(1.0),
     max: None,
+    allowed: None,
+};
+
+const P_WINDOW_9: IndicatorParamMeta = IndicatorParamMeta {
+    name: "window",
+    kind: IndicatorParamKind::Integer,
+    required: false,
+    default: Some(IndicatorParamDefault::Integer(9)),
+    description: "Trailing sample count the Gaussian weight vector is spread over",
+    min: Some(1.0),
+    max: None,
+    allowed: None,
+};
+
+const P_OFFSET_0_85: IndicatorParamMeta = IndicatorParamMeta {
+    name: "offset",
+    kind: IndicatorParamKind::Float,
+    required: false,
+    default: Some(IndicatorParamDefault::Float(0.85)),
+    description: "Weight-curve peak position within the window, from 0 (oldest) to 1 (newest)",
+    min: Some(0.0),
+    max: Some(1.0),
+    allowed: None,
+};
+
+const P_SIGMA_6: IndicatorParamMeta = IndicatorParamMeta {
+    name: "sigma",
+    kind: IndicatorParamKind::Float,
+    required: false,
+    default: Some(IndicatorParamDefault::Float(6.0)),
+    description: "Controls how quickly the Gaussian weights taper away from the peak",
+    min: Some(0.0),
+    max: None,
+    allowed: None,
 };
 
 const P_STD_DEV_2: IndicatorParamMeta = IndicatorParamMeta {
     name: "std_dev",
     kind: IndicatorParamKind::Float,
     required: false,
-    default: Some("2.0"),
+    default: Some(IndicatorParamDefault::Float(2.0)),
     description: "Standard deviation multiplier",
     min: Some(0.0),
     max: None,
+    allowed: None,
+};
+
+const P_SESSION_SECONDS_86400: IndicatorParamMeta = IndicatorParamMeta {
+    name: "session_seconds",
+    kind: IndicatorParamKind::Integer,
+    required: false,
+    default: Some(IndicatorParamDefault::Integer(86400)),
+    description: "Session bucket width in seconds used to reset the anchor; 0 disables session resets",
+    min: Some(0.0),
+    max: None,
+    allowed: None,
 };
 
 const P_PCT_5: IndicatorParamMeta = IndicatorParamMeta {
     name: "pct",
     kind: IndicatorParamKind::Float,
     required: false,
-    default: Some("5"),
+    default: Some(IndicatorParamDefault::Float(5.0)),
     description: "Percentage threshold",
     min: Some(0.0),
     max: None,
+    allowed: None,
 };
 const P_SOURCE_STR: IndicatorParamMeta = IndicatorParamMeta {
     name: "source",
@@ -232,6 +457,7 @@ const P_SOURCE_STR: IndicatorParamMeta = IndicatorParamMeta {
     description: "Source field override",
     min: None,
     max: None,
+    allowed: None,
 };
 const P_A_SERIES: IndicatorParamMeta = IndicatorParamMeta {
     name: "a",
@@ -241,6 +467,7 @@ const P_A_SERIES: IndicatorParamMeta = IndicatorParamMeta {
     description: "Primary series input",
     min: None,
     max: None,
+    allowed: None,
 };
 const P_B_SERIES: IndicatorParamMeta = IndicatorParamMeta {
     name: "b",
@@ -250,6 +477,7 @@ const P_B_SERIES: IndicatorParamMeta = IndicatorParamMeta {
     description: "Secondary series input",
     min: None,
     max: None,
+    allowed: None,
 };
 const P_PRICE_SERIES: IndicatorParamMeta = IndicatorParamMeta {
     name: "price",
@@ -259,6 +487,7 @@ const P_PRICE_SERIES: IndicatorParamMeta = IndicatorParamMeta {
     description: "Price series input",
     min: None,
     max: None,
+    allowed: None,
 };
 const P_UPPER_SERIES: IndicatorParamMeta = IndicatorParamMeta {
     name: "upper",
@@ -268,6 +497,7 @@ const P_UPPER_SERIES: IndicatorParamMeta = IndicatorParamMeta {
     description: "Upper bound input",
     min: None,
     max: None,
+    allowed: None,
 };
 const P_LOWER_SERIES: IndicatorParamMeta = IndicatorParamMeta {
     name: "lower",
@@ -277,173 +507,396 @@ const P_LOWER_SERIES: IndicatorParamMeta = IndicatorParamMeta {
     description: "Lower bound input",
     min: None,
     max: None,
+    allowed: None,
 };
 
 const P_MULTIPLIER_3: IndicatorParamMeta = IndicatorParamMeta {
     name: "multiplier",
     kind: IndicatorParamKind::Float,
     required: false,
-    default: Some("3.0"),
+    default: Some(IndicatorParamDefault::Float(3.0)),
     description: "Channel multiplier",
     min: Some(0.0),
     max: None,
+    allowed: None,
 };
 const P_MULTIPLIER_2: IndicatorParamMeta = IndicatorParamMeta {
     name: "multiplier",
     kind: IndicatorParamKind::Float,
     required: false,
-    default: Some("2.0"),
+    default: Some(IndicatorParamDefault::Float(2.0)),
     description: "Channel multiplier",
     min: Some(0.0),
     max: None,
+    allowed: None,
 };
 const P_EMA_PERIOD_20: IndicatorParamMeta = IndicatorParamMeta {
     name: "ema_period",
     kind: IndicatorParamKind::Integer,
     required: false,
-    default: Some("20"),
+    default: Some(IndicatorParamDefault::Integer(20)),
     description: "EMA period",
     min: Some(1.0),
     max: None,
+    allowed: None,
 };
 const P_ATR_PERIOD_10: IndicatorParamMeta = IndicatorParamMeta {
     name: "atr_period",
     kind: IndicatorParamKind::Integer,
     required: false,
-    default: Some("10"),
+    default: Some(IndicatorParamDefault::Integer(10)),
     description: "ATR period",
     min: Some(1.0),
     max: None,
+    allowed: None,
+};
+
+const P_ATR_SMOOTHING: IndicatorParamMeta = IndicatorParamMeta {
+    name: "smoothing",
+    kind: IndicatorParamKind::String,
+    required: false,
+    default: Some(IndicatorParamDefault::String("wilder")),
+    description: "True-range averaging method: wilder, sma, or ema",
+    min: None,
+    max: None,
+    allowed: Some(&["wilder", "sma", "ema"]),
 };
 
 const P_TENKAN_9: IndicatorParamMeta = IndicatorParamMeta {
     name: "tenkan_period",
     kind: IndicatorParamKind::Integer,
     required: false,
-    default: Some("9"),
+    default: Some(IndicatorParamDefault::Integer(9)),
     description: "Tenkan-sen period",
     min: Some(1.0),
     max: None,
+    allowed: None,
 };
 
 const P_KIJUN_26: IndicatorParamMeta = IndicatorParamMeta {
     name: "kijun_period",
     kind: IndicatorParamKind::Integer,
     required: false,
-    default: Some("26"),
+    default: Some(IndicatorParamDefault::Integer(26)),
     description: "Kijun-sen period",
     min: Some(1.0),
     max: None,
+    allowed: None,
 };
 
 const P_SPAN_B_52: IndicatorParamMeta = IndicatorParamMeta {
     name: "span_b_period",
     kind: IndicatorParamKind::Integer,
     required: false,
-    default: Some("52"),
+    default: Some(IndicatorParamDefault::Integer(52)),
     description: "Senkou Span B period",
     min: Some(1.0),
     max: None,
+    allowed: None,
 };
 
 const P_DISPLACEMENT_26: IndicatorParamMeta = IndicatorParamMeta {
     name: "displacement",
     kind: IndicatorParamKind::Integer,
     required: false,
-    default: Some("26"),
+    default: Some(IndicatorParamDefault::Integer(26)),
     description: "Ichimoku displacement",
     min: Some(1.0),
     max: None,
+    allowed: None,
 };
 
 const P_AF_START_002: IndicatorParamMeta = IndicatorParamMeta {
     name: "af_start",
     kind: IndicatorParamKind::Float,
     required: false,
-    default: Some("0.02"),
+    default: Some(IndicatorParamDefault::Float(0.02)),
     description: "Initial acceleration factor",
     min: Some(0.0),
     max: None,
+    allowed: None,
 };
 
 const P_AF_INCREMENT_002: IndicatorParamMeta = IndicatorParamMeta {
     name: "af_increment",
     kind: IndicatorParamKind::Float,
     required: false,
-    default: Some("0.02"),
+    default: Some(IndicatorParamDefault::Float(0.02)),
     description: "Acceleration factor increment",
     min: Some(0.0),
     max: None,
+    allowed: None,
 };
 
 const P_AF_MAX_02: IndicatorParamMeta = IndicatorParamMeta {
     name: "af_max",
     kind: IndicatorParamKind::Float,
     required: false,
-    default: Some("0.2"),
+    default: Some(IndicatorParamDefault::Float(0.2)),
     description: "Maximum acceleration factor",
     min: Some(0.0),
     max: None,
+    allowed: None,
 };
 
 const P_K_PERIOD_14: IndicatorParamMeta = IndicatorParamMeta {
     name: "k_period",
     kind: IndicatorParamKind::Integer,
     required: false,
-    default: Some("14"),
+    default: Some(IndicatorParamDefault::Integer(14)),
     description: "Fast stochastic lookback",
     min: Some(1.0),
     max: None,
+    allowed: None,
 };
 
 const P_D_PERIOD_3: IndicatorParamMeta = IndicatorParamMeta {
     name: "d_period",
     kind: IndicatorParamKind::Integer,
     required: false,
-    default: Some("3"),
+    default: Some(IndicatorParamDefault::Integer(3)),
     description: "Signal smoothing period",
     min: Some(1.0),
     max: None,
+    allowed: None,
 };
 
 const P_SMOOTH_1: IndicatorParamMeta = IndicatorParamMeta {
     name: "smooth",
     kind: IndicatorParamKind::Integer,
     required: false,
-    default: Some("1"),
+    default: Some(IndicatorParamDefault::Integer(1)),
     description: "Pre-smoothing for stochastic K",
     min: Some(1.0),
     max: None,
+    allowed: None,
+};
+
+const P_SOURCE_INDICATOR: IndicatorParamMeta = IndicatorParamMeta {
+    name: "source",
+    kind: IndicatorParamKind::String,
+    required: false,
+    default: Some(IndicatorParamDefault::String("rsi")),
+    description: "Catalog indicator id to compute the stochastic over (e.g. \"rsi\" for StochRSI)",
+    min: None,
+    max: None,
+    allowed: None,
+};
+
+const P_RSI_PERIOD_14: IndicatorParamMeta = IndicatorParamMeta {
+    name: "rsi_period",
+    kind: IndicatorParamKind::Integer,
+    required: false,
+    default: Some(IndicatorParamDefault::Integer(14)),
+    description: "Lookback period for the underlying RSI",
+    min: Some(1.0),
+    max: None,
+    allowed: None,
+};
+
+const P_RSI_PERIOD_13: IndicatorParamMeta = IndicatorParamMeta {
+    name: "rsi_period",
+    kind: IndicatorParamKind::Integer,
+    required: false,
+    default: Some(IndicatorParamDefault::Integer(13)),
+    description: "Lookback period for the underlying RSI",
+    min: Some(1.0),
+    max: None,
+    allowed: None,
+};
+
+const P_STOCH_PERIOD_14: IndicatorParamMeta = IndicatorParamMeta {
+    name: "stoch_period",
+    kind: IndicatorParamKind::Integer,
+    required: false,
+    default: Some(IndicatorParamDefault::Integer(14)),
+    description: "Window the stochastic rescale of the RSI series is taken over",
+    min: Some(1.0),
+    max: None,
+    allowed: None,
+};
+
+const P_STOCH_PERIOD_8: IndicatorParamMeta = IndicatorParamMeta {
+    name: "stoch_period",
+    kind: IndicatorParamKind::Integer,
+    required: false,
+    default: Some(IndicatorParamDefault::Integer(8)),
+    description: "Window the stochastic rescale of the RSI series is taken over",
+    min: Some(1.0),
+    max: None,
+    allowed: None,
+};
+
+const P_SMOOTH_K_3: IndicatorParamMeta = IndicatorParamMeta {
+    name: "smooth_k",
+    kind: IndicatorParamKind::Integer,
+    required: false,
+    default: Some(IndicatorParamDefault::Integer(3)),
+    description: "Smoothing period applied to %K",
+    min: Some(1.0),
+    max: None,
+    allowed: None,
+};
+
+const P_SD_PERIOD_3: IndicatorParamMeta = IndicatorParamMeta {
+    name: "sd_period",
+    kind: IndicatorParamKind::Integer,
+    required: false,
+    default: Some(IndicatorParamDefault::Integer(3)),
+    description: "Smoothing period applied to %D",
+    min: Some(1.0),
+    max: None,
+    allowed: None,
+};
+
+const P_SK_PERIOD_5: IndicatorParamMeta = IndicatorParamMeta {
+    name: "sk_period",
+    kind: IndicatorParamKind::Integer,
+    required: false,
+    default: Some(IndicatorParamDefault::Integer(5)),
+    description: "Smoothing period applied to %K",
+    min: Some(1.0),
+    max: None,
+    allowed: None,
+};
+
+const P_SMOOTH_D_3: IndicatorParamMeta = IndicatorParamMeta {
+    name: "smooth_d",
+    kind: IndicatorParamKind::Integer,
+    required: false,
+    default: Some(IndicatorParamDefault::Integer(3)),
+    description: "Smoothing period applied to %D",
+    min: Some(1.0),
+    max: None,
+    allowed: None,
+};
+
+const P_MA_METHOD_SMA_DT: IndicatorParamMeta = IndicatorParamMeta {
+    name: "ma_type",
+    kind: IndicatorParamKind::MaType,
+    required: false,
+    default: Some(IndicatorParamDefault::String("SMA")),
+    description: "Moving average kernel used for the %K/%D smoothing stages",
+    min: None,
+    max: None,
+    allowed: Some(MA_TYPE_CHOICES),
+};
+
+const GAP_POLICY_CHOICES: &[&str] = &["propagate_na", "skip_na"];
+
+const P_GAP_POLICY: IndicatorParamMeta = IndicatorParamMeta {
+    name: "gap_policy",
+    kind: IndicatorParamKind::String,
+    required: false,
+    default: Some(IndicatorParamDefault::String("propagate_na")),
+    description: "How a NaN bar inside a rolling window is treated: propagate_na (the window's output is NaN) or skip_na (the window is reduced over its remaining valid values)",
+    min: None,
+    max: None,
+    allowed: Some(GAP_POLICY_CHOICES),
+};
+
+const MA_TYPE_CHOICES: &[&str] = &[
+    "SMA", "EMA", "WMA", "TMA", "VIDYA", "WWMA", "ZLEMA", "DEMA", "TEMA", "TRIMA", "KAMA", "HULL",
+    "SINE_WMA", "T3", "LINREG",
+];
+
+const P_MA_TYPE_SMA: IndicatorParamMeta = IndicatorParamMeta {
+    name: "ma_type",
+    kind: IndicatorParamKind::MaType,
+    required: false,
+    default: Some(IndicatorParamDefault::String("SMA")),
+    description: "Moving average kernel: SMA, EMA, WMA, TMA, VIDYA, WWMA, ZLEMA, DEMA, TEMA, TRIMA, KAMA, HULL, SINE_WMA, T3, or LINREG",
+    min: None,
+    max: None,
+    allowed: Some(MA_TYPE_CHOICES),
+};
+
+const P_MA_TYPE_EMA: IndicatorParamMeta = IndicatorParamMeta {
+    name: "ma_type",
+    kind: IndicatorParamKind::MaType,
+    required: false,
+    default: Some(IndicatorParamDefault::String("EMA")),
+    description: "Moving average kernel: SMA, EMA, WMA, TMA, VIDYA, WWMA, ZLEMA, DEMA, TEMA, TRIMA, KAMA, HULL, SINE_WMA, T3, or LINREG",
+    min: None,
+    max: None,
+    allowed: Some(MA_TYPE_CHOICES),
+};
+
+const P_SIGNAL_MA_TYPE_EMA: IndicatorParamMeta = IndicatorParamMeta {
+    name: "signal_ma_type",
+    kind: IndicatorParamKind::MaType,
+    required: false,
+    default: Some(IndicatorParamDefault::String("EMA")),
+    description: "Moving average kernel for the signal line, independent of the fast/slow `ma_type`",
+    min: None,
+    max: None,
+    allowed: Some(MA_TYPE_CHOICES),
+};
+
+const P_MA_METHOD_SMA: IndicatorParamMeta = IndicatorParamMeta {
+    name: "ma_method",
+    kind: IndicatorParamKind::MaType,
+    required: false,
+    default: Some(IndicatorParamDefault::String("SMA")),
+    description: "Moving average kernel used to smooth %K/%D: SMA, EMA, WMA, TMA, VIDYA, WWMA, ZLEMA, DEMA, TEMA, TRIMA, KAMA, HULL, SINE_WMA, T3, or LINREG",
+    min: None,
+    max: None,
+    allowed: Some(MA_TYPE_CHOICES),
+};
+
+const P_SMOOTH_PERIOD_1: IndicatorParamMeta = IndicatorParamMeta {
+    name: "smooth_period",
+    kind: IndicatorParamKind::Integer,
+    required: false,
+    default: Some(IndicatorParamDefault::Integer(1)),
+    description: "Smoothing period applied to the raw output via `ma_type`; 1 disables smoothing",
+    min: Some(1.0),
+    max: None,
+    allowed: None,
 };
 
 const P_LEFT_2: IndicatorParamMeta = IndicatorParamMeta {
     name: "left",
     kind: IndicatorParamKind::Integer,
     required: false,
-    default: Some("2"),
+    default: Some(IndicatorParamDefault::Integer(2)),
     description: "Left pivot lookback",
     min: Some(1.0),
     max: None,
+    allowed: None,
 };
 
 const P_RIGHT_2: IndicatorParamMeta = IndicatorParamMeta {
     name: "right",
     kind: IndicatorParamKind::Integer,
     required: false,
-    default: Some("2"),
+    default: Some(IndicatorParamDefault::Integer(2)),
     description: "Right pivot lookback",
     min: Some(1.0),
     max: None,
+    allowed: None,
+};
+
+const P_PIVOT_LOOKBACK_2: IndicatorParamMeta = IndicatorParamMeta {
+    name: "pivot_lookback",
+    kind: IndicatorParamKind::Integer,
+    required: false,
+    default: Some(IndicatorParamDefault::Integer(2)),
+    description: "Bars on each side a pivot must exceed to be confirmed",
+    min: Some(1.0),
+    max: None,
+    allowed: None,
 };
 
 const P_ALLOW_EQUAL_FALSE: IndicatorParamMeta = IndicatorParamMeta {
     name: "allow_equal_extremes",
     kind: IndicatorParamKind::Boolean,
     required: false,
-    default: Some("false"),
+    default: Some(IndicatorParamDefault::Boolean(false)),
     description: "Allow equality while detecting extrema",
     min: None,
     max: None,
+    allowed: None,
 };
 
 const PARAM_ALIAS_LOOKBACK_PERIOD: IndicatorAliasMeta = IndicatorAliasMeta {
@@ -457,6 +910,25 @@ const SEM_CLOSE_PERIOD: IndicatorSemanticsMeta = IndicatorSemanticsMeta {
     lookback_params: &["period"],
     default_lookback: None,
     warmup_policy: "window",
+    source_param: None,
+};
+
+const SEM_CLOSE_WINDOW: IndicatorSemanticsMeta = IndicatorSemanticsMeta {
+    required_fields: &["close"],
+    optional_fields: &[],
+    lookback_params: &["window"],
+    default_lookback: None,
+    warmup_policy: "window",
+    source_param: None,
+};
+
+const SEM_CLOSE_ER_PERIOD: IndicatorSemanticsMeta = IndicatorSemanticsMeta {
+    required_fields: &["close"],
+    optional_fields: &[],
+    lookback_params: &["er_period"],
+    default_lookback: None,
+    warmup_policy: "window",
+    source_param: None,
 };
 
 const SEM_CLOSE_FAST_SLOW_SIGNAL: IndicatorSemanticsMeta = IndicatorSemanticsMeta {
@@ -465,6 +937,7 @@ const SEM_CLOSE_FAST_SLOW_SIGNAL: IndicatorSemanticsMeta = IndicatorSemanticsMet
     lookback_params: &["fast_period", "slow_period", "signal_period"],
     default_lookback: None,
     warmup_policy: "window",
+    source_param: None,
 };
 
 const SEM_OHLC_PERIOD: IndicatorSemanticsMeta = IndicatorSemanticsMeta {
@@ -473,6 +946,7 @@ const SEM_OHLC_PERIOD: IndicatorSemanticsMeta = IndicatorSemanticsMeta {
     lookback_params: &["period"],
     default_lookback: None,
     warmup_policy: "window",
+    source_param: None,
 };
 
 const SEM_OHLC_STOCH: IndicatorSemanticsMeta = IndicatorSemanticsMeta {
@@ -481,6 +955,16 @@ const SEM_OHLC_STOCH: IndicatorSemanticsMeta = IndicatorSemanticsMeta {
     lookback_params: &["k_period", "d_period", "smooth"],
     default_lookback: None,
     warmup_policy: "window",
+    source_param: None,
+};
+
+const SEM_TECHNICAL_RATING: IndicatorSemanticsMeta = IndicatorSemanticsMeta {
+    required_fields: &["high", "low", "close"],
+    optional_fields: &[],
+    lookback_params: &["ma_period"],
+    default_lookback: None,
+    warmup_policy: "window",
+    source_param: None,
 };
 
 const SEM_CLOSE_NO_LOOKBACK: IndicatorSemanticsMeta = IndicatorSemanticsMeta {
@@ -489,6 +973,7 @@ const SEM_CLOSE_NO_LOOKBACK: IndicatorSemanticsMeta = IndicatorSemanticsMeta {
     lookback_params: &[],
     default_lookback: Some(1),
     warmup_policy: "none",
+    source_param: None,
 };
 
 const SEM_CLOSE_PAIR: IndicatorSemanticsMeta = IndicatorSemanticsMeta {
@@ -497,11 +982,40 @@ const SEM_CLOSE_PAIR: IndicatorSemanticsMeta = IndicatorSemanticsMeta {
     lookback_params: &[],
     default_lookback: Some(2),
     warmup_policy: "none",
+    source_param: None,
+};
+
+const SEM_INDICATOR_SOURCE_STOCH: IndicatorSemanticsMeta = IndicatorSemanticsMeta {
+    required_fields: &[],
+    optional_fields: &[],
+    lookback_params: &["k_period", "d_period", "smooth"],
+    default_lookback: None,
+    warmup_policy: "window",
+    source_param: Some("source"),
+};
+
+const SEM_STOCH_RSI: IndicatorSemanticsMeta = IndicatorSemanticsMeta {
+    required_fields: &[],
+    optional_fields: &[],
+    lookback_params: &["rsi_period", "stoch_period", "smooth_k", "smooth_d"],
+    default_lookback: None,
+    warmup_policy: "window",
+    source_param: None,
+};
+
+const SEM_DT_OSCILLATOR: IndicatorSemanticsMeta = IndicatorSemanticsMeta {
+    required_fields: &[],
+    optional_fields: &[],
+    lookback_params: &["rsi_period", "stoch_period", "sk_period", "sd_period"],
+    default_lookback: None,
+    warmup_policy: "window",
+    source_param: None,
 };
 
 const STYLE_PRIMARY_LINE: &[StyleSlotMeta] = &[StyleSlotMeta {
     slot: "primary_line",
     kind: StyleSlotType::Stroke,
+    token: Some(ColorToken::Primary),
     default: StyleDefaultMeta {
         color: "#38bdf8",
         width: Some(1.5),
@@ -512,6 +1026,7 @@ const STYLE_PRIMARY_LINE: &[StyleSlotMeta] = &[StyleSlotMeta {
 const STYLE_VOLUME_HIST: &[StyleSlotMeta] = &[StyleSlotMeta {
     slot: "volume_hist",
     kind: StyleSlotType::Fill,
+    token: Some(ColorToken::Neutral),
     default: StyleDefaultMeta {
         color: "#94a3b8",
         width: None,
@@ -522,6 +1037,7 @@ const STYLE_VOLUME_HIST: &[StyleSlotMeta] = &[StyleSlotMeta {
 const STYLE_SIGNAL_MARKER: &[StyleSlotMeta] = &[StyleSlotMeta {
     slot: "signal_marker",
     kind: StyleSlotType::Stroke,
+    token: Some(ColorToken::Bearish),
     default: StyleDefaultMeta {
         color: "#ef4444",
         width: Some(1.0),
@@ -533,6 +1049,7 @@ const STYLE_PRIMARY_SECONDARY: &[StyleSlotMeta] = &[
     StyleSlotMeta {
         slot: "primary_line",
         kind: StyleSlotType::Stroke,
+        token: Some(ColorToken::Primary),
         default: StyleDefaultMeta {
             color: "#38bdf8",
             width: Some(1.5),
@@ -543,6 +1060,7 @@ const STYLE_PRIMARY_SECONDARY: &[StyleSlotMeta] = &[
     StyleSlotMeta {
         slot: "secondary_line",
         kind: StyleSlotType::Stroke,
+        token: Some(ColorToken::Secondary),
         default: StyleDefaultMeta {
             color: "#f97316",
             width: Some(1.5),
@@ -555,6 +1073,7 @@ const STYLE_LINE_SIGNAL: &[StyleSlotMeta] = &[
     StyleSlotMeta {
         slot: "primary_line",
         kind: StyleSlotType::Stroke,
+        token: Some(ColorToken::Primary),
         default: StyleDefaultMeta {
             color: "#38bdf8",
             width: Some(1.5),
@@ -565,6 +1084,7 @@ const STYLE_LINE_SIGNAL: &[StyleSlotMeta] = &[
     StyleSlotMeta {
         slot: "signal_marker",
         kind: StyleSlotType::Stroke,
+        token: Some(ColorToken::Bearish),
         default: StyleDefaultMeta {
             color: "#ef4444",
             width: Some(1.0),
@@ -605,6 +1125,130 @@ const VIS_SIGNAL_OUTPUTS: &[OutputVisualMeta] = &[OutputVisualMeta {
     z_index: 50,
 }];
 
+const STYLE_TECHNICAL_RATING: &[StyleSlotMeta] = &[
+    StyleSlotMeta {
+        slot: "oscillators_rating_line",
+        kind: StyleSlotType::Stroke,
+        token: Some(ColorToken::Secondary),
+        default: StyleDefaultMeta {
+            color: "#f97316",
+            width: Some(1.25),
+            opacity: None,
+            pattern: Some(StrokePattern::Solid),
+        },
+    },
+    StyleSlotMeta {
+        slot: "ma_rating_line",
+        kind: StyleSlotType::Stroke,
+        token: Some(ColorToken::Tertiary),
+        default: StyleDefaultMeta {
+            color: "#a855f7",
+            width: Some(1.25),
+            opacity: None,
+            pattern: Some(StrokePattern::Solid),
+        },
+    },
+    StyleSlotMeta {
+        slot: "all_rating_line",
+        kind: StyleSlotType::Stroke,
+        token: Some(ColorToken::Primary),
+        default: StyleDefaultMeta {
+            color: "#38bdf8",
+            width: Some(1.5),
+            opacity: None,
+            pattern: Some(StrokePattern::Solid),
+        },
+    },
+];
+const VIS_TECHNICAL_RATING_OUTPUTS: &[OutputVisualMeta] = &[
+    OutputVisualMeta {
+        output: "oscillators_rating",
+        primitive: OutputVisualPrimitive::Line,
+        style_slot: "oscillators_rating_line",
+        z_index: 30,
+    },
+    OutputVisualMeta {
+        output: "ma_rating",
+        primitive: OutputVisualPrimitive::Line,
+        style_slot: "ma_rating_line",
+        z_index: 31,
+    },
+    OutputVisualMeta {
+        output: "all_rating",
+        primitive: OutputVisualPrimitive::Line,
+        style_slot: "all_rating_line",
+        z_index: 32,
+    },
+];
+const VIS_TECHNICAL_RATING: IndicatorVisualMeta = IndicatorVisualMeta {
+    pane_hint: IndicatorPaneHint::SeparatePane,
+    scale_group: IndicatorScaleGroup::Normalized,
+    output_visuals: VIS_TECHNICAL_RATING_OUTPUTS,
+    style_slots: STYLE_TECHNICAL_RATING,
+};
+
+const STYLE_LINREG_SLOPE: &[StyleSlotMeta] = &[
+    StyleSlotMeta {
+        slot: "slope_line",
+        kind: StyleSlotType::Stroke,
+        token: Some(ColorToken::Primary),
+        default: StyleDefaultMeta {
+            color: "#38bdf8",
+            width: Some(1.5),
+            opacity: None,
+            pattern: Some(StrokePattern::Solid),
+        },
+    },
+    StyleSlotMeta {
+        slot: "intercept_line",
+        kind: StyleSlotType::Stroke,
+        token: Some(ColorToken::Secondary),
+        default: StyleDefaultMeta {
+            color: "#f97316",
+            width: Some(1.0),
+            opacity: None,
+            pattern: Some(StrokePattern::Dashed),
+        },
+    },
+    StyleSlotMeta {
+        slot: "r_squared_line",
+        kind: StyleSlotType::Stroke,
+        token: Some(ColorToken::Tertiary),
+        default: StyleDefaultMeta {
+            color: "#a855f7",
+            width: Some(1.0),
+            opacity: None,
+            pattern: Some(StrokePattern::Dotted),
+        },
+    },
+];
+const VIS_LINREG_SLOPE_OUTPUTS: &[OutputVisualMeta] = &[
+    OutputVisualMeta {
+        output: "slope",
+        primitive: OutputVisualPrimitive::Line,
+        style_slot: "slope_line",
+        z_index: 30,
+    },
+    OutputVisualMeta {
+        output: "intercept",
+        primitive: OutputVisualPrimitive::Line,
+        style_slot: "intercept_line",
+        z_index: 29,
+    },
+    OutputVisualMeta {
+        output: "r_squared",
+        primitive: OutputVisualPrimitive::Line,
+        style_slot: "r_squared_line",
+        z_index: 28,
+    },
+];
+const VIS_LINREG_SLOPE: IndicatorVisualMeta = IndicatorVisualMeta {
+    pane_hint: IndicatorPaneHint::SeparatePane,
+    scale_group: IndicatorScaleGroup::Normalized,
+    output_visuals: VIS_LINREG_SLOPE_OUTPUTS,
+    style_slots: STYLE_LINREG_SLOPE,
+};
+
 const VIS_PRICE_LINE: IndicatorVisualMeta = IndicatorVisualMeta {
     pane_hint: IndicatorPaneHint::PriceOverlay,
     scale_group: IndicatorScaleGroup::Price,
@@ -639,6 +1283,7 @@ const STYLE_BBANDS: &[StyleSlotMeta] = &[
     StyleSlotMeta {
         slot: "upper_stroke",
         kind: StyleSlotType::Stroke,
+        token: Some(ColorToken::Primary),
         default: StyleDefaultMeta {
             color: "#38bdf8",
             width: Some(1.25),
@@ -649,6 +1294,7 @@ const STYLE_BBANDS: &[StyleSlotMeta] = &[
     StyleSlotMeta {
         slot: "lower_stroke",
         kind: StyleSlotType::Stroke,
+        token: Some(ColorToken::Primary),
         default: StyleDefaultMeta {
             color: "#38bdf8",
             width: Some(1.25),
@@ -659,6 +1305,7 @@ const STYLE_BBANDS: &[StyleSlotMeta] = &[
     StyleSlotMeta {
         slot: "middle_stroke",
         kind: StyleSlotType::Stroke,
+        token: Some(ColorToken::Tertiary),
         default: StyleDefaultMeta {
             color: "#93c5fd",
             width: Some(1.25),
@@ -669,6 +1316,7 @@ const STYLE_BBANDS: &[StyleSlotMeta] = &[
     StyleSlotMeta {
         slot: "channel_fill",
         kind: StyleSlotType::Fill,
+        token: Some(ColorToken::Primary),
         default: StyleDefaultMeta {
             color: "#38bdf8",
             width: None,
@@ -709,10 +1357,89 @@ const VIS_BBANDS: IndicatorVisualMeta = IndicatorVisualMeta {
     output_visuals: VIS_BBANDS_OUTPUTS,
     style_slots: STYLE_BBANDS,
 };
+const STYLE_VWAP_ANCHORED: &[StyleSlotMeta] = &[
+    StyleSlotMeta {
+        slot: "vwap_line",
+        kind: StyleSlotType::Stroke,
+        token: Some(ColorToken::Warning),
+        default: StyleDefaultMeta {
+            color: "#f59e0b",
+            width: Some(1.5),
+            opacity: None,
+            pattern: Some(StrokePattern::Solid),
+        },
+    },
+    StyleSlotMeta {
+        slot: "upper_stroke",
+        kind: StyleSlotType::Stroke,
+        token: Some(ColorToken::Warning),
+        default: StyleDefaultMeta {
+            color: "#f59e0b",
+            width: Some(1.0),
+            opacity: None,
+            pattern: Some(StrokePattern::Dashed),
+        },
+    },
+    StyleSlotMeta {
+        slot: "lower_stroke",
+        kind: StyleSlotType::Stroke,
+        token: Some(ColorToken::Warning),
+        default: StyleDefaultMeta {
+            color: "#f59e0b",
+            width: Some(1.0),
+            opacity: None,
+            pattern: Some(StrokePattern::Dashed),
+        },
+    },
+    StyleSlotMeta {
+        slot: "channel_fill",
+        kind: StyleSlotType::Fill,
+        token: Some(ColorToken::Warning),
+        default: StyleDefaultMeta {
+            color: "#f59e0b",
+            width: None,
+            opacity: Some(0.1),
+            pattern: None,
+        },
+    },
+];
+const VIS_VWAP_ANCHORED_OUTPUTS: &[OutputVisualMeta] = &[
+    OutputVisualMeta {
+        output: "vwap",
+        primitive: OutputVisualPrimitive::Line,
+        style_slot: "vwap_line",
+        z_index: 31,
+    },
+    OutputVisualMeta {
+        output: "upper_k",
+        primitive: OutputVisualPrimitive::Line,
+        style_slot: "upper_stroke",
+        z_index: 30,
+    },
+    OutputVisualMeta {
+        output: "lower_k",
+        primitive: OutputVisualPrimitive::Line,
+        style_slot: "lower_stroke",
+        z_index: 30,
+    },
+    OutputVisualMeta {
+        output: "upper_k|lower_k",
+        primitive: OutputVisualPrimitive::BandFill,
+        style_slot: "channel_fill",
+        z_index: 20,
+    },
+];
+const VIS_VWAP_ANCHORED: IndicatorVisualMeta = IndicatorVisualMeta {
+    pane_hint: IndicatorPaneHint::PriceOverlay,
+    scale_group: IndicatorScaleGroup::Price,
+    output_visuals: VIS_VWAP_ANCHORED_OUTPUTS,
+    style_slots: STYLE_VWAP_ANCHORED,
+};
 const STYLE_MACD: &[StyleSlotMeta] = &[
     StyleSlotMeta {
         slot: "macd_line",
         kind: StyleSlotType::Stroke,
+        token: Some(ColorToken::Primary),
         default: StyleDefaultMeta {
             color: "#38bdf8",
             width: Some(1.5),
@@ -723,6 +1450,7 @@ const STYLE_MACD: &[StyleSlotMeta] = &[
     StyleSlotMeta {
         slot: "signal_line",
         kind: StyleSlotType::Stroke,
+        token: Some(ColorToken::Secondary),
         default: StyleDefaultMeta {
             color: "#f97316",
             width: Some(1.25),
@@ -733,6 +1461,7 @@ const STYLE_MACD: &[StyleSlotMeta] = &[
     StyleSlotMeta {
         slot: "histogram_fill",
         kind: StyleSlotType::Fill,
+        token: Some(ColorToken::Neutral),
         default: StyleDefaultMeta {
             color: "#94a3b8",
             width: None,
@@ -771,6 +1500,7 @@ const STYLE_ADX: &[StyleSlotMeta] = &[
     StyleSlotMeta {
         slot: "adx_line",
         kind: StyleSlotType::Stroke,
+        token: Some(ColorToken::Primary),
         default: StyleDefaultMeta {
             color: "#38bdf8",
             width: Some(1.5),
@@ -781,6 +1511,7 @@ const STYLE_ADX: &[StyleSlotMeta] = &[
     StyleSlotMeta {
         slot: "plus_di_line",
         kind: StyleSlotType::Stroke,
+        token: Some(ColorToken::Bullish),
         default: StyleDefaultMeta {
             color: "#22c55e",
             width: Some(1.0),
@@ -791,6 +1522,7 @@ const STYLE_ADX: &[StyleSlotMeta] = &[
     StyleSlotMeta {
         slot: "minus_di_line",
         kind: StyleSlotType::Stroke,
+        token: Some(ColorToken::Bearish),
         default: StyleDefaultMeta {
             color: "#ef4444",
             width: Some(1.0),
@@ -907,6 +1639,7 @@ const STYLE_ICHIMOKU: &[StyleSlotMeta] = &[
     StyleSlotMeta {
         slot: "tenkan_line",
         kind: StyleSlotType::Stroke,
+        token: Some(ColorToken::Secondary),
         default: StyleDefaultMeta {
             color: "#f97316",
             width: Some(1.2),
@@ -917,6 +1650,7 @@ const STYLE_ICHIMOKU: &[StyleSlotMeta] = &[
     StyleSlotMeta {
         slot: "kijun_line",
         kind: StyleSlotType::Stroke,
+        token: Some(ColorToken::Highlight),
         default: StyleDefaultMeta {
             color: "#3b82f6",
             width: Some(1.2),
@@ -927,6 +1661,7 @@ const STYLE_ICHIMOKU: &[StyleSlotMeta] = &[
     StyleSlotMeta {
         slot: "senkou_a_line",
         kind: StyleSlotType::Stroke,
+        token: Some(ColorToken::Bullish),
         default: StyleDefaultMeta {
             color: "#22c55e",
             width: Some(1.0),
@@ -937,6 +1672,7 @@ const STYLE_ICHIMOKU: &[StyleSlotMeta] = &[
     StyleSlotMeta {
         slot: "senkou_b_line",
         kind: StyleSlotType::Stroke,
+        token: Some(ColorToken::Bearish),
         default: StyleDefaultMeta {
             color: "#ef4444",
             width: Some(1.0),
@@ -947,6 +1683,7 @@ const STYLE_ICHIMOKU: &[StyleSlotMeta] = &[
     StyleSlotMeta {
         slot: "kumo_fill",
         kind: StyleSlotType::Fill,
+        token: Some(ColorToken::NeutralFill),
         default: StyleDefaultMeta {
             color: "#64748b",
             width: None,
@@ -957,6 +1694,7 @@ const STYLE_ICHIMOKU: &[StyleSlotMeta] = &[
     StyleSlotMeta {
         slot: "chikou_line",
         kind: StyleSlotType::Stroke,
+        token: Some(ColorToken::Accent),
         default: StyleDefaultMeta {
             color: "#a855f7",
             width: Some(1.0),
@@ -1071,14 +1809,62 @@ const VIS_SWING_POINTS: IndicatorVisualMeta = IndicatorVisualMeta {
     output_visuals: VIS_SWING_POINTS_OUTPUTS,
     style_slots: STYLE_SIGNAL_MARKER,
 };
+const VIS_DIVERGENCE_OUTPUTS: &[OutputVisualMeta] = &[
+    OutputVisualMeta {
+        output: "bearish",
+        primitive: OutputVisualPrimitive::SignalFlag,
+        style_slot: "signal_marker",
+        z_index: 50,
+    },
+    OutputVisualMeta {
+        output: "bullish",
+        primitive: OutputVisualPrimitive::SignalFlag,
+        style_slot: "signal_marker",
+        z_index: 50,
+    },
+    OutputVisualMeta {
+        output: "hidden_bearish",
+        primitive: OutputVisualPrimitive::SignalFlag,
+        style_slot: "signal_marker",
+        z_index: 50,
+    },
+    OutputVisualMeta {
+        output: "hidden_bullish",
+        primitive: OutputVisualPrimitive::SignalFlag,
+        style_slot: "signal_marker",
+        z_index: 50,
+    },
+];
+const VIS_DIVERGENCE: IndicatorVisualMeta = IndicatorVisualMeta {
+    pane_hint: IndicatorPaneHint::PriceOverlay,
+    scale_group: IndicatorScaleGroup::Binary,
+    output_visuals: VIS_DIVERGENCE_OUTPUTS,
+    style_slots: STYLE_SIGNAL_MARKER,
+};
 
+mod consistency;
 mod event;
+#[cfg(feature = "serde")]
+mod export;
 mod momentum;
 mod pattern;
+mod rating;
+mod registry;
+mod statistics;
+mod theme;
 mod trend;
 mod volatility;
 mod volume;
 
+pub use consistency::{render_diagnostics, validate_catalog, validate_indicators, Diagnostic, Label, Severity};
+pub use registry::{all, by_alias, by_id};
+#[cfg(feature = "serde")]
+pub use export::{catalog_json_schema, catalog_snapshot, catalog_to_json, CATALOG_SCHEMA_VERSION};
+pub use theme::{
+    resolve_style, resolve_styles, ColorToken, ResolvedStyleSlot, SlotOverride, StyleError,
+    StyleTheme, Theme,
+};
+
 /// Returns the canonical indicator catalog exposed by Rust compute.
 pub fn indicator_catalog() -> &'static [IndicatorMeta] {
     use std::sync::OnceLock;
@@ -1093,6 +1879,8 @@ pub fn indicator_catalog() -> &'static [IndicatorMeta] {
             catalog.extend_from_slice(volume::ENTRIES);
             catalog.extend_from_slice(event::ENTRIES);
             catalog.extend_from_slice(pattern::ENTRIES);
+            catalog.extend_from_slice(rating::ENTRIES);
+            catalog.extend_from_slice(statistics::ENTRIES);
             catalog.sort_by(|a, b| a.id.cmp(b.id));
             catalog.into_boxed_slice()
         })
@@ -1109,3 +1897,43 @@ pub fn find_indicator_meta(id: &str) -> Option<&'static IndicatorMeta> {
                 .any(|alias| alias.eq_ignore_ascii_case(id))
     })
 }
+
+/// All catalog entries in the given `category` (e.g. "trend", "volume").
+pub fn indicators_by_category(category: &str) -> Vec<&'static IndicatorMeta> {
+    indicator_catalog()
+        .iter()
+        .filter(|meta| meta.category.eq_ignore_ascii_case(category))
+        .collect()
+}
+
+/// All catalog entries whose `semantics.required_fields` list `field`
+/// (e.g. "volume" for indicators that need a volume series).
+pub fn indicators_requiring_field(field: &str) -> Vec<&'static IndicatorMeta> {
+    indicator_catalog()
+        .iter()
+        .filter(|meta| {
+            meta.semantics
+                .required_fields
+                .iter()
+                .any(|f| f.eq_ignore_ascii_case(field))
+        })
+        .collect()
+}
+
+/// All catalog entries that declare an output of the given `kind` (e.g.
+/// "line", "histogram", "signal").
+pub fn indicators_by_output_kind(kind: &str) -> Vec<&'static IndicatorMeta> {
+    indicator_catalog()
+        .iter()
+        .filter(|meta| meta.outputs.iter().any(|o| o.kind.eq_ignore_ascii_case(kind)))
+        .collect()
+}
+
+/// Finds the catalog entry whose `runtime_binding` matches `binding`, for
+/// round-tripping a runtime dispatch key back to its metadata (e.g. to
+/// validate that every binding has exactly one catalog entry).
+pub fn resolve_by_runtime_binding(binding: &str) -> Option<&'static IndicatorMeta> {
+    indicator_catalog()
+        .iter()
+        .find(|meta| meta.runtime_binding.eq_ignore_ascii_case(binding))
+}
@@ -0,0 +1,26 @@
+use super::*;
+
+pub const META: IndicatorMeta = IndicatorMeta {
+    id: "ac",
+    display_name: "Accelerator Oscillator",
+    category: "momentum",
+    aliases: &[],
+    param_aliases: &[],
+    params: &[P_FAST_PERIOD_5, P_SLOW_PERIOD_34, P_SIGNAL_PERIOD_5],
+    outputs: &[IndicatorOutputMeta {
+        name: "result",
+        kind: "column",
+        description: "AC value",
+    }],
+    semantics: IndicatorSemanticsMeta {
+        required_fields: &["high", "low"],
+        optional_fields: &[],
+        lookback_params: &["fast_period", "slow_period", "signal_period"],
+        default_lookback: None,
+        warmup_policy: "window",
+        source_param: None,
+    },
+    visual: VIS_VOLUME_HIST,
+    runtime_binding: "ac",
+    constraints: &[],
+};
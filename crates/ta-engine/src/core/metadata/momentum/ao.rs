@@ -6,7 +6,7 @@ pub const META: IndicatorMeta = IndicatorMeta {
     category: "momentum",
     aliases: &[],
     param_aliases: &[],
-    params: &[P_FAST_PERIOD_5, P_SLOW_PERIOD_34],
+    params: &[P_FAST_PERIOD_5, P_SLOW_PERIOD_34, P_GAP_POLICY],
     outputs: &[IndicatorOutputMeta {
         name: "result",
         kind: "column",
@@ -18,7 +18,13 @@ pub const META: IndicatorMeta = IndicatorMeta {
         lookback_params: &["fast_period", "slow_period"],
         default_lookback: None,
         warmup_policy: "window",
+        source_param: None,
     },
     visual: VIS_VOLUME_HIST,
     runtime_binding: "ao",
+    constraints: &[ParamConstraint {
+        left: "fast_period",
+        op: ConstraintOp::Lt,
+        right: "slow_period",
+    }],
 };
@@ -6,7 +6,7 @@ pub const META: IndicatorMeta = IndicatorMeta {
     category: "momentum",
     aliases: &[],
     param_aliases: &[PARAM_ALIAS_LOOKBACK_PERIOD],
-    params: &[P_PERIOD_20],
+    params: &[P_PERIOD_20, P_GAP_POLICY],
     outputs: &[IndicatorOutputMeta {
         name: "result",
         kind: "line",
@@ -15,4 +15,5 @@ pub const META: IndicatorMeta = IndicatorMeta {
     semantics: SEM_OHLC_PERIOD,
     visual: VIS_OSC_LINE,
     runtime_binding: "cci",
+    constraints: &[],
 };
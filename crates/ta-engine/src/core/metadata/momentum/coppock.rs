@@ -11,28 +11,31 @@ pub const META: IndicatorMeta = IndicatorMeta {
             name: "wma_period",
             kind: IndicatorParamKind::Integer,
             required: false,
-            default: Some("10"),
+            default: Some(IndicatorParamDefault::Integer(10)),
             description: "WMA period",
             min: Some(1.0),
             max: None,
+            allowed: None,
         },
         IndicatorParamMeta {
             name: "fast_roc",
             kind: IndicatorParamKind::Integer,
             required: false,
-            default: Some("11"),
+            default: Some(IndicatorParamDefault::Integer(11)),
             description: "Fast ROC period",
             min: Some(1.0),
             max: None,
+            allowed: None,
         },
         IndicatorParamMeta {
             name: "slow_roc",
             kind: IndicatorParamKind::Integer,
             required: false,
-            default: Some("14"),
+            default: Some(IndicatorParamDefault::Integer(14)),
             description: "Slow ROC period",
             min: Some(1.0),
             max: None,
+            allowed: None,
         },
     ],
     outputs: &[IndicatorOutputMeta {
@@ -43,4 +46,9 @@ pub const META: IndicatorMeta = IndicatorMeta {
     semantics: SEM_CLOSE_FAST_SLOW_SIGNAL,
     visual: VIS_OSC_LINE,
     runtime_binding: "coppock",
+    constraints: &[ParamConstraint {
+        left: "fast_roc",
+        op: ConstraintOp::Lt,
+        right: "slow_roc",
+    }],
 };
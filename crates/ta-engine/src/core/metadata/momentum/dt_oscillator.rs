@@ -0,0 +1,32 @@
+use super::*;
+
+pub const META: IndicatorMeta = IndicatorMeta {
+    id: "dt_oscillator",
+    display_name: "DT Oscillator",
+    category: "momentum",
+    aliases: &[],
+    param_aliases: &[],
+    params: &[
+        P_RSI_PERIOD_13,
+        P_STOCH_PERIOD_8,
+        P_SK_PERIOD_5,
+        P_SD_PERIOD_3,
+        P_MA_METHOD_SMA_DT,
+    ],
+    outputs: &[
+        IndicatorOutputMeta {
+            name: "k",
+            kind: "osc_main",
+            description: "Smoothed %K of the RSI's stochastic rescale",
+        },
+        IndicatorOutputMeta {
+            name: "d",
+            kind: "osc_signal",
+            description: "Smoothed %D of %K",
+        },
+    ],
+    semantics: SEM_DT_OSCILLATOR,
+    visual: VIS_STOCHASTIC,
+    runtime_binding: "dt_oscillator",
+    constraints: &[],
+};
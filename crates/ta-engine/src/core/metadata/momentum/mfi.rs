@@ -6,7 +6,7 @@ pub const META: IndicatorMeta = IndicatorMeta {
     category: "momentum",
     aliases: &[],
     param_aliases: &[PARAM_ALIAS_LOOKBACK_PERIOD],
-    params: &[P_PERIOD_14],
+    params: &[P_PERIOD_14, P_GAP_POLICY],
     outputs: &[IndicatorOutputMeta {
         name: "result",
         kind: "line",
@@ -18,7 +18,9 @@ pub const META: IndicatorMeta = IndicatorMeta {
         lookback_params: &["period"],
         default_lookback: None,
         warmup_policy: "window",
+        source_param: None,
     },
     visual: VIS_OSC_LINE,
     runtime_binding: "mfi",
+    constraints: &[],
 };
@@ -1,24 +1,32 @@
 use super::*;
 
+mod ac;
 mod ao;
 mod cci;
 mod cmo;
 mod coppock;
+mod dt_oscillator;
 mod mfi;
 mod roc;
 mod rsi;
+mod stoch_of;
+mod stoch_rsi;
 mod stochastic;
 mod vortex;
 mod williams_r;
 
 pub const ENTRIES: &[IndicatorMeta] = &[
+    ac::META,
     ao::META,
     cci::META,
     cmo::META,
     coppock::META,
+    dt_oscillator::META,
     mfi::META,
     roc::META,
     rsi::META,
+    stoch_of::META,
+    stoch_rsi::META,
     stochastic::META,
     vortex::META,
     williams_r::META,
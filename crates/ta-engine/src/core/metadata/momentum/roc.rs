@@ -15,4 +15,5 @@ pub const META: IndicatorMeta = IndicatorMeta {
     semantics: SEM_CLOSE_PERIOD,
     visual: VIS_PRICE_LINE,
     runtime_binding: "roc",
+    constraints: &[],
 };
@@ -6,7 +6,19 @@ pub const META: IndicatorMeta = IndicatorMeta {
     category: "momentum",
     aliases: &[],
     param_aliases: &[PARAM_ALIAS_LOOKBACK_PERIOD],
-    params: &[P_PERIOD_14],
+    params: &[
+        P_PERIOD_14,
+        IndicatorParamMeta {
+            name: "method",
+            kind: IndicatorParamKind::String,
+            required: false,
+            default: Some(IndicatorParamDefault::String("wilder")),
+            description: "Gain/loss averaging method: wilder, sma, or ema",
+            min: None,
+            max: None,
+            allowed: Some(&["wilder", "sma", "ema"]),
+        },
+    ],
     outputs: &[IndicatorOutputMeta {
         name: "result",
         kind: "line",
@@ -15,4 +27,5 @@ pub const META: IndicatorMeta = IndicatorMeta {
     semantics: SEM_CLOSE_PERIOD,
     visual: VIS_OSC_LINE,
     runtime_binding: "rsi",
+    constraints: &[],
 };
@@ -0,0 +1,26 @@
+use super::*;
+
+pub const META: IndicatorMeta = IndicatorMeta {
+    id: "stoch_of",
+    display_name: "Stochastic of Indicator",
+    category: "momentum",
+    aliases: &[],
+    param_aliases: &[],
+    params: &[P_SOURCE_INDICATOR, P_K_PERIOD_14, P_D_PERIOD_3, P_SMOOTH_1],
+    outputs: &[
+        IndicatorOutputMeta {
+            name: "k",
+            kind: "osc_main",
+            description: "%K of the source indicator's output",
+        },
+        IndicatorOutputMeta {
+            name: "d",
+            kind: "osc_signal",
+            description: "%D of the source indicator's output",
+        },
+    ],
+    semantics: SEM_INDICATOR_SOURCE_STOCH,
+    visual: VIS_STOCHASTIC,
+    runtime_binding: "stoch_of",
+    constraints: &[],
+};
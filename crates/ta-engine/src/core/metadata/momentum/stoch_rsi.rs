@@ -0,0 +1,26 @@
+use super::*;
+
+pub const META: IndicatorMeta = IndicatorMeta {
+    id: "stoch_rsi",
+    display_name: "Stochastic RSI",
+    category: "momentum",
+    aliases: &[],
+    param_aliases: &[],
+    params: &[P_RSI_PERIOD_14, P_STOCH_PERIOD_14, P_SMOOTH_K_3, P_SMOOTH_D_3],
+    outputs: &[
+        IndicatorOutputMeta {
+            name: "k",
+            kind: "osc_main",
+            description: "Smoothed %K of the RSI's stochastic rescale",
+        },
+        IndicatorOutputMeta {
+            name: "d",
+            kind: "osc_signal",
+            description: "Smoothed %D of %K",
+        },
+    ],
+    semantics: SEM_STOCH_RSI,
+    visual: VIS_STOCHASTIC,
+    runtime_binding: "stoch_rsi",
+    constraints: &[],
+};
@@ -6,7 +6,7 @@ pub const META: IndicatorMeta = IndicatorMeta {
     category: "momentum",
     aliases: &["stoch", "stochastic_kd", "stoch_k", "stoch_d"],
     param_aliases: &[],
-    params: &[P_K_PERIOD_14, P_D_PERIOD_3, P_SMOOTH_1],
+    params: &[P_K_PERIOD_14, P_D_PERIOD_3, P_SMOOTH_1, P_MA_METHOD_SMA, P_GAP_POLICY],
     outputs: &[
         IndicatorOutputMeta {
             name: "k",
@@ -22,4 +22,9 @@ pub const META: IndicatorMeta = IndicatorMeta {
     semantics: SEM_OHLC_STOCH,
     visual: VIS_STOCHASTIC,
     runtime_binding: "stochastic_kd",
+    constraints: &[ParamConstraint {
+        left: "smooth",
+        op: ConstraintOp::Le,
+        right: "k_period",
+    }],
 };
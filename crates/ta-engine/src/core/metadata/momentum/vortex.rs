@@ -6,7 +6,7 @@ pub const META: IndicatorMeta = IndicatorMeta {
     category: "momentum",
     aliases: &[],
     param_aliases: &[PARAM_ALIAS_LOOKBACK_PERIOD],
-    params: &[P_PERIOD_14],
+    params: &[P_PERIOD_14, P_GAP_POLICY],
     outputs: &[
         IndicatorOutputMeta {
             name: "plus",
@@ -22,4 +22,5 @@ pub const META: IndicatorMeta = IndicatorMeta {
     semantics: SEM_OHLC_PERIOD,
     visual: VIS_VORTEX,
     runtime_binding: "vortex",
+    constraints: &[],
 };
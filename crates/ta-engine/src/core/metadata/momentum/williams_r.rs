@@ -6,7 +6,7 @@ pub const META: IndicatorMeta = IndicatorMeta {
     category: "momentum",
     aliases: &[],
     param_aliases: &[PARAM_ALIAS_LOOKBACK_PERIOD],
-    params: &[P_PERIOD_14],
+    params: &[P_PERIOD_14, P_SMOOTH_PERIOD_1, P_MA_TYPE_SMA, P_GAP_POLICY],
     outputs: &[IndicatorOutputMeta {
         name: "result",
         kind: "line",
@@ -15,4 +15,5 @@ pub const META: IndicatorMeta = IndicatorMeta {
     semantics: SEM_OHLC_PERIOD,
     visual: VIS_OSC_LINE,
     runtime_binding: "williams_r",
+    constraints: &[],
 };
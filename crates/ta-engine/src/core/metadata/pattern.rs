@@ -31,7 +31,9 @@ pub const ENTRIES: &[IndicatorMeta] = &[IndicatorMeta {
         lookback_params: &["left", "right"],
         default_lookback: None,
         warmup_policy: "window",
+        source_param: None,
     },
     visual: VIS_SWING_POINTS,
     runtime_binding: "swing_points_raw",
+    constraints: &[],
 }];
@@ -0,0 +1,31 @@
+use super::*;
+
+pub const ENTRIES: &[IndicatorMeta] = &[IndicatorMeta {
+    id: "technical_rating",
+    display_name: "Technical Rating",
+    category: "rating",
+    aliases: &[],
+    param_aliases: &[],
+    params: &[P_MA_PERIOD_50],
+    outputs: &[
+        IndicatorOutputMeta {
+            name: "oscillators_rating",
+            kind: "line",
+            description: "Mean vote of the oscillator contributors (RSI, Stochastic, CCI, AO, MACD, CMO), in [-1, 1]",
+        },
+        IndicatorOutputMeta {
+            name: "ma_rating",
+            kind: "line",
+            description: "Mean vote of the moving-average cross contributors (SMA, EMA), in [-1, 1]",
+        },
+        IndicatorOutputMeta {
+            name: "all_rating",
+            kind: "line",
+            description: "Mean vote across every contributor, in [-1, 1]",
+        },
+    ],
+    semantics: SEM_TECHNICAL_RATING,
+    visual: VIS_TECHNICAL_RATING,
+    runtime_binding: "technical_rating",
+    constraints: &[],
+}];
@@ -0,0 +1,32 @@
+//! Thin, explicitly-named query surface over [`super::indicator_catalog`] for
+//! consumers that want a conventional `all`/`by_id`/`by_alias` registry
+//! rather than the free functions scattered across [`super`]. Every
+//! function here is a direct delegate -- there's exactly one catalog and
+//! one sort order, built once in [`super::indicator_catalog`].
+
+use super::{find_indicator_meta, indicator_catalog, IndicatorMeta};
+
+/// The full catalog, sorted by `id`. Identical to [`super::indicator_catalog`];
+/// exists under this name for callers that think in terms of a registry's
+/// `all()` rather than a free-standing catalog accessor.
+pub fn all() -> &'static [IndicatorMeta] {
+    indicator_catalog()
+}
+
+/// Looks up an indicator by its canonical `id` only -- unlike
+/// [`by_alias`], this does not also check `aliases`.
+pub fn by_id(id: &str) -> Option<&'static IndicatorMeta> {
+    indicator_catalog()
+        .iter()
+        .find(|meta| meta.id.eq_ignore_ascii_case(id))
+}
+
+/// Looks up an indicator by its canonical `id` or any of its `aliases`.
+/// Identical to [`super::find_indicator_meta`]; exists under this name for
+/// registry-shaped call sites. `param_aliases` are per-parameter name
+/// normalization (see [`super::IndicatorAliasMeta`] and
+/// `runtime::params::normalize_params_for`), scoped to one already-resolved
+/// indicator rather than a way to find one, so they aren't searched here.
+pub fn by_alias(alias: &str) -> Option<&'static IndicatorMeta> {
+    find_indicator_meta(alias)
+}
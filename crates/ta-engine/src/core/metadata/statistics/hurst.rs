@@ -0,0 +1,19 @@
+use super::*;
+
+pub const META: IndicatorMeta = IndicatorMeta {
+    id: "hurst",
+    display_name: "Hurst Exponent",
+    category: "statistics",
+    aliases: &["hurst_exponent"],
+    param_aliases: &[PARAM_ALIAS_LOOKBACK_PERIOD],
+    params: &[P_PERIOD_100],
+    outputs: &[IndicatorOutputMeta {
+        name: "result",
+        kind: "line",
+        description: "Hurst exponent estimate via rescaled-range analysis; >0.5 trending, <0.5 mean-reverting",
+    }],
+    semantics: SEM_CLOSE_PERIOD,
+    visual: VIS_OSC_LINE,
+    runtime_binding: "hurst",
+    constraints: &[],
+};
@@ -0,0 +1,31 @@
+use super::*;
+
+pub const META: IndicatorMeta = IndicatorMeta {
+    id: "linreg_slope",
+    display_name: "Linear Regression Slope",
+    category: "statistics",
+    aliases: &[],
+    param_aliases: &[PARAM_ALIAS_LOOKBACK_PERIOD],
+    params: &[P_PERIOD_14],
+    outputs: &[
+        IndicatorOutputMeta {
+            name: "slope",
+            kind: "line",
+            description: "Least-squares slope of close over the window",
+        },
+        IndicatorOutputMeta {
+            name: "intercept",
+            kind: "line",
+            description: "Least-squares intercept of close over the window",
+        },
+        IndicatorOutputMeta {
+            name: "r_squared",
+            kind: "line",
+            description: "Coefficient of determination of the fit",
+        },
+    ],
+    semantics: SEM_CLOSE_PERIOD,
+    visual: VIS_LINREG_SLOPE,
+    runtime_binding: "linreg_slope",
+    constraints: &[],
+};
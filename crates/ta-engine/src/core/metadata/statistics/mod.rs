@@ -0,0 +1,13 @@
+use super::*;
+
+mod hurst;
+mod linreg_slope;
+mod normalized_atr;
+mod smma;
+
+pub const ENTRIES: &[IndicatorMeta] = &[
+    hurst::META,
+    linreg_slope::META,
+    normalized_atr::META,
+    smma::META,
+];
@@ -0,0 +1,19 @@
+use super::*;
+
+pub const META: IndicatorMeta = IndicatorMeta {
+    id: "normalized_atr",
+    display_name: "Normalized ATR",
+    category: "statistics",
+    aliases: &["natr"],
+    param_aliases: &[PARAM_ALIAS_LOOKBACK_PERIOD],
+    params: &[P_PERIOD_14],
+    outputs: &[IndicatorOutputMeta {
+        name: "result",
+        kind: "line",
+        description: "ATR as a percentage of close",
+    }],
+    semantics: SEM_OHLC_PERIOD,
+    visual: VIS_OSC_LINE,
+    runtime_binding: "normalized_atr",
+    constraints: &[],
+};
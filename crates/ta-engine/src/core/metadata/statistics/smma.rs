@@ -0,0 +1,19 @@
+use super::*;
+
+pub const META: IndicatorMeta = IndicatorMeta {
+    id: "smma",
+    display_name: "Smoothed Moving Average",
+    category: "statistics",
+    aliases: &["wilder_ma"],
+    param_aliases: &[PARAM_ALIAS_LOOKBACK_PERIOD],
+    params: &[P_PERIOD_14],
+    outputs: &[IndicatorOutputMeta {
+        name: "result",
+        kind: "line",
+        description: "SMMA value",
+    }],
+    semantics: SEM_CLOSE_PERIOD,
+    visual: VIS_PRICE_LINE,
+    runtime_binding: "smma",
+    constraints: &[],
+};
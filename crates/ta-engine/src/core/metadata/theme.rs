@@ -0,0 +1,279 @@
+//! Two ways to restyle the visual catalog without hard-coding hex values:
+//! [`Theme`]/[`resolve_style`] resolve a [`StyleSlotMeta`]'s semantic
+//! [`ColorToken`] to a concrete color, while [`StyleTheme`]/
+//! [`resolve_styles`] let a caller override a [`StyleDefaultMeta`]'s raw
+//! fields (color/width/opacity/pattern) per slot by indicator id. Both
+//! fall back to the slot's compiled default wherever nothing overrides it.
+
+use std::collections::BTreeMap;
+
+use super::{IndicatorVisualMeta, StrokePattern, StyleDefaultMeta, StyleSlotType};
+
+/// A semantic color role a [`StyleSlotMeta`](super::StyleSlotMeta) can
+/// reference instead of a literal hex value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum ColorToken {
+    Primary,
+    Secondary,
+    Tertiary,
+    Highlight,
+    Accent,
+    Bullish,
+    Bearish,
+    Warning,
+    Neutral,
+    NeutralFill,
+}
+
+impl ColorToken {
+    /// The hex value the built-in default theme resolves this token to.
+    fn default_hex(self) -> &'static str {
+        match self {
+            Self::Primary => "#38bdf8",
+            Self::Secondary => "#f97316",
+            Self::Tertiary => "#93c5fd",
+            Self::Highlight => "#3b82f6",
+            Self::Accent => "#a855f7",
+            Self::Bullish => "#22c55e",
+            Self::Bearish => "#ef4444",
+            Self::Warning => "#f59e0b",
+            Self::Neutral => "#94a3b8",
+            Self::NeutralFill => "#64748b",
+        }
+    }
+}
+
+/// A palette that resolves [`ColorToken`]s to concrete colors at query
+/// time. [`Theme::default_theme`] reproduces the catalog's original hex
+/// values; [`Theme::with_color`] layers per-token overrides on top for a
+/// brand or dark/light palette.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    name: &'static str,
+    overrides: BTreeMap<ColorToken, String>,
+}
+
+impl Theme {
+    /// An empty theme that resolves every token to its built-in default.
+    pub fn named(name: &'static str) -> Self {
+        Self {
+            name,
+            overrides: BTreeMap::new(),
+        }
+    }
+
+    /// The built-in theme, reproducing the hex values the catalog shipped
+    /// with before tokens existed.
+    pub fn default_theme() -> Self {
+        Self::named("default")
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Overrides `token` to resolve to `color` in this theme.
+    pub fn with_color(mut self, token: ColorToken, color: impl Into<String>) -> Self {
+        self.overrides.insert(token, color.into());
+        self
+    }
+
+    /// Resolves `token` to a concrete color: the theme's override if one
+    /// was set, otherwise the token's built-in default.
+    pub fn resolve(&self, token: ColorToken) -> &str {
+        self.overrides
+            .get(&token)
+            .map(String::as_str)
+            .unwrap_or_else(|| token.default_hex())
+    }
+}
+
+/// A [`StyleSlotMeta`](super::StyleSlotMeta) with its color fully
+/// materialized for a chosen [`Theme`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedStyleSlot {
+    pub slot: &'static str,
+    pub kind: StyleSlotType,
+    pub color: String,
+    pub width: Option<f64>,
+    pub opacity: Option<f64>,
+    pub pattern: Option<StrokePattern>,
+}
+
+/// Materializes every style slot in `meta` against `theme`: a slot with a
+/// [`ColorToken`] resolves through the theme, a slot without one falls
+/// back to its [`StyleDefaultMeta`](super::StyleDefaultMeta) color as-is.
+pub fn resolve_style(meta: &IndicatorVisualMeta, theme: &Theme) -> Vec<ResolvedStyleSlot> {
+    meta.style_slots
+        .iter()
+        .map(|slot| {
+            let color = match slot.token {
+                Some(token) => theme.resolve(token).to_string(),
+                None => slot.default.color.to_string(),
+            };
+            ResolvedStyleSlot {
+                slot: slot.slot,
+                kind: slot.kind,
+                color,
+                width: slot.default.width,
+                opacity: slot.default.opacity,
+                pattern: slot.default.pattern,
+            }
+        })
+        .collect()
+}
+
+/// A per-slot partial override of a [`StyleDefaultMeta`]: a field left
+/// unset falls back to the slot's compiled default. Setters take the
+/// field's actual type (not a further `Option`-of-override), so clearing
+/// an optional field (e.g. dropping `width`) is just passing `None`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SlotOverride {
+    color: Option<&'static str>,
+    width: Option<Option<f64>>,
+    opacity: Option<Option<f64>>,
+    pattern: Option<Option<StrokePattern>>,
+}
+
+impl SlotOverride {
+    pub fn color(mut self, color: &'static str) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn width(mut self, width: Option<f64>) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    pub fn opacity(mut self, opacity: Option<f64>) -> Self {
+        self.opacity = Some(opacity);
+        self
+    }
+
+    pub fn pattern(mut self, pattern: Option<StrokePattern>) -> Self {
+        self.pattern = Some(pattern);
+        self
+    }
+
+    fn apply(self, default: StyleDefaultMeta) -> StyleDefaultMeta {
+        StyleDefaultMeta {
+            color: self.color.unwrap_or(default.color),
+            width: self.width.unwrap_or(default.width),
+            opacity: self.opacity.unwrap_or(default.opacity),
+            pattern: self.pattern.unwrap_or(default.pattern),
+        }
+    }
+}
+
+/// A named table of per-`style_slot` [`SlotOverride`]s, applied on top of
+/// the catalog's compiled [`StyleDefaultMeta`]s by [`resolve_styles`].
+/// [`StyleTheme::with_slot`] can be called again on a built-in theme like
+/// [`StyleTheme::dark`] to layer a further, more specific override on
+/// top of it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StyleTheme {
+    name: &'static str,
+    overrides: BTreeMap<&'static str, SlotOverride>,
+}
+
+impl StyleTheme {
+    pub fn named(name: &'static str) -> Self {
+        Self {
+            name,
+            overrides: BTreeMap::new(),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Overrides `slot` with `overlay` in this theme, replacing any
+    /// override already set for that slot.
+    pub fn with_slot(mut self, slot: &'static str, overlay: SlotOverride) -> Self {
+        self.overrides.insert(slot, overlay);
+        self
+    }
+
+    /// The built-in light theme: every slot keeps its compiled default.
+    pub fn light() -> Self {
+        Self::named("light")
+    }
+
+    /// The built-in dark theme: brightens the handful of slots that read
+    /// poorly on a dark background -- the Ichimoku kumo fill, PSAR and
+    /// other signal markers, and oscillator lines.
+    pub fn dark() -> Self {
+        Self::named("dark")
+            .with_slot(
+                "kumo_fill",
+                SlotOverride::default().color("#94a3b8").opacity(Some(0.25)),
+            )
+            .with_slot("signal_marker", SlotOverride::default().color("#fca5a5"))
+            .with_slot("primary_line", SlotOverride::default().color("#7dd3fc"))
+    }
+}
+
+/// Why [`resolve_styles`] couldn't resolve a style map.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StyleError {
+    /// No catalog indicator has this `id`.
+    UnknownIndicator(String),
+    /// `theme` overrides a `style_slot` this indicator doesn't declare.
+    UnknownSlot {
+        indicator: &'static str,
+        slot: &'static str,
+    },
+}
+
+impl std::fmt::Display for StyleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownIndicator(id) => write!(f, "'{id}' is not a known indicator"),
+            Self::UnknownSlot { indicator, slot } => {
+                write!(f, "'{slot}' is not a style_slot declared by '{indicator}'")
+            }
+        }
+    }
+}
+
+impl std::error::Error for StyleError {}
+
+/// Resolves every `style_slot` `indicator_id` declares against its
+/// compiled [`StyleDefaultMeta`], with `theme`'s per-slot overrides
+/// layered on top. Errors if `indicator_id` isn't in the catalog, or if
+/// `theme` overrides a slot this indicator doesn't declare.
+pub fn resolve_styles(
+    indicator_id: &str,
+    theme: &StyleTheme,
+) -> Result<BTreeMap<&'static str, StyleDefaultMeta>, StyleError> {
+    let meta = super::indicator_catalog()
+        .iter()
+        .find(|meta| meta.id == indicator_id)
+        .ok_or_else(|| StyleError::UnknownIndicator(indicator_id.to_string()))?;
+
+    for slot in theme.overrides.keys().copied() {
+        if !meta.visual.style_slots.iter().any(|declared| declared.slot == slot) {
+            return Err(StyleError::UnknownSlot {
+                indicator: meta.id,
+                slot,
+            });
+        }
+    }
+
+    let resolved = meta
+        .visual
+        .style_slots
+        .iter()
+        .map(|slot| {
+            let style = match theme.overrides.get(slot.slot) {
+                Some(overlay) => overlay.apply(slot.default),
+                None => slot.default,
+            };
+            (slot.slot, style)
+        })
+        .collect();
+    Ok(resolved)
+}
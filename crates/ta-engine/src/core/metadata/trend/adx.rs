@@ -27,4 +27,5 @@ pub const META: IndicatorMeta = IndicatorMeta {
     semantics: SEM_OHLC_PERIOD,
     visual: VIS_ADX,
     runtime_binding: "adx",
+    constraints: &[],
 };
@@ -0,0 +1,19 @@
+use super::*;
+
+pub const META: IndicatorMeta = IndicatorMeta {
+    id: "alma",
+    display_name: "Arnaud Legoux Moving Average",
+    category: "trend",
+    aliases: &[],
+    param_aliases: &[],
+    params: &[P_WINDOW_9, P_OFFSET_0_85, P_SIGMA_6],
+    outputs: &[IndicatorOutputMeta {
+        name: "result",
+        kind: "line",
+        description: "ALMA value",
+    }],
+    semantics: SEM_CLOSE_WINDOW,
+    visual: VIS_PRICE_LINE,
+    runtime_binding: "alma",
+    constraints: &[],
+};
@@ -0,0 +1,19 @@
+use super::*;
+
+pub const META: IndicatorMeta = IndicatorMeta {
+    id: "dema",
+    display_name: "Double Exponential Moving Average",
+    category: "trend",
+    aliases: &[],
+    param_aliases: &[PARAM_ALIAS_LOOKBACK_PERIOD],
+    params: &[P_PERIOD_14],
+    outputs: &[IndicatorOutputMeta {
+        name: "result",
+        kind: "line",
+        description: "DEMA value",
+    }],
+    semantics: SEM_CLOSE_PERIOD,
+    visual: VIS_PRICE_LINE,
+    runtime_binding: "dema",
+    constraints: &[],
+};
@@ -10,10 +10,11 @@ pub const META: IndicatorMeta = IndicatorMeta {
         name: "period",
         kind: IndicatorParamKind::Integer,
         required: false,
-        default: Some("13"),
+        default: Some(IndicatorParamDefault::Integer(13)),
         description: "EMA period",
         min: Some(1.0),
         max: None,
+        allowed: None,
     }],
     outputs: &[
         IndicatorOutputMeta {
@@ -30,4 +31,5 @@ pub const META: IndicatorMeta = IndicatorMeta {
     semantics: SEM_OHLC_PERIOD,
     visual: VIS_ELDER_RAY,
     runtime_binding: "elder_ray",
+    constraints: &[],
 };
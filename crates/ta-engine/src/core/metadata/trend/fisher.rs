@@ -10,10 +10,11 @@ pub const META: IndicatorMeta = IndicatorMeta {
         name: "period",
         kind: IndicatorParamKind::Integer,
         required: false,
-        default: Some("9"),
+        default: Some(IndicatorParamDefault::Integer(9)),
         description: "Lookback period",
         min: Some(1.0),
         max: None,
+        allowed: None,
     }],
     outputs: &[
         IndicatorOutputMeta {
@@ -33,7 +34,9 @@ pub const META: IndicatorMeta = IndicatorMeta {
         lookback_params: &["period"],
         default_lookback: None,
         warmup_policy: "window",
+        source_param: None,
     },
     visual: VIS_FISHER,
     runtime_binding: "fisher",
+    constraints: &[],
 };
@@ -37,4 +37,5 @@ pub const META: IndicatorMeta = IndicatorMeta {
     semantics: SEM_OHLC_PERIOD,
     visual: VIS_ICHIMOKU,
     runtime_binding: "ichimoku",
+    constraints: &[],
 };
@@ -0,0 +1,19 @@
+use super::*;
+
+pub const META: IndicatorMeta = IndicatorMeta {
+    id: "kama",
+    display_name: "Kaufman Adaptive Moving Average",
+    category: "trend",
+    aliases: &[],
+    param_aliases: &[],
+    params: &[P_ER_PERIOD_10, P_FAST_PERIOD_2, P_SLOW_PERIOD_30],
+    outputs: &[IndicatorOutputMeta {
+        name: "result",
+        kind: "line",
+        description: "KAMA value",
+    }],
+    semantics: SEM_CLOSE_ER_PERIOD,
+    visual: VIS_PRICE_LINE,
+    runtime_binding: "kama",
+    constraints: &[],
+};
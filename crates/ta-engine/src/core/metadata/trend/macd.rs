@@ -6,7 +6,13 @@ pub const META: IndicatorMeta = IndicatorMeta {
     category: "trend",
     aliases: &[],
     param_aliases: &[],
-    params: &[P_FAST_PERIOD_12, P_SLOW_PERIOD_26, P_SIGNAL_PERIOD_9],
+    params: &[
+        P_FAST_PERIOD_12,
+        P_SLOW_PERIOD_26,
+        P_SIGNAL_PERIOD_9,
+        P_MA_TYPE_EMA,
+        P_SIGNAL_MA_TYPE_EMA,
+    ],
     outputs: &[
         IndicatorOutputMeta {
             name: "macd",
@@ -27,4 +33,5 @@ pub const META: IndicatorMeta = IndicatorMeta {
     semantics: SEM_CLOSE_FAST_SLOW_SIGNAL,
     visual: VIS_MACD,
     runtime_binding: "macd",
+    constraints: &[],
 };
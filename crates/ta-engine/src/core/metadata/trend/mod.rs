@@ -1,27 +1,41 @@
 use super::*;
 
 mod adx;
+mod alma;
+mod dema;
 mod elder_ray;
 mod ema;
 mod fisher;
 mod hma;
 mod ichimoku;
+mod kama;
 mod macd;
 mod psar;
 mod sma;
 mod supertrend;
+mod t3;
+mod tema;
+mod trima;
 mod wma;
+mod zlema;
 
 pub const ENTRIES: &[IndicatorMeta] = &[
     adx::META,
+    alma::META,
+    dema::META,
     elder_ray::META,
     ema::META,
     fisher::META,
     hma::META,
     ichimoku::META,
+    kama::META,
     macd::META,
     psar::META,
     sma::META,
     supertrend::META,
+    t3::META,
+    tema::META,
+    trima::META,
     wma::META,
+    zlema::META,
 ];
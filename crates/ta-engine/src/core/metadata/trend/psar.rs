@@ -22,4 +22,5 @@ pub const META: IndicatorMeta = IndicatorMeta {
     semantics: SEM_OHLC_PERIOD,
     visual: VIS_PSAR,
     runtime_binding: "psar",
+    constraints: &[],
 };
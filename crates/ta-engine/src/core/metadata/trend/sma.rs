@@ -6,7 +6,7 @@ pub const META: IndicatorMeta = IndicatorMeta {
     category: "trend",
     aliases: &["rolling_mean", "mean"],
     param_aliases: &[PARAM_ALIAS_LOOKBACK_PERIOD],
-    params: &[P_PERIOD_20, P_SOURCE_STR],
+    params: &[P_PERIOD_20, P_SOURCE_STR, P_MA_TYPE_SMA],
     outputs: &[IndicatorOutputMeta {
         name: "result",
         kind: "line",
@@ -15,4 +15,5 @@ pub const META: IndicatorMeta = IndicatorMeta {
     semantics: SEM_CLOSE_PERIOD,
     visual: VIS_PRICE_LINE,
     runtime_binding: "sma",
+    constraints: &[],
 };
@@ -6,7 +6,7 @@ pub const META: IndicatorMeta = IndicatorMeta {
     category: "trend",
     aliases: &[],
     param_aliases: &[],
-    params: &[P_PERIOD_12, P_MULTIPLIER_3],
+    params: &[P_PERIOD_10, P_MULTIPLIER_3],
     outputs: &[
         IndicatorOutputMeta {
             name: "supertrend",
@@ -22,4 +22,5 @@ pub const META: IndicatorMeta = IndicatorMeta {
     semantics: SEM_OHLC_PERIOD,
     visual: VIS_SUPERTREND,
     runtime_binding: "supertrend",
+    constraints: &[],
 };
@@ -6,7 +6,7 @@ pub const META: IndicatorMeta = IndicatorMeta {
     category: "volatility",
     aliases: &[],
     param_aliases: &[PARAM_ALIAS_LOOKBACK_PERIOD],
-    params: &[P_PERIOD_14],
+    params: &[P_PERIOD_14, P_ATR_SMOOTHING],
     outputs: &[IndicatorOutputMeta {
         name: "result",
         kind: "line",
@@ -15,4 +15,5 @@ pub const META: IndicatorMeta = IndicatorMeta {
     semantics: SEM_OHLC_PERIOD,
     visual: VIS_PRICE_LINE,
     runtime_binding: "atr",
+    constraints: &[],
 };
@@ -6,7 +6,7 @@ pub const META: IndicatorMeta = IndicatorMeta {
     category: "volatility",
     aliases: &["bb", "bb_upper", "bb_lower"],
     param_aliases: &[PARAM_ALIAS_LOOKBACK_PERIOD],
-    params: &[P_PERIOD_20, P_STD_DEV_2],
+    params: &[P_PERIOD_20, P_STD_DEV_2, P_MA_TYPE_SMA],
     outputs: &[
         IndicatorOutputMeta {
             name: "upper",
@@ -27,4 +27,5 @@ pub const META: IndicatorMeta = IndicatorMeta {
     semantics: SEM_CLOSE_PERIOD,
     visual: VIS_BBANDS,
     runtime_binding: "bbands",
+    constraints: &[],
 };
@@ -30,7 +30,9 @@ pub const META: IndicatorMeta = IndicatorMeta {
         lookback_params: &["period"],
         default_lookback: None,
         warmup_policy: "window",
+        source_param: None,
     },
     visual: VIS_BBANDS,
     runtime_binding: "donchian",
+    constraints: &[],
 };
@@ -6,7 +6,7 @@ pub const META: IndicatorMeta = IndicatorMeta {
     category: "volatility",
     aliases: &[],
     param_aliases: &[],
-    params: &[P_EMA_PERIOD_20, P_ATR_PERIOD_10, P_MULTIPLIER_2],
+    params: &[P_EMA_PERIOD_20, P_ATR_PERIOD_10, P_MULTIPLIER_2, P_MA_TYPE_EMA, P_ATR_SMOOTHING],
     outputs: &[
         IndicatorOutputMeta {
             name: "upper",
@@ -27,4 +27,5 @@ pub const META: IndicatorMeta = IndicatorMeta {
     semantics: SEM_OHLC_PERIOD,
     visual: VIS_BBANDS,
     runtime_binding: "keltner",
+    constraints: &[],
 };
@@ -4,5 +4,7 @@ mod atr;
 mod bbands;
 mod donchian;
 mod keltner;
+mod squeeze;
 
-pub const ENTRIES: &[IndicatorMeta] = &[atr::META, bbands::META, donchian::META, keltner::META];
+pub const ENTRIES: &[IndicatorMeta] =
+    &[atr::META, bbands::META, donchian::META, keltner::META, squeeze::META];
@@ -0,0 +1,78 @@
+use super::*;
+
+const STYLE_SQUEEZE: &[StyleSlotMeta] = &[
+    StyleSlotMeta {
+        slot: "squeeze_marker",
+        kind: StyleSlotType::Stroke,
+        token: Some(ColorToken::Warning),
+        default: StyleDefaultMeta {
+            color: "#f59e0b",
+            width: Some(1.0),
+            opacity: None,
+            pattern: Some(StrokePattern::Solid),
+        },
+    },
+    StyleSlotMeta {
+        slot: "momentum_hist",
+        kind: StyleSlotType::Fill,
+        token: Some(ColorToken::Neutral),
+        default: StyleDefaultMeta {
+            color: "#94a3b8",
+            width: None,
+            opacity: Some(0.7),
+            pattern: None,
+        },
+    },
+];
+const VIS_SQUEEZE_OUTPUTS: &[OutputVisualMeta] = &[
+    OutputVisualMeta {
+        output: "squeeze",
+        primitive: OutputVisualPrimitive::SignalFlag,
+        style_slot: "squeeze_marker",
+        z_index: 50,
+    },
+    OutputVisualMeta {
+        output: "momentum",
+        primitive: OutputVisualPrimitive::Histogram,
+        style_slot: "momentum_hist",
+        z_index: 20,
+    },
+];
+const VIS_SQUEEZE: IndicatorVisualMeta = IndicatorVisualMeta {
+    pane_hint: IndicatorPaneHint::SeparatePane,
+    scale_group: IndicatorScaleGroup::Oscillator,
+    output_visuals: VIS_SQUEEZE_OUTPUTS,
+    style_slots: STYLE_SQUEEZE,
+};
+
+pub const META: IndicatorMeta = IndicatorMeta {
+    id: "squeeze",
+    display_name: "Bollinger/Keltner Squeeze",
+    category: "volatility",
+    aliases: &[],
+    param_aliases: &[],
+    params: &[P_PERIOD_20, P_STD_DEV_2, P_EMA_PERIOD_20, P_ATR_PERIOD_10, P_MULTIPLIER_2],
+    outputs: &[
+        IndicatorOutputMeta {
+            name: "squeeze",
+            kind: "signal",
+            description: "True while the Bollinger Bands sit inside the Keltner Channel",
+        },
+        IndicatorOutputMeta {
+            name: "momentum",
+            kind: "histogram",
+            description: "Close minus the Donchian midpoint over the same period",
+        },
+    ],
+    semantics: IndicatorSemanticsMeta {
+        required_fields: &["high", "low", "close"],
+        optional_fields: &[],
+        lookback_params: &["period", "ema_period", "atr_period"],
+        default_lookback: None,
+        warmup_policy: "window",
+        source_param: None,
+    },
+    visual: VIS_SQUEEZE,
+    runtime_binding: "squeeze",
+    constraints: &[],
+};
@@ -0,0 +1,26 @@
+use super::*;
+
+pub const META: IndicatorMeta = IndicatorMeta {
+    id: "chaikin_osc",
+    display_name: "Chaikin Oscillator",
+    category: "volume",
+    aliases: &[],
+    param_aliases: &[],
+    params: &[P_FAST_PERIOD_3, P_SLOW_PERIOD_10],
+    outputs: &[IndicatorOutputMeta {
+        name: "result",
+        kind: "line",
+        description: "Chaikin Oscillator value",
+    }],
+    semantics: IndicatorSemanticsMeta {
+        required_fields: &["high", "low", "close", "volume"],
+        optional_fields: &[],
+        lookback_params: &["fast_period", "slow_period"],
+        default_lookback: None,
+        warmup_policy: "window",
+        source_param: None,
+    },
+    visual: VIS_VOLUME_LINE,
+    runtime_binding: "chaikin_osc",
+    constraints: &[],
+};
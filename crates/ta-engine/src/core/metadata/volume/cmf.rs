@@ -18,7 +18,9 @@ pub const META: IndicatorMeta = IndicatorMeta {
         lookback_params: &["period"],
         default_lookback: None,
         warmup_policy: "window",
+        source_param: None,
     },
     visual: VIS_VOLUME_LINE,
     runtime_binding: "cmf",
+    constraints: &[],
 };
@@ -1,8 +1,19 @@
 use super::*;
 
+mod chaikin_osc;
 mod cmf;
 mod klinger_vf;
 mod obv;
 mod vwap;
+mod vwap_anchored;
+mod vwma;
 
-pub const ENTRIES: &[IndicatorMeta] = &[cmf::META, klinger_vf::META, obv::META, vwap::META];
+pub const ENTRIES: &[IndicatorMeta] = &[
+    chaikin_osc::META,
+    cmf::META,
+    klinger_vf::META,
+    obv::META,
+    vwap::META,
+    vwap_anchored::META,
+    vwma::META,
+];
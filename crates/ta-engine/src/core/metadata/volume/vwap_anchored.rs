@@ -0,0 +1,38 @@
+use super::*;
+
+pub const META: IndicatorMeta = IndicatorMeta {
+    id: "vwap_anchored",
+    display_name: "Anchored VWAP",
+    category: "volume",
+    aliases: &[],
+    param_aliases: &[],
+    params: &[P_SESSION_SECONDS_86400, P_STD_DEV_2],
+    outputs: &[
+        IndicatorOutputMeta {
+            name: "vwap",
+            kind: "volume",
+            description: "VWAP value within the current anchored segment",
+        },
+        IndicatorOutputMeta {
+            name: "upper_k",
+            kind: "band_upper",
+            description: "VWAP plus std_dev times the volume-weighted standard deviation",
+        },
+        IndicatorOutputMeta {
+            name: "lower_k",
+            kind: "band_lower",
+            description: "VWAP minus std_dev times the volume-weighted standard deviation",
+        },
+    ],
+    semantics: IndicatorSemanticsMeta {
+        required_fields: &["high", "low", "close", "volume"],
+        optional_fields: &[],
+        lookback_params: &[],
+        default_lookback: Some(1),
+        warmup_policy: "none",
+        source_param: None,
+    },
+    visual: VIS_VWAP_ANCHORED,
+    runtime_binding: "vwap_anchored",
+    constraints: &[],
+};
@@ -0,0 +1,26 @@
+use super::*;
+
+pub const META: IndicatorMeta = IndicatorMeta {
+    id: "vwma",
+    display_name: "Volume-Weighted Moving Average",
+    category: "volume",
+    aliases: &[],
+    param_aliases: &[PARAM_ALIAS_LOOKBACK_PERIOD],
+    params: &[P_PERIOD_20],
+    outputs: &[IndicatorOutputMeta {
+        name: "result",
+        kind: "line",
+        description: "VWMA value",
+    }],
+    semantics: IndicatorSemanticsMeta {
+        required_fields: &["close", "volume"],
+        optional_fields: &[],
+        lookback_params: &["period"],
+        default_lookback: None,
+        warmup_policy: "window",
+        source_param: None,
+    },
+    visual: VIS_PRICE_LINE,
+    runtime_binding: "vwma",
+    constraints: &[],
+};
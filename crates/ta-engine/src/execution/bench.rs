@@ -0,0 +1,375 @@
+//! Workload-driven throughput benchmarking for `execute_plan`.
+//!
+//! A [`BenchWorkload`] loaded from JSON describes a dataset partition shape,
+//! a row count, and a list of [`KernelStepRequest`]s. [`run_bench_workload`]
+//! generates deterministic synthetic OHLCV for that shape -- so a benchmark
+//! is reproducible without a checked-in dataset -- steps each request's
+//! kernel through every row, and reports per-`node_id` step latency
+//! percentiles alongside overall wall time and ticks/sec.
+//! [`diff_bench_reports`] compares a run against a prior baseline so a
+//! regression in one [`KernelId`] (e.g. `Rsi` or `Obv`) shows up without
+//! having to eyeball every node's numbers by hand.
+
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+use serde_json::{Map, Value};
+
+use super::incremental::backend::{IncrementalBackend, KernelStepRequest, StepInputSource};
+use super::incremental::contracts::IncrementalValue;
+use super::incremental::kernel_registry::KernelId;
+use crate::dataset::{self, DatasetPartitionKey};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchWorkload {
+    pub partition_key: DatasetPartitionKey,
+    pub row_count: usize,
+    pub seed: u64,
+    pub requests: Vec<KernelStepRequest>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BenchWorkloadError {
+    MissingField(&'static str),
+    WrongType(&'static str),
+    UnsupportedKernelId(String),
+}
+
+impl std::fmt::Display for BenchWorkloadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingField(field) => write!(f, "missing field: {field}"),
+            Self::WrongType(field) => write!(f, "wrong type for field: {field}"),
+            Self::UnsupportedKernelId(id) => write!(f, "unsupported kernel_id: {id}"),
+        }
+    }
+}
+
+impl std::error::Error for BenchWorkloadError {}
+
+/// Parses a workload JSON object shaped like:
+/// `{"symbol": "BTCUSDT", "timeframe": "1m", "source": "ohlcv",
+/// "row_count": 10000, "seed": 1, "requests": [{"node_id": 1,
+/// "kernel_id": "rsi", "input_field": "close", "kwargs": {"period": 14.0}}]}`.
+pub fn parse_bench_workload(json: &Value) -> Result<BenchWorkload, BenchWorkloadError> {
+    let obj = json.as_object().ok_or(BenchWorkloadError::WrongType("root"))?;
+
+    let partition_key = DatasetPartitionKey {
+        symbol: str_field(obj, "symbol")?.to_string(),
+        timeframe: str_field(obj, "timeframe")?.to_string(),
+        source: str_field(obj, "source")?.to_string(),
+    };
+    let row_count = obj
+        .get("row_count")
+        .and_then(Value::as_u64)
+        .ok_or(BenchWorkloadError::MissingField("row_count"))? as usize;
+    let seed = obj.get("seed").and_then(Value::as_u64).unwrap_or(1);
+
+    let requests_json = obj
+        .get("requests")
+        .and_then(Value::as_array)
+        .ok_or(BenchWorkloadError::MissingField("requests"))?;
+
+    let mut requests = Vec::with_capacity(requests_json.len());
+    for request in requests_json {
+        let request = request
+            .as_object()
+            .ok_or(BenchWorkloadError::WrongType("requests[]"))?;
+        let node_id = request
+            .get("node_id")
+            .and_then(Value::as_u64)
+            .ok_or(BenchWorkloadError::MissingField("requests[].node_id"))? as u32;
+        let kernel_name = str_field(request, "kernel_id")?;
+        let kernel_id = KernelId::from_name(kernel_name)
+            .ok_or_else(|| BenchWorkloadError::UnsupportedKernelId(kernel_name.to_string()))?;
+        let input_field = str_field(request, "input_field")?.to_string();
+        let kwargs = request
+            .get("kwargs")
+            .and_then(Value::as_object)
+            .map(|kwargs| {
+                kwargs
+                    .iter()
+                    .map(|(k, v)| (k.clone(), bench_kwarg_from_json(v)))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        requests.push(KernelStepRequest {
+            node_id,
+            kernel_id,
+            input: StepInputSource::TickField(input_field),
+            kwargs,
+        });
+    }
+
+    Ok(BenchWorkload {
+        partition_key,
+        row_count,
+        seed,
+        requests,
+    })
+}
+
+fn str_field<'a>(
+    obj: &'a Map<String, Value>,
+    field: &'static str,
+) -> Result<&'a str, BenchWorkloadError> {
+    obj.get(field)
+        .and_then(Value::as_str)
+        .ok_or(BenchWorkloadError::MissingField(field))
+}
+
+fn bench_kwarg_from_json(value: &Value) -> IncrementalValue {
+    match value {
+        Value::Number(n) => n
+            .as_f64()
+            .map_or(IncrementalValue::Null, IncrementalValue::Number),
+        Value::Bool(b) => IncrementalValue::Bool(*b),
+        Value::String(s) => IncrementalValue::Text(s.clone()),
+        _ => IncrementalValue::Null,
+    }
+}
+
+/// Deterministic xorshift64-based OHLCV generator -- no external `rand`
+/// dependency, and the same `(row_count, seed)` always reproduces the same
+/// series bar-for-bar, so a benchmark run can be repeated and diffed.
+fn synthetic_ohlcv(
+    row_count: usize,
+    seed: u64,
+) -> (Vec<i64>, Vec<f64>, Vec<f64>, Vec<f64>, Vec<f64>, Vec<f64>) {
+    let mut state = seed.max(1);
+    let mut next_unit = || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state >> 11) as f64 / (1u64 << 53) as f64
+    };
+
+    let mut timestamps = Vec::with_capacity(row_count);
+    let mut open = Vec::with_capacity(row_count);
+    let mut high = Vec::with_capacity(row_count);
+    let mut low = Vec::with_capacity(row_count);
+    let mut close = Vec::with_capacity(row_count);
+    let mut volume = Vec::with_capacity(row_count);
+
+    let mut price = 100.0;
+    for i in 0..row_count {
+        let drift = (next_unit() - 0.5) * 2.0;
+        let bar_open = price;
+        price = (price + drift).max(0.01);
+        let bar_close = price;
+        let spread = next_unit() * 0.5 + 0.01;
+
+        timestamps.push(i as i64);
+        open.push(bar_open);
+        high.push(bar_open.max(bar_close) + spread);
+        low.push(bar_open.min(bar_close) - spread);
+        close.push(bar_close);
+        volume.push(next_unit() * 1_000.0 + 1.0);
+    }
+
+    (timestamps, open, high, low, close, volume)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NodeLatencyStats {
+    pub node_id: u32,
+    pub kernel_id: KernelId,
+    pub samples: usize,
+    pub p50_nanos: u64,
+    pub p90_nanos: u64,
+    pub p99_nanos: u64,
+    pub max_nanos: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchReport {
+    pub row_count: usize,
+    pub wall_nanos: u64,
+    pub ticks_per_sec: f64,
+    pub node_stats: Vec<NodeLatencyStats>,
+}
+
+/// Runs `workload` against freshly generated synthetic OHLCV, timing each
+/// request's own `step` call rather than a whole tick at once, so
+/// [`NodeLatencyStats`] reflects one `node_id`'s own cost even when several
+/// requests share a tick.
+pub fn run_bench_workload(workload: &BenchWorkload) -> BenchReport {
+    let dataset_id = dataset::create_dataset();
+    let (timestamps, open, high, low, close, volume) =
+        synthetic_ohlcv(workload.row_count, workload.seed);
+    dataset::append_ohlcv(
+        dataset_id,
+        workload.partition_key.clone(),
+        &timestamps,
+        &open,
+        &high,
+        &low,
+        &close,
+        &volume,
+    )
+    .expect("synthetic ohlcv always satisfies dataset invariants");
+
+    let mut backend = IncrementalBackend::default();
+    let mut samples: BTreeMap<u32, Vec<u64>> = workload
+        .requests
+        .iter()
+        .map(|req| (req.node_id, Vec::with_capacity(workload.row_count)))
+        .collect();
+
+    let wall_start = Instant::now();
+    for idx in 0..workload.row_count {
+        let tick = BTreeMap::from([
+            ("open".to_string(), IncrementalValue::Number(open[idx])),
+            ("high".to_string(), IncrementalValue::Number(high[idx])),
+            ("low".to_string(), IncrementalValue::Number(low[idx])),
+            ("close".to_string(), IncrementalValue::Number(close[idx])),
+            ("volume".to_string(), IncrementalValue::Number(volume[idx])),
+        ]);
+
+        for req in &workload.requests {
+            let step_start = Instant::now();
+            backend
+                .step(0, idx as u64 + 1, std::slice::from_ref(req), &tick)
+                .expect("bench backend has no quota set, so step never fails");
+            samples
+                .get_mut(&req.node_id)
+                .expect("every request's node_id was seeded into samples above")
+                .push(step_start.elapsed().as_nanos() as u64);
+        }
+    }
+    let wall_nanos = wall_start.elapsed().as_nanos() as u64;
+
+    let node_stats = workload
+        .requests
+        .iter()
+        .map(|req| {
+            let mut node_samples = samples.remove(&req.node_id).unwrap_or_default();
+            node_samples.sort_unstable();
+            NodeLatencyStats {
+                node_id: req.node_id,
+                kernel_id: req.kernel_id,
+                samples: node_samples.len(),
+                p50_nanos: percentile(&node_samples, 0.50),
+                p90_nanos: percentile(&node_samples, 0.90),
+                p99_nanos: percentile(&node_samples, 0.99),
+                max_nanos: node_samples.last().copied().unwrap_or(0),
+            }
+        })
+        .collect();
+
+    let ticks_per_sec = if wall_nanos == 0 {
+        0.0
+    } else {
+        (workload.row_count as f64) / (wall_nanos as f64 / 1_000_000_000.0)
+    };
+
+    BenchReport {
+        row_count: workload.row_count,
+        wall_nanos,
+        ticks_per_sec,
+        node_stats,
+    }
+}
+
+fn percentile(sorted_samples: &[u64], p: f64) -> u64 {
+    if sorted_samples.is_empty() {
+        return 0;
+    }
+    let rank = (((sorted_samples.len() - 1) as f64) * p).round() as usize;
+    sorted_samples[rank]
+}
+
+pub fn encode_bench_report_to_json(report: &BenchReport) -> Value {
+    let node_stats = report
+        .node_stats
+        .iter()
+        .map(|stats| {
+            Value::Object(Map::from_iter([
+                ("node_id".to_string(), Value::from(stats.node_id)),
+                (
+                    "kernel_id".to_string(),
+                    Value::from(stats.kernel_id.as_str()),
+                ),
+                ("samples".to_string(), Value::from(stats.samples)),
+                ("p50_nanos".to_string(), Value::from(stats.p50_nanos)),
+                ("p90_nanos".to_string(), Value::from(stats.p90_nanos)),
+                ("p99_nanos".to_string(), Value::from(stats.p99_nanos)),
+                ("max_nanos".to_string(), Value::from(stats.max_nanos)),
+            ]))
+        })
+        .collect();
+
+    Value::Object(Map::from_iter([
+        ("row_count".to_string(), Value::from(report.row_count)),
+        ("wall_nanos".to_string(), Value::from(report.wall_nanos)),
+        (
+            "ticks_per_sec".to_string(),
+            Value::from(report.ticks_per_sec),
+        ),
+        ("node_stats".to_string(), Value::Array(node_stats)),
+    ]))
+}
+
+/// How much slower a node's p99 (or the run's overall ticks/sec) must get
+/// relative to the baseline before [`diff_bench_reports`] flags it as a
+/// regression, rather than ordinary run-to-run timing noise.
+const REGRESSION_THRESHOLD: f64 = 1.2;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NodeLatencyDiff {
+    pub node_id: u32,
+    pub kernel_id: KernelId,
+    pub baseline_p99_nanos: u64,
+    pub current_p99_nanos: u64,
+    pub regressed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchReportDiff {
+    pub ticks_per_sec_ratio: f64,
+    pub throughput_regressed: bool,
+    pub node_diffs: Vec<NodeLatencyDiff>,
+}
+
+/// Compares `current` against `baseline`, flagging a node whose p99 grew by
+/// more than [`REGRESSION_THRESHOLD`] and an overall run whose ticks/sec
+/// dropped by the same margin. Nodes present in only one of the two reports
+/// (e.g. a workload that added or dropped a request) are skipped rather
+/// than guessed at.
+pub fn diff_bench_reports(baseline: &BenchReport, current: &BenchReport) -> BenchReportDiff {
+    let baseline_by_node: BTreeMap<u32, &NodeLatencyStats> = baseline
+        .node_stats
+        .iter()
+        .map(|stats| (stats.node_id, stats))
+        .collect();
+
+    let node_diffs = current
+        .node_stats
+        .iter()
+        .filter_map(|current_stats| {
+            let baseline_stats = baseline_by_node.get(&current_stats.node_id)?;
+            let regressed = (current_stats.p99_nanos as f64)
+                > (baseline_stats.p99_nanos as f64) * REGRESSION_THRESHOLD;
+            Some(NodeLatencyDiff {
+                node_id: current_stats.node_id,
+                kernel_id: current_stats.kernel_id,
+                baseline_p99_nanos: baseline_stats.p99_nanos,
+                current_p99_nanos: current_stats.p99_nanos,
+                regressed,
+            })
+        })
+        .collect();
+
+    let ticks_per_sec_ratio = if baseline.ticks_per_sec == 0.0 {
+        1.0
+    } else {
+        current.ticks_per_sec / baseline.ticks_per_sec
+    };
+
+    BenchReportDiff {
+        ticks_per_sec_ratio,
+        throughput_regressed: ticks_per_sec_ratio < (1.0 / REGRESSION_THRESHOLD),
+        node_diffs,
+    }
+}
@@ -1,22 +1,76 @@
 use std::collections::BTreeMap;
 
 use super::call_step::{eval_call_step, initialize_kernel_state, KernelRuntimeState};
-use super::contracts::{IncrementalValue, RuntimeSnapshot};
+use super::codec::KernelStateCodecError;
+use super::contracts::{incremental_blob_bytes, BackendCounters, IncrementalValue, RuntimeSnapshot};
 use super::graph_exec;
+use super::graph_order::DependencyGraph;
+use super::int_map::IntMap;
 use super::kernel_registry::KernelId;
+use super::migrations::MigrationError;
 use super::payload_parse;
-use super::state::NodeRuntimeState;
+use super::state::{NodeProfile, NodeRuntimeState};
 use super::state_codec;
-use super::store::RuntimeStateStore;
-use crate::contracts::RustExecutionPayload;
+use super::store::{RuntimeStateStore, StateStore};
+use super::vector_clock;
+use crate::contracts::{RustExecutionPartition, RustExecutionPayload};
 use crate::dataset::{self, DatasetId, DatasetPartitionKey};
 use thiserror::Error;
 
+/// The stream id [`execute_plan_with_stats`] steps under -- there is only
+/// ever one event source (the partition's own OHLCV rows) in a single
+/// synchronous run, so every tick's causal token is attached to this one
+/// stream rather than threading a caller-supplied id through a plan that
+/// never replays overlapping ranges.
+const SINGLE_PARTITION_STREAM: u32 = 0;
+
+/// Which limit a [`BackendQuota`] check tripped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuotaKind {
+    /// The backend would have started tracking more than `max_nodes`
+    /// distinct `node_id`s.
+    Nodes,
+    /// Cumulative ticks processed across every node would have exceeded
+    /// `max_total_ticks`.
+    TotalTicks,
+    /// One node's output series would have grown past `max_output_len`.
+    OutputLen,
+}
+
+/// Resource limits for one [`IncrementalBackend`] or [`execute_plan`] run,
+/// checked inside `step`/`execute_plan_with_stats`. Following Garage's
+/// bucket-quota work, every limit defaults to unbounded (`None`) so running
+/// a plan without a quota behaves exactly as before -- a caller running
+/// untrusted or very large plans opts in by setting one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BackendQuota {
+    /// Caps how many distinct `node_id`s this backend will hold state for.
+    pub max_nodes: Option<usize>,
+    /// Caps the cumulative ticks processed across every node.
+    pub max_total_ticks: Option<u64>,
+    /// Caps how many values `execute_plan_with_stats_and_quota` accumulates
+    /// per output series.
+    pub max_output_len: Option<usize>,
+}
+
+/// Where a [`KernelStepRequest`] reads its per-tick input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StepInputSource {
+    /// One of the tick's own fields (`open`/`high`/`low`/`close`/`volume`,
+    /// or any other key a caller populates the tick `BTreeMap` with).
+    TickField(String),
+    /// Another request's output from the same `step` call, keyed by its
+    /// `node_id` -- lets one kernel consume another's computed value (an
+    /// EMA of an RSI, a cross of two moving averages) instead of only the
+    /// raw tick.
+    NodeOutput(u32),
+}
+
 #[derive(Debug, Clone)]
 pub struct KernelStepRequest {
     pub node_id: u32,
     pub kernel_id: KernelId,
-    pub input_field: String,
+    pub input: StepInputSource,
     pub kwargs: BTreeMap<String, IncrementalValue>,
 }
 
@@ -27,93 +81,271 @@ pub struct ExecutePlanPayload {
     pub requests: Vec<KernelStepRequest>,
 }
 
+/// Steps a plan's kernels tick by tick, keeping per-node runtime state in
+/// `S`. Generic over [`StateStore`] so a caller can swap the default
+/// in-memory [`RuntimeStateStore`] for a durable adapter (e.g.
+/// `store::FileStateStore`) and resume a long `replay`/`execute_plan` run
+/// across a process restart instead of losing `call_states` and node
+/// snapshots when it exits. `call_states` is an [`IntMap`] rather than a
+/// `BTreeMap`: a plan's node ids are small and densely assigned, so a long
+/// `replay` touching every node on every tick gets O(1) `remove`/`insert`
+/// instead of repeated tree-walk churn -- the node store behind `S` uses the
+/// same structure for the same reason.
 #[derive(Debug, Clone, Default)]
-pub struct IncrementalBackend {
-    store: RuntimeStateStore,
-    call_states: BTreeMap<u32, KernelRuntimeState>,
+pub struct IncrementalBackend<S: StateStore = RuntimeStateStore> {
+    store: S,
+    call_states: IntMap<KernelRuntimeState>,
+    profiling_enabled: bool,
+    profiles: BTreeMap<u32, NodeProfile>,
+    quota: BackendQuota,
+    total_ticks_processed: u64,
 }
 
-impl IncrementalBackend {
+impl<S: StateStore> IncrementalBackend<S> {
     pub fn initialize(&mut self) {
         self.store.initialize();
         self.call_states.clear();
+        self.profiles.clear();
+        self.total_ticks_processed = 0;
+    }
+
+    /// Turns on per-node cost accounting for this backend -- cumulative
+    /// ticks, time spent in each kernel's update, peak `state_blob` size,
+    /// and how often a node's kernel state was recreated from scratch
+    /// rather than continued. Off by default since it adds a timer read
+    /// and a blob-size walk to every `step` call.
+    pub fn enable_profiling(&mut self) {
+        self.profiling_enabled = true;
+    }
+
+    pub fn profiling_enabled(&self) -> bool {
+        self.profiling_enabled
+    }
+
+    pub fn profile(&self) -> &BTreeMap<u32, NodeProfile> {
+        &self.profiles
+    }
+
+    /// Sets the resource limits `step` enforces from here on. Unbounded
+    /// (`BackendQuota::default()`) until called.
+    pub fn set_quota(&mut self, quota: BackendQuota) {
+        self.quota = quota;
+    }
+
+    pub fn quota(&self) -> BackendQuota {
+        self.quota
+    }
+
+    /// Aggregate resource usage so far -- active nodes, cumulative ticks,
+    /// and total `state_blob` bytes -- for operators to watch against
+    /// [`BackendQuota`] without waiting for a limit to actually trip.
+    pub fn counters(&self) -> BackendCounters {
+        self.store.snapshot().counters()
     }
 
+    /// Steps `requests` against one tick carrying `event_index` from
+    /// `stream_id`. Each node's stored [`vector_clock::VectorClock`] records
+    /// the highest `event_index` already applied per stream; if it already
+    /// dominates this tick's `(stream_id, event_index)`, the tick is a
+    /// replayed duplicate (e.g. two overlapping crash-recovery ranges, or
+    /// two partitions' streams merged into one backend) and is skipped as a
+    /// no-op, re-emitting the node's last output instead of recomputing it.
+    /// This makes `replay`ing an overlapping or out-of-order range of events
+    /// idempotent and lets two independently advanced backends be merged by
+    /// joining their clocks, per node, with [`vector_clock::merge`].
+    ///
+    /// `requests` don't have to be in dependency order: a
+    /// [`StepInputSource::NodeOutput`] edge from one request to another
+    /// builds a dependency DAG (via [`DependencyGraph::topological_order`],
+    /// Kahn's algorithm) that's re-sorted every call, so a node that reads
+    /// another node's output always evaluates after it. `outputs` itself
+    /// doubles as the per-step scratch map a `NodeOutput` reference reads
+    /// from, since it's already populated in evaluation order as each node
+    /// finishes.
+    ///
+    /// Requests that share the same `(kernel_id, input field, kwargs)` --
+    /// several signals all keyed off `rsi(close, 14)`, say -- are
+    /// canonicalized via [`canonical_node_ids`] and evaluated once per tick:
+    /// every node still gets its own `outputs` entry and `NodeRuntimeState`,
+    /// but only the group's canonical node (its smallest node id) actually
+    /// runs `eval_call_step`, so `call_states` holds one entry per unique
+    /// computation rather than one per node.
     pub fn step(
         &mut self,
+        stream_id: u32,
         event_index: u64,
         requests: &[KernelStepRequest],
         tick: &BTreeMap<String, IncrementalValue>,
-    ) -> BTreeMap<u32, IncrementalValue> {
-        self.store.set_last_event_index(event_index);
+    ) -> Result<BTreeMap<u32, IncrementalValue>, ExecutePlanError> {
+        self.store
+            .set_last_event_index(event_index)
+            .expect("state store write failed");
         let mut outputs = BTreeMap::new();
 
-        for req in requests {
+        let by_id: BTreeMap<u32, &KernelStepRequest> =
+            requests.iter().map(|req| (req.node_id, req)).collect();
+        let order = step_order(requests)?;
+        let canonical_of = canonical_node_ids(requests);
+
+        for node_id in order {
+            let req = by_id[&node_id];
+            let existing = self.store.get_node(req.node_id);
+            if let Some(existing) = &existing {
+                if vector_clock::dominates(&existing.clock, stream_id, event_index) {
+                    outputs.insert(req.node_id, existing.last_output.clone());
+                    continue;
+                }
+            }
+
+            if let Some(max_nodes) = self.quota.max_nodes {
+                if existing.is_none() && self.store.snapshot().nodes.len() >= max_nodes {
+                    return Err(ExecutePlanError::QuotaExceeded {
+                        kind: QuotaKind::Nodes,
+                        limit: max_nodes as u64,
+                    });
+                }
+            }
+            if let Some(max_total_ticks) = self.quota.max_total_ticks {
+                if self.total_ticks_processed >= max_total_ticks {
+                    return Err(ExecutePlanError::QuotaExceeded {
+                        kind: QuotaKind::TotalTicks,
+                        limit: max_total_ticks,
+                    });
+                }
+            }
+
+            let canonical_id = canonical_of[&req.node_id];
+            if canonical_id != req.node_id {
+                // Same `(kernel_id, input field, kwargs)` as `canonical_id`,
+                // already evaluated earlier in this tick's topological order
+                // (canonical ids are the smallest node id in their group, and
+                // Kahn's algorithm breaks ties by ascending id) -- reuse its
+                // output instead of running `eval_call_step` a second time.
+                // `call_states` never gets an entry for `req.node_id`, so a
+                // group of N duplicate nodes still costs one kernel state.
+                let out = outputs.get(&canonical_id).cloned().unwrap_or_else(|| {
+                    panic!("canonical node {canonical_id} must evaluate before its duplicate {node_id} in topological order")
+                });
+                let ticks_processed = existing.as_ref().map(|s| s.ticks_processed + 1).unwrap_or(1);
+                let mut clock = existing.map(|s| s.clock).unwrap_or_default();
+                vector_clock::record(&mut clock, stream_id, event_index);
+                self.store
+                    .upsert_node(NodeRuntimeState {
+                        node_id: req.node_id,
+                        ticks_processed,
+                        last_output: out.clone(),
+                        state_blob: BTreeMap::new(),
+                        clock,
+                    })
+                    .expect("state store write failed");
+                self.total_ticks_processed += 1;
+                outputs.insert(req.node_id, out);
+                continue;
+            }
+
+            let had_existing_state = self.call_states.contains_key(req.node_id);
             let state = self
                 .call_states
-                .remove(&req.node_id)
+                .remove(req.node_id)
                 .unwrap_or_else(|| initialize_kernel_state(req.kernel_id, &req.kwargs));
 
-            let input = tick
-                .get(&req.input_field)
-                .cloned()
-                .unwrap_or(IncrementalValue::Null);
+            let input = match &req.input {
+                StepInputSource::TickField(field) => tick.get(field).cloned(),
+                StepInputSource::NodeOutput(node_id) => outputs.get(node_id).cloned(),
+            }
+            .unwrap_or(IncrementalValue::Null);
 
+            let started_at = self.profiling_enabled.then(std::time::Instant::now);
             let (new_state, out) = eval_call_step(req.kernel_id, state, input, tick);
             self.call_states.insert(req.node_id, new_state);
             let state_blob = self
                 .call_states
-                .get(&req.node_id)
+                .get(req.node_id)
                 .map(state_codec::encode_kernel_state)
                 .unwrap_or_default();
 
-            let ticks_processed = self
-                .store
-                .get_node(req.node_id)
-                .map(|s| s.ticks_processed + 1)
-                .unwrap_or(1);
+            if let Some(started_at) = started_at {
+                let elapsed_nanos = started_at.elapsed().as_nanos() as u64;
+                let blob_bytes = incremental_blob_bytes(&state_blob);
+                let profile = self.profiles.entry(req.node_id).or_insert_with(|| NodeProfile {
+                    node_id: req.node_id,
+                    ..Default::default()
+                });
+                profile.ticks_processed += 1;
+                profile.total_nanos += elapsed_nanos;
+                profile.peak_state_blob_bytes = profile.peak_state_blob_bytes.max(blob_bytes);
+                if !had_existing_state {
+                    profile.recompute_count += 1;
+                }
+            }
+
+            let ticks_processed = existing.as_ref().map(|s| s.ticks_processed + 1).unwrap_or(1);
+            let mut clock = existing.map(|s| s.clock).unwrap_or_default();
+            vector_clock::record(&mut clock, stream_id, event_index);
 
-            self.store.upsert_node(NodeRuntimeState {
-                node_id: req.node_id,
-                ticks_processed,
-                last_output: out.clone(),
-                state_blob,
-            });
+            self.store
+                .upsert_node(NodeRuntimeState {
+                    node_id: req.node_id,
+                    ticks_processed,
+                    last_output: out.clone(),
+                    state_blob,
+                    clock,
+                })
+                .expect("state store write failed");
 
+            self.total_ticks_processed += 1;
             outputs.insert(req.node_id, out);
         }
 
-        outputs
+        Ok(outputs)
     }
 
     pub fn snapshot(&self) -> RuntimeSnapshot {
         self.store.snapshot()
     }
 
-    pub fn restore(&mut self, snapshot: RuntimeSnapshot) -> Result<(), &'static str> {
+    pub fn restore(&mut self, snapshot: RuntimeSnapshot) -> Result<(), RestoreError> {
         self.store.restore(snapshot.clone())?;
         self.call_states.clear();
         for (node_id, node) in snapshot.nodes {
-            if let Some(state) = state_codec::decode_kernel_state(&node.state_blob) {
+            if let Some(state) = state_codec::decode_kernel_state(&node.state_blob)? {
                 self.call_states.insert(node_id, state);
             }
         }
         Ok(())
     }
 
+    /// Steps `events` one by one as consecutive indices starting at
+    /// `start_event_index` from `stream_id`. Callers resuming a backend
+    /// across multiple `replay` calls (e.g. warm up, snapshot, restore
+    /// elsewhere, then continue) pass the next unused index rather than
+    /// always restarting at the stream's beginning, which -- now that
+    /// `step` checks each node's [`vector_clock::VectorClock`] -- would
+    /// otherwise make every event in the new call look like an
+    /// already-applied duplicate.
     pub fn replay(
         &mut self,
+        stream_id: u32,
+        start_event_index: u64,
         requests: &[KernelStepRequest],
         events: &[BTreeMap<String, IncrementalValue>],
-    ) -> Vec<BTreeMap<u32, IncrementalValue>> {
+    ) -> Result<Vec<BTreeMap<u32, IncrementalValue>>, ExecutePlanError> {
         events
             .iter()
             .enumerate()
-            .map(|(idx, tick)| self.step(idx as u64 + 1, requests, tick))
+            .map(|(idx, tick)| self.step(stream_id, start_event_index + idx as u64, requests, tick))
             .collect()
     }
 }
 
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum RestoreError {
+    #[error(transparent)]
+    Migration(#[from] MigrationError),
+    #[error(transparent)]
+    KernelState(#[from] KernelStateCodecError),
+}
+
 #[derive(Debug, Error, PartialEq, Eq)]
 pub enum ExecutePlanError {
     #[error(transparent)]
@@ -136,6 +368,103 @@ pub enum ExecutePlanError {
     InvalidPayload(String),
     #[error("unsupported kernel_id in payload: {0}")]
     UnsupportedKernelId(String),
+    #[error("backend quota exceeded: {kind:?} limit of {limit} reached")]
+    QuotaExceeded { kind: QuotaKind, limit: u64 },
+    #[error("dependency cycle detected among node-to-node step requests: {0:?}")]
+    CycleDetected(Vec<u32>),
+}
+
+/// Builds the `StepInputSource::NodeOutput` dependency DAG over `requests`
+/// and returns a topological evaluation order (Kahn's algorithm, via
+/// [`DependencyGraph::topological_order`]) so a node is always stepped
+/// after every node it reads from.
+fn step_order(requests: &[KernelStepRequest]) -> Result<Vec<u32>, ExecutePlanError> {
+    let nodes: Vec<u32> = requests.iter().map(|req| req.node_id).collect();
+    let mut edges: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+    for req in requests {
+        if let StepInputSource::NodeOutput(input_node_id) = &req.input {
+            edges.entry(req.node_id).or_default().push(*input_node_id);
+        }
+    }
+
+    DependencyGraph::new(nodes, edges)
+        .topological_order()
+        .map_err(|super::graph_order::GraphOrderError::CycleDetected(nodes)| {
+            ExecutePlanError::CycleDetected(nodes)
+        })
+}
+
+/// Maps every request's node id to its canonical node id within `requests`:
+/// the smallest node id among every request sharing its `tick_signature`
+/// (or itself, for a singleton or a [`StepInputSource::NodeOutput`] request,
+/// which is never grouped -- same reasoning as `dedupe_requests` not
+/// aliasing those for `execute_plan`: a request another node depends on by
+/// id must keep evaluating under its own id). Canonical ids are always the
+/// smallest in their group so [`step_order`]'s ascending-id tie-break
+/// guarantees a duplicate's canonical has already run by the time `step`
+/// reaches it.
+fn canonical_node_ids(requests: &[KernelStepRequest]) -> BTreeMap<u32, u32> {
+    let mut min_id_by_signature: BTreeMap<String, u32> = BTreeMap::new();
+    let mut signature_by_node: BTreeMap<u32, String> = BTreeMap::new();
+    for req in requests {
+        if let Some(signature) = tick_signature(req) {
+            min_id_by_signature
+                .entry(signature.clone())
+                .and_modify(|id| *id = (*id).min(req.node_id))
+                .or_insert(req.node_id);
+            signature_by_node.insert(req.node_id, signature);
+        }
+    }
+
+    requests
+        .iter()
+        .map(|req| {
+            let canonical_id = signature_by_node
+                .get(&req.node_id)
+                .and_then(|signature| min_id_by_signature.get(signature))
+                .copied()
+                .unwrap_or(req.node_id);
+            (req.node_id, canonical_id)
+        })
+        .collect()
+}
+
+/// `req`'s per-tick structural signature: `kernel_id` + input field +
+/// a stable (sorted-key) rendering of `kwargs`. Two requests reading the
+/// same tick with the same signature are guaranteed to produce the same
+/// output this step, so one of them can stand in for the other. Unlike
+/// `request_signature` (used by `execute_plan`'s batch-over-dataset dedup),
+/// this doesn't need a column fingerprint: `step` only ever sees one tick,
+/// so the field name alone identifies which value is read. Returns `None`
+/// for a [`StepInputSource::NodeOutput`] request, which is never grouped.
+fn tick_signature(req: &KernelStepRequest) -> Option<String> {
+    let StepInputSource::TickField(field) = &req.input else {
+        return None;
+    };
+
+    let mut signature = String::new();
+    signature.push_str(req.kernel_id.as_str());
+    signature.push(';');
+    signature.push_str(field);
+    signature.push(';');
+    for (key, value) in &req.kwargs {
+        signature.push_str(key);
+        signature.push('=');
+        signature.push_str(&format!("{value:?}"));
+        signature.push(',');
+    }
+    Some(signature)
+}
+
+/// Cheap, allocation-light counters about one [`execute_plan`] invocation,
+/// surfaced so callers (and eventually users, over a wide feature set) can
+/// see how much a plan's duplicate sub-expressions actually saved.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PlanExecutionStats {
+    /// How many of `requests` were recognized as duplicates of an
+    /// already-computed request and served from the in-run cache instead of
+    /// being stepped through their own kernel state machine.
+    pub cache_hits: usize,
 }
 
 pub fn execute_plan(
@@ -143,6 +472,43 @@ pub fn execute_plan(
     partition_key: &DatasetPartitionKey,
     requests: &[KernelStepRequest],
 ) -> Result<BTreeMap<u32, Vec<IncrementalValue>>, ExecutePlanError> {
+    execute_plan_with_quota(dataset_id, partition_key, requests, BackendQuota::default())
+}
+
+/// Same as [`execute_plan`], but also returns [`PlanExecutionStats`] --
+/// split out as its own entry point so callers that don't care about the
+/// stats (the rest of this crate) don't have to destructure a tuple.
+pub fn execute_plan_with_stats(
+    dataset_id: DatasetId,
+    partition_key: &DatasetPartitionKey,
+    requests: &[KernelStepRequest],
+) -> Result<(BTreeMap<u32, Vec<IncrementalValue>>, PlanExecutionStats), ExecutePlanError> {
+    execute_plan_with_stats_and_quota(dataset_id, partition_key, requests, BackendQuota::default())
+}
+
+/// Same as [`execute_plan`], but stops as soon as `quota` is tripped
+/// instead of running an untrusted or unexpectedly large plan to
+/// completion.
+pub fn execute_plan_with_quota(
+    dataset_id: DatasetId,
+    partition_key: &DatasetPartitionKey,
+    requests: &[KernelStepRequest],
+    quota: BackendQuota,
+) -> Result<BTreeMap<u32, Vec<IncrementalValue>>, ExecutePlanError> {
+    execute_plan_with_stats_and_quota(dataset_id, partition_key, requests, quota)
+        .map(|(outputs, _stats)| outputs)
+}
+
+/// Same as [`execute_plan_with_stats`], but enforces `quota` -- see
+/// [`BackendQuota`] -- while stepping the plan, returning
+/// [`ExecutePlanError::QuotaExceeded`] as soon as a limit is reached
+/// rather than running to completion and reporting it afterwards.
+pub fn execute_plan_with_stats_and_quota(
+    dataset_id: DatasetId,
+    partition_key: &DatasetPartitionKey,
+    requests: &[KernelStepRequest],
+    quota: BackendQuota,
+) -> Result<(BTreeMap<u32, Vec<IncrementalValue>>, PlanExecutionStats), ExecutePlanError> {
     let record = dataset::get_dataset(dataset_id)?;
     let partition = record.partitions.get(partition_key).ok_or_else(|| {
         ExecutePlanError::PartitionNotFound {
@@ -161,8 +527,11 @@ pub fn execute_plan(
             data_source: partition_key.source.clone(),
         })?;
 
-    let mut backend = IncrementalBackend::default();
+    let deduped = dedupe_requests(requests, ohlcv);
+
+    let mut backend: IncrementalBackend = IncrementalBackend::default();
     backend.initialize();
+    backend.set_quota(quota);
 
     let mut out: BTreeMap<u32, Vec<IncrementalValue>> = BTreeMap::new();
     for node in requests {
@@ -190,13 +559,158 @@ pub fn execute_plan(
             IncrementalValue::Number(ohlcv.volume[idx]),
         );
 
-        let step_out = backend.step((idx as u64) + 1, requests, &tick);
+        let step_out = backend.step(
+            SINGLE_PARTITION_STREAM,
+            (idx as u64) + 1,
+            &deduped.unique_requests,
+            &tick,
+        )?;
         for (node_id, value) in step_out {
-            out.entry(node_id).or_default().push(value);
+            let series = out.entry(node_id).or_default();
+            series.push(value);
+            if let Some(max_output_len) = quota.max_output_len {
+                if series.len() > max_output_len {
+                    return Err(ExecutePlanError::QuotaExceeded {
+                        kind: QuotaKind::OutputLen,
+                        limit: max_output_len as u64,
+                    });
+                }
+            }
         }
     }
 
-    Ok(out)
+    for (duplicate_id, canonical_id) in &deduped.alias_of {
+        if let Some(series) = out.get(canonical_id).cloned() {
+            out.insert(*duplicate_id, series);
+        }
+    }
+
+    Ok((
+        out,
+        PlanExecutionStats {
+            cache_hits: deduped.cache_hits,
+        },
+    ))
+}
+
+/// The outcome of collapsing `requests` down to one request per distinct
+/// `(kernel_id, input field, kwargs, input column)` signature.
+struct DedupedRequests {
+    /// One request per distinct signature, in first-seen order -- this is
+    /// what actually gets stepped through the backend.
+    unique_requests: Vec<KernelStepRequest>,
+    /// Maps a duplicate request's `node_id` to the `node_id` of the first
+    /// request that shared its signature, so the duplicate's output series
+    /// can be cloned from the canonical one after the tick loop finishes.
+    alias_of: BTreeMap<u32, u32>,
+    cache_hits: usize,
+}
+
+/// Collapses `requests` by content-addressed signature (see
+/// `request_signature`): a plan that references the same indicator with
+/// identical parameters more than once (an `ema(close, 20)` feeding both a
+/// MACD node and a standalone crossover check, say) only pays for computing
+/// it once per tick. Two requests with the same signature are guaranteed to
+/// replay identical outputs -- same kernel, same resolved params, same
+/// input column -- so reusing the first one's series for every duplicate is
+/// exact, not approximate.
+///
+/// Requests reading a [`StepInputSource::NodeOutput`] are never deduped:
+/// aliasing one away would mean nothing actually runs under its `node_id`,
+/// so any other request referencing it by that id would read a ghost
+/// output. Skipping dedup for composed nodes keeps every `node_id` a
+/// downstream reference might target alive in `unique_requests`.
+fn dedupe_requests(requests: &[KernelStepRequest], ohlcv: &dataset::OhlcvColumns) -> DedupedRequests {
+    let mut unique_requests = Vec::with_capacity(requests.len());
+    let mut alias_of = BTreeMap::new();
+    let mut canonical_by_signature: std::collections::HashMap<String, u32> =
+        std::collections::HashMap::new();
+    let mut cache_hits = 0usize;
+
+    for req in requests {
+        let Some(signature) = request_signature(req, ohlcv) else {
+            unique_requests.push(req.clone());
+            continue;
+        };
+        match canonical_by_signature.get(&signature) {
+            Some(&canonical_id) => {
+                alias_of.insert(req.node_id, canonical_id);
+                cache_hits += 1;
+            }
+            None => {
+                canonical_by_signature.insert(signature, req.node_id);
+                unique_requests.push(req.clone());
+            }
+        }
+    }
+
+    DedupedRequests {
+        unique_requests,
+        alias_of,
+        cache_hits,
+    }
+}
+
+/// Builds `req`'s content-addressed signature: its kernel id and input
+/// field name identify *what* is being computed, `kwargs` (already a
+/// `BTreeMap`, so this iterates in sorted key order) identifies the
+/// resolved params, and `column_fingerprint` over the tick field's column
+/// identifies *which* data it reads without hashing every row. Returns
+/// `None` for a [`StepInputSource::NodeOutput`] request -- see
+/// `dedupe_requests` for why those are never deduped.
+fn request_signature(req: &KernelStepRequest, ohlcv: &dataset::OhlcvColumns) -> Option<String> {
+    let StepInputSource::TickField(field) = &req.input else {
+        return None;
+    };
+
+    let mut signature = String::new();
+    signature.push_str(req.kernel_id.as_str());
+    signature.push(';');
+    signature.push_str(field);
+    signature.push(';');
+    for (key, value) in &req.kwargs {
+        signature.push_str(key);
+        signature.push('=');
+        signature.push_str(&format!("{value:?}"));
+        signature.push(',');
+    }
+    signature.push(';');
+    signature.push_str(&column_fingerprint(input_column(field, ohlcv)).to_string());
+    Some(signature)
+}
+
+fn input_column<'a>(field: &str, ohlcv: &'a dataset::OhlcvColumns) -> &'a [f64] {
+    match field {
+        "open" => &ohlcv.open,
+        "high" => &ohlcv.high,
+        "low" => &ohlcv.low,
+        "volume" => &ohlcv.volume,
+        _ => &ohlcv.close,
+    }
+}
+
+/// A cheap stand-in for hashing an entire column: the length plus the
+/// first, last, and a handful of stride-sampled values. The length is
+/// folded into the hash first, so two columns of different length can
+/// never collide into the same signature, without paying to hash
+/// potentially millions of rows per signature.
+fn column_fingerprint(values: &[f64]) -> u64 {
+    use std::hash::{Hash, Hasher};
+    const SAMPLE_COUNT: usize = 8;
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    values.len().hash(&mut hasher);
+    if !values.is_empty() {
+        values[0].to_bits().hash(&mut hasher);
+        values[values.len() - 1].to_bits().hash(&mut hasher);
+        let stride = (values.len() / SAMPLE_COUNT).max(1);
+        let mut idx = 0;
+        while idx < values.len() {
+            values[idx].to_bits().hash(&mut hasher);
+            idx += stride;
+        }
+    }
+    hasher.finish()
 }
 
 pub fn execute_plan_payload(
@@ -209,14 +723,160 @@ pub fn execute_plan_payload(
     )
 }
 
+/// Same as [`execute_plan_payload`], but also returns [`PlanExecutionStats`].
+pub fn execute_plan_payload_with_stats(
+    payload: &ExecutePlanPayload,
+) -> Result<(BTreeMap<u32, Vec<IncrementalValue>>, PlanExecutionStats), ExecutePlanError> {
+    execute_plan_with_stats(
+        payload.dataset_id,
+        &payload.partition_key,
+        &payload.requests,
+    )
+}
+
 pub fn parse_execute_plan_payload(
     payload: &RustExecutionPayload,
 ) -> Result<ExecutePlanPayload, ExecutePlanError> {
     payload_parse::parse_execute_plan_payload(payload)
 }
 
+/// Like [`parse_execute_plan_payload`], but expands `payload.partitions`
+/// into one [`ExecutePlanPayload`] per partition when it's non-empty,
+/// resolving `payload.requests`' kernel ids exactly once and sharing them
+/// read-only across every partition.
+pub fn parse_execute_plan_payloads(
+    payload: &RustExecutionPayload,
+) -> Result<Vec<ExecutePlanPayload>, ExecutePlanError> {
+    payload_parse::parse_execute_plan_payloads(payload)
+}
+
+/// Runs every [`ExecutePlanPayload`] in `payloads` in parallel via rayon,
+/// one task per partition, mirroring [`execute_plan_batch`]'s
+/// parallelization but taking the already-parsed payloads
+/// [`parse_execute_plan_payloads`] returns instead of raw partition keys
+/// plus a shared requests slice. Results come back paired with the
+/// partition key each one is for, in the same order as `payloads`.
+pub fn execute_plan_payloads_batch(
+    payloads: &[ExecutePlanPayload],
+) -> Vec<(
+    DatasetPartitionKey,
+    Result<BTreeMap<u32, Vec<IncrementalValue>>, ExecutePlanError>,
+)> {
+    use rayon::prelude::*;
+
+    payloads
+        .par_iter()
+        .map(|payload| (payload.partition_key.clone(), execute_plan_payload(payload)))
+        .collect()
+}
+
 pub fn execute_plan_graph_payload(
     payload: &RustExecutionPayload,
 ) -> Result<BTreeMap<u32, Vec<IncrementalValue>>, ExecutePlanError> {
     graph_exec::execute_plan_graph_payload(payload)
 }
+
+/// Runs [`execute_plan_graph_payload`] over every payload in `payloads` in
+/// parallel, one rayon task per partition -- each payload is independent
+/// (its own dataset partition and graph), so there's no shared state to
+/// coordinate. Results come back in the same order as `payloads`, paired
+/// with the partition each one is for.
+pub fn execute_plan_graph_batch(
+    payloads: &[RustExecutionPayload],
+) -> Vec<(
+    RustExecutionPartition,
+    Result<BTreeMap<u32, Vec<IncrementalValue>>, ExecutePlanError>,
+)> {
+    use rayon::prelude::*;
+
+    payloads
+        .par_iter()
+        .map(|payload| {
+            (
+                payload.partition.clone(),
+                graph_exec::execute_plan_graph_payload(payload),
+            )
+        })
+        .collect()
+}
+
+/// Runs [`execute_plan`]'s kernel-list `requests` plan against every key in
+/// `partition_keys`, in parallel via rayon -- one task per partition. Unlike
+/// [`execute_plan_graph_batch`] (one independent `RustExecutionPayload` per
+/// partition, each with its own graph and requests), this is for the plainer
+/// kernel-list shape `execute_plan`/`execute_plan_payload` use: `requests` is
+/// parsed and validated once by the caller and shared read-only across every
+/// partition, so a 100-indicator plan run over hundreds of symbols pays the
+/// kernel-id lookup cost once instead of once per symbol. Results come back
+/// paired with the partition key each one is for, in the same order as
+/// `partition_keys` regardless of which partition's task finishes first --
+/// `par_iter().map().collect()` over a slice is index-preserving, so the
+/// caller never needs to re-sort. `max_threads` bounds the rayon pool used
+/// for this call to that many worker threads (building a scoped pool rather
+/// than touching rayon's process-wide global pool, so concurrent callers
+/// with different limits don't clobber each other); `None` uses rayon's
+/// default global pool.
+pub fn execute_plan_batch(
+    dataset_id: DatasetId,
+    partition_keys: &[DatasetPartitionKey],
+    requests: &[KernelStepRequest],
+    max_threads: Option<usize>,
+) -> Vec<(
+    DatasetPartitionKey,
+    Result<BTreeMap<u32, Vec<IncrementalValue>>, ExecutePlanError>,
+)> {
+    use rayon::prelude::*;
+
+    let run = || {
+        partition_keys
+            .par_iter()
+            .map(|key| (key.clone(), execute_plan(dataset_id, key, requests)))
+            .collect()
+    };
+
+    match max_threads {
+        Some(threads) => rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .expect("failed to build scoped rayon thread pool")
+            .install(run),
+        None => run(),
+    }
+}
+
+/// Runs `base_payload`'s graph against every partition of `base_payload.dataset_id`
+/// whose `symbol`/`timeframe` match the given filters (see
+/// [`dataset::list_partitions`] for the filter rules), via
+/// [`execute_plan_graph_batch`]. `base_payload.partition` is ignored -- one
+/// payload is built per matched partition key, reusing `base_payload`'s
+/// graph and requests.
+pub fn execute_plan_graph_prefix(
+    base_payload: &RustExecutionPayload,
+    symbol: Option<&str>,
+    timeframe: Option<&str>,
+) -> Result<
+    Vec<(
+        RustExecutionPartition,
+        Result<BTreeMap<u32, Vec<IncrementalValue>>, ExecutePlanError>,
+    )>,
+    ExecutePlanError,
+> {
+    let keys = dataset::list_partitions(base_payload.dataset_id, symbol, timeframe)?;
+
+    let payloads: Vec<RustExecutionPayload> = keys
+        .into_iter()
+        .map(|key| RustExecutionPayload {
+            dataset_id: base_payload.dataset_id,
+            partition: RustExecutionPartition {
+                symbol: key.symbol,
+                timeframe: key.timeframe,
+                source: key.source,
+            },
+            partitions: Vec::new(),
+            graph: base_payload.graph.clone(),
+            requests: base_payload.requests.clone(),
+        })
+        .collect();
+
+    Ok(execute_plan_graph_batch(&payloads))
+}
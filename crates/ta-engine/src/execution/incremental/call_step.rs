@@ -0,0 +1,961 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use super::contracts::IncrementalValue;
+use super::kernel_registry::{coerce_incremental_input, KernelId};
+use super::sliding_extrema::SlidingExtrema;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum KernelRuntimeState {
+    Rsi {
+        period: usize,
+        prev_close: Option<f64>,
+        avg_gain: Option<f64>,
+        avg_loss: Option<f64>,
+        count: usize,
+    },
+    Atr {
+        period: usize,
+        prev_close: Option<f64>,
+        rma_tr: Option<f64>,
+        count: usize,
+    },
+    /// `highs`/`lows` track the trailing `k_period` window's running max/min
+    /// via [`SlidingExtrema`] in O(1) amortized per tick, instead of folding
+    /// the whole window on every bar.
+    Stochastic {
+        k_period: usize,
+        highs: SlidingExtrema,
+        lows: SlidingExtrema,
+    },
+    /// Running OBV total and the last close seen, so a new bar's direction
+    /// (up/down/flat) can be applied in O(1) without replaying history.
+    Obv {
+        running_total: f64,
+        last_close: Option<f64>,
+    },
+    /// Trailing money-flow-volume and volume windows over `period` bars —
+    /// the same rolling sums `cmf` keeps internally, exposed so a bar can be
+    /// folded in and the oldest one evicted without rescanning the series.
+    Cmf {
+        period: usize,
+        mfv_window: Vec<f64>,
+        volume_window: Vec<f64>,
+    },
+    /// Cumulative price*volume and volume sums `vwap` accumulates session-wide.
+    Vwap { sum_pv: f64, sum_vol: f64 },
+    /// Klinger's running trend/EMA state: `prev_tp` drives the next bar's
+    /// trend sign, and `ema_fast`/`ema_slow` are the same two EMAs `klinger`
+    /// batches over the volume-force series, updated one bar at a time.
+    KlingerVf {
+        fast_period: usize,
+        slow_period: usize,
+        prev_tp: Option<f64>,
+        ema_fast: Option<f64>,
+        ema_slow: Option<f64>,
+    },
+    /// Trailing `period` typical prices `cci` needs for its mean absolute
+    /// deviation; unlike the max/min windows above, MAD isn't expressible as
+    /// a running sum, so this re-scans the window each tick the same way
+    /// the batch `cci` does internally.
+    Cci {
+        period: usize,
+        tp_window: VecDeque<f64>,
+    },
+    /// Rolling high/low window via [`SlidingExtrema`], the same O(1)
+    /// amortized shape as `Stochastic`, but with `williams_r`'s
+    /// zero-range-is-0.0 and inverted-range formula.
+    WilliamsR {
+        period: usize,
+        highs: SlidingExtrema,
+        lows: SlidingExtrema,
+    },
+    /// Trailing positive/negative money-flow windows plus the previous
+    /// bar's typical price, mirroring `Cmf`'s window-and-resum shape.
+    Mfi {
+        period: usize,
+        prev_tp: Option<f64>,
+        pos_window: VecDeque<f64>,
+        neg_window: VecDeque<f64>,
+    },
+    /// Trailing true-range/vortex-movement windows plus the previous bar's
+    /// high/low/close `vortex` needs to compute the next bar's movement.
+    Vortex {
+        period: usize,
+        prev_high: Option<f64>,
+        prev_low: Option<f64>,
+        prev_close: Option<f64>,
+        tr_window: VecDeque<f64>,
+        vm_plus_window: VecDeque<f64>,
+        vm_minus_window: VecDeque<f64>,
+    },
+    /// Trailing gain/loss windows -- a fixed-size window sum, unlike RSI's
+    /// Wilder smoothing -- mirroring `cmo`'s batch rolling-sum math.
+    Cmo {
+        period: usize,
+        prev_value: Option<f64>,
+        gains_window: VecDeque<f64>,
+        losses_window: VecDeque<f64>,
+    },
+    /// Trailing `period` closes plus running `sum`/`sumsq`, so mean and
+    /// population variance update in O(1) per tick instead of rescanning
+    /// the window the way the batch `bbands` does internally.
+    Bbands {
+        period: usize,
+        std_dev: f64,
+        window: VecDeque<f64>,
+        sum: f64,
+        sumsq: f64,
+    },
+    /// Placeholder for kernels with no streaming implementation yet.
+    Generic { kernel_id: KernelId },
+}
+
+pub fn initialize_kernel_state(
+    kernel_id: KernelId,
+    kwargs: &BTreeMap<String, IncrementalValue>,
+) -> KernelRuntimeState {
+    match kernel_id {
+        KernelId::Rsi => KernelRuntimeState::Rsi {
+            period: get_usize(kwargs, "period", 14),
+            prev_close: None,
+            avg_gain: None,
+            avg_loss: None,
+            count: 0,
+        },
+        KernelId::Atr => KernelRuntimeState::Atr {
+            period: get_usize(kwargs, "period", 14),
+            prev_close: None,
+            rma_tr: None,
+            count: 0,
+        },
+        KernelId::Stochastic => {
+            let k_period = get_usize(kwargs, "k_period", 14);
+            KernelRuntimeState::Stochastic {
+                k_period,
+                highs: SlidingExtrema::new(k_period),
+                lows: SlidingExtrema::new(k_period),
+            }
+        }
+        KernelId::Obv => KernelRuntimeState::Obv {
+            running_total: 0.0,
+            last_close: None,
+        },
+        KernelId::Cmf => KernelRuntimeState::Cmf {
+            period: get_usize(kwargs, "period", 20),
+            mfv_window: Vec::new(),
+            volume_window: Vec::new(),
+        },
+        KernelId::Vwap => KernelRuntimeState::Vwap {
+            sum_pv: 0.0,
+            sum_vol: 0.0,
+        },
+        KernelId::KlingerVf => KernelRuntimeState::KlingerVf {
+            fast_period: get_usize(kwargs, "fast_period", 34),
+            slow_period: get_usize(kwargs, "slow_period", 55),
+            prev_tp: None,
+            ema_fast: None,
+            ema_slow: None,
+        },
+        KernelId::Cci => KernelRuntimeState::Cci {
+            period: get_usize(kwargs, "period", 20),
+            tp_window: VecDeque::new(),
+        },
+        KernelId::WilliamsR => {
+            let period = get_usize(kwargs, "period", 14);
+            KernelRuntimeState::WilliamsR {
+                period,
+                highs: SlidingExtrema::new(period),
+                lows: SlidingExtrema::new(period),
+            }
+        }
+        KernelId::Mfi => KernelRuntimeState::Mfi {
+            period: get_usize(kwargs, "period", 14),
+            prev_tp: None,
+            pos_window: VecDeque::new(),
+            neg_window: VecDeque::new(),
+        },
+        KernelId::Vortex => KernelRuntimeState::Vortex {
+            period: get_usize(kwargs, "period", 14),
+            prev_high: None,
+            prev_low: None,
+            prev_close: None,
+            tr_window: VecDeque::new(),
+            vm_plus_window: VecDeque::new(),
+            vm_minus_window: VecDeque::new(),
+        },
+        KernelId::Cmo => KernelRuntimeState::Cmo {
+            period: get_usize(kwargs, "period", 14),
+            prev_value: None,
+            gains_window: VecDeque::new(),
+            losses_window: VecDeque::new(),
+        },
+        KernelId::Bbands => KernelRuntimeState::Bbands {
+            period: get_usize(kwargs, "period", 20),
+            std_dev: get_f64(kwargs, "std_dev", 2.0),
+            window: VecDeque::new(),
+            sum: 0.0,
+            sumsq: 0.0,
+        },
+        other => KernelRuntimeState::Generic { kernel_id: other },
+    }
+}
+
+pub fn eval_call_step(
+    kernel_id: KernelId,
+    state: KernelRuntimeState,
+    input_value: IncrementalValue,
+    tick: &BTreeMap<String, IncrementalValue>,
+) -> (KernelRuntimeState, IncrementalValue) {
+    match state {
+        KernelRuntimeState::Rsi {
+            period,
+            prev_close,
+            avg_gain,
+            avg_loss,
+            count,
+        } => {
+            let coerced = coerce_incremental_input(kernel_id, input_value, tick, prev_close);
+            let close = match coerced {
+                IncrementalValue::Number(v) => v,
+                _ => {
+                    return (
+                        KernelRuntimeState::Rsi {
+                            period,
+                            prev_close,
+                            avg_gain,
+                            avg_loss,
+                            count,
+                        },
+                        IncrementalValue::Null,
+                    )
+                }
+            };
+
+            let new_count = count + 1;
+            if prev_close.is_none() {
+                return (
+                    KernelRuntimeState::Rsi {
+                        period,
+                        prev_close: Some(close),
+                        avg_gain,
+                        avg_loss,
+                        count: new_count,
+                    },
+                    IncrementalValue::Null,
+                );
+            }
+
+            let diff = close - prev_close.unwrap_or(close);
+            let gain = if diff > 0.0 { diff } else { 0.0 };
+            let loss = if diff < 0.0 { -diff } else { 0.0 };
+
+            if avg_gain.is_none() || avg_loss.is_none() {
+                return (
+                    KernelRuntimeState::Rsi {
+                        period,
+                        prev_close: Some(close),
+                        avg_gain: Some(gain),
+                        avg_loss: Some(loss),
+                        count: new_count,
+                    },
+                    IncrementalValue::Null,
+                );
+            }
+
+            let ag = ((avg_gain.unwrap_or(0.0) * (period as f64 - 1.0)) + gain) / period as f64;
+            let al = ((avg_loss.unwrap_or(0.0) * (period as f64 - 1.0)) + loss) / period as f64;
+
+            let rsi = if al == 0.0 {
+                if ag > 0.0 {
+                    100.0
+                } else {
+                    50.0
+                }
+            } else {
+                let rs = ag / al;
+                100.0 - (100.0 / (1.0 + rs))
+            };
+
+            let output = if new_count < period + 1 {
+                IncrementalValue::Null
+            } else {
+                IncrementalValue::Number(rsi.clamp(0.0, 100.0))
+            };
+
+            (
+                KernelRuntimeState::Rsi {
+                    period,
+                    prev_close: Some(close),
+                    avg_gain: Some(ag),
+                    avg_loss: Some(al),
+                    count: new_count,
+                },
+                output,
+            )
+        }
+        KernelRuntimeState::Atr {
+            period,
+            prev_close,
+            rma_tr,
+            count,
+        } => {
+            let coerced = coerce_incremental_input(kernel_id, input_value, tick, prev_close);
+            let tr = match coerced {
+                IncrementalValue::Number(v) => v,
+                _ => 0.0,
+            };
+            let close = get_num(tick, "close").unwrap_or(0.0);
+            let new_count = count + 1;
+
+            if rma_tr.is_none() {
+                let out = if new_count < period {
+                    IncrementalValue::Null
+                } else {
+                    IncrementalValue::Number(tr)
+                };
+                return (
+                    KernelRuntimeState::Atr {
+                        period,
+                        prev_close: Some(close),
+                        rma_tr: Some(tr),
+                        count: new_count,
+                    },
+                    out,
+                );
+            }
+
+            let new_rma = ((rma_tr.unwrap_or(0.0) * (period as f64 - 1.0)) + tr) / period as f64;
+            let out = if new_count < period {
+                IncrementalValue::Null
+            } else {
+                IncrementalValue::Number(new_rma)
+            };
+
+            (
+                KernelRuntimeState::Atr {
+                    period,
+                    prev_close: Some(close),
+                    rma_tr: Some(new_rma),
+                    count: new_count,
+                },
+                out,
+            )
+        }
+        KernelRuntimeState::Stochastic {
+            k_period,
+            mut highs,
+            mut lows,
+        } => {
+            let coerced = coerce_incremental_input(kernel_id, input_value, tick, None);
+            let (h, l, c) = match coerced {
+                IncrementalValue::Fields(ref f) if f.len() == 3 => (f[0], f[1], f[2]),
+                _ => {
+                    return (
+                        KernelRuntimeState::Stochastic {
+                            k_period,
+                            highs,
+                            lows,
+                        },
+                        IncrementalValue::Null,
+                    )
+                }
+            };
+
+            highs.push(h);
+            lows.push(l);
+
+            if !highs.is_full() {
+                return (
+                    KernelRuntimeState::Stochastic {
+                        k_period,
+                        highs,
+                        lows,
+                    },
+                    IncrementalValue::Null,
+                );
+            }
+
+            let hh = highs.max().unwrap_or(h);
+            let ll = lows.min().unwrap_or(l);
+            let denom = hh - ll;
+            let k = if denom == 0.0 {
+                50.0
+            } else {
+                100.0 * (c - ll) / denom
+            };
+
+            (
+                KernelRuntimeState::Stochastic {
+                    k_period,
+                    highs,
+                    lows,
+                },
+                IncrementalValue::Number(k),
+            )
+        }
+        KernelRuntimeState::Obv {
+            running_total,
+            last_close,
+        } => {
+            let coerced = coerce_incremental_input(kernel_id, input_value, tick, last_close);
+            let (close, volume) = match parse_pair(&coerced) {
+                Some(pair) => pair,
+                None => {
+                    return (
+                        KernelRuntimeState::Obv {
+                            running_total,
+                            last_close,
+                        },
+                        IncrementalValue::Null,
+                    )
+                }
+            };
+
+            let new_total = match last_close {
+                None => volume,
+                Some(prev) if close > prev => running_total + volume,
+                Some(prev) if close < prev => running_total - volume,
+                Some(_) => running_total,
+            };
+
+            (
+                KernelRuntimeState::Obv {
+                    running_total: new_total,
+                    last_close: Some(close),
+                },
+                IncrementalValue::Number(new_total),
+            )
+        }
+        KernelRuntimeState::Cmf {
+            period,
+            mut mfv_window,
+            mut volume_window,
+        } => {
+            let coerced = coerce_incremental_input(kernel_id, input_value, tick, None);
+            let (h, l, c, v) = match parse_quad(&coerced) {
+                Some(quad) => quad,
+                None => {
+                    return (
+                        KernelRuntimeState::Cmf {
+                            period,
+                            mfv_window,
+                            volume_window,
+                        },
+                        IncrementalValue::Null,
+                    )
+                }
+            };
+
+            let hl = h - l;
+            let mfv = if hl == 0.0 {
+                0.0
+            } else {
+                (((c - l) - (h - c)) / hl) * v
+            };
+
+            mfv_window.push(mfv);
+            volume_window.push(v);
+            if mfv_window.len() > period {
+                mfv_window.remove(0);
+                volume_window.remove(0);
+            }
+
+            let output = if mfv_window.len() < period {
+                IncrementalValue::Null
+            } else {
+                let sum_mfv: f64 = mfv_window.iter().sum();
+                let sum_vol: f64 = volume_window.iter().sum();
+                let cmf = if sum_vol == 0.0 { 0.0 } else { sum_mfv / sum_vol };
+                IncrementalValue::Number(cmf)
+            };
+
+            (
+                KernelRuntimeState::Cmf {
+                    period,
+                    mfv_window,
+                    volume_window,
+                },
+                output,
+            )
+        }
+        KernelRuntimeState::Vwap { sum_pv, sum_vol } => {
+            let coerced = coerce_incremental_input(kernel_id, input_value, tick, None);
+            let (h, l, c, v) = match parse_quad(&coerced) {
+                Some(quad) => quad,
+                None => {
+                    return (KernelRuntimeState::Vwap { sum_pv, sum_vol }, IncrementalValue::Null)
+                }
+            };
+
+            let tp = (h + l + c) / 3.0;
+            let new_sum_pv = sum_pv + tp * v;
+            let new_sum_vol = sum_vol + v;
+            let vwap = if new_sum_vol > 0.0 {
+                new_sum_pv / new_sum_vol
+            } else {
+                tp
+            };
+
+            (
+                KernelRuntimeState::Vwap {
+                    sum_pv: new_sum_pv,
+                    sum_vol: new_sum_vol,
+                },
+                IncrementalValue::Number(vwap),
+            )
+        }
+        KernelRuntimeState::KlingerVf {
+            fast_period,
+            slow_period,
+            prev_tp,
+            ema_fast,
+            ema_slow,
+        } => {
+            let coerced = coerce_incremental_input(kernel_id, input_value, tick, None);
+            let (h, l, c, v) = match parse_quad(&coerced) {
+                Some(quad) => quad,
+                None => {
+                    return (
+                        KernelRuntimeState::KlingerVf {
+                            fast_period,
+                            slow_period,
+                            prev_tp,
+                            ema_fast,
+                            ema_slow,
+                        },
+                        IncrementalValue::Null,
+                    )
+                }
+            };
+
+            let tp = (h + l + c) / 3.0;
+            let dm = h - l;
+
+            let vf = match prev_tp {
+                None => 0.0,
+                Some(prev) => {
+                    let trend = if tp > prev { 1.0 } else { -1.0 };
+                    let safe_dm = if dm > 0.0 { dm } else { 1e-10 };
+                    v * (2.0 * ((tp - prev) / safe_dm) - 1.0).abs() * trend * 100.0
+                }
+            };
+
+            let new_ema_fast = Some(ema_step(ema_fast, vf, fast_period));
+            let new_ema_slow = Some(ema_step(ema_slow, vf, slow_period));
+
+            let output = match (new_ema_fast, new_ema_slow) {
+                (Some(fast), Some(slow)) => IncrementalValue::Number(fast - slow),
+                _ => IncrementalValue::Null,
+            };
+
+            (
+                KernelRuntimeState::KlingerVf {
+                    fast_period,
+                    slow_period,
+                    prev_tp: Some(tp),
+                    ema_fast: new_ema_fast,
+                    ema_slow: new_ema_slow,
+                },
+                output,
+            )
+        }
+        KernelRuntimeState::Cci { period, mut tp_window } => {
+            let coerced = coerce_incremental_input(kernel_id, input_value, tick, None);
+            let (h, l, c) = match coerced {
+                IncrementalValue::Fields(ref f) if f.len() == 3 => (f[0], f[1], f[2]),
+                _ => return (KernelRuntimeState::Cci { period, tp_window }, IncrementalValue::Null),
+            };
+
+            let tp = (h + l + c) / 3.0;
+            tp_window.push_back(tp);
+            if tp_window.len() > period {
+                tp_window.pop_front();
+            }
+
+            let output = if tp_window.len() < period {
+                IncrementalValue::Null
+            } else {
+                let sma: f64 = tp_window.iter().sum::<f64>() / period as f64;
+                let mean_deviation: f64 =
+                    tp_window.iter().map(|v| (v - sma).abs()).sum::<f64>() / period as f64;
+                let cci = if mean_deviation == 0.0 {
+                    0.0
+                } else {
+                    (tp - sma) / (0.015 * mean_deviation)
+                };
+                IncrementalValue::Number(cci)
+            };
+
+            (KernelRuntimeState::Cci { period, tp_window }, output)
+        }
+        KernelRuntimeState::WilliamsR {
+            period,
+            mut highs,
+            mut lows,
+        } => {
+            let coerced = coerce_incremental_input(kernel_id, input_value, tick, None);
+            let (h, l, c) = match coerced {
+                IncrementalValue::Fields(ref f) if f.len() == 3 => (f[0], f[1], f[2]),
+                _ => {
+                    return (
+                        KernelRuntimeState::WilliamsR { period, highs, lows },
+                        IncrementalValue::Null,
+                    )
+                }
+            };
+
+            highs.push(h);
+            lows.push(l);
+
+            if !highs.is_full() {
+                return (
+                    KernelRuntimeState::WilliamsR { period, highs, lows },
+                    IncrementalValue::Null,
+                );
+            }
+
+            let hh = highs.max().unwrap_or(h);
+            let ll = lows.min().unwrap_or(l);
+            let range = hh - ll;
+            let r = if range == 0.0 {
+                0.0
+            } else {
+                ((hh - c) / range) * -100.0
+            };
+
+            (
+                KernelRuntimeState::WilliamsR { period, highs, lows },
+                IncrementalValue::Number(r),
+            )
+        }
+        KernelRuntimeState::Mfi {
+            period,
+            prev_tp,
+            mut pos_window,
+            mut neg_window,
+        } => {
+            let coerced = coerce_incremental_input(kernel_id, input_value, tick, None);
+            let (h, l, c, v) = match parse_quad(&coerced) {
+                Some(quad) => quad,
+                None => {
+                    return (
+                        KernelRuntimeState::Mfi {
+                            period,
+                            prev_tp,
+                            pos_window,
+                            neg_window,
+                        },
+                        IncrementalValue::Null,
+                    )
+                }
+            };
+
+            let tp = (h + l + c) / 3.0;
+            let rmf = tp * v;
+
+            let (pos, neg) = match prev_tp {
+                Some(prev) if tp > prev => (rmf, 0.0),
+                Some(prev) if tp < prev => (0.0, rmf),
+                _ => (0.0, 0.0),
+            };
+
+            pos_window.push_back(pos);
+            neg_window.push_back(neg);
+            if pos_window.len() > period {
+                pos_window.pop_front();
+                neg_window.pop_front();
+            }
+
+            let output = if pos_window.len() < period {
+                IncrementalValue::Null
+            } else {
+                let pos_sum: f64 = pos_window.iter().sum();
+                let neg_sum: f64 = neg_window.iter().sum();
+                let mfi = if neg_sum == 0.0 {
+                    100.0
+                } else {
+                    let mfr = pos_sum / neg_sum;
+                    100.0 - (100.0 / (1.0 + mfr))
+                };
+                IncrementalValue::Number(mfi)
+            };
+
+            (
+                KernelRuntimeState::Mfi {
+                    period,
+                    prev_tp: Some(tp),
+                    pos_window,
+                    neg_window,
+                },
+                output,
+            )
+        }
+        KernelRuntimeState::Vortex {
+            period,
+            prev_high,
+            prev_low,
+            prev_close,
+            mut tr_window,
+            mut vm_plus_window,
+            mut vm_minus_window,
+        } => {
+            let coerced = coerce_incremental_input(kernel_id, input_value, tick, None);
+            let (h, l, c) = match coerced {
+                IncrementalValue::Fields(ref f) if f.len() == 3 => (f[0], f[1], f[2]),
+                _ => {
+                    return (
+                        KernelRuntimeState::Vortex {
+                            period,
+                            prev_high,
+                            prev_low,
+                            prev_close,
+                            tr_window,
+                            vm_plus_window,
+                            vm_minus_window,
+                        },
+                        IncrementalValue::Null,
+                    )
+                }
+            };
+
+            let (ph, pl, pc) = match (prev_high, prev_low, prev_close) {
+                (Some(ph), Some(pl), Some(pc)) => (ph, pl, pc),
+                _ => {
+                    return (
+                        KernelRuntimeState::Vortex {
+                            period,
+                            prev_high: Some(h),
+                            prev_low: Some(l),
+                            prev_close: Some(c),
+                            tr_window,
+                            vm_plus_window,
+                            vm_minus_window,
+                        },
+                        IncrementalValue::Null,
+                    )
+                }
+            };
+
+            let vm_plus = (h - pl).abs();
+            let vm_minus = (l - ph).abs();
+            let tr = (h - l).max((h - pc).abs()).max((l - pc).abs());
+
+            tr_window.push_back(tr);
+            vm_plus_window.push_back(vm_plus);
+            vm_minus_window.push_back(vm_minus);
+            if tr_window.len() > period {
+                tr_window.pop_front();
+                vm_plus_window.pop_front();
+                vm_minus_window.pop_front();
+            }
+
+            let output = if tr_window.len() < period {
+                IncrementalValue::Null
+            } else {
+                let tr_sum: f64 = tr_window.iter().sum();
+                if tr_sum == 0.0 {
+                    IncrementalValue::Null
+                } else {
+                    let vp_sum: f64 = vm_plus_window.iter().sum();
+                    let vm_sum: f64 = vm_minus_window.iter().sum();
+                    IncrementalValue::Fields(vec![vp_sum / tr_sum, vm_sum / tr_sum])
+                }
+            };
+
+            (
+                KernelRuntimeState::Vortex {
+                    period,
+                    prev_high: Some(h),
+                    prev_low: Some(l),
+                    prev_close: Some(c),
+                    tr_window,
+                    vm_plus_window,
+                    vm_minus_window,
+                },
+                output,
+            )
+        }
+        KernelRuntimeState::Cmo {
+            period,
+            prev_value,
+            mut gains_window,
+            mut losses_window,
+        } => {
+            let coerced = coerce_incremental_input(kernel_id, input_value, tick, prev_value);
+            let value = match coerced {
+                IncrementalValue::Number(v) => v,
+                _ => {
+                    return (
+                        KernelRuntimeState::Cmo {
+                            period,
+                            prev_value,
+                            gains_window,
+                            losses_window,
+                        },
+                        IncrementalValue::Null,
+                    )
+                }
+            };
+
+            let prev = match prev_value {
+                None => {
+                    return (
+                        KernelRuntimeState::Cmo {
+                            period,
+                            prev_value: Some(value),
+                            gains_window,
+                            losses_window,
+                        },
+                        IncrementalValue::Null,
+                    )
+                }
+                Some(prev) => prev,
+            };
+
+            let diff = value - prev;
+            let (gain, loss) = if diff > 0.0 { (diff, 0.0) } else { (0.0, -diff) };
+
+            gains_window.push_back(gain);
+            losses_window.push_back(loss);
+            if gains_window.len() > period {
+                gains_window.pop_front();
+                losses_window.pop_front();
+            }
+
+            let output = if gains_window.len() < period {
+                IncrementalValue::Null
+            } else {
+                let sg: f64 = gains_window.iter().sum();
+                let sl: f64 = losses_window.iter().sum();
+                let denom = sg + sl;
+                let cmo = if denom == 0.0 { 0.0 } else { 100.0 * (sg - sl) / denom };
+                IncrementalValue::Number(cmo)
+            };
+
+            (
+                KernelRuntimeState::Cmo {
+                    period,
+                    prev_value: Some(value),
+                    gains_window,
+                    losses_window,
+                },
+                output,
+            )
+        }
+        KernelRuntimeState::Bbands {
+            period,
+            std_dev,
+            mut window,
+            mut sum,
+            mut sumsq,
+        } => {
+            let coerced = coerce_incremental_input(kernel_id, input_value, tick, None);
+            let close = match coerced {
+                IncrementalValue::Number(v) => v,
+                _ => {
+                    return (
+                        KernelRuntimeState::Bbands {
+                            period,
+                            std_dev,
+                            window,
+                            sum,
+                            sumsq,
+                        },
+                        IncrementalValue::Null,
+                    )
+                }
+            };
+
+            window.push_back(close);
+            sum += close;
+            sumsq += close * close;
+
+            if window.len() > period {
+                let old = window.pop_front().unwrap_or(0.0);
+                sum -= old;
+                sumsq -= old * old;
+
+                // Catastrophic-cancellation guard: on a long stream of
+                // large-magnitude prices, `sumsq -= old*old` erodes
+                // precision one eviction at a time. Once an evicted value
+                // dwarfs the window's current mean, recompute `sumsq` from
+                // the surviving window instead of letting the drift compound.
+                let mean_guess = sum / period as f64;
+                if old.abs() > BBANDS_SUMSQ_REFRESH_RATIO * mean_guess.abs().max(1.0) {
+                    sumsq = window.iter().map(|v| v * v).sum();
+                }
+            }
+
+            let output = if window.len() < period {
+                IncrementalValue::Null
+            } else {
+                let mean = sum / period as f64;
+                let variance = (sumsq / period as f64 - mean * mean).max(0.0);
+                let std = variance.sqrt();
+                IncrementalValue::Fields(vec![mean + std_dev * std, mean, mean - std_dev * std])
+            };
+
+            (
+                KernelRuntimeState::Bbands {
+                    period,
+                    std_dev,
+                    window,
+                    sum,
+                    sumsq,
+                },
+                output,
+            )
+        }
+        KernelRuntimeState::Generic { kernel_id } => {
+            (KernelRuntimeState::Generic { kernel_id }, IncrementalValue::Null)
+        }
+    }
+}
+
+/// How many times an evicted close's magnitude must exceed the window's
+/// current mean before [`eval_call_step`]'s `Bbands` arm recomputes `sumsq`
+/// from scratch rather than trusting the running subtraction.
+const BBANDS_SUMSQ_REFRESH_RATIO: f64 = 1e6;
+
+fn ema_step(prev: Option<f64>, value: f64, period: usize) -> f64 {
+    let alpha = 2.0 / (period as f64 + 1.0);
+    match prev {
+        None => value,
+        Some(prev) => alpha * value + (1.0 - alpha) * prev,
+    }
+}
+
+fn parse_pair(value: &IncrementalValue) -> Option<(f64, f64)> {
+    match value {
+        IncrementalValue::Fields(f) if f.len() == 2 => Some((f[0], f[1])),
+        _ => None,
+    }
+}
+
+fn parse_quad(value: &IncrementalValue) -> Option<(f64, f64, f64, f64)> {
+    match value {
+        IncrementalValue::Fields(f) if f.len() == 4 => Some((f[0], f[1], f[2], f[3])),
+        _ => None,
+    }
+}
+
+fn get_usize(kwargs: &BTreeMap<String, IncrementalValue>, key: &str, default: usize) -> usize {
+    match kwargs.get(key) {
+        Some(IncrementalValue::Number(n)) if *n > 0.0 => *n as usize,
+        _ => default,
+    }
+}
+
+fn get_f64(kwargs: &BTreeMap<String, IncrementalValue>, key: &str, default: f64) -> f64 {
+    match kwargs.get(key) {
+        Some(IncrementalValue::Number(n)) => *n,
+        _ => default,
+    }
+}
+
+fn get_num(tick: &BTreeMap<String, IncrementalValue>, key: &str) -> Option<f64> {
+    match tick.get(key) {
+        Some(IncrementalValue::Number(n)) => Some(*n),
+        _ => None,
+    }
+}
@@ -0,0 +1,463 @@
+//! Deterministic typed binary codec for [`KernelRuntimeState`], in the
+//! spirit of parity-scale-codec's SCALE: a 1-byte variant tag, a 1-byte
+//! schema version, then fields in declaration order with fixed-width
+//! little-endian primitives and length-prefixed vectors. Unlike the legacy
+//! [`super::state_codec`] path (which joins float vectors with
+//! `f64::to_string()` and re-parses them), floats round-trip through
+//! `to_bits()`/`from_bits()` so snapshots are bit-exact even for
+//! irrational intermediate values.
+//!
+//! The version byte lets a kernel's runtime struct change shape without
+//! silently corrupting (or silently discarding, via `decode`'s old
+//! `Option`-returning fallback) a blob written by an older build: every
+//! blob is written at [`CURRENT_KERNEL_STATE_VERSION`], and
+//! [`decode_kernel_state_binary`] runs [`KERNEL_STATE_MIGRATIONS`]'s
+//! upgrade function for a blob's `(tag, schema_version)` when it is older,
+//! erroring rather than guessing if no such migration is registered or if
+//! the blob claims a version newer than this build knows about.
+
+use thiserror::Error;
+
+use super::call_step::KernelRuntimeState;
+use super::kernel_registry::KernelId;
+use super::sliding_extrema::SlidingExtrema;
+
+const TAG_RSI: u8 = 0;
+const TAG_ATR: u8 = 1;
+const TAG_STOCHASTIC: u8 = 2;
+const TAG_OBV: u8 = 3;
+const TAG_CMF: u8 = 4;
+const TAG_VWAP: u8 = 5;
+const TAG_KLINGER_VF: u8 = 6;
+const TAG_GENERIC: u8 = 7;
+const TAG_CCI: u8 = 8;
+const TAG_WILLIAMS_R: u8 = 9;
+const TAG_MFI: u8 = 10;
+const TAG_VORTEX: u8 = 11;
+const TAG_CMO: u8 = 12;
+const TAG_BBANDS: u8 = 13;
+
+/// The schema version every [`encode_kernel_state_binary`] call writes.
+/// Bump this and add an entry to [`KERNEL_STATE_MIGRATIONS`] whenever a
+/// `KernelRuntimeState` variant's fields change in a way an old blob can't
+/// just be read with the new layout.
+const CURRENT_KERNEL_STATE_VERSION: u8 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum KernelStateCodecError {
+    #[error("kernel state blob is truncated or malformed")]
+    Malformed,
+    #[error(
+        "kernel state schema_version {0} is newer than the supported version \
+         {CURRENT_KERNEL_STATE_VERSION}"
+    )]
+    UnsupportedVersion(u8),
+    #[error(
+        "no migration registered to advance kernel tag {tag} from schema_version {from_version}"
+    )]
+    NoMigrationPath { tag: u8, from_version: u8 },
+}
+
+/// An upgrade step from `(tag, from_version)` straight to
+/// [`CURRENT_KERNEL_STATE_VERSION`], given the blob's payload bytes (those
+/// following the tag and version byte, in that older version's layout).
+pub type KernelStateMigrationFn = fn(&[u8]) -> Result<KernelRuntimeState, KernelStateCodecError>;
+
+/// Migrations keyed by the `(tag, schema_version)` they migrate *from*,
+/// mirroring [`super::migrations`]'s `RuntimeSnapshot` migration chain but
+/// for one kernel's runtime struct at a time. Empty today since every
+/// kernel is still at `CURRENT_KERNEL_STATE_VERSION`; add an entry here
+/// (alongside bumping the constant and this tag's encode/decode arms)
+/// whenever a kernel's fields change shape.
+const KERNEL_STATE_MIGRATIONS: &[(u8, u8, KernelStateMigrationFn)] = &[];
+
+fn push_header(out: &mut Vec<u8>, tag: u8) {
+    out.push(tag);
+    out.push(CURRENT_KERNEL_STATE_VERSION);
+}
+
+/// Encodes `state` to its compact binary form.
+pub fn encode_kernel_state_binary(state: &KernelRuntimeState) -> Vec<u8> {
+    let mut out = Vec::new();
+    match state {
+        KernelRuntimeState::Rsi {
+            period,
+            prev_close,
+            avg_gain,
+            avg_loss,
+            count,
+        } => {
+            push_header(&mut out, TAG_RSI);
+            write_usize(&mut out, *period);
+            write_option_f64(&mut out, *prev_close);
+            write_option_f64(&mut out, *avg_gain);
+            write_option_f64(&mut out, *avg_loss);
+            write_usize(&mut out, *count);
+        }
+        KernelRuntimeState::Atr {
+            period,
+            prev_close,
+            rma_tr,
+            count,
+        } => {
+            push_header(&mut out, TAG_ATR);
+            write_usize(&mut out, *period);
+            write_option_f64(&mut out, *prev_close);
+            write_option_f64(&mut out, *rma_tr);
+            write_usize(&mut out, *count);
+        }
+        KernelRuntimeState::Stochastic {
+            k_period,
+            highs,
+            lows,
+        } => {
+            push_header(&mut out, TAG_STOCHASTIC);
+            write_usize(&mut out, *k_period);
+            write_f64_vec(&mut out, &highs.values());
+            write_f64_vec(&mut out, &lows.values());
+        }
+        KernelRuntimeState::Obv {
+            running_total,
+            last_close,
+        } => {
+            push_header(&mut out, TAG_OBV);
+            write_f64(&mut out, *running_total);
+            write_option_f64(&mut out, *last_close);
+        }
+        KernelRuntimeState::Cmf {
+            period,
+            mfv_window,
+            volume_window,
+        } => {
+            push_header(&mut out, TAG_CMF);
+            write_usize(&mut out, *period);
+            write_f64_vec(&mut out, mfv_window);
+            write_f64_vec(&mut out, volume_window);
+        }
+        KernelRuntimeState::Vwap { sum_pv, sum_vol } => {
+            push_header(&mut out, TAG_VWAP);
+            write_f64(&mut out, *sum_pv);
+            write_f64(&mut out, *sum_vol);
+        }
+        KernelRuntimeState::KlingerVf {
+            fast_period,
+            slow_period,
+            prev_tp,
+            ema_fast,
+            ema_slow,
+        } => {
+            push_header(&mut out, TAG_KLINGER_VF);
+            write_usize(&mut out, *fast_period);
+            write_usize(&mut out, *slow_period);
+            write_option_f64(&mut out, *prev_tp);
+            write_option_f64(&mut out, *ema_fast);
+            write_option_f64(&mut out, *ema_slow);
+        }
+        KernelRuntimeState::Cci { period, tp_window } => {
+            push_header(&mut out, TAG_CCI);
+            write_usize(&mut out, *period);
+            write_f64_vec(&mut out, &deque_to_vec(tp_window));
+        }
+        KernelRuntimeState::WilliamsR { period, highs, lows } => {
+            push_header(&mut out, TAG_WILLIAMS_R);
+            write_usize(&mut out, *period);
+            write_f64_vec(&mut out, &highs.values());
+            write_f64_vec(&mut out, &lows.values());
+        }
+        KernelRuntimeState::Mfi {
+            period,
+            prev_tp,
+            pos_window,
+            neg_window,
+        } => {
+            push_header(&mut out, TAG_MFI);
+            write_usize(&mut out, *period);
+            write_option_f64(&mut out, *prev_tp);
+            write_f64_vec(&mut out, &deque_to_vec(pos_window));
+            write_f64_vec(&mut out, &deque_to_vec(neg_window));
+        }
+        KernelRuntimeState::Vortex {
+            period,
+            prev_high,
+            prev_low,
+            prev_close,
+            tr_window,
+            vm_plus_window,
+            vm_minus_window,
+        } => {
+            push_header(&mut out, TAG_VORTEX);
+            write_usize(&mut out, *period);
+            write_option_f64(&mut out, *prev_high);
+            write_option_f64(&mut out, *prev_low);
+            write_option_f64(&mut out, *prev_close);
+            write_f64_vec(&mut out, &deque_to_vec(tr_window));
+            write_f64_vec(&mut out, &deque_to_vec(vm_plus_window));
+            write_f64_vec(&mut out, &deque_to_vec(vm_minus_window));
+        }
+        KernelRuntimeState::Cmo {
+            period,
+            prev_value,
+            gains_window,
+            losses_window,
+        } => {
+            push_header(&mut out, TAG_CMO);
+            write_usize(&mut out, *period);
+            write_option_f64(&mut out, *prev_value);
+            write_f64_vec(&mut out, &deque_to_vec(gains_window));
+            write_f64_vec(&mut out, &deque_to_vec(losses_window));
+        }
+        KernelRuntimeState::Bbands {
+            period,
+            std_dev,
+            window,
+            sum,
+            sumsq,
+        } => {
+            push_header(&mut out, TAG_BBANDS);
+            write_usize(&mut out, *period);
+            write_f64(&mut out, *std_dev);
+            write_f64_vec(&mut out, &deque_to_vec(window));
+            write_f64(&mut out, *sum);
+            write_f64(&mut out, *sumsq);
+        }
+        KernelRuntimeState::Generic { kernel_id } => {
+            push_header(&mut out, TAG_GENERIC);
+            write_str(&mut out, kernel_id.as_str());
+        }
+    }
+    out
+}
+
+fn deque_to_vec(deque: &std::collections::VecDeque<f64>) -> Vec<f64> {
+    deque.iter().copied().collect()
+}
+
+/// Decodes `bytes` back into a [`KernelRuntimeState`]. Reads the `(tag,
+/// schema_version)` header first: a version newer than this build knows
+/// about is [`KernelStateCodecError::UnsupportedVersion`] rather than a
+/// best-effort guess, and a version older than current is routed through
+/// [`KERNEL_STATE_MIGRATIONS`], erroring with
+/// [`KernelStateCodecError::NoMigrationPath`] if nothing bridges the gap
+/// instead of silently dropping the node's state back to a fresh start.
+pub fn decode_kernel_state_binary(
+    bytes: &[u8],
+) -> Result<KernelRuntimeState, KernelStateCodecError> {
+    let mut cursor = Cursor::new(bytes);
+    let tag = cursor.read_u8().ok_or(KernelStateCodecError::Malformed)?;
+    let version = cursor.read_u8().ok_or(KernelStateCodecError::Malformed)?;
+
+    if version > CURRENT_KERNEL_STATE_VERSION {
+        return Err(KernelStateCodecError::UnsupportedVersion(version));
+    }
+    if version < CURRENT_KERNEL_STATE_VERSION {
+        let upgrade = KERNEL_STATE_MIGRATIONS
+            .iter()
+            .find(|(t, from, _)| *t == tag && *from == version)
+            .map(|(_, _, f)| *f)
+            .ok_or(KernelStateCodecError::NoMigrationPath {
+                tag,
+                from_version: version,
+            })?;
+        return upgrade(cursor.remaining_bytes());
+    }
+
+    let state = decode_body(tag, &mut cursor).ok_or(KernelStateCodecError::Malformed)?;
+    if cursor.remaining() != 0 {
+        return Err(KernelStateCodecError::Malformed);
+    }
+    Ok(state)
+}
+
+/// Reads `tag`'s fields at [`CURRENT_KERNEL_STATE_VERSION`]'s layout.
+/// Returns `None` on truncation or an unrecognized tag, leaving the version
+/// check and error typing to [`decode_kernel_state_binary`].
+fn decode_body(tag: u8, cursor: &mut Cursor) -> Option<KernelRuntimeState> {
+    let state = match tag {
+        TAG_RSI => KernelRuntimeState::Rsi {
+            period: cursor.read_usize()?,
+            prev_close: cursor.read_option_f64()?,
+            avg_gain: cursor.read_option_f64()?,
+            avg_loss: cursor.read_option_f64()?,
+            count: cursor.read_usize()?,
+        },
+        TAG_ATR => KernelRuntimeState::Atr {
+            period: cursor.read_usize()?,
+            prev_close: cursor.read_option_f64()?,
+            rma_tr: cursor.read_option_f64()?,
+            count: cursor.read_usize()?,
+        },
+        TAG_STOCHASTIC => {
+            let k_period = cursor.read_usize()?;
+            let highs = cursor.read_f64_vec()?;
+            let lows = cursor.read_f64_vec()?;
+            KernelRuntimeState::Stochastic {
+                k_period,
+                highs: SlidingExtrema::from_values(k_period, &highs),
+                lows: SlidingExtrema::from_values(k_period, &lows),
+            }
+        }
+        TAG_OBV => KernelRuntimeState::Obv {
+            running_total: cursor.read_f64()?,
+            last_close: cursor.read_option_f64()?,
+        },
+        TAG_CMF => KernelRuntimeState::Cmf {
+            period: cursor.read_usize()?,
+            mfv_window: cursor.read_f64_vec()?,
+            volume_window: cursor.read_f64_vec()?,
+        },
+        TAG_VWAP => KernelRuntimeState::Vwap {
+            sum_pv: cursor.read_f64()?,
+            sum_vol: cursor.read_f64()?,
+        },
+        TAG_KLINGER_VF => KernelRuntimeState::KlingerVf {
+            fast_period: cursor.read_usize()?,
+            slow_period: cursor.read_usize()?,
+            prev_tp: cursor.read_option_f64()?,
+            ema_fast: cursor.read_option_f64()?,
+            ema_slow: cursor.read_option_f64()?,
+        },
+        TAG_GENERIC => KernelRuntimeState::Generic {
+            kernel_id: KernelId::from_name(&cursor.read_str()?)?,
+        },
+        TAG_CCI => KernelRuntimeState::Cci {
+            period: cursor.read_usize()?,
+            tp_window: cursor.read_f64_vec()?.into(),
+        },
+        TAG_WILLIAMS_R => {
+            let period = cursor.read_usize()?;
+            let highs = cursor.read_f64_vec()?;
+            let lows = cursor.read_f64_vec()?;
+            KernelRuntimeState::WilliamsR {
+                period,
+                highs: SlidingExtrema::from_values(period, &highs),
+                lows: SlidingExtrema::from_values(period, &lows),
+            }
+        }
+        TAG_MFI => KernelRuntimeState::Mfi {
+            period: cursor.read_usize()?,
+            prev_tp: cursor.read_option_f64()?,
+            pos_window: cursor.read_f64_vec()?.into(),
+            neg_window: cursor.read_f64_vec()?.into(),
+        },
+        TAG_VORTEX => KernelRuntimeState::Vortex {
+            period: cursor.read_usize()?,
+            prev_high: cursor.read_option_f64()?,
+            prev_low: cursor.read_option_f64()?,
+            prev_close: cursor.read_option_f64()?,
+            tr_window: cursor.read_f64_vec()?.into(),
+            vm_plus_window: cursor.read_f64_vec()?.into(),
+            vm_minus_window: cursor.read_f64_vec()?.into(),
+        },
+        TAG_CMO => KernelRuntimeState::Cmo {
+            period: cursor.read_usize()?,
+            prev_value: cursor.read_option_f64()?,
+            gains_window: cursor.read_f64_vec()?.into(),
+            losses_window: cursor.read_f64_vec()?.into(),
+        },
+        TAG_BBANDS => KernelRuntimeState::Bbands {
+            period: cursor.read_usize()?,
+            std_dev: cursor.read_f64()?,
+            window: cursor.read_f64_vec()?.into(),
+            sum: cursor.read_f64()?,
+            sumsq: cursor.read_f64()?,
+        },
+        _ => return None,
+    };
+    Some(state)
+}
+
+fn write_usize(out: &mut Vec<u8>, value: usize) {
+    out.extend_from_slice(&(value as u32).to_le_bytes());
+}
+
+fn write_f64(out: &mut Vec<u8>, value: f64) {
+    out.extend_from_slice(&value.to_bits().to_le_bytes());
+}
+
+fn write_option_f64(out: &mut Vec<u8>, value: Option<f64>) {
+    match value {
+        Some(v) => {
+            out.push(1);
+            write_f64(out, v);
+        }
+        None => out.push(0),
+    }
+}
+
+fn write_f64_vec(out: &mut Vec<u8>, values: &[f64]) {
+    out.extend_from_slice(&(values.len() as u32).to_le_bytes());
+    for v in values {
+        write_f64(out, *v);
+    }
+}
+
+fn write_str(out: &mut Vec<u8>, value: &str) {
+    let bytes = value.as_bytes();
+    out.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+    out.extend_from_slice(bytes);
+}
+
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    fn remaining(&self) -> usize {
+        self.bytes.len() - self.pos
+    }
+
+    fn remaining_bytes(&self) -> &'a [u8] {
+        &self.bytes[self.pos..]
+    }
+
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.remaining() < len {
+            return None;
+        }
+        let slice = &self.bytes[self.pos..self.pos + len];
+        self.pos += len;
+        Some(slice)
+    }
+
+    fn read_u8(&mut self) -> Option<u8> {
+        self.take(1).map(|b| b[0])
+    }
+
+    fn read_usize(&mut self) -> Option<usize> {
+        let bytes: [u8; 4] = self.take(4)?.try_into().ok()?;
+        Some(u32::from_le_bytes(bytes) as usize)
+    }
+
+    fn read_f64(&mut self) -> Option<f64> {
+        let bytes: [u8; 8] = self.take(8)?.try_into().ok()?;
+        Some(f64::from_bits(u64::from_le_bytes(bytes)))
+    }
+
+    fn read_option_f64(&mut self) -> Option<Option<f64>> {
+        match self.read_u8()? {
+            0 => Some(None),
+            1 => self.read_f64().map(Some),
+            _ => None,
+        }
+    }
+
+    fn read_f64_vec(&mut self) -> Option<Vec<f64>> {
+        let len = self.read_usize()?;
+        let mut out = Vec::with_capacity(len);
+        for _ in 0..len {
+            out.push(self.read_f64()?);
+        }
+        Some(out)
+    }
+
+    fn read_str(&mut self) -> Option<String> {
+        let len = self.read_usize()?;
+        let bytes = self.take(len)?;
+        String::from_utf8(bytes.to_vec()).ok()
+    }
+}
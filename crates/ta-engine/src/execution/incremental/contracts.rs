@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+
+use super::vector_clock::VectorClock;
+
+pub const INCREMENTAL_STATE_SCHEMA_VERSION: u16 = 1;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum IncrementalValue {
+    Number(f64),
+    Bool(bool),
+    Text(String),
+    /// A small fixed-shape bundle of numeric fields (e.g. `high, low, close`
+    /// for a single OHLC tick). Used by [`super::kernel_registry::coerce_incremental_input`]
+    /// to hand multi-field kernels their inputs without formatting and
+    /// re-parsing a comma-joined string on every tick.
+    Fields(Vec<f64>),
+    /// Opaque byte payload, used by [`super::codec`]'s binary kernel-state
+    /// encoding so float vectors round-trip bit-exact instead of through a
+    /// lossy text join/parse.
+    Bytes(Vec<u8>),
+    Null,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TickUpdate {
+    pub event_index: u64,
+    pub fields: BTreeMap<String, IncrementalValue>,
+}
+
+impl TickUpdate {
+    pub fn new(event_index: u64, fields: BTreeMap<String, IncrementalValue>) -> Self {
+        Self {
+            event_index,
+            fields,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeStepResult {
+    pub node_id: u32,
+    pub output: IncrementalValue,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuntimeSnapshot {
+    pub schema_version: u16,
+    pub last_event_index: u64,
+    pub nodes: BTreeMap<u32, NodeSnapshotState>,
+}
+
+impl RuntimeSnapshot {
+    /// Aggregate resource usage across every node, derived from the
+    /// snapshot's own fields rather than persisted separately -- there is
+    /// nothing here a reader couldn't already compute by walking `nodes`
+    /// itself, so there is no new state to keep in sync or migrate.
+    pub fn counters(&self) -> BackendCounters {
+        BackendCounters {
+            active_nodes: self.nodes.len(),
+            total_ticks_processed: self.nodes.values().map(|node| node.ticks_processed).sum(),
+            state_blob_bytes: self
+                .nodes
+                .values()
+                .map(|node| incremental_blob_bytes(&node.state_blob))
+                .sum(),
+        }
+    }
+}
+
+/// Aggregate resource usage for one [`super::backend::IncrementalBackend`],
+/// returned by [`RuntimeSnapshot::counters`] so operators can observe (and,
+/// via [`super::backend::BackendQuota`], cap) how much a running incremental
+/// plan is costing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BackendCounters {
+    pub active_nodes: usize,
+    pub total_ticks_processed: u64,
+    pub state_blob_bytes: usize,
+}
+
+/// Approximates the heap footprint of a `state_blob` map -- a key's UTF-8
+/// length plus its value's payload size, with fixed-width variants costing
+/// their in-memory size and variable-width ones their actual byte/char
+/// length. Shared by [`super::state::NodeProfile::peak_state_blob_bytes`]
+/// and [`RuntimeSnapshot::counters`] so the two don't drift apart.
+pub(crate) fn incremental_blob_bytes(blob: &BTreeMap<String, IncrementalValue>) -> usize {
+    blob.iter()
+        .map(|(key, value)| {
+            key.len()
+                + match value {
+                    IncrementalValue::Number(_) => std::mem::size_of::<f64>(),
+                    IncrementalValue::Bool(_) => std::mem::size_of::<bool>(),
+                    IncrementalValue::Text(text) => text.len(),
+                    IncrementalValue::Fields(fields) => fields.len() * std::mem::size_of::<f64>(),
+                    IncrementalValue::Bytes(bytes) => bytes.len(),
+                    IncrementalValue::Null => 0,
+                }
+        })
+        .sum()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeSnapshotState {
+    pub ticks_processed: u64,
+    pub last_output: IncrementalValue,
+    pub state_blob: BTreeMap<String, IncrementalValue>,
+    /// Per-stream causal progress; see [`super::vector_clock`]. Old
+    /// snapshots written before this field existed decode to an empty
+    /// clock, which dominates nothing and so never masks a replayed tick.
+    pub clock: VectorClock,
+}
+
+impl RuntimeSnapshot {
+    pub fn empty() -> Self {
+        Self {
+            schema_version: INCREMENTAL_STATE_SCHEMA_VERSION,
+            last_event_index: 0,
+            nodes: BTreeMap::new(),
+        }
+    }
+}
@@ -0,0 +1,589 @@
+//! A small text expression grammar that lowers directly to the streaming
+//! step evaluators in [`super::node_adapters`], so a formula like
+//! `filter(aggregate(sum, source.close - time_shift(1, source.close)),
+//! source.volume > 1000)` can be authored as a string instead of a
+//! hand-built node tree. `compile` parses and lowers in one pass; the
+//! returned [`Plan`] owns one [`super::node_adapters::AggregateState`] or
+//! [`super::node_adapters::TimeShiftState`] per node that needs one, indexed
+//! by the node's position in a depth-first walk of the AST.
+
+use std::collections::BTreeMap;
+
+use thiserror::Error;
+
+use super::contracts::IncrementalValue;
+use super::node_adapters::{
+    self, AggregateState, TimeShiftState,
+};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("{message} (at offset {offset})")]
+pub struct ParseError {
+    pub offset: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    fn new(offset: usize, message: impl Into<String>) -> Self {
+        Self {
+            offset,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Ident(String),
+    Dot,
+    Comma,
+    LParen,
+    RParen,
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    EqEq,
+    Gt,
+    Lt,
+    AndAnd,
+    OrOr,
+    Bang,
+    Eof,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Spanned {
+    offset: usize,
+}
+
+fn lex(source: &str) -> Result<Vec<(Token, Spanned)>, ParseError> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let c = bytes[i] as char;
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        match c {
+            '(' => {
+                tokens.push((Token::LParen, Spanned { offset: start }));
+                i += 1;
+            }
+            ')' => {
+                tokens.push((Token::RParen, Spanned { offset: start }));
+                i += 1;
+            }
+            ',' => {
+                tokens.push((Token::Comma, Spanned { offset: start }));
+                i += 1;
+            }
+            '.' => {
+                tokens.push((Token::Dot, Spanned { offset: start }));
+                i += 1;
+            }
+            '+' => {
+                tokens.push((Token::Plus, Spanned { offset: start }));
+                i += 1;
+            }
+            '-' => {
+                tokens.push((Token::Minus, Spanned { offset: start }));
+                i += 1;
+            }
+            '*' => {
+                tokens.push((Token::Star, Spanned { offset: start }));
+                i += 1;
+            }
+            '/' => {
+                tokens.push((Token::Slash, Spanned { offset: start }));
+                i += 1;
+            }
+            '>' => {
+                tokens.push((Token::Gt, Spanned { offset: start }));
+                i += 1;
+            }
+            '<' => {
+                tokens.push((Token::Lt, Spanned { offset: start }));
+                i += 1;
+            }
+            '=' if bytes.get(i + 1) == Some(&b'=') => {
+                tokens.push((Token::EqEq, Spanned { offset: start }));
+                i += 2;
+            }
+            '&' if bytes.get(i + 1) == Some(&b'&') => {
+                tokens.push((Token::AndAnd, Spanned { offset: start }));
+                i += 2;
+            }
+            '|' if bytes.get(i + 1) == Some(&b'|') => {
+                tokens.push((Token::OrOr, Spanned { offset: start }));
+                i += 2;
+            }
+            '!' => {
+                tokens.push((Token::Bang, Spanned { offset: start }));
+                i += 1;
+            }
+            c if c.is_ascii_digit() => {
+                while i < bytes.len() && (bytes[i] as char).is_ascii_digit() {
+                    i += 1;
+                }
+                if i < bytes.len() && bytes[i] as char == '.' {
+                    i += 1;
+                    while i < bytes.len() && (bytes[i] as char).is_ascii_digit() {
+                        i += 1;
+                    }
+                }
+                let text = &source[start..i];
+                let value: f64 = text
+                    .parse()
+                    .map_err(|_| ParseError::new(start, format!("invalid number '{text}'")))?;
+                tokens.push((Token::Number(value), Spanned { offset: start }));
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                while i < bytes.len()
+                    && ((bytes[i] as char).is_ascii_alphanumeric() || bytes[i] as char == '_')
+                {
+                    i += 1;
+                }
+                tokens.push((
+                    Token::Ident(source[start..i].to_string()),
+                    Spanned { offset: start },
+                ));
+            }
+            other => {
+                return Err(ParseError::new(start, format!("unexpected character '{other}'")));
+            }
+        }
+    }
+    tokens.push((Token::Eof, Spanned { offset: bytes.len() }));
+    Ok(tokens)
+}
+
+/// A parsed formula node, lowered to the same shapes `node_adapters` already
+/// evaluates. `Aggregate`/`TimeShift` nodes each own a slot in
+/// [`Plan`]'s state vectors, keyed by `state_index`.
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Source {
+        source: String,
+        field: String,
+    },
+    Literal(IncrementalValue),
+    Binary {
+        op: &'static str,
+        left: Box<Expr>,
+        right: Box<Expr>,
+    },
+    Unary {
+        op: &'static str,
+        operand: Box<Expr>,
+    },
+    Filter {
+        input: Box<Expr>,
+        condition: Box<Expr>,
+    },
+    Aggregate {
+        op: String,
+        input: Box<Expr>,
+        window: Option<usize>,
+        state_index: usize,
+    },
+    TimeShift {
+        mode: String,
+        lag: usize,
+        input: Box<Expr>,
+        state_index: usize,
+    },
+}
+
+struct Parser {
+    tokens: Vec<(Token, Spanned)>,
+    pos: usize,
+    aggregate_count: usize,
+    time_shift_count: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.pos].0
+    }
+
+    fn offset(&self) -> usize {
+        self.tokens[self.pos].1.offset
+    }
+
+    fn advance(&mut self) -> Token {
+        let token = self.tokens[self.pos].0.clone();
+        if self.pos + 1 < self.tokens.len() {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn expect(&mut self, expected: &Token, what: &str) -> Result<(), ParseError> {
+        if self.peek() == expected {
+            self.advance();
+            Ok(())
+        } else {
+            Err(ParseError::new(self.offset(), format!("expected {what}")))
+        }
+    }
+
+    fn parse_expr(&mut self) -> Result<Expr, ParseError> {
+        self.parse_or()
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, ParseError> {
+        let mut left = self.parse_and()?;
+        while *self.peek() == Token::OrOr {
+            self.advance();
+            let right = self.parse_and()?;
+            left = Expr::Binary {
+                op: "or",
+                left: Box::new(left),
+                right: Box::new(right),
+            };
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, ParseError> {
+        let mut left = self.parse_equality()?;
+        while *self.peek() == Token::AndAnd {
+            self.advance();
+            let right = self.parse_equality()?;
+            left = Expr::Binary {
+                op: "and",
+                left: Box::new(left),
+                right: Box::new(right),
+            };
+        }
+        Ok(left)
+    }
+
+    fn parse_equality(&mut self) -> Result<Expr, ParseError> {
+        let mut left = self.parse_comparison()?;
+        while *self.peek() == Token::EqEq {
+            self.advance();
+            let right = self.parse_comparison()?;
+            left = Expr::Binary {
+                op: "eq",
+                left: Box::new(left),
+                right: Box::new(right),
+            };
+        }
+        Ok(left)
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expr, ParseError> {
+        let mut left = self.parse_additive()?;
+        loop {
+            let op = match self.peek() {
+                Token::Gt => "gt",
+                Token::Lt => "lt",
+                _ => break,
+            };
+            self.advance();
+            let right = self.parse_additive()?;
+            left = Expr::Binary {
+                op,
+                left: Box::new(left),
+                right: Box::new(right),
+            };
+        }
+        Ok(left)
+    }
+
+    fn parse_additive(&mut self) -> Result<Expr, ParseError> {
+        let mut left = self.parse_multiplicative()?;
+        loop {
+            let op = match self.peek() {
+                Token::Plus => "add",
+                Token::Minus => "sub",
+                _ => break,
+            };
+            self.advance();
+            let right = self.parse_multiplicative()?;
+            left = Expr::Binary {
+                op,
+                left: Box::new(left),
+                right: Box::new(right),
+            };
+        }
+        Ok(left)
+    }
+
+    fn parse_multiplicative(&mut self) -> Result<Expr, ParseError> {
+        let mut left = self.parse_unary()?;
+        loop {
+            let op = match self.peek() {
+                Token::Star => "mul",
+                Token::Slash => "div",
+                _ => break,
+            };
+            self.advance();
+            let right = self.parse_unary()?;
+            left = Expr::Binary {
+                op,
+                left: Box::new(left),
+                right: Box::new(right),
+            };
+        }
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, ParseError> {
+        let op = match self.peek() {
+            Token::Minus => Some("neg"),
+            Token::Plus => Some("pos"),
+            Token::Bang => Some("not"),
+            _ => None,
+        };
+        if let Some(op) = op {
+            self.advance();
+            let operand = self.parse_unary()?;
+            return Ok(Expr::Unary {
+                op,
+                operand: Box::new(operand),
+            });
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, ParseError> {
+        match self.peek().clone() {
+            Token::Number(value) => {
+                self.advance();
+                Ok(Expr::Literal(IncrementalValue::Number(value)))
+            }
+            Token::LParen => {
+                self.advance();
+                let inner = self.parse_expr()?;
+                self.expect(&Token::RParen, "')'")?;
+                Ok(inner)
+            }
+            Token::Ident(name) => {
+                self.advance();
+                if *self.peek() == Token::Dot {
+                    self.advance();
+                    let field = self.expect_ident("a field name after '.'")?;
+                    return Ok(Expr::Source { source: name, field });
+                }
+                if *self.peek() == Token::LParen {
+                    return self.parse_call(&name);
+                }
+                Err(ParseError::new(
+                    self.offset(),
+                    format!("unexpected bare identifier '{name}'"),
+                ))
+            }
+            _ => Err(ParseError::new(self.offset(), "expected an expression")),
+        }
+    }
+
+    fn expect_ident(&mut self, what: &str) -> Result<String, ParseError> {
+        match self.peek().clone() {
+            Token::Ident(name) => {
+                self.advance();
+                Ok(name)
+            }
+            _ => Err(ParseError::new(self.offset(), format!("expected {what}"))),
+        }
+    }
+
+    fn parse_call(&mut self, name: &str) -> Result<Expr, ParseError> {
+        let call_offset = self.offset();
+        self.expect(&Token::LParen, "'('")?;
+
+        match name {
+            "filter" => {
+                let input = self.parse_expr()?;
+                self.expect(&Token::Comma, "','")?;
+                let condition = self.parse_expr()?;
+                self.expect(&Token::RParen, "')'")?;
+                Ok(Expr::Filter {
+                    input: Box::new(input),
+                    condition: Box::new(condition),
+                })
+            }
+            "aggregate" => {
+                let op = self.expect_ident("an aggregate operator")?;
+                self.expect(&Token::Comma, "','")?;
+                let input = self.parse_expr()?;
+                let window = if *self.peek() == Token::Comma {
+                    self.advance();
+                    Some(self.expect_number("a window size")? as usize)
+                } else {
+                    None
+                };
+                self.expect(&Token::RParen, "')'")?;
+                let state_index = self.aggregate_count;
+                self.aggregate_count += 1;
+                Ok(Expr::Aggregate {
+                    op,
+                    input: Box::new(input),
+                    window,
+                    state_index,
+                })
+            }
+            "time_shift" => {
+                let bare_mode = matches!(self.tokens.get(self.pos + 1), Some((Token::Comma, _)));
+                let first = match self.peek().clone() {
+                    Token::Ident(name) if bare_mode => {
+                        self.advance();
+                        Expr::Source { source: name, field: String::new() }
+                    }
+                    _ => self.parse_expr()?,
+                };
+                self.expect(&Token::Comma, "','")?;
+                let second = self.parse_expr()?;
+                let (mode, lag, input) = if *self.peek() == Token::Comma {
+                    self.advance();
+                    let third = self.parse_expr()?;
+                    let mode = expect_literal_ident(&first, call_offset)?;
+                    let lag = expect_literal_number(&second, call_offset)? as usize;
+                    (mode, lag, third)
+                } else {
+                    let lag = expect_literal_number(&first, call_offset)? as usize;
+                    ("lag".to_string(), lag, second)
+                };
+                self.expect(&Token::RParen, "')'")?;
+                let state_index = self.time_shift_count;
+                self.time_shift_count += 1;
+                Ok(Expr::TimeShift {
+                    mode,
+                    lag,
+                    input: Box::new(input),
+                    state_index,
+                })
+            }
+            other => Err(ParseError::new(
+                call_offset,
+                format!("unknown function '{other}'"),
+            )),
+        }
+    }
+
+    fn expect_number(&mut self, what: &str) -> Result<f64, ParseError> {
+        match self.peek().clone() {
+            Token::Number(value) => {
+                self.advance();
+                Ok(value)
+            }
+            _ => Err(ParseError::new(self.offset(), format!("expected {what}"))),
+        }
+    }
+}
+
+/// `time_shift`'s bare mode name (e.g. `diff`) is read by `parse_call` as a
+/// field-less `Expr::Source`, not a literal -- this unwraps that back into a
+/// plain string for the mode argument.
+fn expect_literal_ident(expr: &Expr, offset: usize) -> Result<String, ParseError> {
+    match expr {
+        Expr::Source { source, field } if field.is_empty() => Ok(source.clone()),
+        _ => Err(ParseError::new(offset, "expected a mode name")),
+    }
+}
+
+fn expect_literal_number(expr: &Expr, offset: usize) -> Result<f64, ParseError> {
+    match expr {
+        Expr::Literal(IncrementalValue::Number(n)) => Ok(*n),
+        _ => Err(ParseError::new(offset, "expected a numeric literal")),
+    }
+}
+
+/// A compiled formula, ready to be driven tick-by-tick. Owns one
+/// [`AggregateState`] per `aggregate(...)` call and one [`TimeShiftState`]
+/// per `time_shift(...)` call in the source, in the order they were parsed.
+#[derive(Debug, Clone)]
+pub struct Plan {
+    root: Expr,
+    aggregate_states: Vec<AggregateState>,
+    time_shift_states: Vec<TimeShiftState>,
+}
+
+impl Plan {
+    /// Evaluates one tick through the whole expression tree, threading each
+    /// `aggregate`/`time_shift` node's state forward.
+    pub fn eval_tick(&mut self, tick: &BTreeMap<String, IncrementalValue>) -> IncrementalValue {
+        let root = self.root.clone();
+        eval_expr(&root, tick, &mut self.aggregate_states, &mut self.time_shift_states)
+    }
+}
+
+fn eval_expr(
+    expr: &Expr,
+    tick: &BTreeMap<String, IncrementalValue>,
+    aggregate_states: &mut [AggregateState],
+    time_shift_states: &mut [TimeShiftState],
+) -> IncrementalValue {
+    match expr {
+        Expr::Source { source, field } => node_adapters::eval_source_ref_step(
+            &node_adapters::SourceRef {
+                source: source.clone(),
+                field: field.clone(),
+            },
+            tick,
+        ),
+        Expr::Literal(value) => node_adapters::eval_literal_step(value),
+        Expr::Binary { op, left, right } => {
+            let left = eval_expr(left, tick, aggregate_states, time_shift_states);
+            let right = eval_expr(right, tick, aggregate_states, time_shift_states);
+            node_adapters::eval_binary_step(op, &left, &right)
+        }
+        Expr::Unary { op, operand } => {
+            let value = eval_expr(operand, tick, aggregate_states, time_shift_states);
+            node_adapters::eval_unary_step(op, &value)
+        }
+        Expr::Filter { input, condition } => {
+            let value = eval_expr(input, tick, aggregate_states, time_shift_states);
+            let condition = eval_expr(condition, tick, aggregate_states, time_shift_states);
+            node_adapters::eval_filter_step(&value, &condition)
+        }
+        Expr::Aggregate {
+            op,
+            input,
+            window,
+            state_index,
+        } => {
+            let value = eval_expr(input, tick, aggregate_states, time_shift_states);
+            node_adapters::eval_aggregate_step(op, *window, &value, &mut aggregate_states[*state_index])
+        }
+        Expr::TimeShift {
+            mode,
+            lag,
+            input,
+            state_index,
+        } => {
+            let value = eval_expr(input, tick, aggregate_states, time_shift_states);
+            node_adapters::eval_time_shift_step(mode, *lag, &value, &mut time_shift_states[*state_index])
+        }
+    }
+}
+
+/// Parses `source` and lowers it straight into a [`Plan`]. Errors report the
+/// byte offset of the offending token alongside a human-readable message.
+pub fn compile(source: &str) -> Result<Plan, ParseError> {
+    let tokens = lex(source)?;
+    let mut parser = Parser {
+        tokens,
+        pos: 0,
+        aggregate_count: 0,
+        time_shift_count: 0,
+    };
+    let root = parser.parse_expr()?;
+    if *parser.peek() != Token::Eof {
+        return Err(ParseError::new(
+            parser.offset(),
+            "unexpected trailing input",
+        ));
+    }
+    Ok(Plan {
+        aggregate_states: vec![AggregateState::default(); parser.aggregate_count],
+        time_shift_states: vec![TimeShiftState::default(); parser.time_shift_count],
+        root,
+    })
+}
@@ -0,0 +1,94 @@
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::core::metadata::IndicatorMeta;
+use crate::runtime::params::normalize_params_for;
+
+use super::backend::{KernelStepRequest, StepInputSource};
+use super::contracts::{IncrementalValue, TickUpdate};
+use super::kernel_registry::KernelId;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum BuildIncrementalError {
+    #[error("indicator '{0}' has no streaming kernel binding yet")]
+    UnsupportedIndicator(String),
+    #[error("invalid params for indicator '{indicator}': {reason}")]
+    InvalidParams { indicator: String, reason: String },
+    #[error("tick is missing required field '{0}' for this indicator")]
+    MissingRequiredField(String),
+}
+
+/// Bridges a catalog entry to the incremental backend: validates `params`
+/// against `meta.params`/`meta.param_aliases` the same way the batch runtime
+/// does, resolves `meta.runtime_binding` to a streaming [`KernelId`], and
+/// assembles the [`KernelStepRequest`] a caller can feed to
+/// [`super::backend::IncrementalBackend::step`]. Indicators whose
+/// `runtime_binding` names no [`KernelId`] (e.g. `sma`) are rejected with
+/// [`BuildIncrementalError::UnsupportedIndicator`]. `macd` and `adx` do
+/// resolve to a [`KernelId`] but have no streaming state, so their requests
+/// build and then yield `Null` on every tick.
+pub fn build_incremental(
+    meta: &IndicatorMeta,
+    node_id: u32,
+    params: &Value,
+) -> Result<KernelStepRequest, BuildIncrementalError> {
+    let kernel_id = KernelId::from_name(meta.runtime_binding).ok_or_else(|| {
+        BuildIncrementalError::UnsupportedIndicator(meta.runtime_binding.to_string())
+    })?;
+
+    let normalized = normalize_params_for(meta, params).map_err(|err| {
+        BuildIncrementalError::InvalidParams {
+            indicator: meta.id.to_string(),
+            reason: err.to_string(),
+        }
+    })?;
+
+    let input_field = meta
+        .semantics
+        .required_fields
+        .first()
+        .copied()
+        .unwrap_or("close")
+        .to_string();
+
+    Ok(KernelStepRequest {
+        node_id,
+        kernel_id,
+        input: StepInputSource::TickField(input_field),
+        kwargs: json_object_to_kwargs(&normalized),
+    })
+}
+
+/// Confirms `tick` carries every field `meta.semantics.required_fields`
+/// demands, so a caller can fail fast instead of silently stepping a kernel
+/// on zeroed-out inputs.
+pub fn ensure_required_fields(
+    meta: &IndicatorMeta,
+    tick: &TickUpdate,
+) -> Result<(), BuildIncrementalError> {
+    for field in meta.semantics.required_fields {
+        if !tick.fields.contains_key(*field) {
+            return Err(BuildIncrementalError::MissingRequiredField((*field).to_string()));
+        }
+    }
+    Ok(())
+}
+
+fn json_object_to_kwargs(value: &Value) -> BTreeMap<String, IncrementalValue> {
+    let mut out = BTreeMap::new();
+    let Value::Object(map) = value else {
+        return out;
+    };
+    for (key, v) in map {
+        let converted = match v {
+            Value::Number(n) => IncrementalValue::Number(n.as_f64().unwrap_or(0.0)),
+            Value::Bool(b) => IncrementalValue::Bool(*b),
+            Value::String(s) => IncrementalValue::Text(s.clone()),
+            _ => IncrementalValue::Null,
+        };
+        out.insert(key.clone(), converted);
+    }
+    out
+}
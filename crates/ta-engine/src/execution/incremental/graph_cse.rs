@@ -0,0 +1,97 @@
+//! Common-subexpression elimination over a [`RustExecutionGraph`].
+//!
+//! User-authored plans frequently reference the same subtree more than
+//! once (two indicators both reading an `ema(close, 20)` node, a crossover
+//! check against a moving average some other branch already computes).
+//! [`canonicalize`] walks `node_order` -- already topological, children
+//! before parents -- and assigns each node a content signature from its
+//! `kind`/attributes and the *canonical* signatures of its children, so
+//! two structurally identical subtrees collapse onto the first node that
+//! produced that signature. The returned graph keeps only the surviving
+//! (canonical) nodes; [`CanonicalGraph::canonical_of`] lets the caller map
+//! every original node id back onto whichever canonical id now holds its
+//! computed output.
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::contracts::RustExecutionGraph;
+
+pub struct CanonicalGraph {
+    pub graph: RustExecutionGraph,
+    pub canonical_of: BTreeMap<u32, u32>,
+}
+
+/// Deduplicates `graph` by structural content, returning a smaller graph
+/// over only the canonical nodes plus the original-id -> canonical-id map.
+pub fn canonicalize(graph: &RustExecutionGraph) -> CanonicalGraph {
+    let mut signature_of: BTreeMap<u32, String> = BTreeMap::new();
+    let mut canonical_of: BTreeMap<u32, u32> = BTreeMap::new();
+    let mut canonical_by_signature: HashMap<String, u32> = HashMap::new();
+
+    let mut node_order = Vec::new();
+    let mut nodes = BTreeMap::new();
+    let mut edges = BTreeMap::new();
+
+    for &node_id in &graph.node_order {
+        let Some(meta) = graph.nodes.get(&node_id) else {
+            continue;
+        };
+        let children = graph.edges.get(&node_id).cloned().unwrap_or_default();
+        let canonical_children: Vec<u32> = children
+            .iter()
+            .map(|child_id| canonical_of.get(child_id).copied().unwrap_or(*child_id))
+            .collect();
+
+        let signature = node_signature(meta, &canonical_children, &signature_of);
+
+        let canonical_id = match canonical_by_signature.get(&signature) {
+            Some(&existing) => existing,
+            None => {
+                canonical_by_signature.insert(signature.clone(), node_id);
+                node_order.push(node_id);
+                nodes.insert(node_id, meta.clone());
+                edges.insert(node_id, canonical_children);
+                node_id
+            }
+        };
+
+        canonical_of.insert(node_id, canonical_id);
+        signature_of.insert(node_id, signature);
+    }
+
+    let root_id = canonical_of.get(&graph.root_id).copied().unwrap_or(graph.root_id);
+
+    CanonicalGraph {
+        graph: RustExecutionGraph {
+            root_id,
+            node_order,
+            nodes,
+            edges,
+        },
+        canonical_of,
+    }
+}
+
+/// Builds a deterministic content signature for a node from its attribute
+/// map (`BTreeMap` already iterates in sorted key order) and the
+/// already-computed canonical signatures of its children. Two nodes with
+/// the same signature are guaranteed to compute the same output.
+fn node_signature(
+    meta: &BTreeMap<String, String>,
+    canonical_children: &[u32],
+    signature_of: &BTreeMap<u32, String>,
+) -> String {
+    let mut signature = String::new();
+    for (key, value) in meta {
+        signature.push_str(key);
+        signature.push('=');
+        signature.push_str(value);
+        signature.push(';');
+    }
+    signature.push_str("children:");
+    for child_id in canonical_children {
+        signature.push_str(signature_of.get(child_id).map(String::as_str).unwrap_or(""));
+        signature.push(',');
+    }
+    signature
+}
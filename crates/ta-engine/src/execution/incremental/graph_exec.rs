@@ -1,17 +1,97 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap, VecDeque};
 
 use crate::contracts::RustExecutionPayload;
 use crate::dataset::{self, DatasetPartitionKey};
 
 use super::backend::ExecutePlanError;
 use super::contracts::IncrementalValue;
+use super::graph_cse;
+use super::graph_fold;
+use super::graph_type_check;
 
+/// Validates `payload`, runs [`graph_cse::canonicalize`] over its graph to
+/// collapse duplicate subtrees onto a single node each, runs
+/// [`graph_fold::fold_constants`] to precompute any subtree built entirely
+/// from literals, runs [`graph_type_check::check_and_insert_casts`] over
+/// the result to reject a type mismatch up front (or insert an explicit
+/// cast where one is legal) instead of letting the runtime's
+/// `as_number`/`truthy` coercions guess, executes the checked graph, then
+/// expands the result back so every original node id -- including ones
+/// that were deduplicated away -- gets its output in the returned map.
 pub(crate) fn execute_plan_graph_payload(
     payload: &RustExecutionPayload,
 ) -> Result<BTreeMap<u32, Vec<IncrementalValue>>, ExecutePlanError> {
     payload
         .validate()
         .map_err(ExecutePlanError::InvalidPayload)?;
+
+    let cse = graph_cse::canonicalize(&payload.graph);
+    let folded = graph_fold::fold_constants(&cse.graph);
+    let checked = graph_type_check::check_and_insert_casts(&folded)?;
+    let mut canonical_payload = payload.clone();
+    canonical_payload.graph = checked.graph;
+    let canonical_outputs = execute_canonical_graph(&canonical_payload)?;
+
+    let mut outputs = BTreeMap::new();
+    for node_id in &payload.graph.node_order {
+        let canonical_id = cse.canonical_of.get(node_id).copied().unwrap_or(*node_id);
+        if let Some(values) = canonical_outputs.get(&canonical_id) {
+            outputs.insert(*node_id, values.clone());
+        }
+    }
+    Ok(outputs)
+}
+
+/// One named indicator call to run against flat columns, independent of any
+/// dataset partition or node graph -- the unit [`execute_plan_on_columns`]
+/// consumes. `params` is the same `kw_<name>`/`arg_<n>` meta shape
+/// [`dispatch_call_node`] already reads.
+#[derive(Debug, Clone)]
+pub struct ColumnCallSpec {
+    pub name: String,
+    pub params: BTreeMap<String, String>,
+}
+
+/// Runs each of `specs` against `close` (and `ohlcv`, for indicators that
+/// need more than a close column) through the same name-dispatch
+/// [`dispatch_call_node`] uses for graph `call` nodes, without requiring a
+/// registered dataset or a node graph around it. Lets a caller holding
+/// plain columnar data -- e.g. extracted from a Polars `DataFrame` --
+/// compute a batch of indicators in one pass.
+///
+/// A single-series result is keyed by `spec.name`; a multi-series result
+/// (e.g. `bbands`) is keyed `"{name}.{component}"` for each component (so
+/// `bbands` over two different specs, or the same spec run twice, each get
+/// their own `bbands.upper`/`bbands.middle`/`bbands.lower` triple -- callers
+/// that want multiple distinct output names should vary `spec.name` via
+/// its `bb_upper`/`bb_lower`-style aliases, or post-process the returned
+/// map). Indicators that need OHLCV columns `ohlcv` doesn't have surface
+/// the same "requires ohlcv data" error `dispatch_call_node` already raises.
+pub fn execute_plan_on_columns(
+    close: &[f64],
+    ohlcv: Option<&crate::dataset::OhlcvColumns>,
+    specs: &[ColumnCallSpec],
+) -> Result<BTreeMap<String, Vec<IncrementalValue>>, ExecutePlanError> {
+    let child_series = vec![close.to_vec()];
+    let mut out = BTreeMap::new();
+    for spec in specs {
+        match dispatch_call_node(&spec.name, &spec.params, &child_series, ohlcv)? {
+            NodeOutput::Series(values) => {
+                out.insert(spec.name.clone(), values);
+            }
+            NodeOutput::MultiSeries(components) => {
+                for (component, values) in components {
+                    out.insert(format!("{}.{component}", spec.name), values);
+                }
+            }
+        }
+    }
+    Ok(out)
+}
+
+fn execute_canonical_graph(
+    payload: &RustExecutionPayload,
+) -> Result<BTreeMap<u32, Vec<IncrementalValue>>, ExecutePlanError> {
     let record = dataset::get_dataset(payload.dataset_id)?;
     let partition_key = DatasetPartitionKey {
         symbol: payload.partition.symbol.clone(),
@@ -35,7 +115,22 @@ pub(crate) fn execute_plan_graph_payload(
             timeframe: partition_key.timeframe.clone(),
             data_source: partition_key.source.clone(),
         })?;
+    let timestamps: &[i64] = partition
+        .ohlcv
+        .as_ref()
+        .map(|ohlcv| ohlcv.timestamps.as_slice())
+        .or_else(|| partition.series.values().next().map(|s| s.timestamps.as_slice()))
+        .ok_or_else(|| ExecutePlanError::MissingOhlcv {
+            symbol: partition_key.symbol.clone(),
+            timeframe: partition_key.timeframe.clone(),
+            data_source: partition_key.source.clone(),
+        })?;
     let mut outputs: BTreeMap<u32, Vec<IncrementalValue>> = BTreeMap::new();
+    // Keyed by the indicator's computation identity (canonical name + inputs
+    // + params, but *not* which component was asked for), so a plan that
+    // references e.g. both `bbands.upper` and `bbands.lower` runs `bbands`
+    // once instead of once per referenced component.
+    let mut multi_series_cache: HashMap<String, BTreeMap<String, Vec<IncrementalValue>>> = HashMap::new();
 
     for node_id in &payload.graph.node_order {
         let meta = payload.graph.nodes.get(node_id).ok_or_else(|| {
@@ -236,7 +331,44 @@ pub(crate) fn execute_plan_graph_payload(
                         }
                     })
                     .collect::<Result<Vec<Vec<f64>>, ExecutePlanError>>()?;
-                dispatch_call_node(&name, meta, &child_series, partition.ohlcv.as_ref())?
+
+                let normalized_name = name.trim().to_ascii_lowercase();
+                let cache_key = multi_series_cache_key(
+                    canonical_dispatch_name(&normalized_name),
+                    meta,
+                    &child_ids,
+                );
+                let components = match multi_series_cache.get(&cache_key) {
+                    Some(cached) => cached.clone(),
+                    None => {
+                        let computed = match dispatch_call_node(
+                            &name,
+                            meta,
+                            &child_series,
+                            partition.ohlcv.as_ref(),
+                        )? {
+                            NodeOutput::Series(values) => {
+                                BTreeMap::from([("value".to_string(), values)])
+                            }
+                            NodeOutput::MultiSeries(components) => components,
+                        };
+                        multi_series_cache.insert(cache_key, computed.clone());
+                        computed
+                    }
+                };
+                let output_key = meta
+                    .get("output")
+                    .map(String::as_str)
+                    .unwrap_or_else(|| default_output_key(&normalized_name));
+                components
+                    .get(output_key)
+                    .or_else(|| components.get("value"))
+                    .cloned()
+                    .ok_or_else(|| {
+                        ExecutePlanError::InvalidPayload(format!(
+                            "call node {node_id} has no output named '{output_key}'"
+                        ))
+                    })?
                 }
             }
             "time_shift" => {
@@ -326,6 +458,10 @@ pub(crate) fn execute_plan_graph_payload(
                     .map(|v| match op.as_str() {
                         "not" => IncrementalValue::Bool(!truthy(v)),
                         "neg" => IncrementalValue::Number(-as_number(v)),
+                        // Inserted by graph_type_check::check_and_insert_casts
+                        // where a node expects the other type.
+                        "to_bool" => IncrementalValue::Bool(as_number(v) != 0.0),
+                        "to_num" => IncrementalValue::Number(if truthy(v) { 1.0 } else { 0.0 }),
                         _ => IncrementalValue::Number(as_number(v)),
                     })
                     .collect()
@@ -421,6 +557,35 @@ pub(crate) fn execute_plan_graph_payload(
                 };
                 vec![aggregated; rows]
             }
+            "rolling_aggregate" => {
+                if child_ids.is_empty() {
+                    return Err(ExecutePlanError::InvalidPayload(format!(
+                        "rolling_aggregate node {node_id} requires one child"
+                    )));
+                }
+                let input = outputs.get(&child_ids[0]).ok_or_else(|| {
+                    ExecutePlanError::InvalidPayload(format!(
+                        "missing rolling_aggregate input for node {}",
+                        child_ids[0]
+                    ))
+                })?;
+                let operation = meta
+                    .get("operation")
+                    .cloned()
+                    .unwrap_or_else(|| "sum".to_string());
+                let period_ms = meta
+                    .get("period")
+                    .and_then(|period| parse_duration_ms(period))
+                    .ok_or_else(|| {
+                        ExecutePlanError::InvalidPayload(format!(
+                            "rolling_aggregate node {node_id} requires a valid \"period\" duration (e.g. \"5m\")"
+                        ))
+                    })?;
+                let closed = ClosedWindow::parse(
+                    meta.get("closed").map(|s| s.as_str()).unwrap_or("right"),
+                );
+                compute_rolling_aggregate(input, timestamps, period_ms, closed, &operation)?
+            }
             other => {
                 return Err(ExecutePlanError::InvalidPayload(format!(
                     "unsupported graph node kind: {other}"
@@ -437,7 +602,7 @@ fn to_f64_vec(values: &[IncrementalValue]) -> Vec<f64> {
     values.iter().map(as_number).collect()
 }
 
-fn as_number(value: &IncrementalValue) -> f64 {
+pub(crate) fn as_number(value: &IncrementalValue) -> f64 {
     match value {
         IncrementalValue::Number(v) => *v,
         IncrementalValue::Bool(v) => {
@@ -448,16 +613,18 @@ fn as_number(value: &IncrementalValue) -> f64 {
             }
         }
         IncrementalValue::Text(v) => v.parse::<f64>().unwrap_or(0.0),
-        IncrementalValue::Null => f64::NAN,
+        IncrementalValue::Fields(_) | IncrementalValue::Bytes(_) | IncrementalValue::Null => f64::NAN,
     }
 }
 
-fn truthy(value: &IncrementalValue) -> bool {
+pub(crate) fn truthy(value: &IncrementalValue) -> bool {
     match value {
         IncrementalValue::Null => false,
         IncrementalValue::Bool(v) => *v,
         IncrementalValue::Number(v) => *v != 0.0 && !v.is_nan(),
         IncrementalValue::Text(v) => !v.is_empty(),
+        IncrementalValue::Fields(v) => !v.is_empty(),
+        IncrementalValue::Bytes(v) => !v.is_empty(),
     }
 }
 
@@ -491,15 +658,280 @@ fn apply_time_shift_op(base: &[IncrementalValue], steps: usize, operation: &str)
     out
 }
 
+/// Which edge of a `[start, stop]` window a `rolling_aggregate` node treats
+/// as inclusive, read from `meta["closed"]`. `stop` is always the row's own
+/// timestamp, so `Left`/`None` are what exclude the current row from its
+/// own window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ClosedWindow {
+    Left,
+    Right,
+    Both,
+    None,
+}
+
+impl ClosedWindow {
+    fn parse(value: &str) -> Self {
+        match value {
+            "left" => ClosedWindow::Left,
+            "both" => ClosedWindow::Both,
+            "none" => ClosedWindow::None,
+            _ => ClosedWindow::Right,
+        }
+    }
+
+    fn includes_lower(self, start: i64, t: i64) -> bool {
+        match self {
+            ClosedWindow::Left | ClosedWindow::Both => start <= t,
+            ClosedWindow::Right | ClosedWindow::None => start < t,
+        }
+    }
+
+    fn includes_upper(self, t: i64, stop: i64) -> bool {
+        match self {
+            ClosedWindow::Right | ClosedWindow::Both => t <= stop,
+            ClosedWindow::Left | ClosedWindow::None => t < stop,
+        }
+    }
+}
+
+/// Parses a duration like `"5m"` (digits followed by a `ms`/`s`/`m`/`h`/`d`
+/// unit) into milliseconds. Returns `None` for anything malformed or
+/// non-positive rather than guessing a fallback window.
+fn parse_duration_ms(period: &str) -> Option<i64> {
+    let split_at = period.find(|c: char| !c.is_ascii_digit())?;
+    let (digits, unit) = period.split_at(split_at);
+    let magnitude: i64 = digits.parse().ok()?;
+    let unit_ms = match unit {
+        "ms" => 1,
+        "s" => 1_000,
+        "m" => 60_000,
+        "h" => 3_600_000,
+        "d" => 86_400_000,
+        _ => return None,
+    };
+    let millis = magnitude.checked_mul(unit_ms)?;
+    (millis > 0).then_some(millis)
+}
+
+/// Computes a time-windowed aggregate for each row `i` over
+/// `[timestamps[i] - period_ms, timestamps[i]]`, per `closed`'s boundary
+/// rule. `timestamps` is assumed monotonically increasing (guaranteed by
+/// [`crate::dataset`]'s ingestion), so `lo` only ever advances forward as
+/// `i` does -- an O(n) two-pointer sweep rather than rescanning the window
+/// from scratch for every row. `max`/`min` track their running extremum via
+/// the same monotonic-deque technique as [`super::sliding_extrema`]'s
+/// fixed-size window, adapted here for a variable-size window bounded by
+/// `lo` instead of a fixed capacity.
+fn compute_rolling_aggregate(
+    values: &[IncrementalValue],
+    timestamps: &[i64],
+    period_ms: i64,
+    closed: ClosedWindow,
+    operation: &str,
+) -> Result<Vec<IncrementalValue>, ExecutePlanError> {
+    let n = values.len();
+    let mut out = vec![IncrementalValue::Null; n];
+    let mut included = vec![false; n];
+    let mut cached = vec![0.0f64; n];
+    let mut lo = 0usize;
+    let mut hi = 0usize;
+    let mut sum = 0.0f64;
+    let mut count = 0usize;
+    let mut max_deque: VecDeque<(usize, f64)> = VecDeque::new();
+    let mut min_deque: VecDeque<(usize, f64)> = VecDeque::new();
+
+    for i in 0..n {
+        let t_i = timestamps[i];
+        let start = t_i - period_ms;
+
+        while lo < i && !closed.includes_lower(start, timestamps[lo]) {
+            if included[lo] {
+                sum -= cached[lo];
+                count -= 1;
+            }
+            lo += 1;
+        }
+        while matches!(max_deque.front(), Some(&(idx, _)) if idx < lo) {
+            max_deque.pop_front();
+        }
+        while matches!(min_deque.front(), Some(&(idx, _)) if idx < lo) {
+            min_deque.pop_front();
+        }
+
+        hi = hi.max(lo);
+        while hi <= i && closed.includes_upper(timestamps[hi], t_i) {
+            let j = hi;
+            hi += 1;
+            if matches!(values[j], IncrementalValue::Null) {
+                continue;
+            }
+            let v = as_number(&values[j]);
+            included[j] = true;
+            cached[j] = v;
+            sum += v;
+            count += 1;
+
+            while matches!(max_deque.back(), Some(&(_, back)) if back <= v) {
+                max_deque.pop_back();
+            }
+            max_deque.push_back((j, v));
+            while matches!(min_deque.back(), Some(&(_, back)) if back >= v) {
+                min_deque.pop_back();
+            }
+            min_deque.push_back((j, v));
+        }
+
+        out[i] = match operation {
+            "count" => IncrementalValue::Number(count as f64),
+            "sum" => IncrementalValue::Number(sum),
+            "avg" => {
+                if count == 0 {
+                    IncrementalValue::Null
+                } else {
+                    IncrementalValue::Number(sum / count as f64)
+                }
+            }
+            "max" => max_deque
+                .front()
+                .map(|&(_, v)| IncrementalValue::Number(v))
+                .unwrap_or(IncrementalValue::Null),
+            "min" => min_deque
+                .front()
+                .map(|&(_, v)| IncrementalValue::Number(v))
+                .unwrap_or(IncrementalValue::Null),
+            other => {
+                return Err(ExecutePlanError::InvalidPayload(format!(
+                    "unsupported rolling_aggregate operation: {other}"
+                )))
+            }
+        };
+    }
+
+    Ok(out)
+}
+
+/// A call node's raw compute result, before the "output" selection that
+/// narrows it down to the single series a particular node in the plan
+/// asked for. `MultiSeries` lets the full component set -- e.g. `bbands`'s
+/// `{upper, middle, lower}` -- be computed once and reused by every node
+/// that references a different one of its components.
+pub enum NodeOutput {
+    Series(Vec<IncrementalValue>),
+    MultiSeries(BTreeMap<String, Vec<IncrementalValue>>),
+}
+
+/// Maps a call name to the underlying indicator it shares a computation
+/// with, so e.g. `bb_upper` and `bb_lower` hit the same `multi_series_cache`
+/// entry as `bbands` instead of recomputing it.
+fn canonical_dispatch_name(name: &str) -> &str {
+    match name {
+        "bb_upper" | "bb_lower" => "bbands",
+        "stoch_k" | "stoch_d" => "stochastic",
+        other => other,
+    }
+}
+
+/// Which component of a multi-series indicator a call node returns when its
+/// `meta["output"]` isn't set, preserving each name variant's historical
+/// default (e.g. `bb_lower` defaulted to the lower band before `output`
+/// selection existed).
+fn default_output_key(name: &str) -> &'static str {
+    match name {
+        "bb_upper" | "bbands" | "donchian" | "keltner" => "upper",
+        "bb_lower" => "lower",
+        "stoch_d" => "d",
+        "stoch_k" | "stochastic" => "k",
+        "vortex" => "plus",
+        "elder_ray" => "bull",
+        "fisher" => "fisher",
+        "ichimoku" => "tenkan",
+        "psar" => "sar",
+        "supertrend" => "supertrend",
+        "macd" => "macd",
+        _ => "value",
+    }
+}
+
+/// Cache key for [`multi_series_cache`](execute_canonical_graph): identifies
+/// a call node's *computation*, ignoring the `name`/`output` fields that
+/// only select which already-computed component to return.
+fn multi_series_cache_key(
+    canonical_name: &str,
+    meta: &BTreeMap<String, String>,
+    child_ids: &[u32],
+) -> String {
+    let mut key = String::from(canonical_name);
+    for id in child_ids {
+        key.push(':');
+        key.push_str(&id.to_string());
+    }
+    for (k, v) in meta {
+        if k == "name" || k == "output" {
+            continue;
+        }
+        key.push('|');
+        key.push_str(k);
+        key.push('=');
+        key.push_str(v);
+    }
+    key
+}
+
+/// A user-registered indicator kernel: given the partition's OHLCV data (if
+/// any), the call node's `meta`, and the selected `output` component, it
+/// produces the same [`NodeOutput`] a built-in arm of `dispatch_call_node`
+/// would, so it participates in plans exactly like `rsi` or `atr`.
+/// `get_usize`/`get_f64` are private to this module; a kernel defined
+/// outside it reads `meta`'s `kw_<name>`/`arg_<n>` entries itself.
+pub type IndicatorKernel = dyn Fn(
+        Option<&crate::dataset::OhlcvColumns>,
+        &BTreeMap<String, String>,
+        Option<&str>,
+    ) -> Result<NodeOutput, ExecutePlanError>
+    + Send
+    + Sync;
+
+type IndicatorRegistry = HashMap<String, Box<IndicatorKernel>>;
+
+static INDICATOR_REGISTRY: std::sync::OnceLock<std::sync::RwLock<IndicatorRegistry>> = std::sync::OnceLock::new();
+
+fn indicator_registry() -> &'static std::sync::RwLock<IndicatorRegistry> {
+    INDICATOR_REGISTRY.get_or_init(|| std::sync::RwLock::new(HashMap::new()))
+}
+
+/// Registers `kernel` under `name`, checked by `dispatch_call_node` before
+/// it falls through to its `other =>` error -- this is the escape hatch for
+/// downstream/dynamically-loaded indicators that the built-in `match` can't
+/// grow to cover. Registering a name that is already registered replaces
+/// the earlier kernel for every subsequent call node with that name. A
+/// built-in name is matched by `dispatch_call_node`'s own arms first, so a
+/// kernel registered under one is never called.
+pub fn register_indicator(name: impl Into<String>, kernel: Box<IndicatorKernel>) {
+    indicator_registry()
+        .write()
+        .expect("indicator registry lock poisoned")
+        .insert(name.into(), kernel);
+}
+
+/// Removes `name` from the registry. A no-op when `name` was never
+/// registered, including when `name` names a built-in (built-ins aren't
+/// stored here, so this can't "unregister" one).
+pub fn deregister_indicator(name: &str) {
+    indicator_registry()
+        .write()
+        .expect("indicator registry lock poisoned")
+        .remove(name);
+}
+
 fn dispatch_call_node(
     name: &str,
     meta: &BTreeMap<String, String>,
     child_series: &[Vec<f64>],
     ohlcv: Option<&crate::dataset::OhlcvColumns>,
-) -> Result<Vec<IncrementalValue>, ExecutePlanError> {
+) -> Result<NodeOutput, ExecutePlanError> {
     let normalized = name.trim().to_ascii_lowercase();
     let name = normalized.as_str();
-    let selected_output = meta.get("output").map(|v| v.as_str());
     let default_close = ohlcv.map(|v| v.close.clone()).unwrap_or_default();
     let close = child_series.first().cloned().unwrap_or_else(|| default_close.clone());
     let second = child_series
@@ -513,6 +945,12 @@ fn dispatch_call_node(
 
     let to_num = |values: Vec<f64>| values.into_iter().map(IncrementalValue::Number).collect();
     let to_bool = |values: Vec<bool>| values.into_iter().map(IncrementalValue::Bool).collect();
+    let to_num_multi = |components: Vec<(&str, Vec<f64>)>| -> BTreeMap<String, Vec<IncrementalValue>> {
+        components
+            .into_iter()
+            .map(|(key, values)| (key.to_string(), to_num(values)))
+            .collect()
+    };
 
     let out = match name {
         "select" => {
@@ -528,7 +966,9 @@ fn dispatch_call_node(
                         "select could not resolve source field '{field}'"
                     )));
                 }
-                return Ok(close.iter().copied().map(IncrementalValue::Number).collect());
+                return Ok(NodeOutput::Series(
+                    close.iter().copied().map(IncrementalValue::Number).collect(),
+                ));
             }
             match (field, ohlcv) {
                 ("open", Some(v)) => v.open.iter().copied().map(IncrementalValue::Number).collect(),
@@ -542,7 +982,7 @@ fn dispatch_call_node(
             }
         }
         "sma" | "mean" | "rolling_mean" => {
-            let period = get_usize(meta, "period", "arg_0", 20);
+            let period = get_usize_checked(meta, &PERIOD_ARG0)?;
             to_num(crate::rolling::rolling_mean(&close, period))
         }
         "rolling_median" | "median" => {
@@ -550,7 +990,7 @@ fn dispatch_call_node(
             to_num(crate::rolling::rolling_median(&close, period))
         }
         "ema" | "rolling_ema" => {
-            let period = get_usize(meta, "period", "arg_0", 20);
+            let period = get_usize_checked(meta, &PERIOD_ARG0)?;
             to_num(crate::moving_averages::ema(&close, period))
         }
         "wma" | "rolling_wma" => {
@@ -562,11 +1002,11 @@ fn dispatch_call_node(
             to_num(crate::moving_averages::hma(&close, period))
         }
         "rsi" => {
-            let period = get_usize(meta, "period", "arg_0", 14);
+            let period = get_usize_checked(meta, &RSI_PERIOD)?;
             to_num(crate::momentum::rsi(&close, period))
         }
         "roc" => {
-            let period = get_usize(meta, "period", "arg_0", 12);
+            let period = get_usize_checked(meta, &ROC_PERIOD)?;
             to_num(crate::momentum::roc(&close, period))
         }
         "coppock" => {
@@ -598,26 +1038,31 @@ fn dispatch_call_node(
             })?;
             let period = get_usize(meta, "period", "arg_0", 14);
             let (plus, minus) = crate::momentum::vortex(&ohlcv.high, &ohlcv.low, &ohlcv.close, period);
-            match selected_output {
-                Some("minus") => to_num(minus),
-                _ => to_num(plus),
-            }
+            return Ok(NodeOutput::MultiSeries(to_num_multi(vec![
+                ("plus", plus),
+                ("minus", minus),
+            ])));
         }
         "bbands" | "bb_upper" | "bb_lower" => {
-            let period = get_usize(meta, "period", "arg_0", 20);
-            let std_dev = get_f64(meta, "std_dev", "arg_1", 2.0);
-            let (upper, _middle, lower) = crate::volatility::bbands(&close, period, std_dev);
-            match name {
-                "bb_upper" => to_num(upper),
-                "bb_lower" => to_num(lower),
-                _ => to_num(upper),
-            }
+            let period = get_usize_checked(meta, &PERIOD_ARG0)?;
+            let std_dev = get_f64_checked(meta, &BBANDS_STD_DEV)?;
+            let (upper, middle, lower) = crate::volatility::bbands(
+                &close,
+                period,
+                std_dev,
+                crate::moving_averages::MovingAverageType::Sma,
+            );
+            return Ok(NodeOutput::MultiSeries(to_num_multi(vec![
+                ("upper", upper),
+                ("middle", middle),
+                ("lower", lower),
+            ])));
         }
         "atr" => {
             let ohlcv = ohlcv.ok_or_else(|| {
                 ExecutePlanError::InvalidPayload("atr requires ohlcv data".to_string())
             })?;
-            let period = get_usize(meta, "period", "arg_0", 14);
+            let period = get_usize_checked(meta, &ATR_PERIOD)?;
             to_num(crate::volatility::atr(
                 &ohlcv.high,
                 &ohlcv.low,
@@ -630,8 +1075,12 @@ fn dispatch_call_node(
                 ExecutePlanError::InvalidPayload("donchian requires ohlcv data".to_string())
             })?;
             let period = get_usize(meta, "period", "arg_0", 20);
-            let (upper, _middle, _lower) = crate::volatility::donchian(&ohlcv.high, &ohlcv.low, period);
-            to_num(upper)
+            let (upper, middle, lower) = crate::volatility::donchian(&ohlcv.high, &ohlcv.low, period);
+            return Ok(NodeOutput::MultiSeries(to_num_multi(vec![
+                ("upper", upper),
+                ("middle", middle),
+                ("lower", lower),
+            ])));
         }
         "keltner" => {
             let ohlcv = ohlcv.ok_or_else(|| {
@@ -640,15 +1089,20 @@ fn dispatch_call_node(
             let ema_period = get_usize(meta, "ema_period", "arg_0", 20);
             let atr_period = get_usize(meta, "atr_period", "arg_1", 10);
             let multiplier = get_f64(meta, "multiplier", "arg_2", 2.0);
-            let (upper, _middle, _lower) = crate::volatility::keltner(
+            let (upper, middle, lower) = crate::volatility::keltner(
                 &ohlcv.high,
                 &ohlcv.low,
                 &ohlcv.close,
                 ema_period,
                 atr_period,
                 multiplier,
+                crate::moving_averages::MovingAverageType::Ema,
             );
-            to_num(upper)
+            return Ok(NodeOutput::MultiSeries(to_num_multi(vec![
+                ("upper", upper),
+                ("middle", middle),
+                ("lower", lower),
+            ])));
         }
         "stochastic" | "stoch_k" | "stoch_d" => {
             let ohlcv = ohlcv.ok_or_else(|| {
@@ -659,10 +1113,7 @@ fn dispatch_call_node(
             let smooth = get_usize(meta, "smooth", "arg_2", 1);
             let (k, d) =
                 crate::momentum::stochastic_kd(&ohlcv.high, &ohlcv.low, &ohlcv.close, k_period, d_period, smooth);
-            match name {
-                "stoch_d" => to_num(d),
-                _ => to_num(k),
-            }
+            return Ok(NodeOutput::MultiSeries(to_num_multi(vec![("k", k), ("d", d)])));
         }
         "adx" => {
             let ohlcv = ohlcv.ok_or_else(|| {
@@ -676,12 +1127,19 @@ fn dispatch_call_node(
             let fast = get_usize(meta, "fast_period", "arg_0", 12);
             let slow = get_usize(meta, "slow_period", "arg_1", 26);
             let signal = get_usize(meta, "signal_period", "arg_2", 9);
-            let (macd, signal_line, histogram) = crate::trend::macd(&close, fast, slow, signal);
-            match selected_output {
-                Some("signal") => to_num(signal_line),
-                Some("histogram") => to_num(histogram),
-                _ => to_num(macd),
-            }
+            let (macd, signal_line, histogram) = crate::trend::macd(
+                &close,
+                fast,
+                slow,
+                signal,
+                crate::moving_averages::MovingAverageType::Ema,
+                crate::moving_averages::MovingAverageType::Ema,
+            );
+            return Ok(NodeOutput::MultiSeries(to_num_multi(vec![
+                ("macd", macd),
+                ("signal", signal_line),
+                ("histogram", histogram),
+            ])));
         }
         "elder_ray" => {
             let ohlcv = ohlcv.ok_or_else(|| {
@@ -689,10 +1147,10 @@ fn dispatch_call_node(
             })?;
             let period = get_usize(meta, "period", "arg_0", 13);
             let (bull, bear) = crate::trend::elder_ray(&ohlcv.high, &ohlcv.low, &ohlcv.close, period);
-            match selected_output {
-                Some("bear") => to_num(bear),
-                _ => to_num(bull),
-            }
+            return Ok(NodeOutput::MultiSeries(to_num_multi(vec![
+                ("bull", bull),
+                ("bear", bear),
+            ])));
         }
         "fisher" => {
             let ohlcv = ohlcv.ok_or_else(|| {
@@ -700,10 +1158,10 @@ fn dispatch_call_node(
             })?;
             let period = get_usize(meta, "period", "arg_0", 9);
             let (fisher, signal) = crate::trend::fisher(&ohlcv.high, &ohlcv.low, period);
-            match selected_output {
-                Some("signal") => to_num(signal),
-                _ => to_num(fisher),
-            }
+            return Ok(NodeOutput::MultiSeries(to_num_multi(vec![
+                ("fisher", fisher),
+                ("signal", signal),
+            ])));
         }
         "ichimoku" => {
             let ohlcv = ohlcv.ok_or_else(|| {
@@ -722,13 +1180,13 @@ fn dispatch_call_node(
                 span_b_period,
                 displacement,
             );
-            match selected_output {
-                Some("kijun_sen") => to_num(kijun),
-                Some("senkou_span_a") => to_num(span_a),
-                Some("senkou_span_b") => to_num(span_b),
-                Some("chikou_span") => to_num(chikou),
-                _ => to_num(tenkan),
-            }
+            return Ok(NodeOutput::MultiSeries(to_num_multi(vec![
+                ("tenkan", tenkan),
+                ("kijun_sen", kijun),
+                ("senkou_span_a", span_a),
+                ("senkou_span_b", span_b),
+                ("chikou_span", chikou),
+            ])));
         }
         "psar" => {
             let ohlcv = ohlcv.ok_or_else(|| {
@@ -739,10 +1197,10 @@ fn dispatch_call_node(
             let af_max = get_f64(meta, "af_max", "arg_2", 0.2);
             let (sar, direction) =
                 crate::trend::psar(&ohlcv.high, &ohlcv.low, &ohlcv.close, af_start, af_increment, af_max);
-            match selected_output {
-                Some("direction") => to_num(direction),
-                _ => to_num(sar),
-            }
+            return Ok(NodeOutput::MultiSeries(to_num_multi(vec![
+                ("sar", sar),
+                ("direction", direction),
+            ])));
         }
         "supertrend" => {
             let ohlcv = ohlcv.ok_or_else(|| {
@@ -752,10 +1210,10 @@ fn dispatch_call_node(
             let multiplier = get_f64(meta, "multiplier", "arg_1", 3.0);
             let (supertrend, direction) =
                 crate::trend::supertrend(&ohlcv.high, &ohlcv.low, &ohlcv.close, period, multiplier);
-            match selected_output {
-                Some("direction") => to_num(direction),
-                _ => to_num(supertrend),
-            }
+            return Ok(NodeOutput::MultiSeries(to_num_multi(vec![
+                ("supertrend", supertrend),
+                ("direction", direction),
+            ])));
         }
         "swing_high_at" => {
             let ohlcv = ohlcv.ok_or_else(|| {
@@ -841,13 +1299,20 @@ fn dispatch_call_node(
         "enter" => to_bool(crate::events::enter_channel(&close, &second, &third)),
         "exit" => to_bool(crate::events::exit_channel(&close, &second, &third)),
         other => {
+            if let Some(kernel) = indicator_registry()
+                .read()
+                .expect("indicator registry lock poisoned")
+                .get(other)
+            {
+                return kernel(ohlcv, meta, meta.get("output").map(String::as_str));
+            }
             return Err(ExecutePlanError::InvalidPayload(format!(
                 "unsupported call node in graph executor: {other}"
-            )))
+            )));
         }
     };
 
-    Ok(out)
+    Ok(NodeOutput::Series(out))
 }
 
 fn get_usize(meta: &BTreeMap<String, String>, kw: &str, arg: &str, default: usize) -> usize {
@@ -863,3 +1328,324 @@ fn get_f64(meta: &BTreeMap<String, String>, kw: &str, arg: &str, default: f64) -
         .and_then(|v| v.parse::<f64>().ok())
         .unwrap_or(default)
 }
+
+/// One parameter an indicator accepts: the `kw_<name>`/positional `arg_<n>`
+/// meta keys `get_usize_checked`/`get_f64_checked` read, its default, and
+/// its inclusive valid range (`None` on either side means unbounded).
+#[derive(Debug, Clone, Copy)]
+pub struct ParamSpec {
+    pub name: &'static str,
+    pub arg: &'static str,
+    pub default: f64,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+/// Full description of one indicator's call-node contract, for introspection
+/// by UIs and plan builders that would otherwise have to hardcode the
+/// knowledge buried in `dispatch_call_node`'s match arms.
+#[derive(Debug, Clone, Copy)]
+pub struct IndicatorSpec {
+    pub name: &'static str,
+    pub params: &'static [ParamSpec],
+    pub requires_ohlcv: &'static [&'static str],
+    pub outputs: &'static [&'static str],
+}
+
+const PERIOD_ARG0: ParamSpec = ParamSpec {
+    name: "period",
+    arg: "arg_0",
+    default: 20.0,
+    min: Some(1.0),
+    max: None,
+};
+
+const RSI_PERIOD: ParamSpec = ParamSpec {
+    name: "period",
+    arg: "arg_0",
+    default: 14.0,
+    min: Some(1.0),
+    max: None,
+};
+
+const ROC_PERIOD: ParamSpec = ParamSpec {
+    name: "period",
+    arg: "arg_0",
+    default: 12.0,
+    min: Some(1.0),
+    max: None,
+};
+
+const ATR_PERIOD: ParamSpec = ParamSpec {
+    name: "period",
+    arg: "arg_0",
+    default: 14.0,
+    min: Some(1.0),
+    max: None,
+};
+
+const BBANDS_STD_DEV: ParamSpec = ParamSpec {
+    name: "std_dev",
+    arg: "arg_1",
+    default: 2.0,
+    min: Some(0.0),
+    max: None,
+};
+
+const INDICATOR_SPECS: &[IndicatorSpec] = &[
+    IndicatorSpec {
+        name: "sma",
+        params: &[PERIOD_ARG0],
+        requires_ohlcv: &[],
+        outputs: &[],
+    },
+    IndicatorSpec {
+        name: "ema",
+        params: &[PERIOD_ARG0],
+        requires_ohlcv: &[],
+        outputs: &[],
+    },
+    IndicatorSpec {
+        name: "rsi",
+        params: &[RSI_PERIOD],
+        requires_ohlcv: &[],
+        outputs: &[],
+    },
+    IndicatorSpec {
+        name: "roc",
+        params: &[ROC_PERIOD],
+        requires_ohlcv: &[],
+        outputs: &[],
+    },
+    IndicatorSpec {
+        name: "atr",
+        params: &[ATR_PERIOD],
+        requires_ohlcv: &["high", "low", "close"],
+        outputs: &[],
+    },
+    IndicatorSpec {
+        name: "bbands",
+        params: &[PERIOD_ARG0, BBANDS_STD_DEV],
+        requires_ohlcv: &[],
+        outputs: &["upper", "middle", "lower"],
+    },
+    IndicatorSpec {
+        name: "donchian",
+        params: &[PERIOD_ARG0],
+        requires_ohlcv: &["high", "low"],
+        outputs: &["upper", "middle", "lower"],
+    },
+    IndicatorSpec {
+        name: "macd",
+        params: &[
+            ParamSpec {
+                name: "fast_period",
+                arg: "arg_0",
+                default: 12.0,
+                min: Some(1.0),
+                max: None,
+            },
+            ParamSpec {
+                name: "slow_period",
+                arg: "arg_1",
+                default: 26.0,
+                min: Some(1.0),
+                max: None,
+            },
+            ParamSpec {
+                name: "signal_period",
+                arg: "arg_2",
+                default: 9.0,
+                min: Some(1.0),
+                max: None,
+            },
+        ],
+        requires_ohlcv: &[],
+        outputs: &["macd", "signal", "histogram"],
+    },
+];
+
+/// Looks up `name`'s [`IndicatorSpec`] by its canonical dispatch name (see
+/// [`canonical_dispatch_name`]), so `describe("bb_upper")` and
+/// `describe("bbands")` return the same spec.
+pub fn describe(name: &str) -> Option<&'static IndicatorSpec> {
+    let canonical = canonical_dispatch_name(name);
+    INDICATOR_SPECS.iter().find(|spec| spec.name == canonical)
+}
+
+/// Validating counterpart to [`get_usize`]: parses and range-checks
+/// `spec`'s meta value instead of silently falling back to the default on
+/// bad input.
+fn get_usize_checked(meta: &BTreeMap<String, String>, spec: &ParamSpec) -> Result<usize, ExecutePlanError> {
+    Ok(get_f64_checked(meta, spec)? as usize)
+}
+
+/// Validating counterpart to [`get_f64`]: parses and range-checks `spec`'s
+/// meta value instead of silently falling back to the default on bad input.
+fn get_f64_checked(meta: &BTreeMap<String, String>, spec: &ParamSpec) -> Result<f64, ExecutePlanError> {
+    let raw = meta.get(&format!("kw_{}", spec.name)).or_else(|| meta.get(spec.arg));
+    let value = match raw {
+        Some(raw) => raw.parse::<f64>().map_err(|_| {
+            ExecutePlanError::InvalidPayload(format!(
+                "'{}' must be numeric, got '{raw}'",
+                spec.name
+            ))
+        })?,
+        None => spec.default,
+    };
+    if let Some(min) = spec.min {
+        if value < min {
+            return Err(ExecutePlanError::InvalidPayload(format!(
+                "'{}' must be >= {min}, got {value}",
+                spec.name
+            )));
+        }
+    }
+    if let Some(max) = spec.max {
+        if value > max {
+            return Err(ExecutePlanError::InvalidPayload(format!(
+                "'{}' must be <= {max}, got {value}",
+                spec.name
+            )));
+        }
+    }
+    Ok(value)
+}
+
+/// A single OHLCV bar fed to an [`IncrementalExecutor`] one at a time.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Bar {
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+}
+
+enum IncrementalKernel {
+    Ema(crate::indicators::streaming::EmaState),
+    Rsi(crate::indicators::streaming::RsiState),
+    Atr(crate::indicators::streaming::AtrState),
+    Roc(crate::indicators::streaming::RocState),
+    RollingMax(crate::indicators::streaming::RollingMaxState),
+    RollingMin(crate::indicators::streaming::RollingMinState),
+    Macd(crate::indicators::streaming::MacdState),
+    Donchian {
+        high: crate::indicators::streaming::RollingMaxState,
+        low: crate::indicators::streaming::RollingMinState,
+    },
+    FibLevel {
+        high: crate::indicators::streaming::RollingMaxState,
+        low: crate::indicators::streaming::RollingMinState,
+        level: f64,
+        up: bool,
+    },
+}
+
+/// A per-node O(1)-per-bar twin of a single `dispatch_call_node` branch: it
+/// reads the same `meta` via `get_usize`/`get_f64` so a streaming plan and
+/// the equivalent batch plan agree bar-for-bar, but `push` folds in one bar
+/// at a time instead of recomputing the whole output vector.
+pub struct IncrementalExecutor {
+    kernel: IncrementalKernel,
+    output: String,
+}
+
+impl IncrementalExecutor {
+    pub fn new(name: &str, meta: &BTreeMap<String, String>) -> Result<Self, ExecutePlanError> {
+        let normalized = name.trim().to_ascii_lowercase();
+        let output = meta
+            .get("output")
+            .cloned()
+            .unwrap_or_else(|| default_output_key(&normalized).to_string());
+
+        let kernel = match normalized.as_str() {
+            "ema" | "rolling_ema" => IncrementalKernel::Ema(crate::indicators::streaming::EmaState::new(
+                get_usize(meta, "period", "arg_0", 20),
+            )),
+            "rsi" => IncrementalKernel::Rsi(crate::indicators::streaming::RsiState::new(get_usize(
+                meta, "period", "arg_0", 14,
+            ))),
+            "atr" => IncrementalKernel::Atr(crate::indicators::streaming::AtrState::new(get_usize(
+                meta, "period", "arg_0", 14,
+            ))),
+            "roc" => IncrementalKernel::Roc(crate::indicators::streaming::RocState::new(get_usize(
+                meta, "period", "arg_0", 12,
+            ))),
+            "swing_high_at" => {
+                let period = get_usize(meta, "left", "arg_1", 2) + get_usize(meta, "right", "arg_2", 2) + 1;
+                IncrementalKernel::RollingMax(crate::indicators::streaming::RollingMaxState::new(period))
+            }
+            "swing_low_at" => {
+                let period = get_usize(meta, "left", "arg_1", 2) + get_usize(meta, "right", "arg_2", 2) + 1;
+                IncrementalKernel::RollingMin(crate::indicators::streaming::RollingMinState::new(period))
+            }
+            "donchian" => {
+                let period = get_usize(meta, "period", "arg_0", 20);
+                IncrementalKernel::Donchian {
+                    high: crate::indicators::streaming::RollingMaxState::new(period),
+                    low: crate::indicators::streaming::RollingMinState::new(period),
+                }
+            }
+            "macd" => {
+                let fast = get_usize(meta, "fast_period", "arg_0", 12);
+                let slow = get_usize(meta, "slow_period", "arg_1", 26);
+                let signal = get_usize(meta, "signal_period", "arg_2", 9);
+                IncrementalKernel::Macd(crate::indicators::streaming::MacdState::new(fast, slow, signal))
+            }
+            "fib_level_down" | "fib_down" | "fib_level_up" => {
+                let level = get_f64(meta, "level", "arg_0", 0.618);
+                let period = get_usize(meta, "left", "arg_1", 2) + get_usize(meta, "right", "arg_2", 2) + 1;
+                IncrementalKernel::FibLevel {
+                    high: crate::indicators::streaming::RollingMaxState::new(period),
+                    low: crate::indicators::streaming::RollingMinState::new(period),
+                    level,
+                    up: normalized == "fib_level_up",
+                }
+            }
+            other => {
+                return Err(ExecutePlanError::InvalidPayload(format!(
+                    "unsupported incremental kernel '{other}'"
+                )))
+            }
+        };
+
+        Ok(Self { kernel, output })
+    }
+
+    /// Folds in one bar, returning `None` while the kernel is still warming
+    /// up (matching the `NaN`-seeded prefix the equivalent batch call would
+    /// produce) and `Some(value)` once it has.
+    pub fn push(&mut self, bar: &Bar) -> Option<f64> {
+        match &mut self.kernel {
+            IncrementalKernel::Ema(state) => Some(state.update(bar.close)),
+            IncrementalKernel::Rsi(state) => state.update(bar.close),
+            IncrementalKernel::Atr(state) => state.update(bar.high, bar.low, bar.close),
+            IncrementalKernel::Roc(state) => state.update(bar.close),
+            IncrementalKernel::RollingMax(state) => state.update(bar.high),
+            IncrementalKernel::RollingMin(state) => state.update(bar.low),
+            IncrementalKernel::Donchian { high, low } => {
+                let upper = high.update(bar.high);
+                let lower = low.update(bar.low);
+                match self.output.as_str() {
+                    "lower" => lower,
+                    "middle" => upper.zip(lower).map(|(u, l)| (u + l) / 2.0),
+                    _ => upper,
+                }
+            }
+            IncrementalKernel::Macd(state) => {
+                let (macd, signal, histogram) = state.update(bar.close);
+                Some(match self.output.as_str() {
+                    "signal" => signal,
+                    "histogram" => histogram,
+                    _ => macd,
+                })
+            }
+            IncrementalKernel::FibLevel { high, low, level, up } => {
+                let h = high.update(bar.high);
+                let l = low.update(bar.low);
+                h.zip(l).map(|(h, l)| if *up { l + (h - l) * *level } else { h - (h - l) * *level })
+            }
+        }
+    }
+}
@@ -0,0 +1,162 @@
+//! Constant-folding pass over a [`RustExecutionGraph`].
+//!
+//! Plans frequently contain subtrees built entirely from `literal` nodes
+//! and `binary_op`/`unary_op` arithmetic over them (threshold math like
+//! `100 * 1.5`), which [`super::graph_exec`] would otherwise re-materialize
+//! as a `rows`-length constant vector and recompute on every request.
+//! [`fold_constants`] walks `node_order` -- already topological, children
+//! before parents -- and rewrites any node whose transitive inputs are all
+//! literal into an equivalent `literal` node holding the precomputed
+//! scalar, in place, using the exact same evaluation rules
+//! [`super::graph_exec`]'s `binary_op`/`unary_op` match arms apply at
+//! execution time (including the div-by-zero -> `0.0` and bool/number
+//! coercion behavior), so folding is observationally identical to running
+//! the unfolded node. `source_ref`, `call`, `filter`, `aggregate`,
+//! `rolling_aggregate`, and `time_shift` nodes are left untouched -- their
+//! output depends on the dataset's rows, prior state, or row count, not
+//! purely on their own literal inputs.
+//!
+//! Every original node id is kept in `node_order` (folded nodes simply lose
+//! their children rather than being removed), so every id a caller expects
+//! an output for -- per [`super::graph_exec::execute_plan_graph_payload`]'s
+//! contract -- still gets one.
+
+use std::collections::BTreeMap;
+
+use crate::contracts::RustExecutionGraph;
+
+use super::contracts::IncrementalValue;
+use super::graph_exec::{as_number, truthy};
+
+const FOLDABLE_KINDS: &[&str] = &["literal", "binary_op", "unary_op"];
+
+/// Folds every node in `graph` whose transitive inputs are all literal into
+/// an equivalent literal node (same node id, now childless). Idempotent --
+/// running it again over its own output is a no-op, since every folded node
+/// is already a `literal`.
+pub fn fold_constants(graph: &RustExecutionGraph) -> RustExecutionGraph {
+    let mut folded: BTreeMap<u32, IncrementalValue> = BTreeMap::new();
+    let mut nodes = graph.nodes.clone();
+    let mut edges = graph.edges.clone();
+
+    for &node_id in &graph.node_order {
+        let Some(meta) = graph.nodes.get(&node_id) else {
+            continue;
+        };
+        let kind = meta.get("kind").map(String::as_str).unwrap_or("");
+        if !FOLDABLE_KINDS.contains(&kind) {
+            continue;
+        }
+        let children = graph.edges.get(&node_id).cloned().unwrap_or_default();
+
+        let value = match kind {
+            "literal" => Some(parse_literal(meta)),
+            "binary_op" if children.len() >= 2 => {
+                match (folded.get(&children[0]), folded.get(&children[1])) {
+                    (Some(left), Some(right)) => Some(eval_binary_op(meta, left, right)),
+                    _ => None,
+                }
+            }
+            "unary_op" if !children.is_empty() => {
+                folded.get(&children[0]).map(|value| eval_unary_op(meta, value))
+            }
+            _ => None,
+        };
+
+        let Some(value) = value else {
+            continue;
+        };
+
+        if kind != "literal" {
+            nodes.insert(
+                node_id,
+                BTreeMap::from([
+                    ("kind".to_string(), "literal".to_string()),
+                    ("value".to_string(), format_literal(&value)),
+                ]),
+            );
+            edges.insert(node_id, Vec::new());
+        }
+        folded.insert(node_id, value);
+    }
+
+    RustExecutionGraph {
+        root_id: graph.root_id,
+        node_order: graph.node_order.clone(),
+        nodes,
+        edges,
+    }
+}
+
+/// Mirrors graph_exec's `"literal"` execution branch exactly.
+fn parse_literal(meta: &BTreeMap<String, String>) -> IncrementalValue {
+    let value_str = meta.get("value").map(String::as_str).unwrap_or("0");
+    if value_str.eq_ignore_ascii_case("true") {
+        IncrementalValue::Bool(true)
+    } else if value_str.eq_ignore_ascii_case("false") {
+        IncrementalValue::Bool(false)
+    } else if let Ok(value) = value_str.parse::<f64>() {
+        IncrementalValue::Number(value)
+    } else {
+        IncrementalValue::Text(value_str.to_string())
+    }
+}
+
+fn format_literal(value: &IncrementalValue) -> String {
+    match value {
+        IncrementalValue::Number(v) => v.to_string(),
+        IncrementalValue::Bool(v) => v.to_string(),
+        IncrementalValue::Text(v) => v.clone(),
+        IncrementalValue::Fields(_) | IncrementalValue::Bytes(_) | IncrementalValue::Null => {
+            "null".to_string()
+        }
+    }
+}
+
+/// Mirrors graph_exec's execution-time `"binary_op"` match arm exactly, so
+/// a folded literal's value can never drift from what running the
+/// unfolded node would have produced.
+fn eval_binary_op(
+    meta: &BTreeMap<String, String>,
+    left: &IncrementalValue,
+    right: &IncrementalValue,
+) -> IncrementalValue {
+    let op = meta.get("operator").map(String::as_str).unwrap_or("eq");
+    match op {
+        "gt" => IncrementalValue::Bool(as_number(left) > as_number(right)),
+        "gte" => IncrementalValue::Bool(as_number(left) >= as_number(right)),
+        "lt" => IncrementalValue::Bool(as_number(left) < as_number(right)),
+        "lte" => IncrementalValue::Bool(as_number(left) <= as_number(right)),
+        "eq" => IncrementalValue::Bool(as_number(left) == as_number(right)),
+        "neq" => IncrementalValue::Bool(as_number(left) != as_number(right)),
+        "and" => IncrementalValue::Bool(truthy(left) && truthy(right)),
+        "or" => IncrementalValue::Bool(truthy(left) || truthy(right)),
+        "add" => IncrementalValue::Number(as_number(left) + as_number(right)),
+        "sub" => IncrementalValue::Number(as_number(left) - as_number(right)),
+        "mul" => IncrementalValue::Number(as_number(left) * as_number(right)),
+        "mod" => IncrementalValue::Number(as_number(left) % as_number(right)),
+        "pow" => IncrementalValue::Number(as_number(left).powf(as_number(right))),
+        "div" => {
+            let right_value = as_number(right);
+            if right_value == 0.0 {
+                IncrementalValue::Number(0.0)
+            } else {
+                IncrementalValue::Number(as_number(left) / right_value)
+            }
+        }
+        _ => IncrementalValue::Null,
+    }
+}
+
+/// Mirrors graph_exec's execution-time `"unary_op"` match arm exactly,
+/// including the `to_bool`/`to_num` casts `graph_type_check` inserts.
+fn eval_unary_op(meta: &BTreeMap<String, String>, value: &IncrementalValue) -> IncrementalValue {
+    let op = meta.get("operator").map(String::as_str).unwrap_or("pos");
+    match op {
+        "not" => IncrementalValue::Bool(!truthy(value)),
+        "neg" => IncrementalValue::Number(-as_number(value)),
+        "to_bool" => IncrementalValue::Bool(as_number(value) != 0.0),
+        "to_num" => IncrementalValue::Number(if truthy(value) { 1.0 } else { 0.0 }),
+        _ => IncrementalValue::Number(as_number(value)),
+    }
+}
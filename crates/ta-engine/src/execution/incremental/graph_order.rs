@@ -0,0 +1,81 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use thiserror::Error;
+
+/// A plan's node dependency graph, in the same shape
+/// [`crate::contracts::RustExecutionGraph`] uses: `edges[node]` lists the
+/// node ids `node` reads its *inputs* from, not the nodes that read from
+/// `node` -- the same direction `graph_exec`'s `child_ids` lookup expects.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct DependencyGraph {
+    pub nodes: Vec<u32>,
+    pub edges: BTreeMap<u32, Vec<u32>>,
+}
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum GraphOrderError {
+    #[error("dependency graph has a cycle involving nodes: {0:?}")]
+    CycleDetected(Vec<u32>),
+}
+
+impl DependencyGraph {
+    pub fn new(nodes: Vec<u32>, edges: BTreeMap<u32, Vec<u32>>) -> Self {
+        Self { nodes, edges }
+    }
+
+    /// Computes a deterministic evaluation order via Kahn's algorithm: each
+    /// node's in-degree starts at its own input count, nodes already at
+    /// zero in-degree (no inputs, e.g. a `source_ref` or `literal`) seed the
+    /// queue in node-id order, and popping a node decrements the in-degree
+    /// of every node that reads from it, enqueuing any that reach zero.
+    /// Queue order is deterministic because both the seed set and every
+    /// dependents list are built by iterating `nodes`/`edges` in their
+    /// existing (id-sorted) order. If fewer nodes come out than went in,
+    /// whatever is left with nonzero in-degree is on or downstream of a
+    /// cycle.
+    pub fn topological_order(&self) -> Result<Vec<u32>, GraphOrderError> {
+        let mut in_degree: BTreeMap<u32, usize> = self.nodes.iter().map(|&n| (n, 0)).collect();
+        let mut dependents: BTreeMap<u32, Vec<u32>> =
+            self.nodes.iter().map(|&n| (n, Vec::new())).collect();
+
+        for &node in &self.nodes {
+            let inputs = self.edges.get(&node).map(Vec::as_slice).unwrap_or(&[]);
+            if let Some(degree) = in_degree.get_mut(&node) {
+                *degree += inputs.len();
+            }
+            for &input in inputs {
+                dependents.entry(input).or_default().push(node);
+            }
+        }
+
+        let mut queue: VecDeque<u32> = in_degree
+            .iter()
+            .filter(|(_, &degree)| degree == 0)
+            .map(|(&node, _)| node)
+            .collect();
+
+        let mut order = Vec::with_capacity(self.nodes.len());
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+            for &dependent in dependents.get(&node).map(Vec::as_slice).unwrap_or(&[]) {
+                if let Some(degree) = in_degree.get_mut(&dependent) {
+                    *degree -= 1;
+                    if *degree == 0 {
+                        queue.push_back(dependent);
+                    }
+                }
+            }
+        }
+
+        if order.len() < self.nodes.len() {
+            let remaining: Vec<u32> = in_degree
+                .into_iter()
+                .filter(|(_, degree)| *degree > 0)
+                .map(|(node, _)| node)
+                .collect();
+            return Err(GraphOrderError::CycleDetected(remaining));
+        }
+
+        Ok(order)
+    }
+}
@@ -0,0 +1,207 @@
+//! Static type-checking pass over a [`RustExecutionGraph`], run after
+//! [`super::graph_cse::canonicalize`] and before execution so a malformed
+//! plan is rejected with a precise diagnostic instead of producing
+//! `Null`/`NaN` deep inside [`super::graph_exec`]'s implicit
+//! `as_number`/`truthy` coercions.
+//!
+//! [`check_and_insert_casts`] walks `node_order` -- already topological,
+//! children before parents -- folding a [`ValueType`] per node: each
+//! node's children are popped off the running per-node type stack in
+//! child order and checked against what that node kind expects, then the
+//! node's own output type is pushed for its parents to consume. When a
+//! child's actual type doesn't match but a legal coercion exists (num -> bool
+//! via `!= 0`, bool -> num via `0`/`1`), an explicit `unary_op` cast node is
+//! spliced onto that edge instead of leaving the runtime to guess. When no
+//! coercion is legal, checking fails with an [`ExecutePlanError::InvalidPayload`]
+//! naming the node id, the child's stack position, and the expected/actual
+//! types.
+
+use std::collections::BTreeMap;
+
+use crate::contracts::RustExecutionGraph;
+
+use super::backend::ExecutePlanError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueType {
+    Number,
+    Bool,
+    Text,
+    Null,
+}
+
+impl ValueType {
+    fn label(self) -> &'static str {
+        match self {
+            ValueType::Number => "Number",
+            ValueType::Bool => "Bool",
+            ValueType::Text => "Text",
+            ValueType::Null => "Null",
+        }
+    }
+}
+
+const BOOL_CALL_NAMES: &[&str] = &[
+    "crossup",
+    "crossdown",
+    "cross",
+    "rising",
+    "falling",
+    "rising_pct",
+    "falling_pct",
+    "in_channel",
+    "out",
+    "enter",
+    "exit",
+];
+
+pub struct TypeCheckedGraph {
+    pub graph: RustExecutionGraph,
+    pub output_type_of: BTreeMap<u32, ValueType>,
+}
+
+/// Computes an output [`ValueType`] for every node in `graph` and checks it
+/// against what each consuming node expects, inserting a cast node onto any
+/// edge that needs one. Returns the (possibly larger) graph plus the output
+/// type of every node, original and inserted.
+pub fn check_and_insert_casts(graph: &RustExecutionGraph) -> Result<TypeCheckedGraph, ExecutePlanError> {
+    let mut node_order = graph.node_order.clone();
+    let mut nodes = graph.nodes.clone();
+    let mut edges = graph.edges.clone();
+    let mut output_type_of: BTreeMap<u32, ValueType> = BTreeMap::new();
+    let mut next_cast_id = graph.node_order.iter().copied().max().unwrap_or(0) + 1;
+
+    for &node_id in &graph.node_order {
+        let Some(meta) = nodes.get(&node_id).cloned() else {
+            continue;
+        };
+        let kind = meta.get("kind").map(String::as_str).unwrap_or("");
+        let children = edges.get(&node_id).cloned().unwrap_or_default();
+
+        let expected = expected_child_types(kind, &meta, children.len());
+        let mut checked_children = children.clone();
+        for (position, &child_id) in children.iter().enumerate() {
+            let Some(&expected_type) = expected.get(&position) else {
+                continue;
+            };
+            let actual_type = output_type_of.get(&child_id).copied().unwrap_or(ValueType::Number);
+            if actual_type == expected_type {
+                continue;
+            }
+            let cast_operator = match (actual_type, expected_type) {
+                (ValueType::Number, ValueType::Bool) => "to_bool",
+                (ValueType::Bool, ValueType::Number) => "to_num",
+                _ => {
+                    return Err(ExecutePlanError::InvalidPayload(format!(
+                        "node {node_id} input {position}: expected {}, found {} with no legal coercion",
+                        expected_type.label(),
+                        actual_type.label()
+                    )));
+                }
+            };
+            let cast_id = next_cast_id;
+            next_cast_id += 1;
+            nodes.insert(
+                cast_id,
+                BTreeMap::from([
+                    ("kind".to_string(), "unary_op".to_string()),
+                    ("operator".to_string(), cast_operator.to_string()),
+                ]),
+            );
+            edges.insert(cast_id, vec![child_id]);
+            let insert_at = node_order
+                .iter()
+                .position(|&id| id == node_id)
+                .unwrap_or(node_order.len());
+            node_order.insert(insert_at, cast_id);
+            output_type_of.insert(cast_id, expected_type);
+            checked_children[position] = cast_id;
+        }
+        let child_output_types: Vec<ValueType> = checked_children
+            .iter()
+            .map(|child_id| output_type_of.get(child_id).copied().unwrap_or(ValueType::Number))
+            .collect();
+        edges.insert(node_id, checked_children);
+
+        output_type_of.insert(node_id, output_type(kind, &meta, &child_output_types));
+    }
+
+    Ok(TypeCheckedGraph {
+        graph: RustExecutionGraph {
+            root_id: graph.root_id,
+            node_order,
+            nodes,
+            edges,
+        },
+        output_type_of,
+    })
+}
+
+/// The type expected at each checked child position of `kind`, keyed by
+/// stack position. A position with no entry isn't checked -- either that
+/// child carries no type constraint (e.g. `filter`'s input, `call`'s
+/// arguments) or `child_count` shows the runtime's own arity check already
+/// has it covered.
+fn expected_child_types(kind: &str, meta: &BTreeMap<String, String>, child_count: usize) -> BTreeMap<usize, ValueType> {
+    match kind {
+        "binary_op" => {
+            let operator = meta.get("operator").map(String::as_str).unwrap_or("eq");
+            match operator {
+                "and" | "or" => BTreeMap::from([(0, ValueType::Bool), (1, ValueType::Bool)]),
+                "gt" | "gte" | "lt" | "lte" | "eq" | "neq" | "add" | "sub" | "mul" | "mod" | "pow" | "div" => {
+                    BTreeMap::from([(0, ValueType::Number), (1, ValueType::Number)])
+                }
+                _ => BTreeMap::new(),
+            }
+        }
+        "unary_op" => match meta.get("operator").map(String::as_str).unwrap_or("pos") {
+            "not" => BTreeMap::from([(0, ValueType::Bool)]),
+            "neg" | "pos" => BTreeMap::from([(0, ValueType::Number)]),
+            _ => BTreeMap::new(),
+        },
+        "filter" if child_count >= 2 => BTreeMap::from([(1, ValueType::Bool)]),
+        "aggregate" | "rolling_aggregate" | "time_shift" => BTreeMap::from([(0, ValueType::Number)]),
+        _ => BTreeMap::new(),
+    }
+}
+
+/// The output type of `kind` given its own metadata and the (already
+/// checked/cast) output types of its children in child order.
+fn output_type(kind: &str, meta: &BTreeMap<String, String>, child_output_types: &[ValueType]) -> ValueType {
+    match kind {
+        "filter" => child_output_types.first().copied().unwrap_or(ValueType::Null),
+        "source_ref" => ValueType::Number,
+        "literal" => {
+            let value_str = meta.get("value").map(String::as_str).unwrap_or("0");
+            if value_str.eq_ignore_ascii_case("true") || value_str.eq_ignore_ascii_case("false") {
+                ValueType::Bool
+            } else if value_str.parse::<f64>().is_ok() {
+                ValueType::Number
+            } else {
+                ValueType::Text
+            }
+        }
+        "call" => {
+            let name = meta
+                .get("name")
+                .map(|n| n.trim().to_ascii_lowercase())
+                .unwrap_or_default();
+            if BOOL_CALL_NAMES.contains(&name.as_str()) {
+                ValueType::Bool
+            } else {
+                ValueType::Number
+            }
+        }
+        "time_shift" | "aggregate" | "rolling_aggregate" => ValueType::Number,
+        "binary_op" => match meta.get("operator").map(String::as_str).unwrap_or("eq") {
+            "add" | "sub" | "mul" | "mod" | "pow" | "div" => ValueType::Number,
+            "gt" | "gte" | "lt" | "lte" | "eq" | "neq" | "and" | "or" => ValueType::Bool,
+            _ => ValueType::Null,
+        },
+        "unary_op" => match meta.get("operator").map(String::as_str).unwrap_or("pos") {
+            "not" | "to_bool" => ValueType::Bool,
+            _ => ValueType::Number,
+        },
+        _ => ValueType::Number,
+    }
+}
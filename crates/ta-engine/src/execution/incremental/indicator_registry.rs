@@ -0,0 +1,182 @@
+//! Caches one [`IncrementalBackend`] node per `(KernelId, interval, params)`
+//! combination, backfilling it from stored history the first time it's
+//! requested and otherwise advancing it only on newly closed bars. Mirrors
+//! the bbgo `initAndBind`/`allocateSimpleIndicator` caching-and-prefill
+//! design: a streaming caller asking for the same indicator/timeframe pair
+//! twice gets back the same warmed-up instance instead of paying for
+//! re-registration and a full history replay every time.
+
+use std::collections::BTreeMap;
+
+use super::backend::{ExecutePlanError, IncrementalBackend, KernelStepRequest, StepInputSource};
+use super::contracts::IncrementalValue;
+use super::kernel_registry::KernelId;
+
+/// Identifies one cached kernel instance: which indicator, on which
+/// timeframe, configured with which params. `params` is rendered to
+/// strings rather than keyed by raw [`IncrementalValue`] so the key stays
+/// `Ord` without pulling `f64` into a total order.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct IndicatorInstanceKey {
+    pub kernel_id: KernelId,
+    pub interval: String,
+    params: Vec<(String, String)>,
+}
+
+impl IndicatorInstanceKey {
+    fn new(kernel_id: KernelId, interval: &str, params: &BTreeMap<String, IncrementalValue>) -> Self {
+        Self {
+            kernel_id,
+            interval: interval.to_string(),
+            params: params
+                .iter()
+                .map(|(name, value)| (name.clone(), render_param(value)))
+                .collect(),
+        }
+    }
+}
+
+fn render_param(value: &IncrementalValue) -> String {
+    match value {
+        IncrementalValue::Number(n) => n.to_string(),
+        IncrementalValue::Bool(b) => b.to_string(),
+        IncrementalValue::Text(s) => s.clone(),
+        IncrementalValue::Fields(fields) => fields
+            .iter()
+            .map(f64::to_string)
+            .collect::<Vec<_>>()
+            .join(","),
+        IncrementalValue::Bytes(bytes) => format!("{bytes:?}"),
+        IncrementalValue::Null => "null".to_string(),
+    }
+}
+
+#[derive(Debug, Clone)]
+struct IndicatorInstance {
+    node_id: u32,
+    input_field: String,
+    kwargs: BTreeMap<String, IncrementalValue>,
+}
+
+/// Per-interval bookkeeping: separate stream ids for the one-shot backfill
+/// replay and the ongoing live feed, so a freshly allocated node's backfill
+/// event indices never collide with the live stream's own counter (each
+/// [`super::vector_clock::VectorClock`] tracks dominance per stream id).
+#[derive(Debug, Clone, Copy)]
+struct IntervalStream {
+    backfill_stream_id: u32,
+    live_stream_id: u32,
+    next_live_event_index: u64,
+}
+
+/// Lazily allocates and caches incremental kernel instances keyed by
+/// `(KernelId, interval, params)`, so requesting the same indicator on the
+/// same timeframe twice returns the same warmed-up state instead of
+/// recomputing it from scratch.
+#[derive(Debug, Clone, Default)]
+pub struct IndicatorInstanceRegistry {
+    backend: IncrementalBackend,
+    next_node_id: u32,
+    next_stream_id: u32,
+    interval_streams: BTreeMap<String, IntervalStream>,
+    instances: BTreeMap<IndicatorInstanceKey, IndicatorInstance>,
+}
+
+impl IndicatorInstanceRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached node id for `(kernel_id, interval, params)`,
+    /// allocating a fresh kernel instance and replaying `history` through it
+    /// via [`IncrementalBackend::step`] if this combination hasn't been
+    /// requested before.
+    pub fn get_or_create(
+        &mut self,
+        kernel_id: KernelId,
+        interval: &str,
+        input_field: &str,
+        params: &BTreeMap<String, IncrementalValue>,
+        history: &[BTreeMap<String, IncrementalValue>],
+    ) -> Result<u32, ExecutePlanError> {
+        let key = IndicatorInstanceKey::new(kernel_id, interval, params);
+        if let Some(instance) = self.instances.get(&key) {
+            return Ok(instance.node_id);
+        }
+
+        let node_id = self.next_node_id;
+        self.next_node_id += 1;
+        let stream = self.interval_stream(interval);
+        let request = [KernelStepRequest {
+            node_id,
+            kernel_id,
+            input: StepInputSource::TickField(input_field.to_string()),
+            kwargs: params.clone(),
+        }];
+        for (offset, candle) in history.iter().enumerate() {
+            self.backend
+                .step(stream.backfill_stream_id, offset as u64, &request, candle)?;
+        }
+
+        self.instances.insert(
+            key,
+            IndicatorInstance {
+                node_id,
+                input_field: input_field.to_string(),
+                kwargs: params.clone(),
+            },
+        );
+        Ok(node_id)
+    }
+
+    /// Advances every kernel instance subscribed to `interval` with one
+    /// newly closed bar's fields, returning each advanced instance's fresh
+    /// output keyed by its node id.
+    pub fn on_bar_closed(
+        &mut self,
+        interval: &str,
+        tick: &BTreeMap<String, IncrementalValue>,
+    ) -> Result<BTreeMap<u32, IncrementalValue>, ExecutePlanError> {
+        let requests: Vec<KernelStepRequest> = self
+            .instances
+            .iter()
+            .filter(|(key, _)| key.interval == interval)
+            .map(|(key, instance)| KernelStepRequest {
+                node_id: instance.node_id,
+                kernel_id: key.kernel_id,
+                input: StepInputSource::TickField(instance.input_field.clone()),
+                kwargs: instance.kwargs.clone(),
+            })
+            .collect();
+
+        if requests.is_empty() {
+            return Ok(BTreeMap::new());
+        }
+
+        let stream = self.interval_stream(interval);
+        let event_index = stream.next_live_event_index;
+        let live_stream_id = stream.live_stream_id;
+        self.interval_streams
+            .get_mut(interval)
+            .expect("interval_stream just allocated this entry")
+            .next_live_event_index += 1;
+
+        self.backend.step(live_stream_id, event_index, &requests, tick)
+    }
+
+    fn interval_stream(&mut self, interval: &str) -> IntervalStream {
+        if let Some(stream) = self.interval_streams.get(interval) {
+            return *stream;
+        }
+        let backfill_stream_id = self.next_stream_id;
+        let live_stream_id = self.next_stream_id + 1;
+        self.next_stream_id += 2;
+        let stream = IntervalStream {
+            backfill_stream_id,
+            live_stream_id,
+            next_live_event_index: 0,
+        };
+        self.interval_streams.insert(interval.to_string(), stream);
+        stream
+    }
+}
@@ -0,0 +1,78 @@
+/// A dense, `Vec`-indexed map keyed by small non-negative integers (plan
+/// node ids), for O(1) get/insert where a `BTreeMap<u32, V>` pays a log-n
+/// tree walk per lookup on every tick. Vec-indexed rather than a two-level
+/// page table since a single plan's node ids are small and densely
+/// assigned; `Option<V>`'s own discriminant doubles as the presence bit, so
+/// there's no separate bitset to keep in sync with the slots.
+#[derive(Debug, Clone, Default)]
+pub struct IntMap<V> {
+    slots: Vec<Option<V>>,
+    len: usize,
+}
+
+impl<V> IntMap<V> {
+    pub fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.slots.clear();
+        self.len = 0;
+    }
+
+    pub fn get(&self, key: u32) -> Option<&V> {
+        self.slots.get(key as usize)?.as_ref()
+    }
+
+    pub fn contains_key(&self, key: u32) -> bool {
+        self.get(key).is_some()
+    }
+
+    pub fn insert(&mut self, key: u32, value: V) -> Option<V> {
+        let index = key as usize;
+        if index >= self.slots.len() {
+            self.slots.resize_with(index + 1, || None);
+        }
+        let previous = self.slots[index].replace(value);
+        if previous.is_none() {
+            self.len += 1;
+        }
+        previous
+    }
+
+    pub fn remove(&mut self, key: u32) -> Option<V> {
+        let removed = self.slots.get_mut(key as usize).and_then(|slot| slot.take());
+        if removed.is_some() {
+            self.len -= 1;
+        }
+        removed
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &V)> {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(index, slot)| slot.as_ref().map(|value| (index as u32, value)))
+    }
+}
+
+impl<V> FromIterator<(u32, V)> for IntMap<V> {
+    fn from_iter<I: IntoIterator<Item = (u32, V)>>(iter: I) -> Self {
+        let mut map = IntMap::new();
+        for (key, value) in iter {
+            map.insert(key, value);
+        }
+        map
+    }
+}
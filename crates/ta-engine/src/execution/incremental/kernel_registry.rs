@@ -0,0 +1,113 @@
+use std::collections::BTreeMap;
+
+use super::contracts::IncrementalValue;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum KernelId {
+    Rsi,
+    Atr,
+    Stochastic,
+    Macd,
+    Bbands,
+    Adx,
+    Vwap,
+    Obv,
+    Cmf,
+    KlingerVf,
+    Cci,
+    WilliamsR,
+    Mfi,
+    Vortex,
+    Cmo,
+}
+
+impl KernelId {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "rsi" => Some(Self::Rsi),
+            "atr" => Some(Self::Atr),
+            "stochastic" => Some(Self::Stochastic),
+            "macd" => Some(Self::Macd),
+            "bbands" => Some(Self::Bbands),
+            "adx" => Some(Self::Adx),
+            "vwap" => Some(Self::Vwap),
+            "obv" => Some(Self::Obv),
+            "cmf" => Some(Self::Cmf),
+            "klinger_vf" => Some(Self::KlingerVf),
+            "cci" => Some(Self::Cci),
+            "williams_r" => Some(Self::WilliamsR),
+            "mfi" => Some(Self::Mfi),
+            "vortex" => Some(Self::Vortex),
+            "cmo" => Some(Self::Cmo),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Rsi => "rsi",
+            Self::Atr => "atr",
+            Self::Stochastic => "stochastic",
+            Self::Macd => "macd",
+            Self::Bbands => "bbands",
+            Self::Adx => "adx",
+            Self::Vwap => "vwap",
+            Self::Obv => "obv",
+            Self::Cmf => "cmf",
+            Self::KlingerVf => "klinger_vf",
+            Self::Cci => "cci",
+            Self::WilliamsR => "williams_r",
+            Self::Mfi => "mfi",
+            Self::Vortex => "vortex",
+            Self::Cmo => "cmo",
+        }
+    }
+}
+
+pub fn coerce_incremental_input(
+    kernel_id: KernelId,
+    input_value: IncrementalValue,
+    tick: &BTreeMap<String, IncrementalValue>,
+    prev_close: Option<f64>,
+) -> IncrementalValue {
+    match kernel_id {
+        KernelId::Atr => {
+            let high = get_num(tick, "high").unwrap_or(0.0);
+            let low = get_num(tick, "low").unwrap_or(0.0);
+            let close = get_num(tick, "close").unwrap_or(0.0);
+
+            let mut tr = high - low;
+            if let Some(prev) = prev_close {
+                tr = tr.max((high - prev).abs()).max((low - prev).abs());
+            }
+            let _ = close;
+            IncrementalValue::Number(tr)
+        }
+        KernelId::Stochastic | KernelId::Adx | KernelId::Cci | KernelId::WilliamsR | KernelId::Vortex => {
+            let h = get_num(tick, "high").unwrap_or(0.0);
+            let l = get_num(tick, "low").unwrap_or(0.0);
+            let c = get_num(tick, "close").unwrap_or(0.0);
+            IncrementalValue::Fields(vec![h, l, c])
+        }
+        KernelId::Vwap | KernelId::Cmf | KernelId::KlingerVf | KernelId::Mfi => {
+            let h = get_num(tick, "high").unwrap_or(0.0);
+            let l = get_num(tick, "low").unwrap_or(0.0);
+            let c = get_num(tick, "close").unwrap_or(0.0);
+            let v = get_num(tick, "volume").unwrap_or(0.0);
+            IncrementalValue::Fields(vec![h, l, c, v])
+        }
+        KernelId::Obv => {
+            let c = get_num(tick, "close").unwrap_or(0.0);
+            let v = get_num(tick, "volume").unwrap_or(0.0);
+            IncrementalValue::Fields(vec![c, v])
+        }
+        KernelId::Macd | KernelId::Bbands | KernelId::Rsi | KernelId::Cmo => input_value,
+    }
+}
+
+fn get_num(tick: &BTreeMap<String, IncrementalValue>, key: &str) -> Option<f64> {
+    match tick.get(key) {
+        Some(IncrementalValue::Number(n)) => Some(*n),
+        _ => None,
+    }
+}
@@ -0,0 +1,293 @@
+use std::collections::BTreeMap;
+
+use super::contracts::IncrementalValue;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceRef {
+    pub source: String,
+    pub field: String,
+}
+
+pub fn eval_source_ref_step(
+    node: &SourceRef,
+    tick: &BTreeMap<String, IncrementalValue>,
+) -> IncrementalValue {
+    let key1 = format!("{}.{}", node.source, node.field);
+    if let Some(v) = tick.get(&key1) {
+        return v.clone();
+    }
+    if let Some(v) = tick.get(&node.field) {
+        return v.clone();
+    }
+    IncrementalValue::Null
+}
+
+pub fn eval_literal_step(value: &IncrementalValue) -> IncrementalValue {
+    value.clone()
+}
+
+pub fn eval_binary_step(
+    op: &str,
+    left: &IncrementalValue,
+    right: &IncrementalValue,
+) -> IncrementalValue {
+    match (op, left, right) {
+        (_, IncrementalValue::Null, _) | (_, _, IncrementalValue::Null) => IncrementalValue::Null,
+        ("add", IncrementalValue::Number(l), IncrementalValue::Number(r)) => {
+            IncrementalValue::Number(l + r)
+        }
+        ("sub", IncrementalValue::Number(l), IncrementalValue::Number(r)) => {
+            IncrementalValue::Number(l - r)
+        }
+        ("mul", IncrementalValue::Number(l), IncrementalValue::Number(r)) => {
+            IncrementalValue::Number(l * r)
+        }
+        ("div", IncrementalValue::Number(_), IncrementalValue::Number(r)) if *r == 0.0 => {
+            IncrementalValue::Number(0.0)
+        }
+        ("div", IncrementalValue::Number(l), IncrementalValue::Number(r)) => {
+            IncrementalValue::Number(l / r)
+        }
+        ("eq", IncrementalValue::Number(l), IncrementalValue::Number(r)) => {
+            IncrementalValue::Bool(l == r)
+        }
+        ("gt", IncrementalValue::Number(l), IncrementalValue::Number(r)) => {
+            IncrementalValue::Bool(l > r)
+        }
+        ("lt", IncrementalValue::Number(l), IncrementalValue::Number(r)) => {
+            IncrementalValue::Bool(l < r)
+        }
+        ("and", l, r) => IncrementalValue::Bool(truthy(l) && truthy(r)),
+        ("or", l, r) => IncrementalValue::Bool(truthy(l) || truthy(r)),
+        _ => IncrementalValue::Null,
+    }
+}
+
+pub fn eval_unary_step(op: &str, value: &IncrementalValue) -> IncrementalValue {
+    match (op, value) {
+        (_, IncrementalValue::Null) => IncrementalValue::Null,
+        ("neg", IncrementalValue::Number(v)) => IncrementalValue::Number(-v),
+        ("pos", IncrementalValue::Number(v)) => IncrementalValue::Number(*v),
+        ("not", v) => IncrementalValue::Bool(!truthy(v)),
+        _ => IncrementalValue::Null,
+    }
+}
+
+pub fn eval_filter_step(
+    value: &IncrementalValue,
+    condition: &IncrementalValue,
+) -> IncrementalValue {
+    if truthy(condition) {
+        value.clone()
+    } else {
+        IncrementalValue::Null
+    }
+}
+
+/// Running aggregate over a stream of ticks, fed one value at a time via
+/// `eval_aggregate_step`. `mean`/`m2` track Welford's online moments so
+/// `var`/`std`/`zscore` never need to revisit prior history. `var`/`std`/
+/// `zscore` are always computed over the full history, even when `window`
+/// narrows `sum`/`avg`/`max`/`min`/`count` to the trailing `N` ticks.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AggregateState {
+    pub count: u64,
+    pub sum: f64,
+    pub max: Option<f64>,
+    pub min: Option<f64>,
+    pub mean: f64,
+    pub m2: f64,
+    window: Option<WindowState>,
+}
+
+/// Sliding-window state for `sum`/`avg`/`max`/`min`/`count`: a ring buffer of
+/// the last `size` values plus a running sum, and a monotonic deque per
+/// extremum so the window max/min are O(1) amortized (values are pushed and
+/// popped at most once each).
+#[derive(Debug, Clone, PartialEq)]
+struct WindowState {
+    size: usize,
+    index: usize,
+    ring: std::collections::VecDeque<f64>,
+    sum: f64,
+    max_deque: std::collections::VecDeque<(usize, f64)>,
+    min_deque: std::collections::VecDeque<(usize, f64)>,
+}
+
+impl WindowState {
+    fn new(size: usize) -> Self {
+        Self {
+            size: size.max(1),
+            index: 0,
+            ring: std::collections::VecDeque::new(),
+            sum: 0.0,
+            max_deque: std::collections::VecDeque::new(),
+            min_deque: std::collections::VecDeque::new(),
+        }
+    }
+
+    fn push(&mut self, value: f64) {
+        self.ring.push_back(value);
+        self.sum += value;
+        if self.ring.len() > self.size {
+            if let Some(evicted) = self.ring.pop_front() {
+                self.sum -= evicted;
+            }
+        }
+
+        while matches!(self.max_deque.back(), Some(&(_, back)) if back <= value) {
+            self.max_deque.pop_back();
+        }
+        self.max_deque.push_back((self.index, value));
+        while matches!(self.max_deque.front(), Some(&(idx, _)) if idx + self.size <= self.index) {
+            self.max_deque.pop_front();
+        }
+
+        while matches!(self.min_deque.back(), Some(&(_, back)) if back >= value) {
+            self.min_deque.pop_back();
+        }
+        self.min_deque.push_back((self.index, value));
+        while matches!(self.min_deque.front(), Some(&(idx, _)) if idx + self.size <= self.index) {
+            self.min_deque.pop_front();
+        }
+
+        self.index += 1;
+    }
+
+    fn count(&self) -> u64 {
+        self.ring.len() as u64
+    }
+
+    fn max(&self) -> Option<f64> {
+        self.max_deque.front().map(|&(_, m)| m)
+    }
+
+    fn min(&self) -> Option<f64> {
+        self.min_deque.front().map(|&(_, m)| m)
+    }
+}
+
+/// Evaluates one tick of an aggregate node. `window` is `None` for an
+/// unbounded cumulative aggregate, or `Some(n)` to narrow `sum`/`avg`/`max`/
+/// `min`/`count` to the trailing `n` numeric values; `var`/`std` are never
+/// windowed.
+pub fn eval_aggregate_step(
+    op: &str,
+    window: Option<usize>,
+    value: &IncrementalValue,
+    state: &mut AggregateState,
+) -> IncrementalValue {
+    if let IncrementalValue::Number(v) = value {
+        state.count += 1;
+        state.sum += *v;
+        state.max = Some(state.max.map_or(*v, |m| m.max(*v)));
+        state.min = Some(state.min.map_or(*v, |m| m.min(*v)));
+
+        let delta = *v - state.mean;
+        state.mean += delta / state.count as f64;
+        let delta2 = *v - state.mean;
+        state.m2 += delta * delta2;
+
+        if let Some(size) = window {
+            state.window.get_or_insert_with(|| WindowState::new(size)).push(*v);
+        }
+    }
+
+    let (count, sum, max, min) = match &state.window {
+        Some(win) => (win.count(), win.sum, win.max(), win.min()),
+        None => (state.count, state.sum, state.max, state.min),
+    };
+
+    match op {
+        "count" => IncrementalValue::Number(count as f64),
+        "sum" => IncrementalValue::Number(sum),
+        "avg" if count > 0 => IncrementalValue::Number(sum / count as f64),
+        "avg" => IncrementalValue::Null,
+        "max" => max.map_or(IncrementalValue::Null, IncrementalValue::Number),
+        "min" => min.map_or(IncrementalValue::Null, IncrementalValue::Number),
+        "var" if state.count >= 2 => {
+            IncrementalValue::Number(state.m2 / (state.count - 1) as f64)
+        }
+        "var" => IncrementalValue::Null,
+        "std" if state.count >= 2 => {
+            IncrementalValue::Number((state.m2 / (state.count - 1) as f64).sqrt())
+        }
+        "std" => IncrementalValue::Null,
+        "zscore" if state.count >= 2 => {
+            let std = (state.m2 / (state.count - 1) as f64).sqrt();
+            if std == 0.0 {
+                IncrementalValue::Null
+            } else if let IncrementalValue::Number(v) = value {
+                IncrementalValue::Number((v - state.mean) / std)
+            } else {
+                IncrementalValue::Null
+            }
+        }
+        "zscore" => IncrementalValue::Null,
+        _ => IncrementalValue::Null,
+    }
+}
+
+/// Ring buffer of the last `lag` numeric values seen by a time-shift node.
+/// Non-numeric ticks are ignored: they neither count towards warmup nor
+/// evict an entry.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TimeShiftState {
+    pub buffer: std::collections::VecDeque<f64>,
+}
+
+/// Evaluates one tick of a time-shift node. `mode` is one of `"lag"` (the
+/// value `lag` steps back), `"diff"` (current minus lagged), `"pct_change"`
+/// (`(current - lagged) / lagged`), or `"roc"` (`pct_change` times 100).
+/// Emits `Null` during warmup (fewer than `lag` numeric values seen so far)
+/// and whenever the lagged value is zero for `"pct_change"`/`"roc"`.
+pub fn eval_time_shift_step(
+    mode: &str,
+    lag: usize,
+    value: &IncrementalValue,
+    state: &mut TimeShiftState,
+) -> IncrementalValue {
+    if lag == 0 {
+        return IncrementalValue::Null;
+    }
+
+    let current = match value {
+        IncrementalValue::Number(n) => Some(*n),
+        _ => None,
+    };
+
+    let out = if state.buffer.len() >= lag {
+        let lagged = state.buffer[state.buffer.len() - lag];
+        match (mode, current) {
+            ("lag", _) => IncrementalValue::Number(lagged),
+            ("diff", Some(curr)) => IncrementalValue::Number(curr - lagged),
+            ("pct_change", Some(_)) if lagged == 0.0 => IncrementalValue::Null,
+            ("pct_change", Some(curr)) => IncrementalValue::Number((curr - lagged) / lagged),
+            ("roc", Some(_)) if lagged == 0.0 => IncrementalValue::Null,
+            ("roc", Some(curr)) => IncrementalValue::Number(((curr - lagged) / lagged) * 100.0),
+            _ => IncrementalValue::Null,
+        }
+    } else {
+        IncrementalValue::Null
+    };
+
+    if let Some(curr) = current {
+        state.buffer.push_back(curr);
+        if state.buffer.len() > lag {
+            state.buffer.pop_front();
+        }
+    }
+
+    out
+}
+
+fn truthy(v: &IncrementalValue) -> bool {
+    match v {
+        IncrementalValue::Null => false,
+        IncrementalValue::Bool(b) => *b,
+        IncrementalValue::Number(n) => *n != 0.0,
+        IncrementalValue::Text(s) => !s.is_empty(),
+        IncrementalValue::Fields(f) => !f.is_empty(),
+        IncrementalValue::Bytes(b) => !b.is_empty(),
+    }
+}
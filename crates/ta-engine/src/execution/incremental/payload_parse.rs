@@ -1,16 +1,26 @@
-use crate::contracts::RustExecutionPayload;
+use crate::contracts::{RustExecutionPartition, RustExecutionPayload};
 use crate::dataset::DatasetPartitionKey;
 
-use super::backend::{ExecutePlanError, ExecutePlanPayload, KernelStepRequest};
+use super::backend::{ExecutePlanError, ExecutePlanPayload, KernelStepRequest, StepInputSource};
 use super::kernel_registry::KernelId;
 
-pub(crate) fn parse_execute_plan_payload(
-    payload: &RustExecutionPayload,
-) -> Result<ExecutePlanPayload, ExecutePlanError> {
-    payload
-        .validate()
-        .map_err(ExecutePlanError::InvalidPayload)?;
+/// `node:<id>` in the wire `input_field` string selects
+/// [`StepInputSource::NodeOutput`] (a reference to another request's output
+/// in the same plan); anything else is a plain [`StepInputSource::TickField`],
+/// same as every plan before node-to-node wiring existed.
+fn parse_step_input_source(input_field: &str) -> StepInputSource {
+    match input_field
+        .strip_prefix("node:")
+        .and_then(|id| id.parse::<u32>().ok())
+    {
+        Some(node_id) => StepInputSource::NodeOutput(node_id),
+        None => StepInputSource::TickField(input_field.to_string()),
+    }
+}
 
+fn parse_kernel_step_requests(
+    payload: &RustExecutionPayload,
+) -> Result<Vec<KernelStepRequest>, ExecutePlanError> {
     let mut requests = Vec::with_capacity(payload.requests.len());
     for request in &payload.requests {
         let kernel_id = KernelId::from_name(&request.kernel_id)
@@ -18,18 +28,68 @@ pub(crate) fn parse_execute_plan_payload(
         requests.push(KernelStepRequest {
             node_id: request.node_id,
             kernel_id,
-            input_field: request.input_field.clone(),
+            input: parse_step_input_source(&request.input_field),
             kwargs: request.kwargs.clone(),
         });
     }
+    Ok(requests)
+}
+
+fn partition_key(partition: &RustExecutionPartition) -> DatasetPartitionKey {
+    DatasetPartitionKey {
+        symbol: partition.symbol.clone(),
+        timeframe: partition.timeframe.clone(),
+        source: partition.source.clone(),
+    }
+}
+
+pub(crate) fn parse_execute_plan_payload(
+    payload: &RustExecutionPayload,
+) -> Result<ExecutePlanPayload, ExecutePlanError> {
+    payload
+        .validate()
+        .map_err(ExecutePlanError::InvalidPayload)?;
+
+    let requests = parse_kernel_step_requests(payload)?;
 
     Ok(ExecutePlanPayload {
         dataset_id: payload.dataset_id,
-        partition_key: DatasetPartitionKey {
-            symbol: payload.partition.symbol.clone(),
-            timeframe: payload.partition.timeframe.clone(),
-            source: payload.partition.source.clone(),
-        },
+        partition_key: partition_key(&payload.partition),
         requests,
     })
 }
+
+/// Like [`parse_execute_plan_payload`], but expands `payload.partitions`
+/// (when non-empty) into one [`ExecutePlanPayload`] per partition, all
+/// sharing a single kernel-id resolution pass over `payload.requests`
+/// instead of paying it once per partition the way N separate
+/// `RustExecutionPayload`s would. Falls back to `payload.partition` and
+/// returns a single-element `Vec` when `partitions` is empty, so callers
+/// that already build one payload per partition keep working unchanged.
+pub(crate) fn parse_execute_plan_payloads(
+    payload: &RustExecutionPayload,
+) -> Result<Vec<ExecutePlanPayload>, ExecutePlanError> {
+    payload
+        .validate()
+        .map_err(ExecutePlanError::InvalidPayload)?;
+
+    let requests = parse_kernel_step_requests(payload)?;
+
+    if payload.partitions.is_empty() {
+        return Ok(vec![ExecutePlanPayload {
+            dataset_id: payload.dataset_id,
+            partition_key: partition_key(&payload.partition),
+            requests,
+        }]);
+    }
+
+    Ok(payload
+        .partitions
+        .iter()
+        .map(|partition| ExecutePlanPayload {
+            dataset_id: payload.dataset_id,
+            partition_key: partition_key(partition),
+            requests: requests.clone(),
+        })
+        .collect())
+}
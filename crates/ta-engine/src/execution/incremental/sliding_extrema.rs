@@ -0,0 +1,131 @@
+use std::collections::VecDeque;
+
+/// Fixed-capacity ring buffer: an index-wrapped array, so pushing past
+/// capacity overwrites the oldest slot in O(1) instead of `Vec::remove(0)`'s
+/// O(k) shift.
+#[derive(Debug, Clone, PartialEq)]
+struct RingBuffer {
+    capacity: usize,
+    buf: Vec<f64>,
+    start: usize,
+    len: usize,
+}
+
+impl RingBuffer {
+    fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            capacity,
+            buf: Vec::with_capacity(capacity),
+            start: 0,
+            len: 0,
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        self.len == self.capacity
+    }
+
+    fn push(&mut self, value: f64) {
+        if self.len < self.capacity {
+            self.buf.push(value);
+            self.len += 1;
+        } else {
+            self.buf[self.start] = value;
+            self.start = (self.start + 1) % self.capacity;
+        }
+    }
+
+    /// Oldest-to-newest snapshot of the current window contents.
+    fn to_vec(&self) -> Vec<f64> {
+        (0..self.len)
+            .map(|i| self.buf[(self.start + i) % self.capacity])
+            .collect()
+    }
+}
+
+/// Tracks a fixed-size sliding window's running max and min in O(1)
+/// amortized per push via the classic monotonic-deque algorithm, instead of
+/// folding the whole window on every query. `push` evicts any values at the
+/// back of each deque that the new sample dominates (it will always be the
+/// extremum for as long as they'd otherwise survive), then evicts whatever
+/// has aged out of the window from the front; the front of each deque is
+/// always the window's current max/min. The raw values are kept in a
+/// [`RingBuffer`] purely so the window can be serialized and replayed (the
+/// monotonic deques alone can't reconstruct it -- they discard everything
+/// that isn't currently a candidate extremum).
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlidingExtrema {
+    window: RingBuffer,
+    next_index: u64,
+    max_deque: VecDeque<(u64, f64)>,
+    min_deque: VecDeque<(u64, f64)>,
+}
+
+impl SlidingExtrema {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            window: RingBuffer::new(capacity),
+            next_index: 0,
+            max_deque: VecDeque::new(),
+            min_deque: VecDeque::new(),
+        }
+    }
+
+    /// Rebuilds a window of the given `capacity` by replaying `values` in
+    /// order, for restoring from a snapshot that only has the raw window
+    /// contents.
+    pub fn from_values(capacity: usize, values: &[f64]) -> Self {
+        let mut extrema = Self::new(capacity);
+        for &value in values {
+            extrema.push(value);
+        }
+        extrema
+    }
+
+    pub fn push(&mut self, value: f64) {
+        self.window.push(value);
+
+        let index = self.next_index;
+        self.next_index += 1;
+
+        while matches!(self.max_deque.back(), Some(&(_, back)) if back <= value) {
+            self.max_deque.pop_back();
+        }
+        self.max_deque.push_back((index, value));
+
+        while matches!(self.min_deque.back(), Some(&(_, back)) if back >= value) {
+            self.min_deque.pop_back();
+        }
+        self.min_deque.push_back((index, value));
+
+        let capacity = self.window.capacity as u64;
+        if index + 1 > capacity {
+            let window_start = index + 1 - capacity;
+            while matches!(self.max_deque.front(), Some(&(idx, _)) if idx < window_start) {
+                self.max_deque.pop_front();
+            }
+            while matches!(self.min_deque.front(), Some(&(idx, _)) if idx < window_start) {
+                self.min_deque.pop_front();
+            }
+        }
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.window.is_full()
+    }
+
+    pub fn max(&self) -> Option<f64> {
+        self.max_deque.front().map(|&(_, v)| v)
+    }
+
+    pub fn min(&self) -> Option<f64> {
+        self.min_deque.front().map(|&(_, v)| v)
+    }
+
+    /// Oldest-to-newest snapshot of the current window contents, for
+    /// [`super::codec`]/[`super::state_codec`] to serialize.
+    pub fn values(&self) -> Vec<f64> {
+        self.window.to_vec()
+    }
+}
@@ -0,0 +1,230 @@
+use std::collections::BTreeMap;
+
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+use super::contracts::{
+    IncrementalValue, NodeSnapshotState, RuntimeSnapshot, INCREMENTAL_STATE_SCHEMA_VERSION,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SnapshotCodecError {
+    #[error("snapshot schema_version {0} is newer than the supported version {INCREMENTAL_STATE_SCHEMA_VERSION}")]
+    UnsupportedVersion(u16),
+    #[error("no migration registered to advance snapshot schema_version {0}")]
+    NoMigrationPath(u16),
+    #[error("malformed snapshot: {0}")]
+    Malformed(String),
+}
+
+type MigrationFn = fn(Value) -> Value;
+
+/// Upgrade closures keyed by the `schema_version` they migrate *from*. Empty
+/// today since `INCREMENTAL_STATE_SCHEMA_VERSION` is still 1; bump the
+/// schema version and add an entry here whenever `RuntimeSnapshot`'s shape
+/// changes in a way older snapshots can't be read as-is.
+const MIGRATIONS: &[(u16, MigrationFn)] = &[];
+
+/// Walks `snapshot` forward through [`MIGRATIONS`] until it reaches
+/// `INCREMENTAL_STATE_SCHEMA_VERSION`. Errors rather than guessing if the
+/// stored version is newer than this build knows about, or if no migration
+/// closure bridges a gap.
+pub fn migrate(snapshot: Value) -> Result<Value, SnapshotCodecError> {
+    let mut current = snapshot;
+    let mut version = read_schema_version(&current)?;
+
+    if version > INCREMENTAL_STATE_SCHEMA_VERSION {
+        return Err(SnapshotCodecError::UnsupportedVersion(version));
+    }
+
+    while version < INCREMENTAL_STATE_SCHEMA_VERSION {
+        let upgrade = MIGRATIONS
+            .iter()
+            .find(|(from, _)| *from == version)
+            .map(|(_, f)| *f)
+            .ok_or(SnapshotCodecError::NoMigrationPath(version))?;
+        current = upgrade(current);
+        version = read_schema_version(&current)?;
+    }
+
+    Ok(current)
+}
+
+/// Encodes a snapshot to its JSON form.
+pub fn encode_snapshot_to_json(snapshot: &RuntimeSnapshot) -> Value {
+    let mut nodes = Map::new();
+    for (node_id, node) in &snapshot.nodes {
+        let mut state_blob = Map::new();
+        for (key, value) in &node.state_blob {
+            state_blob.insert(key.clone(), incremental_value_to_json(value));
+        }
+
+        let mut clock = Map::new();
+        for (stream_id, event_index) in &node.clock {
+            clock.insert(stream_id.to_string(), Value::from(*event_index));
+        }
+
+        let mut node_json = Map::new();
+        node_json.insert(
+            "ticks_processed".to_string(),
+            Value::from(node.ticks_processed),
+        );
+        node_json.insert(
+            "last_output".to_string(),
+            incremental_value_to_json(&node.last_output),
+        );
+        node_json.insert("state_blob".to_string(), Value::Object(state_blob));
+        node_json.insert("clock".to_string(), Value::Object(clock));
+        nodes.insert(node_id.to_string(), Value::Object(node_json));
+    }
+
+    let mut root = Map::new();
+    root.insert(
+        "schema_version".to_string(),
+        Value::from(snapshot.schema_version),
+    );
+    root.insert(
+        "last_event_index".to_string(),
+        Value::from(snapshot.last_event_index),
+    );
+    root.insert("nodes".to_string(), Value::Object(nodes));
+    Value::Object(root)
+}
+
+/// Decodes a snapshot from its JSON form, migrating it to the current
+/// schema version first.
+pub fn decode_snapshot_from_json(value: &Value) -> Result<RuntimeSnapshot, SnapshotCodecError> {
+    let migrated = migrate(value.clone())?;
+    let root = migrated
+        .as_object()
+        .ok_or_else(|| SnapshotCodecError::Malformed("snapshot root must be an object".to_string()))?;
+
+    let schema_version = root
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .ok_or_else(|| SnapshotCodecError::Malformed("missing schema_version".to_string()))?
+        as u16;
+    let last_event_index = root
+        .get("last_event_index")
+        .and_then(Value::as_u64)
+        .unwrap_or(0);
+    let nodes_obj = root
+        .get("nodes")
+        .and_then(Value::as_object)
+        .ok_or_else(|| SnapshotCodecError::Malformed("missing nodes".to_string()))?;
+
+    let mut nodes = BTreeMap::new();
+    for (key, node_value) in nodes_obj {
+        let node_id: u32 = key
+            .parse()
+            .map_err(|_| SnapshotCodecError::Malformed(format!("invalid node id '{key}'")))?;
+        let node_obj = node_value.as_object().ok_or_else(|| {
+            SnapshotCodecError::Malformed(format!("node '{key}' must be an object"))
+        })?;
+
+        let ticks_processed = node_obj
+            .get("ticks_processed")
+            .and_then(Value::as_u64)
+            .unwrap_or(0);
+        let last_output = node_obj
+            .get("last_output")
+            .map(incremental_value_from_json)
+            .unwrap_or(IncrementalValue::Null);
+        let mut state_blob = BTreeMap::new();
+        if let Some(blob_obj) = node_obj.get("state_blob").and_then(Value::as_object) {
+            for (blob_key, blob_value) in blob_obj {
+                state_blob.insert(blob_key.clone(), incremental_value_from_json(blob_value));
+            }
+        }
+
+        let mut clock = BTreeMap::new();
+        if let Some(clock_obj) = node_obj.get("clock").and_then(Value::as_object) {
+            for (stream_key, index_value) in clock_obj {
+                if let (Ok(stream_id), Some(event_index)) =
+                    (stream_key.parse(), index_value.as_u64())
+                {
+                    clock.insert(stream_id, event_index);
+                }
+            }
+        }
+
+        nodes.insert(
+            node_id,
+            NodeSnapshotState {
+                ticks_processed,
+                last_output,
+                state_blob,
+                clock,
+            },
+        );
+    }
+
+    Ok(RuntimeSnapshot {
+        schema_version,
+        last_event_index,
+        nodes,
+    })
+}
+
+/// Compact binary form, used for handing a snapshot across a process
+/// boundary (e.g. to Python) as opaque bytes. This is the JSON encoding
+/// serialized with `serde_json::to_vec`; swap in a tighter wire format here
+/// without touching callers if size ever becomes a concern.
+pub fn encode_snapshot_to_bytes(snapshot: &RuntimeSnapshot) -> Vec<u8> {
+    serde_json::to_vec(&encode_snapshot_to_json(snapshot)).unwrap_or_default()
+}
+
+pub fn decode_snapshot_from_bytes(bytes: &[u8]) -> Result<RuntimeSnapshot, SnapshotCodecError> {
+    let value: Value =
+        serde_json::from_slice(bytes).map_err(|err| SnapshotCodecError::Malformed(err.to_string()))?;
+    decode_snapshot_from_json(&value)
+}
+
+fn read_schema_version(value: &Value) -> Result<u16, SnapshotCodecError> {
+    value
+        .as_object()
+        .and_then(|obj| obj.get("schema_version"))
+        .and_then(Value::as_u64)
+        .map(|v| v as u16)
+        .ok_or_else(|| SnapshotCodecError::Malformed("missing schema_version".to_string()))
+}
+
+fn incremental_value_to_json(value: &IncrementalValue) -> Value {
+    match value {
+        IncrementalValue::Number(n) => serde_json::Number::from_f64(*n)
+            .map(Value::Number)
+            .unwrap_or(Value::Null),
+        IncrementalValue::Bool(b) => Value::Bool(*b),
+        IncrementalValue::Text(s) => Value::String(s.clone()),
+        IncrementalValue::Fields(fields) => Value::Array(
+            fields
+                .iter()
+                .map(|f| {
+                    serde_json::Number::from_f64(*f)
+                        .map(Value::Number)
+                        .unwrap_or(Value::Null)
+                })
+                .collect(),
+        ),
+        IncrementalValue::Bytes(bytes) => {
+            Value::Array(bytes.iter().map(|b| Value::from(*b)).collect())
+        }
+        IncrementalValue::Null => Value::Null,
+    }
+}
+
+fn incremental_value_from_json(value: &Value) -> IncrementalValue {
+    match value {
+        Value::Number(n) => n.as_f64().map_or(IncrementalValue::Null, IncrementalValue::Number),
+        Value::Bool(b) => IncrementalValue::Bool(*b),
+        Value::String(s) => IncrementalValue::Text(s.clone()),
+        Value::Array(items) => {
+            let bytes: Option<Vec<u8>> = items
+                .iter()
+                .map(|item| item.as_u64().and_then(|v| u8::try_from(v).ok()))
+                .collect();
+            bytes.map_or(IncrementalValue::Null, IncrementalValue::Bytes)
+        }
+        _ => IncrementalValue::Null,
+    }
+}
@@ -1,6 +1,7 @@
 use std::collections::BTreeMap;
 
 use super::contracts::IncrementalValue;
+use super::vector_clock::VectorClock;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct NodeRuntimeState {
@@ -8,4 +9,20 @@ pub struct NodeRuntimeState {
     pub ticks_processed: u64,
     pub last_output: IncrementalValue,
     pub state_blob: BTreeMap<String, IncrementalValue>,
+    /// Per-stream causal progress for this node, used to make replaying an
+    /// overlapping or out-of-order range of events idempotent. See
+    /// [`super::vector_clock`].
+    pub clock: VectorClock,
+}
+
+/// Per-node cost accounting captured by [`super::backend::IncrementalBackend`]
+/// when profiling is enabled via `enable_profiling`. Cumulative across every
+/// `step`/`replay` call since profiling was turned on for that backend.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NodeProfile {
+    pub node_id: u32,
+    pub ticks_processed: u64,
+    pub total_nanos: u64,
+    pub peak_state_blob_bytes: usize,
+    pub recompute_count: u64,
 }
@@ -1,13 +1,24 @@
 use std::collections::BTreeMap;
 
 use super::call_step::KernelRuntimeState;
+use super::codec::{decode_kernel_state_binary, encode_kernel_state_binary, KernelStateCodecError};
 use super::contracts::IncrementalValue;
 use super::kernel_registry::KernelId;
+use super::sliding_extrema::SlidingExtrema;
 
+/// Encodes `state` to its snapshot blob. The primary path is the
+/// deterministic binary codec in [`super::codec`], stored whole under the
+/// `binary` key; the per-field `kind`/CSV entries below are kept alongside
+/// it purely so a snapshot written by this build can still be inspected or
+/// restored by the legacy reader if the binary form is ever stripped out.
 pub(crate) fn encode_kernel_state(
     state: &KernelRuntimeState,
 ) -> BTreeMap<String, IncrementalValue> {
     let mut blob = BTreeMap::new();
+    blob.insert(
+        "binary".to_string(),
+        IncrementalValue::Bytes(encode_kernel_state_binary(state)),
+    );
     match state {
         KernelRuntimeState::Rsi {
             period,
@@ -77,74 +88,243 @@ pub(crate) fn encode_kernel_state(
             );
             blob.insert(
                 "highs".to_string(),
-                IncrementalValue::Text(
-                    highs
-                        .iter()
-                        .map(|v| v.to_string())
-                        .collect::<Vec<_>>()
-                        .join(","),
-                ),
+                IncrementalValue::Text(join_csv(&highs.values())),
             );
             blob.insert(
                 "lows".to_string(),
-                IncrementalValue::Text(
-                    lows.iter()
-                        .map(|v| v.to_string())
-                        .collect::<Vec<_>>()
-                        .join(","),
-                ),
+                IncrementalValue::Text(join_csv(&lows.values())),
             );
         }
-        KernelRuntimeState::Vwap {
-            highs,
-            lows,
-            closes,
-            volumes,
+        KernelRuntimeState::Obv {
+            running_total,
+            last_close,
         } => {
+            blob.insert(
+                "kind".to_string(),
+                IncrementalValue::Text("obv".to_string()),
+            );
+            blob.insert(
+                "running_total".to_string(),
+                IncrementalValue::Number(*running_total),
+            );
+            blob.insert(
+                "last_close".to_string(),
+                last_close.map_or(IncrementalValue::Null, IncrementalValue::Number),
+            );
+        }
+        KernelRuntimeState::Cmf {
+            period,
+            mfv_window,
+            volume_window,
+        } => {
+            blob.insert(
+                "kind".to_string(),
+                IncrementalValue::Text("cmf".to_string()),
+            );
+            blob.insert(
+                "period".to_string(),
+                IncrementalValue::Number(*period as f64),
+            );
+            blob.insert(
+                "mfv_window".to_string(),
+                IncrementalValue::Text(join_csv(mfv_window)),
+            );
+            blob.insert(
+                "volume_window".to_string(),
+                IncrementalValue::Text(join_csv(volume_window)),
+            );
+        }
+        KernelRuntimeState::Vwap { sum_pv, sum_vol } => {
             blob.insert(
                 "kind".to_string(),
                 IncrementalValue::Text("vwap".to_string()),
             );
+            blob.insert("sum_pv".to_string(), IncrementalValue::Number(*sum_pv));
+            blob.insert("sum_vol".to_string(), IncrementalValue::Number(*sum_vol));
+        }
+        KernelRuntimeState::KlingerVf {
+            fast_period,
+            slow_period,
+            prev_tp,
+            ema_fast,
+            ema_slow,
+        } => {
+            blob.insert(
+                "kind".to_string(),
+                IncrementalValue::Text("klinger_vf".to_string()),
+            );
+            blob.insert(
+                "fast_period".to_string(),
+                IncrementalValue::Number(*fast_period as f64),
+            );
+            blob.insert(
+                "slow_period".to_string(),
+                IncrementalValue::Number(*slow_period as f64),
+            );
+            blob.insert(
+                "prev_tp".to_string(),
+                prev_tp.map_or(IncrementalValue::Null, IncrementalValue::Number),
+            );
+            blob.insert(
+                "ema_fast".to_string(),
+                ema_fast.map_or(IncrementalValue::Null, IncrementalValue::Number),
+            );
+            blob.insert(
+                "ema_slow".to_string(),
+                ema_slow.map_or(IncrementalValue::Null, IncrementalValue::Number),
+            );
+        }
+        KernelRuntimeState::Cci { period, tp_window } => {
+            blob.insert(
+                "kind".to_string(),
+                IncrementalValue::Text("cci".to_string()),
+            );
+            blob.insert(
+                "period".to_string(),
+                IncrementalValue::Number(*period as f64),
+            );
+            blob.insert(
+                "tp_window".to_string(),
+                IncrementalValue::Text(join_csv_deque(tp_window)),
+            );
+        }
+        KernelRuntimeState::WilliamsR { period, highs, lows } => {
+            blob.insert(
+                "kind".to_string(),
+                IncrementalValue::Text("williams_r".to_string()),
+            );
+            blob.insert(
+                "period".to_string(),
+                IncrementalValue::Number(*period as f64),
+            );
             blob.insert(
                 "highs".to_string(),
-                IncrementalValue::Text(
-                    highs
-                        .iter()
-                        .map(|v| v.to_string())
-                        .collect::<Vec<_>>()
-                        .join(","),
-                ),
+                IncrementalValue::Text(join_csv(&highs.values())),
             );
             blob.insert(
                 "lows".to_string(),
-                IncrementalValue::Text(
-                    lows.iter()
-                        .map(|v| v.to_string())
-                        .collect::<Vec<_>>()
-                        .join(","),
-                ),
-            );
-            blob.insert(
-                "closes".to_string(),
-                IncrementalValue::Text(
-                    closes
-                        .iter()
-                        .map(|v| v.to_string())
-                        .collect::<Vec<_>>()
-                        .join(","),
-                ),
-            );
-            blob.insert(
-                "volumes".to_string(),
-                IncrementalValue::Text(
-                    volumes
-                        .iter()
-                        .map(|v| v.to_string())
-                        .collect::<Vec<_>>()
-                        .join(","),
-                ),
+                IncrementalValue::Text(join_csv(&lows.values())),
+            );
+        }
+        KernelRuntimeState::Mfi {
+            period,
+            prev_tp,
+            pos_window,
+            neg_window,
+        } => {
+            blob.insert(
+                "kind".to_string(),
+                IncrementalValue::Text("mfi".to_string()),
+            );
+            blob.insert(
+                "period".to_string(),
+                IncrementalValue::Number(*period as f64),
+            );
+            blob.insert(
+                "prev_tp".to_string(),
+                prev_tp.map_or(IncrementalValue::Null, IncrementalValue::Number),
+            );
+            blob.insert(
+                "pos_window".to_string(),
+                IncrementalValue::Text(join_csv_deque(pos_window)),
+            );
+            blob.insert(
+                "neg_window".to_string(),
+                IncrementalValue::Text(join_csv_deque(neg_window)),
             );
         }
+        KernelRuntimeState::Vortex {
+            period,
+            prev_high,
+            prev_low,
+            prev_close,
+            tr_window,
+            vm_plus_window,
+            vm_minus_window,
+        } => {
+            blob.insert(
+                "kind".to_string(),
+                IncrementalValue::Text("vortex".to_string()),
+            );
+            blob.insert(
+                "period".to_string(),
+                IncrementalValue::Number(*period as f64),
+            );
+            blob.insert(
+                "prev_high".to_string(),
+                prev_high.map_or(IncrementalValue::Null, IncrementalValue::Number),
+            );
+            blob.insert(
+                "prev_low".to_string(),
+                prev_low.map_or(IncrementalValue::Null, IncrementalValue::Number),
+            );
+            blob.insert(
+                "prev_close".to_string(),
+                prev_close.map_or(IncrementalValue::Null, IncrementalValue::Number),
+            );
+            blob.insert(
+                "tr_window".to_string(),
+                IncrementalValue::Text(join_csv_deque(tr_window)),
+            );
+            blob.insert(
+                "vm_plus_window".to_string(),
+                IncrementalValue::Text(join_csv_deque(vm_plus_window)),
+            );
+            blob.insert(
+                "vm_minus_window".to_string(),
+                IncrementalValue::Text(join_csv_deque(vm_minus_window)),
+            );
+        }
+        KernelRuntimeState::Cmo {
+            period,
+            prev_value,
+            gains_window,
+            losses_window,
+        } => {
+            blob.insert(
+                "kind".to_string(),
+                IncrementalValue::Text("cmo".to_string()),
+            );
+            blob.insert(
+                "period".to_string(),
+                IncrementalValue::Number(*period as f64),
+            );
+            blob.insert(
+                "prev_value".to_string(),
+                prev_value.map_or(IncrementalValue::Null, IncrementalValue::Number),
+            );
+            blob.insert(
+                "gains_window".to_string(),
+                IncrementalValue::Text(join_csv_deque(gains_window)),
+            );
+            blob.insert(
+                "losses_window".to_string(),
+                IncrementalValue::Text(join_csv_deque(losses_window)),
+            );
+        }
+        KernelRuntimeState::Bbands {
+            period,
+            std_dev,
+            window,
+            sum,
+            sumsq,
+        } => {
+            blob.insert(
+                "kind".to_string(),
+                IncrementalValue::Text("bbands".to_string()),
+            );
+            blob.insert(
+                "period".to_string(),
+                IncrementalValue::Number(*period as f64),
+            );
+            blob.insert("std_dev".to_string(), IncrementalValue::Number(*std_dev));
+            blob.insert(
+                "window".to_string(),
+                IncrementalValue::Text(join_csv_deque(window)),
+            );
+            blob.insert("sum".to_string(), IncrementalValue::Number(*sum));
+            blob.insert("sumsq".to_string(), IncrementalValue::Number(*sumsq));
+        }
         KernelRuntimeState::Generic { kernel_id: _ } => {
             blob.insert(
                 "kind".to_string(),
@@ -155,15 +335,31 @@ pub(crate) fn encode_kernel_state(
     blob
 }
 
+/// Decodes a snapshot blob back into a [`KernelRuntimeState`]. Prefers the
+/// bit-exact binary encoding under the `binary` key, falling back to the
+/// legacy per-field/CSV reader below when that key is absent (the case for
+/// snapshots written before this codec existed) or its blob is malformed. A binary blob
+/// whose version this build can't read or can't migrate is a real error,
+/// not something to paper over with the legacy fallback -- it is returned
+/// as-is so `IncrementalBackend::restore` can surface it instead of
+/// silently restarting the node from scratch.
 pub(crate) fn decode_kernel_state(
     blob: &BTreeMap<String, IncrementalValue>,
-) -> Option<KernelRuntimeState> {
+) -> Result<Option<KernelRuntimeState>, KernelStateCodecError> {
+    if let Some(IncrementalValue::Bytes(bytes)) = blob.get("binary") {
+        match decode_kernel_state_binary(bytes) {
+            Ok(state) => return Ok(Some(state)),
+            Err(KernelStateCodecError::Malformed) => {}
+            Err(err) => return Err(err),
+        }
+    }
+
     let kind = match blob.get("kind") {
         Some(IncrementalValue::Text(s)) => s.as_str(),
-        _ => return None,
+        _ => return Ok(None),
     };
 
-    match kind {
+    Ok(match kind {
         "rsi" => Some(KernelRuntimeState::Rsi {
             period: get_num(blob, "period").unwrap_or(14.0) as usize,
             prev_close: get_num(blob, "prev_close"),
@@ -177,22 +373,87 @@ pub(crate) fn decode_kernel_state(
             rma_tr: get_num(blob, "rma_tr"),
             count: get_num(blob, "count").unwrap_or(0.0) as usize,
         }),
-        "stochastic" => Some(KernelRuntimeState::Stochastic {
-            k_period: get_num(blob, "k_period").unwrap_or(14.0) as usize,
-            highs: get_csv_nums(blob, "highs"),
-            lows: get_csv_nums(blob, "lows"),
+        "stochastic" => {
+            let k_period = get_num(blob, "k_period").unwrap_or(14.0) as usize;
+            Some(KernelRuntimeState::Stochastic {
+                k_period,
+                highs: SlidingExtrema::from_values(k_period, &get_csv_nums(blob, "highs")),
+                lows: SlidingExtrema::from_values(k_period, &get_csv_nums(blob, "lows")),
+            })
+        }
+        "obv" => Some(KernelRuntimeState::Obv {
+            running_total: get_num(blob, "running_total").unwrap_or(0.0),
+            last_close: get_num(blob, "last_close"),
+        }),
+        "cmf" => Some(KernelRuntimeState::Cmf {
+            period: get_num(blob, "period").unwrap_or(20.0) as usize,
+            mfv_window: get_csv_nums(blob, "mfv_window"),
+            volume_window: get_csv_nums(blob, "volume_window"),
         }),
         "vwap" => Some(KernelRuntimeState::Vwap {
-            highs: get_csv_nums(blob, "highs"),
-            lows: get_csv_nums(blob, "lows"),
-            closes: get_csv_nums(blob, "closes"),
-            volumes: get_csv_nums(blob, "volumes"),
+            sum_pv: get_num(blob, "sum_pv").unwrap_or(0.0),
+            sum_vol: get_num(blob, "sum_vol").unwrap_or(0.0),
+        }),
+        "klinger_vf" => Some(KernelRuntimeState::KlingerVf {
+            fast_period: get_num(blob, "fast_period").unwrap_or(34.0) as usize,
+            slow_period: get_num(blob, "slow_period").unwrap_or(55.0) as usize,
+            prev_tp: get_num(blob, "prev_tp"),
+            ema_fast: get_num(blob, "ema_fast"),
+            ema_slow: get_num(blob, "ema_slow"),
+        }),
+        "cci" => Some(KernelRuntimeState::Cci {
+            period: get_num(blob, "period").unwrap_or(20.0) as usize,
+            tp_window: get_csv_nums(blob, "tp_window").into(),
+        }),
+        "williams_r" => {
+            let period = get_num(blob, "period").unwrap_or(14.0) as usize;
+            Some(KernelRuntimeState::WilliamsR {
+                period,
+                highs: SlidingExtrema::from_values(period, &get_csv_nums(blob, "highs")),
+                lows: SlidingExtrema::from_values(period, &get_csv_nums(blob, "lows")),
+            })
+        }
+        "mfi" => Some(KernelRuntimeState::Mfi {
+            period: get_num(blob, "period").unwrap_or(14.0) as usize,
+            prev_tp: get_num(blob, "prev_tp"),
+            pos_window: get_csv_nums(blob, "pos_window").into(),
+            neg_window: get_csv_nums(blob, "neg_window").into(),
+        }),
+        "vortex" => Some(KernelRuntimeState::Vortex {
+            period: get_num(blob, "period").unwrap_or(14.0) as usize,
+            prev_high: get_num(blob, "prev_high"),
+            prev_low: get_num(blob, "prev_low"),
+            prev_close: get_num(blob, "prev_close"),
+            tr_window: get_csv_nums(blob, "tr_window").into(),
+            vm_plus_window: get_csv_nums(blob, "vm_plus_window").into(),
+            vm_minus_window: get_csv_nums(blob, "vm_minus_window").into(),
+        }),
+        "cmo" => Some(KernelRuntimeState::Cmo {
+            period: get_num(blob, "period").unwrap_or(14.0) as usize,
+            prev_value: get_num(blob, "prev_value"),
+            gains_window: get_csv_nums(blob, "gains_window").into(),
+            losses_window: get_csv_nums(blob, "losses_window").into(),
+        }),
+        "bbands" => Some(KernelRuntimeState::Bbands {
+            period: get_num(blob, "period").unwrap_or(20.0) as usize,
+            std_dev: get_num(blob, "std_dev").unwrap_or(2.0),
+            window: get_csv_nums(blob, "window").into(),
+            sum: get_num(blob, "sum").unwrap_or(0.0),
+            sumsq: get_num(blob, "sumsq").unwrap_or(0.0),
         }),
         "generic" => Some(KernelRuntimeState::Generic {
             kernel_id: KernelId::Rsi,
         }),
         _ => None,
-    }
+    })
+}
+
+fn join_csv(values: &[f64]) -> String {
+    values.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(",")
+}
+
+fn join_csv_deque(values: &std::collections::VecDeque<f64>) -> String {
+    values.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(",")
 }
 
 fn get_num(blob: &BTreeMap<String, IncrementalValue>, key: &str) -> Option<f64> {
@@ -1,41 +1,111 @@
 use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::PathBuf;
+
+use thiserror::Error;
 
 use super::contracts::{NodeSnapshotState, RuntimeSnapshot, INCREMENTAL_STATE_SCHEMA_VERSION};
+use super::graph_order::{DependencyGraph, GraphOrderError};
+use super::int_map::IntMap;
+use super::migrations::{migrate_snapshot, MigrationError};
+use super::snapshot_codec::{self, SnapshotCodecError};
 use super::state::NodeRuntimeState;
 
+/// Persistence contract behind [`super::backend::IncrementalBackend`],
+/// factored out so the backend doesn't have to know whether node runtime
+/// state lives in a plain map or survives a process restart. Modeled on
+/// Garage's single `Db` trait with interchangeable LMDB/SQLite adapters:
+/// one trait, several adapters, the backend stays storage-agnostic.
+/// [`RuntimeStateStore`] is the in-memory default; [`FileStateStore`] is a
+/// durable adapter that checkpoints to a single file.
+///
+/// `set_last_event_index`/`upsert_node` return a [`StateStoreError`] so a
+/// durable adapter can surface a failed write instead of silently losing
+/// it -- [`RuntimeStateStore`]'s in-memory impl never fails and always
+/// returns `Ok(())`.
+pub trait StateStore: Default {
+    fn initialize(&mut self);
+    fn set_last_event_index(&mut self, event_index: u64) -> Result<(), StateStoreError>;
+    fn upsert_node(&mut self, node: NodeRuntimeState) -> Result<(), StateStoreError>;
+    fn get_node(&self, node_id: u32) -> Option<NodeRuntimeState>;
+    fn snapshot(&self) -> RuntimeSnapshot;
+    fn restore(&mut self, snapshot: RuntimeSnapshot) -> Result<(), MigrationError>;
+}
+
+#[derive(Debug, Error)]
+pub enum StateStoreError {
+    #[error("failed to persist state store to '{path}': {source}")]
+    Io { path: PathBuf, source: io::Error },
+    #[error("failed to encode persisted snapshot: {0}")]
+    Codec(#[from] SnapshotCodecError),
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct RuntimeStateStore {
     last_event_index: u64,
-    nodes: BTreeMap<u32, NodeRuntimeState>,
+    nodes: IntMap<NodeRuntimeState>,
+    eval_order: Vec<u32>,
+    eval_order_graph_fingerprint: Option<u64>,
 }
 
 impl RuntimeStateStore {
-    pub fn initialize(&mut self) {
+    /// The dependency-ordered node sequence computed by the last
+    /// [`ensure_eval_order`](Self::ensure_eval_order) call, for per-tick
+    /// evaluation to walk instead of insertion order. Not part of
+    /// [`StateStore`]: nothing outside this type reads it today, and it is
+    /// specific to how an in-memory store caches a graph's topological
+    /// order rather than to persistence in general.
+    pub fn eval_order(&self) -> &[u32] {
+        &self.eval_order
+    }
+
+    /// Recomputes and caches `graph`'s topological evaluation order, but
+    /// only if `graph` differs from whatever was cached last time -- a
+    /// cheap fingerprint comparison instead of re-running Kahn's algorithm
+    /// on every tick for a plan whose graph never changes between ticks.
+    pub fn ensure_eval_order(&mut self, graph: &DependencyGraph) -> Result<&[u32], GraphOrderError> {
+        let fingerprint = graph_fingerprint(graph);
+        if self.eval_order_graph_fingerprint != Some(fingerprint) {
+            self.eval_order = graph.topological_order()?;
+            self.eval_order_graph_fingerprint = Some(fingerprint);
+        }
+        Ok(&self.eval_order)
+    }
+}
+
+impl StateStore for RuntimeStateStore {
+    fn initialize(&mut self) {
         self.last_event_index = 0;
         self.nodes.clear();
+        self.eval_order.clear();
+        self.eval_order_graph_fingerprint = None;
     }
 
-    pub fn set_last_event_index(&mut self, event_index: u64) {
+    fn set_last_event_index(&mut self, event_index: u64) -> Result<(), StateStoreError> {
         self.last_event_index = event_index;
+        Ok(())
     }
 
-    pub fn upsert_node(&mut self, node: NodeRuntimeState) {
+    fn upsert_node(&mut self, node: NodeRuntimeState) -> Result<(), StateStoreError> {
         self.nodes.insert(node.node_id, node);
+        Ok(())
     }
 
-    pub fn get_node(&self, node_id: u32) -> Option<&NodeRuntimeState> {
-        self.nodes.get(&node_id)
+    fn get_node(&self, node_id: u32) -> Option<NodeRuntimeState> {
+        self.nodes.get(node_id).cloned()
     }
 
-    pub fn snapshot(&self) -> RuntimeSnapshot {
+    fn snapshot(&self) -> RuntimeSnapshot {
         let mut nodes: BTreeMap<u32, NodeSnapshotState> = BTreeMap::new();
-        for (node_id, state) in &self.nodes {
+        for (node_id, state) in self.nodes.iter() {
             nodes.insert(
-                *node_id,
+                node_id,
                 NodeSnapshotState {
                     ticks_processed: state.ticks_processed,
                     last_output: state.last_output.clone(),
                     state_blob: state.state_blob.clone(),
+                    clock: state.clock.clone(),
                 },
             );
         }
@@ -47,10 +117,8 @@ impl RuntimeStateStore {
         }
     }
 
-    pub fn restore(&mut self, snapshot: RuntimeSnapshot) -> Result<(), &'static str> {
-        if snapshot.schema_version != INCREMENTAL_STATE_SCHEMA_VERSION {
-            return Err("unsupported snapshot schema version");
-        }
+    fn restore(&mut self, snapshot: RuntimeSnapshot) -> Result<(), MigrationError> {
+        let snapshot = migrate_snapshot(snapshot)?;
 
         self.last_event_index = snapshot.last_event_index;
         self.nodes = snapshot
@@ -64,6 +132,7 @@ impl RuntimeStateStore {
                         ticks_processed: node.ticks_processed,
                         last_output: node.last_output,
                         state_blob: node.state_blob,
+                        clock: node.clock,
                     },
                 )
             })
@@ -72,3 +141,90 @@ impl RuntimeStateStore {
         Ok(())
     }
 }
+
+fn graph_fingerprint(graph: &DependencyGraph) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    graph.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Durable [`StateStore`] adapter that checkpoints its entire state to a
+/// single file on every write, using [`snapshot_codec`]'s existing
+/// JSON-over-bytes encoding (the same form already used to hand a snapshot
+/// across the Python boundary) rather than inventing a second wire format.
+/// Reads stay served from an in-memory [`RuntimeStateStore`] mirror -- only
+/// writes touch disk -- so this is meant for checkpointed or batch-replay
+/// use (e.g. [`super::backend::execute_plan`]) rather than a
+/// latency-sensitive per-tick streaming path; a real LMDB/SQLite adapter
+/// would batch writes instead of rewriting the whole file each time.
+#[derive(Debug, Clone, Default)]
+pub struct FileStateStore {
+    path: Option<PathBuf>,
+    memory: RuntimeStateStore,
+}
+
+impl FileStateStore {
+    /// Opens `path` as this store's backing file, restoring whatever
+    /// snapshot is already there. A missing file is treated as an empty
+    /// store rather than an error, so the first run against a fresh path
+    /// doesn't need special-casing by the caller.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, StateStoreError> {
+        let path = path.into();
+        let mut store = FileStateStore {
+            path: Some(path.clone()),
+            memory: RuntimeStateStore::default(),
+        };
+
+        match std::fs::read(&path) {
+            Ok(bytes) => {
+                let snapshot = snapshot_codec::decode_snapshot_from_bytes(&bytes)?;
+                store.memory.restore(snapshot).map_err(|err| {
+                    StateStoreError::Codec(SnapshotCodecError::Malformed(err.to_string()))
+                })?;
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(source) => return Err(StateStoreError::Io { path, source }),
+        }
+
+        Ok(store)
+    }
+
+    fn persist(&self) -> Result<(), StateStoreError> {
+        let Some(path) = self.path.as_ref() else {
+            return Ok(());
+        };
+        let bytes = snapshot_codec::encode_snapshot_to_bytes(&self.memory.snapshot());
+        std::fs::write(path, bytes).map_err(|source| StateStoreError::Io {
+            path: path.clone(),
+            source,
+        })
+    }
+}
+
+impl StateStore for FileStateStore {
+    fn initialize(&mut self) {
+        self.memory.initialize();
+    }
+
+    fn set_last_event_index(&mut self, event_index: u64) -> Result<(), StateStoreError> {
+        self.memory.set_last_event_index(event_index)?;
+        self.persist()
+    }
+
+    fn upsert_node(&mut self, node: NodeRuntimeState) -> Result<(), StateStoreError> {
+        self.memory.upsert_node(node)?;
+        self.persist()
+    }
+
+    fn get_node(&self, node_id: u32) -> Option<NodeRuntimeState> {
+        self.memory.get_node(node_id)
+    }
+
+    fn snapshot(&self) -> RuntimeSnapshot {
+        self.memory.snapshot()
+    }
+
+    fn restore(&mut self, snapshot: RuntimeSnapshot) -> Result<(), MigrationError> {
+        self.memory.restore(snapshot)
+    }
+}
@@ -0,0 +1,35 @@
+use std::collections::BTreeMap;
+
+/// Per-node causal token, modeled on Garage K2V's causality tokens: for each
+/// source `stream_id`, the highest `event_index` already applied from it.
+/// Backs [`super::backend::IncrementalBackend::step`]'s idempotent replay --
+/// a tick whose index the clock already dominates is a duplicate (or an
+/// overlap from replaying two partitions' event ranges) and must be skipped
+/// rather than double-counted.
+pub type VectorClock = BTreeMap<u32, u64>;
+
+/// True if `clock` has already applied `event_index` (or a later one) from
+/// `stream_id`.
+pub fn dominates(clock: &VectorClock, stream_id: u32, event_index: u64) -> bool {
+    clock
+        .get(&stream_id)
+        .is_some_and(|&applied| applied >= event_index)
+}
+
+/// Records that `event_index` from `stream_id` has been applied. A clock
+/// only moves forward, so this is a no-op if `stream_id` already has an
+/// equal or later index recorded.
+pub fn record(clock: &mut VectorClock, stream_id: u32, event_index: u64) {
+    let applied = clock.entry(stream_id).or_insert(0);
+    *applied = (*applied).max(event_index);
+}
+
+/// Merges `other` into `clock` by taking the element-wise max of every
+/// stream's progress, so two backends that each advanced past a disjoint
+/// (or overlapping) slice of events can be combined into one clock that
+/// dominates everything either one has seen.
+pub fn merge(clock: &mut VectorClock, other: &VectorClock) {
+    for (&stream_id, &event_index) in other {
+        record(clock, stream_id, event_index);
+    }
+}
@@ -0,0 +1,265 @@
+//! A tiny register-based bytecode VM for composing indicator expressions
+//! like `(ema(close,12) - ema(close,26)) / atr(...)` without crossing the
+//! FFI boundary once per indicator call. Build a [`Program`] once, then
+//! [`eval_program`] runs every op over the whole series length and returns
+//! the designated result register.
+
+use std::collections::BTreeMap;
+
+use thiserror::Error;
+
+/// Named series a program can read with [`Op::LoadSeries`] or pass as the
+/// input to [`Op::CallIndicator`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputId {
+    Open,
+    High,
+    Low,
+    Close,
+    Volume,
+}
+
+/// One bytecode instruction. Every `dst`/`a`/`b`/`cond`/`src` field is a
+/// register index; every op runs elementwise over the whole series length.
+#[derive(Debug, Clone)]
+pub enum Op {
+    LoadSeries { dst: usize, input: InputId },
+    CallIndicator {
+        dst: usize,
+        indicator_id: String,
+        input: InputId,
+        params: BTreeMap<String, f64>,
+    },
+    Move { dst: usize, src: usize },
+    Add { dst: usize, a: usize, b: usize },
+    Sub { dst: usize, a: usize, b: usize },
+    Mul { dst: usize, a: usize, b: usize },
+    Div { dst: usize, a: usize, b: usize },
+    AddConst { dst: usize, src: usize, value: f64 },
+    MulConst { dst: usize, src: usize, value: f64 },
+    MinConst { dst: usize, src: usize, value: f64 },
+    MaxConst { dst: usize, src: usize, value: f64 },
+    Abs { dst: usize, src: usize },
+    Recip { dst: usize, src: usize },
+    Min { dst: usize, a: usize, b: usize },
+    Max { dst: usize, a: usize, b: usize },
+    /// Elementwise `cond[i] >= 0.0 ? a[i] : b[i]`.
+    IfPosTE { dst: usize, cond: usize, a: usize, b: usize },
+}
+
+/// A program is a flat op list plus the register holding the final result.
+#[derive(Debug, Clone)]
+pub struct Program {
+    pub ops: Vec<Op>,
+    pub result_reg: usize,
+    pub register_count: usize,
+}
+
+/// The named inputs `LoadSeries`/`CallIndicator` can read from. All slices
+/// must share the same length as the program is evaluated over.
+#[derive(Debug, Clone, Copy)]
+pub struct Inputs<'a> {
+    pub open: &'a [f64],
+    pub high: &'a [f64],
+    pub low: &'a [f64],
+    pub close: &'a [f64],
+    pub volume: &'a [f64],
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum EvalProgramError {
+    #[error("register {0} is out of bounds for a program with {1} registers")]
+    RegisterOutOfBounds(usize, usize),
+    #[error("unknown indicator id: {0}")]
+    UnknownIndicator(String),
+    #[error("missing required param '{param}' for indicator '{indicator}'")]
+    MissingParam { indicator: String, param: String },
+}
+
+/// Runs `program` over `inputs`, each of length `len`, and returns the
+/// series held in the program's `result_reg` once every op has executed.
+pub fn eval_program(
+    program: &Program,
+    inputs: &Inputs,
+    len: usize,
+) -> Result<Vec<f64>, EvalProgramError> {
+    let mut registers: Vec<Vec<f64>> = vec![vec![0.0; len]; program.register_count];
+
+    for op in &program.ops {
+        match op {
+            Op::LoadSeries { dst, input } => {
+                let dst = check_reg(*dst, program.register_count)?;
+                registers[dst] = pick_input(*input, inputs).to_vec();
+            }
+            Op::CallIndicator {
+                dst,
+                indicator_id,
+                input,
+                params,
+            } => {
+                let dst = check_reg(*dst, program.register_count)?;
+                registers[dst] = call_indicator(indicator_id, *input, params, inputs)?;
+            }
+            Op::Move { dst, src } => {
+                let dst = check_reg(*dst, program.register_count)?;
+                let src = check_reg(*src, program.register_count)?;
+                registers[dst] = registers[src].clone();
+            }
+            Op::Add { dst, a, b } => binary_op(&mut registers, program.register_count, *dst, *a, *b, |x, y| x + y)?,
+            Op::Sub { dst, a, b } => binary_op(&mut registers, program.register_count, *dst, *a, *b, |x, y| x - y)?,
+            Op::Mul { dst, a, b } => binary_op(&mut registers, program.register_count, *dst, *a, *b, |x, y| x * y)?,
+            Op::Div { dst, a, b } => binary_op(&mut registers, program.register_count, *dst, *a, *b, |x, y| x / y)?,
+            Op::Min { dst, a, b } => binary_op(&mut registers, program.register_count, *dst, *a, *b, f64::min)?,
+            Op::Max { dst, a, b } => binary_op(&mut registers, program.register_count, *dst, *a, *b, f64::max)?,
+            Op::AddConst { dst, src, value } => {
+                let value = *value;
+                unary_op(&mut registers, program.register_count, *dst, *src, move |x| x + value)?
+            }
+            Op::MulConst { dst, src, value } => {
+                let value = *value;
+                unary_op(&mut registers, program.register_count, *dst, *src, move |x| x * value)?
+            }
+            Op::MinConst { dst, src, value } => {
+                let value = *value;
+                unary_op(&mut registers, program.register_count, *dst, *src, move |x| x.min(value))?
+            }
+            Op::MaxConst { dst, src, value } => {
+                let value = *value;
+                unary_op(&mut registers, program.register_count, *dst, *src, move |x| x.max(value))?
+            }
+            Op::Abs { dst, src } => unary_op(&mut registers, program.register_count, *dst, *src, f64::abs)?,
+            Op::Recip { dst, src } => unary_op(&mut registers, program.register_count, *dst, *src, f64::recip)?,
+            Op::IfPosTE { dst, cond, a, b } => {
+                let dst_i = check_reg(*dst, program.register_count)?;
+                let cond_i = check_reg(*cond, program.register_count)?;
+                let a_i = check_reg(*a, program.register_count)?;
+                let b_i = check_reg(*b, program.register_count)?;
+                registers[dst_i] = (0..len)
+                    .map(|i| {
+                        if registers[cond_i][i] >= 0.0 {
+                            registers[a_i][i]
+                        } else {
+                            registers[b_i][i]
+                        }
+                    })
+                    .collect();
+            }
+        }
+    }
+
+    let result_reg = check_reg(program.result_reg, program.register_count)?;
+    Ok(registers[result_reg].clone())
+}
+
+fn check_reg(reg: usize, register_count: usize) -> Result<usize, EvalProgramError> {
+    if reg < register_count {
+        Ok(reg)
+    } else {
+        Err(EvalProgramError::RegisterOutOfBounds(reg, register_count))
+    }
+}
+
+fn unary_op(
+    registers: &mut [Vec<f64>],
+    register_count: usize,
+    dst: usize,
+    src: usize,
+    f: impl Fn(f64) -> f64,
+) -> Result<(), EvalProgramError> {
+    let dst = check_reg(dst, register_count)?;
+    let src = check_reg(src, register_count)?;
+    registers[dst] = registers[src].iter().map(|&x| f(x)).collect();
+    Ok(())
+}
+
+fn binary_op(
+    registers: &mut [Vec<f64>],
+    register_count: usize,
+    dst: usize,
+    a: usize,
+    b: usize,
+    f: impl Fn(f64, f64) -> f64,
+) -> Result<(), EvalProgramError> {
+    let dst = check_reg(dst, register_count)?;
+    let a = check_reg(a, register_count)?;
+    let b = check_reg(b, register_count)?;
+    registers[dst] = registers[a]
+        .iter()
+        .zip(registers[b].iter())
+        .map(|(&x, &y)| f(x, y))
+        .collect();
+    Ok(())
+}
+
+fn pick_input<'a>(input: InputId, inputs: &Inputs<'a>) -> &'a [f64] {
+    match input {
+        InputId::Open => inputs.open,
+        InputId::High => inputs.high,
+        InputId::Low => inputs.low,
+        InputId::Close => inputs.close,
+        InputId::Volume => inputs.volume,
+    }
+}
+
+/// Calls into the existing batch indicator functions, reusing them as VM
+/// callables instead of reimplementing their math here.
+fn call_indicator(
+    indicator_id: &str,
+    input: InputId,
+    params: &BTreeMap<String, f64>,
+    inputs: &Inputs,
+) -> Result<Vec<f64>, EvalProgramError> {
+    let series = pick_input(input, inputs);
+    let period = |name: &str| -> Result<usize, EvalProgramError> {
+        params
+            .get(name)
+            .copied()
+            .map(|v| v.max(0.0) as usize)
+            .ok_or_else(|| EvalProgramError::MissingParam {
+                indicator: indicator_id.to_string(),
+                param: name.to_string(),
+            })
+    };
+
+    match indicator_id {
+        "ema" => Ok(crate::moving_averages::ema(series, period("period")?)),
+        "sma" => Ok(crate::rolling::rolling_mean(series, period("period")?)),
+        "rma" => Ok(crate::moving_averages::rma(series, period("period")?)),
+        "wma" => Ok(crate::moving_averages::wma(series, period("period")?)),
+        "rsi" => Ok(crate::momentum::rsi(series, period("period")?)),
+        "atr" => Ok(crate::volatility::atr(
+            inputs.high,
+            inputs.low,
+            inputs.close,
+            period("period")?,
+        )),
+        "macd_line" => Ok(crate::trend::macd(
+            series,
+            period("fast_period")?,
+            period("slow_period")?,
+            period("signal_period")?,
+            crate::moving_averages::MovingAverageType::Ema,
+            crate::moving_averages::MovingAverageType::Ema,
+        )
+        .0),
+        "macd_signal" => Ok(crate::trend::macd(
+            series,
+            period("fast_period")?,
+            period("slow_period")?,
+            period("signal_period")?,
+            crate::moving_averages::MovingAverageType::Ema,
+            crate::moving_averages::MovingAverageType::Ema,
+        )
+        .1),
+        "macd_histogram" => Ok(crate::trend::macd(
+            series,
+            period("fast_period")?,
+            period("slow_period")?,
+            period("signal_period")?,
+            crate::moving_averages::MovingAverageType::Ema,
+            crate::moving_averages::MovingAverageType::Ema,
+        )
+        .2),
+        other => Err(EvalProgramError::UnknownIndicator(other.to_string())),
+    }
+}
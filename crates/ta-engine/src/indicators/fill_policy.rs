@@ -0,0 +1,62 @@
+//! Explicit policy for resolving `NaN` points (warmup bars and degenerate
+//! divide-by-zero cases) in indicator output, modeled on the same idea as
+//! tract-linalg's `RoundingPolicy`: instead of an indicator silently
+//! deciding how to paper over an undefined point, the caller picks.
+
+/// How to resolve a `NaN` point in an indicator's output series.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FillPolicy {
+    /// Leave the point as `NaN` (the historical default).
+    Nan,
+    /// Replace the point with `0.0`.
+    Zero,
+    /// Carry the last valid value forward; stays `NaN` until the first
+    /// valid value has been seen.
+    Ffill,
+    /// Replace the point with a fixed constant.
+    Constant(f64),
+    /// Drop every leading `NaN` point and shift the series down; the
+    /// returned offset is how many points were dropped from the front.
+    Drop,
+}
+
+impl Default for FillPolicy {
+    fn default() -> Self {
+        FillPolicy::Nan
+    }
+}
+
+/// Applies `policy` to every `NaN` point in `values`, returning the
+/// resolved series plus how many leading points were dropped (always `0`
+/// unless `policy` is [`FillPolicy::Drop`]).
+pub fn apply_fill_policy(values: &[f64], policy: FillPolicy) -> (Vec<f64>, usize) {
+    match policy {
+        FillPolicy::Nan => (values.to_vec(), 0),
+        FillPolicy::Zero => (
+            values.iter().map(|v| if v.is_nan() { 0.0 } else { *v }).collect(),
+            0,
+        ),
+        FillPolicy::Constant(fill) => (
+            values
+                .iter()
+                .map(|v| if v.is_nan() { fill } else { *v })
+                .collect(),
+            0,
+        ),
+        FillPolicy::Ffill => {
+            let mut out = Vec::with_capacity(values.len());
+            let mut last = f64::NAN;
+            for v in values {
+                if !v.is_nan() {
+                    last = *v;
+                }
+                out.push(last);
+            }
+            (out, 0)
+        }
+        FillPolicy::Drop => {
+            let offset = values.iter().position(|v| !v.is_nan()).unwrap_or(values.len());
+            (values[offset..].to_vec(), offset)
+        }
+    }
+}
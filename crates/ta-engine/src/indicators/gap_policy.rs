@@ -0,0 +1,33 @@
+//! Explicit policy for treating a `NaN` bar embedded *inside* a rolling
+//! window, as opposed to [`super::fill_policy::FillPolicy`] which resolves
+//! `NaN` points already sitting in an indicator's *output*. Modeled on the
+//! same idea: instead of each kernel quietly picking its own answer (some
+//! windowed scans here poison forever past a single gap, others silently
+//! skip it), the caller picks.
+
+/// How a windowed reduction treats a `NaN` bar embedded inside its window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GapPolicy {
+    /// A `NaN` anywhere in the window makes the window's output `NaN`
+    /// (the historical default).
+    PropagateNa,
+    /// Ignore `NaN` entries and reduce over the window's remaining valid
+    /// values; the output is only `NaN` if every value in the window is.
+    SkipNa,
+}
+
+impl Default for GapPolicy {
+    fn default() -> Self {
+        GapPolicy::PropagateNa
+    }
+}
+
+impl GapPolicy {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "propagate_na" => Some(Self::PropagateNa),
+            "skip_na" => Some(Self::SkipNa),
+            _ => None,
+        }
+    }
+}
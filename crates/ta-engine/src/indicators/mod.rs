@@ -0,0 +1,8 @@
+pub mod fill_policy;
+pub mod gap_policy;
+pub mod momentum;
+pub mod moving_averages;
+pub mod rolling;
+pub mod streaming;
+pub mod trend;
+pub mod volatility;
@@ -0,0 +1,1173 @@
+use super::fill_policy::{apply_fill_policy, FillPolicy};
+use super::gap_policy::GapPolicy;
+use super::moving_averages::MovingAverageType;
+
+/// Gain/loss averaging method for [`rsi_with_method`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RsiMethod {
+    /// Wilder's running average -- matches the plain [`rsi`].
+    Wilder,
+    /// Cutler's variant: a plain rolling mean of gains/losses.
+    Sma,
+    /// An EMA of gains/losses with alpha = `2/(period+1)`.
+    Ema,
+}
+
+impl RsiMethod {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "wilder" => Some(Self::Wilder),
+            "sma" => Some(Self::Sma),
+            "ema" => Some(Self::Ema),
+            _ => None,
+        }
+    }
+}
+
+pub fn rsi(values: &[f64], period: usize) -> Vec<f64> {
+    let n = values.len();
+    let mut out = vec![f64::NAN; n];
+    if n == 0 || period == 0 || n < 2 {
+        return out;
+    }
+
+    let mut avg_gain = 0.0;
+    let mut avg_loss = 0.0;
+
+    let seed = period.min(n - 1);
+    for i in 1..=seed {
+        let diff = values[i] - values[i - 1];
+        if diff > 0.0 {
+            avg_gain += diff;
+        } else {
+            avg_loss += -diff;
+        }
+    }
+
+    avg_gain /= seed as f64;
+    avg_loss /= seed as f64;
+
+    if n <= period {
+        return out;
+    }
+
+    // First RSI value appears at index = period
+    out[period] = if avg_loss == 0.0 {
+        if avg_gain > 0.0 {
+            100.0
+        } else {
+            50.0
+        }
+    } else {
+        let rs = avg_gain / avg_loss;
+        100.0 - (100.0 / (1.0 + rs))
+    };
+
+    for i in (period + 1)..n {
+        let diff = values[i] - values[i - 1];
+        let gain = if diff > 0.0 { diff } else { 0.0 };
+        let loss = if diff < 0.0 { -diff } else { 0.0 };
+
+        avg_gain = (avg_gain * (period as f64 - 1.0) + gain) / period as f64;
+        avg_loss = (avg_loss * (period as f64 - 1.0) + loss) / period as f64;
+
+        out[i] = if avg_loss == 0.0 {
+            if avg_gain > 0.0 {
+                100.0
+            } else {
+                50.0
+            }
+        } else {
+            let rs = avg_gain / avg_loss;
+            100.0 - (100.0 / (1.0 + rs))
+        };
+    }
+
+    out
+}
+
+/// Same as [`rsi`], but the gain/loss averaging can be switched from
+/// Wilder's running average to Cutler's SMA-based variant or a plain EMA.
+/// The warmup guards and the first emitted index (`period`) are identical
+/// across all three methods -- only the recurrence used from `period + 1`
+/// onward (and, for [`RsiMethod::Sma`], throughout) differs.
+pub fn rsi_with_method(values: &[f64], period: usize, method: RsiMethod) -> Vec<f64> {
+    if method == RsiMethod::Wilder {
+        return rsi(values, period);
+    }
+
+    let n = values.len();
+    let mut out = vec![f64::NAN; n];
+    if n == 0 || period == 0 || n < 2 {
+        return out;
+    }
+
+    let mut gains = vec![0.0; n];
+    let mut losses = vec![0.0; n];
+    for i in 1..n {
+        let diff = values[i] - values[i - 1];
+        if diff > 0.0 {
+            gains[i] = diff;
+        } else {
+            losses[i] = -diff;
+        }
+    }
+
+    if n <= period {
+        return out;
+    }
+
+    match method {
+        RsiMethod::Sma => {
+            let avg_gain = crate::rolling::rolling_mean(&gains[1..], period);
+            let avg_loss = crate::rolling::rolling_mean(&losses[1..], period);
+            for i in period..n {
+                out[i] = rsi_from_averages(avg_gain[i - 1], avg_loss[i - 1]);
+            }
+        }
+        RsiMethod::Ema => {
+            let alpha = 2.0 / (period as f64 + 1.0);
+            let seed = period.min(n - 1);
+            let mut avg_gain: f64 = gains[1..=seed].iter().sum::<f64>() / seed as f64;
+            let mut avg_loss: f64 = losses[1..=seed].iter().sum::<f64>() / seed as f64;
+            out[period] = rsi_from_averages(avg_gain, avg_loss);
+
+            for i in (period + 1)..n {
+                avg_gain = alpha * gains[i] + (1.0 - alpha) * avg_gain;
+                avg_loss = alpha * losses[i] + (1.0 - alpha) * avg_loss;
+                out[i] = rsi_from_averages(avg_gain, avg_loss);
+            }
+        }
+        RsiMethod::Wilder => unreachable!(),
+    }
+
+    out
+}
+
+fn rsi_from_averages(avg_gain: f64, avg_loss: f64) -> f64 {
+    if avg_loss == 0.0 {
+        if avg_gain > 0.0 {
+            100.0
+        } else {
+            50.0
+        }
+    } else {
+        let rs = avg_gain / avg_loss;
+        100.0 - (100.0 / (1.0 + rs))
+    }
+}
+
+/// Same recurrence as [`rsi`], but the no-loss-in-window point (normally
+/// hardcoded to `50.0`/`100.0`) is left as `NaN` and resolved through
+/// `policy` instead, alongside the ordinary warmup `NaN`s. Returns the
+/// resolved series plus the [`FillPolicy::Drop`] offset.
+pub fn rsi_with_policy(values: &[f64], period: usize, policy: FillPolicy) -> (Vec<f64>, usize) {
+    let n = values.len();
+    let mut out = vec![f64::NAN; n];
+    if n == 0 || period == 0 || n < 2 {
+        return apply_fill_policy(&out, policy);
+    }
+
+    let mut avg_gain = 0.0;
+    let mut avg_loss = 0.0;
+
+    let seed = period.min(n - 1);
+    for i in 1..=seed {
+        let diff = values[i] - values[i - 1];
+        if diff > 0.0 {
+            avg_gain += diff;
+        } else {
+            avg_loss += -diff;
+        }
+    }
+
+    avg_gain /= seed as f64;
+    avg_loss /= seed as f64;
+
+    if n <= period {
+        return apply_fill_policy(&out, policy);
+    }
+
+    out[period] = if avg_loss == 0.0 {
+        f64::NAN
+    } else {
+        let rs = avg_gain / avg_loss;
+        100.0 - (100.0 / (1.0 + rs))
+    };
+
+    for i in (period + 1)..n {
+        let diff = values[i] - values[i - 1];
+        let gain = if diff > 0.0 { diff } else { 0.0 };
+        let loss = if diff < 0.0 { -diff } else { 0.0 };
+
+        avg_gain = (avg_gain * (period as f64 - 1.0) + gain) / period as f64;
+        avg_loss = (avg_loss * (period as f64 - 1.0) + loss) / period as f64;
+
+        out[i] = if avg_loss == 0.0 {
+            f64::NAN
+        } else {
+            let rs = avg_gain / avg_loss;
+            100.0 - (100.0 / (1.0 + rs))
+        };
+    }
+
+    apply_fill_policy(&out, policy)
+}
+
+pub fn stochastic_kd(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    k_period: usize,
+    d_period: usize,
+    smooth_period: usize,
+) -> (Vec<f64>, Vec<f64>) {
+    let n = close.len();
+    let mut k = vec![f64::NAN; n];
+    let mut d = vec![f64::NAN; n];
+    if n == 0 || k_period == 0 || d_period == 0 || high.len() != n || low.len() != n {
+        return (k, d);
+    }
+
+    for i in 0..n {
+        if i + 1 < k_period {
+            continue;
+        }
+        let start = i + 1 - k_period;
+        let mut hh = high[start];
+        let mut ll = low[start];
+        for j in (start + 1)..=i {
+            if high[j] > hh {
+                hh = high[j];
+            }
+            if low[j] < ll {
+                ll = low[j];
+            }
+        }
+        let denom = hh - ll;
+        let k_val = if denom == 0.0 {
+            50.0
+        } else {
+            100.0 * (close[i] - ll) / denom
+        };
+        k[i] = k_val;
+    }
+
+    // Apply smoothing to %K if smooth_period > 1
+    let k_smoothed = if smooth_period > 1 {
+        crate::rolling::rolling_mean(&k, smooth_period)
+    } else {
+        k
+    };
+
+    for i in 0..n {
+        if i + 1 < d_period {
+            continue;
+        }
+        let start = i + 1 - d_period;
+        let mut sum = 0.0;
+        let mut valid = true;
+        for value in &k_smoothed[start..=i] {
+            if value.is_nan() {
+                valid = false;
+                break;
+            }
+            sum += *value;
+        }
+        if valid {
+            d[i] = sum / d_period as f64;
+        }
+    }
+
+    (k_smoothed, d)
+}
+
+/// Same as [`stochastic_kd`], but the %K window's high/low scan honors
+/// `gap`: [`stochastic_kd`]'s hand-rolled `hh`/`ll` loop lets a `NaN` bar
+/// silently drop out of the comparison (an implicit skip), while the %D
+/// averaging already propagates a `NaN` across its whole window -- the two
+/// disagreed on gap handling before `gap` made the choice explicit. Thin
+/// wrapper over [`stochastic_kd_with_method`] fixed to `MovingAverageType::Sma`,
+/// preserving this function's long-standing gap-aware SMA smoothing exactly.
+pub fn stochastic_kd_with_gap_policy(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    k_period: usize,
+    d_period: usize,
+    smooth_period: usize,
+    gap: GapPolicy,
+) -> (Vec<f64>, Vec<f64>) {
+    stochastic_kd_with_method(
+        high,
+        low,
+        close,
+        k_period,
+        d_period,
+        smooth_period,
+        gap,
+        MovingAverageType::Sma,
+    )
+}
+
+/// Same as [`stochastic_kd_with_gap_policy`], but the %K/%D smoothing step
+/// is driven by `ma_method` instead of being fixed to an SMA: `Sma` keeps the
+/// gap-aware rolling mean below so the default matches
+/// [`stochastic_kd_with_gap_policy`] exactly, while every other
+/// [`MovingAverageType`] choice applies its kernel directly to the %K/%D
+/// series, same as the `ma_type`-driven indicators in [`crate::trend::macd`].
+pub fn stochastic_kd_with_method(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    k_period: usize,
+    d_period: usize,
+    smooth_period: usize,
+    gap: GapPolicy,
+    ma_method: MovingAverageType,
+) -> (Vec<f64>, Vec<f64>) {
+    let n = close.len();
+    let mut k = vec![f64::NAN; n];
+    let d = vec![f64::NAN; n];
+    if n == 0 || k_period == 0 || d_period == 0 || high.len() != n || low.len() != n {
+        return (k, d);
+    }
+
+    let hh = crate::rolling::rolling_max_with_gap(high, k_period, gap);
+    let ll = crate::rolling::rolling_min_with_gap(low, k_period, gap);
+
+    for i in 0..n {
+        if hh[i].is_nan() || ll[i].is_nan() || close[i].is_nan() {
+            continue;
+        }
+        let denom = hh[i] - ll[i];
+        k[i] = if denom == 0.0 {
+            50.0
+        } else {
+            100.0 * (close[i] - ll[i]) / denom
+        };
+    }
+
+    let k_smoothed = if smooth_period > 1 {
+        smooth_with_method(&k, smooth_period, gap, ma_method)
+    } else {
+        k
+    };
+
+    let d = smooth_with_method(&k_smoothed, d_period, gap, ma_method);
+
+    (k_smoothed, d)
+}
+
+/// %K/%D smoothing helper for [`stochastic_kd_with_method`]: `Sma` keeps the
+/// gap-aware rolling mean, since [`MovingAverageType::apply`] has no
+/// gap-aware variant; every other method applies its kernel directly.
+fn smooth_with_method(
+    values: &[f64],
+    period: usize,
+    gap: GapPolicy,
+    ma_method: MovingAverageType,
+) -> Vec<f64> {
+    match ma_method {
+        MovingAverageType::Sma => crate::rolling::rolling_mean_with_gap(values, period, gap),
+        other => other.apply(values, period),
+    }
+}
+
+/// Generalized stochastic oscillator over an arbitrary series rather than
+/// high/low/close: each value is rescaled against its own rolling min/max
+/// window, `100*(v - min)/(max - min)`. Backs "stochastic of indicator"
+/// composites (Stochastic RSI, stochastic CCI, ...) on top of any source
+/// series, including one with a `NaN` warmup prefix.
+pub fn stochastic_of_series(
+    values: &[f64],
+    k_period: usize,
+    d_period: usize,
+    smooth_period: usize,
+) -> (Vec<f64>, Vec<f64>) {
+    stochastic_of_series_with_method(values, k_period, d_period, smooth_period, MovingAverageType::Sma)
+}
+
+/// Like [`stochastic_of_series`], but the `%K`/`%D` smoothing stages use an
+/// arbitrary [`MovingAverageType`] instead of always averaging with a
+/// NaN-skipping SMA. Backs composites that expose their smoothing choice,
+/// such as the DT Oscillator.
+pub fn stochastic_of_series_with_method(
+    values: &[f64],
+    k_period: usize,
+    d_period: usize,
+    smooth_period: usize,
+    ma_method: MovingAverageType,
+) -> (Vec<f64>, Vec<f64>) {
+    let n = values.len();
+    let mut k = vec![f64::NAN; n];
+    let d = vec![f64::NAN; n];
+    if n == 0 || k_period == 0 || d_period == 0 {
+        return (k, d);
+    }
+
+    for i in 0..n {
+        if i + 1 < k_period {
+            continue;
+        }
+        let start = i + 1 - k_period;
+        let window = &values[start..=i];
+        if window.iter().any(|v| v.is_nan()) {
+            continue;
+        }
+        let mut hh = window[0];
+        let mut ll = window[0];
+        for &v in &window[1..] {
+            if v > hh {
+                hh = v;
+            }
+            if v < ll {
+                ll = v;
+            }
+        }
+        let denom = hh - ll;
+        k[i] = if denom == 0.0 {
+            50.0
+        } else {
+            100.0 * (values[i] - ll) / denom
+        };
+    }
+
+    let k_smoothed = if smooth_period > 1 {
+        match ma_method {
+            MovingAverageType::Sma => nan_skipping_sma(&k, smooth_period),
+            other => other.apply(&k, smooth_period),
+        }
+    } else {
+        k
+    };
+
+    let d = match ma_method {
+        MovingAverageType::Sma => nan_skipping_sma(&k_smoothed, d_period),
+        other => other.apply(&k_smoothed, d_period),
+    };
+
+    (k_smoothed, d)
+}
+
+/// Simple moving average that only fills a window once every value in it is
+/// non-`NaN`, leaving the rest `NaN`: the smoothing helper
+/// [`stochastic_of_series_with_method`] uses for its default `Sma` stages,
+/// since a source series like RSI can carry its own `NaN` warmup prefix.
+fn nan_skipping_sma(values: &[f64], period: usize) -> Vec<f64> {
+    let n = values.len();
+    let mut out = vec![f64::NAN; n];
+    for i in 0..n {
+        if i + 1 < period {
+            continue;
+        }
+        let start = i + 1 - period;
+        let mut sum = 0.0;
+        let mut valid = true;
+        for value in &values[start..=i] {
+            if value.is_nan() {
+                valid = false;
+                break;
+            }
+            sum += *value;
+        }
+        if valid {
+            out[i] = sum / period as f64;
+        }
+    }
+    out
+}
+
+pub fn cci(high: &[f64], low: &[f64], close: &[f64], period: usize) -> Vec<f64> {
+    let n = close.len();
+    if n == 0 || period == 0 {
+        return vec![f64::NAN; n];
+    }
+
+    let mut tp = vec![0.0; n];
+    for i in 0..n {
+        tp[i] = (high[i] + low[i] + close[i]) / 3.0;
+    }
+
+    let sma = crate::rolling::rolling_mean(&tp, period);
+    let mut out = vec![f64::NAN; n];
+
+    for i in 0..n {
+        if i + 1 < period {
+            continue;
+        }
+
+        let mut mean_deviation = 0.0;
+        let start = i + 1 - period;
+        let current_sma = sma[i];
+
+        for j in start..=i {
+            mean_deviation += (tp[j] - current_sma).abs();
+        }
+        mean_deviation /= period as f64;
+
+        if mean_deviation == 0.0 {
+            out[i] = 0.0;
+        } else {
+            out[i] = (tp[i] - current_sma) / (0.015 * mean_deviation);
+        }
+    }
+
+    out
+}
+
+/// Same as [`cci`], but the flat-window (`mean_deviation == 0.0`) point is
+/// left as `NaN` and resolved through `policy` instead of being hardcoded
+/// to `0.0`.
+pub fn cci_with_policy(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    period: usize,
+    policy: FillPolicy,
+) -> (Vec<f64>, usize) {
+    let n = close.len();
+    if n == 0 || period == 0 {
+        return apply_fill_policy(&vec![f64::NAN; n], policy);
+    }
+
+    let mut tp = vec![0.0; n];
+    for i in 0..n {
+        tp[i] = (high[i] + low[i] + close[i]) / 3.0;
+    }
+
+    let sma = crate::rolling::rolling_mean(&tp, period);
+    let mut out = vec![f64::NAN; n];
+
+    for i in 0..n {
+        if i + 1 < period {
+            continue;
+        }
+
+        let mut mean_deviation = 0.0;
+        let start = i + 1 - period;
+        let current_sma = sma[i];
+
+        for j in start..=i {
+            mean_deviation += (tp[j] - current_sma).abs();
+        }
+        mean_deviation /= period as f64;
+
+        out[i] = if mean_deviation == 0.0 {
+            f64::NAN
+        } else {
+            (tp[i] - current_sma) / (0.015 * mean_deviation)
+        };
+    }
+
+    apply_fill_policy(&out, policy)
+}
+
+/// Same as [`cci`], but a `NaN` bar inside the mean/mean-deviation windows
+/// is handled per `gap` instead of unconditionally poisoning the rest of
+/// the series through [`crate::rolling::rolling_mean`]'s running sum.
+pub fn cci_with_gap_policy(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    period: usize,
+    gap: GapPolicy,
+) -> Vec<f64> {
+    let n = close.len();
+    if n == 0 || period == 0 {
+        return vec![f64::NAN; n];
+    }
+
+    let mut tp = vec![0.0; n];
+    for i in 0..n {
+        tp[i] = (high[i] + low[i] + close[i]) / 3.0;
+    }
+
+    let sma = crate::rolling::rolling_mean_with_gap(&tp, period, gap);
+    let mut out = vec![f64::NAN; n];
+
+    for i in 0..n {
+        if i + 1 < period || sma[i].is_nan() {
+            continue;
+        }
+
+        let start = i + 1 - period;
+        let window = &tp[start..=i];
+        let current_sma = sma[i];
+        let mean_deviation = match gap {
+            GapPolicy::PropagateNa => {
+                if window.iter().any(|v| v.is_nan()) {
+                    f64::NAN
+                } else {
+                    window.iter().map(|v| (v - current_sma).abs()).sum::<f64>() / period as f64
+                }
+            }
+            GapPolicy::SkipNa => {
+                let valid: Vec<f64> = window.iter().copied().filter(|v| !v.is_nan()).collect();
+                if valid.is_empty() {
+                    f64::NAN
+                } else {
+                    valid.iter().map(|v| (v - current_sma).abs()).sum::<f64>() / valid.len() as f64
+                }
+            }
+        };
+
+        if mean_deviation.is_nan() {
+            continue;
+        }
+        out[i] = if mean_deviation == 0.0 {
+            0.0
+        } else {
+            (tp[i] - current_sma) / (0.015 * mean_deviation)
+        };
+    }
+
+    out
+}
+
+pub fn roc(values: &[f64], period: usize) -> Vec<f64> {
+    let n = values.len();
+    let mut out = vec![f64::NAN; n];
+    if period == 0 || n == 0 {
+        return out;
+    }
+
+    for i in period..n {
+        let prev = values[i - period];
+        if prev == 0.0 || prev.is_nan() || values[i].is_nan() {
+            out[i] = f64::NAN;
+        } else {
+            out[i] = ((values[i] - prev) / prev) * 100.0;
+        }
+    }
+    out
+}
+
+pub fn williams_r(high: &[f64], low: &[f64], close: &[f64], period: usize) -> Vec<f64> {
+    let n = close.len();
+    let mut out = vec![f64::NAN; n];
+    if period == 0 || high.len() != n || low.len() != n || n == 0 {
+        return out;
+    }
+
+    for i in 0..n {
+        if i + 1 < period {
+            continue;
+        }
+        let start = i + 1 - period;
+        let mut hh = high[start];
+        let mut ll = low[start];
+        for j in (start + 1)..=i {
+            if high[j] > hh {
+                hh = high[j];
+            }
+            if low[j] < ll {
+                ll = low[j];
+            }
+        }
+        let range = hh - ll;
+        if range == 0.0 {
+            out[i] = 0.0;
+        } else {
+            out[i] = ((hh - close[i]) / range) * -100.0;
+        }
+    }
+    out
+}
+
+/// Same as [`williams_r`], but the flat-range (`range == 0.0`) point is
+/// left as `NaN` and resolved through `policy` instead of being hardcoded
+/// to `0.0`.
+pub fn williams_r_with_policy(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    period: usize,
+    policy: FillPolicy,
+) -> (Vec<f64>, usize) {
+    let n = close.len();
+    let mut out = vec![f64::NAN; n];
+    if period == 0 || high.len() != n || low.len() != n || n == 0 {
+        return apply_fill_policy(&out, policy);
+    }
+
+    for i in 0..n {
+        if i + 1 < period {
+            continue;
+        }
+        let start = i + 1 - period;
+        let mut hh = high[start];
+        let mut ll = low[start];
+        for j in (start + 1)..=i {
+            if high[j] > hh {
+                hh = high[j];
+            }
+            if low[j] < ll {
+                ll = low[j];
+            }
+        }
+        let range = hh - ll;
+        out[i] = if range == 0.0 {
+            f64::NAN
+        } else {
+            ((hh - close[i]) / range) * -100.0
+        };
+    }
+
+    apply_fill_policy(&out, policy)
+}
+
+/// Same as [`williams_r`], but the window's high/low scan treats an
+/// embedded `NaN` per `gap` instead of the hand-rolled loop's implicit
+/// skip (a `NaN` never compares greater/less than the running extremum).
+pub fn williams_r_with_gap_policy(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    period: usize,
+    gap: GapPolicy,
+) -> Vec<f64> {
+    let n = close.len();
+    let mut out = vec![f64::NAN; n];
+    if period == 0 || high.len() != n || low.len() != n || n == 0 {
+        return out;
+    }
+
+    let hh = crate::rolling::rolling_max_with_gap(high, period, gap);
+    let ll = crate::rolling::rolling_min_with_gap(low, period, gap);
+
+    for i in 0..n {
+        if hh[i].is_nan() || ll[i].is_nan() || close[i].is_nan() {
+            continue;
+        }
+        let range = hh[i] - ll[i];
+        out[i] = if range == 0.0 {
+            0.0
+        } else {
+            ((hh[i] - close[i]) / range) * -100.0
+        };
+    }
+    out
+}
+
+pub fn cmo(values: &[f64], period: usize) -> Vec<f64> {
+    let n = values.len();
+    let mut out = vec![f64::NAN; n];
+    if period == 0 || n < 2 {
+        return out;
+    }
+
+    let mut gains = vec![0.0; n];
+    let mut losses = vec![0.0; n];
+    for i in 1..n {
+        let diff = values[i] - values[i - 1];
+        if diff > 0.0 {
+            gains[i] = diff;
+        } else {
+            losses[i] = -diff;
+        }
+    }
+
+    let sum_gains = crate::rolling::rolling_sum(&gains, period);
+    let sum_losses = crate::rolling::rolling_sum(&losses, period);
+
+    for i in 0..n {
+        let sg = sum_gains[i];
+        let sl = sum_losses[i];
+        if sg.is_nan() || sl.is_nan() {
+            continue;
+        }
+        let denom = sg + sl;
+        out[i] = if denom == 0.0 {
+            0.0
+        } else {
+            100.0 * (sg - sl) / denom
+        };
+    }
+
+    out
+}
+
+/// Same as [`cmo`], but a `NaN` input bar is handled per `gap`. In [`cmo`]
+/// a `NaN` bar's difference lands in `losses` and poisons every later
+/// window through [`crate::rolling::rolling_sum`]'s running sum.
+pub fn cmo_with_gap_policy(values: &[f64], period: usize, gap: GapPolicy) -> Vec<f64> {
+    let n = values.len();
+    let mut out = vec![f64::NAN; n];
+    if period == 0 || n < 2 {
+        return out;
+    }
+
+    let mut gains = vec![f64::NAN; n];
+    let mut losses = vec![f64::NAN; n];
+    gains[0] = 0.0;
+    losses[0] = 0.0;
+    for i in 1..n {
+        if values[i].is_nan() || values[i - 1].is_nan() {
+            continue;
+        }
+        let diff = values[i] - values[i - 1];
+        if diff > 0.0 {
+            gains[i] = diff;
+            losses[i] = 0.0;
+        } else {
+            gains[i] = 0.0;
+            losses[i] = -diff;
+        }
+    }
+
+    let sum_gains = crate::rolling::rolling_sum_with_gap(&gains, period, gap);
+    let sum_losses = crate::rolling::rolling_sum_with_gap(&losses, period, gap);
+
+    for i in 0..n {
+        let sg = sum_gains[i];
+        let sl = sum_losses[i];
+        if sg.is_nan() || sl.is_nan() {
+            continue;
+        }
+        let denom = sg + sl;
+        out[i] = if denom == 0.0 {
+            0.0
+        } else {
+            100.0 * (sg - sl) / denom
+        };
+    }
+
+    out
+}
+
+pub fn ao(high: &[f64], low: &[f64], fast_period: usize, slow_period: usize) -> Vec<f64> {
+    let n = high.len();
+    let mut out = vec![f64::NAN; n];
+    if n == 0 || low.len() != n || fast_period == 0 || slow_period == 0 {
+        return out;
+    }
+
+    let mut median = vec![0.0; n];
+    for i in 0..n {
+        median[i] = (high[i] + low[i]) / 2.0;
+    }
+
+    let fast = crate::rolling::rolling_mean(&median, fast_period);
+    let slow = crate::rolling::rolling_mean(&median, slow_period);
+
+    for i in 0..n {
+        if fast[i].is_nan() || slow[i].is_nan() {
+            continue;
+        }
+        out[i] = fast[i] - slow[i];
+    }
+    out
+}
+
+/// Same as [`ao`], but a `NaN` bar inside either averaging window is
+/// handled per `gap` instead of unconditionally poisoning the rest of the
+/// series through [`crate::rolling::rolling_mean`]'s running sum.
+pub fn ao_with_gap_policy(
+    high: &[f64],
+    low: &[f64],
+    fast_period: usize,
+    slow_period: usize,
+    gap: GapPolicy,
+) -> Vec<f64> {
+    let n = high.len();
+    let mut out = vec![f64::NAN; n];
+    if n == 0 || low.len() != n || fast_period == 0 || slow_period == 0 {
+        return out;
+    }
+
+    let mut median = vec![0.0; n];
+    for i in 0..n {
+        median[i] = (high[i] + low[i]) / 2.0;
+    }
+
+    let fast = crate::rolling::rolling_mean_with_gap(&median, fast_period, gap);
+    let slow = crate::rolling::rolling_mean_with_gap(&median, slow_period, gap);
+
+    for i in 0..n {
+        if fast[i].is_nan() || slow[i].is_nan() {
+            continue;
+        }
+        out[i] = fast[i] - slow[i];
+    }
+    out
+}
+
+/// Accelerator Oscillator: `AO - SMA(AO, signal_period)`. Recomputes the
+/// signal average fresh per window (rather than [`crate::rolling::rolling_mean`]'s
+/// incremental running sum) since [`ao`]'s leading `NaN`s would otherwise
+/// poison the running sum for the rest of the series.
+pub fn ac(
+    high: &[f64],
+    low: &[f64],
+    fast_period: usize,
+    slow_period: usize,
+    signal_period: usize,
+) -> Vec<f64> {
+    let oscillator = ao(high, low, fast_period, slow_period);
+    let n = oscillator.len();
+    let mut out = vec![f64::NAN; n];
+    if signal_period == 0 || n < signal_period {
+        return out;
+    }
+    for i in (signal_period - 1)..n {
+        let window = &oscillator[i + 1 - signal_period..=i];
+        if window.iter().any(|v| v.is_nan()) {
+            continue;
+        }
+        let signal = window.iter().sum::<f64>() / signal_period as f64;
+        out[i] = oscillator[i] - signal;
+    }
+    out
+}
+
+pub fn coppock(values: &[f64], wma_period: usize, fast_roc: usize, slow_roc: usize) -> Vec<f64> {
+    let n = values.len();
+    if n == 0 || wma_period == 0 || fast_roc == 0 || slow_roc == 0 {
+        return vec![f64::NAN; n];
+    }
+
+    let roc_fast = roc(values, fast_roc);
+    let roc_slow = roc(values, slow_roc);
+    let mut sum = vec![f64::NAN; n];
+    for i in 0..n {
+        if !roc_fast[i].is_nan() && !roc_slow[i].is_nan() {
+            sum[i] = roc_fast[i] + roc_slow[i];
+        }
+    }
+    crate::moving_averages::wma(&sum, wma_period)
+}
+
+pub fn mfi(high: &[f64], low: &[f64], close: &[f64], volume: &[f64], period: usize) -> Vec<f64> {
+    let n = close.len();
+    let mut out = vec![f64::NAN; n];
+    if n == 0 || period == 0 || high.len() != n || low.len() != n || volume.len() != n {
+        return out;
+    }
+
+    let mut tp = vec![0.0; n];
+    let mut rmf = vec![0.0; n];
+    for i in 0..n {
+        tp[i] = (high[i] + low[i] + close[i]) / 3.0;
+        rmf[i] = tp[i] * volume[i];
+    }
+
+    let mut pos = vec![0.0; n];
+    let mut neg = vec![0.0; n];
+    for i in 1..n {
+        if tp[i] > tp[i - 1] {
+            pos[i] = rmf[i];
+        } else if tp[i] < tp[i - 1] {
+            neg[i] = rmf[i];
+        }
+    }
+
+    let pos_sum = crate::rolling::rolling_sum(&pos, period);
+    let neg_sum = crate::rolling::rolling_sum(&neg, period);
+    for i in 0..n {
+        if pos_sum[i].is_nan() || neg_sum[i].is_nan() {
+            continue;
+        }
+        if neg_sum[i] == 0.0 {
+            out[i] = 100.0;
+        } else {
+            let mfr = pos_sum[i] / neg_sum[i];
+            out[i] = 100.0 - (100.0 / (1.0 + mfr));
+        }
+    }
+    out
+}
+
+/// Same as [`mfi`], but the no-negative-flow-in-window point (normally
+/// hardcoded to `100.0`) is left as `NaN` and resolved through `policy`.
+pub fn mfi_with_policy(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    volume: &[f64],
+    period: usize,
+    policy: FillPolicy,
+) -> (Vec<f64>, usize) {
+    let n = close.len();
+    let mut out = vec![f64::NAN; n];
+    if n == 0 || period == 0 || high.len() != n || low.len() != n || volume.len() != n {
+        return apply_fill_policy(&out, policy);
+    }
+
+    let mut tp = vec![0.0; n];
+    let mut rmf = vec![0.0; n];
+    for i in 0..n {
+        tp[i] = (high[i] + low[i] + close[i]) / 3.0;
+        rmf[i] = tp[i] * volume[i];
+    }
+
+    let mut pos = vec![0.0; n];
+    let mut neg = vec![0.0; n];
+    for i in 1..n {
+        if tp[i] > tp[i - 1] {
+            pos[i] = rmf[i];
+        } else if tp[i] < tp[i - 1] {
+            neg[i] = rmf[i];
+        }
+    }
+
+    let pos_sum = crate::rolling::rolling_sum(&pos, period);
+    let neg_sum = crate::rolling::rolling_sum(&neg, period);
+    for i in 0..n {
+        if pos_sum[i].is_nan() || neg_sum[i].is_nan() {
+            continue;
+        }
+        out[i] = if neg_sum[i] == 0.0 {
+            f64::NAN
+        } else {
+            let mfr = pos_sum[i] / neg_sum[i];
+            100.0 - (100.0 / (1.0 + mfr))
+        };
+    }
+
+    apply_fill_policy(&out, policy)
+}
+
+/// Same as [`mfi`], but a `NaN` bar is handled per `gap` instead of being
+/// silently counted as zero money flow.
+pub fn mfi_with_gap_policy(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    volume: &[f64],
+    period: usize,
+    gap: GapPolicy,
+) -> Vec<f64> {
+    let n = close.len();
+    let mut out = vec![f64::NAN; n];
+    if n == 0 || period == 0 || high.len() != n || low.len() != n || volume.len() != n {
+        return out;
+    }
+
+    let mut tp = vec![0.0; n];
+    let mut rmf = vec![0.0; n];
+    for i in 0..n {
+        tp[i] = (high[i] + low[i] + close[i]) / 3.0;
+        rmf[i] = tp[i] * volume[i];
+    }
+
+    let mut pos = vec![f64::NAN; n];
+    let mut neg = vec![f64::NAN; n];
+    pos[0] = 0.0;
+    neg[0] = 0.0;
+    for i in 1..n {
+        if tp[i].is_nan() || tp[i - 1].is_nan() {
+            continue;
+        }
+        if tp[i] > tp[i - 1] {
+            pos[i] = rmf[i];
+            neg[i] = 0.0;
+        } else if tp[i] < tp[i - 1] {
+            pos[i] = 0.0;
+            neg[i] = rmf[i];
+        } else {
+            pos[i] = 0.0;
+            neg[i] = 0.0;
+        }
+    }
+
+    let pos_sum = crate::rolling::rolling_sum_with_gap(&pos, period, gap);
+    let neg_sum = crate::rolling::rolling_sum_with_gap(&neg, period, gap);
+    for i in 0..n {
+        if pos_sum[i].is_nan() || neg_sum[i].is_nan() {
+            continue;
+        }
+        if neg_sum[i] == 0.0 {
+            out[i] = 100.0;
+        } else {
+            let mfr = pos_sum[i] / neg_sum[i];
+            out[i] = 100.0 - (100.0 / (1.0 + mfr));
+        }
+    }
+    out
+}
+
+pub fn vortex(high: &[f64], low: &[f64], close: &[f64], period: usize) -> (Vec<f64>, Vec<f64>) {
+    let n = close.len();
+    let mut plus = vec![f64::NAN; n];
+    let mut minus = vec![f64::NAN; n];
+    if n == 0 || period == 0 || high.len() != n || low.len() != n {
+        return (plus, minus);
+    }
+
+    let mut tr = vec![f64::NAN; n];
+    let mut vm_plus = vec![f64::NAN; n];
+    let mut vm_minus = vec![f64::NAN; n];
+
+    for i in 1..n {
+        vm_plus[i] = (high[i] - low[i - 1]).abs();
+        vm_minus[i] = (low[i] - high[i - 1]).abs();
+
+        let hl = high[i] - low[i];
+        let hc = (high[i] - close[i - 1]).abs();
+        let lc = (low[i] - close[i - 1]).abs();
+        tr[i] = hl.max(hc).max(lc);
+    }
+
+    let tr_sum = crate::rolling::rolling_sum(&tr, period);
+    let vp_sum = crate::rolling::rolling_sum(&vm_plus, period);
+    let vm_sum = crate::rolling::rolling_sum(&vm_minus, period);
+
+    for i in 0..n {
+        if tr_sum[i].is_nan() || tr_sum[i] == 0.0 || vp_sum[i].is_nan() || vm_sum[i].is_nan() {
+            continue;
+        }
+        plus[i] = vp_sum[i] / tr_sum[i];
+        minus[i] = vm_sum[i] / tr_sum[i];
+    }
+
+    (plus, minus)
+}
+
+/// Same as [`vortex`], but a `NaN` bar inside a summing window is handled
+/// per `gap`: [`vortex`]'s own `tr`/`vm_plus`/`vm_minus` leave bar `0` as
+/// `NaN` (there's no prior bar to diff against), which otherwise poisons
+/// every later window forever through [`crate::rolling::rolling_sum`]'s
+/// running sum rather than just the windows that actually overlap it.
+pub fn vortex_with_gap_policy(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    period: usize,
+    gap: GapPolicy,
+) -> (Vec<f64>, Vec<f64>) {
+    let n = close.len();
+    let mut plus = vec![f64::NAN; n];
+    let mut minus = vec![f64::NAN; n];
+    if n == 0 || period == 0 || high.len() != n || low.len() != n {
+        return (plus, minus);
+    }
+
+    let mut tr = vec![f64::NAN; n];
+    let mut vm_plus = vec![f64::NAN; n];
+    let mut vm_minus = vec![f64::NAN; n];
+
+    for i in 1..n {
+        vm_plus[i] = (high[i] - low[i - 1]).abs();
+        vm_minus[i] = (low[i] - high[i - 1]).abs();
+
+        let hl = high[i] - low[i];
+        let hc = (high[i] - close[i - 1]).abs();
+        let lc = (low[i] - close[i - 1]).abs();
+        tr[i] = hl.max(hc).max(lc);
+    }
+
+    let tr_sum = crate::rolling::rolling_sum_with_gap(&tr, period, gap);
+    let vp_sum = crate::rolling::rolling_sum_with_gap(&vm_plus, period, gap);
+    let vm_sum = crate::rolling::rolling_sum_with_gap(&vm_minus, period, gap);
+
+    for i in 0..n {
+        if tr_sum[i].is_nan() || tr_sum[i] == 0.0 || vp_sum[i].is_nan() || vm_sum[i].is_nan() {
+            continue;
+        }
+        plus[i] = vp_sum[i] / tr_sum[i];
+        minus[i] = vm_sum[i] / tr_sum[i];
+    }
+
+    (plus, minus)
+}
@@ -1,5 +1,98 @@
+use crate::momentum;
 use crate::rolling;
 
+/// The averaging kernel an MA-based indicator's `ma_type` catalog parameter
+/// selects between. `parse` is the inverse of that param's closed string
+/// choices (see `P_MA_TYPE_SMA`/`P_MA_TYPE_EMA` in `core::metadata`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MovingAverageType {
+    Sma,
+    Ema,
+    Wma,
+    /// Triangular moving average: an SMA of an SMA.
+    Tma,
+    /// Chande's Variable Index Dynamic Average: an EMA whose smoothing
+    /// constant is scaled by `|CMO|/100` so it adapts to volatility.
+    Vidya,
+    /// Wilder's/running moving average (alpha = 1/period).
+    Wwma,
+    /// Zero-lag EMA: an EMA of a series de-lagged by `2*x_t - x_{t-lag}`.
+    Zlema,
+    /// Double EMA: `2*ema1 - ema2`, where `ema2` is an EMA of `ema1`.
+    Dema,
+    /// Triple EMA: `3*ema1 - 3*ema2 + ema3`, cascading the EMA three times.
+    Tema,
+    /// Triangular moving average, reusing [`tma`] (an SMA of an SMA).
+    Trima,
+    /// Kaufman's Adaptive Moving Average: an EMA whose smoothing constant
+    /// is scaled by an efficiency ratio between the EMA-style fast (2)
+    /// and slow (30) constants.
+    Kama,
+    /// Hull moving average, reusing [`hma`].
+    Hull,
+    /// Sine-weighted moving average: weights the window by `sin(pi *
+    /// (i+1) / (period+1))`, front- and back-loading less than [`wma`].
+    SineWma,
+    /// Tillson's T3: a cascade of six EMAs blended via `vfactor` (TA-Lib's
+    /// standard default of 0.7) to reduce lag while staying smooth.
+    T3,
+    /// The endpoint of the rolling least-squares regression line, reusing
+    /// [`crate::statistics::linreg_slope`].
+    LinReg,
+}
+
+impl MovingAverageType {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "SMA" => Some(Self::Sma),
+            "EMA" => Some(Self::Ema),
+            "WMA" => Some(Self::Wma),
+            "TMA" => Some(Self::Tma),
+            "VIDYA" => Some(Self::Vidya),
+            "WWMA" => Some(Self::Wwma),
+            "ZLEMA" => Some(Self::Zlema),
+            "DEMA" => Some(Self::Dema),
+            "TEMA" => Some(Self::Tema),
+            "TRIMA" => Some(Self::Trima),
+            "KAMA" => Some(Self::Kama),
+            "HULL" => Some(Self::Hull),
+            "SINE_WMA" => Some(Self::SineWma),
+            "T3" => Some(Self::T3),
+            "LINREG" => Some(Self::LinReg),
+            _ => None,
+        }
+    }
+
+    /// Applies this kernel to `values` with the given `period`.
+    pub fn apply(self, values: &[f64], period: usize) -> Vec<f64> {
+        match self {
+            Self::Sma => rolling::rolling_mean(values, period),
+            Self::Ema => ema(values, period),
+            Self::Wma => wma(values, period),
+            Self::Tma => tma(values, period),
+            Self::Vidya => vidya(values, period),
+            Self::Wwma => rma(values, period),
+            Self::Zlema => zlema(values, period),
+            Self::Dema => dema(values, period),
+            Self::Tema => tema(values, period),
+            Self::Trima => tma(values, period),
+            Self::Kama => kama(values, period),
+            Self::Hull => hma(values, period),
+            Self::SineWma => sine_wma(values, period),
+            Self::T3 => t3(values, period),
+            Self::LinReg => linreg_ma(values, period),
+        }
+    }
+}
+
+/// Central dispatcher for the `ma_type`-style catalog params: applies
+/// whichever [`MovingAverageType`] kernel the caller selected. Thin wrapper
+/// over [`MovingAverageType::apply`] so runtime bindings that only hold a
+/// resolved kind (not the enum's method) have a free function to call.
+pub fn apply_ma(kind: MovingAverageType, values: &[f64], period: usize) -> Vec<f64> {
+    kind.apply(values, period)
+}
+
 pub fn ema(values: &[f64], period: usize) -> Vec<f64> {
     rolling::ema(values, period)
 }
@@ -28,3 +121,271 @@ pub fn hma(values: &[f64], period: usize) -> Vec<f64> {
         .collect();
     rolling::wma(&raw, sqrt_n)
 }
+
+/// Triangular moving average: a windowed mean of length `(period + 1) / 2`
+/// applied twice, front-loading weight toward the middle of the window
+/// rather than its edges. Uses a fresh window sum per output (like [`wma`])
+/// rather than `rolling::rolling_mean`'s incremental running sum, since the
+/// second pass's input already has leading `NaN`s that an incremental sum
+/// would carry forward forever.
+pub fn tma(values: &[f64], period: usize) -> Vec<f64> {
+    let half = ((period + 1) / 2).max(1);
+    windowed_mean(&windowed_mean(values, half), half)
+}
+
+/// Triangular moving average with the textbook asymmetric sub-windows: for
+/// an even `period` the two passes use `period/2` and `period/2 + 1`; for
+/// an odd `period` both passes use `(period + 1) / 2`. More precise than
+/// [`tma`]'s single-half-window shortcut; backs the standalone `trima`
+/// catalog entry.
+pub fn trima(values: &[f64], period: usize) -> Vec<f64> {
+    if period == 0 {
+        return vec![f64::NAN; values.len()];
+    }
+    let (first, second) = if period % 2 == 0 {
+        (period / 2, period / 2 + 1)
+    } else {
+        let half = (period + 1) / 2;
+        (half, half)
+    };
+    windowed_mean(&windowed_mean(values, first), second)
+}
+
+fn windowed_mean(values: &[f64], period: usize) -> Vec<f64> {
+    let n = values.len();
+    let mut out = vec![f64::NAN; n];
+    if period == 0 || n == 0 || period > n {
+        return out;
+    }
+    for i in (period - 1)..n {
+        let window = &values[i + 1 - period..=i];
+        if window.iter().any(|v| v.is_nan()) {
+            continue;
+        }
+        out[i] = window.iter().sum::<f64>() / period as f64;
+    }
+    out
+}
+
+/// Zero-lag EMA: de-lags `values` by `2*x_t - x_{t-lag}` (`lag = (period -
+/// 1) / 2`), then EMAs the result, seeding the recurrence at the first
+/// non-`NaN` de-lagged value instead of assuming index 0 is valid.
+pub fn zlema(values: &[f64], period: usize) -> Vec<f64> {
+    let n = values.len();
+    if period == 0 || n == 0 {
+        return vec![f64::NAN; n];
+    }
+    let lag = (period - 1) / 2;
+    let mut de_lagged = vec![f64::NAN; n];
+    for i in lag..n {
+        de_lagged[i] = 2.0 * values[i] - values[i - lag];
+    }
+    ema_seeded(&de_lagged, period)
+}
+
+/// Chande's VIDYA: an EMA whose smoothing constant is scaled by
+/// `|CMO|/100` each step, so it widens in trending stretches and narrows in
+/// choppy ones instead of staying fixed.
+pub fn vidya(values: &[f64], period: usize) -> Vec<f64> {
+    let n = values.len();
+    if period == 0 || n == 0 {
+        return vec![f64::NAN; n];
+    }
+    let cmo = momentum::cmo(values, period);
+    let base_alpha = 2.0 / (period as f64 + 1.0);
+    let mut out = vec![f64::NAN; n];
+    let Some(start) = cmo.iter().position(|v| !v.is_nan()) else {
+        return out;
+    };
+    out[start] = values[start];
+    for i in (start + 1)..n {
+        let alpha = base_alpha * (cmo[i].abs() / 100.0);
+        out[i] = alpha.mul_add(values[i], (1.0 - alpha) * out[i - 1]);
+    }
+    out
+}
+
+/// Double EMA: `2*ema1 - ema2`, de-lagging a single EMA by subtracting an
+/// EMA of itself.
+pub fn dema(values: &[f64], period: usize) -> Vec<f64> {
+    let ema1 = ema(values, period);
+    let ema2 = ema_seeded(&ema1, period);
+    ema1.iter()
+        .zip(ema2.iter())
+        .map(|(a, b)| (2.0 * *a) - *b)
+        .collect()
+}
+
+/// Triple EMA: `3*ema1 - 3*ema2 + ema3`, cascading the EMA three deep for
+/// less lag than [`dema`] at the cost of more overshoot.
+pub fn tema(values: &[f64], period: usize) -> Vec<f64> {
+    let ema1 = ema(values, period);
+    let ema2 = ema_seeded(&ema1, period);
+    let ema3 = ema_seeded(&ema2, period);
+    let n = values.len();
+    let mut out = vec![f64::NAN; n];
+    for i in 0..n {
+        if !ema1[i].is_nan() && !ema2[i].is_nan() && !ema3[i].is_nan() {
+            out[i] = 3.0 * ema1[i] - 3.0 * ema2[i] + ema3[i];
+        }
+    }
+    out
+}
+
+/// Kaufman's Adaptive Moving Average with TA-Lib's standard fast (2) and
+/// slow (30) smoothing constants. See [`kama_with_rates`] for the
+/// configurable form.
+pub fn kama(values: &[f64], period: usize) -> Vec<f64> {
+    kama_with_rates(values, period, 2, 30)
+}
+
+/// Kaufman's Adaptive Moving Average: an EMA whose smoothing constant is
+/// scaled between a fast and a slow constant by an efficiency ratio (net
+/// change over `er_period` divided by the sum of absolute one-bar changes
+/// over the same window), so it speeds up in trends and slows in chop.
+pub fn kama_with_rates(
+    values: &[f64],
+    er_period: usize,
+    fast_period: usize,
+    slow_period: usize,
+) -> Vec<f64> {
+    let n = values.len();
+    let mut out = vec![f64::NAN; n];
+    if er_period == 0 || n <= er_period {
+        return out;
+    }
+    let fast_sc = 2.0 / (fast_period as f64 + 1.0);
+    let slow_sc = 2.0 / (slow_period as f64 + 1.0);
+    out[er_period] = values[er_period];
+    for i in (er_period + 1)..n {
+        let change = (values[i] - values[i - er_period]).abs();
+        let volatility: f64 = (i - er_period + 1..=i)
+            .map(|j| (values[j] - values[j - 1]).abs())
+            .sum();
+        let efficiency_ratio = if volatility == 0.0 {
+            0.0
+        } else {
+            change / volatility
+        };
+        let smoothing = (efficiency_ratio * (fast_sc - slow_sc) + slow_sc).powi(2);
+        out[i] = smoothing.mul_add(values[i], (1.0 - smoothing) * out[i - 1]);
+    }
+    out
+}
+
+/// Sine-weighted moving average: weights window position `k` (1-indexed)
+/// by `sin(pi * k / (period + 1))`, so the middle of the window carries the
+/// most weight and both edges taper smoothly instead of [`wma`]'s linear
+/// ramp.
+pub fn sine_wma(values: &[f64], period: usize) -> Vec<f64> {
+    let n = values.len();
+    let mut out = vec![f64::NAN; n];
+    if period == 0 || n == 0 || period > n {
+        return out;
+    }
+    let weights: Vec<f64> = (1..=period)
+        .map(|k| (std::f64::consts::PI * k as f64 / (period as f64 + 1.0)).sin())
+        .collect();
+    let weight_sum: f64 = weights.iter().sum();
+    for i in (period - 1)..n {
+        let window = &values[i + 1 - period..=i];
+        if window.iter().any(|v| v.is_nan()) {
+            continue;
+        }
+        let weighted: f64 = window.iter().zip(weights.iter()).map(|(v, w)| v * w).sum();
+        out[i] = weighted / weight_sum;
+    }
+    out
+}
+
+/// Tillson's T3: a cascade of six EMAs blended via `vfactor` (TA-Lib's
+/// standard default of 0.7) to track price more closely than a plain EMA
+/// while staying smooth.
+pub fn t3(values: &[f64], period: usize) -> Vec<f64> {
+    let vfactor = 0.7;
+    let e1 = ema(values, period);
+    let e2 = ema_seeded(&e1, period);
+    let e3 = ema_seeded(&e2, period);
+    let e4 = ema_seeded(&e3, period);
+    let e5 = ema_seeded(&e4, period);
+    let e6 = ema_seeded(&e5, period);
+
+    let c1 = -(vfactor.powi(3));
+    let c2 = 3.0 * vfactor.powi(2) + 3.0 * vfactor.powi(3);
+    let c3 = -6.0 * vfactor.powi(2) - 3.0 * vfactor - 3.0 * vfactor.powi(3);
+    let c4 = 1.0 + 3.0 * vfactor + vfactor.powi(3) + 3.0 * vfactor.powi(2);
+
+    let n = values.len();
+    let mut out = vec![f64::NAN; n];
+    for i in 0..n {
+        if [&e3, &e4, &e5, &e6].iter().all(|series| !series[i].is_nan()) {
+            out[i] = c1 * e6[i] + c2 * e5[i] + c3 * e4[i] + c4 * e3[i];
+        }
+    }
+    out
+}
+
+/// The endpoint of the rolling least-squares regression line (`intercept +
+/// slope * (period - 1)`), reusing [`crate::statistics::linreg_slope`]
+/// rather than re-deriving the closed-form sums.
+pub fn linreg_ma(values: &[f64], period: usize) -> Vec<f64> {
+    let (slope, intercept, _) = crate::statistics::linreg_slope(values, period);
+    slope
+        .iter()
+        .zip(intercept.iter())
+        .map(|(s, i)| i + s * (period as f64 - 1.0))
+        .collect()
+}
+
+/// ALMA (Arnaud Legoux Moving Average): a windowed mean whose weights
+/// follow a Gaussian curve peaking at `offset` (clamped to `[0, 1]`) of the
+/// way through the window and tapering at a rate controlled by `sigma`.
+/// The weight vector depends only on `(window, offset, sigma)`, so it's
+/// computed once and reused for every output bar.
+pub fn alma(values: &[f64], window: usize, offset: f64, sigma: f64) -> Vec<f64> {
+    let n = values.len();
+    let mut out = vec![f64::NAN; n];
+    if window == 0 || sigma <= 0.0 || n < window {
+        return out;
+    }
+    let offset = offset.clamp(0.0, 1.0);
+    let m = offset * (window as f64 - 1.0);
+    let s = window as f64 / sigma;
+    let weights: Vec<f64> = (0..window)
+        .map(|i| (-((i as f64 - m).powi(2)) / (2.0 * s * s)).exp())
+        .collect();
+    let weight_sum: f64 = weights.iter().sum();
+    for i in (window - 1)..n {
+        let values_window = &values[i + 1 - window..=i];
+        if values_window.iter().any(|v| v.is_nan()) {
+            continue;
+        }
+        let weighted: f64 = values_window
+            .iter()
+            .zip(weights.iter())
+            .map(|(v, w)| v * w)
+            .sum();
+        out[i] = weighted / weight_sum;
+    }
+    out
+}
+
+/// Like [`ema`], but seeds the recurrence at the first non-`NaN` value
+/// instead of assuming `values[0]` is valid, for callers (e.g. [`zlema`])
+/// whose input already has leading `NaN`s from an earlier windowing pass.
+fn ema_seeded(values: &[f64], period: usize) -> Vec<f64> {
+    let n = values.len();
+    let mut out = vec![f64::NAN; n];
+    if n == 0 || period == 0 {
+        return out;
+    }
+    let alpha = 2.0 / (period as f64 + 1.0);
+    let Some(start) = values.iter().position(|v| !v.is_nan()) else {
+        return out;
+    };
+    out[start] = values[start];
+    for i in (start + 1)..n {
+        out[i] = alpha.mul_add(values[i], (1.0 - alpha) * out[i - 1]);
+    }
+    out
+}
@@ -0,0 +1,1241 @@
+//! O(1)-per-tick incremental state, mirroring the batch recurrences in
+//! `moving_averages`/`momentum`/`volatility`/`trend` so a live caller can
+//! fold in one bar at a time instead of recomputing the whole series.
+
+use std::collections::VecDeque;
+
+/// Common interface across this module's tick-by-tick state structs:
+/// `update` folds in one more bar and returns the indicator's value once
+/// warmed up, `reset` clears accumulated state back to a freshly
+/// constructed indicator while keeping its configured periods, and
+/// `has_inputs`/`initialized` report progress through that warmup. Mirrors
+/// the Nautilus `Indicator` port (`reset` clears buffers and flips
+/// `has_inputs`/`initialized` back to `false`) and the bbgo push-per-kline
+/// model of folding in one closed bar at a time.
+pub trait IncrementalIndicator {
+    /// The per-tick input this indicator folds in: a single price for
+    /// single-series indicators, an OHLC(V) tuple for bar-based ones.
+    type Input;
+    /// The value `update` produces once warmed up.
+    type Output;
+
+    fn update(&mut self, tick: Self::Input) -> Option<Self::Output>;
+    fn reset(&mut self);
+    fn has_inputs(&self) -> bool;
+    fn initialized(&self) -> bool;
+}
+
+/// Exponential moving average state. `update` never warms up: like the
+/// batch `moving_averages::ema`, the first value seeds the series directly.
+#[derive(Debug, Clone, Copy)]
+pub struct EmaState {
+    alpha: f64,
+    value: Option<f64>,
+}
+
+impl EmaState {
+    pub fn new(period: usize) -> Self {
+        Self {
+            alpha: 2.0 / (period.max(1) as f64 + 1.0),
+            value: None,
+        }
+    }
+
+    pub fn update(&mut self, value: f64) -> f64 {
+        let next = match self.value {
+            None => value,
+            Some(prev) => prev + self.alpha * (value - prev),
+        };
+        self.value = Some(next);
+        next
+    }
+}
+
+impl IncrementalIndicator for EmaState {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, tick: f64) -> Option<f64> {
+        Some(self.update(tick))
+    }
+
+    fn reset(&mut self) {
+        self.value = None;
+    }
+
+    fn has_inputs(&self) -> bool {
+        self.value.is_some()
+    }
+
+    fn initialized(&self) -> bool {
+        self.value.is_some()
+    }
+}
+
+/// Wilder-smoothed RSI state. `update` returns `None` until `period` diffs
+/// have been seeded, matching the batch `momentum::rsi` warmup.
+#[derive(Debug, Clone, Copy)]
+pub struct RsiState {
+    period: usize,
+    prev_value: Option<f64>,
+    seed_gain: f64,
+    seed_loss: f64,
+    seed_count: usize,
+    avg_gain: f64,
+    avg_loss: f64,
+}
+
+impl RsiState {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period: period.max(1),
+            prev_value: None,
+            seed_gain: 0.0,
+            seed_loss: 0.0,
+            seed_count: 0,
+            avg_gain: 0.0,
+            avg_loss: 0.0,
+        }
+    }
+
+    pub fn update(&mut self, value: f64) -> Option<f64> {
+        let prev = self.prev_value.replace(value)?;
+
+        let diff = value - prev;
+        let gain = diff.max(0.0);
+        let loss = (-diff).max(0.0);
+
+        if self.seed_count < self.period {
+            self.seed_gain += gain;
+            self.seed_loss += loss;
+            self.seed_count += 1;
+            if self.seed_count < self.period {
+                return None;
+            }
+            self.avg_gain = self.seed_gain / self.period as f64;
+            self.avg_loss = self.seed_loss / self.period as f64;
+            return Some(rsi_from_averages(self.avg_gain, self.avg_loss));
+        }
+
+        let period = self.period as f64;
+        self.avg_gain = (self.avg_gain * (period - 1.0) + gain) / period;
+        self.avg_loss = (self.avg_loss * (period - 1.0) + loss) / period;
+        Some(rsi_from_averages(self.avg_gain, self.avg_loss))
+    }
+}
+
+impl IncrementalIndicator for RsiState {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, tick: f64) -> Option<f64> {
+        self.update(tick)
+    }
+
+    fn reset(&mut self) {
+        self.prev_value = None;
+        self.seed_gain = 0.0;
+        self.seed_loss = 0.0;
+        self.seed_count = 0;
+        self.avg_gain = 0.0;
+        self.avg_loss = 0.0;
+    }
+
+    fn has_inputs(&self) -> bool {
+        self.prev_value.is_some()
+    }
+
+    fn initialized(&self) -> bool {
+        self.seed_count >= self.period
+    }
+}
+
+fn rsi_from_averages(avg_gain: f64, avg_loss: f64) -> f64 {
+    if avg_loss == 0.0 {
+        if avg_gain > 0.0 {
+            100.0
+        } else {
+            50.0
+        }
+    } else {
+        let rs = avg_gain / avg_loss;
+        100.0 - (100.0 / (1.0 + rs))
+    }
+}
+
+/// Wilder-smoothed ATR state. `update` returns `None` until `period` true
+/// ranges have been seeded, matching the batch `volatility::atr_from_tr`
+/// warmup.
+#[derive(Debug, Clone, Copy)]
+pub struct AtrState {
+    period: usize,
+    prev_close: Option<f64>,
+    seed_sum: f64,
+    seed_count: usize,
+    atr: f64,
+}
+
+impl AtrState {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period: period.max(1),
+            prev_close: None,
+            seed_sum: 0.0,
+            seed_count: 0,
+            atr: 0.0,
+        }
+    }
+
+    pub fn update(&mut self, high: f64, low: f64, close: f64) -> Option<f64> {
+        let tr = match self.prev_close {
+            None => high - low,
+            Some(prev) => (high - low).max((high - prev).abs()).max((low - prev).abs()),
+        };
+        self.prev_close = Some(close);
+
+        if self.seed_count < self.period {
+            self.seed_sum += tr;
+            self.seed_count += 1;
+            if self.seed_count < self.period {
+                return None;
+            }
+            self.atr = self.seed_sum / self.period as f64;
+            return Some(self.atr);
+        }
+
+        self.atr = (self.atr * (self.period as f64 - 1.0) + tr) / self.period as f64;
+        Some(self.atr)
+    }
+}
+
+impl IncrementalIndicator for AtrState {
+    type Input = (f64, f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, tick: (f64, f64, f64)) -> Option<f64> {
+        let (high, low, close) = tick;
+        self.update(high, low, close)
+    }
+
+    fn reset(&mut self) {
+        self.prev_close = None;
+        self.seed_sum = 0.0;
+        self.seed_count = 0;
+        self.atr = 0.0;
+    }
+
+    fn has_inputs(&self) -> bool {
+        self.prev_close.is_some()
+    }
+
+    fn initialized(&self) -> bool {
+        self.seed_count >= self.period
+    }
+}
+
+/// MACD state: two `EmaState`s over the raw value plus a signal `EmaState`
+/// over the MACD line, matching the batch `trend::macd` composition.
+#[derive(Debug, Clone, Copy)]
+pub struct MacdState {
+    fast: EmaState,
+    slow: EmaState,
+    signal: EmaState,
+    warmup: usize,
+    ticks: usize,
+}
+
+impl MacdState {
+    pub fn new(fast_period: usize, slow_period: usize, signal_period: usize) -> Self {
+        Self {
+            fast: EmaState::new(fast_period),
+            slow: EmaState::new(slow_period),
+            signal: EmaState::new(signal_period),
+            warmup: fast_period.max(slow_period).max(signal_period),
+            ticks: 0,
+        }
+    }
+
+    pub fn update(&mut self, value: f64) -> (f64, f64, f64) {
+        self.ticks += 1;
+        let macd = self.fast.update(value) - self.slow.update(value);
+        let signal = self.signal.update(macd);
+        (macd, signal, macd - signal)
+    }
+}
+
+impl IncrementalIndicator for MacdState {
+    type Input = f64;
+    type Output = (f64, f64, f64);
+
+    fn update(&mut self, tick: f64) -> Option<(f64, f64, f64)> {
+        Some(self.update(tick))
+    }
+
+    fn reset(&mut self) {
+        self.fast.reset();
+        self.slow.reset();
+        self.signal.reset();
+        self.ticks = 0;
+    }
+
+    fn has_inputs(&self) -> bool {
+        self.ticks > 0
+    }
+
+    fn initialized(&self) -> bool {
+        self.ticks >= self.warmup
+    }
+}
+
+/// Wilder-smoothed ADX state. `update` returns `None` until the `+DI`/`-DI`
+/// smoothing has seeded over `period` bars and a further `period` `DX`
+/// values have seeded the ADX average itself, matching the two-stage warmup
+/// a batch Wilder ADX would need.
+#[derive(Debug, Clone, Copy)]
+pub struct AdxState {
+    period: usize,
+    prev_high: Option<f64>,
+    prev_low: Option<f64>,
+    prev_close: Option<f64>,
+    seed_tr: f64,
+    seed_plus_dm: f64,
+    seed_minus_dm: f64,
+    seed_count: usize,
+    smoothed_tr: f64,
+    smoothed_plus_dm: f64,
+    smoothed_minus_dm: f64,
+    dx_seed_sum: f64,
+    dx_seed_count: usize,
+    adx: Option<f64>,
+}
+
+impl AdxState {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period: period.max(1),
+            prev_high: None,
+            prev_low: None,
+            prev_close: None,
+            seed_tr: 0.0,
+            seed_plus_dm: 0.0,
+            seed_minus_dm: 0.0,
+            seed_count: 0,
+            smoothed_tr: 0.0,
+            smoothed_plus_dm: 0.0,
+            smoothed_minus_dm: 0.0,
+            dx_seed_sum: 0.0,
+            dx_seed_count: 0,
+            adx: None,
+        }
+    }
+
+    pub fn update(&mut self, high: f64, low: f64, close: f64) -> Option<f64> {
+        let (prev_high, prev_low, prev_close) =
+            match (self.prev_high, self.prev_low, self.prev_close) {
+                (Some(h), Some(l), Some(c)) => (h, l, c),
+                _ => {
+                    self.prev_high = Some(high);
+                    self.prev_low = Some(low);
+                    self.prev_close = Some(close);
+                    return None;
+                }
+            };
+
+        let tr = (high - low)
+            .max((high - prev_close).abs())
+            .max((low - prev_close).abs());
+        let up_move = high - prev_high;
+        let down_move = prev_low - low;
+        let plus_dm = if up_move > down_move && up_move > 0.0 { up_move } else { 0.0 };
+        let minus_dm = if down_move > up_move && down_move > 0.0 { down_move } else { 0.0 };
+
+        self.prev_high = Some(high);
+        self.prev_low = Some(low);
+        self.prev_close = Some(close);
+
+        let period = self.period as f64;
+        if self.seed_count < self.period {
+            self.seed_tr += tr;
+            self.seed_plus_dm += plus_dm;
+            self.seed_minus_dm += minus_dm;
+            self.seed_count += 1;
+            if self.seed_count < self.period {
+                return None;
+            }
+            self.smoothed_tr = self.seed_tr;
+            self.smoothed_plus_dm = self.seed_plus_dm;
+            self.smoothed_minus_dm = self.seed_minus_dm;
+        } else {
+            self.smoothed_tr = (self.smoothed_tr * (period - 1.0) + tr) / period;
+            self.smoothed_plus_dm = (self.smoothed_plus_dm * (period - 1.0) + plus_dm) / period;
+            self.smoothed_minus_dm = (self.smoothed_minus_dm * (period - 1.0) + minus_dm) / period;
+        }
+
+        let plus_di = 100.0 * self.smoothed_plus_dm / self.smoothed_tr;
+        let minus_di = 100.0 * self.smoothed_minus_dm / self.smoothed_tr;
+        let di_sum = plus_di + minus_di;
+        let dx = if di_sum == 0.0 {
+            0.0
+        } else {
+            100.0 * (plus_di - minus_di).abs() / di_sum
+        };
+
+        match self.adx {
+            None => {
+                self.dx_seed_sum += dx;
+                self.dx_seed_count += 1;
+                if self.dx_seed_count < self.period {
+                    return None;
+                }
+                self.adx = Some(self.dx_seed_sum / period);
+                self.adx
+            }
+            Some(prev_adx) => {
+                self.adx = Some((prev_adx * (period - 1.0) + dx) / period);
+                self.adx
+            }
+        }
+    }
+}
+
+impl IncrementalIndicator for AdxState {
+    type Input = (f64, f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, tick: (f64, f64, f64)) -> Option<f64> {
+        let (high, low, close) = tick;
+        self.update(high, low, close)
+    }
+
+    fn reset(&mut self) {
+        self.prev_high = None;
+        self.prev_low = None;
+        self.prev_close = None;
+        self.seed_tr = 0.0;
+        self.seed_plus_dm = 0.0;
+        self.seed_minus_dm = 0.0;
+        self.seed_count = 0;
+        self.smoothed_tr = 0.0;
+        self.smoothed_plus_dm = 0.0;
+        self.smoothed_minus_dm = 0.0;
+        self.dx_seed_sum = 0.0;
+        self.dx_seed_count = 0;
+        self.adx = None;
+    }
+
+    fn has_inputs(&self) -> bool {
+        self.prev_high.is_some()
+    }
+
+    fn initialized(&self) -> bool {
+        self.adx.is_some()
+    }
+}
+
+/// Parabolic SAR state: tracks the current stop, extreme point, and
+/// acceleration factor so each bar advances the trailing stop in O(1).
+/// Direction seeds from the first two bars (long if the second bar's high
+/// doesn't fall below the first), then flips whenever price crosses the
+/// active stop, same as the classic Wilder formulation.
+#[derive(Debug, Clone, Copy)]
+pub struct PsarState {
+    af_step: f64,
+    af_max: f64,
+    sar: Option<f64>,
+    ep: f64,
+    af: f64,
+    is_long: bool,
+    prev_high: Option<f64>,
+    prev_low: Option<f64>,
+}
+
+impl PsarState {
+    pub fn new(af_step: f64, af_max: f64) -> Self {
+        Self {
+            af_step,
+            af_max,
+            sar: None,
+            ep: 0.0,
+            af: af_step,
+            is_long: true,
+            prev_high: None,
+            prev_low: None,
+        }
+    }
+
+    pub fn update(&mut self, high: f64, low: f64) -> Option<f64> {
+        let (prev_high, prev_low) = match (self.prev_high, self.prev_low) {
+            Some(pair) => pair,
+            None => {
+                self.prev_high = Some(high);
+                self.prev_low = Some(low);
+                return None;
+            }
+        };
+
+        let sar = match self.sar {
+            None => {
+                self.is_long = high >= prev_high;
+                self.ep = if self.is_long { high } else { low };
+                self.af = self.af_step;
+                let seeded = if self.is_long { prev_low } else { prev_high };
+                self.sar = Some(seeded);
+                seeded
+            }
+            Some(prev_sar) => {
+                let mut next_sar = prev_sar + self.af * (self.ep - prev_sar);
+                if self.is_long {
+                    next_sar = next_sar.min(prev_low).min(low);
+                    if low < next_sar {
+                        self.is_long = false;
+                        next_sar = self.ep;
+                        self.ep = low;
+                        self.af = self.af_step;
+                    } else if high > self.ep {
+                        self.ep = high;
+                        self.af = (self.af + self.af_step).min(self.af_max);
+                    }
+                } else {
+                    next_sar = next_sar.max(prev_high).max(high);
+                    if high > next_sar {
+                        self.is_long = true;
+                        next_sar = self.ep;
+                        self.ep = high;
+                        self.af = self.af_step;
+                    } else if low < self.ep {
+                        self.ep = low;
+                        self.af = (self.af + self.af_step).min(self.af_max);
+                    }
+                }
+                self.sar = Some(next_sar);
+                next_sar
+            }
+        };
+
+        self.prev_high = Some(high);
+        self.prev_low = Some(low);
+        Some(sar)
+    }
+}
+
+impl IncrementalIndicator for PsarState {
+    type Input = (f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, tick: (f64, f64)) -> Option<f64> {
+        let (high, low) = tick;
+        self.update(high, low)
+    }
+
+    fn reset(&mut self) {
+        self.sar = None;
+        self.ep = 0.0;
+        self.af = self.af_step;
+        self.is_long = true;
+        self.prev_high = None;
+        self.prev_low = None;
+    }
+
+    fn has_inputs(&self) -> bool {
+        self.prev_high.is_some()
+    }
+
+    fn initialized(&self) -> bool {
+        self.sar.is_some()
+    }
+}
+
+/// Supertrend state: an internal [`AtrState`] plus the prior final
+/// upper/lower bands and trend direction, matching the batch
+/// `trend::supertrend` recurrence bar-by-bar. `update` returns `None` until
+/// the ATR itself has warmed up.
+#[derive(Debug, Clone, Copy)]
+pub struct SupertrendState {
+    atr: AtrState,
+    multiplier: f64,
+    final_upper: Option<f64>,
+    final_lower: Option<f64>,
+    trend_up: bool,
+    prev_close: Option<f64>,
+}
+
+impl SupertrendState {
+    pub fn new(period: usize, multiplier: f64) -> Self {
+        Self {
+            atr: AtrState::new(period),
+            multiplier,
+            final_upper: None,
+            final_lower: None,
+            trend_up: true,
+            prev_close: None,
+        }
+    }
+
+    /// Returns `(supertrend, direction)`, matching the batch function's
+    /// return shape.
+    pub fn update(&mut self, high: f64, low: f64, close: f64) -> Option<(f64, f64)> {
+        let atr = self.atr.update(high, low, close)?;
+
+        let hl2 = (high + low) / 2.0;
+        let atr_mul = self.multiplier * atr;
+        let basic_upper = hl2 + atr_mul;
+        let basic_lower = hl2 - atr_mul;
+        let prev_close = self.prev_close.unwrap_or(close);
+
+        self.final_upper = Some(match self.final_upper {
+            None => basic_upper,
+            Some(prev) if basic_upper < prev || prev_close > prev => basic_upper,
+            Some(prev) => prev,
+        });
+        self.final_lower = Some(match self.final_lower {
+            None => basic_lower,
+            Some(prev) if basic_lower > prev || prev_close < prev => basic_lower,
+            Some(prev) => prev,
+        });
+
+        let final_upper = self.final_upper.unwrap();
+        let final_lower = self.final_lower.unwrap();
+
+        if self.trend_up && close < final_lower {
+            self.trend_up = false;
+        } else if !self.trend_up && close > final_upper {
+            self.trend_up = true;
+        }
+        self.prev_close = Some(close);
+
+        let line = if self.trend_up { final_lower } else { final_upper };
+        let direction = if self.trend_up { 1.0 } else { -1.0 };
+        Some((line, direction))
+    }
+}
+
+impl IncrementalIndicator for SupertrendState {
+    type Input = (f64, f64, f64);
+    type Output = (f64, f64);
+
+    fn update(&mut self, tick: (f64, f64, f64)) -> Option<(f64, f64)> {
+        let (high, low, close) = tick;
+        self.update(high, low, close)
+    }
+
+    fn reset(&mut self) {
+        self.atr.reset();
+        self.final_upper = None;
+        self.final_lower = None;
+        self.trend_up = true;
+        self.prev_close = None;
+    }
+
+    fn has_inputs(&self) -> bool {
+        self.atr.has_inputs()
+    }
+
+    fn initialized(&self) -> bool {
+        self.final_upper.is_some() && self.final_lower.is_some()
+    }
+}
+
+/// Rolling minimum over the trailing `period` values, held as a monotonic
+/// increasing deque of `(index, value)` so the front is always the current
+/// minimum: `update` pops dominated entries off the back, pushes the new
+/// value, then drops front entries that have aged out of the window. Each
+/// step is amortized O(1) since every value enters and leaves the deque at
+/// most once.
+#[derive(Debug, Clone)]
+pub struct RollingMinState {
+    period: usize,
+    index: usize,
+    deque: VecDeque<(usize, f64)>,
+}
+
+impl RollingMinState {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period: period.max(1),
+            index: 0,
+            deque: VecDeque::new(),
+        }
+    }
+
+    pub fn update(&mut self, value: f64) -> Option<f64> {
+        while matches!(self.deque.back(), Some(&(_, back)) if super::rolling::min_should_evict(back, value)) {
+            self.deque.pop_back();
+        }
+        self.deque.push_back((self.index, value));
+        while matches!(self.deque.front(), Some(&(idx, _)) if idx + self.period <= self.index) {
+            self.deque.pop_front();
+        }
+        self.index += 1;
+
+        if self.index < self.period {
+            return None;
+        }
+        self.deque.front().map(|&(_, min)| min)
+    }
+
+    pub fn warmup_complete(&self) -> bool {
+        self.index >= self.period
+    }
+}
+
+impl IncrementalIndicator for RollingMinState {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, tick: f64) -> Option<f64> {
+        self.update(tick)
+    }
+
+    fn reset(&mut self) {
+        self.index = 0;
+        self.deque.clear();
+    }
+
+    fn has_inputs(&self) -> bool {
+        self.index > 0
+    }
+
+    fn initialized(&self) -> bool {
+        self.warmup_complete()
+    }
+}
+
+/// Rolling maximum, the mirror image of [`RollingMinState`] with a
+/// monotonic decreasing deque instead.
+#[derive(Debug, Clone)]
+pub struct RollingMaxState {
+    period: usize,
+    index: usize,
+    deque: VecDeque<(usize, f64)>,
+}
+
+impl RollingMaxState {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period: period.max(1),
+            index: 0,
+            deque: VecDeque::new(),
+        }
+    }
+
+    pub fn update(&mut self, value: f64) -> Option<f64> {
+        while matches!(self.deque.back(), Some(&(_, back)) if super::rolling::max_should_evict(back, value)) {
+            self.deque.pop_back();
+        }
+        self.deque.push_back((self.index, value));
+        while matches!(self.deque.front(), Some(&(idx, _)) if idx + self.period <= self.index) {
+            self.deque.pop_front();
+        }
+        self.index += 1;
+
+        if self.index < self.period {
+            return None;
+        }
+        self.deque.front().map(|&(_, max)| max)
+    }
+
+    pub fn warmup_complete(&self) -> bool {
+        self.index >= self.period
+    }
+}
+
+impl IncrementalIndicator for RollingMaxState {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, tick: f64) -> Option<f64> {
+        self.update(tick)
+    }
+
+    fn reset(&mut self) {
+        self.index = 0;
+        self.deque.clear();
+    }
+
+    fn has_inputs(&self) -> bool {
+        self.index > 0
+    }
+
+    fn initialized(&self) -> bool {
+        self.warmup_complete()
+    }
+}
+
+/// Rate-of-change state: a ring buffer of the last `period` values, matching
+/// the batch `momentum::roc` warmup and zero/NaN-guard semantics.
+#[derive(Debug, Clone)]
+pub struct RocState {
+    period: usize,
+    ring: VecDeque<f64>,
+}
+
+impl RocState {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period: period.max(1),
+            ring: VecDeque::new(),
+        }
+    }
+
+    pub fn update(&mut self, value: f64) -> Option<f64> {
+        if self.ring.len() < self.period {
+            self.ring.push_back(value);
+            return None;
+        }
+        let prev = *self.ring.front().unwrap();
+        self.ring.pop_front();
+        self.ring.push_back(value);
+
+        if prev == 0.0 || prev.is_nan() || value.is_nan() {
+            Some(f64::NAN)
+        } else {
+            Some(((value - prev) / prev) * 100.0)
+        }
+    }
+}
+
+impl IncrementalIndicator for RocState {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, tick: f64) -> Option<f64> {
+        self.update(tick)
+    }
+
+    fn reset(&mut self) {
+        self.ring.clear();
+    }
+
+    fn has_inputs(&self) -> bool {
+        !self.ring.is_empty()
+    }
+
+    fn initialized(&self) -> bool {
+        self.ring.len() >= self.period
+    }
+}
+
+/// Bollinger Bands state: a trailing window of the last `period` values
+/// feeding a population mean/stddev, matching the batch
+/// `volatility::bbands` with its default SMA `ma_type`.
+#[derive(Debug, Clone)]
+pub struct BbandsState {
+    period: usize,
+    std_dev: f64,
+    window: VecDeque<f64>,
+}
+
+impl BbandsState {
+    pub fn new(period: usize, std_dev: f64) -> Self {
+        Self {
+            period: period.max(1),
+            std_dev,
+            window: VecDeque::new(),
+        }
+    }
+
+    /// Returns `(upper, middle, lower)` once the window has filled.
+    pub fn update(&mut self, value: f64) -> Option<(f64, f64, f64)> {
+        self.window.push_back(value);
+        if self.window.len() > self.period {
+            self.window.pop_front();
+        }
+        if self.window.len() < self.period {
+            return None;
+        }
+
+        let mean = self.window.iter().sum::<f64>() / self.period as f64;
+        let variance =
+            self.window.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / self.period as f64;
+        let std = variance.sqrt();
+        Some((mean + self.std_dev * std, mean, mean - self.std_dev * std))
+    }
+}
+
+impl IncrementalIndicator for BbandsState {
+    type Input = f64;
+    type Output = (f64, f64, f64);
+
+    fn update(&mut self, tick: f64) -> Option<(f64, f64, f64)> {
+        self.update(tick)
+    }
+
+    fn reset(&mut self) {
+        self.window.clear();
+    }
+
+    fn has_inputs(&self) -> bool {
+        !self.window.is_empty()
+    }
+
+    fn initialized(&self) -> bool {
+        self.window.len() >= self.period
+    }
+}
+
+/// Commodity Channel Index state: a trailing `period` window of typical
+/// prices, matching the batch `momentum::cci`'s mean/mean-deviation with
+/// the same flat-window (`mean_deviation == 0.0` -> `0.0`) convention.
+#[derive(Debug, Clone)]
+pub struct CciState {
+    period: usize,
+    window: VecDeque<f64>,
+}
+
+impl CciState {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period: period.max(1),
+            window: VecDeque::new(),
+        }
+    }
+
+    pub fn update(&mut self, high: f64, low: f64, close: f64) -> Option<f64> {
+        let tp = (high + low + close) / 3.0;
+        self.window.push_back(tp);
+        if self.window.len() > self.period {
+            self.window.pop_front();
+        }
+        if self.window.len() < self.period {
+            return None;
+        }
+
+        let sma = self.window.iter().sum::<f64>() / self.period as f64;
+        let mean_deviation =
+            self.window.iter().map(|v| (v - sma).abs()).sum::<f64>() / self.period as f64;
+        Some(if mean_deviation == 0.0 {
+            0.0
+        } else {
+            (tp - sma) / (0.015 * mean_deviation)
+        })
+    }
+}
+
+impl IncrementalIndicator for CciState {
+    type Input = (f64, f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, tick: (f64, f64, f64)) -> Option<f64> {
+        let (high, low, close) = tick;
+        self.update(high, low, close)
+    }
+
+    fn reset(&mut self) {
+        self.window.clear();
+    }
+
+    fn has_inputs(&self) -> bool {
+        !self.window.is_empty()
+    }
+
+    fn initialized(&self) -> bool {
+        self.window.len() >= self.period
+    }
+}
+
+/// Chande Momentum Oscillator state: trailing `period` gain/loss windows,
+/// matching the batch `momentum::cmo`'s fixed-size window sum (unlike
+/// `RsiState`, this is not Wilder-smoothed).
+#[derive(Debug, Clone)]
+pub struct CmoState {
+    period: usize,
+    prev_value: Option<f64>,
+    gains: VecDeque<f64>,
+    losses: VecDeque<f64>,
+}
+
+impl CmoState {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period: period.max(1),
+            prev_value: None,
+            gains: VecDeque::new(),
+            losses: VecDeque::new(),
+        }
+    }
+
+    pub fn update(&mut self, value: f64) -> Option<f64> {
+        let prev = self.prev_value.replace(value)?;
+
+        let diff = value - prev;
+        let (gain, loss) = if diff > 0.0 { (diff, 0.0) } else { (0.0, -diff) };
+        self.gains.push_back(gain);
+        self.losses.push_back(loss);
+        if self.gains.len() > self.period {
+            self.gains.pop_front();
+            self.losses.pop_front();
+        }
+
+        if self.gains.len() < self.period {
+            return None;
+        }
+        let sum_gain: f64 = self.gains.iter().sum();
+        let sum_loss: f64 = self.losses.iter().sum();
+        let denom = sum_gain + sum_loss;
+        Some(if denom == 0.0 {
+            0.0
+        } else {
+            100.0 * (sum_gain - sum_loss) / denom
+        })
+    }
+}
+
+impl IncrementalIndicator for CmoState {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, tick: f64) -> Option<f64> {
+        self.update(tick)
+    }
+
+    fn reset(&mut self) {
+        self.prev_value = None;
+        self.gains.clear();
+        self.losses.clear();
+    }
+
+    fn has_inputs(&self) -> bool {
+        self.prev_value.is_some()
+    }
+
+    fn initialized(&self) -> bool {
+        self.gains.len() >= self.period
+    }
+}
+
+/// Williams %R state: trailing `period` high/low windows via
+/// [`RollingMaxState`]/[`RollingMinState`], matching the batch
+/// `momentum::williams_r`'s zero-range-is-`0.0` convention.
+#[derive(Debug, Clone)]
+pub struct WilliamsRState {
+    highs: RollingMaxState,
+    lows: RollingMinState,
+}
+
+impl WilliamsRState {
+    pub fn new(period: usize) -> Self {
+        Self {
+            highs: RollingMaxState::new(period),
+            lows: RollingMinState::new(period),
+        }
+    }
+
+    pub fn update(&mut self, high: f64, low: f64, close: f64) -> Option<f64> {
+        let hh = self.highs.update(high);
+        let ll = self.lows.update(low);
+        let (hh, ll) = match (hh, ll) {
+            (Some(hh), Some(ll)) => (hh, ll),
+            _ => return None,
+        };
+        let range = hh - ll;
+        Some(if range == 0.0 {
+            0.0
+        } else {
+            ((hh - close) / range) * -100.0
+        })
+    }
+}
+
+impl IncrementalIndicator for WilliamsRState {
+    type Input = (f64, f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, tick: (f64, f64, f64)) -> Option<f64> {
+        let (high, low, close) = tick;
+        self.update(high, low, close)
+    }
+
+    fn reset(&mut self) {
+        self.highs.reset();
+        self.lows.reset();
+    }
+
+    fn has_inputs(&self) -> bool {
+        self.highs.has_inputs()
+    }
+
+    fn initialized(&self) -> bool {
+        self.highs.initialized() && self.lows.initialized()
+    }
+}
+
+/// On-Balance Volume state: a running total adjusted by each bar's close
+/// direction, matching the batch `volume::obv` exactly. This indicator has
+/// no real warmup, so `initialized` flips true as soon as one bar is seen.
+#[derive(Debug, Clone, Copy)]
+pub struct ObvState {
+    running_total: f64,
+    last_close: Option<f64>,
+}
+
+impl ObvState {
+    pub fn new() -> Self {
+        Self {
+            running_total: 0.0,
+            last_close: None,
+        }
+    }
+
+    pub fn update(&mut self, close: f64, volume: f64) -> f64 {
+        self.running_total = match self.last_close {
+            None => volume,
+            Some(prev) if close > prev => self.running_total + volume,
+            Some(prev) if close < prev => self.running_total - volume,
+            Some(_) => self.running_total,
+        };
+        self.last_close = Some(close);
+        self.running_total
+    }
+}
+
+impl Default for ObvState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IncrementalIndicator for ObvState {
+    type Input = (f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, tick: (f64, f64)) -> Option<f64> {
+        let (close, volume) = tick;
+        Some(self.update(close, volume))
+    }
+
+    fn reset(&mut self) {
+        self.running_total = 0.0;
+        self.last_close = None;
+    }
+
+    fn has_inputs(&self) -> bool {
+        self.last_close.is_some()
+    }
+
+    fn initialized(&self) -> bool {
+        self.last_close.is_some()
+    }
+}
+
+/// Session VWAP state: cumulative price*volume and volume sums, matching
+/// the batch `volume::vwap` exactly. Like OBV, there's no warmup — the
+/// first bar already produces a (single-bar) VWAP.
+#[derive(Debug, Clone, Copy)]
+pub struct VwapState {
+    sum_pv: f64,
+    sum_vol: f64,
+}
+
+impl VwapState {
+    pub fn new() -> Self {
+        Self {
+            sum_pv: 0.0,
+            sum_vol: 0.0,
+        }
+    }
+
+    pub fn update(&mut self, high: f64, low: f64, close: f64, volume: f64) -> f64 {
+        let tp = (high + low + close) / 3.0;
+        self.sum_pv += tp * volume;
+        self.sum_vol += volume;
+        if self.sum_vol > 0.0 {
+            self.sum_pv / self.sum_vol
+        } else {
+            tp
+        }
+    }
+}
+
+impl Default for VwapState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IncrementalIndicator for VwapState {
+    type Input = (f64, f64, f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, tick: (f64, f64, f64, f64)) -> Option<f64> {
+        let (high, low, close, volume) = tick;
+        Some(self.update(high, low, close, volume))
+    }
+
+    fn reset(&mut self) {
+        self.sum_pv = 0.0;
+        self.sum_vol = 0.0;
+    }
+
+    fn has_inputs(&self) -> bool {
+        self.sum_vol > 0.0
+    }
+
+    fn initialized(&self) -> bool {
+        self.has_inputs()
+    }
+}
+
+/// Chaikin Money Flow state: trailing money-flow-volume and volume windows
+/// over `period` bars, matching the batch `volume::cmf` exactly.
+#[derive(Debug, Clone)]
+pub struct CmfState {
+    period: usize,
+    mfv_window: VecDeque<f64>,
+    volume_window: VecDeque<f64>,
+}
+
+impl CmfState {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period: period.max(1),
+            mfv_window: VecDeque::new(),
+            volume_window: VecDeque::new(),
+        }
+    }
+
+    pub fn update(&mut self, high: f64, low: f64, close: f64, volume: f64) -> Option<f64> {
+        let hl = high - low;
+        let mfv = if hl == 0.0 {
+            0.0
+        } else {
+            (((close - low) - (high - close)) / hl) * volume
+        };
+
+        self.mfv_window.push_back(mfv);
+        self.volume_window.push_back(volume);
+        if self.mfv_window.len() > self.period {
+            self.mfv_window.pop_front();
+            self.volume_window.pop_front();
+        }
+
+        if self.mfv_window.len() < self.period {
+            return None;
+        }
+        let sum_mfv: f64 = self.mfv_window.iter().sum();
+        let sum_vol: f64 = self.volume_window.iter().sum();
+        Some(if sum_vol == 0.0 { 0.0 } else { sum_mfv / sum_vol })
+    }
+}
+
+impl IncrementalIndicator for CmfState {
+    type Input = (f64, f64, f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, tick: (f64, f64, f64, f64)) -> Option<f64> {
+        let (high, low, close, volume) = tick;
+        self.update(high, low, close, volume)
+    }
+
+    fn reset(&mut self) {
+        self.mfv_window.clear();
+        self.volume_window.clear();
+    }
+
+    fn has_inputs(&self) -> bool {
+        !self.mfv_window.is_empty()
+    }
+
+    fn initialized(&self) -> bool {
+        self.mfv_window.len() >= self.period
+    }
+}
@@ -0,0 +1,389 @@
+use crate::moving_averages::MovingAverageType;
+
+/// MACD: the difference between a fast and slow moving average of `values`
+/// (`ma_type` selects the kernel for both), plus a signal line (a
+/// `signal_ma_type` moving average of the MACD line) and the histogram
+/// between the two.
+pub fn macd(
+    values: &[f64],
+    fast_period: usize,
+    slow_period: usize,
+    signal_period: usize,
+    ma_type: MovingAverageType,
+    signal_ma_type: MovingAverageType,
+) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let fast = ma_type.apply(values, fast_period);
+    let slow = ma_type.apply(values, slow_period);
+    let n = values.len();
+    let mut macd_line = vec![f64::NAN; n];
+
+    for i in 0..n {
+        if !fast[i].is_nan() && !slow[i].is_nan() {
+            macd_line[i] = fast[i] - slow[i];
+        }
+    }
+
+    let signal_line = signal_ma_type.apply(&macd_line, signal_period);
+    let mut histogram = vec![f64::NAN; n];
+
+    for i in 0..n {
+        if !macd_line[i].is_nan() && !signal_line[i].is_nan() {
+            histogram[i] = macd_line[i] - signal_line[i];
+        }
+    }
+
+    (macd_line, signal_line, histogram)
+}
+
+/// Supertrend: an ATR-band trend-follower. Returns `(supertrend, direction)`,
+/// where `direction` is `1.0` while price is above the active band (bullish)
+/// and `-1.0` while below it (bearish). The bands only ever tighten toward
+/// price within a trend and snap to the fresh basic band on a flip, per the
+/// classic formulation.
+pub fn supertrend(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    period: usize,
+    multiplier: f64,
+) -> (Vec<f64>, Vec<f64>) {
+    let n = close.len();
+    let atr = crate::volatility::atr(high, low, close, period);
+    let (basic_upper, basic_lower) = basic_bands(high, low, &atr, multiplier);
+    let mut line = vec![f64::NAN; n];
+    let mut direction = vec![f64::NAN; n];
+
+    let mut final_upper = f64::NAN;
+    let mut final_lower = f64::NAN;
+    let mut trend_up = true;
+
+    for i in 0..n {
+        if atr[i].is_nan() {
+            continue;
+        }
+
+        let prev_close = if i > 0 { close[i - 1] } else { close[i] };
+
+        final_upper = if final_upper.is_nan()
+            || basic_upper[i] < final_upper
+            || prev_close > final_upper
+        {
+            basic_upper[i]
+        } else {
+            final_upper
+        };
+        final_lower = if final_lower.is_nan()
+            || basic_lower[i] > final_lower
+            || prev_close < final_lower
+        {
+            basic_lower[i]
+        } else {
+            final_lower
+        };
+
+        if trend_up && close[i] < final_lower {
+            trend_up = false;
+        } else if !trend_up && close[i] > final_upper {
+            trend_up = true;
+        }
+
+        line[i] = if trend_up { final_lower } else { final_upper };
+        direction[i] = if trend_up { 1.0 } else { -1.0 };
+    }
+
+    (line, direction)
+}
+
+/// The raw `hl2 ± multiplier * atr` bands, one independent computation per
+/// index -- unlike the tightening/flip loop above that consumes them, this
+/// has no dependency between indices, so it vectorizes in
+/// [`crate::rolling::simd_enabled`]-width chunks instead of one index at a
+/// time.
+fn basic_bands(high: &[f64], low: &[f64], atr: &[f64], multiplier: f64) -> (Vec<f64>, Vec<f64>) {
+    let n = high.len();
+    let mut upper = vec![f64::NAN; n];
+    let mut lower = vec![f64::NAN; n];
+    if crate::rolling::simd_enabled() {
+        basic_bands_into::<8>(high, low, atr, multiplier, &mut upper, &mut lower);
+    } else {
+        basic_bands_into::<4>(high, low, atr, multiplier, &mut upper, &mut lower);
+    }
+    (upper, lower)
+}
+
+fn basic_bands_into<const N: usize>(
+    high: &[f64],
+    low: &[f64],
+    atr: &[f64],
+    multiplier: f64,
+    upper: &mut [f64],
+    lower: &mut [f64],
+) {
+    let n = high.len();
+    let mut i = 0;
+    while i + N <= n {
+        for lane in 0..N {
+            band_at(high, low, atr, multiplier, i + lane, upper, lower);
+        }
+        i += N;
+    }
+    for idx in i..n {
+        band_at(high, low, atr, multiplier, idx, upper, lower);
+    }
+}
+
+fn band_at(
+    high: &[f64],
+    low: &[f64],
+    atr: &[f64],
+    multiplier: f64,
+    idx: usize,
+    upper: &mut [f64],
+    lower: &mut [f64],
+) {
+    if atr[idx].is_nan() {
+        return;
+    }
+    let hl2 = (high[idx] + low[idx]) / 2.0;
+    let atr_mul = multiplier * atr[idx];
+    upper[idx] = hl2 + atr_mul;
+    lower[idx] = hl2 - atr_mul;
+}
+
+/// Seasonal-trend decomposition via iterated Loess (classic STL). Returns
+/// `(trend, seasonal, remainder)`, each the same length as `values`.
+/// `values.len() < 2 * period` (or `period == 0`) is degenerate and yields
+/// three `NaN`-filled series rather than panicking, matching the warmup
+/// convention the rest of this module uses for undersized inputs.
+pub fn stl(values: &[f64], period: usize) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let n = values.len();
+    if period == 0 || n < 2 * period {
+        let nan = vec![f64::NAN; n];
+        return (nan.clone(), nan.clone(), nan);
+    }
+
+    const OUTER_ITERATIONS: usize = 2;
+    let seasonal_window = next_odd(7).min(next_odd(n));
+    let trend_window = {
+        let raw = 1.5 * period as f64 / (1.0 - 1.5 / seasonal_window as f64);
+        next_odd(raw.ceil().max(3.0) as usize).min(next_odd(n))
+    };
+
+    let mut trend = vec![0.0; n];
+    let mut seasonal = vec![0.0; n];
+    let mut remainder = vec![0.0; n];
+    let mut robustness_weights = vec![1.0; n];
+
+    for outer in 0..OUTER_ITERATIONS {
+        let detrended: Vec<f64> = values
+            .iter()
+            .zip(&trend)
+            .map(|(v, t)| v - t)
+            .collect();
+
+        let c = cycle_subseries_smooth(&detrended, &robustness_weights, period, seasonal_window);
+        let low_pass = low_pass_filter(&c, period, trend_window);
+
+        for t in 0..n {
+            seasonal[t] = c[t + period] - low_pass[t];
+        }
+
+        let deseasonalized: Vec<f64> = values
+            .iter()
+            .zip(&seasonal)
+            .map(|(v, s)| v - s)
+            .collect();
+        trend = loess_fit(&deseasonalized, trend_window, Some(&robustness_weights));
+
+        for t in 0..n {
+            remainder[t] = values[t] - trend[t] - seasonal[t];
+        }
+
+        if outer + 1 < OUTER_ITERATIONS {
+            robustness_weights = bisquare_weights(&remainder);
+        }
+    }
+
+    (trend, seasonal, remainder)
+}
+
+/// Groups `detrended` by phase (`index % period`), Loess-smooths each
+/// cycle-subseries, and extends each by one cycle on both ends. Returns the
+/// reassembled series of length `detrended.len() + 2 * period`, where index
+/// `i` holds the value at time `i - period`.
+fn cycle_subseries_smooth(
+    detrended: &[f64],
+    weights: &[f64],
+    period: usize,
+    seasonal_window: usize,
+) -> Vec<f64> {
+    let n = detrended.len();
+    let mut c = vec![0.0; n + 2 * period];
+
+    for phase in 0..period {
+        let times: Vec<usize> = (phase..n).step_by(period).collect();
+        let subseries: Vec<f64> = times.iter().map(|&t| detrended[t]).collect();
+        let sub_weights: Vec<f64> = times.iter().map(|&t| weights[t]).collect();
+        let window = seasonal_window.min(next_odd(subseries.len()));
+
+        let extended = loess_fit_with_extrapolation(&subseries, window, &sub_weights);
+        for (idx, &value) in extended.iter().enumerate() {
+            // idx 0 is one cycle before `times[0]`, idx extended.len()-1 is
+            // one cycle after `times[last]`; everything between lines up
+            // with `times` one-to-one.
+            let time = phase as isize + (idx as isize - 1) * period as isize;
+            let c_index = time + period as isize;
+            if c_index >= 0 && (c_index as usize) < c.len() {
+                c[c_index as usize] = value;
+            }
+        }
+    }
+
+    c
+}
+
+/// Low-pass filters `c` (length `n + 2*period`) down to length `n`: two
+/// moving averages of length `period`, then one of length 3, then a final
+/// Loess smooth of `trend_window` to remove residual high-frequency noise.
+fn low_pass_filter(c: &[f64], period: usize, trend_window: usize) -> Vec<f64> {
+    let pass1 = moving_average(c, period);
+    let pass2 = moving_average(&pass1, period);
+    let pass3 = moving_average(&pass2, 3);
+    loess_fit(&pass3, trend_window.min(next_odd(pass3.len())), None)
+}
+
+fn moving_average(ys: &[f64], window: usize) -> Vec<f64> {
+    if window == 0 || window > ys.len() {
+        return Vec::new();
+    }
+    let mut out = Vec::with_capacity(ys.len() - window + 1);
+    let mut sum: f64 = ys[..window].iter().sum();
+    out.push(sum / window as f64);
+    for i in window..ys.len() {
+        sum += ys[i] - ys[i - window];
+        out.push(sum / window as f64);
+    }
+    out
+}
+
+/// Local linear (degree-1) regression with tricube weighting, one fitted
+/// value per input point. Boundary points use an asymmetric nearest-neighbor
+/// window (clipped to the series edge) rather than a centered one.
+fn loess_fit(ys: &[f64], window: usize, robustness_weights: Option<&[f64]>) -> Vec<f64> {
+    let n = ys.len();
+    let window = window.min(n).max(1);
+    (0..n)
+        .map(|i| loess_fit_at(ys, robustness_weights, window, i as f64, n))
+        .collect()
+}
+
+/// Like [`loess_fit`], but also evaluates the boundary regressions one step
+/// beyond each end, returning `ys.len() + 2` values: an extrapolated point
+/// before index 0, the smoothed series, then an extrapolated point after the
+/// last index.
+fn loess_fit_with_extrapolation(ys: &[f64], window: usize, weights: &[f64]) -> Vec<f64> {
+    let n = ys.len();
+    let window = window.min(n).max(1);
+    let mut out = Vec::with_capacity(n + 2);
+    out.push(loess_fit_at(ys, Some(weights), window, -1.0, n));
+    for i in 0..n {
+        out.push(loess_fit_at(ys, Some(weights), window, i as f64, n));
+    }
+    out.push(loess_fit_at(ys, Some(weights), window, n as f64, n));
+    out
+}
+
+fn loess_fit_at(
+    ys: &[f64],
+    robustness_weights: Option<&[f64]>,
+    window: usize,
+    x: f64,
+    n: usize,
+) -> f64 {
+    if n == 0 {
+        return f64::NAN;
+    }
+    let center = x.round().clamp(0.0, n as f64 - 1.0) as usize;
+    let mut lo = center.saturating_sub(window / 2);
+    if lo + window > n {
+        lo = n - window;
+    }
+    let hi = lo + window - 1;
+
+    let max_dist = (x - lo as f64)
+        .abs()
+        .max((x - hi as f64).abs())
+        .max(1e-9);
+
+    let mut sum_w = 0.0;
+    let mut sum_wx = 0.0;
+    let mut sum_wy = 0.0;
+    let mut sum_wxx = 0.0;
+    let mut sum_wxy = 0.0;
+
+    for j in lo..=hi {
+        let dist = ((x - j as f64).abs() / max_dist).min(1.0);
+        let tricube = (1.0 - dist.powi(3)).max(0.0).powi(3);
+        let robustness = robustness_weights.map_or(1.0, |w| w[j]);
+        let weight = tricube * robustness;
+
+        let xj = j as f64;
+        sum_w += weight;
+        sum_wx += weight * xj;
+        sum_wy += weight * ys[j];
+        sum_wxx += weight * xj * xj;
+        sum_wxy += weight * xj * ys[j];
+    }
+
+    if sum_w <= 1e-12 {
+        return ys[center];
+    }
+
+    let denom = sum_w * sum_wxx - sum_wx * sum_wx;
+    if denom.abs() < 1e-12 {
+        return sum_wy / sum_w;
+    }
+
+    let slope = (sum_w * sum_wxy - sum_wx * sum_wy) / denom;
+    let intercept = (sum_wy - slope * sum_wx) / sum_w;
+    intercept + slope * x
+}
+
+/// Bisquare robustness weights from remainder magnitudes, scaled by
+/// `6 * median(|remainder|)` as classic STL prescribes.
+fn bisquare_weights(remainder: &[f64]) -> Vec<f64> {
+    let abs_remainder: Vec<f64> = remainder.iter().map(|r| r.abs()).collect();
+    let h = 6.0 * median(&abs_remainder);
+    if h <= 1e-12 {
+        return vec![1.0; remainder.len()];
+    }
+    abs_remainder
+        .iter()
+        .map(|&r| {
+            let u = (r / h).min(1.0);
+            (1.0 - u * u).powi(2)
+        })
+        .collect()
+}
+
+fn median(values: &[f64]) -> f64 {
+    let mut sorted: Vec<f64> = values.iter().copied().filter(|v| !v.is_nan()).collect();
+    if sorted.is_empty() {
+        return f64::NAN;
+    }
+    sorted.sort_by(|a, b| a.partial_cmp(b).expect("NaN filtered out above"));
+    let mid = sorted.len() / 2;
+    if sorted.len() % 2 == 0 {
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    } else {
+        sorted[mid]
+    }
+}
+
+fn next_odd(value: usize) -> usize {
+    if value % 2 == 0 {
+        value + 1
+    } else {
+        value
+    }
+}
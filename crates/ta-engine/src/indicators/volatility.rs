@@ -0,0 +1,235 @@
+pub fn atr_from_tr(true_ranges: &[f64], period: usize) -> Vec<f64> {
+    crate::rolling::wilder_smooth(true_ranges, period)
+}
+
+/// How the true-range series is averaged into ATR. Charting platforms
+/// disagree on this, so it's exposed as a catalog param rather than baked
+/// into `atr`/`keltner` the way [`crate::momentum::RsiMethod`] is for RSI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AtrSmoothing {
+    /// Wilder's running average -- matches the plain [`atr`].
+    Wilder,
+    /// A plain rolling mean of the true-range series.
+    Sma,
+    /// An EMA of the true-range series with alpha = `2/(period+1)`.
+    Ema,
+}
+
+impl AtrSmoothing {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "wilder" => Some(Self::Wilder),
+            "sma" => Some(Self::Sma),
+            "ema" => Some(Self::Ema),
+            _ => None,
+        }
+    }
+}
+
+pub fn atr_from_tr_with_smoothing(
+    true_ranges: &[f64],
+    period: usize,
+    smoothing: AtrSmoothing,
+) -> Vec<f64> {
+    match smoothing {
+        AtrSmoothing::Wilder => atr_from_tr(true_ranges, period),
+        AtrSmoothing::Sma => crate::rolling::rolling_mean(true_ranges, period),
+        AtrSmoothing::Ema => crate::moving_averages::ema(true_ranges, period),
+    }
+}
+
+/// True range, elementwise: `high[i]-low[i]` and the two close-anchored
+/// spans have no dependency between indices, unlike the Wilder recurrence
+/// `atr_from_tr` folds them into next, so this is vectorized in
+/// [`crate::rolling::simd_enabled`]-width chunks while that recurrence stays
+/// a plain sequential loop.
+pub fn atr(high: &[f64], low: &[f64], close: &[f64], period: usize) -> Vec<f64> {
+    let n = close.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let mut tr = vec![0.0; n];
+    if crate::rolling::simd_enabled() {
+        true_range_into::<8>(high, low, close, &mut tr);
+    } else {
+        true_range_into::<4>(high, low, close, &mut tr);
+    }
+    tr[0] = high[0] - low[0];
+    atr_from_tr(&tr, period)
+}
+
+pub fn atr_with_smoothing(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    period: usize,
+    smoothing: AtrSmoothing,
+) -> Vec<f64> {
+    if smoothing == AtrSmoothing::Wilder {
+        return atr(high, low, close, period);
+    }
+
+    let n = close.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let mut tr = vec![0.0; n];
+    if crate::rolling::simd_enabled() {
+        true_range_into::<8>(high, low, close, &mut tr);
+    } else {
+        true_range_into::<4>(high, low, close, &mut tr);
+    }
+    tr[0] = high[0] - low[0];
+    atr_from_tr_with_smoothing(&tr, period, smoothing)
+}
+
+fn true_range_into<const N: usize>(high: &[f64], low: &[f64], close: &[f64], tr: &mut [f64]) {
+    let n = close.len();
+    let mut i = 1;
+    while i + N <= n {
+        for lane in 0..N {
+            let idx = i + lane;
+            let hl = high[idx] - low[idx];
+            let hc = (high[idx] - close[idx - 1]).abs();
+            let lc = (low[idx] - close[idx - 1]).abs();
+            tr[idx] = hl.max(hc).max(lc);
+        }
+        i += N;
+    }
+    for idx in i..n {
+        let hl = high[idx] - low[idx];
+        let hc = (high[idx] - close[idx - 1]).abs();
+        let lc = (low[idx] - close[idx - 1]).abs();
+        tr[idx] = hl.max(hc).max(lc);
+    }
+}
+
+pub fn bbands(
+    values: &[f64],
+    period: usize,
+    std_dev: f64,
+    ma_type: crate::moving_averages::MovingAverageType,
+) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let n = values.len();
+    let mean = ma_type.apply(values, period);
+    let std = crate::rolling::rolling_std(values, period);
+    let mut upper = vec![f64::NAN; n];
+    let mut lower = vec![f64::NAN; n];
+
+    for i in 0..n {
+        if !mean[i].is_nan() && !std[i].is_nan() {
+            upper[i] = mean[i] + (std_dev * std[i]);
+            lower[i] = mean[i] - (std_dev * std[i]);
+        }
+    }
+
+    (upper, mean, lower)
+}
+
+pub fn donchian(high: &[f64], low: &[f64], period: usize) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let upper = crate::rolling::rolling_max(high, period);
+    let lower = crate::rolling::rolling_min(low, period);
+    let n = upper.len().min(lower.len());
+    let mut middle = vec![f64::NAN; n];
+    for i in 0..n {
+        if !upper[i].is_nan() && !lower[i].is_nan() {
+            middle[i] = (upper[i] + lower[i]) / 2.0;
+        }
+    }
+    (upper, lower, middle)
+}
+
+/// Bollinger/Keltner "squeeze": true for bars where the Bollinger Bands sit
+/// entirely inside the Keltner Channel, the classic volatility-contraction
+/// setup that often precedes a breakout. Paired with a momentum histogram --
+/// close minus the Donchian midpoint over the same `period` -- since the
+/// squeeze flag alone doesn't say which way price is leaning while
+/// compressed.
+pub fn squeeze(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    period: usize,
+    std_dev: f64,
+    ema_period: usize,
+    atr_period: usize,
+    multiplier: f64,
+    ma_type: crate::moving_averages::MovingAverageType,
+) -> (Vec<bool>, Vec<f64>) {
+    let (bb_upper, _, bb_lower) = bbands(close, period, std_dev, ma_type);
+    let (kc_upper, _, kc_lower) =
+        keltner(high, low, close, ema_period, atr_period, multiplier, ma_type);
+    let (_, _, dc_middle) = donchian(high, low, period);
+
+    let n = close.len();
+    let mut squeeze_on = vec![false; n];
+    let mut momentum = vec![f64::NAN; n];
+
+    for i in 0..n {
+        if !bb_upper[i].is_nan()
+            && !bb_lower[i].is_nan()
+            && !kc_upper[i].is_nan()
+            && !kc_lower[i].is_nan()
+        {
+            squeeze_on[i] = bb_upper[i] < kc_upper[i] && bb_lower[i] > kc_lower[i];
+        }
+        if !dc_middle[i].is_nan() {
+            momentum[i] = close[i] - dc_middle[i];
+        }
+    }
+
+    (squeeze_on, momentum)
+}
+
+pub fn keltner(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    ema_period: usize,
+    atr_period: usize,
+    multiplier: f64,
+    ma_type: crate::moving_averages::MovingAverageType,
+) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let middle = ma_type.apply(close, ema_period);
+    let atr_vals = atr(high, low, close, atr_period);
+    let n = close.len();
+    let mut upper = vec![f64::NAN; n];
+    let mut lower = vec![f64::NAN; n];
+    for i in 0..n {
+        if !middle[i].is_nan() && !atr_vals[i].is_nan() {
+            let offset = atr_vals[i] * multiplier;
+            upper[i] = middle[i] + offset;
+            lower[i] = middle[i] - offset;
+        }
+    }
+    (upper, middle, lower)
+}
+
+pub fn keltner_with_smoothing(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    ema_period: usize,
+    atr_period: usize,
+    multiplier: f64,
+    ma_type: crate::moving_averages::MovingAverageType,
+    smoothing: AtrSmoothing,
+) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    if smoothing == AtrSmoothing::Wilder {
+        return keltner(high, low, close, ema_period, atr_period, multiplier, ma_type);
+    }
+
+    let middle = ma_type.apply(close, ema_period);
+    let atr_vals = atr_with_smoothing(high, low, close, atr_period, smoothing);
+    let n = close.len();
+    let mut upper = vec![f64::NAN; n];
+    let mut lower = vec![f64::NAN; n];
+    for i in 0..n {
+        if !middle[i].is_nan() && !atr_vals[i].is_nan() {
+            let offset = atr_vals[i] * multiplier;
+            upper[i] = middle[i] + offset;
+            lower[i] = middle[i] - offset;
+        }
+    }
+    (upper, middle, lower)
+}
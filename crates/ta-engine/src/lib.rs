@@ -3,14 +3,28 @@
 pub mod core;
 pub mod execution;
 pub mod indicators;
+pub mod rating;
 pub mod runtime;
+pub mod statistics;
+pub mod strategy;
+pub mod volume;
 
-pub use core::{contracts, dataset, dataset_ops, events, metadata};
-pub use execution::incremental;
-pub use indicators::{momentum, moving_averages, rolling, trend, volatility, volume};
+pub use core::{contracts, dataset, dataset_mmap, dataset_ops, events, metadata};
+pub use execution::{bench, incremental, vm};
+pub use indicators::{
+    fill_policy, gap_policy, momentum, moving_averages, rolling, streaming, trend, volatility,
+};
+pub use fill_policy::FillPolicy;
+pub use gap_policy::GapPolicy;
 pub use runtime::{
-    compute_indicator, runtime_catalog, ComputeIndicatorRequest, ComputeIndicatorResponse,
-    ComputeRuntimeError, NamedSeries, OhlcvInput, RuntimeCatalogEntry,
+    compute_batch, compute_indicator, compute_indicator_batch, compute_pipeline, evaluate_pipeline,
+    execute_indicator_plan, runtime_catalog, BinOp, BinOpRhs, ComputeIndicatorBatchResponse,
+    ComputeIndicatorRequest, ComputeIndicatorResponse, ComputeRuntimeError, EventKind,
+    IndicatorPlan, NamedSeries, NamedSeriesBatch, OhlcvBatch, OhlcvInput, ParamError,
+    ParamErrorDetail, ParamErrorKind, ParamRule, ParamValue, Pipeline, PipelineIndicatorSpec,
+    PipelineNode, PipelineNodeKind, PipelineValue,
+    PlanInputSource, PlanNode, RuntimeCatalogEntry, ValidatedParams, validate,
+    validate_and_normalize,
 };
 
 pub fn engine_version() -> &'static str {
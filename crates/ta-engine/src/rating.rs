@@ -0,0 +1,114 @@
+/// Consensus "Technical Rating": combines the standard buy/sell rule of
+/// several catalog oscillators (RSI, Stochastic, CCI, AO, MACD, CMO) and
+/// two moving-average crosses (SMA, EMA) into a mean vote in `[-1, 1]`.
+///
+/// Each contributor votes `1.0` (buy), `-1.0` (sell) or `0.0` (neutral)
+/// using its own standard threshold. `oscillators_rating` is the mean
+/// oscillator vote, `ma_rating` is the mean moving-average vote, and
+/// `all_rating` is the mean vote across every contributor. Bucket the
+/// mean vote as Strong Sell (<= -0.5), Sell (<= -0.1), Neutral, Buy
+/// (>= 0.1) or Strong Buy (>= 0.5) for a human-readable label.
+pub fn technical_rating(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    ma_period: usize,
+) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let n = close.len();
+    let mut oscillators_rating = vec![f64::NAN; n];
+    let mut ma_rating = vec![f64::NAN; n];
+    let mut all_rating = vec![f64::NAN; n];
+    if n == 0 || high.len() != n || low.len() != n {
+        return (oscillators_rating, ma_rating, all_rating);
+    }
+
+    let rsi = crate::momentum::rsi(close, 14);
+    let (stoch_k, stoch_d) = crate::momentum::stochastic_kd(high, low, close, 14, 3, 3);
+    let cci = crate::momentum::cci(high, low, close, 20);
+    let ao = crate::momentum::ao(high, low, 5, 34);
+    let (macd_line, macd_signal, _) = crate::trend::macd(
+        close,
+        12,
+        26,
+        9,
+        crate::moving_averages::MovingAverageType::Ema,
+        crate::moving_averages::MovingAverageType::Ema,
+    );
+    let cmo = crate::momentum::cmo(close, 9);
+    let sma = crate::rolling::rolling_mean(close, ma_period);
+    let ema = crate::moving_averages::ema(close, ma_period);
+
+    for i in 0..n {
+        let rsi_vote = vote(rsi[i], |v| v < 30.0, |v| v > 70.0);
+        let stoch_vote = if stoch_k[i].is_nan() || stoch_d[i].is_nan() {
+            f64::NAN
+        } else {
+            vote_cond(
+                stoch_k[i] > stoch_d[i] && stoch_k[i] < 80.0,
+                stoch_k[i] < stoch_d[i] && stoch_k[i] > 20.0,
+            )
+        };
+        let cci_vote = vote(cci[i], |v| v < -100.0, |v| v > 100.0);
+        let ao_vote = vote(ao[i], |v| v > 0.0, |v| v < 0.0);
+        let macd_vote = if macd_line[i].is_nan() || macd_signal[i].is_nan() {
+            f64::NAN
+        } else {
+            vote_cond(macd_line[i] > macd_signal[i], macd_line[i] < macd_signal[i])
+        };
+        let cmo_vote = vote(cmo[i], |v| v < -50.0, |v| v > 50.0);
+        let sma_vote = if sma[i].is_nan() {
+            f64::NAN
+        } else {
+            vote_cond(close[i] > sma[i], close[i] < sma[i])
+        };
+        let ema_vote = if ema[i].is_nan() {
+            f64::NAN
+        } else {
+            vote_cond(close[i] > ema[i], close[i] < ema[i])
+        };
+
+        let osc_votes = [rsi_vote, stoch_vote, cci_vote, ao_vote, macd_vote, cmo_vote];
+        let ma_votes = [sma_vote, ema_vote];
+
+        oscillators_rating[i] = mean_ignoring_nan(&osc_votes);
+        ma_rating[i] = mean_ignoring_nan(&ma_votes);
+        all_rating[i] = mean_ignoring_nan(&[
+            rsi_vote, stoch_vote, cci_vote, ao_vote, macd_vote, cmo_vote, sma_vote, ema_vote,
+        ]);
+    }
+
+    (oscillators_rating, ma_rating, all_rating)
+}
+
+fn vote(value: f64, buy: impl Fn(f64) -> bool, sell: impl Fn(f64) -> bool) -> f64 {
+    if value.is_nan() {
+        return f64::NAN;
+    }
+    vote_cond(buy(value), sell(value))
+}
+
+fn vote_cond(buy: bool, sell: bool) -> f64 {
+    if buy {
+        1.0
+    } else if sell {
+        -1.0
+    } else {
+        0.0
+    }
+}
+
+fn mean_ignoring_nan(votes: &[f64]) -> f64 {
+    let mut sum = 0.0;
+    let mut count = 0;
+    for &v in votes {
+        if !v.is_nan() {
+            sum += v;
+            count += 1;
+        }
+    }
+    if count == 0 {
+        f64::NAN
+    } else {
+        sum / count as f64
+    }
+}
@@ -0,0 +1,281 @@
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use serde_json::Value;
+
+use crate::metadata::find_indicator_meta;
+
+use super::compute::{compute_indicator, compute_indicator_unvalidated};
+use super::contracts::{
+    ComputeIndicatorRequest, ComputeIndicatorResponse, ComputeRuntimeError, OhlcvInput,
+};
+use super::params::normalize_params_for;
+
+/// Column-major OHLCV input for many symbols at once. Each field holds one
+/// column per symbol (`columns.len() == n_symbols`), and every column must be
+/// the same length as `timestamps` (`n_bars`).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct OhlcvBatch {
+    pub timestamps: Vec<i64>,
+    pub open: Vec<Vec<f64>>,
+    pub high: Vec<Vec<f64>>,
+    pub low: Vec<Vec<f64>>,
+    pub close: Vec<Vec<f64>>,
+    pub volume: Option<Vec<Vec<f64>>>,
+}
+
+impl OhlcvBatch {
+    pub fn n_bars(&self) -> usize {
+        self.timestamps.len()
+    }
+
+    pub fn n_symbols(&self) -> usize {
+        self.close.len()
+    }
+
+    pub fn validate(&self) -> Result<(), ComputeRuntimeError> {
+        let n_symbols = self.n_symbols();
+        ensure_columns("open", &self.open, n_symbols, self.n_bars())?;
+        ensure_columns("high", &self.high, n_symbols, self.n_bars())?;
+        ensure_columns("low", &self.low, n_symbols, self.n_bars())?;
+        ensure_columns("close", &self.close, n_symbols, self.n_bars())?;
+        if let Some(volume) = &self.volume {
+            ensure_columns("volume", volume, n_symbols, self.n_bars())?;
+        }
+        Ok(())
+    }
+
+    fn column(&self, symbol: usize) -> OhlcvInput {
+        OhlcvInput {
+            timestamps: self.timestamps.clone(),
+            open: self.open[symbol].clone(),
+            high: self.high[symbol].clone(),
+            low: self.low[symbol].clone(),
+            close: self.close[symbol].clone(),
+            volume: self.volume.as_ref().map(|v| v[symbol].clone()),
+        }
+    }
+}
+
+fn ensure_columns(
+    field: &'static str,
+    columns: &[Vec<f64>],
+    expected_symbols: usize,
+    expected_bars: usize,
+) -> Result<(), ComputeRuntimeError> {
+    if columns.len() != expected_symbols {
+        return Err(ComputeRuntimeError::new(
+            "invalid_input",
+            format!(
+                "{field} has {} columns, expected {expected_symbols} (from close)",
+                columns.len()
+            ),
+        ));
+    }
+    for (idx, column) in columns.iter().enumerate() {
+        if column.len() != expected_bars {
+            return Err(ComputeRuntimeError::new(
+                "invalid_input",
+                format!(
+                    "{field} column {idx} has {} bars, expected {expected_bars}",
+                    column.len()
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// One named output series across every symbol in the batch, column-major
+/// (`values[symbol][bar]`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamedSeriesBatch {
+    pub name: String,
+    pub values: Vec<Vec<Option<f64>>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComputeIndicatorBatchResponse {
+    pub indicator_id: String,
+    pub runtime_binding: String,
+    pub instance_id: Option<String>,
+    pub outputs: Vec<NamedSeriesBatch>,
+}
+
+/// Runs `indicator_id` over every column of `batch`, broadcasting scalar
+/// params to all symbols and applying per-symbol param vectors column by
+/// column. A param is treated as per-symbol when its JSON value is an array;
+/// its length must equal `batch.n_symbols()` or this returns a
+/// `shape_mismatch` error. Columns are computed in parallel across rayon's
+/// thread pool, since each symbol's `compute_indicator` call is independent.
+/// This is the vectorized counterpart of `compute_indicator`; a
+/// single-symbol batch (`n_symbols == 1`) computes the same result as
+/// calling `compute_indicator` directly.
+pub fn compute_indicator_batch(
+    indicator_id: &str,
+    params: &Value,
+    batch: &OhlcvBatch,
+    instance_id: Option<String>,
+) -> Result<ComputeIndicatorBatchResponse, ComputeRuntimeError> {
+    use rayon::prelude::*;
+
+    batch.validate()?;
+    let n_symbols = batch.n_symbols();
+
+    let responses: Vec<Result<_, ComputeRuntimeError>> = (0..n_symbols)
+        .into_par_iter()
+        .map(|symbol| {
+            let req = ComputeIndicatorRequest {
+                indicator_id: indicator_id.to_string(),
+                params: params_for_symbol(params, symbol, n_symbols)?,
+                ohlcv: batch.column(symbol),
+                instance_id: instance_id.clone(),
+                named_inputs: Default::default(),
+            };
+            compute_indicator(req)
+        })
+        .collect();
+
+    let mut runtime_binding = String::new();
+    let mut outputs: Vec<NamedSeriesBatch> = Vec::new();
+
+    for response in responses {
+        let response = response?;
+        runtime_binding = response.runtime_binding;
+
+        if outputs.is_empty() {
+            outputs = response
+                .outputs
+                .iter()
+                .map(|series| NamedSeriesBatch {
+                    name: series.name.clone(),
+                    values: Vec::with_capacity(n_symbols),
+                })
+                .collect();
+        }
+        for (slot, series) in outputs.iter_mut().zip(response.outputs.into_iter()) {
+            slot.values.push(series.values);
+        }
+    }
+
+    Ok(ComputeIndicatorBatchResponse {
+        indicator_id: indicator_id.to_string(),
+        runtime_binding,
+        instance_id,
+        outputs,
+    })
+}
+
+/// Resolves the param object for one column: array-valued params are indexed
+/// by `symbol` (after a length check against `n_symbols`), every other value
+/// is passed through unchanged so it broadcasts to every column.
+fn params_for_symbol(
+    params: &Value,
+    symbol: usize,
+    n_symbols: usize,
+) -> Result<Value, ComputeRuntimeError> {
+    let object = match params.as_object() {
+        Some(object) => object,
+        None => return Ok(params.clone()),
+    };
+
+    let mut resolved = serde_json::Map::with_capacity(object.len());
+    for (key, value) in object {
+        match value {
+            Value::Array(values) => {
+                if values.len() != n_symbols {
+                    return Err(ComputeRuntimeError::new(
+                        "shape_mismatch",
+                        format!(
+                            "param '{key}' has {} values, expected {n_symbols} (one per symbol)",
+                            values.len()
+                        ),
+                    ));
+                }
+                resolved.insert(key.clone(), values[symbol].clone());
+            }
+            other => {
+                resolved.insert(key.clone(), other.clone());
+            }
+        }
+    }
+    Ok(Value::Object(resolved))
+}
+
+/// Computes many, possibly heterogeneous, `ComputeIndicatorRequest`s against
+/// OHLCV data assumed shared across the whole batch: only the first
+/// request's frame is validated (every other request is trusted to carry
+/// the same data), and requests that reduce to the same `(runtime_binding,
+/// normalized_params, named_inputs)` key reuse an earlier response instead
+/// of recomputing the kernel. Results preserve request order. This is the
+/// cross-indicator counterpart to `compute_indicator_batch`'s
+/// cross-symbol vectorization: one OHLCV frame, many distinct kernels,
+/// with the dashboard-style pattern of overlapping sub-expressions (three
+/// EMAs feeding a MACD and a separate EMA crossover) computed once.
+pub fn compute_batch(
+    requests: Vec<ComputeIndicatorRequest>,
+) -> Result<Vec<ComputeIndicatorResponse>, ComputeRuntimeError> {
+    if let Some(first) = requests.first() {
+        first.ohlcv.validate()?;
+    }
+
+    let mut cache: HashMap<String, ComputeIndicatorResponse> = HashMap::new();
+    let mut responses = Vec::with_capacity(requests.len());
+
+    for req in requests {
+        let instance_id = req.instance_id.clone();
+        let key = batch_cache_key(&req);
+
+        let response = match key.as_ref().and_then(|key| cache.get(key)) {
+            Some(cached) => ComputeIndicatorResponse {
+                instance_id,
+                ..cached.clone()
+            },
+            None => {
+                let response = compute_indicator_unvalidated(req)?;
+                if let Some(key) = key {
+                    cache.insert(key, response.clone());
+                }
+                response
+            }
+        };
+        responses.push(response);
+    }
+
+    Ok(responses)
+}
+
+/// Builds the `(runtime_binding, normalized_params, named_inputs)` cache key
+/// `compute_batch` dedupes on. Returns `None` for requests whose params
+/// can't be normalized against the generic catalog schema -- the handful of
+/// composite bindings (`stoch_of`, `stoch_rsi`, `dt_oscillator`,
+/// `divergence`) that forward extra keys like `source_params` to a nested
+/// `compute_indicator` call -- which are always computed directly rather
+/// than risking an incorrect dedup.
+fn batch_cache_key(req: &ComputeIndicatorRequest) -> Option<String> {
+    let meta = find_indicator_meta(&req.indicator_id)?;
+    let normalized_params = normalize_params_for(meta, &req.params).ok()?;
+
+    let mut key = format!("{}|{normalized_params}", meta.runtime_binding);
+    for (name, values) in &req.named_inputs {
+        key.push('|');
+        key.push_str(name);
+        key.push(':');
+        key.push_str(&column_fingerprint(values).to_string());
+    }
+    Some(key)
+}
+
+/// A cheap stand-in for hashing an entire named-input series: the length
+/// plus the first and last values. Folding the length in first means two
+/// series of different length can never collide, without paying to hash
+/// every row of every named input on every batch request.
+fn column_fingerprint(values: &[f64]) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    values.len().hash(&mut hasher);
+    if let (Some(first), Some(last)) = (values.first(), values.last()) {
+        first.to_bits().hash(&mut hasher);
+        last.to_bits().hash(&mut hasher);
+    }
+    hasher.finish()
+}
@@ -1,22 +1,120 @@
+use std::borrow::Cow;
+
 use serde_json::{Map, Value};
 
+use crate::core::metadata::IndicatorMeta;
 use crate::{core::events, metadata::find_indicator_meta};
 
 use super::contracts::{
     ComputeIndicatorRequest, ComputeIndicatorResponse, ComputeRuntimeError, NamedSeries,
+    ParamErrorDetail, ParamErrorKind,
 };
 use super::params::normalize_params_for;
 
+/// Every `runtime_binding` string this module's dispatch `match` (plus the
+/// `stoch_of` special case handled ahead of it) actually recognizes, kept
+/// here so [`crate::metadata::registry`] can validate that every catalog
+/// entry's `runtime_binding` resolves to a real kernel instead of silently
+/// falling through to the `unsupported_indicator` error at request time.
+pub const REGISTERED_RUNTIME_BINDINGS: &[&str] = &[
+    "stoch_of",
+    "stoch_rsi",
+    "dt_oscillator",
+    "sma",
+    "ema",
+    "wma",
+    "hma",
+    "alma",
+    "dema",
+    "tema",
+    "zlema",
+    "t3",
+    "trima",
+    "kama",
+    "rsi",
+    "roc",
+    "cmo",
+    "macd",
+    "bbands",
+    "donchian",
+    "keltner",
+    "squeeze",
+    "atr",
+    "stochastic_kd",
+    "obv",
+    "vwap",
+    "cmf",
+    "vwma",
+    "vwap_anchored",
+    "klinger_vf",
+    "adx",
+    "ao",
+    "ac",
+    "chaikin_osc",
+    "cci",
+    "williams_r",
+    "mfi",
+    "vortex",
+    "coppock",
+    "elder_ray",
+    "ichimoku",
+    "fisher",
+    "psar",
+    "supertrend",
+    "swing_points_raw",
+    "cross",
+    "crossup",
+    "crossdown",
+    "rising",
+    "falling",
+    "rising_pct",
+    "falling_pct",
+    "in_channel",
+    "out",
+    "enter",
+    "exit",
+    "divergence",
+    "normalized_atr",
+    "linreg_slope",
+    "smma",
+    "hurst",
+    "technical_rating",
+];
+
 pub fn compute_indicator(
     req: ComputeIndicatorRequest,
 ) -> Result<ComputeIndicatorResponse, ComputeRuntimeError> {
     req.ohlcv.validate()?;
+    compute_indicator_unvalidated(req)
+}
+
+/// `compute_indicator` minus the `ohlcv.validate()` call, for callers (namely
+/// `runtime::batch::compute_batch`) that already validated a frame shared
+/// across many requests and don't want to pay for the same length checks
+/// again on every one of them.
+pub(crate) fn compute_indicator_unvalidated(
+    req: ComputeIndicatorRequest,
+) -> Result<ComputeIndicatorResponse, ComputeRuntimeError> {
     let meta = find_indicator_meta(&req.indicator_id).ok_or_else(|| {
         ComputeRuntimeError::new(
             "unknown_indicator",
             format!("unknown indicator '{}'", req.indicator_id),
         )
     })?;
+
+    if meta.runtime_binding == "stoch_of" {
+        return compute_stoch_of(meta, req);
+    }
+    if meta.runtime_binding == "stoch_rsi" {
+        return compute_stoch_rsi(meta, req);
+    }
+    if meta.runtime_binding == "dt_oscillator" {
+        return compute_dt_oscillator(meta, req);
+    }
+    if meta.runtime_binding == "divergence" {
+        return compute_divergence(meta, req);
+    }
+
     let normalized_params = normalize_params_for(meta, &req.params)?;
     let params = normalized_params
         .as_object()
@@ -25,51 +123,106 @@ pub fn compute_indicator(
     let outputs = match meta.runtime_binding {
         "sma" => vec![line(
             meta.outputs[0].name,
-            crate::rolling::rolling_mean(
-                series_param(&req, params, "source", "close")?,
+            p_ma_type(params)?.apply(
+                &series_param(&req, params, "source", "close")?,
                 p_usize(params, "period")?,
             ),
         )],
         "ema" => vec![line(
             meta.outputs[0].name,
-            crate::moving_averages::ema(
-                series_param(&req, params, "source", "close")?,
+            p_ma_type(params)?.apply(
+                &series_param(&req, params, "source", "close")?,
                 p_usize(params, "period")?,
             ),
         )],
         "wma" => vec![line(
             meta.outputs[0].name,
             crate::moving_averages::wma(
-                series_param(&req, params, "source", "close")?,
+                &series_param(&req, params, "source", "close")?,
                 p_usize(params, "period")?,
             ),
         )],
         "hma" => vec![line(
             meta.outputs[0].name,
             crate::moving_averages::hma(
-                series_param(&req, params, "source", "close")?,
+                &series_param(&req, params, "source", "close")?,
+                p_usize(params, "period")?,
+            ),
+        )],
+        "alma" => vec![line(
+            meta.outputs[0].name,
+            crate::moving_averages::alma(
+                &series_param(&req, params, "source", "close")?,
+                p_usize(params, "window")?,
+                p_f64(params, "offset")?,
+                p_f64(params, "sigma")?,
+            ),
+        )],
+        "dema" => vec![line(
+            meta.outputs[0].name,
+            crate::moving_averages::dema(
+                &series_param(&req, params, "source", "close")?,
+                p_usize(params, "period")?,
+            ),
+        )],
+        "tema" => vec![line(
+            meta.outputs[0].name,
+            crate::moving_averages::tema(
+                &series_param(&req, params, "source", "close")?,
+                p_usize(params, "period")?,
+            ),
+        )],
+        "zlema" => vec![line(
+            meta.outputs[0].name,
+            crate::moving_averages::zlema(
+                &series_param(&req, params, "source", "close")?,
+                p_usize(params, "period")?,
+            ),
+        )],
+        "t3" => vec![line(
+            meta.outputs[0].name,
+            crate::moving_averages::t3(
+                &series_param(&req, params, "source", "close")?,
                 p_usize(params, "period")?,
             ),
         )],
+        "trima" => vec![line(
+            meta.outputs[0].name,
+            crate::moving_averages::trima(
+                &series_param(&req, params, "source", "close")?,
+                p_usize(params, "period")?,
+            ),
+        )],
+        "kama" => vec![line(
+            meta.outputs[0].name,
+            crate::moving_averages::kama_with_rates(
+                &series_param(&req, params, "source", "close")?,
+                p_usize(params, "er_period")?,
+                p_usize(params, "fast_period")?,
+                p_usize(params, "slow_period")?,
+            ),
+        )],
         "rsi" => vec![line(
             meta.outputs[0].name,
-            crate::momentum::rsi(
-                series_param(&req, params, "source", "close")?,
+            crate::momentum::rsi_with_method(
+                &series_param(&req, params, "source", "close")?,
                 p_usize(params, "period")?,
+                p_rsi_method(params)?,
             ),
         )],
         "roc" => vec![line(
             meta.outputs[0].name,
             crate::momentum::roc(
-                series_param(&req, params, "source", "close")?,
+                &series_param(&req, params, "source", "close")?,
                 p_usize(params, "period")?,
             ),
         )],
         "cmo" => vec![line(
             meta.outputs[0].name,
-            crate::momentum::cmo(
-                series_param(&req, params, "source", "close")?,
+            crate::momentum::cmo_with_gap_policy(
+                &series_param(&req, params, "source", "close")?,
                 p_usize(params, "period")?,
+                p_gap_policy(params)?,
             ),
         )],
         "macd" => {
@@ -77,16 +230,20 @@ pub fn compute_indicator(
             let slow = p_usize(params, "slow_period")?;
             let signal = p_usize(params, "signal_period")?;
             if fast >= slow {
-                return Err(ComputeRuntimeError::new(
-                    "invalid_param",
+                return Err(cross_field_param(
+                    "fast_period",
                     "fast_period must be less than slow_period",
+                    "fast_period < slow_period",
+                    format!("fast_period={fast}, slow_period={slow}"),
                 ));
             }
             let (macd, signal_line, histogram) = crate::trend::macd(
-                series_param(&req, params, "source", "close")?,
+                &series_param(&req, params, "source", "close")?,
                 fast,
                 slow,
                 signal,
+                p_ma_type(params)?,
+                p_ma_type_named(params, "signal_ma_type")?,
             );
             vec![
                 line(meta.outputs[0].name, macd),
@@ -96,9 +253,10 @@ pub fn compute_indicator(
         }
         "bbands" => {
             let (upper, middle, lower) = crate::volatility::bbands(
-                series_param(&req, params, "source", "close")?,
+                &series_param(&req, params, "source", "close")?,
                 p_usize(params, "period")?,
                 p_f64(params, "std_dev")?,
+                p_ma_type(params)?,
             );
             vec![
                 line(meta.outputs[0].name, upper),
@@ -119,13 +277,15 @@ pub fn compute_indicator(
             ]
         }
         "keltner" => {
-            let (upper, middle, lower) = crate::volatility::keltner(
+            let (upper, middle, lower) = crate::volatility::keltner_with_smoothing(
                 &req.ohlcv.high,
                 &req.ohlcv.low,
                 &req.ohlcv.close,
                 p_usize(params, "ema_period")?,
                 p_usize(params, "atr_period")?,
                 p_f64(params, "multiplier")?,
+                p_ma_type(params)?,
+                p_atr_smoothing(params)?,
             );
             vec![
                 line(meta.outputs[0].name, upper),
@@ -133,23 +293,43 @@ pub fn compute_indicator(
                 line(meta.outputs[2].name, lower),
             ]
         }
+        "squeeze" => {
+            let (squeeze_on, momentum) = crate::volatility::squeeze(
+                &req.ohlcv.high,
+                &req.ohlcv.low,
+                &req.ohlcv.close,
+                p_usize(params, "period")?,
+                p_f64(params, "std_dev")?,
+                p_usize(params, "ema_period")?,
+                p_usize(params, "atr_period")?,
+                p_f64(params, "multiplier")?,
+                crate::moving_averages::MovingAverageType::Sma,
+            );
+            vec![
+                signal(meta.outputs[0].name, squeeze_on),
+                line(meta.outputs[1].name, momentum),
+            ]
+        }
         "atr" => vec![line(
             meta.outputs[0].name,
-            crate::volatility::atr(
+            crate::volatility::atr_with_smoothing(
                 &req.ohlcv.high,
                 &req.ohlcv.low,
                 &req.ohlcv.close,
                 p_usize(params, "period")?,
+                p_atr_smoothing(params)?,
             ),
         )],
         "stochastic_kd" => {
-            let (k, d) = crate::momentum::stochastic_kd(
+            let (k, d) = crate::momentum::stochastic_kd_with_method(
                 &req.ohlcv.high,
                 &req.ohlcv.low,
                 &req.ohlcv.close,
                 p_usize(params, "k_period")?,
                 p_usize(params, "d_period")?,
                 p_usize(params, "smooth")?,
+                p_gap_policy(params)?,
+                p_ma_type_named(params, "ma_method")?,
             );
             vec![line(meta.outputs[0].name, k), line(meta.outputs[1].name, d)]
         }
@@ -176,6 +356,31 @@ pub fn compute_indicator(
                 p_usize(params, "period")?,
             ),
         )],
+        "vwma" => vec![line(
+            meta.outputs[0].name,
+            crate::volume::vwma(
+                &series_param(&req, params, "source", "close")?,
+                volume(&req)?,
+                p_usize(params, "period")?,
+            ),
+        )],
+        "vwap_anchored" => {
+            let (vwap, upper_k, lower_k) = crate::volume::vwap_anchored(
+                &req.ohlcv.timestamps,
+                &req.ohlcv.high,
+                &req.ohlcv.low,
+                &req.ohlcv.close,
+                volume(&req)?,
+                &[],
+                p_usize(params, "session_seconds")? as i64,
+                p_f64(params, "std_dev")?,
+            );
+            vec![
+                line(meta.outputs[0].name, vwap),
+                line(meta.outputs[1].name, upper_k),
+                line(meta.outputs[2].name, lower_k),
+            ]
+        }
         "klinger_vf" => vec![line(
             meta.outputs[0].name,
             crate::volume::klinger_vf(
@@ -202,50 +407,113 @@ pub fn compute_indicator(
             let fast = p_usize(params, "fast_period")?;
             let slow = p_usize(params, "slow_period")?;
             if fast >= slow {
-                return Err(ComputeRuntimeError::new(
-                    "invalid_param",
+                return Err(cross_field_param(
+                    "fast_period",
                     "fast_period must be less than slow_period",
+                    "fast_period < slow_period",
+                    format!("fast_period={fast}, slow_period={slow}"),
                 ));
             }
             vec![line(
                 meta.outputs[0].name,
-                crate::momentum::ao(&req.ohlcv.high, &req.ohlcv.low, fast, slow),
+                crate::momentum::ao_with_gap_policy(
+                    &req.ohlcv.high,
+                    &req.ohlcv.low,
+                    fast,
+                    slow,
+                    p_gap_policy(params)?,
+                ),
+            )]
+        }
+        "ac" => {
+            let fast = p_usize(params, "fast_period")?;
+            let slow = p_usize(params, "slow_period")?;
+            if fast >= slow {
+                return Err(cross_field_param(
+                    "fast_period",
+                    "fast_period must be less than slow_period",
+                    "fast_period < slow_period",
+                    format!("fast_period={fast}, slow_period={slow}"),
+                ));
+            }
+            vec![line(
+                meta.outputs[0].name,
+                crate::momentum::ac(
+                    &req.ohlcv.high,
+                    &req.ohlcv.low,
+                    fast,
+                    slow,
+                    p_usize(params, "signal_period")?,
+                ),
+            )]
+        }
+        "chaikin_osc" => {
+            let fast = p_usize(params, "fast_period")?;
+            let slow = p_usize(params, "slow_period")?;
+            if fast >= slow {
+                return Err(cross_field_param(
+                    "fast_period",
+                    "fast_period must be less than slow_period",
+                    "fast_period < slow_period",
+                    format!("fast_period={fast}, slow_period={slow}"),
+                ));
+            }
+            vec![line(
+                meta.outputs[0].name,
+                crate::volume::chaikin_oscillator(
+                    &req.ohlcv.high,
+                    &req.ohlcv.low,
+                    &req.ohlcv.close,
+                    volume(&req)?,
+                    fast,
+                    slow,
+                ),
             )]
         }
         "cci" => vec![line(
             meta.outputs[0].name,
-            crate::momentum::cci(
+            crate::momentum::cci_with_gap_policy(
                 &req.ohlcv.high,
                 &req.ohlcv.low,
                 &req.ohlcv.close,
                 p_usize(params, "period")?,
+                p_gap_policy(params)?,
             ),
         )],
-        "williams_r" => vec![line(
-            meta.outputs[0].name,
-            crate::momentum::williams_r(
+        "williams_r" => {
+            let raw = crate::momentum::williams_r_with_gap_policy(
                 &req.ohlcv.high,
                 &req.ohlcv.low,
                 &req.ohlcv.close,
                 p_usize(params, "period")?,
-            ),
-        )],
+                p_gap_policy(params)?,
+            );
+            let smooth_period = p_usize(params, "smooth_period")?;
+            let result = if smooth_period > 1 {
+                p_ma_type(params)?.apply(&raw, smooth_period)
+            } else {
+                raw
+            };
+            vec![line(meta.outputs[0].name, result)]
+        }
         "mfi" => vec![line(
             meta.outputs[0].name,
-            crate::momentum::mfi(
+            crate::momentum::mfi_with_gap_policy(
                 &req.ohlcv.high,
                 &req.ohlcv.low,
                 &req.ohlcv.close,
                 volume(&req)?,
                 p_usize(params, "period")?,
+                p_gap_policy(params)?,
             ),
         )],
         "vortex" => {
-            let (plus, minus) = crate::momentum::vortex(
+            let (plus, minus) = crate::momentum::vortex_with_gap_policy(
                 &req.ohlcv.high,
                 &req.ohlcv.low,
                 &req.ohlcv.close,
                 p_usize(params, "period")?,
+                p_gap_policy(params)?,
             );
             vec![
                 line(meta.outputs[0].name, plus),
@@ -255,7 +523,7 @@ pub fn compute_indicator(
         "coppock" => vec![line(
             meta.outputs[0].name,
             crate::momentum::coppock(
-                series_param(&req, params, "source", "close")?,
+                &series_param(&req, params, "source", "close")?,
                 p_usize(params, "wma_period")?,
                 p_usize(params, "fast_roc")?,
                 p_usize(params, "slow_roc")?,
@@ -343,78 +611,117 @@ pub fn compute_indicator(
         "cross" => vec![signal(
             meta.outputs[0].name,
             events::cross(
-                series_param(&req, params, "a", "close")?,
-                series_param(&req, params, "b", "open")?,
+                &series_param(&req, params, "a", "close")?,
+                &series_param(&req, params, "b", "open")?,
             ),
         )],
         "crossup" => vec![signal(
             meta.outputs[0].name,
             events::crossup(
-                series_param(&req, params, "a", "close")?,
-                series_param(&req, params, "b", "open")?,
+                &series_param(&req, params, "a", "close")?,
+                &series_param(&req, params, "b", "open")?,
             ),
         )],
         "crossdown" => vec![signal(
             meta.outputs[0].name,
             events::crossdown(
-                series_param(&req, params, "a", "close")?,
-                series_param(&req, params, "b", "open")?,
+                &series_param(&req, params, "a", "close")?,
+                &series_param(&req, params, "b", "open")?,
             ),
         )],
         "rising" => vec![signal(
             meta.outputs[0].name,
-            events::rising(series_param(&req, params, "a", "close")?),
+            events::rising(&series_param(&req, params, "a", "close")?),
         )],
         "falling" => vec![signal(
             meta.outputs[0].name,
-            events::falling(series_param(&req, params, "a", "close")?),
+            events::falling(&series_param(&req, params, "a", "close")?),
         )],
         "rising_pct" => vec![signal(
             meta.outputs[0].name,
             events::rising_pct(
-                series_param(&req, params, "a", "close")?,
+                &series_param(&req, params, "a", "close")?,
                 p_f64(params, "pct")?,
             ),
         )],
         "falling_pct" => vec![signal(
             meta.outputs[0].name,
             events::falling_pct(
-                series_param(&req, params, "a", "close")?,
+                &series_param(&req, params, "a", "close")?,
                 p_f64(params, "pct")?,
             ),
         )],
         "in_channel" => vec![signal(
             meta.outputs[0].name,
             events::in_channel(
-                series_param(&req, params, "price", "close")?,
-                series_param(&req, params, "upper", "high")?,
-                series_param(&req, params, "lower", "low")?,
+                &series_param(&req, params, "price", "close")?,
+                &series_param(&req, params, "upper", "high")?,
+                &series_param(&req, params, "lower", "low")?,
             ),
         )],
         "out" => vec![signal(
             meta.outputs[0].name,
             events::out_channel(
-                series_param(&req, params, "price", "close")?,
-                series_param(&req, params, "upper", "high")?,
-                series_param(&req, params, "lower", "low")?,
+                &series_param(&req, params, "price", "close")?,
+                &series_param(&req, params, "upper", "high")?,
+                &series_param(&req, params, "lower", "low")?,
             ),
         )],
         "enter" => vec![signal(
             meta.outputs[0].name,
             events::enter_channel(
-                series_param(&req, params, "price", "close")?,
-                series_param(&req, params, "upper", "high")?,
-                series_param(&req, params, "lower", "low")?,
+                &series_param(&req, params, "price", "close")?,
+                &series_param(&req, params, "upper", "high")?,
+                &series_param(&req, params, "lower", "low")?,
             ),
         )],
         "exit" => vec![signal(
             meta.outputs[0].name,
             events::exit_channel(
-                series_param(&req, params, "price", "close")?,
-                series_param(&req, params, "upper", "high")?,
-                series_param(&req, params, "lower", "low")?,
+                &series_param(&req, params, "price", "close")?,
+                &series_param(&req, params, "upper", "high")?,
+                &series_param(&req, params, "lower", "low")?,
             ),
         )],
+        "normalized_atr" => vec![line(
+            meta.outputs[0].name,
+            crate::statistics::normalized_atr(
+                &req.ohlcv.high,
+                &req.ohlcv.low,
+                &req.ohlcv.close,
+                p_usize(params, "period")?,
+            ),
+        )],
+        "linreg_slope" => {
+            let (slope, intercept, r_squared) =
+                crate::statistics::linreg_slope(&req.ohlcv.close, p_usize(params, "period")?);
+            vec![
+                line(meta.outputs[0].name, slope),
+                line(meta.outputs[1].name, intercept),
+                line(meta.outputs[2].name, r_squared),
+            ]
+        }
+        "smma" => vec![line(
+            meta.outputs[0].name,
+            crate::moving_averages::rma(&req.ohlcv.close, p_usize(params, "period")?),
+        )],
+        "hurst" => vec![line(
+            meta.outputs[0].name,
+            crate::statistics::hurst_exponent(&req.ohlcv.close, p_usize(params, "period")?),
+        )],
+        "technical_rating" => {
+            let (oscillators_rating, ma_rating, all_rating) = crate::rating::technical_rating(
+                &req.ohlcv.high,
+                &req.ohlcv.low,
+                &req.ohlcv.close,
+                p_usize(params, "ma_period")?,
+            );
+            vec![
+                line(meta.outputs[0].name, oscillators_rating),
+                line(meta.outputs[1].name, ma_rating),
+                line(meta.outputs[2].name, all_rating),
+            ]
+        }
         _ => {
             return Err(ComputeRuntimeError::new(
                 "unsupported_indicator",
@@ -436,31 +743,397 @@ pub fn compute_indicator(
     })
 }
 
+/// `stoch_of` composes another catalog indicator rather than consuming raw
+/// OHLCV fields directly (see `IndicatorSemanticsMeta::source_param`), so it
+/// can't go through the generic dispatch above: only its own params
+/// (`source`, `k_period`, `d_period`, `smooth`) are validated against the
+/// catalog, while everything under `source_params` is forwarded verbatim to
+/// a recursive `compute_indicator` call for the indicator named by `source`
+/// — that indicator's own param schema isn't known statically here.
+fn compute_stoch_of(
+    meta: &'static IndicatorMeta,
+    req: ComputeIndicatorRequest,
+) -> Result<ComputeIndicatorResponse, ComputeRuntimeError> {
+    let raw = req.params.as_object().cloned().unwrap_or_default();
+
+    let mut own_params = Map::new();
+    for key in ["source", "k_period", "d_period", "smooth"] {
+        if let Some(value) = raw.get(key) {
+            own_params.insert(key.to_string(), value.clone());
+        }
+    }
+    let normalized_params = normalize_params_for(meta, &Value::Object(own_params))?;
+    let params = normalized_params
+        .as_object()
+        .expect("normalize_params_for always returns object");
+
+    let source_id = params
+        .get("source")
+        .and_then(Value::as_str)
+        .ok_or_else(|| ComputeRuntimeError::new("invalid_param", "missing/invalid 'source'"))?
+        .to_string();
+    let k_period = p_usize(params, "k_period")?;
+    let d_period = p_usize(params, "d_period")?;
+    let smooth = p_usize(params, "smooth")?;
+
+    let source_params = raw.get("source_params").cloned().unwrap_or(Value::Null);
+    let source_req = ComputeIndicatorRequest {
+        indicator_id: source_id.clone(),
+        params: source_params,
+        ohlcv: req.ohlcv.clone(),
+        instance_id: None,
+        named_inputs: req.named_inputs.clone(),
+    };
+    let source_out = compute_indicator(source_req)?;
+    let source_series: Vec<f64> = source_out
+        .outputs
+        .first()
+        .ok_or_else(|| {
+            ComputeRuntimeError::new(
+                "invalid_param",
+                format!("source indicator '{source_id}' produced no output series"),
+            )
+        })?
+        .values
+        .iter()
+        .map(|v| v.unwrap_or(f64::NAN))
+        .collect();
+
+    let (k, d) =
+        crate::momentum::stochastic_of_series(&source_series, k_period, d_period, smooth);
+
+    let mut normalized_out = params.clone();
+    normalized_out.insert("source_params".to_string(), source_out.normalized_params);
+
+    Ok(ComputeIndicatorResponse {
+        indicator_id: meta.id.to_string(),
+        runtime_binding: meta.runtime_binding.to_string(),
+        instance_id: req.instance_id,
+        outputs: vec![line(meta.outputs[0].name, k), line(meta.outputs[1].name, d)],
+        visual: meta.visual,
+        normalized_params: Value::Object(normalized_out),
+    })
+}
+
+/// `stoch_rsi` is `stoch_of` with the source fixed to `rsi` rather than
+/// user-selectable: the RSI period and the stochastic rescale/smoothing
+/// periods are exposed as this indicator's own flat params
+/// (`rsi_period`, `stoch_period`, `smooth_k`, `smooth_d`) instead of a
+/// `source`/`source_params` pair, and the RSI call is made directly rather
+/// than recursing through the generic `source` dispatch.
+fn compute_stoch_rsi(
+    meta: &'static IndicatorMeta,
+    req: ComputeIndicatorRequest,
+) -> Result<ComputeIndicatorResponse, ComputeRuntimeError> {
+    let normalized_params = normalize_params_for(meta, &req.params)?;
+    let params = normalized_params
+        .as_object()
+        .expect("normalize_params_for always returns object");
+
+    let rsi_period = p_usize(params, "rsi_period")?;
+    let stoch_period = p_usize(params, "stoch_period")?;
+    let smooth_k = p_usize(params, "smooth_k")?;
+    let smooth_d = p_usize(params, "smooth_d")?;
+
+    let rsi_out = compute_rsi_source(&req, rsi_period)?;
+    let rsi_series: Vec<f64> = rsi_out
+        .outputs
+        .first()
+        .ok_or_else(|| ComputeRuntimeError::new("invalid_param", "rsi produced no output series"))?
+        .values
+        .iter()
+        .map(|v| v.unwrap_or(f64::NAN))
+        .collect();
+
+    let (k, d) = crate::momentum::stochastic_of_series(&rsi_series, stoch_period, smooth_d, smooth_k);
+
+    let mut normalized_out = params.clone();
+    normalized_out.insert("rsi_params".to_string(), rsi_out.normalized_params);
+
+    Ok(ComputeIndicatorResponse {
+        indicator_id: meta.id.to_string(),
+        runtime_binding: meta.runtime_binding.to_string(),
+        instance_id: req.instance_id,
+        outputs: vec![line(meta.outputs[0].name, k), line(meta.outputs[1].name, d)],
+        visual: meta.visual,
+        normalized_params: Value::Object(normalized_out),
+    })
+}
+
+/// `dt_oscillator` is `stoch_rsi` with heavier default smoothing (RSI 13,
+/// stochastic window 8, %K smoothing 5, %D smoothing 3) and a selectable
+/// `ma_type` for both smoothing stages instead of the fixed NaN-skipping
+/// SMA `stoch_rsi` always uses — the "stochastic of RSI" composition
+/// technique behind the DT Oscillator / iStochasticOfOsc indicators.
+fn compute_dt_oscillator(
+    meta: &'static IndicatorMeta,
+    req: ComputeIndicatorRequest,
+) -> Result<ComputeIndicatorResponse, ComputeRuntimeError> {
+    let normalized_params = normalize_params_for(meta, &req.params)?;
+    let params = normalized_params
+        .as_object()
+        .expect("normalize_params_for always returns object");
+
+    let rsi_period = p_usize(params, "rsi_period")?;
+    let stoch_period = p_usize(params, "stoch_period")?;
+    let sk_period = p_usize(params, "sk_period")?;
+    let sd_period = p_usize(params, "sd_period")?;
+    let ma_method = p_ma_type(params)?;
+
+    let rsi_out = compute_rsi_source(&req, rsi_period)?;
+    let rsi_series: Vec<f64> = rsi_out
+        .outputs
+        .first()
+        .ok_or_else(|| ComputeRuntimeError::new("invalid_param", "rsi produced no output series"))?
+        .values
+        .iter()
+        .map(|v| v.unwrap_or(f64::NAN))
+        .collect();
+
+    let (k, d) = crate::momentum::stochastic_of_series_with_method(
+        &rsi_series,
+        stoch_period,
+        sd_period,
+        sk_period,
+        ma_method,
+    );
+
+    let mut normalized_out = params.clone();
+    normalized_out.insert("rsi_params".to_string(), rsi_out.normalized_params);
+
+    Ok(ComputeIndicatorResponse {
+        indicator_id: meta.id.to_string(),
+        runtime_binding: meta.runtime_binding.to_string(),
+        instance_id: req.instance_id,
+        outputs: vec![line(meta.outputs[0].name, k), line(meta.outputs[1].name, d)],
+        visual: meta.visual,
+        normalized_params: Value::Object(normalized_out),
+    })
+}
+
+/// Shared by `stoch_rsi`/`dt_oscillator`: recurse into a plain `rsi` compute
+/// over the same OHLCV/named inputs with only `period` overridden, the way
+/// `compute_stoch_of` recurses into its own `source` indicator.
+fn compute_rsi_source(
+    req: &ComputeIndicatorRequest,
+    period: usize,
+) -> Result<ComputeIndicatorResponse, ComputeRuntimeError> {
+    let mut rsi_params = Map::new();
+    rsi_params.insert("period".to_string(), Value::from(period));
+    let rsi_req = ComputeIndicatorRequest {
+        indicator_id: "rsi".to_string(),
+        params: Value::Object(rsi_params),
+        ohlcv: req.ohlcv.clone(),
+        instance_id: None,
+        named_inputs: req.named_inputs.clone(),
+    };
+    compute_indicator(rsi_req)
+}
+
+/// `divergence` composes another catalog oscillator the same way `stoch_of`
+/// does: only its own params (`source`, `period`, `pivot_lookback`) are
+/// validated against the catalog, and `period` is forwarded to the source
+/// indicator's own `period` param (every oscillator `source` currently
+/// allows -- `rsi`, `cci`, `cmo`, `mfi`, `williams_r` -- is named that way).
+fn compute_divergence(
+    meta: &'static IndicatorMeta,
+    req: ComputeIndicatorRequest,
+) -> Result<ComputeIndicatorResponse, ComputeRuntimeError> {
+    let normalized_params = normalize_params_for(meta, &req.params)?;
+    let params = normalized_params
+        .as_object()
+        .expect("normalize_params_for always returns object");
+
+    let source_id = params
+        .get("source")
+        .and_then(Value::as_str)
+        .ok_or_else(|| ComputeRuntimeError::new("invalid_param", "missing/invalid 'source'"))?
+        .to_string();
+    let period = p_usize(params, "period")?;
+    let pivot_lookback = p_usize(params, "pivot_lookback")?;
+
+    let mut source_params = Map::new();
+    source_params.insert("period".to_string(), Value::from(period));
+    let source_req = ComputeIndicatorRequest {
+        indicator_id: source_id.clone(),
+        params: Value::Object(source_params),
+        ohlcv: req.ohlcv.clone(),
+        instance_id: None,
+        named_inputs: req.named_inputs.clone(),
+    };
+    let source_out = compute_indicator(source_req)?;
+    let oscillator: Vec<f64> = source_out
+        .outputs
+        .first()
+        .ok_or_else(|| {
+            ComputeRuntimeError::new(
+                "invalid_param",
+                format!("source indicator '{source_id}' produced no output series"),
+            )
+        })?
+        .values
+        .iter()
+        .map(|v| v.unwrap_or(f64::NAN))
+        .collect();
+
+    let (bearish, bullish, hidden_bearish, hidden_bullish) =
+        events::divergence(&req.ohlcv.close, &oscillator, pivot_lookback);
+
+    let mut normalized_out = params.clone();
+    normalized_out.insert("source_params".to_string(), source_out.normalized_params);
+
+    Ok(ComputeIndicatorResponse {
+        indicator_id: meta.id.to_string(),
+        runtime_binding: meta.runtime_binding.to_string(),
+        instance_id: req.instance_id,
+        outputs: vec![
+            signal(meta.outputs[0].name, bearish),
+            signal(meta.outputs[1].name, bullish),
+            signal(meta.outputs[2].name, hidden_bearish),
+            signal(meta.outputs[3].name, hidden_bullish),
+        ],
+        visual: meta.visual,
+        normalized_params: Value::Object(normalized_out),
+    })
+}
+
 fn p_usize(params: &Map<String, Value>, name: &str) -> Result<usize, ComputeRuntimeError> {
-    params
-        .get(name)
-        .and_then(|v| {
-            v.as_u64()
-                .or_else(|| v.as_i64().and_then(|x| (x >= 0).then_some(x as u64)))
-        })
+    let Some(value) = params.get(name) else {
+        return Err(missing_param(name));
+    };
+    value
+        .as_u64()
+        .or_else(|| value.as_i64().and_then(|x| (x >= 0).then_some(x as u64)))
         .map(|v| v as usize)
-        .ok_or_else(|| {
-            ComputeRuntimeError::new("invalid_param", format!("missing/invalid '{name}'"))
-        })
+        .ok_or_else(|| wrong_type_param(name, "a non-negative integer", value))
 }
 
 fn p_f64(params: &Map<String, Value>, name: &str) -> Result<f64, ComputeRuntimeError> {
-    params.get(name).and_then(Value::as_f64).ok_or_else(|| {
-        ComputeRuntimeError::new("invalid_param", format!("missing/invalid '{name}'"))
-    })
+    let Some(value) = params.get(name) else {
+        return Err(missing_param(name));
+    };
+    value
+        .as_f64()
+        .ok_or_else(|| wrong_type_param(name, "a number", value))
 }
 
 fn p_bool(params: &Map<String, Value>, name: &str) -> Result<bool, ComputeRuntimeError> {
-    params.get(name).and_then(Value::as_bool).ok_or_else(|| {
+    let Some(value) = params.get(name) else {
+        return Err(missing_param(name));
+    };
+    value
+        .as_bool()
+        .ok_or_else(|| wrong_type_param(name, "a boolean", value))
+}
+
+fn missing_param(name: &str) -> ComputeRuntimeError {
+    ComputeRuntimeError::with_param(
+        "invalid_param",
+        format!("missing/invalid '{name}'"),
+        ParamErrorDetail {
+            param_name: name.to_string(),
+            kind: ParamErrorKind::Missing,
+            expected: Some("a required parameter".to_string()),
+            got: None,
+        },
+    )
+}
+
+fn wrong_type_param(name: &str, expected: &str, got: &Value) -> ComputeRuntimeError {
+    ComputeRuntimeError::with_param(
+        "invalid_param",
+        format!("missing/invalid '{name}'"),
+        ParamErrorDetail {
+            param_name: name.to_string(),
+            kind: ParamErrorKind::WrongType,
+            expected: Some(expected.to_string()),
+            got: Some(json_type_name(got).to_string()),
+        },
+    )
+}
+
+fn json_type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Builds the `CrossFieldConstraint` error the `fast_period < slow_period`
+/// checks below (`macd`, `ao`, `ac`, `chaikin_osc`) share, since unlike
+/// `p_usize`/`p_f64`/`p_bool` each value here individually validated fine --
+/// the failure is only in how the two compare.
+fn cross_field_param(
+    param_name: &str,
+    message: impl Into<String>,
+    expected: impl Into<String>,
+    got: impl Into<String>,
+) -> ComputeRuntimeError {
+    let message = message.into();
+    ComputeRuntimeError::with_param(
+        "invalid_param",
+        message,
+        ParamErrorDetail {
+            param_name: param_name.to_string(),
+            kind: ParamErrorKind::CrossFieldConstraint,
+            expected: Some(expected.into()),
+            got: Some(got.into()),
+        },
+    )
+}
+
+fn p_ma_type(
+    params: &Map<String, Value>,
+) -> Result<crate::moving_averages::MovingAverageType, ComputeRuntimeError> {
+    p_ma_type_named(params, "ma_type")
+}
+
+fn p_ma_type_named(
+    params: &Map<String, Value>,
+    name: &str,
+) -> Result<crate::moving_averages::MovingAverageType, ComputeRuntimeError> {
+    let kind = params.get(name).and_then(Value::as_str).ok_or_else(|| {
+        ComputeRuntimeError::new("invalid_param", format!("missing/invalid '{name}'"))
+    })?;
+    crate::moving_averages::MovingAverageType::parse(kind).ok_or_else(|| {
         ComputeRuntimeError::new("invalid_param", format!("missing/invalid '{name}'"))
     })
 }
 
+fn p_rsi_method(
+    params: &Map<String, Value>,
+) -> Result<crate::momentum::RsiMethod, ComputeRuntimeError> {
+    let kind = params.get("method").and_then(Value::as_str).ok_or_else(|| {
+        ComputeRuntimeError::new("invalid_param", "missing/invalid 'method'")
+    })?;
+    crate::momentum::RsiMethod::parse(kind)
+        .ok_or_else(|| ComputeRuntimeError::new("invalid_param", "missing/invalid 'method'"))
+}
+
+fn p_atr_smoothing(
+    params: &Map<String, Value>,
+) -> Result<crate::volatility::AtrSmoothing, ComputeRuntimeError> {
+    let kind = params.get("smoothing").and_then(Value::as_str).ok_or_else(|| {
+        ComputeRuntimeError::new("invalid_param", "missing/invalid 'smoothing'")
+    })?;
+    crate::volatility::AtrSmoothing::parse(kind)
+        .ok_or_else(|| ComputeRuntimeError::new("invalid_param", "missing/invalid 'smoothing'"))
+}
+
+fn p_gap_policy(
+    params: &Map<String, Value>,
+) -> Result<crate::gap_policy::GapPolicy, ComputeRuntimeError> {
+    let kind = params.get("gap_policy").and_then(Value::as_str).ok_or_else(|| {
+        ComputeRuntimeError::new("invalid_param", "missing/invalid 'gap_policy'")
+    })?;
+    crate::gap_policy::GapPolicy::parse(kind)
+        .ok_or_else(|| ComputeRuntimeError::new("invalid_param", "missing/invalid 'gap_policy'"))
+}
+
 fn line(name: &str, values: Vec<f64>) -> NamedSeries {
     NamedSeries {
         name: name.to_string(),
@@ -486,7 +1159,10 @@ fn series_param<'a>(
     params: &'a Map<String, Value>,
     name: &str,
     fallback: &'a str,
-) -> Result<&'a [f64], ComputeRuntimeError> {
+) -> Result<Cow<'a, [f64]>, ComputeRuntimeError> {
+    if let Some(named) = req.named_inputs.get(name) {
+        return Ok(Cow::Borrowed(named.as_slice()));
+    }
     let field = params
         .get(name)
         .and_then(Value::as_str)
@@ -495,16 +1171,34 @@ fn series_param<'a>(
     ohlcv_field(req, field)
 }
 
+/// Resolves `field` to a price series, either borrowing a raw OHLCV column or
+/// computing one of the derived price sources (`hl2`, `hlc3`/`typical`,
+/// `ohlc4`, `hlcc4`/`weighted`) into an owned buffer on demand.
 fn ohlcv_field<'a>(
     req: &'a ComputeIndicatorRequest,
     field: &str,
-) -> Result<&'a [f64], ComputeRuntimeError> {
+) -> Result<Cow<'a, [f64]>, ComputeRuntimeError> {
     match field.to_ascii_lowercase().as_str() {
-        "open" => Ok(&req.ohlcv.open),
-        "high" => Ok(&req.ohlcv.high),
-        "low" => Ok(&req.ohlcv.low),
-        "close" => Ok(&req.ohlcv.close),
-        "volume" => volume(req),
+        "open" => Ok(Cow::Borrowed(&req.ohlcv.open)),
+        "high" => Ok(Cow::Borrowed(&req.ohlcv.high)),
+        "low" => Ok(Cow::Borrowed(&req.ohlcv.low)),
+        "close" => Ok(Cow::Borrowed(&req.ohlcv.close)),
+        "volume" => volume(req).map(Cow::Borrowed),
+        "hl2" => Ok(Cow::Owned(derived_price(req, |o, h, l, c| {
+            let _ = (o, c);
+            (h + l) / 2.0
+        }))),
+        "hlc3" | "typical" => Ok(Cow::Owned(derived_price(req, |o, h, l, c| {
+            let _ = o;
+            (h + l + c) / 3.0
+        }))),
+        "ohlc4" => Ok(Cow::Owned(derived_price(req, |o, h, l, c| {
+            (o + h + l + c) / 4.0
+        }))),
+        "hlcc4" | "weighted" => Ok(Cow::Owned(derived_price(req, |o, h, l, c| {
+            let _ = o;
+            (h + l + c + c) / 4.0
+        }))),
         _ => Err(ComputeRuntimeError::new(
             "missing_input_field",
             format!("unknown input field '{field}'"),
@@ -512,6 +1206,23 @@ fn ohlcv_field<'a>(
     }
 }
 
+/// Zips the four OHLC columns pointwise through `combine`, producing one
+/// owned series the same length as the input candles.
+fn derived_price(
+    req: &ComputeIndicatorRequest,
+    combine: impl Fn(f64, f64, f64, f64) -> f64,
+) -> Vec<f64> {
+    let ohlcv = &req.ohlcv;
+    ohlcv
+        .open
+        .iter()
+        .zip(&ohlcv.high)
+        .zip(&ohlcv.low)
+        .zip(&ohlcv.close)
+        .map(|(((o, h), l), c)| combine(*o, *h, *l, *c))
+        .collect()
+}
+
 fn volume(req: &ComputeIndicatorRequest) -> Result<&[f64], ComputeRuntimeError> {
     req.ohlcv
         .volume
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fmt;
 
 use serde_json::Value;
@@ -6,7 +7,7 @@ use crate::core::metadata::{
     ComputeCapability, IndicatorMeta, IndicatorVisualMeta, PlotCapability,
 };
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct OhlcvInput {
     pub timestamps: Vec<i64>,
     pub open: Vec<f64>,
@@ -38,12 +39,18 @@ impl OhlcvInput {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct ComputeIndicatorRequest {
     pub indicator_id: String,
     pub params: Value,
     pub ohlcv: OhlcvInput,
     pub instance_id: Option<String>,
+    /// Upstream series values keyed by the name a `source`/`a`/`b`/... param
+    /// can select instead of a raw OHLCV field, with `NaN` standing in for
+    /// the upstream's warmup gaps. Populated by plan execution (see
+    /// `runtime::plan`) when a node consumes another node's output; empty
+    /// for ordinary single-indicator requests.
+    pub named_inputs: BTreeMap<String, Vec<f64>>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -94,10 +101,44 @@ impl RuntimeCatalogEntry {
     }
 }
 
+/// Why a single parameter failed validation, for callers that want to
+/// highlight the offending field programmatically instead of parsing
+/// `ComputeRuntimeError::message`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamErrorKind {
+    /// A required parameter was absent and has no default.
+    Missing,
+    /// The value was present but didn't coerce to the parameter's declared
+    /// kind (e.g. a string where an integer was expected).
+    WrongType,
+    /// The value coerced fine but fell outside the parameter's declared
+    /// `min`/`max` bounds.
+    OutOfRange,
+    /// The value is individually valid but violates a constraint between
+    /// two parameters (e.g. `fast_period` must be less than `slow_period`).
+    CrossFieldConstraint,
+}
+
+/// Structured detail attached to `ComputeRuntimeError` for parameter
+/// validation failures. `expected`/`got` are free-form human-readable
+/// descriptions (not necessarily valid JSON) meant for surfacing in an
+/// error message or UI tooltip, not for re-parsing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParamErrorDetail {
+    pub param_name: String,
+    pub kind: ParamErrorKind,
+    pub expected: Option<String>,
+    pub got: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ComputeRuntimeError {
     pub code: String,
     pub message: String,
+    /// Populated for parameter-validation failures (`p_usize`/`p_f64`/
+    /// `p_bool` and the catalog normalizer's min/max/constraint checks);
+    /// `None` for everything else (unknown indicator, shape mismatch, ...).
+    pub param: Option<ParamErrorDetail>,
 }
 
 impl ComputeRuntimeError {
@@ -105,6 +146,15 @@ impl ComputeRuntimeError {
         Self {
             code: code.to_string(),
             message: message.into(),
+            param: None,
+        }
+    }
+
+    pub fn with_param(code: &str, message: impl Into<String>, param: ParamErrorDetail) -> Self {
+        Self {
+            code: code.to_string(),
+            message: message.into(),
+            param: Some(param),
         }
     }
 }
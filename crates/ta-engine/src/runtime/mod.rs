@@ -1,11 +1,28 @@
+mod batch;
 mod catalog;
 mod compute;
 mod contracts;
-mod params;
+pub(crate) mod params;
+mod pipeline;
+mod plan;
+mod validate;
 
+pub use batch::{
+    compute_batch, compute_indicator_batch, ComputeIndicatorBatchResponse, NamedSeriesBatch,
+    OhlcvBatch,
+};
 pub use catalog::runtime_catalog;
-pub use compute::compute_indicator;
+pub use compute::{compute_indicator, REGISTERED_RUNTIME_BINDINGS};
 pub use contracts::{
     ComputeIndicatorRequest, ComputeIndicatorResponse, ComputeRuntimeError, NamedSeries,
-    OhlcvInput, RuntimeCatalogEntry,
+    OhlcvInput, ParamErrorDetail, ParamErrorKind, RuntimeCatalogEntry,
+};
+pub use pipeline::{
+    evaluate_pipeline, BinOp, BinOpRhs, EventKind, Pipeline, PipelineNode, PipelineNodeKind,
+    PipelineValue,
+};
+pub use plan::{
+    compute_pipeline, execute_indicator_plan, IndicatorPlan, PipelineIndicatorSpec,
+    PlanInputSource, PlanNode,
 };
+pub use validate::{validate, validate_and_normalize, ParamError, ParamRule, ParamValue, ValidatedParams};
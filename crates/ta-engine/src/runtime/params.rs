@@ -1,8 +1,10 @@
 use serde_json::{Map, Number, Value};
 
-use crate::core::metadata::{IndicatorMeta, IndicatorParamKind};
+use crate::core::metadata::{
+    ConstraintOp, IndicatorMeta, IndicatorParamDefault, IndicatorParamKind,
+};
 
-use super::contracts::ComputeRuntimeError;
+use super::contracts::{ComputeRuntimeError, ParamErrorDetail, ParamErrorKind};
 
 pub fn normalize_params_for(
     meta: &IndicatorMeta,
@@ -39,14 +41,22 @@ pub fn normalize_params_for(
     let mut normalized = Map::new();
     for param in meta.params {
         let value = match canonical_in.remove(param.name) {
-            Some(value) => coerce_param_value(meta, param.name, param.kind, value)?,
+            Some(value) => {
+                coerce_param_value(meta, param.name, param.kind, value, param.allowed)?
+            }
             None => {
                 if let Some(default) = param.default {
-                    coerce_default(meta, param.name, param.kind, default)?
+                    coerce_default(meta, param.name, default)?
                 } else if param.required {
-                    return Err(ComputeRuntimeError::new(
+                    return Err(ComputeRuntimeError::with_param(
                         "invalid_param",
                         format!("missing required parameter '{}'", param.name),
+                        ParamErrorDetail {
+                            param_name: param.name.to_string(),
+                            kind: ParamErrorKind::Missing,
+                            expected: Some("a required parameter".to_string()),
+                            got: None,
+                        },
                     ));
                 } else {
                     continue;
@@ -64,9 +74,59 @@ pub fn normalize_params_for(
         normalized.insert(param.name.to_string(), value);
     }
 
+    for constraint in meta.constraints {
+        ensure_constraint(meta, &normalized, constraint)?;
+    }
+
     Ok(Value::Object(normalized))
 }
 
+fn ensure_constraint(
+    meta: &IndicatorMeta,
+    normalized: &Map<String, Value>,
+    constraint: &crate::core::metadata::ParamConstraint,
+) -> Result<(), ComputeRuntimeError> {
+    let (Some(left), Some(right)) = (
+        normalized.get(constraint.left).and_then(Value::as_f64),
+        normalized.get(constraint.right).and_then(Value::as_f64),
+    ) else {
+        return Ok(());
+    };
+
+    let holds = match constraint.op {
+        ConstraintOp::Lt => left < right,
+        ConstraintOp::Le => left <= right,
+        ConstraintOp::Gt => left > right,
+        ConstraintOp::Ge => left >= right,
+    };
+    if holds {
+        return Ok(());
+    }
+
+    let op = match constraint.op {
+        ConstraintOp::Lt => "<",
+        ConstraintOp::Le => "<=",
+        ConstraintOp::Gt => ">",
+        ConstraintOp::Ge => ">=",
+    };
+    Err(ComputeRuntimeError::with_param(
+        "invalid_param",
+        format!(
+            "indicator '{}' param '{}': must satisfy '{}' {op} '{}'",
+            meta.id, constraint.left, constraint.left, constraint.right
+        ),
+        ParamErrorDetail {
+            param_name: constraint.left.to_string(),
+            kind: ParamErrorKind::CrossFieldConstraint,
+            expected: Some(format!("{} {op} {}", constraint.left, constraint.right)),
+            got: Some(format!(
+                "{}={left}, {}={right}",
+                constraint.left, constraint.right
+            )),
+        },
+    ))
+}
+
 fn resolve_param_target<'a>(meta: &'a IndicatorMeta, key: &str) -> Option<&'a str> {
     if let Some(param) = meta
         .params
@@ -84,30 +144,22 @@ fn resolve_param_target<'a>(meta: &'a IndicatorMeta, key: &str) -> Option<&'a st
 fn coerce_default(
     meta: &IndicatorMeta,
     name: &str,
-    kind: IndicatorParamKind,
-    default: &str,
+    default: IndicatorParamDefault,
 ) -> Result<Value, ComputeRuntimeError> {
-    let parsed =
-        match kind {
-            IndicatorParamKind::Integer => default
-                .parse::<i64>()
-                .map(Value::from)
-                .map_err(|_| invalid_param(meta, name, "default integer parse failed"))?,
-            IndicatorParamKind::Float => {
-                let value = default
-                    .parse::<f64>()
-                    .map_err(|_| invalid_param(meta, name, "default float parse failed"))?;
-                Value::Number(Number::from_f64(value).ok_or_else(|| {
-                    invalid_param(meta, name, "default float cannot be represented")
-                })?)
-            }
-            IndicatorParamKind::Boolean => match default {
-                "true" | "1" => Value::Bool(true),
-                "false" | "0" => Value::Bool(false),
-                _ => return Err(invalid_param(meta, name, "default bool parse failed")),
-            },
-            IndicatorParamKind::String => Value::String(default.to_string()),
-        };
+    let parsed = match default {
+        IndicatorParamDefault::Integer(n) => Value::from(n),
+        IndicatorParamDefault::Float(n) => Value::Number(Number::from_f64(n).ok_or_else(|| {
+            ComputeRuntimeError::new(
+                "invalid_param",
+                format!(
+                    "indicator '{}' param '{name}': default float cannot be represented",
+                    meta.id
+                ),
+            )
+        })?),
+        IndicatorParamDefault::Boolean(flag) => Value::Bool(flag),
+        IndicatorParamDefault::String(s) => Value::String(s.to_string()),
+    };
     Ok(parsed)
 }
 
@@ -116,34 +168,36 @@ fn coerce_param_value(
     name: &str,
     kind: IndicatorParamKind,
     value: Value,
+    allowed: Option<&'static [&'static str]>,
 ) -> Result<Value, ComputeRuntimeError> {
+    let got_type = json_type_name(&value);
     match kind {
         IndicatorParamKind::Integer => match value {
             Value::Number(n) if n.is_i64() || n.is_u64() => Ok(Value::Number(n)),
             Value::String(s) => s
                 .parse::<i64>()
                 .map(Value::from)
-                .map_err(|_| invalid_param(meta, name, "must be an integer")),
-            _ => Err(invalid_param(meta, name, "must be an integer")),
+                .map_err(|_| wrong_type_param(meta, name, "an integer", got_type)),
+            _ => Err(wrong_type_param(meta, name, "an integer", got_type)),
         },
         IndicatorParamKind::Float => match value {
             Value::Number(n) => {
                 let as_f64 = n
                     .as_f64()
-                    .ok_or_else(|| invalid_param(meta, name, "must be numeric"))?;
+                    .ok_or_else(|| wrong_type_param(meta, name, "a finite number", got_type))?;
                 let repr = Number::from_f64(as_f64)
-                    .ok_or_else(|| invalid_param(meta, name, "must be finite"))?;
+                    .ok_or_else(|| wrong_type_param(meta, name, "a finite number", got_type))?;
                 Ok(Value::Number(repr))
             }
             Value::String(s) => {
                 let parsed = s
                     .parse::<f64>()
-                    .map_err(|_| invalid_param(meta, name, "must be numeric"))?;
-                Ok(Value::Number(Number::from_f64(parsed).ok_or_else(
-                    || invalid_param(meta, name, "must be finite"),
-                )?))
+                    .map_err(|_| wrong_type_param(meta, name, "a number", got_type))?;
+                Ok(Value::Number(Number::from_f64(parsed).ok_or_else(|| {
+                    wrong_type_param(meta, name, "a finite number", got_type)
+                })?))
             }
-            _ => Err(invalid_param(meta, name, "must be numeric")),
+            _ => Err(wrong_type_param(meta, name, "a number", got_type)),
         },
         IndicatorParamKind::Boolean => match value {
             Value::Bool(flag) => Ok(Value::Bool(flag)),
@@ -151,11 +205,23 @@ fn coerce_param_value(
             Value::String(s) if s.eq_ignore_ascii_case("false") || s == "0" => {
                 Ok(Value::Bool(false))
             }
-            _ => Err(invalid_param(meta, name, "must be a boolean")),
+            _ => Err(wrong_type_param(meta, name, "a boolean", got_type)),
         },
-        IndicatorParamKind::String => match value {
-            Value::String(s) => Ok(Value::String(s)),
-            _ => Err(invalid_param(meta, name, "must be a string")),
+        IndicatorParamKind::String | IndicatorParamKind::MaType => match value {
+            Value::String(s) => {
+                if let Some(choices) = allowed {
+                    if !choices.contains(&s.as_str()) {
+                        return Err(wrong_type_param(
+                            meta,
+                            name,
+                            &format!("one of {choices:?}"),
+                            &s,
+                        ));
+                    }
+                }
+                Ok(Value::String(s))
+            }
+            _ => Err(wrong_type_param(meta, name, "a string", got_type)),
         },
     }
 }
@@ -165,10 +231,7 @@ fn ensure_min(name: &str, value: &Value, min: f64) -> Result<(), ComputeRuntimeE
         ComputeRuntimeError::new("invalid_param", format!("'{name}' must be numeric"))
     })?;
     if numeric < min {
-        return Err(ComputeRuntimeError::new(
-            "invalid_param",
-            format!("'{name}' must be >= {min}"),
-        ));
+        return Err(out_of_range_param(name, format!(">= {min}"), numeric));
     }
     Ok(())
 }
@@ -178,17 +241,50 @@ fn ensure_max(name: &str, value: &Value, max: f64) -> Result<(), ComputeRuntimeE
         ComputeRuntimeError::new("invalid_param", format!("'{name}' must be numeric"))
     })?;
     if numeric > max {
-        return Err(ComputeRuntimeError::new(
-            "invalid_param",
-            format!("'{name}' must be <= {max}"),
-        ));
+        return Err(out_of_range_param(name, format!("<= {max}"), numeric));
     }
     Ok(())
 }
 
-fn invalid_param(meta: &IndicatorMeta, name: &str, reason: &str) -> ComputeRuntimeError {
-    ComputeRuntimeError::new(
+fn out_of_range_param(name: &str, expected: impl Into<String>, got: f64) -> ComputeRuntimeError {
+    let expected = expected.into();
+    ComputeRuntimeError::with_param(
         "invalid_param",
-        format!("indicator '{}' param '{name}': {reason}", meta.id),
+        format!("'{name}' must be {expected}"),
+        ParamErrorDetail {
+            param_name: name.to_string(),
+            kind: ParamErrorKind::OutOfRange,
+            expected: Some(expected),
+            got: Some(got.to_string()),
+        },
     )
 }
+
+fn wrong_type_param(
+    meta: &IndicatorMeta,
+    name: &str,
+    expected: &str,
+    got: impl std::fmt::Display,
+) -> ComputeRuntimeError {
+    ComputeRuntimeError::with_param(
+        "invalid_param",
+        format!("indicator '{}' param '{name}': must be {expected}", meta.id),
+        ParamErrorDetail {
+            param_name: name.to_string(),
+            kind: ParamErrorKind::WrongType,
+            expected: Some(expected.to_string()),
+            got: Some(got.to_string()),
+        },
+    )
+}
+
+fn json_type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
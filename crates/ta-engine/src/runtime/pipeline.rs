@@ -0,0 +1,368 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use serde_json::Value;
+
+use crate::core::events;
+
+use super::compute::compute_indicator;
+use super::contracts::{ComputeIndicatorRequest, ComputeRuntimeError, OhlcvInput};
+
+/// Element-wise binary op between two nodes, or a node and a scalar
+/// broadcast. `SubF` is the flipped subtraction (`rhs - lhs`), for the
+/// common case of wanting "b minus a" without a second node.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BinOp {
+    Min,
+    Max,
+    Add,
+    Mul,
+    Sub,
+    SubF,
+}
+
+impl BinOp {
+    fn apply(self, a: f64, b: f64) -> f64 {
+        match self {
+            BinOp::Min => a.min(b),
+            BinOp::Max => a.max(b),
+            BinOp::Add => a + b,
+            BinOp::Mul => a * b,
+            BinOp::Sub => a - b,
+            BinOp::SubF => b - a,
+        }
+    }
+}
+
+/// The right-hand operand of a [`BinOp`] node.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BinOpRhs {
+    Node(String),
+    Scalar(f64),
+}
+
+/// Which two-series or one-series crossing/direction check an event node
+/// runs, mirroring `core::events`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EventKind {
+    CrossUp,
+    CrossDown,
+    Cross,
+    Rising,
+    Falling,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PipelineNodeKind {
+    /// A raw OHLCV column: `open`/`high`/`low`/`close`/`volume`.
+    Source(String),
+    /// A catalog indicator, with its required inputs wired to earlier
+    /// nodes by param name (e.g. `"source"` -> an upstream node id) and
+    /// `output` selecting which of its named outputs becomes this node's
+    /// value (the first output if `None`).
+    Indicator {
+        indicator_id: String,
+        params: Value,
+        inputs: BTreeMap<String, String>,
+        output: Option<String>,
+    },
+    BinOp {
+        op: BinOp,
+        lhs: String,
+        rhs: BinOpRhs,
+    },
+    Event {
+        kind: EventKind,
+        lhs: String,
+        rhs: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PipelineNode {
+    pub id: String,
+    pub kind: PipelineNodeKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Pipeline {
+    pub nodes: Vec<PipelineNode>,
+    pub ohlcv: OhlcvInput,
+}
+
+/// A pipeline node's evaluated output: numeric for source/indicator/binop
+/// nodes, boolean for event nodes (matching `core::events`' own return
+/// type instead of smuggling booleans through as `0.0`/`1.0`).
+#[derive(Debug, Clone, PartialEq)]
+pub enum PipelineValue {
+    Numeric(Vec<f64>),
+    Boolean(Vec<bool>),
+}
+
+/// Evaluates every node in `pipeline` in dependency order in a single Rust
+/// pass, sharing intermediates in memory instead of round-tripping each
+/// node through the Python boundary. Shared sub-expressions (e.g. one EMA
+/// feeding two downstream nodes) are computed exactly once since every
+/// node is resolved by id and cached in `node_values`.
+pub fn evaluate_pipeline(
+    pipeline: &Pipeline,
+) -> Result<BTreeMap<String, PipelineValue>, ComputeRuntimeError> {
+    pipeline.ohlcv.validate()?;
+
+    let by_id: HashMap<&str, &PipelineNode> = pipeline
+        .nodes
+        .iter()
+        .map(|node| (node.id.as_str(), node))
+        .collect();
+    let order = topological_order(pipeline, &by_id)?;
+
+    let mut node_values: HashMap<String, Vec<f64>> = HashMap::new();
+    let mut results: BTreeMap<String, PipelineValue> = BTreeMap::new();
+
+    for node_id in order {
+        let node = by_id[node_id.as_str()];
+        let value = match &node.kind {
+            PipelineNodeKind::Source(field) => ohlcv_field(&pipeline.ohlcv, field)?.to_vec(),
+            PipelineNodeKind::Indicator {
+                indicator_id,
+                params,
+                inputs,
+                output,
+            } => evaluate_indicator(pipeline, indicator_id, params, inputs, output, &node_values)?,
+            PipelineNodeKind::BinOp { op, lhs, rhs } => {
+                evaluate_binop(*op, lhs, rhs, &node_values)?
+            }
+            PipelineNodeKind::Event { kind, lhs, rhs } => {
+                let signal = evaluate_event(*kind, lhs, rhs.as_deref(), &node_values)?;
+                let numeric: Vec<f64> = signal
+                    .iter()
+                    .map(|&b| if b { 1.0 } else { 0.0 })
+                    .collect();
+                results.insert(node.id.clone(), PipelineValue::Boolean(signal));
+                node_values.insert(node.id.clone(), numeric);
+                continue;
+            }
+        };
+        results.insert(node.id.clone(), PipelineValue::Numeric(value.clone()));
+        node_values.insert(node.id.clone(), value);
+    }
+
+    Ok(results)
+}
+
+fn evaluate_indicator(
+    pipeline: &Pipeline,
+    indicator_id: &str,
+    params: &Value,
+    inputs: &BTreeMap<String, String>,
+    output: &Option<String>,
+    node_values: &HashMap<String, Vec<f64>>,
+) -> Result<Vec<f64>, ComputeRuntimeError> {
+    let mut named_inputs = BTreeMap::new();
+    for (param_name, upstream) in inputs {
+        let series = node_values.get(upstream).ok_or_else(|| {
+            ComputeRuntimeError::new(
+                "unknown_pipeline_node",
+                format!("indicator input '{param_name}' depends on unresolved node '{upstream}'"),
+            )
+        })?;
+        named_inputs.insert(param_name.clone(), series.clone());
+    }
+
+    let response = compute_indicator(ComputeIndicatorRequest {
+        indicator_id: indicator_id.to_string(),
+        params: params.clone(),
+        ohlcv: pipeline.ohlcv.clone(),
+        instance_id: None,
+        named_inputs,
+    })?;
+
+    let series = match output {
+        Some(name) => response
+            .outputs
+            .iter()
+            .find(|series| &series.name == name)
+            .ok_or_else(|| {
+                ComputeRuntimeError::new(
+                    "unknown_pipeline_output",
+                    format!("indicator '{indicator_id}' has no output named '{name}'"),
+                )
+            })?,
+        None => response.outputs.first().ok_or_else(|| {
+            ComputeRuntimeError::new(
+                "unknown_pipeline_output",
+                format!("indicator '{indicator_id}' produced no outputs"),
+            )
+        })?,
+    };
+
+    Ok(series.values.iter().map(|v| v.unwrap_or(f64::NAN)).collect())
+}
+
+fn evaluate_binop(
+    op: BinOp,
+    lhs: &str,
+    rhs: &BinOpRhs,
+    node_values: &HashMap<String, Vec<f64>>,
+) -> Result<Vec<f64>, ComputeRuntimeError> {
+    let lhs_series = lookup_node(node_values, lhs)?;
+
+    match rhs {
+        BinOpRhs::Scalar(scalar) => {
+            Ok(lhs_series.iter().map(|&a| op.apply(a, *scalar)).collect())
+        }
+        BinOpRhs::Node(node) => {
+            let rhs_series = lookup_node(node_values, node)?;
+            if lhs_series.len() != rhs_series.len() {
+                return Err(ComputeRuntimeError::new(
+                    "pipeline_length_mismatch",
+                    format!(
+                        "binop operands '{lhs}' (len {}) and '{node}' (len {}) have different lengths",
+                        lhs_series.len(),
+                        rhs_series.len()
+                    ),
+                ));
+            }
+            Ok(lhs_series
+                .iter()
+                .zip(rhs_series)
+                .map(|(&a, &b)| op.apply(a, b))
+                .collect())
+        }
+    }
+}
+
+fn evaluate_event(
+    kind: EventKind,
+    lhs: &str,
+    rhs: Option<&str>,
+    node_values: &HashMap<String, Vec<f64>>,
+) -> Result<Vec<bool>, ComputeRuntimeError> {
+    let lhs_series = lookup_node(node_values, lhs)?;
+    match kind {
+        EventKind::Rising => Ok(events::rising(lhs_series)),
+        EventKind::Falling => Ok(events::falling(lhs_series)),
+        EventKind::CrossUp | EventKind::CrossDown | EventKind::Cross => {
+            let rhs_name = rhs.ok_or_else(|| {
+                ComputeRuntimeError::new(
+                    "pipeline_missing_operand",
+                    format!("event node over '{lhs}' requires an 'rhs' node"),
+                )
+            })?;
+            let rhs_series = lookup_node(node_values, rhs_name)?;
+            Ok(match kind {
+                EventKind::CrossUp => events::crossup(lhs_series, rhs_series),
+                EventKind::CrossDown => events::crossdown(lhs_series, rhs_series),
+                EventKind::Cross => events::cross(lhs_series, rhs_series),
+                EventKind::Rising | EventKind::Falling => unreachable!(),
+            })
+        }
+    }
+}
+
+fn lookup_node<'a>(
+    node_values: &'a HashMap<String, Vec<f64>>,
+    node: &str,
+) -> Result<&'a [f64], ComputeRuntimeError> {
+    node_values
+        .get(node)
+        .map(Vec::as_slice)
+        .ok_or_else(|| {
+            ComputeRuntimeError::new(
+                "unknown_pipeline_node",
+                format!("reference to unresolved node '{node}'"),
+            )
+        })
+}
+
+fn ohlcv_field<'a>(ohlcv: &'a OhlcvInput, field: &str) -> Result<&'a [f64], ComputeRuntimeError> {
+    match field.to_ascii_lowercase().as_str() {
+        "open" => Ok(&ohlcv.open),
+        "high" => Ok(&ohlcv.high),
+        "low" => Ok(&ohlcv.low),
+        "close" => Ok(&ohlcv.close),
+        "volume" => ohlcv.volume.as_deref().ok_or_else(|| {
+            ComputeRuntimeError::new("missing_volume", "volume column was not provided")
+        }),
+        other => Err(ComputeRuntimeError::new(
+            "unknown_source_field",
+            format!("unknown source field '{other}'"),
+        )),
+    }
+}
+
+fn node_dependencies(kind: &PipelineNodeKind) -> Vec<&str> {
+    match kind {
+        PipelineNodeKind::Source(_) => Vec::new(),
+        PipelineNodeKind::Indicator { inputs, .. } => {
+            inputs.values().map(String::as_str).collect()
+        }
+        PipelineNodeKind::BinOp { lhs, rhs, .. } => {
+            let mut deps = vec![lhs.as_str()];
+            if let BinOpRhs::Node(node) = rhs {
+                deps.push(node.as_str());
+            }
+            deps
+        }
+        PipelineNodeKind::Event { lhs, rhs, .. } => {
+            let mut deps = vec![lhs.as_str()];
+            if let Some(rhs) = rhs {
+                deps.push(rhs.as_str());
+            }
+            deps
+        }
+    }
+}
+
+/// Performs a Kahn topological sort over `pipeline.nodes`, returning node
+/// ids in evaluation order (mirrors `runtime::plan`'s node sort).
+fn topological_order(
+    pipeline: &Pipeline,
+    by_id: &HashMap<&str, &PipelineNode>,
+) -> Result<Vec<String>, ComputeRuntimeError> {
+    let mut in_degree: HashMap<&str, usize> =
+        pipeline.nodes.iter().map(|n| (n.id.as_str(), 0)).collect();
+    let mut dependents: HashMap<&str, Vec<&str>> = HashMap::new();
+
+    for node in &pipeline.nodes {
+        for upstream in node_dependencies(&node.kind) {
+            if !by_id.contains_key(upstream) {
+                return Err(ComputeRuntimeError::new(
+                    "unknown_pipeline_node",
+                    format!("node '{}' references unknown node '{upstream}'", node.id),
+                ));
+            }
+            *in_degree.get_mut(node.id.as_str()).unwrap() += 1;
+            dependents.entry(upstream).or_default().push(node.id.as_str());
+        }
+    }
+
+    let mut ready: BTreeSet<&str> = in_degree
+        .iter()
+        .filter(|(_, degree)| **degree == 0)
+        .map(|(id, _)| *id)
+        .collect();
+
+    let mut order = Vec::with_capacity(pipeline.nodes.len());
+    while let Some(id) = ready.iter().next().copied() {
+        ready.remove(id);
+        order.push(id.to_string());
+        if let Some(children) = dependents.get(id) {
+            for child in children {
+                let degree = in_degree.get_mut(child).unwrap();
+                *degree -= 1;
+                if *degree == 0 {
+                    ready.insert(child);
+                }
+            }
+        }
+    }
+
+    if order.len() != pipeline.nodes.len() {
+        return Err(ComputeRuntimeError::new(
+            "pipeline_cycle",
+            "pipeline contains a dependency cycle",
+        ));
+    }
+
+    Ok(order)
+}
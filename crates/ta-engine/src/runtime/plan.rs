@@ -0,0 +1,266 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use serde_json::Value;
+
+use super::compute::compute_indicator;
+use super::contracts::{
+    ComputeIndicatorRequest, ComputeIndicatorResponse, ComputeRuntimeError, OhlcvInput,
+};
+
+/// Where a plan node's required input field is sourced from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlanInputSource {
+    /// A raw OHLCV field (`open`/`high`/`low`/`close`/`volume`).
+    Field(String),
+    /// A named output of an upstream node in the same plan.
+    Node { node: String, output: String },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanNode {
+    pub id: String,
+    pub indicator_id: String,
+    pub params: Value,
+    pub inputs: BTreeMap<String, PlanInputSource>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndicatorPlan {
+    pub nodes: Vec<PlanNode>,
+    pub ohlcv: OhlcvInput,
+}
+
+/// Runs every node in `plan` in dependency order, feeding upstream named
+/// outputs into downstream nodes that reference them as an input source
+/// instead of a raw OHLCV field. The upstream's warmup `NaN` prefix carries
+/// through unchanged, so a downstream indicator sees the same gap its
+/// upstream produced rather than treating it as real data.
+pub fn execute_indicator_plan(
+    plan: &IndicatorPlan,
+) -> Result<BTreeMap<String, ComputeIndicatorResponse>, ComputeRuntimeError> {
+    plan.ohlcv.validate()?;
+
+    let by_id: HashMap<&str, &PlanNode> =
+        plan.nodes.iter().map(|node| (node.id.as_str(), node)).collect();
+    let order = topological_order(plan, &by_id)?;
+
+    let mut node_outputs: HashMap<String, BTreeMap<String, Vec<f64>>> = HashMap::new();
+    let mut responses: BTreeMap<String, ComputeIndicatorResponse> = BTreeMap::new();
+
+    for node_id in order {
+        let node = by_id[node_id.as_str()];
+        let named_inputs = resolve_named_inputs(node, &node_outputs)?;
+
+        let req = ComputeIndicatorRequest {
+            indicator_id: node.indicator_id.clone(),
+            params: params_with_named_overrides(&node.params, &node.inputs),
+            ohlcv: plan.ohlcv.clone(),
+            instance_id: Some(node.id.clone()),
+            named_inputs,
+        };
+        let response = compute_indicator(req)?;
+
+        let by_name: BTreeMap<String, Vec<f64>> = response
+            .outputs
+            .iter()
+            .map(|series| {
+                (
+                    series.name.clone(),
+                    series.values.iter().map(|v| v.unwrap_or(f64::NAN)).collect(),
+                )
+            })
+            .collect();
+        node_outputs.insert(node.id.clone(), by_name);
+        responses.insert(node.id.clone(), response);
+    }
+
+    Ok(responses)
+}
+
+/// One entry in a [`compute_pipeline`] call: an indicator to run, tagged
+/// with the `instance_id` later specs can reference its outputs by.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PipelineIndicatorSpec {
+    pub instance_id: String,
+    pub indicator_id: String,
+    pub params: Value,
+}
+
+/// Convenience entry point over [`execute_indicator_plan`] for callers that
+/// would rather write `"<instance_id>.<output_name>"` straight into a
+/// `source`/`a`/`b`/`price`-style param than build [`PlanInputSource`]s by
+/// hand. Any string param value of that shape, where `<instance_id>` names
+/// another spec in `specs`, is rewired into a `PlanInputSource::Node`
+/// reference (e.g. `"price": "ema_fast.result"` feeds `ema_fast`'s `result`
+/// output into this node's `price` field); every other param passes through
+/// unchanged and falls back to a raw OHLCV field as usual. Dependency
+/// ordering and cycle detection (`plan_cycle`) are exactly
+/// `execute_indicator_plan`'s -- this only handles translating the
+/// dotted-string convention into the typed plan it already understands.
+pub fn compute_pipeline(
+    specs: &[PipelineIndicatorSpec],
+    ohlcv: OhlcvInput,
+) -> Result<BTreeMap<String, ComputeIndicatorResponse>, ComputeRuntimeError> {
+    let instance_ids: BTreeSet<&str> =
+        specs.iter().map(|spec| spec.instance_id.as_str()).collect();
+
+    let nodes = specs
+        .iter()
+        .map(|spec| {
+            let mut params = match &spec.params {
+                Value::Object(map) => map.clone(),
+                _ => serde_json::Map::new(),
+            };
+            let mut inputs = BTreeMap::new();
+            for field in params.keys().cloned().collect::<Vec<_>>() {
+                let Some(reference) = params
+                    .get(&field)
+                    .and_then(Value::as_str)
+                    .and_then(|value| parse_pipeline_reference(value, &instance_ids))
+                else {
+                    continue;
+                };
+                inputs.insert(
+                    field.clone(),
+                    PlanInputSource::Node {
+                        node: reference.0.to_string(),
+                        output: reference.1.to_string(),
+                    },
+                );
+                params.remove(&field);
+            }
+            PlanNode {
+                id: spec.instance_id.clone(),
+                indicator_id: spec.indicator_id.clone(),
+                params: Value::Object(params),
+                inputs,
+            }
+        })
+        .collect();
+
+    execute_indicator_plan(&IndicatorPlan { nodes, ohlcv })
+}
+
+/// Splits `value` on its first `.` and returns `(instance_id, output_name)`
+/// if the left half names a spec in `instance_ids`, so a plain string like a
+/// moving-average method name (`"ema"`) or an OHLCV field (`"close"`) isn't
+/// mistaken for a pipeline reference.
+fn parse_pipeline_reference<'a>(
+    value: &'a str,
+    instance_ids: &BTreeSet<&str>,
+) -> Option<(&'a str, &'a str)> {
+    let (id, output) = value.split_once('.')?;
+    instance_ids.contains(id).then_some((id, output))
+}
+
+/// Performs a Kahn topological sort over `plan.nodes`, returning node ids in
+/// evaluation order. Cycles are reported as a `ComputeRuntimeError` with code
+/// `plan_cycle` rather than panicking or looping forever.
+fn topological_order(
+    plan: &IndicatorPlan,
+    by_id: &HashMap<&str, &PlanNode>,
+) -> Result<Vec<String>, ComputeRuntimeError> {
+    let mut in_degree: HashMap<&str, usize> =
+        plan.nodes.iter().map(|n| (n.id.as_str(), 0)).collect();
+    let mut dependents: HashMap<&str, Vec<&str>> = HashMap::new();
+
+    for node in &plan.nodes {
+        for source in node.inputs.values() {
+            if let PlanInputSource::Node { node: upstream, .. } = source {
+                if !by_id.contains_key(upstream.as_str()) {
+                    return Err(ComputeRuntimeError::new(
+                        "unknown_plan_node",
+                        format!("node '{}' references unknown node '{upstream}'", node.id),
+                    ));
+                }
+                *in_degree.get_mut(node.id.as_str()).unwrap() += 1;
+                dependents
+                    .entry(upstream.as_str())
+                    .or_default()
+                    .push(node.id.as_str());
+            }
+        }
+    }
+
+    let mut ready: BTreeSet<&str> = in_degree
+        .iter()
+        .filter(|(_, degree)| **degree == 0)
+        .map(|(id, _)| *id)
+        .collect();
+
+    let mut order = Vec::with_capacity(plan.nodes.len());
+    while let Some(id) = ready.iter().next().copied() {
+        ready.remove(id);
+        order.push(id.to_string());
+        if let Some(children) = dependents.get(id) {
+            for child in children {
+                let degree = in_degree.get_mut(child).unwrap();
+                *degree -= 1;
+                if *degree == 0 {
+                    ready.insert(child);
+                }
+            }
+        }
+    }
+
+    if order.len() != plan.nodes.len() {
+        return Err(ComputeRuntimeError::new(
+            "plan_cycle",
+            "indicator plan contains a dependency cycle",
+        ));
+    }
+
+    Ok(order)
+}
+
+/// Looks up each `PlanInputSource::Node` reference for `node`, returning the
+/// upstream output series keyed by the required-field name it should be
+/// substituted for.
+fn resolve_named_inputs(
+    node: &PlanNode,
+    node_outputs: &HashMap<String, BTreeMap<String, Vec<f64>>>,
+) -> Result<BTreeMap<String, Vec<f64>>, ComputeRuntimeError> {
+    let mut resolved = BTreeMap::new();
+    for (field, source) in &node.inputs {
+        if let PlanInputSource::Node {
+            node: upstream,
+            output,
+        } = source
+        {
+            let upstream_outputs = node_outputs.get(upstream).ok_or_else(|| {
+                ComputeRuntimeError::new(
+                    "unknown_plan_node",
+                    format!("node '{}' depends on unresolved node '{upstream}'", node.id),
+                )
+            })?;
+            let series = upstream_outputs.get(output).ok_or_else(|| {
+                ComputeRuntimeError::new(
+                    "unknown_plan_output",
+                    format!("node '{upstream}' has no output named '{output}'"),
+                )
+            })?;
+            resolved.insert(field.clone(), series.clone());
+        }
+    }
+    Ok(resolved)
+}
+
+/// Rewrites `params` so every field wired to a `PlanInputSource::Field`
+/// explicitly names that field (letting the raw OHLCV override still work),
+/// while fields wired to `PlanInputSource::Node` are left untouched — the
+/// compute layer resolves them from `ComputeIndicatorRequest::named_inputs`.
+fn params_with_named_overrides(
+    params: &Value,
+    inputs: &BTreeMap<String, PlanInputSource>,
+) -> Value {
+    let mut merged = match params {
+        Value::Object(map) => map.clone(),
+        _ => serde_json::Map::new(),
+    };
+    for (field, source) in inputs {
+        if let PlanInputSource::Field(name) = source {
+            merged.insert(field.clone(), Value::String(name.clone()));
+        }
+    }
+    Value::Object(merged)
+}
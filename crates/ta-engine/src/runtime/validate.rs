@@ -0,0 +1,289 @@
+//! Typed parameter-validation boundary: [`validate`] resolves
+//! `param_aliases`, fills in `default`s, coerces by `kind`, and range/finite
+//! checks an indicator's params in one pass, so code holding a
+//! [`ValidatedParams`] can assume clean, in-range, finite inputs instead of
+//! re-checking `isNan`/`isFinite` itself. Structured [`ParamError`]s name the
+//! offending field and the rule it broke, rather than the caller getting a
+//! panic or a silently clamped value. `compute_indicator` does not go through
+//! this module; it normalizes its JSON params with `params::normalize_params_for`.
+
+use std::collections::BTreeMap;
+
+use crate::core::metadata::{
+    find_indicator_meta, IndicatorMeta, IndicatorParamDefault, IndicatorParamKind,
+};
+
+/// A parameter value as supplied by a caller, before catalog-driven
+/// coercion and validation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParamValue {
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    String(String),
+}
+
+/// Parameters that have passed [`validate`]: aliases resolved to their
+/// canonical name, missing optionals filled from `default`, every value
+/// coerced to its `kind`, and range/finiteness checked. Downstream compute
+/// can read from this without re-checking any of it.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ValidatedParams {
+    values: BTreeMap<String, ParamValue>,
+}
+
+impl ValidatedParams {
+    pub fn get(&self, name: &str) -> Option<&ParamValue> {
+        self.values.get(name)
+    }
+}
+
+/// The rule a [`ParamError`] reports as violated.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParamRule {
+    /// `field` doesn't match any `IndicatorParamMeta::name` or
+    /// `param_aliases` entry for this indicator.
+    Unknown,
+    /// `field` was supplied both directly and through an alias that
+    /// resolves to it.
+    DuplicateAssignment,
+    /// `field` is `required` and neither supplied nor defaulted.
+    MissingRequired,
+    /// The supplied value's variant can't be coerced to `field`'s `kind`.
+    WrongKind(IndicatorParamKind),
+    /// `field` is a `Float` param and the value is NaN or infinite.
+    NotFinite,
+    /// The value is below `IndicatorParamMeta::min`.
+    BelowMin(f64),
+    /// The value is above `IndicatorParamMeta::max`.
+    AboveMax(f64),
+    /// The value isn't one of `IndicatorParamMeta::allowed`'s choices.
+    NotAllowed(&'static [&'static str]),
+    /// `id` itself doesn't resolve to any catalog entry.
+    UnknownIndicator,
+}
+
+/// A single validation failure, naming the field and the rule it violated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParamError {
+    pub field: String,
+    pub rule: ParamRule,
+}
+
+impl ParamError {
+    fn new(field: &str, rule: ParamRule) -> Self {
+        Self {
+            field: field.to_string(),
+            rule,
+        }
+    }
+}
+
+impl std::fmt::Display for ParamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.rule {
+            ParamRule::Unknown => write!(f, "'{}' is not a known parameter", self.field),
+            ParamRule::DuplicateAssignment => {
+                write!(f, "'{}' was supplied more than once (via an alias)", self.field)
+            }
+            ParamRule::MissingRequired => write!(f, "'{}' is required", self.field),
+            ParamRule::WrongKind(kind) => write!(f, "'{}' must be a {kind:?}", self.field),
+            ParamRule::NotFinite => write!(f, "'{}' must be finite", self.field),
+            ParamRule::BelowMin(min) => write!(f, "'{}' must be >= {min}", self.field),
+            ParamRule::AboveMax(max) => write!(f, "'{}' must be <= {max}", self.field),
+            ParamRule::NotAllowed(choices) => {
+                write!(f, "'{}' must be one of {choices:?}", self.field)
+            }
+            ParamRule::UnknownIndicator => write!(f, "'{}' is not a known indicator", self.field),
+        }
+    }
+}
+
+impl std::error::Error for ParamError {}
+
+/// Validates `supplied` against `meta.params`, resolving aliases, filling
+/// defaults, coercing kinds, and range/finiteness-checking every value.
+/// Collects every violation rather than stopping at the first, so a caller
+/// can report them all at once.
+pub fn validate(
+    meta: &IndicatorMeta,
+    supplied: &BTreeMap<&str, ParamValue>,
+) -> Result<ValidatedParams, Vec<ParamError>> {
+    let mut errors = Vec::new();
+    let mut canonical_in: BTreeMap<&'static str, ParamValue> = BTreeMap::new();
+
+    for (&key, value) in supplied {
+        let Some(target) = resolve_param_target(meta, key) else {
+            errors.push(ParamError::new(key, ParamRule::Unknown));
+            continue;
+        };
+        if canonical_in.contains_key(target) {
+            errors.push(ParamError::new(target, ParamRule::DuplicateAssignment));
+            continue;
+        }
+        canonical_in.insert(target, value.clone());
+    }
+
+    let mut values = BTreeMap::new();
+    for param in meta.params {
+        let resolved = match canonical_in.remove(param.name) {
+            Some(value) => coerce_kind(param.name, param.kind, value, &mut errors),
+            None => match param.default {
+                Some(default) => Some(coerce_default(default)),
+                None if param.required => {
+                    errors.push(ParamError::new(param.name, ParamRule::MissingRequired));
+                    None
+                }
+                None => None,
+            },
+        };
+
+        let Some(resolved) = resolved else {
+            continue;
+        };
+
+        if let Some(numeric) = as_f64(&resolved) {
+            if param.kind == IndicatorParamKind::Float && !numeric.is_finite() {
+                errors.push(ParamError::new(param.name, ParamRule::NotFinite));
+                continue;
+            }
+            if let Some(min) = param.min {
+                if numeric < min {
+                    errors.push(ParamError::new(param.name, ParamRule::BelowMin(min)));
+                    continue;
+                }
+            }
+            if let Some(max) = param.max {
+                if numeric > max {
+                    errors.push(ParamError::new(param.name, ParamRule::AboveMax(max)));
+                    continue;
+                }
+            }
+        }
+
+        if let (Some(choices), ParamValue::String(value)) = (param.allowed, &resolved) {
+            if !choices.contains(&value.as_str()) {
+                errors.push(ParamError::new(param.name, ParamRule::NotAllowed(choices)));
+                continue;
+            }
+        }
+
+        values.insert(param.name.to_string(), resolved);
+    }
+
+    if errors.is_empty() {
+        Ok(ValidatedParams { values })
+    } else {
+        Err(errors)
+    }
+}
+
+/// String-keyed front door onto [`validate`] for callers that only have raw
+/// text (query params, CLI flags, form fields) rather than typed
+/// [`ParamValue`]s. Resolves `id` via [`find_indicator_meta`], parses each
+/// raw string according to its target param's `kind` (falling back to a
+/// plain string for unrecognized keys, which `validate` then reports as
+/// [`ParamRule::Unknown`]), and otherwise defers entirely to `validate` for
+/// alias resolution, defaulting, and range/finiteness/allowed checks.
+pub fn validate_and_normalize(
+    id: &str,
+    params: &BTreeMap<String, String>,
+) -> Result<ValidatedParams, Vec<ParamError>> {
+    let meta = find_indicator_meta(id)
+        .ok_or_else(|| vec![ParamError::new(id, ParamRule::UnknownIndicator)])?;
+
+    let mut errors = Vec::new();
+    let mut supplied: BTreeMap<&str, ParamValue> = BTreeMap::new();
+    for (key, raw) in params {
+        let kind = resolve_param_target(meta, key)
+            .and_then(|target| meta.params.iter().find(|param| param.name == target))
+            .map(|param| param.kind);
+        match kind {
+            Some(kind) => match parse_param_value(kind, raw) {
+                Some(value) => {
+                    supplied.insert(key.as_str(), value);
+                }
+                None => errors.push(ParamError::new(key, ParamRule::WrongKind(kind))),
+            },
+            None => {
+                supplied.insert(key.as_str(), ParamValue::String(raw.clone()));
+            }
+        }
+    }
+
+    match validate(meta, &supplied) {
+        Ok(validated) if errors.is_empty() => Ok(validated),
+        Ok(_) => Err(errors),
+        Err(mut validate_errors) => {
+            errors.append(&mut validate_errors);
+            Err(errors)
+        }
+    }
+}
+
+fn parse_param_value(kind: IndicatorParamKind, raw: &str) -> Option<ParamValue> {
+    match kind {
+        IndicatorParamKind::Integer => raw.parse::<i64>().ok().map(ParamValue::Integer),
+        IndicatorParamKind::Float => raw.parse::<f64>().ok().map(ParamValue::Float),
+        IndicatorParamKind::Boolean => match raw {
+            "true" | "1" => Some(ParamValue::Boolean(true)),
+            "false" | "0" => Some(ParamValue::Boolean(false)),
+            _ => None,
+        },
+        IndicatorParamKind::String | IndicatorParamKind::MaType => {
+            Some(ParamValue::String(raw.to_string()))
+        }
+    }
+}
+
+fn resolve_param_target(meta: &IndicatorMeta, key: &str) -> Option<&'static str> {
+    if let Some(param) = meta
+        .params
+        .iter()
+        .find(|param| param.name.eq_ignore_ascii_case(key))
+    {
+        return Some(param.name);
+    }
+    meta.param_aliases
+        .iter()
+        .find(|alias| alias.alias.eq_ignore_ascii_case(key))
+        .map(|alias| alias.target)
+}
+
+fn coerce_kind(
+    field: &str,
+    kind: IndicatorParamKind,
+    value: ParamValue,
+    errors: &mut Vec<ParamError>,
+) -> Option<ParamValue> {
+    let coerced = match (kind, value) {
+        (IndicatorParamKind::Integer, ParamValue::Integer(n)) => ParamValue::Integer(n),
+        (IndicatorParamKind::Float, ParamValue::Integer(n)) => ParamValue::Float(n as f64),
+        (IndicatorParamKind::Float, ParamValue::Float(n)) => ParamValue::Float(n),
+        (IndicatorParamKind::Boolean, ParamValue::Boolean(b)) => ParamValue::Boolean(b),
+        (IndicatorParamKind::String, ParamValue::String(s)) => ParamValue::String(s),
+        (IndicatorParamKind::MaType, ParamValue::String(s)) => ParamValue::String(s),
+        (_, _) => {
+            errors.push(ParamError::new(field, ParamRule::WrongKind(kind)));
+            return None;
+        }
+    };
+    Some(coerced)
+}
+
+fn coerce_default(default: IndicatorParamDefault) -> ParamValue {
+    match default {
+        IndicatorParamDefault::Integer(n) => ParamValue::Integer(n),
+        IndicatorParamDefault::Float(n) => ParamValue::Float(n),
+        IndicatorParamDefault::Boolean(b) => ParamValue::Boolean(b),
+        IndicatorParamDefault::String(s) => ParamValue::String(s.to_string()),
+    }
+}
+
+fn as_f64(value: &ParamValue) -> Option<f64> {
+    match value {
+        ParamValue::Integer(n) => Some(*n as f64),
+        ParamValue::Float(n) => Some(*n),
+        ParamValue::Boolean(_) | ParamValue::String(_) => None,
+    }
+}
@@ -0,0 +1,158 @@
+/// Normalized ATR: [`crate::volatility::atr`] expressed as a percentage of
+/// `close`, so volatility is comparable across instruments at different
+/// price levels.
+pub fn normalized_atr(high: &[f64], low: &[f64], close: &[f64], period: usize) -> Vec<f64> {
+    let atr = crate::volatility::atr(high, low, close, period);
+    let n = close.len();
+    let mut out = vec![f64::NAN; n];
+    for i in 0..n {
+        if atr[i].is_nan() || close[i] == 0.0 {
+            continue;
+        }
+        out[i] = atr[i] / close[i] * 100.0;
+    }
+    out
+}
+
+/// Rolling least-squares fit of `close` against `i = 0..period-1` within
+/// each window. Returns `(slope, intercept, r_squared)` per point.
+pub fn linreg_slope(close: &[f64], period: usize) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let n = close.len();
+    let mut slope = vec![f64::NAN; n];
+    let mut intercept = vec![f64::NAN; n];
+    let mut r_squared = vec![f64::NAN; n];
+    if n == 0 || period < 2 {
+        return (slope, intercept, r_squared);
+    }
+
+    let p = period as f64;
+    let sum_i: f64 = (0..period).map(|i| i as f64).sum();
+    let sum_i2: f64 = (0..period).map(|i| (i as f64).powi(2)).sum();
+    let denom = p * sum_i2 - sum_i * sum_i;
+
+    for idx in 0..n {
+        if idx + 1 < period {
+            continue;
+        }
+        let start = idx + 1 - period;
+        let window = &close[start..=idx];
+
+        let sum_y: f64 = window.iter().sum();
+        let sum_iy: f64 = window.iter().enumerate().map(|(i, &y)| i as f64 * y).sum();
+        let m = if denom == 0.0 {
+            0.0
+        } else {
+            (p * sum_iy - sum_i * sum_y) / denom
+        };
+        let b = (sum_y - m * sum_i) / p;
+
+        let mean_y = sum_y / p;
+        let ss_tot: f64 = window.iter().map(|&y| (y - mean_y).powi(2)).sum();
+        let r2 = if ss_tot == 0.0 {
+            0.0
+        } else {
+            let ss_res: f64 = window
+                .iter()
+                .enumerate()
+                .map(|(i, &y)| (y - (b + m * i as f64)).powi(2))
+                .sum();
+            1.0 - ss_res / ss_tot
+        };
+
+        slope[idx] = m;
+        intercept[idx] = b;
+        r_squared[idx] = r2;
+    }
+
+    (slope, intercept, r_squared)
+}
+
+/// Hurst exponent via rescaled-range (R/S) analysis: each `period`-long
+/// window is halved down into sub-windows of at least 8 points, the mean
+/// R/S statistic is computed per sub-window size, and H is estimated as
+/// the slope of `log(R/S)` against `log(sub_window_size)`. `H > 0.5`
+/// indicates a trending series, `H < 0.5` a mean-reverting one.
+pub fn hurst_exponent(close: &[f64], period: usize) -> Vec<f64> {
+    let n = close.len();
+    let mut out = vec![f64::NAN; n];
+    if n == 0 || period < 16 {
+        return out;
+    }
+
+    for i in 0..n {
+        if i + 1 < period {
+            continue;
+        }
+        let start = i + 1 - period;
+        out[i] = hurst_of_window(&close[start..=i]);
+    }
+
+    out
+}
+
+fn hurst_of_window(window: &[f64]) -> f64 {
+    let period = window.len();
+    let mut log_sizes = Vec::new();
+    let mut log_rs = Vec::new();
+
+    let mut size = period;
+    while size >= 8 {
+        let num_chunks = period / size;
+        let mut rs_sum = 0.0;
+        let mut rs_count = 0usize;
+        for chunk in window[..num_chunks * size].chunks(size) {
+            if let Some(rs) = rescaled_range(chunk) {
+                rs_sum += rs;
+                rs_count += 1;
+            }
+        }
+        if rs_count > 0 {
+            let avg_rs = rs_sum / rs_count as f64;
+            if avg_rs > 0.0 {
+                log_sizes.push((size as f64).ln());
+                log_rs.push(avg_rs.ln());
+            }
+        }
+        size /= 2;
+    }
+
+    if log_sizes.len() < 2 {
+        return f64::NAN;
+    }
+
+    let n_pts = log_sizes.len() as f64;
+    let sum_x: f64 = log_sizes.iter().sum();
+    let sum_y: f64 = log_rs.iter().sum();
+    let sum_xy: f64 = log_sizes.iter().zip(&log_rs).map(|(x, y)| x * y).sum();
+    let sum_x2: f64 = log_sizes.iter().map(|x| x * x).sum();
+    let denom = n_pts * sum_x2 - sum_x * sum_x;
+    if denom == 0.0 {
+        return f64::NAN;
+    }
+    (n_pts * sum_xy - sum_x * sum_y) / denom
+}
+
+fn rescaled_range(chunk: &[f64]) -> Option<f64> {
+    let m = chunk.len();
+    if m < 2 {
+        return None;
+    }
+    let mean = chunk.iter().sum::<f64>() / m as f64;
+
+    let mut cum = 0.0;
+    let mut max_cum = f64::MIN;
+    let mut min_cum = f64::MAX;
+    for &v in chunk {
+        cum += v - mean;
+        max_cum = max_cum.max(cum);
+        min_cum = min_cum.min(cum);
+    }
+    let range = max_cum - min_cum;
+
+    let variance = chunk.iter().map(|&v| (v - mean).powi(2)).sum::<f64>() / m as f64;
+    let std_dev = variance.sqrt();
+    if std_dev == 0.0 {
+        return None;
+    }
+    Some(range / std_dev)
+}
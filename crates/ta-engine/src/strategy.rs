@@ -0,0 +1,148 @@
+//! Signal/risk-exit layer on top of the raw indicator and event functions:
+//! an [`StrategyPlan`] binds an already-evaluated entry rule (e.g. the
+//! boolean series `core::events::crossup` produces for `ema(fast)` crossing
+//! `ema(slow)`) to a take-profit and a stop-loss, and [`run_strategy`] walks
+//! the bars turning that into a per-bar `{enter_long, exit, pnl}` signal
+//! plus a trade summary, so a rule set can be validated without wiring up
+//! an external backtester.
+
+/// How far a take-profit or stop-loss sits from the entry price. The sign
+/// carries the direction: a take-profit above entry uses a positive value,
+/// a stop-loss below entry uses a negative one (e.g. `PercentOfEntry(0.02)`
+/// for a 2% target, `PercentOfEntry(-0.01)` for a 1% stop).
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExitLevel {
+    /// A fixed percentage of the entry price.
+    PercentOfEntry(f64),
+    /// A multiple of the ATR reading at entry.
+    AtrMultiple(f64),
+    /// A dynamic trailing level, one value per bar, computed upstream (e.g.
+    /// from `trend::supertrend` or `trend::psar`). The position exits the
+    /// bar its low/high first crosses the level at that bar's index.
+    Trailing(Vec<f64>),
+}
+
+/// An entry rule plus risk exits, ready to be walked by [`run_strategy`].
+#[derive(Debug, Clone)]
+pub struct StrategyPlan {
+    /// Bars where a new long is opened, e.g. `core::events::crossup(&fast, &slow)`.
+    pub entry_long: Vec<bool>,
+    pub take_profit: ExitLevel,
+    pub stop_loss: ExitLevel,
+}
+
+/// One bar's signal output: `enter_long`/`exit` mark position transitions,
+/// `pnl` is the realized return (as a fraction of entry price) on the bar a
+/// position closes, and `0.0` otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct BarSignal {
+    pub enter_long: bool,
+    pub exit: bool,
+    pub pnl: f64,
+}
+
+/// Aggregate stats across every trade `run_strategy` closed.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct StrategySummary {
+    pub trade_count: usize,
+    pub win_rate: f64,
+    pub max_drawdown: f64,
+}
+
+/// Walks `high`/`low`/`close` bar by bar applying `plan`: on an `entry_long`
+/// bar with no open position, enters at `close[i]` and fixes the
+/// take-profit/stop-loss levels for that trade; on later bars, exits at
+/// whichever level the bar's high/low reaches first (stop-loss checked
+/// first on a bar that touches both, matching the conservative assumption
+/// a real fill could have gone against the position). `atr_period` sizes
+/// any [`ExitLevel::AtrMultiple`] leg from `volatility::atr`.
+pub fn run_strategy(
+    plan: &StrategyPlan,
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    atr_period: usize,
+) -> (Vec<BarSignal>, StrategySummary) {
+    let n = close.len();
+    let mut signals = vec![BarSignal::default(); n];
+    if n == 0 || high.len() != n || low.len() != n || plan.entry_long.len() != n {
+        return (signals, StrategySummary::default());
+    }
+
+    let atr = crate::volatility::atr(high, low, close, atr_period);
+
+    let mut entry_price: Option<f64> = None;
+    let mut entry_atr = f64::NAN;
+
+    let mut equity = 1.0_f64;
+    let mut peak_equity = 1.0_f64;
+    let mut max_drawdown = 0.0_f64;
+    let mut wins = 0usize;
+    let mut trade_count = 0usize;
+
+    for i in 0..n {
+        if let Some(price) = entry_price {
+            let stop_level = exit_level_value(&plan.stop_loss, price, entry_atr, i);
+            let target_level = exit_level_value(&plan.take_profit, price, entry_atr, i);
+            let hit_stop = stop_level.is_some_and(|level| low[i] <= level);
+            let hit_target = target_level.is_some_and(|level| high[i] >= level);
+
+            if hit_stop || hit_target {
+                let exit_price = if hit_stop { stop_level } else { target_level }.unwrap_or(f64::NAN);
+                let pnl = (exit_price - price) / price;
+
+                signals[i].exit = true;
+                signals[i].pnl = pnl;
+
+                equity *= 1.0 + pnl;
+                peak_equity = peak_equity.max(equity);
+                max_drawdown = max_drawdown.max((peak_equity - equity) / peak_equity);
+
+                trade_count += 1;
+                if pnl > 0.0 {
+                    wins += 1;
+                }
+                entry_price = None;
+                continue;
+            }
+        }
+
+        if entry_price.is_none() && plan.entry_long[i] {
+            let price = close[i];
+            entry_price = Some(price);
+            entry_atr = atr[i];
+            signals[i].enter_long = true;
+        }
+    }
+
+    let win_rate = if trade_count == 0 {
+        0.0
+    } else {
+        wins as f64 / trade_count as f64
+    };
+
+    (
+        signals,
+        StrategySummary {
+            trade_count,
+            win_rate,
+            max_drawdown,
+        },
+    )
+}
+
+/// Resolves an [`ExitLevel`] to an absolute price at bar `i`, given the
+/// position's `entry_price` and the ATR reading at entry.
+fn exit_level_value(level: &ExitLevel, entry_price: f64, entry_atr: f64, bar_index: usize) -> Option<f64> {
+    match level {
+        ExitLevel::PercentOfEntry(pct) => Some(entry_price * (1.0 + pct)),
+        ExitLevel::AtrMultiple(mult) => {
+            if entry_atr.is_nan() {
+                None
+            } else {
+                Some(entry_price + entry_atr * mult)
+            }
+        }
+        ExitLevel::Trailing(levels) => levels.get(bar_index).copied().filter(|v| !v.is_nan()),
+    }
+}
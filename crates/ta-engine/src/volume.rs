@@ -96,6 +96,82 @@ pub fn cmf(high: &[f64], low: &[f64], close: &[f64], volume: &[f64], period: usi
     out
 }
 
+/// Accumulation/Distribution line: a running sum of volume weighted by the
+/// close location within the bar's high-low range, guarding the
+/// `high == low` case by contributing zero for that bar.
+pub fn ad_line(high: &[f64], low: &[f64], close: &[f64], volume: &[f64]) -> Vec<f64> {
+    let n = close.len();
+    let mut out = vec![f64::NAN; n];
+    if n == 0 || high.len() != n || low.len() != n || volume.len() != n {
+        return out;
+    }
+
+    let mut ad = 0.0;
+    for i in 0..n {
+        let hl = high[i] - low[i];
+        let contribution = if hl == 0.0 {
+            0.0
+        } else {
+            ((close[i] - low[i]) - (high[i] - close[i])) / hl * volume[i]
+        };
+        ad += contribution;
+        out[i] = ad;
+    }
+    out
+}
+
+/// Chaikin Oscillator: `EMA(AD, fast_period) - EMA(AD, slow_period)` over
+/// the [`ad_line`].
+pub fn chaikin_oscillator(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    volume: &[f64],
+    fast_period: usize,
+    slow_period: usize,
+) -> Vec<f64> {
+    let ad = ad_line(high, low, close, volume);
+    let fast = crate::moving_averages::ema(&ad, fast_period);
+    let slow = crate::moving_averages::ema(&ad, slow_period);
+
+    let n = ad.len();
+    let mut out = vec![f64::NAN; n];
+    for i in 0..n {
+        if !fast[i].is_nan() && !slow[i].is_nan() {
+            out[i] = fast[i] - slow[i];
+        }
+    }
+    out
+}
+
+/// Volume-weighted moving average: `sum(close*volume)/sum(volume)` over the
+/// trailing `period` window, falling back to a plain SMA of `close` when a
+/// window's volume sums to zero.
+pub fn vwma(close: &[f64], volume: &[f64], period: usize) -> Vec<f64> {
+    let n = close.len();
+    let mut out = vec![f64::NAN; n];
+    if period == 0 || n == 0 || volume.len() != n {
+        return out;
+    }
+
+    for i in (period - 1)..n {
+        let window_close = &close[i + 1 - period..=i];
+        let window_vol = &volume[i + 1 - period..=i];
+        let sum_vol: f64 = window_vol.iter().sum();
+        out[i] = if sum_vol == 0.0 {
+            window_close.iter().sum::<f64>() / period as f64
+        } else {
+            window_close
+                .iter()
+                .zip(window_vol.iter())
+                .map(|(c, v)| c * v)
+                .sum::<f64>()
+                / sum_vol
+        };
+    }
+    out
+}
+
 pub fn vwap(high: &[f64], low: &[f64], close: &[f64], volume: &[f64]) -> Vec<f64> {
     let n = close.len();
     let mut out = vec![f64::NAN; n];
@@ -143,3 +219,79 @@ pub fn klinger(
     let signal = crate::moving_averages::ema(&klinger_line, signal_period);
     (klinger_line, signal)
 }
+
+/// Anchored VWAP with volume-weighted standard-deviation bands.
+///
+/// `sum_pv`/`sum_vol` reset to zero at every index in `anchors`; when
+/// `anchors` is empty, a session boundary is derived from `timestamps`
+/// instead, resetting whenever a bar falls into a different
+/// `session_seconds`-sized bucket than the previous bar (e.g. `86_400` for
+/// a daily session). Passing `anchors` empty and `session_seconds <= 0`
+/// reproduces plain cumulative `vwap`. `std_dev` is the band multiplier
+/// applied to the volume-weighted standard deviation of typical price
+/// around the running VWAP within the current anchored segment.
+pub fn vwap_anchored(
+    timestamps: &[i64],
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    volume: &[f64],
+    anchors: &[usize],
+    session_seconds: i64,
+    std_dev: f64,
+) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let n = close.len();
+    let mut vwap_out = vec![f64::NAN; n];
+    let mut upper = vec![f64::NAN; n];
+    let mut lower = vec![f64::NAN; n];
+    if n == 0
+        || timestamps.len() != n
+        || high.len() != n
+        || low.len() != n
+        || volume.len() != n
+    {
+        return (vwap_out, upper, lower);
+    }
+
+    let anchor_set: std::collections::BTreeSet<usize> = anchors.iter().copied().collect();
+
+    let mut sum_pv = 0.0;
+    let mut sum_vol = 0.0;
+    let mut sum_vol_tp2 = 0.0;
+    let mut session_bucket: Option<i64> = None;
+
+    for i in 0..n {
+        let bucket = (session_seconds > 0).then(|| timestamps[i].div_euclid(session_seconds));
+        let session_reset = anchor_set.is_empty()
+            && bucket.is_some()
+            && session_bucket.is_some()
+            && session_bucket != bucket;
+
+        if anchor_set.contains(&i) || session_reset {
+            sum_pv = 0.0;
+            sum_vol = 0.0;
+            sum_vol_tp2 = 0.0;
+        }
+        session_bucket = bucket;
+
+        let tp = (high[i] + low[i] + close[i]) / 3.0;
+        sum_pv += tp * volume[i];
+        sum_vol += volume[i];
+        sum_vol_tp2 += volume[i] * tp * tp;
+
+        if sum_vol > 0.0 {
+            let mean = sum_pv / sum_vol;
+            let variance = (sum_vol_tp2 / sum_vol - mean * mean).max(0.0);
+            let band = std_dev * variance.sqrt();
+            vwap_out[i] = mean;
+            upper[i] = mean + band;
+            lower[i] = mean - band;
+        } else {
+            vwap_out[i] = tp;
+            upper[i] = tp;
+            lower[i] = tp;
+        }
+    }
+
+    (vwap_out, upper, lower)
+}
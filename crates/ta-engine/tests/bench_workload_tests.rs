@@ -0,0 +1,141 @@
+use ta_engine::bench::{
+    diff_bench_reports, encode_bench_report_to_json, parse_bench_workload, run_bench_workload,
+    BenchReport, BenchWorkloadError, NodeLatencyStats,
+};
+use ta_engine::incremental::kernel_registry::KernelId;
+
+fn valid_workload_json() -> serde_json::Value {
+    serde_json::json!({
+        "symbol": "BTCUSDT",
+        "timeframe": "1m",
+        "source": "ohlcv",
+        "row_count": 200,
+        "seed": 7,
+        "requests": [
+            {
+                "node_id": 1,
+                "kernel_id": "rsi",
+                "input_field": "close",
+                "kwargs": {"period": 14.0}
+            },
+            {
+                "node_id": 2,
+                "kernel_id": "obv",
+                "input_field": "close",
+                "kwargs": {}
+            }
+        ]
+    })
+}
+
+#[test]
+fn parse_bench_workload_accepts_a_valid_workload() {
+    let workload = parse_bench_workload(&valid_workload_json()).expect("workload should parse");
+    assert_eq!(workload.partition_key.symbol, "BTCUSDT");
+    assert_eq!(workload.row_count, 200);
+    assert_eq!(workload.seed, 7);
+    assert_eq!(workload.requests.len(), 2);
+    assert_eq!(workload.requests[0].kernel_id, KernelId::Rsi);
+}
+
+#[test]
+fn parse_bench_workload_rejects_a_missing_field() {
+    let mut json = valid_workload_json();
+    json.as_object_mut().unwrap().remove("row_count");
+    let err = parse_bench_workload(&json).expect_err("workload should fail");
+    assert_eq!(err, BenchWorkloadError::MissingField("row_count"));
+}
+
+#[test]
+fn parse_bench_workload_rejects_an_unsupported_kernel_id() {
+    let mut json = valid_workload_json();
+    json["requests"][0]["kernel_id"] = serde_json::json!("not_a_kernel");
+    let err = parse_bench_workload(&json).expect_err("workload should fail");
+    assert_eq!(
+        err,
+        BenchWorkloadError::UnsupportedKernelId("not_a_kernel".to_string())
+    );
+}
+
+#[test]
+fn run_bench_workload_is_deterministic_and_covers_every_row() {
+    let workload = parse_bench_workload(&valid_workload_json()).expect("workload should parse");
+
+    let first = run_bench_workload(&workload);
+    let second = run_bench_workload(&workload);
+
+    assert_eq!(first.row_count, 200);
+    assert_eq!(first.node_stats.len(), 2);
+    for stats in &first.node_stats {
+        assert_eq!(stats.samples, 200);
+        assert!(stats.p50_nanos <= stats.p90_nanos);
+        assert!(stats.p90_nanos <= stats.p99_nanos);
+        assert!(stats.p99_nanos <= stats.max_nanos);
+    }
+
+    // Synthetic OHLCV generation is seeded, so the same workload always
+    // steps the exact same series -- run-to-run variance should only show up
+    // in the timings, never in which rows were produced or how many.
+    assert_eq!(first.row_count, second.row_count);
+    assert_eq!(first.node_stats.len(), second.node_stats.len());
+}
+
+fn report_with_p99(node_id: u32, p99_nanos: u64) -> BenchReport {
+    BenchReport {
+        row_count: 10,
+        wall_nanos: 1_000,
+        ticks_per_sec: 10_000.0,
+        node_stats: vec![NodeLatencyStats {
+            node_id,
+            kernel_id: KernelId::Rsi,
+            samples: 10,
+            p50_nanos: p99_nanos / 2,
+            p90_nanos: p99_nanos,
+            p99_nanos,
+            max_nanos: p99_nanos,
+        }],
+    }
+}
+
+#[test]
+fn diff_bench_reports_flags_a_node_whose_p99_regressed() {
+    let baseline = report_with_p99(1, 1_000);
+    let current = report_with_p99(1, 2_000);
+
+    let diff = diff_bench_reports(&baseline, &current);
+
+    assert_eq!(diff.node_diffs.len(), 1);
+    assert!(diff.node_diffs[0].regressed);
+}
+
+#[test]
+fn diff_bench_reports_does_not_flag_ordinary_run_to_run_noise() {
+    let baseline = report_with_p99(1, 1_000);
+    let current = report_with_p99(1, 1_050);
+
+    let diff = diff_bench_reports(&baseline, &current);
+
+    assert_eq!(diff.node_diffs.len(), 1);
+    assert!(!diff.node_diffs[0].regressed);
+}
+
+#[test]
+fn diff_bench_reports_skips_nodes_missing_from_the_baseline() {
+    let baseline = report_with_p99(1, 1_000);
+    let current = report_with_p99(2, 1_000);
+
+    let diff = diff_bench_reports(&baseline, &current);
+
+    assert!(diff.node_diffs.is_empty());
+}
+
+#[test]
+fn encode_bench_report_to_json_includes_every_node() {
+    let workload = parse_bench_workload(&valid_workload_json()).expect("workload should parse");
+    let report = run_bench_workload(&workload);
+
+    let json = encode_bench_report_to_json(&report);
+
+    assert_eq!(json["row_count"], serde_json::json!(200));
+    assert_eq!(json["node_stats"].as_array().unwrap().len(), 2);
+}
@@ -0,0 +1,210 @@
+use ta_engine::metadata::{
+    indicator_catalog, validate_catalog, validate_indicators, IndicatorMeta, IndicatorOutputMeta,
+    IndicatorPaneHint, IndicatorScaleGroup, IndicatorSemanticsMeta, IndicatorVisualMeta,
+    OutputVisualMeta, OutputVisualPrimitive, Severity, StyleDefaultMeta, StyleSlotMeta,
+    StyleSlotType,
+};
+
+fn base_meta() -> IndicatorMeta {
+    IndicatorMeta {
+        id: "fixture",
+        display_name: "Fixture Indicator",
+        category: "trend",
+        aliases: &[],
+        param_aliases: &[],
+        params: &[],
+        outputs: &[
+            IndicatorOutputMeta {
+                name: "a",
+                kind: "line",
+                description: "a",
+            },
+            IndicatorOutputMeta {
+                name: "b",
+                kind: "line",
+                description: "b",
+            },
+        ],
+        semantics: IndicatorSemanticsMeta {
+            required_fields: &["close"],
+            optional_fields: &[],
+            lookback_params: &[],
+            default_lookback: None,
+            warmup_policy: "none",
+            source_param: None,
+        },
+        visual: IndicatorVisualMeta {
+            pane_hint: IndicatorPaneHint::SeparatePane,
+            scale_group: IndicatorScaleGroup::Oscillator,
+            output_visuals: &[],
+            style_slots: &[],
+        },
+        runtime_binding: "fixture",
+        constraints: &[],
+    }
+}
+
+const STROKE_SLOT: StyleSlotMeta = StyleSlotMeta {
+    slot: "stroke_a",
+    kind: StyleSlotType::Stroke,
+    token: None,
+    default: StyleDefaultMeta {
+        color: "#38bdf8",
+        width: Some(1.0),
+        opacity: None,
+        pattern: None,
+    },
+};
+
+const FILL_SLOT: StyleSlotMeta = StyleSlotMeta {
+    slot: "fill_a",
+    kind: StyleSlotType::Fill,
+    token: None,
+    default: StyleDefaultMeta {
+        color: "#38bdf8",
+        width: None,
+        opacity: Some(0.2),
+        pattern: None,
+    },
+};
+
+#[test]
+fn the_real_catalog_has_no_consistency_findings() {
+    let diagnostics = validate_catalog();
+    assert!(
+        diagnostics.is_empty(),
+        "expected a clean catalog, found: {}",
+        ta_engine::metadata::render_diagnostics(&diagnostics)
+    );
+}
+
+#[test]
+fn flags_an_output_pointing_at_an_undeclared_style_slot() {
+    let mut meta = base_meta();
+    meta.visual.output_visuals = &[OutputVisualMeta {
+        output: "a",
+        primitive: OutputVisualPrimitive::Line,
+        style_slot: "missing",
+        z_index: 30,
+    }];
+    let diagnostics = validate_indicators(std::slice::from_ref(&meta));
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+}
+
+#[test]
+fn flags_a_compound_output_referencing_an_unknown_output_name() {
+    let mut meta = base_meta();
+    meta.visual.style_slots = &[FILL_SLOT];
+    meta.visual.output_visuals = &[OutputVisualMeta {
+        output: "a|missing",
+        primitive: OutputVisualPrimitive::BandFill,
+        style_slot: "fill_a",
+        z_index: 20,
+    }];
+    let diagnostics = validate_indicators(std::slice::from_ref(&meta));
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+}
+
+#[test]
+fn flags_a_band_fill_drawn_through_a_stroke_slot() {
+    let mut meta = base_meta();
+    meta.visual.style_slots = &[STROKE_SLOT];
+    meta.visual.output_visuals = &[OutputVisualMeta {
+        output: "a|b",
+        primitive: OutputVisualPrimitive::BandFill,
+        style_slot: "stroke_a",
+        z_index: 20,
+    }];
+    let diagnostics = validate_indicators(std::slice::from_ref(&meta));
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Severity::Warning);
+}
+
+#[test]
+fn allows_histogram_through_either_a_stroke_or_a_fill_slot() {
+    let mut meta = base_meta();
+    meta.visual.style_slots = &[STROKE_SLOT, FILL_SLOT];
+    meta.visual.output_visuals = &[
+        OutputVisualMeta {
+            output: "a",
+            primitive: OutputVisualPrimitive::Histogram,
+            style_slot: "stroke_a",
+            z_index: 20,
+        },
+        OutputVisualMeta {
+            output: "b",
+            primitive: OutputVisualPrimitive::Histogram,
+            style_slot: "fill_a",
+            z_index: 21,
+        },
+    ];
+    let diagnostics = validate_indicators(std::slice::from_ref(&meta));
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn flags_two_differently_styled_outputs_sharing_a_z_index() {
+    let mut meta = base_meta();
+    meta.visual.style_slots = &[STROKE_SLOT, FILL_SLOT];
+    meta.visual.output_visuals = &[
+        OutputVisualMeta {
+            output: "a",
+            primitive: OutputVisualPrimitive::Line,
+            style_slot: "stroke_a",
+            z_index: 30,
+        },
+        OutputVisualMeta {
+            output: "b",
+            primitive: OutputVisualPrimitive::Histogram,
+            style_slot: "fill_a",
+            z_index: 30,
+        },
+    ];
+    let diagnostics = validate_indicators(std::slice::from_ref(&meta));
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Severity::Warning);
+}
+
+#[test]
+fn allows_a_shared_z_index_when_both_outputs_use_the_same_style_slot() {
+    let mut meta = base_meta();
+    meta.visual.style_slots = &[STROKE_SLOT];
+    meta.visual.output_visuals = &[
+        OutputVisualMeta {
+            output: "a",
+            primitive: OutputVisualPrimitive::SignalFlag,
+            style_slot: "stroke_a",
+            z_index: 50,
+        },
+        OutputVisualMeta {
+            output: "b",
+            primitive: OutputVisualPrimitive::SignalFlag,
+            style_slot: "stroke_a",
+            z_index: 50,
+        },
+    ];
+    let diagnostics = validate_indicators(std::slice::from_ref(&meta));
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn render_diagnostics_includes_the_indicator_id_and_message() {
+    let mut meta = base_meta();
+    meta.visual.output_visuals = &[OutputVisualMeta {
+        output: "a",
+        primitive: OutputVisualPrimitive::Line,
+        style_slot: "missing",
+        z_index: 30,
+    }];
+    let diagnostics = validate_indicators(std::slice::from_ref(&meta));
+    let report = ta_engine::metadata::render_diagnostics(&diagnostics);
+    assert!(report.contains("fixture"));
+    assert!(report.contains("missing"));
+}
+
+#[test]
+fn the_catalog_is_non_empty_sanity_check() {
+    assert!(!indicator_catalog().is_empty());
+}
@@ -0,0 +1,29 @@
+#![cfg(feature = "serde")]
+
+use ta_engine::metadata::{catalog_json_schema, catalog_to_json, indicator_catalog};
+
+#[test]
+fn exports_one_json_entry_per_catalog_indicator() {
+    let exported = catalog_to_json();
+    let array = exported.as_array().expect("catalog exports as a JSON array");
+    assert_eq!(array.len(), indicator_catalog().len());
+}
+
+#[test]
+fn schema_has_one_definition_per_catalog_indicator() {
+    let schema = catalog_json_schema();
+    let definitions = schema["definitions"]
+        .as_object()
+        .expect("schema has a definitions object");
+    assert_eq!(definitions.len(), indicator_catalog().len());
+}
+
+#[test]
+fn schema_marks_required_params_and_carries_bounds() {
+    let schema = catalog_json_schema();
+    let rsi = &schema["definitions"]["rsi"];
+    let period = &rsi["properties"]["period"];
+    assert_eq!(period["type"], "integer");
+    assert_eq!(period["default"], 14);
+    assert_eq!(period["minimum"], 1.0);
+}
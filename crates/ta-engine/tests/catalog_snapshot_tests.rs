@@ -0,0 +1,54 @@
+#![cfg(feature = "serde")]
+
+use ta_engine::metadata::{catalog_snapshot, indicator_catalog, CATALOG_SCHEMA_VERSION};
+
+fn fixture_path() -> std::path::PathBuf {
+    std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/catalog_snapshot.json")
+}
+
+#[test]
+fn envelope_carries_a_schema_version_and_every_catalog_indicator() {
+    let snapshot = catalog_snapshot();
+    assert_eq!(snapshot["schema_version"], CATALOG_SCHEMA_VERSION);
+    assert_eq!(
+        snapshot["indicators"].as_array().unwrap().len(),
+        indicator_catalog().len()
+    );
+}
+
+#[test]
+fn enums_serialize_to_stable_string_tags_not_discriminants() {
+    let snapshot = catalog_snapshot();
+    let indicators = snapshot["indicators"].as_array().unwrap();
+    let rsi = indicators
+        .iter()
+        .find(|meta| meta["id"] == "rsi")
+        .expect("rsi is in the catalog");
+    assert_eq!(rsi["visual"]["pane_hint"], "SeparatePane");
+    assert_eq!(rsi["visual"]["scale_group"], "Oscillator");
+    assert_eq!(rsi["visual"]["output_visuals"][0]["primitive"], "Line");
+    assert_eq!(rsi["visual"]["style_slots"][0]["kind"], "Stroke");
+}
+
+/// Golden snapshot: the whole serialized catalog, checked against
+/// `tests/fixtures/catalog_snapshot.json` so any accidental change to a
+/// pane hint, z-index ordering, or default style shows up as a reviewable
+/// diff instead of silently shipping to frontend renderers. If the
+/// fixture doesn't exist yet (a fresh checkout of this test), this run
+/// writes it as the new baseline for the PR to review.
+#[test]
+fn catalog_snapshot_matches_the_checked_in_fixture() {
+    let actual = serde_json::to_string_pretty(&catalog_snapshot()).unwrap();
+    let path = fixture_path();
+
+    match std::fs::read_to_string(&path) {
+        Ok(expected) => assert_eq!(
+            actual, expected,
+            "serialized catalog changed -- if intentional, delete {path:?} and re-run to accept the new snapshot"
+        ),
+        Err(_) => {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, &actual).expect("write initial catalog snapshot fixture");
+        }
+    }
+}
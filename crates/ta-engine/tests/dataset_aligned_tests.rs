@@ -0,0 +1,120 @@
+use ta_engine::dataset::{self, DatasetPartitionKey};
+use ta_engine::FillPolicy;
+
+fn key() -> DatasetPartitionKey {
+    DatasetPartitionKey {
+        symbol: "BTCUSDT".to_string(),
+        timeframe: "1m".to_string(),
+        source: "ohlcv".to_string(),
+    }
+}
+
+#[test]
+fn aligned_read_merges_close_and_a_sparser_series_forward_filling_gaps() {
+    let id = dataset::create_dataset();
+    let partition_key = key();
+    dataset::append_ohlcv(
+        id,
+        partition_key.clone(),
+        &[0, 60_000, 120_000, 180_000],
+        &[10.0, 11.0, 12.0, 13.0],
+        &[10.0, 11.0, 12.0, 13.0],
+        &[10.0, 11.0, 12.0, 13.0],
+        &[10.0, 11.0, 12.0, 13.0],
+        &[1.0, 1.0, 1.0, 1.0],
+    )
+    .unwrap();
+    // "rsi" only has samples at t=0 and t=120_000 -- sparser than close.
+    dataset::append_series(
+        id,
+        partition_key.clone(),
+        "rsi".to_string(),
+        &[0, 120_000],
+        &[30.0, 50.0],
+    )
+    .unwrap();
+
+    let aligned =
+        dataset::get_aligned(id, &partition_key, &["close", "rsi"], FillPolicy::Ffill).unwrap();
+
+    assert_eq!(aligned.timestamps, vec![0, 60_000, 120_000, 180_000]);
+    assert_eq!(aligned.columns[0], vec![10.0, 11.0, 12.0, 13.0]);
+    assert_eq!(aligned.columns[1], vec![30.0, 30.0, 50.0, 50.0]);
+}
+
+#[test]
+fn aligned_read_with_nan_policy_leaves_gaps_unfilled() {
+    let id = dataset::create_dataset();
+    let partition_key = key();
+    dataset::append_series(
+        id,
+        partition_key.clone(),
+        "a".to_string(),
+        &[0, 60_000],
+        &[1.0, 2.0],
+    )
+    .unwrap();
+    dataset::append_series(
+        id,
+        partition_key.clone(),
+        "b".to_string(),
+        &[60_000],
+        &[9.0],
+    )
+    .unwrap();
+
+    let aligned =
+        dataset::get_aligned(id, &partition_key, &["a", "b"], FillPolicy::Nan).unwrap();
+
+    assert_eq!(aligned.timestamps, vec![0, 60_000]);
+    assert_eq!(aligned.columns[0], vec![1.0, 2.0]);
+    assert!(aligned.columns[1][0].is_nan());
+    assert_eq!(aligned.columns[1][1], 9.0);
+}
+
+#[test]
+fn aligned_read_with_zero_policy_fills_gaps_with_zero() {
+    let id = dataset::create_dataset();
+    let partition_key = key();
+    dataset::append_series(
+        id,
+        partition_key.clone(),
+        "a".to_string(),
+        &[0, 60_000],
+        &[1.0, 2.0],
+    )
+    .unwrap();
+    dataset::append_series(
+        id,
+        partition_key.clone(),
+        "b".to_string(),
+        &[60_000],
+        &[9.0],
+    )
+    .unwrap();
+
+    let aligned =
+        dataset::get_aligned(id, &partition_key, &["a", "b"], FillPolicy::Zero).unwrap();
+
+    assert_eq!(aligned.columns[1], vec![0.0, 9.0]);
+}
+
+#[test]
+fn aligned_read_on_missing_partition_returns_empty_columns() {
+    let id = dataset::create_dataset();
+    let aligned = dataset::get_aligned(id, &key(), &["close", "rsi"], FillPolicy::Ffill).unwrap();
+
+    assert!(aligned.timestamps.is_empty());
+    assert_eq!(aligned.columns.len(), 2);
+    assert!(aligned.columns[0].is_empty());
+    assert!(aligned.columns[1].is_empty());
+}
+
+#[test]
+fn aligned_read_on_unknown_dataset_id_is_rejected() {
+    let err = dataset::get_aligned(u64::MAX, &key(), &["close"], FillPolicy::Ffill).unwrap_err();
+    assert!(matches!(
+        err,
+        ta_engine::dataset::DatasetRegistryError::UnknownDatasetId(_)
+    ));
+}
@@ -1,5 +1,6 @@
 use ta_engine::dataset::{
-    create_dataset, dataset_exists, drop_dataset, get_dataset, DatasetRegistryError,
+    create_dataset, dataset_exists, drop_dataset, get_dataset, DatasetPartitionKey,
+    DatasetRegistry, DatasetRegistryError,
 };
 
 #[test]
@@ -38,3 +39,50 @@ fn dataset_ids_are_monotonic() {
     drop_dataset(first).expect("drop first should succeed");
     drop_dataset(second).expect("drop second should succeed");
 }
+
+#[test]
+fn scoped_registry_is_isolated_from_the_default_registry() {
+    let scoped = DatasetRegistry::new();
+    let scoped_id = scoped.create_dataset();
+
+    // Ids are only unique within the registry that issued them, so the
+    // default (process-wide) registry has no knowledge of this id even if
+    // it happens to collide with one of its own.
+    assert!(scoped.dataset_exists(scoped_id));
+    assert!(!dataset_exists(scoped_id));
+
+    let default_id = create_dataset();
+    assert!(dataset_exists(default_id));
+    assert!(!scoped.dataset_exists(default_id));
+
+    drop_dataset(default_id).expect("drop should succeed");
+}
+
+#[test]
+fn scoped_registry_supports_the_same_append_and_lookup_operations_as_the_default() {
+    let scoped = DatasetRegistry::new();
+    let id = scoped.create_dataset();
+    let key = DatasetPartitionKey {
+        symbol: "BTCUSDT".to_string(),
+        timeframe: "1m".to_string(),
+        source: "ohlcv".to_string(),
+    };
+
+    let rows = scoped
+        .append_ohlcv(
+            id,
+            key,
+            &[0, 60_000],
+            &[10.0, 11.0],
+            &[10.0, 11.0],
+            &[10.0, 11.0],
+            &[10.0, 11.0],
+            &[1.0, 1.0],
+        )
+        .expect("ohlcv append should succeed");
+    assert_eq!(rows, 2);
+
+    let info = scoped.dataset_info(id).expect("dataset should exist");
+    assert_eq!(info.partition_count, 1);
+    assert_eq!(info.ohlcv_row_count, 2);
+}
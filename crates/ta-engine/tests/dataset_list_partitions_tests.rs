@@ -0,0 +1,177 @@
+use ta_engine::dataset::{
+    self, DatasetPartitionKey, DatasetRegistryError,
+};
+
+fn key(symbol: &str, timeframe: &str, source: &str) -> DatasetPartitionKey {
+    DatasetPartitionKey {
+        symbol: symbol.to_string(),
+        timeframe: timeframe.to_string(),
+        source: source.to_string(),
+    }
+}
+
+fn seed_dataset(keys: &[DatasetPartitionKey]) -> u64 {
+    let id = dataset::create_dataset();
+    for partition_key in keys {
+        dataset::append_series(
+            id,
+            partition_key.clone(),
+            "close".to_string(),
+            &[1, 2],
+            &[1.0, 2.0],
+        )
+        .unwrap();
+    }
+    id
+}
+
+#[test]
+fn no_filters_return_every_partition_in_order() {
+    let id = seed_dataset(&[
+        key("ETHUSDT", "1m", "ohlcv"),
+        key("BTCUSDT", "5m", "ohlcv"),
+        key("BTCUSDT", "1m", "ohlcv"),
+    ]);
+
+    let keys = dataset::list_partitions(id, None, None).unwrap();
+
+    assert_eq!(
+        keys,
+        vec![
+            key("BTCUSDT", "1m", "ohlcv"),
+            key("BTCUSDT", "5m", "ohlcv"),
+            key("ETHUSDT", "1m", "ohlcv"),
+        ]
+    );
+}
+
+#[test]
+fn symbol_filter_returns_a_contiguous_prefix_across_timeframes_and_sources() {
+    let id = seed_dataset(&[
+        key("BTCUSDT", "1m", "ohlcv"),
+        key("BTCUSDT", "5m", "ohlcv"),
+        key("BTCUSDT", "5m", "spot"),
+        key("ETHUSDT", "1m", "ohlcv"),
+    ]);
+
+    let keys = dataset::list_partitions(id, Some("BTCUSDT"), None).unwrap();
+
+    assert_eq!(
+        keys,
+        vec![
+            key("BTCUSDT", "1m", "ohlcv"),
+            key("BTCUSDT", "5m", "ohlcv"),
+            key("BTCUSDT", "5m", "spot"),
+        ]
+    );
+}
+
+#[test]
+fn symbol_and_timeframe_filter_narrows_to_that_pair_across_sources() {
+    let id = seed_dataset(&[
+        key("BTCUSDT", "1m", "ohlcv"),
+        key("BTCUSDT", "1m", "spot"),
+        key("BTCUSDT", "5m", "ohlcv"),
+    ]);
+
+    let keys = dataset::list_partitions(id, Some("BTCUSDT"), Some("1m")).unwrap();
+
+    assert_eq!(
+        keys,
+        vec![key("BTCUSDT", "1m", "ohlcv"), key("BTCUSDT", "1m", "spot")]
+    );
+}
+
+#[test]
+fn no_matching_partitions_returns_an_empty_list() {
+    let id = seed_dataset(&[key("BTCUSDT", "1m", "ohlcv")]);
+
+    let keys = dataset::list_partitions(id, Some("SOLUSDT"), None).unwrap();
+
+    assert!(keys.is_empty());
+}
+
+#[test]
+fn timeframe_without_symbol_is_rejected() {
+    let id = seed_dataset(&[key("BTCUSDT", "1m", "ohlcv")]);
+
+    let err = dataset::list_partitions(id, None, Some("1m")).unwrap_err();
+
+    assert!(matches!(
+        err,
+        DatasetRegistryError::InvalidPartitionFilter { .. }
+    ));
+}
+
+#[test]
+fn unknown_dataset_id_is_rejected() {
+    let err = dataset::list_partitions(u64::MAX, None, None).unwrap_err();
+
+    assert!(matches!(err, DatasetRegistryError::UnknownDatasetId(_)));
+}
+
+#[test]
+fn count_partitions_matches_the_number_of_seeded_keys() {
+    let id = seed_dataset(&[
+        key("BTCUSDT", "1m", "ohlcv"),
+        key("BTCUSDT", "5m", "ohlcv"),
+        key("ETHUSDT", "1m", "ohlcv"),
+    ]);
+
+    assert_eq!(dataset::count_partitions(id).unwrap(), 3);
+}
+
+#[test]
+fn find_partitions_with_no_filters_matches_list_partitions() {
+    let id = seed_dataset(&[
+        key("BTCUSDT", "1m", "ohlcv"),
+        key("ETHUSDT", "5m", "ohlcv"),
+    ]);
+
+    let found = dataset::find_partitions(id, None, None, None).unwrap();
+    let listed = dataset::list_partitions(id, None, None).unwrap();
+    assert_eq!(found, listed);
+}
+
+#[test]
+fn find_partitions_by_source_alone_filters_across_symbols_and_timeframes() {
+    let id = seed_dataset(&[
+        key("BTCUSDT", "1m", "ohlcv"),
+        key("BTCUSDT", "1m", "spot"),
+        key("ETHUSDT", "5m", "spot"),
+    ]);
+
+    let found = dataset::find_partitions(id, None, None, Some("spot")).unwrap();
+
+    assert_eq!(
+        found,
+        vec![key("BTCUSDT", "1m", "spot"), key("ETHUSDT", "5m", "spot")]
+    );
+}
+
+#[test]
+fn find_partitions_combines_symbol_timeframe_and_source_filters() {
+    let id = seed_dataset(&[
+        key("BTCUSDT", "1m", "ohlcv"),
+        key("BTCUSDT", "1m", "spot"),
+        key("BTCUSDT", "5m", "ohlcv"),
+    ]);
+
+    let found = dataset::find_partitions(id, Some("BTCUSDT"), Some("1m"), Some("spot")).unwrap();
+
+    assert_eq!(found, vec![key("BTCUSDT", "1m", "spot")]);
+}
+
+#[test]
+fn find_partitions_with_an_unindexed_symbol_is_empty() {
+    let id = seed_dataset(&[key("BTCUSDT", "1m", "ohlcv")]);
+
+    let found = dataset::find_partitions(id, Some("SOLUSDT"), None, None).unwrap();
+    assert!(found.is_empty());
+}
+
+#[test]
+fn find_partitions_on_unknown_dataset_id_is_rejected() {
+    let err = dataset::find_partitions(u64::MAX, None, None, None).unwrap_err();
+    assert!(matches!(err, DatasetRegistryError::UnknownDatasetId(_)));
+}
@@ -0,0 +1,221 @@
+use ta_engine::dataset_ops::{
+    deregister_aggregator, downsample, downsample_interval, downsample_ohlcv, register_aggregator,
+    resample_ohlcv, BucketAggregator, BucketState, DatasetOpsError,
+};
+
+#[test]
+fn downsample_ohlcv_builds_candle_aware_buckets() {
+    let ts = vec![1, 2, 3, 4, 5];
+    let open = vec![10.0, 11.0, 9.0, 12.0, 13.0];
+    let high = vec![12.0, 13.0, 10.0, 14.0, 15.0];
+    let low = vec![9.0, 10.0, 8.0, 11.0, 12.0];
+    let close = vec![11.0, 9.0, 9.5, 13.0, 14.0];
+    let volume = vec![100.0, 200.0, 150.0, 300.0, 250.0];
+
+    let (out_ts, out_open, out_high, out_low, out_close, out_volume) =
+        downsample_ohlcv(&ts, &open, &high, &low, &close, &volume, 2)
+            .expect("downsample_ohlcv should build buckets");
+
+    assert_eq!(out_ts, vec![2, 4, 5]);
+    assert_eq!(out_open, vec![10.0, 9.0, 13.0]);
+    assert_eq!(out_high, vec![13.0, 14.0, 15.0]);
+    assert_eq!(out_low, vec![9.0, 8.0, 12.0]);
+    assert_eq!(out_close, vec![9.0, 13.0, 14.0]);
+    assert_eq!(out_volume, vec![300.0, 450.0, 250.0]);
+}
+
+#[test]
+fn downsample_ohlcv_rejects_zero_factor() {
+    let err = downsample_ohlcv(&[1], &[1.0], &[1.0], &[1.0], &[1.0], &[1.0], 0)
+        .expect_err("factor 0 should be rejected");
+    assert_eq!(err, DatasetOpsError::InvalidFactor);
+}
+
+#[test]
+fn downsample_ohlcv_rejects_empty_input() {
+    let empty: Vec<i64> = Vec::new();
+    let err = downsample_ohlcv(&empty, &[], &[], &[], &[], &[], 2)
+        .expect_err("empty input should be rejected");
+    assert_eq!(err, DatasetOpsError::LengthMismatch);
+}
+
+#[test]
+fn downsample_ohlcv_rejects_column_length_mismatch() {
+    let err = downsample_ohlcv(&[1, 2], &[1.0], &[1.0, 2.0], &[1.0, 2.0], &[1.0, 2.0], &[1.0, 2.0], 2)
+        .expect_err("mismatched column length should be rejected");
+    assert_eq!(err, DatasetOpsError::LengthMismatch);
+}
+
+#[test]
+fn downsample_resolves_built_in_aggregators_by_name() {
+    let ts = vec![1, 2, 3, 4];
+    let values = vec![1.0, 2.0, 3.0, 4.0];
+
+    let (_, mean) = downsample(&ts, &values, 2, "mean").expect("mean should resolve");
+    assert_eq!(mean, vec![1.5, 3.5]);
+
+    let (_, median) = downsample(&ts, &values, 2, "median").expect("median should resolve");
+    assert_eq!(median, vec![1.5, 3.5]);
+
+    let (_, range) = downsample(&ts, &values, 2, "range").expect("range should resolve");
+    assert_eq!(range, vec![1.0, 1.0]);
+
+    let (_, count_nonzero) =
+        downsample(&ts, &[0.0, 2.0, 0.0, 4.0], 2, "count_nonzero").expect("count_nonzero should resolve");
+    assert_eq!(count_nonzero, vec![1.0, 1.0]);
+}
+
+#[test]
+fn downsample_median_ignores_nan_values_in_a_bucket_instead_of_panicking() {
+    let ts = vec![1, 2, 3, 4];
+    let values = vec![1.0, f64::NAN, 3.0, 4.0];
+
+    let (_, median) = downsample(&ts, &values, 2, "median").expect("median should resolve");
+
+    assert_eq!(median[0], 1.0);
+    assert_eq!(median[1], 3.5);
+}
+
+#[test]
+fn downsample_median_of_an_all_nan_bucket_is_nan() {
+    let ts = vec![1, 2];
+    let values = vec![f64::NAN, f64::NAN];
+
+    let (_, median) = downsample(&ts, &values, 2, "median").expect("median should resolve");
+
+    assert!(median[0].is_nan());
+}
+
+#[test]
+fn downsample_rejects_unregistered_aggregation_as_a_lookup_miss() {
+    let err = downsample(&[1, 2], &[1.0, 2.0], 2, "nonexistent")
+        .expect_err("unregistered aggregation name should be rejected");
+    assert_eq!(err, DatasetOpsError::UnsupportedAggregation("nonexistent".to_string()));
+}
+
+#[test]
+fn downsample_vwap_falls_back_to_mean_when_driven_with_uniform_weight() {
+    // `downsample` always calls aggregators with weight = 1.0, so "vwap"
+    // degenerates to the plain mean here -- the real weighting only shows
+    // up when driven directly with (value, weight) pairs, as below.
+    let (_, vwap) = downsample(&[1, 2], &[2.0, 4.0], 2, "vwap").expect("vwap should resolve");
+    assert_eq!(vwap, vec![3.0]);
+}
+
+#[test]
+fn resample_ohlcv_aligns_a_vwap_column_with_the_ohlcv_buckets() {
+    let ts = vec![1, 2, 3, 4];
+    let open = vec![10.0, 11.0, 9.0, 12.0];
+    let high = vec![12.0, 13.0, 10.0, 14.0];
+    let low = vec![9.0, 10.0, 8.0, 11.0];
+    let close = vec![11.0, 9.0, 9.5, 13.0];
+    let volume = vec![100.0, 200.0, 150.0, 300.0];
+
+    let (out_ts, out_open, out_high, out_low, out_close, out_volume, out_vwap) =
+        resample_ohlcv(&ts, &open, &high, &low, &close, &volume, 2)
+            .expect("resample_ohlcv should build buckets");
+
+    assert_eq!(out_ts, vec![2, 4]);
+    assert_eq!(out_open, vec![10.0, 9.0]);
+    assert_eq!(out_high, vec![13.0, 14.0]);
+    assert_eq!(out_low, vec![9.0, 8.0]);
+    assert_eq!(out_close, vec![9.0, 13.0]);
+    assert_eq!(out_volume, vec![300.0, 450.0]);
+
+    let expected_first = (11.0 * 100.0 + 9.0 * 200.0) / 300.0;
+    let expected_second = (9.5 * 150.0 + 13.0 * 300.0) / 450.0;
+    assert_eq!(out_vwap, vec![expected_first, expected_second]);
+}
+
+#[test]
+fn resample_ohlcv_rejects_zero_factor() {
+    let err = resample_ohlcv(&[1], &[1.0], &[1.0], &[1.0], &[1.0], &[1.0], 0)
+        .expect_err("factor 0 should be rejected");
+    assert_eq!(err, DatasetOpsError::InvalidFactor);
+}
+
+#[test]
+fn downsample_interval_groups_samples_into_calendar_windows() {
+    let ts = vec![100, 150, 250, 260, 400];
+    let values = vec![10.0, 20.0, 30.0, 40.0, 50.0];
+
+    let (out_ts, out_values) = downsample_interval(&ts, &values, 100, "mean", "skip")
+        .expect("downsample_interval should build calendar windows");
+
+    assert_eq!(out_ts, vec![100, 200, 400]);
+    assert_eq!(out_values, vec![15.0, 35.0, 50.0]);
+}
+
+#[test]
+fn downsample_interval_zero_fills_empty_windows() {
+    let ts = vec![100, 150, 250, 260, 400];
+    let values = vec![10.0, 20.0, 30.0, 40.0, 50.0];
+
+    let (out_ts, out_values) = downsample_interval(&ts, &values, 100, "mean", "zero")
+        .expect("downsample_interval should zero-fill the empty window");
+
+    assert_eq!(out_ts, vec![100, 200, 300, 400]);
+    assert_eq!(out_values, vec![15.0, 35.0, 0.0, 50.0]);
+}
+
+#[test]
+fn downsample_interval_ffill_carries_the_prior_window_forward() {
+    let ts = vec![100, 150, 250, 260, 400];
+    let values = vec![10.0, 20.0, 30.0, 40.0, 50.0];
+
+    let (out_ts, out_values) = downsample_interval(&ts, &values, 100, "mean", "ffill")
+        .expect("downsample_interval should ffill the empty window");
+
+    assert_eq!(out_ts, vec![100, 200, 300, 400]);
+    assert_eq!(out_values, vec![15.0, 35.0, 35.0, 50.0]);
+}
+
+#[test]
+fn downsample_interval_rejects_non_positive_interval() {
+    let err = downsample_interval(&[100], &[1.0], 0, "mean", "skip")
+        .expect_err("interval 0 should be rejected");
+    assert_eq!(err, DatasetOpsError::InvalidInterval);
+}
+
+#[test]
+fn downsample_interval_rejects_unsupported_empty_policy() {
+    let err = downsample_interval(&[100, 250], &[1.0, 2.0], 100, "mean", "nearest")
+        .expect_err("unsupported empty policy should be rejected");
+    assert_eq!(
+        err,
+        DatasetOpsError::UnsupportedEmptyPolicy("nearest".to_string())
+    );
+}
+
+struct ProductAggregator;
+impl BucketAggregator for ProductAggregator {
+    fn init(&self) -> BucketState {
+        BucketState {
+            sum: 1.0,
+            ..BucketState::default()
+        }
+    }
+    fn accumulate(&self, state: &mut BucketState, value: f64, _weight: f64) {
+        state.sum *= value;
+    }
+    fn finalize(&self, state: &BucketState) -> f64 {
+        state.sum
+    }
+}
+
+#[test]
+fn downsample_resolves_a_registered_custom_aggregator() {
+    register_aggregator("product_test", Box::new(ProductAggregator));
+
+    let (_, out) = downsample(&[1, 2], &[2.0, 3.0], 2, "product_test")
+        .expect("registered aggregator should resolve");
+    assert_eq!(out, vec![6.0]);
+
+    deregister_aggregator("product_test");
+    let err = downsample(&[1, 2], &[2.0, 3.0], 2, "product_test")
+        .expect_err("deregistered aggregator should no longer resolve");
+    assert_eq!(
+        err,
+        DatasetOpsError::UnsupportedAggregation("product_test".to_string())
+    );
+}
@@ -0,0 +1,121 @@
+use ta_engine::dataset::{self, DatasetPartitionKey, DatasetRegistryError};
+
+fn key(symbol: &str, timeframe: &str, source: &str) -> DatasetPartitionKey {
+    DatasetPartitionKey {
+        symbol: symbol.to_string(),
+        timeframe: timeframe.to_string(),
+        source: source.to_string(),
+    }
+}
+
+#[test]
+fn ohlcv_range_returns_only_rows_within_the_window() {
+    let id = dataset::create_dataset();
+    let partition_key = key("BTCUSDT", "1m", "ohlcv");
+    dataset::append_ohlcv(
+        id,
+        partition_key.clone(),
+        &[0, 60_000, 120_000, 180_000],
+        &[10.0, 11.0, 12.0, 13.0],
+        &[10.0, 11.0, 12.0, 13.0],
+        &[10.0, 11.0, 12.0, 13.0],
+        &[10.0, 11.0, 12.0, 13.0],
+        &[1.0, 2.0, 3.0, 4.0],
+    )
+    .unwrap();
+
+    let columns = dataset::get_ohlcv_range(id, &partition_key, 60_000, 120_000).unwrap();
+
+    assert_eq!(columns.timestamps, vec![60_000, 120_000]);
+    assert_eq!(columns.open, vec![11.0, 12.0]);
+    assert_eq!(columns.volume, vec![2.0, 3.0]);
+}
+
+#[test]
+fn ohlcv_range_entirely_before_or_after_the_data_is_empty() {
+    let id = dataset::create_dataset();
+    let partition_key = key("BTCUSDT", "1m", "ohlcv");
+    dataset::append_ohlcv(
+        id,
+        partition_key.clone(),
+        &[100, 200],
+        &[1.0, 2.0],
+        &[1.0, 2.0],
+        &[1.0, 2.0],
+        &[1.0, 2.0],
+        &[1.0, 2.0],
+    )
+    .unwrap();
+
+    let before = dataset::get_ohlcv_range(id, &partition_key, 0, 50).unwrap();
+    assert!(before.timestamps.is_empty());
+
+    let after = dataset::get_ohlcv_range(id, &partition_key, 300, 400).unwrap();
+    assert!(after.timestamps.is_empty());
+}
+
+#[test]
+fn ohlcv_range_on_a_missing_partition_is_empty_not_an_error() {
+    let id = dataset::create_dataset();
+    let columns = dataset::get_ohlcv_range(id, &key("BTCUSDT", "1m", "ohlcv"), 0, 100).unwrap();
+    assert!(columns.timestamps.is_empty());
+}
+
+#[test]
+fn ohlcv_range_rejects_start_after_end() {
+    let id = dataset::create_dataset();
+    let err =
+        dataset::get_ohlcv_range(id, &key("BTCUSDT", "1m", "ohlcv"), 100, 0).unwrap_err();
+    assert!(matches!(err, DatasetRegistryError::InvalidTimeRange { .. }));
+}
+
+#[test]
+fn ohlcv_range_on_unknown_dataset_id_is_rejected() {
+    let err =
+        dataset::get_ohlcv_range(u64::MAX, &key("BTCUSDT", "1m", "ohlcv"), 0, 100).unwrap_err();
+    assert!(matches!(err, DatasetRegistryError::UnknownDatasetId(_)));
+}
+
+#[test]
+fn series_range_returns_only_rows_within_the_window() {
+    let id = dataset::create_dataset();
+    let partition_key = key("BTCUSDT", "1m", "ohlcv");
+    dataset::append_series(
+        id,
+        partition_key.clone(),
+        "rsi".to_string(),
+        &[0, 60_000, 120_000],
+        &[30.0, 40.0, 50.0],
+    )
+    .unwrap();
+
+    let series = dataset::get_series_range(id, &partition_key, "rsi", 60_000, 120_000).unwrap();
+
+    assert_eq!(series.timestamps, vec![60_000, 120_000]);
+    assert_eq!(series.values, vec![40.0, 50.0]);
+}
+
+#[test]
+fn series_range_on_a_missing_field_is_empty_not_an_error() {
+    let id = dataset::create_dataset();
+    let partition_key = key("BTCUSDT", "1m", "ohlcv");
+    dataset::append_series(
+        id,
+        partition_key.clone(),
+        "rsi".to_string(),
+        &[0],
+        &[30.0],
+    )
+    .unwrap();
+
+    let series = dataset::get_series_range(id, &partition_key, "macd", 0, 1).unwrap();
+    assert!(series.timestamps.is_empty());
+}
+
+#[test]
+fn series_range_rejects_start_after_end() {
+    let id = dataset::create_dataset();
+    let err = dataset::get_series_range(id, &key("BTCUSDT", "1m", "ohlcv"), "rsi", 100, 0)
+        .unwrap_err();
+    assert!(matches!(err, DatasetRegistryError::InvalidTimeRange { .. }));
+}
@@ -0,0 +1,262 @@
+use std::collections::BTreeMap;
+
+use ta_engine::contracts::{
+    RustExecutionGraph, RustExecutionPartition, RustExecutionPayload, RustExecutionRequest,
+};
+use ta_engine::dataset::{self, DatasetPartitionKey, DatasetRegistryError};
+use ta_engine::incremental::backend::{
+    execute_plan_batch, execute_plan_graph_batch, execute_plan_graph_prefix, ExecutePlanError,
+    KernelStepRequest, StepInputSource,
+};
+use ta_engine::incremental::contracts::IncrementalValue;
+use ta_engine::incremental::kernel_registry::KernelId;
+
+fn payload_for(symbol: &str, dataset_id: u64) -> RustExecutionPayload {
+    RustExecutionPayload {
+        dataset_id,
+        partition: RustExecutionPartition {
+            symbol: symbol.to_string(),
+            timeframe: "1m".to_string(),
+            source: "ohlcv".to_string(),
+        },
+        partitions: Vec::new(),
+        graph: RustExecutionGraph {
+            root_id: 2,
+            node_order: vec![1, 2],
+            nodes: BTreeMap::from([
+                (
+                    1,
+                    BTreeMap::from([("kind".to_string(), "source_ref".to_string())]),
+                ),
+                (
+                    2,
+                    BTreeMap::from([("kind".to_string(), "call".to_string())]),
+                ),
+            ]),
+            edges: BTreeMap::from([(2, vec![1])]),
+        },
+        requests: vec![RustExecutionRequest {
+            node_id: 2,
+            kernel_id: "rsi".to_string(),
+            input_field: "close".to_string(),
+            kwargs: BTreeMap::from([("period".to_string(), IncrementalValue::Number(14.0))]),
+        }],
+    }
+}
+
+#[test]
+fn batch_runs_every_payload_and_pairs_results_with_their_own_partition() {
+    let payloads = vec![
+        payload_for("BTCUSDT", 999_001),
+        payload_for("ETHUSDT", 999_002),
+        payload_for("SOLUSDT", 999_003),
+    ];
+
+    let results = execute_plan_graph_batch(&payloads);
+
+    assert_eq!(results.len(), payloads.len());
+    for (payload, (partition, result)) in payloads.iter().zip(results.iter()) {
+        assert_eq!(partition, &payload.partition);
+        assert!(matches!(
+            result,
+            Err(ExecutePlanError::Dataset(_)) | Err(ExecutePlanError::PartitionNotFound { .. })
+        ));
+    }
+}
+
+#[test]
+fn batch_rejects_each_payload_independently() {
+    let mut malformed_payload = payload_for("ETHUSDT", 999_005);
+    malformed_payload.graph.root_id = 999;
+    let payloads = vec![payload_for("BTCUSDT", 999_004), malformed_payload];
+
+    let results = execute_plan_graph_batch(&payloads);
+
+    assert_eq!(results.len(), 2);
+    assert!(matches!(
+        results[0].1,
+        Err(ExecutePlanError::Dataset(_)) | Err(ExecutePlanError::PartitionNotFound { .. })
+    ));
+    assert!(matches!(results[1].1, Err(ExecutePlanError::InvalidPayload(_))));
+}
+
+fn rsi_payload_for(dataset_id: u64) -> RustExecutionPayload {
+    let mut payload = payload_for("unused", dataset_id);
+    payload.graph.nodes.insert(
+        2,
+        BTreeMap::from([
+            ("kind".to_string(), "call".to_string()),
+            ("name".to_string(), "rsi".to_string()),
+        ]),
+    );
+    payload
+}
+
+fn seed_ohlcv_dataset(keys: &[DatasetPartitionKey]) -> u64 {
+    let id = dataset::create_dataset();
+    for key in keys {
+        dataset::append_ohlcv(
+            id,
+            key.clone(),
+            &[1, 2, 3],
+            &[1.0, 2.0, 3.0],
+            &[1.5, 2.5, 3.5],
+            &[0.5, 1.5, 2.5],
+            &[1.0, 2.0, 3.0],
+            &[10.0, 11.0, 12.0],
+        )
+        .unwrap();
+    }
+    id
+}
+
+#[test]
+fn prefix_runs_the_graph_against_every_matching_partition() {
+    let keys = [
+        DatasetPartitionKey {
+            symbol: "BTCUSDT".to_string(),
+            timeframe: "1m".to_string(),
+            source: "ohlcv".to_string(),
+        },
+        DatasetPartitionKey {
+            symbol: "BTCUSDT".to_string(),
+            timeframe: "5m".to_string(),
+            source: "ohlcv".to_string(),
+        },
+        DatasetPartitionKey {
+            symbol: "ETHUSDT".to_string(),
+            timeframe: "1m".to_string(),
+            source: "ohlcv".to_string(),
+        },
+    ];
+    let dataset_id = seed_ohlcv_dataset(&keys);
+    let base_payload = rsi_payload_for(dataset_id);
+
+    let results = execute_plan_graph_prefix(&base_payload, Some("BTCUSDT"), None).unwrap();
+
+    let mut partitions: Vec<(String, String, String)> = results
+        .iter()
+        .map(|(p, _)| (p.symbol.clone(), p.timeframe.clone(), p.source.clone()))
+        .collect();
+    partitions.sort();
+    assert_eq!(
+        partitions,
+        vec![
+            ("BTCUSDT".to_string(), "1m".to_string(), "ohlcv".to_string()),
+            ("BTCUSDT".to_string(), "5m".to_string(), "ohlcv".to_string()),
+        ]
+    );
+    for (_, result) in &results {
+        assert!(result.is_ok());
+    }
+}
+
+fn seed_rsi_dataset(keys: &[DatasetPartitionKey]) -> u64 {
+    let id = dataset::create_dataset();
+    for (i, key) in keys.iter().enumerate() {
+        // Vary the series length per partition so tasks don't all finish in
+        // lockstep -- the slowest (longest) partition is seeded last, so a
+        // naive "first to finish wins" ordering would put it last too.
+        let len = 3 + i;
+        let closes: Vec<f64> = (0..len).map(|n| 10.0 + n as f64).collect();
+        let timestamps: Vec<i64> = (0..len).map(|n| n as i64).collect();
+        dataset::append_ohlcv(
+            id,
+            key.clone(),
+            &timestamps,
+            &closes,
+            &closes,
+            &closes,
+            &closes,
+            &vec![1.0; len],
+        )
+        .unwrap();
+    }
+    id
+}
+
+fn rsi_requests() -> Vec<KernelStepRequest> {
+    vec![KernelStepRequest {
+        node_id: 1,
+        kernel_id: KernelId::Rsi,
+        input: StepInputSource::TickField("close".to_string()),
+        kwargs: BTreeMap::new(),
+    }]
+}
+
+#[test]
+fn batch_results_preserve_partition_key_order_regardless_of_completion_order() {
+    let keys = vec![
+        DatasetPartitionKey {
+            symbol: "BTCUSDT".to_string(),
+            timeframe: "1m".to_string(),
+            source: "ohlcv".to_string(),
+        },
+        DatasetPartitionKey {
+            symbol: "ETHUSDT".to_string(),
+            timeframe: "1m".to_string(),
+            source: "ohlcv".to_string(),
+        },
+        DatasetPartitionKey {
+            symbol: "SOLUSDT".to_string(),
+            timeframe: "1m".to_string(),
+            source: "ohlcv".to_string(),
+        },
+    ];
+    let dataset_id = seed_rsi_dataset(&keys);
+
+    let results = execute_plan_batch(dataset_id, &keys, &rsi_requests(), None);
+
+    let returned_keys: Vec<DatasetPartitionKey> =
+        results.into_iter().map(|(key, _)| key).collect();
+    assert_eq!(returned_keys, keys);
+}
+
+#[test]
+fn batch_with_a_bounded_thread_pool_matches_the_unbounded_default() {
+    let keys = vec![
+        DatasetPartitionKey {
+            symbol: "BTCUSDT".to_string(),
+            timeframe: "1m".to_string(),
+            source: "ohlcv".to_string(),
+        },
+        DatasetPartitionKey {
+            symbol: "ETHUSDT".to_string(),
+            timeframe: "1m".to_string(),
+            source: "ohlcv".to_string(),
+        },
+    ];
+    let dataset_id = seed_rsi_dataset(&keys);
+    let requests = rsi_requests();
+
+    let unbounded = execute_plan_batch(dataset_id, &keys, &requests, None);
+    let bounded = execute_plan_batch(dataset_id, &keys, &requests, Some(1));
+
+    assert_eq!(unbounded.len(), bounded.len());
+    for ((unbounded_key, unbounded_result), (bounded_key, bounded_result)) in
+        unbounded.iter().zip(bounded.iter())
+    {
+        assert_eq!(unbounded_key, bounded_key);
+        match (unbounded_result, bounded_result) {
+            (Ok(a), Ok(b)) => assert_eq!(a, b),
+            _ => panic!("both runs should succeed identically for {:?}", unbounded_key),
+        }
+    }
+}
+
+#[test]
+fn prefix_rejects_a_timeframe_filter_without_a_symbol_filter() {
+    let dataset_id = seed_ohlcv_dataset(&[DatasetPartitionKey {
+        symbol: "BTCUSDT".to_string(),
+        timeframe: "1m".to_string(),
+        source: "ohlcv".to_string(),
+    }]);
+    let base_payload = payload_for("unused", dataset_id);
+
+    let err = execute_plan_graph_prefix(&base_payload, None, Some("1m")).unwrap_err();
+
+    assert!(matches!(
+        err,
+        ExecutePlanError::Dataset(DatasetRegistryError::InvalidPartitionFilter { .. })
+    ));
+}
@@ -0,0 +1,72 @@
+use std::collections::BTreeMap;
+
+use ta_engine::dataset::{append_ohlcv, create_dataset, DatasetPartitionKey};
+use ta_engine::incremental::backend::{execute_plan_with_stats, KernelStepRequest, StepInputSource};
+use ta_engine::incremental::contracts::IncrementalValue;
+use ta_engine::incremental::kernel_registry::KernelId;
+
+fn seed_dataset() -> (u64, DatasetPartitionKey) {
+    let id = create_dataset();
+    let key = DatasetPartitionKey {
+        symbol: "BTCUSDT".to_string(),
+        timeframe: "1m".to_string(),
+        source: "ohlcv".to_string(),
+    };
+    append_ohlcv(
+        id,
+        key.clone(),
+        &[0, 60_000, 120_000, 180_000, 240_000],
+        &[10.0, 11.0, 12.0, 11.5, 12.5],
+        &[10.5, 11.5, 12.5, 12.0, 13.0],
+        &[9.5, 10.5, 11.5, 11.0, 12.0],
+        &[10.2, 11.2, 12.2, 11.8, 12.8],
+        &[1.0, 1.0, 1.0, 1.0, 1.0],
+    )
+    .expect("ohlcv append should succeed");
+    (id, key)
+}
+
+fn rsi_request(node_id: u32, field: &str, period: f64) -> KernelStepRequest {
+    KernelStepRequest {
+        node_id,
+        kernel_id: KernelId::Rsi,
+        input: StepInputSource::TickField(field.to_string()),
+        kwargs: BTreeMap::from([("period".to_string(), IncrementalValue::Number(period))]),
+    }
+}
+
+#[test]
+fn identical_requests_against_the_same_column_share_one_computed_result_and_report_a_cache_hit() {
+    let (id, key) = seed_dataset();
+    let requests = [rsi_request(1, "close", 3.0), rsi_request(2, "close", 3.0)];
+
+    let (out, stats) =
+        execute_plan_with_stats(id, &key, &requests).expect("plan should execute");
+
+    assert_eq!(out.get(&1), out.get(&2), "duplicate requests should share one computed series");
+    assert_eq!(stats.cache_hits, 1);
+}
+
+#[test]
+fn a_request_differing_only_by_params_is_not_merged() {
+    let (id, key) = seed_dataset();
+    let requests = [rsi_request(1, "close", 3.0), rsi_request(2, "close", 4.0)];
+
+    let (out, stats) =
+        execute_plan_with_stats(id, &key, &requests).expect("plan should execute");
+
+    assert_ne!(out.get(&1), out.get(&2), "distinct periods must not collapse");
+    assert_eq!(stats.cache_hits, 0);
+}
+
+#[test]
+fn a_request_differing_only_by_input_column_is_not_merged() {
+    let (id, key) = seed_dataset();
+    let requests = [rsi_request(1, "close", 3.0), rsi_request(2, "open", 3.0)];
+
+    let (out, stats) =
+        execute_plan_with_stats(id, &key, &requests).expect("plan should execute");
+
+    assert_ne!(out.get(&1), out.get(&2), "distinct input columns must not collapse");
+    assert_eq!(stats.cache_hits, 0);
+}
@@ -3,7 +3,9 @@ use std::collections::BTreeMap;
 use ta_engine::contracts::{
     RustExecutionGraph, RustExecutionPartition, RustExecutionPayload, RustExecutionRequest,
 };
-use ta_engine::incremental::backend::{parse_execute_plan_payload, ExecutePlanError};
+use ta_engine::incremental::backend::{
+    parse_execute_plan_payload, parse_execute_plan_payloads, ExecutePlanError,
+};
 use ta_engine::incremental::contracts::IncrementalValue;
 
 fn valid_payload() -> RustExecutionPayload {
@@ -14,6 +16,7 @@ fn valid_payload() -> RustExecutionPayload {
             timeframe: "1m".to_string(),
             source: "ohlcv".to_string(),
         },
+        partitions: Vec::new(),
         graph: RustExecutionGraph {
             root_id: 10,
             node_order: vec![1, 2, 10],
@@ -69,3 +72,72 @@ fn parse_execute_payload_rejects_unknown_kernel() {
         ExecutePlanError::UnsupportedKernelId("unknown_kernel".to_string())
     );
 }
+
+#[test]
+fn parse_execute_payloads_falls_back_to_single_partition_when_empty() {
+    let payload = valid_payload();
+    let parsed = parse_execute_plan_payloads(&payload).expect("payload should parse");
+    assert_eq!(parsed.len(), 1);
+    assert_eq!(parsed[0].partition_key.symbol, "BTCUSDT");
+}
+
+#[test]
+fn parse_execute_payloads_fans_out_across_partitions() {
+    let mut payload = valid_payload();
+    payload.partitions = vec![
+        RustExecutionPartition {
+            symbol: "BTCUSDT".to_string(),
+            timeframe: "1m".to_string(),
+            source: "ohlcv".to_string(),
+        },
+        RustExecutionPartition {
+            symbol: "ETHUSDT".to_string(),
+            timeframe: "1m".to_string(),
+            source: "ohlcv".to_string(),
+        },
+    ];
+
+    let parsed = parse_execute_plan_payloads(&payload).expect("payload should parse");
+    assert_eq!(parsed.len(), 2);
+    assert_eq!(parsed[0].partition_key.symbol, "BTCUSDT");
+    assert_eq!(parsed[1].partition_key.symbol, "ETHUSDT");
+    assert_eq!(parsed[0].requests.len(), 1);
+    assert_eq!(parsed[1].requests.len(), 1);
+}
+
+#[test]
+fn parse_execute_payloads_rejects_malformed_partition_entry() {
+    let mut payload = valid_payload();
+    payload.partitions = vec![RustExecutionPartition {
+        symbol: String::new(),
+        timeframe: "1m".to_string(),
+        source: "ohlcv".to_string(),
+    }];
+
+    let err = parse_execute_plan_payloads(&payload).expect_err("payload should fail");
+    assert!(matches!(err, ExecutePlanError::InvalidPayload(_)));
+}
+
+#[test]
+fn parse_execute_payloads_resolves_kernel_ids_once_and_shares_errors_across_partitions() {
+    let mut payload = valid_payload();
+    payload.requests[0].kernel_id = "unknown_kernel".to_string();
+    payload.partitions = vec![
+        RustExecutionPartition {
+            symbol: "BTCUSDT".to_string(),
+            timeframe: "1m".to_string(),
+            source: "ohlcv".to_string(),
+        },
+        RustExecutionPartition {
+            symbol: "ETHUSDT".to_string(),
+            timeframe: "1m".to_string(),
+            source: "ohlcv".to_string(),
+        },
+    ];
+
+    let err = parse_execute_plan_payloads(&payload).expect_err("payload should fail");
+    assert_eq!(
+        err,
+        ExecutePlanError::UnsupportedKernelId("unknown_kernel".to_string())
+    );
+}
@@ -0,0 +1,47 @@
+//! Manual throughput check for `execute_plan` driven by a JSON workload
+//! file, run on demand with `cargo test --release -- --ignored
+//! execute_plan_workload_bench` (not part of the regular suite since it's a
+//! timing measurement, not a correctness check).
+
+use ta_engine::bench::{encode_bench_report_to_json, parse_bench_workload, run_bench_workload};
+
+fn workload_json() -> serde_json::Value {
+    serde_json::json!({
+        "symbol": "BTCUSDT",
+        "timeframe": "1m",
+        "source": "ohlcv",
+        "row_count": 50_000,
+        "seed": 1,
+        "requests": [
+            {"node_id": 1, "kernel_id": "rsi", "input_field": "close", "kwargs": {"period": 14.0}},
+            {"node_id": 2, "kernel_id": "atr", "input_field": "close", "kwargs": {"period": 14.0}},
+            {"node_id": 3, "kernel_id": "obv", "input_field": "close", "kwargs": {}}
+        ]
+    })
+}
+
+#[test]
+#[ignore]
+fn execute_plan_workload_bench() {
+    let workload = parse_bench_workload(&workload_json()).expect("workload should parse");
+    let report = run_bench_workload(&workload);
+
+    println!(
+        "execute_plan_workload_bench: {} rows in {:?}, {:.0} ticks/sec",
+        report.row_count,
+        std::time::Duration::from_nanos(report.wall_nanos),
+        report.ticks_per_sec
+    );
+    for stats in &report.node_stats {
+        println!(
+            "  node {} ({}): p50={}ns p90={}ns p99={}ns max={}ns",
+            stats.node_id,
+            stats.kernel_id.as_str(),
+            stats.p50_nanos,
+            stats.p90_nanos,
+            stats.p99_nanos,
+            stats.max_nanos
+        );
+    }
+    println!("{}", encode_bench_report_to_json(&report));
+}
@@ -0,0 +1,71 @@
+use std::collections::BTreeMap;
+
+use ta_engine::incremental::backend::{IncrementalBackend, KernelStepRequest, StepInputSource};
+use ta_engine::incremental::contracts::IncrementalValue;
+use ta_engine::incremental::kernel_registry::KernelId;
+
+fn tick(close: f64) -> BTreeMap<String, IncrementalValue> {
+    BTreeMap::from([("close".to_string(), IncrementalValue::Number(close))])
+}
+
+fn rsi_request(node_id: u32, period: f64) -> KernelStepRequest {
+    KernelStepRequest {
+        node_id,
+        kernel_id: KernelId::Rsi,
+        input: StepInputSource::TickField("close".to_string()),
+        kwargs: BTreeMap::from([("period".to_string(), IncrementalValue::Number(period))]),
+    }
+}
+
+#[test]
+fn nodes_requesting_the_same_computation_get_identical_outputs() {
+    // Three nodes all asking for rsi(close, 3); only the smallest node id
+    // (1) should actually run the kernel, but all three must agree.
+    let requests = [rsi_request(3, 3.0), rsi_request(1, 3.0), rsi_request(2, 3.0)];
+
+    let mut backend = IncrementalBackend::default();
+    let mut last = BTreeMap::new();
+    for (i, close) in [10.0, 10.5, 11.0, 10.2, 9.8].into_iter().enumerate() {
+        last = backend
+            .step(0, i as u64, &requests, &tick(close))
+            .expect("step should succeed");
+    }
+
+    assert_eq!(last.get(&1), last.get(&2));
+    assert_eq!(last.get(&1), last.get(&3));
+    assert!(matches!(last.get(&1), Some(IncrementalValue::Number(_))));
+}
+
+#[test]
+fn only_the_canonical_node_pays_for_the_kernel_computation() {
+    let requests = [rsi_request(5, 3.0), rsi_request(1, 3.0)];
+
+    let mut backend = IncrementalBackend::default();
+    backend.enable_profiling();
+    for (i, close) in [10.0, 10.5, 11.0, 10.2].into_iter().enumerate() {
+        backend
+            .step(0, i as u64, &requests, &tick(close))
+            .expect("step should succeed");
+    }
+
+    // Profiling only records a tick for a node that actually ran
+    // `eval_call_step`, so the duplicate (node 5) should never appear.
+    let profile = backend.profile();
+    assert!(profile.contains_key(&1));
+    assert!(!profile.contains_key(&5));
+}
+
+#[test]
+fn distinct_params_are_not_collapsed_into_one_computation() {
+    let requests = [rsi_request(1, 3.0), rsi_request(2, 7.0)];
+
+    let mut backend = IncrementalBackend::default();
+    let mut last = BTreeMap::new();
+    for (i, close) in [10.0, 10.5, 11.0, 10.2, 9.8, 11.3, 12.1].into_iter().enumerate() {
+        last = backend
+            .step(0, i as u64, &requests, &tick(close))
+            .expect("step should succeed");
+    }
+
+    assert_ne!(last.get(&1), last.get(&2));
+}
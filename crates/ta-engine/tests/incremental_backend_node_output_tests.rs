@@ -0,0 +1,83 @@
+use std::collections::BTreeMap;
+
+use ta_engine::incremental::backend::{
+    ExecutePlanError, IncrementalBackend, KernelStepRequest, StepInputSource,
+};
+use ta_engine::incremental::contracts::IncrementalValue;
+use ta_engine::incremental::kernel_registry::KernelId;
+
+fn tick(close: f64) -> BTreeMap<String, IncrementalValue> {
+    BTreeMap::from([("close".to_string(), IncrementalValue::Number(close))])
+}
+
+fn rsi_request(node_id: u32, input: StepInputSource, period: f64) -> KernelStepRequest {
+    KernelStepRequest {
+        node_id,
+        kernel_id: KernelId::Rsi,
+        input,
+        kwargs: BTreeMap::from([("period".to_string(), IncrementalValue::Number(period))]),
+    }
+}
+
+#[test]
+fn a_node_can_consume_another_nodes_output_from_the_same_step() {
+    // Node 2 (an "RSI of RSI") reads node 1's output rather than a tick field.
+    let inner = rsi_request(1, StepInputSource::TickField("close".to_string()), 3.0);
+    let outer = rsi_request(2, StepInputSource::NodeOutput(1), 3.0);
+    let requests = [outer, inner];
+
+    let mut backend = IncrementalBackend::default();
+    let mut last = BTreeMap::new();
+    for (i, close) in [10.0, 10.5, 11.0, 10.2, 9.8, 10.9, 11.4].into_iter().enumerate() {
+        last = backend
+            .step(0, i as u64, &requests, &tick(close))
+            .expect("step should succeed");
+    }
+
+    let inner_rsi = last.get(&1).expect("node 1 should have produced an output");
+    let outer_rsi = last.get(&2).expect("node 2 should have produced an output");
+    assert!(matches!(inner_rsi, IncrementalValue::Number(_)));
+    assert!(matches!(outer_rsi, IncrementalValue::Number(_)));
+}
+
+#[test]
+fn node_output_requests_are_evaluated_regardless_of_their_order_in_the_slice() {
+    // The consumer is listed before its dependency; `step` must still
+    // evaluate node 1 first so node 2 doesn't read a stale/empty output.
+    let inner = rsi_request(1, StepInputSource::TickField("close".to_string()), 2.0);
+    let outer = rsi_request(2, StepInputSource::NodeOutput(1), 2.0);
+    let requests = [outer, inner];
+
+    let mut backend = IncrementalBackend::default();
+    for (i, close) in [10.0, 11.0, 9.0, 12.0].into_iter().enumerate() {
+        backend
+            .step(0, i as u64, &requests, &tick(close))
+            .expect("step should succeed");
+    }
+    let out = backend
+        .step(0, 4, &requests, &tick(13.0))
+        .expect("step should succeed");
+
+    assert!(matches!(out.get(&1), Some(IncrementalValue::Number(_))));
+    assert!(matches!(out.get(&2), Some(IncrementalValue::Number(_))));
+}
+
+#[test]
+fn a_cycle_of_node_output_requests_is_rejected() {
+    let a = rsi_request(1, StepInputSource::NodeOutput(2), 3.0);
+    let b = rsi_request(2, StepInputSource::NodeOutput(1), 3.0);
+    let requests = [a, b];
+
+    let mut backend = IncrementalBackend::default();
+    let err = backend
+        .step(0, 0, &requests, &tick(10.0))
+        .expect_err("a cycle between node-output requests should be rejected");
+
+    match err {
+        ExecutePlanError::CycleDetected(mut nodes) => {
+            nodes.sort_unstable();
+            assert_eq!(nodes, vec![1, 2]);
+        }
+        other => panic!("expected CycleDetected, got {other:?}"),
+    }
+}
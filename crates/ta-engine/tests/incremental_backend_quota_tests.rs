@@ -0,0 +1,167 @@
+use std::collections::BTreeMap;
+
+use ta_engine::dataset::{append_ohlcv, create_dataset, DatasetPartitionKey};
+use ta_engine::incremental::backend::{
+    execute_plan_with_quota, BackendQuota, ExecutePlanError, IncrementalBackend,
+    KernelStepRequest, QuotaKind, StepInputSource,
+};
+use ta_engine::incremental::contracts::IncrementalValue;
+use ta_engine::incremental::kernel_registry::KernelId;
+
+fn tick(close: f64) -> BTreeMap<String, IncrementalValue> {
+    BTreeMap::from([("close".to_string(), IncrementalValue::Number(close))])
+}
+
+fn request(node_id: u32) -> KernelStepRequest {
+    KernelStepRequest {
+        node_id,
+        kernel_id: KernelId::Rsi,
+        input: StepInputSource::TickField("close".to_string()),
+        kwargs: BTreeMap::from([("period".to_string(), IncrementalValue::Number(2.0))]),
+    }
+}
+
+fn seeded_dataset(dataset_id: u64) -> DatasetPartitionKey {
+    let key = DatasetPartitionKey {
+        symbol: "BTCUSDT".to_string(),
+        timeframe: "1m".to_string(),
+        source: "ohlcv".to_string(),
+    };
+    append_ohlcv(
+        dataset_id,
+        key.clone(),
+        &[0, 60_000, 120_000],
+        &[10.0, 11.0, 12.0],
+        &[10.0, 11.0, 12.0],
+        &[10.0, 11.0, 12.0],
+        &[10.0, 11.0, 12.0],
+        &[1.0, 1.0, 1.0],
+    )
+    .expect("ohlcv append should succeed");
+    key
+}
+
+#[test]
+fn default_quota_is_unbounded_and_never_trips() {
+    let mut backend: IncrementalBackend = IncrementalBackend::default();
+    assert_eq!(backend.quota(), BackendQuota::default());
+
+    for idx in 1..=10 {
+        backend
+            .step(0, idx, &[request(1)], &tick(idx as f64))
+            .expect("unbounded quota should never trip");
+    }
+    assert_eq!(backend.counters().total_ticks_processed, 10);
+}
+
+#[test]
+fn max_nodes_quota_trips_once_a_new_node_id_would_exceed_it() {
+    let mut backend: IncrementalBackend = IncrementalBackend::default();
+    backend.set_quota(BackendQuota {
+        max_nodes: Some(1),
+        ..Default::default()
+    });
+
+    backend
+        .step(0, 1, &[request(1)], &tick(10.0))
+        .expect("first node_id is within quota");
+
+    let err = backend
+        .step(0, 2, &[request(2)], &tick(11.0))
+        .expect_err("a second distinct node_id should trip the quota");
+    assert_eq!(
+        err,
+        ExecutePlanError::QuotaExceeded {
+            kind: QuotaKind::Nodes,
+            limit: 1,
+        }
+    );
+}
+
+#[test]
+fn max_total_ticks_quota_trips_once_cumulative_ticks_would_exceed_it() {
+    let mut backend: IncrementalBackend = IncrementalBackend::default();
+    backend.set_quota(BackendQuota {
+        max_total_ticks: Some(2),
+        ..Default::default()
+    });
+
+    backend
+        .step(0, 1, &[request(1)], &tick(10.0))
+        .expect("first tick is within quota");
+    backend
+        .step(0, 2, &[request(1)], &tick(11.0))
+        .expect("second tick is within quota");
+
+    let err = backend
+        .step(0, 3, &[request(1)], &tick(12.0))
+        .expect_err("a third tick should trip the quota");
+    assert_eq!(
+        err,
+        ExecutePlanError::QuotaExceeded {
+            kind: QuotaKind::TotalTicks,
+            limit: 2,
+        }
+    );
+}
+
+#[test]
+fn counters_reflect_active_nodes_and_ticks_processed_so_far() {
+    let mut backend: IncrementalBackend = IncrementalBackend::default();
+    backend
+        .step(0, 1, &[request(1), request(2)], &tick(10.0))
+        .expect("step should succeed");
+    backend
+        .step(0, 2, &[request(1)], &tick(11.0))
+        .expect("step should succeed");
+
+    let counters = backend.counters();
+    assert_eq!(counters.active_nodes, 2);
+    assert_eq!(counters.total_ticks_processed, 3);
+    assert!(counters.state_blob_bytes > 0);
+}
+
+#[test]
+fn execute_plan_with_quota_surfaces_max_output_len_as_an_error() {
+    let dataset_id = create_dataset();
+    let key = seeded_dataset(dataset_id);
+
+    let err = execute_plan_with_quota(
+        dataset_id,
+        &key,
+        &[request(1)],
+        BackendQuota {
+            max_output_len: Some(1),
+            ..Default::default()
+        },
+    )
+    .expect_err("a 3-row partition should exceed a max_output_len of 1");
+
+    assert_eq!(
+        err,
+        ExecutePlanError::QuotaExceeded {
+            kind: QuotaKind::OutputLen,
+            limit: 1,
+        }
+    );
+}
+
+#[test]
+fn execute_plan_with_quota_succeeds_when_limits_are_not_exceeded() {
+    let dataset_id = create_dataset();
+    let key = seeded_dataset(dataset_id);
+
+    let outputs = execute_plan_with_quota(
+        dataset_id,
+        &key,
+        &[request(1)],
+        BackendQuota {
+            max_nodes: Some(1),
+            max_total_ticks: Some(3),
+            max_output_len: Some(3),
+        },
+    )
+    .expect("plan should execute within quota");
+
+    assert_eq!(outputs[&1].len(), 3);
+}
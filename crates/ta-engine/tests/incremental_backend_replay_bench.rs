@@ -0,0 +1,58 @@
+//! Manual throughput check for `IncrementalBackend::replay` across many
+//! nodes, run on demand with `cargo test --release -- --ignored
+//! incremental_backend_replay_bench` (not part of the regular suite since
+//! it's a timing measurement, not a correctness check). Exercises the
+//! `IntMap`-backed `call_states` directly through `step`, unlike
+//! `incremental_state_bench`, which drives `IntMap` standalone.
+
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+use ta_engine::incremental::backend::{IncrementalBackend, KernelStepRequest, StepInputSource};
+use ta_engine::incremental::contracts::IncrementalValue;
+use ta_engine::incremental::kernel_registry::KernelId;
+
+const NODE_COUNT: u32 = 500;
+const TICK_COUNT: usize = 4_000;
+
+fn requests() -> Vec<KernelStepRequest> {
+    (1..=NODE_COUNT)
+        .map(|node_id| KernelStepRequest {
+            node_id,
+            kernel_id: KernelId::Rsi,
+            input: StepInputSource::TickField("close".to_string()),
+            kwargs: BTreeMap::from([("period".to_string(), IncrementalValue::Number(14.0))]),
+        })
+        .collect()
+}
+
+fn synthetic_events() -> Vec<BTreeMap<String, IncrementalValue>> {
+    (0..TICK_COUNT)
+        .map(|i| {
+            let close = (i as f64 * 0.0017).sin() * 50.0 + (i as f64) * 0.01;
+            BTreeMap::from([("close".to_string(), IncrementalValue::Number(close))])
+        })
+        .collect()
+}
+
+#[test]
+#[ignore]
+fn incremental_backend_replay_bench() {
+    let requests = requests();
+    let events = synthetic_events();
+
+    let mut backend: IncrementalBackend = IncrementalBackend::default();
+    let start = Instant::now();
+    backend
+        .replay(0, 1, &requests, &events)
+        .expect("replay should succeed");
+    let elapsed = start.elapsed();
+
+    let node_ticks = (NODE_COUNT as usize) * TICK_COUNT;
+    println!(
+        "incremental_backend_replay_bench: {:?} for {NODE_COUNT} nodes over {TICK_COUNT} ticks \
+         ({node_ticks} node-ticks, {:.0} node-ticks/sec)",
+        elapsed,
+        node_ticks as f64 / elapsed.as_secs_f64()
+    );
+}
@@ -3,6 +3,8 @@ use std::collections::BTreeMap;
 use ta_engine::incremental::call_step::{eval_call_step, initialize_kernel_state};
 use ta_engine::incremental::contracts::IncrementalValue;
 use ta_engine::incremental::kernel_registry::{coerce_incremental_input, KernelId};
+use ta_engine::moving_averages::MovingAverageType;
+use ta_engine::volatility::bbands;
 
 #[test]
 fn kernel_id_resolution_and_atr_coercion_work() {
@@ -60,3 +62,39 @@ fn stochastic_call_step_emits_after_window() {
 
     assert!(matches!(last, IncrementalValue::Number(_)));
 }
+
+#[test]
+fn bbands_call_step_matches_the_batch_function() {
+    let period = 5usize;
+    let std_dev = 2.0;
+    let closes: Vec<f64> = (0..30).map(|i| 100.0 + (i as f64 * 0.37).sin() * 10.0).collect();
+
+    let mut kwargs = BTreeMap::new();
+    kwargs.insert("period".to_string(), IncrementalValue::Number(period as f64));
+    kwargs.insert("std_dev".to_string(), IncrementalValue::Number(std_dev));
+    let mut state = initialize_kernel_state(KernelId::Bbands, &kwargs);
+
+    let (batch_upper, batch_middle, batch_lower) =
+        bbands(&closes, period, std_dev, MovingAverageType::Sma);
+
+    for (i, &close) in closes.iter().enumerate() {
+        let tick = BTreeMap::from([("close".to_string(), IncrementalValue::Number(close))]);
+        let (new_state, out) =
+            eval_call_step(KernelId::Bbands, state, IncrementalValue::Number(close), &tick);
+        state = new_state;
+
+        if batch_middle[i].is_nan() {
+            assert!(matches!(out, IncrementalValue::Null), "expected warmup null at index {i}");
+            continue;
+        }
+
+        match out {
+            IncrementalValue::Fields(ref f) if f.len() == 3 => {
+                assert!((f[0] - batch_upper[i]).abs() < 1e-9, "upper mismatch at {i}: {f:?}");
+                assert!((f[1] - batch_middle[i]).abs() < 1e-9, "middle mismatch at {i}: {f:?}");
+                assert!((f[2] - batch_lower[i]).abs() < 1e-9, "lower mismatch at {i}: {f:?}");
+            }
+            other => panic!("expected 3-field output at index {i}, got {other:?}"),
+        }
+    }
+}
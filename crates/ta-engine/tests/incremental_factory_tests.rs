@@ -0,0 +1,129 @@
+use std::collections::BTreeMap;
+
+use ta_engine::incremental::backend::StepInputSource;
+use ta_engine::incremental::contracts::{IncrementalValue, TickUpdate};
+use ta_engine::incremental::factory::{build_incremental, ensure_required_fields, BuildIncrementalError};
+use ta_engine::incremental::kernel_registry::KernelId;
+use ta_engine::metadata::{
+    IndicatorAliasMeta, IndicatorMeta, IndicatorOutputMeta, IndicatorPaneHint, IndicatorParamDefault,
+    IndicatorParamKind, IndicatorParamMeta, IndicatorScaleGroup, IndicatorSemanticsMeta,
+    IndicatorVisualMeta,
+};
+use serde_json::json;
+
+fn rsi_meta() -> IndicatorMeta {
+    IndicatorMeta {
+        id: "rsi",
+        display_name: "Relative Strength Index",
+        category: "momentum",
+        aliases: &[],
+        param_aliases: &[IndicatorAliasMeta {
+            alias: "lookback",
+            target: "period",
+        }],
+        params: &[IndicatorParamMeta {
+            name: "period",
+            kind: IndicatorParamKind::Integer,
+            required: false,
+            default: Some(IndicatorParamDefault::Integer(14)),
+            description: "Lookback period",
+            min: Some(1.0),
+            max: None,
+            allowed: None,
+        }],
+        outputs: &[IndicatorOutputMeta {
+            name: "result",
+            kind: "line",
+            description: "RSI value",
+        }],
+        semantics: IndicatorSemanticsMeta {
+            required_fields: &["close"],
+            optional_fields: &[],
+            lookback_params: &["period"],
+            default_lookback: None,
+            warmup_policy: "window",
+            source_param: None,
+        },
+        visual: IndicatorVisualMeta {
+            pane_hint: IndicatorPaneHint::SeparatePane,
+            scale_group: IndicatorScaleGroup::Oscillator,
+            output_visuals: &[],
+            style_slots: &[],
+        },
+        runtime_binding: "rsi",
+        constraints: &[],
+    }
+}
+
+#[test]
+fn builds_a_kernel_step_request_from_catalog_params() {
+    let meta = rsi_meta();
+    let request = build_incremental(&meta, 1, &json!({"lookback": 9})).expect("should build");
+    assert_eq!(request.node_id, 1);
+    assert_eq!(request.kernel_id, KernelId::Rsi);
+    assert_eq!(request.input, StepInputSource::TickField("close".to_string()));
+    assert_eq!(
+        request.kwargs.get("period"),
+        Some(&IncrementalValue::Number(9.0))
+    );
+}
+
+#[test]
+fn applies_the_catalog_default_when_no_param_is_given() {
+    let meta = rsi_meta();
+    let request = build_incremental(&meta, 1, &json!({})).expect("should build");
+    assert_eq!(
+        request.kwargs.get("period"),
+        Some(&IncrementalValue::Number(14.0))
+    );
+}
+
+#[test]
+fn rejects_an_unknown_param() {
+    let meta = rsi_meta();
+    let err = build_incremental(&meta, 1, &json!({"not_a_param": 1})).unwrap_err();
+    assert!(matches!(err, BuildIncrementalError::InvalidParams { .. }));
+}
+
+#[test]
+fn resolves_volume_indicators_to_their_streaming_kernels() {
+    let cases = [
+        ("obv", KernelId::Obv),
+        ("cmf", KernelId::Cmf),
+        ("klinger_vf", KernelId::KlingerVf),
+    ];
+    for (id, expected_kernel) in cases {
+        let meta = ta_engine::metadata::find_indicator_meta(id)
+            .unwrap_or_else(|| panic!("catalog should know about '{id}'"));
+        let request = build_incremental(meta, 1, &json!({})).expect("should build");
+        assert_eq!(request.kernel_id, expected_kernel);
+    }
+}
+
+#[test]
+fn rejects_an_indicator_with_no_streaming_kernel() {
+    let mut meta = rsi_meta();
+    meta.id = "sma";
+    meta.runtime_binding = "sma";
+    let err = build_incremental(&meta, 1, &json!({})).unwrap_err();
+    assert_eq!(
+        err,
+        BuildIncrementalError::UnsupportedIndicator("sma".to_string())
+    );
+}
+
+#[test]
+fn ensure_required_fields_flags_a_missing_field() {
+    let meta = rsi_meta();
+    let tick = TickUpdate::new(1, BTreeMap::new());
+    let err = ensure_required_fields(&meta, &tick).unwrap_err();
+    assert_eq!(
+        err,
+        BuildIncrementalError::MissingRequiredField("close".to_string())
+    );
+
+    let mut fields = BTreeMap::new();
+    fields.insert("close".to_string(), IncrementalValue::Number(101.0));
+    let tick = TickUpdate::new(1, fields);
+    assert!(ensure_required_fields(&meta, &tick).is_ok());
+}
@@ -0,0 +1,154 @@
+use std::collections::BTreeMap;
+
+use ta_engine::contracts::{
+    RustExecutionGraph, RustExecutionPartition, RustExecutionPayload, RustExecutionRequest,
+};
+use ta_engine::dataset::{append_ohlcv, create_dataset, DatasetPartitionKey};
+use ta_engine::incremental::backend::execute_plan_graph_payload;
+use ta_engine::incremental::contracts::IncrementalValue;
+use ta_engine::incremental::graph_cse::canonicalize;
+
+/// A diamond: two independently-authored `source_ref(close)` nodes (1, 2)
+/// that are structurally identical, each fed into a distinct `unary_op`
+/// (3, 4), recombined at the root `binary_op` (10). Mirrors a plan where
+/// two branches both happen to reference the same underlying series.
+fn diamond_graph() -> RustExecutionGraph {
+    RustExecutionGraph {
+        root_id: 10,
+        node_order: vec![1, 2, 3, 4, 10],
+        nodes: BTreeMap::from([
+            (
+                1,
+                BTreeMap::from([
+                    ("kind".to_string(), "source_ref".to_string()),
+                    ("field".to_string(), "close".to_string()),
+                ]),
+            ),
+            (
+                2,
+                BTreeMap::from([
+                    ("kind".to_string(), "source_ref".to_string()),
+                    ("field".to_string(), "close".to_string()),
+                ]),
+            ),
+            (
+                3,
+                BTreeMap::from([
+                    ("kind".to_string(), "unary_op".to_string()),
+                    ("operator".to_string(), "neg".to_string()),
+                ]),
+            ),
+            (
+                4,
+                BTreeMap::from([
+                    ("kind".to_string(), "unary_op".to_string()),
+                    ("operator".to_string(), "pos".to_string()),
+                ]),
+            ),
+            (
+                10,
+                BTreeMap::from([
+                    ("kind".to_string(), "binary_op".to_string()),
+                    ("operator".to_string(), "add".to_string()),
+                ]),
+            ),
+        ]),
+        edges: BTreeMap::from([(3, vec![1]), (4, vec![2]), (10, vec![3, 4])]),
+    }
+}
+
+#[test]
+fn diamond_shaped_duplicate_subtree_collapses_to_one_canonical_node() {
+    let graph = diamond_graph();
+    let cse = canonicalize(&graph);
+
+    // Node 2 is a structural duplicate of node 1 (same kind + field, no
+    // children), so it should canonicalize onto node 1 instead of getting
+    // its own entry in the surviving graph.
+    assert_eq!(cse.canonical_of.get(&2), Some(&1));
+    assert_eq!(cse.graph.node_order, vec![1, 3, 4, 10]);
+}
+
+#[test]
+fn near_duplicate_nodes_differing_only_by_attribute_do_not_collapse() {
+    let graph = diamond_graph();
+    let cse = canonicalize(&graph);
+
+    // Nodes 3 and 4 are both `unary_op`, but `operator` differs (`neg` vs
+    // `pos`) -- a wrong merge here would silently return one node's output
+    // for the other, so they must canonicalize onto themselves, not each
+    // other.
+    assert_ne!(cse.canonical_of.get(&3), cse.canonical_of.get(&4));
+    assert_eq!(cse.canonical_of.get(&3), Some(&3));
+    assert_eq!(cse.canonical_of.get(&4), Some(&4));
+}
+
+#[test]
+fn diamond_shaped_plan_dispatches_the_shared_source_once_and_produces_correct_values() {
+    let dataset_id = create_dataset();
+    let key = DatasetPartitionKey {
+        symbol: "BTCUSDT".to_string(),
+        timeframe: "1m".to_string(),
+        source: "ohlcv".to_string(),
+    };
+    append_ohlcv(
+        dataset_id,
+        key.clone(),
+        &[0, 60_000, 120_000],
+        &[10.0, 11.0, 12.0],
+        &[10.0, 11.0, 12.0],
+        &[10.0, 11.0, 12.0],
+        &[10.0, 11.0, 12.0],
+        &[1.0, 1.0, 1.0],
+    )
+    .expect("ohlcv append should succeed");
+
+    let payload = RustExecutionPayload {
+        dataset_id,
+        partition: RustExecutionPartition {
+            symbol: key.symbol.clone(),
+            timeframe: key.timeframe.clone(),
+            source: key.source.clone(),
+        },
+        partitions: Vec::new(),
+        graph: diamond_graph(),
+        requests: Vec::<RustExecutionRequest>::new(),
+    };
+
+    // The canonical graph executed under the hood has one fewer node than
+    // the original -- the duplicate "close" read is dispatched exactly
+    // once, not twice.
+    let cse = canonicalize(&payload.graph);
+    assert_eq!(cse.graph.node_order.len(), payload.graph.node_order.len() - 1);
+
+    let outputs = execute_plan_graph_payload(&payload).expect("plan should execute");
+
+    // Both original "close" node ids still get their own entry in the
+    // result map, and agree since they're the same underlying series.
+    assert_eq!(outputs.get(&1), outputs.get(&2));
+    assert_eq!(
+        outputs[&1],
+        vec![
+            IncrementalValue::Number(10.0),
+            IncrementalValue::Number(11.0),
+            IncrementalValue::Number(12.0),
+        ]
+    );
+    assert_eq!(
+        outputs[&3],
+        vec![
+            IncrementalValue::Number(-10.0),
+            IncrementalValue::Number(-11.0),
+            IncrementalValue::Number(-12.0),
+        ]
+    );
+    assert_eq!(outputs[&4], outputs[&2]);
+    assert_eq!(
+        outputs[&10],
+        vec![
+            IncrementalValue::Number(0.0),
+            IncrementalValue::Number(0.0),
+            IncrementalValue::Number(0.0),
+        ]
+    );
+}
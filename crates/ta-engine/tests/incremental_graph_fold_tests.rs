@@ -0,0 +1,172 @@
+use std::collections::BTreeMap;
+
+use ta_engine::contracts::{
+    RustExecutionGraph, RustExecutionPartition, RustExecutionPayload, RustExecutionRequest,
+};
+use ta_engine::dataset::{append_ohlcv, create_dataset, DatasetPartitionKey};
+use ta_engine::incremental::backend::execute_plan_graph_payload;
+use ta_engine::incremental::contracts::IncrementalValue;
+use ta_engine::incremental::graph_fold::fold_constants;
+
+fn literal_node(value: &str) -> BTreeMap<String, String> {
+    BTreeMap::from([
+        ("kind".to_string(), "literal".to_string()),
+        ("value".to_string(), value.to_string()),
+    ])
+}
+
+fn binary_node(operator: &str) -> BTreeMap<String, String> {
+    BTreeMap::from([
+        ("kind".to_string(), "binary_op".to_string()),
+        ("operator".to_string(), operator.to_string()),
+    ])
+}
+
+fn unary_node(operator: &str) -> BTreeMap<String, String> {
+    BTreeMap::from([
+        ("kind".to_string(), "unary_op".to_string()),
+        ("operator".to_string(), operator.to_string()),
+    ])
+}
+
+/// `neg(mul(2, 3))`: node 3 (mul) folds children 1 and 2, then node 4 (neg)
+/// folds over node 3 -- a chain of purely-literal arithmetic.
+fn literal_chain_graph() -> RustExecutionGraph {
+    RustExecutionGraph {
+        root_id: 4,
+        node_order: vec![1, 2, 3, 4],
+        nodes: BTreeMap::from([
+            (1, literal_node("2")),
+            (2, literal_node("3")),
+            (3, binary_node("mul")),
+            (4, unary_node("neg")),
+        ]),
+        edges: BTreeMap::from([(3, vec![1, 2]), (4, vec![3])]),
+    }
+}
+
+#[test]
+fn a_chain_of_literal_arithmetic_collapses_to_a_literal_at_every_folded_node() {
+    let graph = literal_chain_graph();
+    let folded = fold_constants(&graph);
+
+    assert_eq!(folded.nodes[&3]["kind"], "literal");
+    assert_eq!(folded.nodes[&3]["value"], "6");
+    assert!(folded.edges[&3].is_empty());
+
+    assert_eq!(folded.nodes[&4]["kind"], "literal");
+    assert_eq!(folded.nodes[&4]["value"], "-6");
+    assert!(folded.edges[&4].is_empty());
+
+    // node_order and node ids are untouched -- every original id still
+    // has an entry, just rewritten to a literal where it was foldable.
+    assert_eq!(folded.node_order, graph.node_order);
+}
+
+#[test]
+fn folding_is_idempotent() {
+    let graph = literal_chain_graph();
+    let once = fold_constants(&graph);
+    let twice = fold_constants(&once);
+    assert_eq!(once.nodes, twice.nodes);
+    assert_eq!(once.edges, twice.edges);
+}
+
+#[test]
+fn a_non_literal_operand_prevents_folding_of_its_parent() {
+    // Node 2 is a source_ref, not a literal, so the "add" node (3) that
+    // reads it can never be folded even though its sibling operand is a
+    // literal chain.
+    let graph = RustExecutionGraph {
+        root_id: 3,
+        node_order: vec![1, 2, 3],
+        nodes: BTreeMap::from([
+            (1, literal_node("5")),
+            (
+                2,
+                BTreeMap::from([
+                    ("kind".to_string(), "source_ref".to_string()),
+                    ("field".to_string(), "close".to_string()),
+                ]),
+            ),
+            (3, binary_node("add")),
+        ]),
+        edges: BTreeMap::from([(3, vec![1, 2])]),
+    };
+
+    let folded = fold_constants(&graph);
+    assert_eq!(folded.nodes[&3]["kind"], "binary_op");
+    assert_eq!(folded.edges[&3], vec![1, 2]);
+}
+
+#[test]
+fn folded_outputs_are_bit_identical_to_running_the_unfolded_plan() {
+    let dataset_id = create_dataset();
+    let key = DatasetPartitionKey {
+        symbol: "BTCUSDT".to_string(),
+        timeframe: "1m".to_string(),
+        source: "ohlcv".to_string(),
+    };
+    append_ohlcv(
+        dataset_id,
+        key.clone(),
+        &[0, 60_000, 120_000],
+        &[10.0, 11.0, 12.0],
+        &[10.0, 11.0, 12.0],
+        &[10.0, 11.0, 12.0],
+        &[10.0, 11.0, 12.0],
+        &[1.0, 1.0, 1.0],
+    )
+    .expect("ohlcv append should succeed");
+
+    // root = add(close, mul(2, 3)) -- the mul branch is a pure literal
+    // chain that should fold to 6 before execution.
+    let graph = RustExecutionGraph {
+        root_id: 5,
+        node_order: vec![1, 2, 3, 4, 5],
+        nodes: BTreeMap::from([
+            (
+                1,
+                BTreeMap::from([
+                    ("kind".to_string(), "source_ref".to_string()),
+                    ("field".to_string(), "close".to_string()),
+                ]),
+            ),
+            (2, literal_node("2")),
+            (3, literal_node("3")),
+            (4, binary_node("mul")),
+            (5, binary_node("add")),
+        ]),
+        edges: BTreeMap::from([(4, vec![2, 3]), (5, vec![1, 4])]),
+    };
+
+    let payload = RustExecutionPayload {
+        dataset_id,
+        partition: RustExecutionPartition {
+            symbol: key.symbol.clone(),
+            timeframe: key.timeframe.clone(),
+            source: key.source.clone(),
+        },
+        partitions: Vec::new(),
+        graph,
+        requests: Vec::<RustExecutionRequest>::new(),
+    };
+
+    let outputs = execute_plan_graph_payload(&payload).expect("plan should execute");
+    assert_eq!(
+        outputs[&5],
+        vec![
+            IncrementalValue::Number(16.0),
+            IncrementalValue::Number(17.0),
+            IncrementalValue::Number(18.0),
+        ]
+    );
+    assert_eq!(
+        outputs[&4],
+        vec![
+            IncrementalValue::Number(6.0),
+            IncrementalValue::Number(6.0),
+            IncrementalValue::Number(6.0),
+        ]
+    );
+}
@@ -0,0 +1,60 @@
+use std::collections::BTreeMap;
+
+use ta_engine::incremental::graph_order::{DependencyGraph, GraphOrderError};
+
+#[test]
+fn orders_leaves_before_the_nodes_that_read_from_them() {
+    // 3 (stoch_d) reads from 2 (stoch_k smoothing), 2 reads from 1 (source_ref).
+    let mut edges = BTreeMap::new();
+    edges.insert(2, vec![1]);
+    edges.insert(3, vec![2]);
+    let graph = DependencyGraph::new(vec![1, 2, 3], edges);
+
+    let order = graph.topological_order().expect("graph should be acyclic");
+    assert_eq!(order, vec![1, 2, 3]);
+}
+
+#[test]
+fn orders_independent_branches_by_node_id_when_both_are_ready() {
+    // 4 reads from both 1 and 2; 1, 2, 3 have no inputs.
+    let mut edges = BTreeMap::new();
+    edges.insert(4, vec![1, 2]);
+    let graph = DependencyGraph::new(vec![1, 2, 3, 4], edges);
+
+    let order = graph.topological_order().expect("graph should be acyclic");
+    assert_eq!(order, vec![1, 2, 3, 4]);
+}
+
+#[test]
+fn detects_a_cycle_and_names_the_nodes_still_stuck() {
+    // 1 reads from 2, 2 reads from 1 -- neither ever reaches in-degree zero.
+    let mut edges = BTreeMap::new();
+    edges.insert(1, vec![2]);
+    edges.insert(2, vec![1]);
+    let graph = DependencyGraph::new(vec![1, 2], edges);
+
+    let err = graph.topological_order().expect_err("cycle should be reported");
+    match err {
+        GraphOrderError::CycleDetected(mut nodes) => {
+            nodes.sort_unstable();
+            assert_eq!(nodes, vec![1, 2]);
+        }
+    }
+}
+
+#[test]
+fn a_cycle_downstream_of_a_valid_root_is_still_reported() {
+    // 1 has no inputs and resolves fine; 2 and 3 depend on each other.
+    let mut edges = BTreeMap::new();
+    edges.insert(2, vec![3]);
+    edges.insert(3, vec![2]);
+    let graph = DependencyGraph::new(vec![1, 2, 3], edges);
+
+    let err = graph.topological_order().expect_err("cycle should be reported");
+    match err {
+        GraphOrderError::CycleDetected(mut nodes) => {
+            nodes.sort_unstable();
+            assert_eq!(nodes, vec![2, 3]);
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use std::collections::BTreeMap;
+
+use ta_engine::contracts::{
+    RustExecutionGraph, RustExecutionPartition, RustExecutionPayload, RustExecutionRequest,
+};
+use ta_engine::dataset::{append_ohlcv, create_dataset, DatasetPartitionKey};
+use ta_engine::incremental::backend::execute_plan_graph_payload;
+use ta_engine::incremental::contracts::IncrementalValue;
+use ta_engine::incremental::graph_type_check::{check_and_insert_casts, ValueType};
+
+fn literal_node(value: &str) -> BTreeMap<String, String> {
+    BTreeMap::from([
+        ("kind".to_string(), "literal".to_string()),
+        ("value".to_string(), value.to_string()),
+    ])
+}
+
+#[test]
+fn a_numeric_child_of_a_logical_and_is_cast_to_bool_instead_of_rejected() {
+    // Node 1 and 2 are numeric literals feeding a logical "and" (node 3),
+    // which expects Bool on both sides -- a legal num->bool coercion, so a
+    // cast node should be spliced onto each edge rather than erroring.
+    let graph = RustExecutionGraph {
+        root_id: 3,
+        node_order: vec![1, 2, 3],
+        nodes: BTreeMap::from([
+            (1, literal_node("5")),
+            (2, literal_node("0")),
+            (
+                3,
+                BTreeMap::from([
+                    ("kind".to_string(), "binary_op".to_string()),
+                    ("operator".to_string(), "and".to_string()),
+                ]),
+            ),
+        ]),
+        edges: BTreeMap::from([(3, vec![1, 2])]),
+    };
+
+    let checked = check_and_insert_casts(&graph).expect("numeric operands should coerce to bool");
+
+    assert_eq!(checked.graph.node_order.len(), 5);
+    assert_eq!(checked.output_type_of[&3], ValueType::Bool);
+
+    let cast_children = checked.graph.edges[&3].clone();
+    assert_eq!(cast_children.len(), 2);
+    for cast_id in &cast_children {
+        let cast_meta = &checked.graph.nodes[cast_id];
+        assert_eq!(cast_meta["kind"], "unary_op");
+        assert_eq!(cast_meta["operator"], "to_bool");
+    }
+}
+
+#[test]
+fn a_text_literal_feeding_an_arithmetic_operator_has_no_legal_coercion() {
+    // Node 1 is a Text literal (doesn't parse as a number or bool), fed
+    // into "add" (node 2) alongside a numeric literal -- Text has no legal
+    // coercion to Number, so this must fail instead of silently defaulting
+    // through as_number's NaN fallback.
+    let graph = RustExecutionGraph {
+        root_id: 2,
+        node_order: vec![1, 3, 2],
+        nodes: BTreeMap::from([
+            (1, literal_node("not-a-number")),
+            (3, literal_node("1")),
+            (
+                2,
+                BTreeMap::from([
+                    ("kind".to_string(), "binary_op".to_string()),
+                    ("operator".to_string(), "add".to_string()),
+                ]),
+            ),
+        ]),
+        edges: BTreeMap::from([(2, vec![1, 3])]),
+    };
+
+    let err = check_and_insert_casts(&graph).unwrap_err();
+    let message = err.to_string();
+    assert!(message.contains("node 2"));
+    assert!(message.contains("input 0"));
+    assert!(message.contains("Number"));
+    assert!(message.contains("Text"));
+}
+
+#[test]
+fn a_filter_condition_that_is_actually_numeric_is_cast_before_execution() {
+    let dataset_id = create_dataset();
+    let key = DatasetPartitionKey {
+        symbol: "BTCUSDT".to_string(),
+        timeframe: "1m".to_string(),
+        source: "ohlcv".to_string(),
+    };
+    append_ohlcv(
+        dataset_id,
+        key.clone(),
+        &[0, 60_000, 120_000],
+        &[10.0, 11.0, 12.0],
+        &[10.0, 11.0, 12.0],
+        &[10.0, 11.0, 12.0],
+        &[10.0, 0.0, 12.0],
+        &[1.0, 1.0, 1.0],
+    )
+    .expect("ohlcv append should succeed");
+
+    // Node 2 reads volume (0 on the middle row), directly driving the
+    // filter's condition (node 3) even though it's a Number series, not a
+    // Bool one -- it should get cast to Bool (!= 0) rather than rejected.
+    let graph = RustExecutionGraph {
+        root_id: 3,
+        node_order: vec![1, 2, 3],
+        nodes: BTreeMap::from([
+            (
+                1,
+                BTreeMap::from([
+                    ("kind".to_string(), "source_ref".to_string()),
+                    ("field".to_string(), "close".to_string()),
+                ]),
+            ),
+            (
+                2,
+                BTreeMap::from([
+                    ("kind".to_string(), "source_ref".to_string()),
+                    ("field".to_string(), "volume".to_string()),
+                ]),
+            ),
+            (
+                3,
+                BTreeMap::from([("kind".to_string(), "filter".to_string())]),
+            ),
+        ]),
+        edges: BTreeMap::from([(3, vec![1, 2])]),
+    };
+
+    let payload = RustExecutionPayload {
+        dataset_id,
+        partition: RustExecutionPartition {
+            symbol: key.symbol.clone(),
+            timeframe: key.timeframe.clone(),
+            source: key.source.clone(),
+        },
+        partitions: Vec::new(),
+        graph,
+        requests: Vec::<RustExecutionRequest>::new(),
+    };
+
+    let outputs = execute_plan_graph_payload(&payload).expect("plan should execute with an inserted cast");
+    assert_eq!(
+        outputs[&3],
+        vec![
+            IncrementalValue::Number(10.0),
+            IncrementalValue::Null,
+            IncrementalValue::Number(12.0),
+        ]
+    );
+}
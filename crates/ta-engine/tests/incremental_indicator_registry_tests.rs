@@ -0,0 +1,90 @@
+use std::collections::BTreeMap;
+
+use ta_engine::incremental::contracts::IncrementalValue;
+use ta_engine::incremental::indicator_registry::IndicatorInstanceRegistry;
+use ta_engine::incremental::kernel_registry::KernelId;
+
+fn tick(close: f64) -> BTreeMap<String, IncrementalValue> {
+    BTreeMap::from([("close".to_string(), IncrementalValue::Number(close))])
+}
+
+fn rsi_params(period: f64) -> BTreeMap<String, IncrementalValue> {
+    BTreeMap::from([("period".to_string(), IncrementalValue::Number(period))])
+}
+
+#[test]
+fn get_or_create_returns_the_same_node_id_for_a_repeated_request() {
+    let mut registry = IndicatorInstanceRegistry::new();
+    let params = rsi_params(14.0);
+    let history = vec![tick(10.0), tick(11.0), tick(12.0)];
+
+    let first = registry
+        .get_or_create(KernelId::Rsi, "1m", "close", &params, &history)
+        .expect("should allocate");
+    let second = registry
+        .get_or_create(KernelId::Rsi, "1m", "close", &params, &history)
+        .expect("should reuse the cached instance");
+
+    assert_eq!(first, second);
+}
+
+#[test]
+fn different_intervals_or_params_get_distinct_instances() {
+    let mut registry = IndicatorInstanceRegistry::new();
+    let history = vec![tick(10.0), tick(11.0)];
+
+    let one_minute = registry
+        .get_or_create(KernelId::Rsi, "1m", "close", &rsi_params(14.0), &history)
+        .expect("should allocate");
+    let five_minute = registry
+        .get_or_create(KernelId::Rsi, "5m", "close", &rsi_params(14.0), &history)
+        .expect("should allocate");
+    let different_period = registry
+        .get_or_create(KernelId::Rsi, "1m", "close", &rsi_params(21.0), &history)
+        .expect("should allocate");
+
+    assert_ne!(one_minute, five_minute);
+    assert_ne!(one_minute, different_period);
+    assert_ne!(five_minute, different_period);
+}
+
+#[test]
+fn on_bar_closed_only_advances_instances_subscribed_to_that_interval() {
+    let mut registry = IndicatorInstanceRegistry::new();
+    let history = vec![tick(10.0), tick(11.0), tick(12.0)];
+
+    registry
+        .get_or_create(KernelId::Rsi, "1m", "close", &rsi_params(2.0), &history)
+        .expect("should allocate");
+    registry
+        .get_or_create(KernelId::Rsi, "5m", "close", &rsi_params(2.0), &history)
+        .expect("should allocate");
+
+    let outputs = registry
+        .on_bar_closed("1m", &tick(13.0))
+        .expect("should step the 1m instance");
+    assert_eq!(outputs.len(), 1);
+
+    let outputs = registry
+        .on_bar_closed("15m", &tick(13.0))
+        .expect("an interval with no subscribers is a no-op");
+    assert!(outputs.is_empty());
+}
+
+#[test]
+fn backfill_through_history_warms_up_the_instance_before_live_ticks_arrive() {
+    let mut registry = IndicatorInstanceRegistry::new();
+    let history: Vec<_> = (1..=5).map(|v| tick(v as f64)).collect();
+
+    let node_id = registry
+        .get_or_create(KernelId::Rsi, "1m", "close", &rsi_params(2.0), &history)
+        .expect("should allocate and backfill");
+
+    let outputs = registry
+        .on_bar_closed("1m", &tick(6.0))
+        .expect("should step after backfill");
+    assert!(matches!(
+        outputs.get(&node_id),
+        Some(IncrementalValue::Number(_))
+    ));
+}
@@ -0,0 +1,58 @@
+use ta_engine::incremental::int_map::IntMap;
+
+#[test]
+fn insert_get_and_remove_round_trip() {
+    let mut map: IntMap<&str> = IntMap::new();
+    assert!(map.is_empty());
+
+    assert_eq!(map.insert(3, "three"), None);
+    assert_eq!(map.insert(0, "zero"), None);
+    assert_eq!(map.len(), 2);
+
+    assert_eq!(map.get(3), Some(&"three"));
+    assert_eq!(map.get(0), Some(&"zero"));
+    assert_eq!(map.get(1), None);
+
+    assert_eq!(map.remove(3), Some("three"));
+    assert_eq!(map.get(3), None);
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn insert_over_an_existing_key_returns_the_previous_value() {
+    let mut map = IntMap::new();
+    map.insert(5, "first");
+    assert_eq!(map.insert(5, "second"), Some("first"));
+    assert_eq!(map.get(5), Some(&"second"));
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn iter_yields_only_occupied_slots_in_key_order() {
+    let mut map = IntMap::new();
+    map.insert(4, "d");
+    map.insert(1, "a");
+    map.insert(2, "b");
+    map.remove(2);
+
+    let pairs: Vec<_> = map.iter().collect();
+    assert_eq!(pairs, vec![(1, &"a"), (4, &"d")]);
+}
+
+#[test]
+fn clear_empties_the_map() {
+    let mut map = IntMap::new();
+    map.insert(1, "a");
+    map.insert(2, "b");
+    map.clear();
+    assert!(map.is_empty());
+    assert_eq!(map.get(1), None);
+}
+
+#[test]
+fn from_iter_builds_an_equivalent_map() {
+    let map: IntMap<&str> = [(2, "b"), (0, "a")].into_iter().collect();
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get(0), Some(&"a"));
+    assert_eq!(map.get(2), Some(&"b"));
+}
@@ -0,0 +1,231 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use ta_engine::incremental::backend::{IncrementalBackend, KernelStepRequest, StepInputSource};
+use ta_engine::incremental::call_step::KernelRuntimeState;
+use ta_engine::incremental::codec::{decode_kernel_state_binary, encode_kernel_state_binary};
+use ta_engine::incremental::contracts::IncrementalValue;
+use ta_engine::incremental::kernel_registry::KernelId;
+use ta_engine::incremental::sliding_extrema::SlidingExtrema;
+
+fn tick(h: f64, l: f64, c: f64) -> BTreeMap<String, IncrementalValue> {
+    BTreeMap::from([
+        ("high".to_string(), IncrementalValue::Number(h)),
+        ("low".to_string(), IncrementalValue::Number(l)),
+        ("close".to_string(), IncrementalValue::Number(c)),
+    ])
+}
+
+#[test]
+fn snapshot_restore_replay_is_bit_exact_for_irrational_window_values() {
+    let requests = vec![KernelStepRequest {
+        node_id: 1,
+        kernel_id: KernelId::Stochastic,
+        input: StepInputSource::TickField("close".to_string()),
+        kwargs: BTreeMap::from([("k_period".to_string(), IncrementalValue::Number(3.0))]),
+    }];
+
+    let irrational: Vec<f64> = (1..=6).map(|n| (n as f64).sqrt() * 10.0_f64.sqrt()).collect();
+    let warmup_events: Vec<_> = irrational[..3]
+        .iter()
+        .map(|v| tick(*v + 1.0, *v - 1.0, *v))
+        .collect();
+    let tail_events: Vec<_> = irrational[3..]
+        .iter()
+        .map(|v| tick(*v + 1.0, *v - 1.0, *v))
+        .collect();
+
+    let mut original: IncrementalBackend = IncrementalBackend::default();
+    original
+        .replay(0, 1, &requests, &warmup_events)
+        .expect("replay should succeed");
+    let snapshot = original.snapshot();
+
+    let mut restored: IncrementalBackend = IncrementalBackend::default();
+    restored.restore(snapshot).expect("restore should succeed");
+
+    let tail_start = warmup_events.len() as u64 + 1;
+    let from_original = original
+        .replay(0, tail_start, &requests, &tail_events)
+        .expect("replay should succeed");
+    let from_restored = restored
+        .replay(0, tail_start, &requests, &tail_events)
+        .expect("replay should succeed");
+
+    assert_eq!(
+        from_original, from_restored,
+        "replaying past a snapshot restore must match uninterrupted replay bit-for-bit"
+    );
+}
+
+/// One populated instance of every [`KernelRuntimeState`] variant -- window
+/// fields carry more than one value so a vector-length bug couldn't hide
+/// behind an empty/singleton window, and `Generic` is included once per
+/// [`KernelId`] that has no dedicated streaming kernel yet, since that's
+/// exactly the case the old CSV codec lost (it decoded every `Generic` back
+/// as `KernelId::Rsi` regardless of which kernel it actually stood in for).
+fn sample_states() -> Vec<KernelRuntimeState> {
+    vec![
+        KernelRuntimeState::Rsi {
+            period: 14,
+            prev_close: Some(101.5),
+            avg_gain: Some(1.25),
+            avg_loss: Some(0.75),
+            count: 20,
+        },
+        KernelRuntimeState::Atr {
+            period: 14,
+            prev_close: Some(99.0),
+            rma_tr: Some(2.5),
+            count: 9,
+        },
+        KernelRuntimeState::Stochastic {
+            k_period: 3,
+            highs: SlidingExtrema::from_values(3, &[10.0, 12.0, 11.0]),
+            lows: SlidingExtrema::from_values(3, &[9.0, 9.5, 8.5]),
+        },
+        KernelRuntimeState::Obv {
+            running_total: 123_456.0,
+            last_close: Some(42.0),
+        },
+        KernelRuntimeState::Cmf {
+            period: 20,
+            mfv_window: vec![1.1, -2.2, 3.3],
+            volume_window: vec![100.0, 200.0, 300.0],
+        },
+        KernelRuntimeState::Vwap {
+            sum_pv: 98_765.4,
+            sum_vol: 4_321.0,
+        },
+        KernelRuntimeState::KlingerVf {
+            fast_period: 34,
+            slow_period: 55,
+            prev_tp: Some(50.0),
+            ema_fast: Some(12.3),
+            ema_slow: Some(45.6),
+        },
+        KernelRuntimeState::Cci {
+            period: 20,
+            tp_window: VecDeque::from(vec![10.0, 11.0, 12.0]),
+        },
+        KernelRuntimeState::WilliamsR {
+            period: 14,
+            highs: SlidingExtrema::from_values(14, &[5.0, 6.0]),
+            lows: SlidingExtrema::from_values(14, &[3.0, 2.0]),
+        },
+        KernelRuntimeState::Mfi {
+            period: 14,
+            prev_tp: Some(77.7),
+            pos_window: VecDeque::from(vec![1.0, 2.0]),
+            neg_window: VecDeque::from(vec![0.5, 1.5]),
+        },
+        KernelRuntimeState::Vortex {
+            period: 14,
+            prev_high: Some(20.0),
+            prev_low: Some(18.0),
+            prev_close: Some(19.0),
+            tr_window: VecDeque::from(vec![1.0, 1.5, 2.0]),
+            vm_plus_window: VecDeque::from(vec![0.5, 0.7]),
+            vm_minus_window: VecDeque::from(vec![0.3, 0.2]),
+        },
+        KernelRuntimeState::Cmo {
+            period: 14,
+            prev_value: Some(5.0),
+            gains_window: VecDeque::from(vec![2.0, 3.0]),
+            losses_window: VecDeque::from(vec![1.0]),
+        },
+        KernelRuntimeState::Bbands {
+            period: 20,
+            std_dev: 2.0,
+            window: VecDeque::from(vec![10.0, 11.0, 9.5]),
+            sum: 30.5,
+            sumsq: 312.25,
+        },
+        KernelRuntimeState::Generic {
+            kernel_id: KernelId::Macd,
+        },
+        KernelRuntimeState::Generic {
+            kernel_id: KernelId::Adx,
+        },
+    ]
+}
+
+#[test]
+fn every_kernel_runtime_state_variant_round_trips_through_the_binary_codec() {
+    for state in sample_states() {
+        let encoded = encode_kernel_state_binary(&state);
+        let decoded = decode_kernel_state_binary(&encoded)
+            .expect("a blob this build just wrote must decode cleanly");
+        assert_eq!(decoded, state, "round trip must be structurally exact for {state:?}");
+    }
+}
+
+#[test]
+fn generic_state_preserves_its_concrete_kernel_id_through_the_binary_codec() {
+    for kernel_id in [KernelId::Macd, KernelId::Adx] {
+        let state = KernelRuntimeState::Generic { kernel_id };
+        let decoded = decode_kernel_state_binary(&encode_kernel_state_binary(&state))
+            .expect("a blob this build just wrote must decode cleanly");
+        match decoded {
+            KernelRuntimeState::Generic { kernel_id: decoded_id } => {
+                assert_eq!(decoded_id, kernel_id);
+            }
+            other => panic!("expected Generic, got {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn snapshot_restore_replay_matches_across_every_kernel_kind_including_unsupported_ones() {
+    // Mirrors `snapshot_restore_replay_is_bit_exact_for_irrational_window_values`,
+    // but over one request per kernel kind the backend knows about,
+    // including `KernelId::Macd` -- which has no streaming implementation
+    // yet and so runs as `KernelRuntimeState::Generic` -- so a restored
+    // `Generic` node keeps replaying as itself instead of silently turning
+    // into an RSI node, the exact corruption the CSV codec used to cause.
+    let requests = vec![
+        KernelStepRequest {
+            node_id: 1,
+            kernel_id: KernelId::Rsi,
+            input: StepInputSource::TickField("close".to_string()),
+            kwargs: BTreeMap::from([("period".to_string(), IncrementalValue::Number(3.0))]),
+        },
+        KernelStepRequest {
+            node_id: 2,
+            kernel_id: KernelId::Stochastic,
+            input: StepInputSource::TickField("close".to_string()),
+            kwargs: BTreeMap::from([("k_period".to_string(), IncrementalValue::Number(3.0))]),
+        },
+        KernelStepRequest {
+            node_id: 3,
+            kernel_id: KernelId::Macd,
+            input: StepInputSource::TickField("close".to_string()),
+            kwargs: BTreeMap::new(),
+        },
+    ];
+
+    let events: Vec<_> = (1..=8)
+        .map(|n| tick(n as f64 + 1.0, n as f64 - 1.0, n as f64))
+        .collect();
+    let (warmup, tail) = events.split_at(4);
+
+    let mut original: IncrementalBackend = IncrementalBackend::default();
+    original.replay(0, 1, &requests, warmup).expect("replay should succeed");
+    let snapshot = original.snapshot();
+
+    let mut restored: IncrementalBackend = IncrementalBackend::default();
+    restored.restore(snapshot).expect("restore should succeed");
+
+    let tail_start = warmup.len() as u64 + 1;
+    let from_original = original
+        .replay(0, tail_start, &requests, tail)
+        .expect("replay should succeed");
+    let from_restored = restored
+        .replay(0, tail_start, &requests, tail)
+        .expect("replay should succeed");
+
+    assert_eq!(
+        from_original, from_restored,
+        "every kernel kind, including an unsupported one backed by Generic, must replay \
+         identically after a snapshot restore"
+    );
+}
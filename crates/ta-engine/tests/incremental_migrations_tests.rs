@@ -0,0 +1,134 @@
+use std::collections::BTreeMap;
+
+use ta_engine::incremental::backend::{IncrementalBackend, KernelStepRequest, StepInputSource};
+use ta_engine::incremental::contracts::{
+    IncrementalValue, NodeSnapshotState, RuntimeSnapshot, INCREMENTAL_STATE_SCHEMA_VERSION,
+};
+use ta_engine::incremental::kernel_registry::KernelId;
+use ta_engine::incremental::migrations::{migrate_snapshot, migrate_through, MigrationError};
+
+fn sample_snapshot(schema_version: u16) -> RuntimeSnapshot {
+    let mut nodes = BTreeMap::new();
+    nodes.insert(
+        1,
+        NodeSnapshotState {
+            ticks_processed: 3,
+            last_output: IncrementalValue::Number(42.5),
+            state_blob: BTreeMap::from([(
+                "kind".to_string(),
+                IncrementalValue::Text("rsi".to_string()),
+            )]),
+            clock: BTreeMap::new(),
+        },
+    );
+    RuntimeSnapshot {
+        schema_version,
+        last_event_index: 7,
+        nodes,
+    }
+}
+
+#[test]
+fn current_schema_version_requires_no_migration() {
+    let snapshot = sample_snapshot(INCREMENTAL_STATE_SCHEMA_VERSION);
+    let migrated = migrate_snapshot(snapshot.clone()).expect("current version needs no migration");
+    assert_eq!(migrated, snapshot);
+}
+
+#[test]
+fn rejects_a_schema_version_newer_than_supported() {
+    let snapshot = sample_snapshot(INCREMENTAL_STATE_SCHEMA_VERSION + 1);
+    let err = migrate_snapshot(snapshot).unwrap_err();
+    assert_eq!(
+        err,
+        MigrationError::UnsupportedVersion(INCREMENTAL_STATE_SCHEMA_VERSION + 1)
+    );
+}
+
+#[test]
+fn reports_a_missing_migration_path_instead_of_guessing() {
+    // No migration is registered to advance from a hypothetical older
+    // version, so restoring one must fail loudly rather than silently
+    // treating it as current.
+    let older_version = INCREMENTAL_STATE_SCHEMA_VERSION - 1;
+    let snapshot = sample_snapshot(older_version);
+    let err = migrate_snapshot(snapshot).unwrap_err();
+    assert_eq!(err, MigrationError::NoMigrationPath(older_version));
+}
+
+#[test]
+fn migrate_through_upgrades_an_older_snapshot_across_multiple_steps() {
+    fn v0_to_v1(mut snapshot: RuntimeSnapshot) -> Result<RuntimeSnapshot, MigrationError> {
+        snapshot.schema_version = 1;
+        snapshot.last_event_index += 1;
+        Ok(snapshot)
+    }
+    fn v1_to_v2(mut snapshot: RuntimeSnapshot) -> Result<RuntimeSnapshot, MigrationError> {
+        snapshot.schema_version = 2;
+        Ok(snapshot)
+    }
+
+    let snapshot = sample_snapshot(0);
+    let migrated = migrate_through(snapshot, &[(0, v0_to_v1), (1, v1_to_v2)], 2)
+        .expect("a fully registered chain should upgrade the snapshot to the target version");
+
+    assert_eq!(migrated.schema_version, 2);
+    assert_eq!(migrated.last_event_index, 8);
+}
+
+#[test]
+fn migrate_through_propagates_a_transform_error() {
+    fn always_fails(_snapshot: RuntimeSnapshot) -> Result<RuntimeSnapshot, MigrationError> {
+        Err(MigrationError::TransformFailed(
+            0,
+            "malformed legacy state_blob".to_string(),
+        ))
+    }
+
+    let snapshot = sample_snapshot(0);
+    let err = migrate_through(snapshot, &[(0, always_fails)], 1).unwrap_err();
+    assert_eq!(
+        err,
+        MigrationError::TransformFailed(0, "malformed legacy state_blob".to_string())
+    );
+}
+
+#[test]
+fn replay_continuity_matches_a_natively_built_current_version_store() {
+    let requests = vec![KernelStepRequest {
+        node_id: 1,
+        kernel_id: KernelId::Rsi,
+        input: StepInputSource::TickField("close".to_string()),
+        kwargs: BTreeMap::from([("period".to_string(), IncrementalValue::Number(2.0))]),
+    }];
+    let warmup_events: Vec<_> = [10.0, 11.0, 12.0]
+        .iter()
+        .map(|c| BTreeMap::from([("close".to_string(), IncrementalValue::Number(*c))]))
+        .collect();
+    let tail_events: Vec<_> = [11.5, 13.0]
+        .iter()
+        .map(|c| BTreeMap::from([("close".to_string(), IncrementalValue::Number(*c))]))
+        .collect();
+
+    let mut native: IncrementalBackend = IncrementalBackend::default();
+    native
+        .replay(0, 1, &requests, &warmup_events)
+        .expect("replay should succeed");
+    let snapshot = native.snapshot();
+    assert_eq!(snapshot.schema_version, INCREMENTAL_STATE_SCHEMA_VERSION);
+
+    let mut restored: IncrementalBackend = IncrementalBackend::default();
+    restored
+        .restore(snapshot)
+        .expect("a current-version snapshot should restore without a migration");
+
+    let tail_start = warmup_events.len() as u64 + 1;
+    assert_eq!(
+        native
+            .replay(0, tail_start, &requests, &tail_events)
+            .expect("replay should succeed"),
+        restored
+            .replay(0, tail_start, &requests, &tail_events)
+            .expect("replay should succeed")
+    );
+}
@@ -0,0 +1,197 @@
+use ta_engine::incremental::contracts::IncrementalValue;
+use ta_engine::incremental::node_adapters::{
+    eval_aggregate_step, eval_time_shift_step, AggregateState, TimeShiftState,
+};
+
+#[test]
+fn var_and_std_are_null_before_two_samples() {
+    let mut var_state = AggregateState::default();
+    let mut std_state = AggregateState::default();
+    assert_eq!(
+        eval_aggregate_step("var", None, &IncrementalValue::Number(5.0), &mut var_state),
+        IncrementalValue::Null
+    );
+    assert_eq!(
+        eval_aggregate_step("std", None, &IncrementalValue::Number(5.0), &mut std_state),
+        IncrementalValue::Null
+    );
+}
+
+#[test]
+fn var_and_std_match_the_sample_formula() {
+    let mut var_state = AggregateState::default();
+    let mut std_state = AggregateState::default();
+    let mut last_var = IncrementalValue::Null;
+    let mut last_std = IncrementalValue::Null;
+    for v in [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0] {
+        last_var = eval_aggregate_step("var", None, &IncrementalValue::Number(v), &mut var_state);
+        last_std = eval_aggregate_step("std", None, &IncrementalValue::Number(v), &mut std_state);
+    }
+
+    let values = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    let expected_var =
+        values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64;
+
+    match (last_var, last_std) {
+        (IncrementalValue::Number(var), IncrementalValue::Number(std)) => {
+            assert!((var - expected_var).abs() < 1e-9);
+            assert!((std - expected_var.sqrt()).abs() < 1e-9);
+        }
+        other => panic!("expected numeric var/std, got {other:?}"),
+    }
+}
+
+#[test]
+fn zscore_is_null_before_two_samples_and_when_std_is_zero() {
+    let mut state = AggregateState::default();
+    assert_eq!(
+        eval_aggregate_step("zscore", None, &IncrementalValue::Number(5.0), &mut state),
+        IncrementalValue::Null
+    );
+    assert_eq!(
+        eval_aggregate_step("zscore", None, &IncrementalValue::Number(5.0), &mut state),
+        IncrementalValue::Null
+    );
+}
+
+#[test]
+fn zscore_matches_value_minus_mean_over_std() {
+    let mut state = AggregateState::default();
+    let mut last = IncrementalValue::Null;
+    for v in [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0] {
+        last = eval_aggregate_step("zscore", None, &IncrementalValue::Number(v), &mut state);
+    }
+
+    let values = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    let var = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64;
+    let expected = (values[values.len() - 1] - mean) / var.sqrt();
+
+    match last {
+        IncrementalValue::Number(z) => assert!((z - expected).abs() < 1e-9),
+        other => panic!("expected numeric zscore, got {other:?}"),
+    }
+}
+
+#[test]
+fn non_numeric_inputs_do_not_advance_the_aggregate() {
+    let mut state = AggregateState::default();
+    eval_aggregate_step("sum", None, &IncrementalValue::Number(3.0), &mut state);
+    eval_aggregate_step("sum", None, &IncrementalValue::Null, &mut state);
+    eval_aggregate_step("sum", None, &IncrementalValue::Bool(true), &mut state);
+    eval_aggregate_step("sum", None, &IncrementalValue::Text("x".to_string()), &mut state);
+    assert_eq!(state.count, 1);
+    assert_eq!(state.sum, 3.0);
+}
+
+#[test]
+fn min_and_max_track_running_extrema() {
+    let mut state = AggregateState::default();
+    for v in [5.0, 1.0, 9.0, -3.0, 4.0] {
+        eval_aggregate_step("sum", None, &IncrementalValue::Number(v), &mut state);
+    }
+    assert_eq!(
+        eval_aggregate_step("max", None, &IncrementalValue::Null, &mut state),
+        IncrementalValue::Number(9.0)
+    );
+    assert_eq!(
+        eval_aggregate_step("min", None, &IncrementalValue::Null, &mut state),
+        IncrementalValue::Number(-3.0)
+    );
+}
+
+#[test]
+fn windowed_sum_and_extrema_only_see_the_trailing_n_ticks() {
+    let mut sum_state = AggregateState::default();
+    let mut max_state = AggregateState::default();
+    let mut min_state = AggregateState::default();
+    let mut last_sum = IncrementalValue::Null;
+    let mut last_max = IncrementalValue::Null;
+    let mut last_min = IncrementalValue::Null;
+    for v in [5.0, 1.0, 9.0, -3.0, 4.0] {
+        last_sum = eval_aggregate_step("sum", Some(3), &IncrementalValue::Number(v), &mut sum_state);
+        last_max = eval_aggregate_step("max", Some(3), &IncrementalValue::Number(v), &mut max_state);
+        last_min = eval_aggregate_step("min", Some(3), &IncrementalValue::Number(v), &mut min_state);
+    }
+    // trailing window is [9.0, -3.0, 4.0]
+    assert_eq!(last_sum, IncrementalValue::Number(10.0));
+    assert_eq!(last_max, IncrementalValue::Number(9.0));
+    assert_eq!(last_min, IncrementalValue::Number(-3.0));
+}
+
+#[test]
+fn windowed_count_stays_capped_at_the_window_size() {
+    let mut state = AggregateState::default();
+    let mut last_count = IncrementalValue::Null;
+    for v in [1.0, 2.0, 3.0, 4.0] {
+        last_count = eval_aggregate_step("count", Some(2), &IncrementalValue::Number(v), &mut state);
+    }
+    assert_eq!(last_count, IncrementalValue::Number(2.0));
+}
+
+#[test]
+fn lag_emits_null_during_warmup_then_the_value_n_steps_back() {
+    let mut state = TimeShiftState::default();
+    let values = [10.0, 11.0, 12.0, 13.0, 14.0];
+    let mut outputs = Vec::new();
+    for v in values {
+        outputs.push(eval_time_shift_step(
+            "lag",
+            3,
+            &IncrementalValue::Number(v),
+            &mut state,
+        ));
+    }
+    assert_eq!(outputs[0], IncrementalValue::Null);
+    assert_eq!(outputs[1], IncrementalValue::Null);
+    assert_eq!(outputs[2], IncrementalValue::Null);
+    assert_eq!(outputs[3], IncrementalValue::Number(10.0));
+    assert_eq!(outputs[4], IncrementalValue::Number(11.0));
+}
+
+#[test]
+fn diff_is_current_minus_lagged() {
+    let mut state = TimeShiftState::default();
+    for v in [10.0, 12.0] {
+        eval_time_shift_step("diff", 1, &IncrementalValue::Number(v), &mut state);
+    }
+    let out = eval_time_shift_step("diff", 1, &IncrementalValue::Number(15.0), &mut state);
+    assert_eq!(out, IncrementalValue::Number(3.0));
+}
+
+#[test]
+fn pct_change_and_roc_are_null_when_the_lagged_value_is_zero() {
+    let mut pct_state = TimeShiftState::default();
+    eval_time_shift_step("pct_change", 1, &IncrementalValue::Number(0.0), &mut pct_state);
+    let pct_out = eval_time_shift_step(
+        "pct_change",
+        1,
+        &IncrementalValue::Number(5.0),
+        &mut pct_state,
+    );
+    assert_eq!(pct_out, IncrementalValue::Null);
+
+    let mut roc_state = TimeShiftState::default();
+    eval_time_shift_step("roc", 1, &IncrementalValue::Number(0.0), &mut roc_state);
+    let roc_out = eval_time_shift_step("roc", 1, &IncrementalValue::Number(5.0), &mut roc_state);
+    assert_eq!(roc_out, IncrementalValue::Null);
+}
+
+#[test]
+fn roc_is_pct_change_times_one_hundred() {
+    let mut state = TimeShiftState::default();
+    eval_time_shift_step("roc", 1, &IncrementalValue::Number(50.0), &mut state);
+    let out = eval_time_shift_step("roc", 1, &IncrementalValue::Number(55.0), &mut state);
+    assert_eq!(out, IncrementalValue::Number(10.0));
+}
+
+#[test]
+fn non_numeric_ticks_are_ignored_by_the_ring_buffer() {
+    let mut state = TimeShiftState::default();
+    eval_time_shift_step("lag", 2, &IncrementalValue::Number(1.0), &mut state);
+    eval_time_shift_step("lag", 2, &IncrementalValue::Null, &mut state);
+    eval_time_shift_step("lag", 2, &IncrementalValue::Number(2.0), &mut state);
+    let out = eval_time_shift_step("lag", 2, &IncrementalValue::Number(3.0), &mut state);
+    assert_eq!(out, IncrementalValue::Number(1.0));
+}
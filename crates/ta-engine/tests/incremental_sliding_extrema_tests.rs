@@ -0,0 +1,51 @@
+use ta_engine::incremental::sliding_extrema::SlidingExtrema;
+
+#[test]
+fn tracks_max_and_min_over_a_sliding_window() {
+    let mut extrema = SlidingExtrema::new(3);
+    for v in [5.0, 1.0, 4.0] {
+        extrema.push(v);
+    }
+    assert!(extrema.is_full());
+    assert_eq!(extrema.max(), Some(5.0));
+    assert_eq!(extrema.min(), Some(1.0));
+
+    // 5.0 ages out of the window, leaving [1.0, 4.0, 2.0]: 4.0 is now the
+    // max, 1.0 still the min.
+    extrema.push(2.0);
+    assert_eq!(extrema.max(), Some(4.0));
+    assert_eq!(extrema.min(), Some(1.0));
+}
+
+#[test]
+fn is_not_full_until_the_window_is_covered() {
+    let mut extrema = SlidingExtrema::new(3);
+    assert!(!extrema.is_full());
+    extrema.push(1.0);
+    extrema.push(2.0);
+    assert!(!extrema.is_full());
+    extrema.push(3.0);
+    assert!(extrema.is_full());
+}
+
+#[test]
+fn values_reports_the_window_contents_oldest_to_newest() {
+    let mut extrema = SlidingExtrema::new(3);
+    for v in [1.0, 2.0, 3.0, 4.0] {
+        extrema.push(v);
+    }
+    assert_eq!(extrema.values(), vec![2.0, 3.0, 4.0]);
+}
+
+#[test]
+fn from_values_replays_a_saved_window_into_an_equivalent_state() {
+    let mut original = SlidingExtrema::new(3);
+    for v in [7.0, 2.0, 9.0, 4.0] {
+        original.push(v);
+    }
+
+    let restored = SlidingExtrema::from_values(3, &original.values());
+    assert_eq!(restored.max(), original.max());
+    assert_eq!(restored.min(), original.min());
+    assert_eq!(restored.values(), original.values());
+}
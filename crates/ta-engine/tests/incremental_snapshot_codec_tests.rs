@@ -0,0 +1,65 @@
+use std::collections::BTreeMap;
+
+use ta_engine::incremental::contracts::{
+    IncrementalValue, NodeSnapshotState, RuntimeSnapshot, INCREMENTAL_STATE_SCHEMA_VERSION,
+};
+use ta_engine::incremental::snapshot_codec::{
+    decode_snapshot_from_bytes, decode_snapshot_from_json, encode_snapshot_to_bytes,
+    encode_snapshot_to_json, migrate, SnapshotCodecError,
+};
+
+fn sample_snapshot() -> RuntimeSnapshot {
+    let mut nodes = BTreeMap::new();
+    nodes.insert(
+        1,
+        NodeSnapshotState {
+            ticks_processed: 3,
+            last_output: IncrementalValue::Number(42.5),
+            state_blob: BTreeMap::from([
+                ("kind".to_string(), IncrementalValue::Text("rsi".to_string())),
+                ("period".to_string(), IncrementalValue::Number(14.0)),
+            ]),
+            clock: BTreeMap::from([(0, 7)]),
+        },
+    );
+    RuntimeSnapshot {
+        schema_version: INCREMENTAL_STATE_SCHEMA_VERSION,
+        last_event_index: 7,
+        nodes,
+    }
+}
+
+#[test]
+fn round_trips_through_json() {
+    let snapshot = sample_snapshot();
+    let json = encode_snapshot_to_json(&snapshot);
+    let restored = decode_snapshot_from_json(&json).expect("decode should succeed");
+    assert_eq!(restored, snapshot);
+}
+
+#[test]
+fn round_trips_through_bytes() {
+    let snapshot = sample_snapshot();
+    let bytes = encode_snapshot_to_bytes(&snapshot);
+    let restored = decode_snapshot_from_bytes(&bytes).expect("decode should succeed");
+    assert_eq!(restored, snapshot);
+}
+
+#[test]
+fn rejects_a_schema_version_newer_than_supported() {
+    let mut json = encode_snapshot_to_json(&sample_snapshot());
+    json["schema_version"] = serde_json::json!(INCREMENTAL_STATE_SCHEMA_VERSION + 1);
+
+    let err = migrate(json).unwrap_err();
+    assert_eq!(
+        err,
+        SnapshotCodecError::UnsupportedVersion(INCREMENTAL_STATE_SCHEMA_VERSION + 1)
+    );
+}
+
+#[test]
+fn current_schema_version_requires_no_migration() {
+    let json = encode_snapshot_to_json(&sample_snapshot());
+    let migrated = migrate(json.clone()).expect("current version migrates to itself");
+    assert_eq!(migrated, json);
+}
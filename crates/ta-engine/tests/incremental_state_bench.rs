@@ -0,0 +1,55 @@
+//! Manual throughput check for the IntMap-backed node store and the
+//! SlidingExtrema-backed Stochastic kernel, run on demand with
+//! `cargo test --release -- --ignored incremental_state_bench` (not part of
+//! the regular suite since it's a timing measurement, not a correctness
+//! check).
+
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+use ta_engine::incremental::call_step::{eval_call_step, initialize_kernel_state, KernelRuntimeState};
+use ta_engine::incremental::contracts::IncrementalValue;
+use ta_engine::incremental::int_map::IntMap;
+use ta_engine::incremental::kernel_registry::KernelId;
+
+const NODE_COUNT: u32 = 2_000;
+const TICK_COUNT: usize = 5_000;
+
+fn synthetic_tick(i: usize) -> BTreeMap<String, IncrementalValue> {
+    let base = (i as f64) * 0.0013;
+    let close = base.sin() * 50.0 + (i as f64) * 0.01;
+    BTreeMap::from([
+        ("high".to_string(), IncrementalValue::Number(close + 1.0)),
+        ("low".to_string(), IncrementalValue::Number(close - 1.0)),
+        ("close".to_string(), IncrementalValue::Number(close)),
+    ])
+}
+
+#[test]
+#[ignore]
+fn incremental_state_bench() {
+    let mut kwargs = BTreeMap::new();
+    kwargs.insert("k_period".to_string(), IncrementalValue::Number(14.0));
+
+    let mut states: IntMap<KernelRuntimeState> = (0..NODE_COUNT)
+        .map(|node_id| (node_id, initialize_kernel_state(KernelId::Stochastic, &kwargs)))
+        .collect();
+
+    let start = Instant::now();
+    for i in 0..TICK_COUNT {
+        let tick = synthetic_tick(i);
+        for node_id in 0..NODE_COUNT {
+            let state = states.get(node_id).cloned().expect("node state present");
+            let (new_state, _out) =
+                eval_call_step(KernelId::Stochastic, state, IncrementalValue::Null, &tick);
+            states.insert(node_id, new_state);
+        }
+    }
+    let elapsed = start.elapsed();
+
+    println!(
+        "incremental_state_bench: {:?} for {NODE_COUNT} nodes over {TICK_COUNT} ticks ({} node-ticks)",
+        elapsed,
+        (NODE_COUNT as usize) * TICK_COUNT
+    );
+}
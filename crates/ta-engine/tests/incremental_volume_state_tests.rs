@@ -0,0 +1,105 @@
+use std::collections::BTreeMap;
+
+use ta_engine::incremental::call_step::{eval_call_step, initialize_kernel_state};
+use ta_engine::incremental::contracts::IncrementalValue;
+use ta_engine::incremental::kernel_registry::KernelId;
+
+fn ohlcv_tick(h: f64, l: f64, c: f64, v: f64) -> BTreeMap<String, IncrementalValue> {
+    BTreeMap::from([
+        ("high".to_string(), IncrementalValue::Number(h)),
+        ("low".to_string(), IncrementalValue::Number(l)),
+        ("close".to_string(), IncrementalValue::Number(c)),
+        ("volume".to_string(), IncrementalValue::Number(v)),
+    ])
+}
+
+#[test]
+fn obv_streaming_matches_batch() {
+    let close = [10.0, 11.0, 10.5, 10.5, 12.0];
+    let volume = [100.0, 200.0, 150.0, 120.0, 300.0];
+    let expected = ta_engine::volume::obv(&close, &volume);
+
+    let mut state = initialize_kernel_state(KernelId::Obv, &BTreeMap::new());
+    for (i, (&c, &v)) in close.iter().zip(volume.iter()).enumerate() {
+        let tick = BTreeMap::from([
+            ("close".to_string(), IncrementalValue::Number(c)),
+            ("volume".to_string(), IncrementalValue::Number(v)),
+        ]);
+        let (next_state, out) = eval_call_step(KernelId::Obv, state, IncrementalValue::Null, &tick);
+        state = next_state;
+        match out {
+            IncrementalValue::Number(v) => assert_eq!(v, expected[i]),
+            other => panic!("expected numeric obv output, got {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn vwap_streaming_matches_batch() {
+    let high = [12.0, 13.0, 11.0];
+    let low = [9.0, 10.0, 8.0];
+    let close = [11.0, 12.0, 9.5];
+    let volume = [100.0, 150.0, 120.0];
+    let expected = ta_engine::volume::vwap(&high, &low, &close, &volume);
+
+    let mut state = initialize_kernel_state(KernelId::Vwap, &BTreeMap::new());
+    for i in 0..high.len() {
+        let tick = ohlcv_tick(high[i], low[i], close[i], volume[i]);
+        let (next_state, out) = eval_call_step(KernelId::Vwap, state, IncrementalValue::Null, &tick);
+        state = next_state;
+        match out {
+            IncrementalValue::Number(v) => assert!((v - expected[i]).abs() < 1e-9),
+            other => panic!("expected numeric vwap output, got {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn cmf_streaming_matches_batch_after_warmup() {
+    let high = [12.0, 13.0, 11.0, 14.0, 15.0];
+    let low = [9.0, 10.0, 8.0, 11.0, 12.0];
+    let close = [11.0, 12.0, 9.5, 13.0, 14.0];
+    let volume = [100.0, 150.0, 120.0, 200.0, 180.0];
+    let expected = ta_engine::volume::cmf(&high, &low, &close, &volume, 3);
+
+    let mut kwargs = BTreeMap::new();
+    kwargs.insert("period".to_string(), IncrementalValue::Number(3.0));
+    let mut state = initialize_kernel_state(KernelId::Cmf, &kwargs);
+
+    for i in 0..high.len() {
+        let tick = ohlcv_tick(high[i], low[i], close[i], volume[i]);
+        let (next_state, out) = eval_call_step(KernelId::Cmf, state, IncrementalValue::Null, &tick);
+        state = next_state;
+        if i + 1 < 3 {
+            assert!(matches!(out, IncrementalValue::Null));
+        } else {
+            match out {
+                IncrementalValue::Number(v) => assert!((v - expected[i]).abs() < 1e-9),
+                other => panic!("expected numeric cmf output, got {other:?}"),
+            }
+        }
+    }
+}
+
+#[test]
+fn klinger_vf_streaming_tracks_trend_sign() {
+    let mut kwargs = BTreeMap::new();
+    kwargs.insert("fast_period".to_string(), IncrementalValue::Number(3.0));
+    kwargs.insert("slow_period".to_string(), IncrementalValue::Number(5.0));
+    let mut state = initialize_kernel_state(KernelId::KlingerVf, &kwargs);
+
+    let bars = [
+        (12.0, 9.0, 11.0, 100.0),
+        (13.0, 10.0, 12.5, 150.0),
+        (14.0, 11.0, 13.5, 120.0),
+        (15.0, 12.0, 14.5, 200.0),
+    ];
+    let mut last = IncrementalValue::Null;
+    for (h, l, c, v) in bars {
+        let tick = ohlcv_tick(h, l, c, v);
+        let (next_state, out) = eval_call_step(KernelId::KlingerVf, state, IncrementalValue::Null, &tick);
+        state = next_state;
+        last = out;
+    }
+    assert!(matches!(last, IncrementalValue::Number(_)));
+}
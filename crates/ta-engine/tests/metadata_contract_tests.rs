@@ -1,6 +1,10 @@
 use std::collections::HashSet;
 
-use ta_engine::metadata::indicator_catalog;
+use ta_engine::metadata::{
+    all, by_alias, by_id, find_indicator_meta, indicator_catalog, indicators_by_category,
+    indicators_by_output_kind, indicators_requiring_field, resolve_by_runtime_binding,
+};
+use ta_engine::runtime::REGISTERED_RUNTIME_BINDINGS;
 
 #[test]
 fn ids_are_unique() {
@@ -98,6 +102,90 @@ fn runtime_binding_and_semantics_fields_are_present() {
     }
 }
 
+#[test]
+fn indicators_by_category_returns_only_that_category() {
+    let trend = indicators_by_category("trend");
+    assert!(!trend.is_empty());
+    assert!(trend.iter().all(|meta| meta.category == "trend"));
+    assert!(trend.iter().any(|meta| meta.id == "macd"));
+}
+
+#[test]
+fn indicators_requiring_field_scans_required_fields() {
+    let volume_based = indicators_requiring_field("volume");
+    assert!(!volume_based.is_empty());
+    assert!(volume_based
+        .iter()
+        .all(|meta| meta.semantics.required_fields.contains(&"volume")));
+    assert!(volume_based.iter().any(|meta| meta.id == "mfi"));
+}
+
+#[test]
+fn indicators_by_output_kind_scans_every_output() {
+    let histograms = indicators_by_output_kind("histogram");
+    assert!(!histograms.is_empty());
+    assert!(histograms
+        .iter()
+        .all(|meta| meta.outputs.iter().any(|o| o.kind == "histogram")));
+    assert!(histograms.iter().any(|meta| meta.id == "macd"));
+}
+
+#[test]
+fn resolve_by_runtime_binding_round_trips_every_catalog_entry() {
+    for indicator in indicator_catalog() {
+        let resolved = resolve_by_runtime_binding(indicator.runtime_binding)
+            .unwrap_or_else(|| panic!("no entry resolves binding '{}'", indicator.runtime_binding));
+        assert_eq!(resolved.runtime_binding, indicator.runtime_binding);
+    }
+}
+
+#[test]
+fn resolve_by_runtime_binding_matches_find_indicator_meta_for_ids() {
+    let macd = find_indicator_meta("macd").expect("macd exists");
+    let by_binding = resolve_by_runtime_binding("macd").expect("macd binding resolves");
+    assert_eq!(macd.id, by_binding.id);
+}
+
+#[test]
+fn runtime_bindings_are_unique_and_map_to_a_registered_kernel() {
+    let registered: HashSet<&str> = REGISTERED_RUNTIME_BINDINGS.iter().copied().collect();
+    let mut seen = HashSet::new();
+    for indicator in indicator_catalog() {
+        assert!(
+            registered.contains(indicator.runtime_binding),
+            "indicator '{}' has runtime_binding '{}' with no registered kernel",
+            indicator.id,
+            indicator.runtime_binding
+        );
+        assert!(
+            seen.insert(indicator.runtime_binding),
+            "runtime_binding '{}' is claimed by more than one indicator",
+            indicator.runtime_binding
+        );
+    }
+}
+
+#[test]
+fn registry_all_matches_indicator_catalog() {
+    assert_eq!(all().len(), indicator_catalog().len());
+    assert!(all().iter().zip(indicator_catalog()).all(|(a, b)| a.id == b.id));
+}
+
+#[test]
+fn registry_by_id_and_by_alias_resolve_the_same_entries_as_find_indicator_meta() {
+    for indicator in indicator_catalog() {
+        assert_eq!(by_id(indicator.id).map(|m| m.id), Some(indicator.id));
+        for alias in indicator.aliases {
+            assert_eq!(
+                by_alias(alias).map(|m| m.id),
+                find_indicator_meta(alias).map(|m| m.id)
+            );
+        }
+    }
+    assert!(by_id("does-not-exist").is_none());
+    assert!(by_alias("does-not-exist").is_none());
+}
+
 #[test]
 fn parameter_aliases_reference_existing_params() {
     for indicator in indicator_catalog() {
@@ -0,0 +1,182 @@
+use ta_engine::moving_averages::{self, MovingAverageType};
+
+#[test]
+fn parses_every_catalog_choice() {
+    assert_eq!(MovingAverageType::parse("SMA"), Some(MovingAverageType::Sma));
+    assert_eq!(MovingAverageType::parse("EMA"), Some(MovingAverageType::Ema));
+    assert_eq!(MovingAverageType::parse("WMA"), Some(MovingAverageType::Wma));
+    assert_eq!(MovingAverageType::parse("TMA"), Some(MovingAverageType::Tma));
+    assert_eq!(MovingAverageType::parse("VIDYA"), Some(MovingAverageType::Vidya));
+    assert_eq!(MovingAverageType::parse("WWMA"), Some(MovingAverageType::Wwma));
+    assert_eq!(MovingAverageType::parse("ZLEMA"), Some(MovingAverageType::Zlema));
+    assert_eq!(MovingAverageType::parse("DEMA"), Some(MovingAverageType::Dema));
+    assert_eq!(MovingAverageType::parse("TEMA"), Some(MovingAverageType::Tema));
+    assert_eq!(MovingAverageType::parse("TRIMA"), Some(MovingAverageType::Trima));
+    assert_eq!(MovingAverageType::parse("KAMA"), Some(MovingAverageType::Kama));
+    assert_eq!(MovingAverageType::parse("HULL"), Some(MovingAverageType::Hull));
+    assert_eq!(MovingAverageType::parse("SINE_WMA"), Some(MovingAverageType::SineWma));
+    assert_eq!(MovingAverageType::parse("T3"), Some(MovingAverageType::T3));
+    assert_eq!(MovingAverageType::parse("LINREG"), Some(MovingAverageType::LinReg));
+    assert_eq!(MovingAverageType::parse("bogus"), None);
+}
+
+#[test]
+fn trima_and_hull_delegate_to_tma_and_hma() {
+    let values: Vec<f64> = (1..=20).map(|v| v as f64).collect();
+    assert_eq!(
+        MovingAverageType::Trima.apply(&values, 4),
+        moving_averages::tma(&values, 4)
+    );
+    assert_eq!(
+        MovingAverageType::Hull.apply(&values, 4),
+        moving_averages::hma(&values, 4)
+    );
+}
+
+#[test]
+fn dema_and_tema_lead_a_plain_ema_on_a_rising_ramp() {
+    let values: Vec<f64> = (1..=30).map(|v| v as f64).collect();
+    let ema = moving_averages::ema(&values, 9);
+    let dema = moving_averages::dema(&values, 9);
+    let tema = moving_averages::tema(&values, 9);
+    assert!(dema[29] > ema[29]);
+    assert!(tema[29] > dema[29]);
+}
+
+#[test]
+fn kama_stays_within_the_series_range() {
+    let values: Vec<f64> = (1..=40).map(|v| (v as f64 * 0.3).sin() * 10.0 + 50.0).collect();
+    let out = moving_averages::kama(&values, 10);
+    let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    for v in out.iter().filter(|v| !v.is_nan()) {
+        assert!(*v >= min && *v <= max);
+    }
+}
+
+#[test]
+fn kama_matches_the_fixed_rate_wrapper_with_its_defaults() {
+    let values: Vec<f64> = (1..=40).map(|v| (v as f64 * 0.3).sin() * 10.0 + 50.0).collect();
+    let wrapped = moving_averages::kama(&values, 10);
+    let configurable = moving_averages::kama_with_rates(&values, 10, 2, 30);
+    assert_eq!(wrapped, configurable);
+}
+
+#[test]
+fn kama_tracks_a_straight_trend_more_closely_than_choppy_noise() {
+    let trend: Vec<f64> = (0..40).map(|v| 100.0 + v as f64).collect();
+    let kama_trend = moving_averages::kama_with_rates(&trend, 10, 2, 30);
+    // A straight line has efficiency ratio 1, so the smoothing constant is
+    // (2/3)^2 = 4/9 and the lag settles at (1 - 4/9) / (4/9) = 1.25.
+    let trend_gap = (kama_trend[39] - trend[39]).abs();
+    assert!((trend_gap - 1.25).abs() < 1e-6);
+
+    let chop: Vec<f64> = (0..40).map(|v| if v % 2 == 0 { 95.0 } else { 105.0 }).collect();
+    let kama_chop = moving_averages::kama_with_rates(&chop, 10, 2, 30);
+    assert!(trend_gap < (kama_chop[39] - chop[39]).abs());
+}
+
+#[test]
+fn alma_warms_up_over_the_window_and_tracks_a_flat_series_exactly() {
+    let values = vec![42.0; 20];
+    let out = moving_averages::alma(&values, 9, 0.85, 6.0);
+    assert!(out[..8].iter().all(|v| v.is_nan()));
+    for v in out[8..].iter() {
+        assert!((*v - 42.0).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn alma_clamps_an_out_of_range_offset() {
+    let values: Vec<f64> = (1..=20).map(|v| v as f64).collect();
+    let clamped = moving_averages::alma(&values, 9, 1.5, 6.0);
+    let pinned = moving_averages::alma(&values, 9, 1.0, 6.0);
+    assert_eq!(clamped, pinned);
+}
+
+#[test]
+fn t3_tracks_a_flat_series_exactly() {
+    let values = vec![42.0; 30];
+    let out = moving_averages::t3(&values, 5);
+    for v in out.iter().filter(|v| !v.is_nan()) {
+        assert!((*v - 42.0).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn linreg_ma_matches_the_regression_line_endpoint() {
+    let values: Vec<f64> = (0..30).map(|v| 10.0 + v as f64 * 2.0).collect();
+    let out = moving_averages::linreg_ma(&values, 10);
+    let last = values.len() - 1;
+    assert!((out[last] - values[last]).abs() < 1e-9);
+}
+
+#[test]
+fn apply_ma_dispatches_to_the_selected_kernel() {
+    let values: Vec<f64> = (1..=20).map(|v| v as f64).collect();
+    assert_eq!(
+        moving_averages::apply_ma(MovingAverageType::Sma, &values, 5),
+        MovingAverageType::Sma.apply(&values, 5)
+    );
+}
+
+#[test]
+fn wwma_matches_rma() {
+    let values = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+    let expected = moving_averages::rma(&values, 3);
+    let actual = MovingAverageType::Wwma.apply(&values, 3);
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn tma_is_an_sma_of_an_sma() {
+    let values: Vec<f64> = (1..=20).map(|v| v as f64).collect();
+    let out = moving_averages::tma(&values, 4);
+    assert!(out[0].is_nan());
+    assert!(out[1].is_nan());
+    assert!(out[2].is_finite());
+    assert!(out[19].is_finite());
+}
+
+#[test]
+fn zlema_leads_a_plain_ema_on_a_rising_ramp() {
+    let values: Vec<f64> = (1..=30).map(|v| v as f64).collect();
+    let zlema = moving_averages::zlema(&values, 9);
+    let ema = moving_averages::ema(&values, 9);
+    assert!(zlema[29] > ema[29]);
+}
+
+#[test]
+fn vidya_stays_within_the_series_range() {
+    let values: Vec<f64> = (1..=40).map(|v| (v as f64 * 0.3).sin() * 10.0 + 50.0).collect();
+    let out = moving_averages::vidya(&values, 10);
+    let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    for v in out.iter().filter(|v| !v.is_nan()) {
+        assert!(*v >= min && *v <= max);
+    }
+}
+
+#[test]
+fn trima_matches_tma_for_an_odd_period_but_not_an_even_one() {
+    let values: Vec<f64> = (1..=30).map(|v| v as f64).collect();
+    assert_eq!(
+        moving_averages::trima(&values, 9),
+        moving_averages::tma(&values, 9),
+        "odd periods use the same half-window size twice in both kernels"
+    );
+    assert_ne!(
+        moving_averages::trima(&values, 10),
+        moving_averages::tma(&values, 10),
+        "trima's even-period sub-windows (5, 6) differ from tma's (5, 5) shortcut"
+    );
+}
+
+#[test]
+fn trima_tracks_a_flat_series_exactly() {
+    let values = vec![5.0; 20];
+    let out = moving_averages::trima(&values, 10);
+    for v in out.iter().filter(|v| !v.is_nan()) {
+        assert!((*v - 5.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,38 @@
+use ta_engine::rating;
+
+fn wiggly_trend(n: usize, slope: f64, start: f64) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let close: Vec<f64> = (0..n)
+        .map(|v| start + slope * v as f64 + (v as f64 * 0.5).sin() * 2.0)
+        .collect();
+    let high: Vec<f64> = close.iter().map(|c| c + 1.0).collect();
+    let low: Vec<f64> = close.iter().map(|c| c - 1.0).collect();
+    (high, low, close)
+}
+
+#[test]
+fn technical_rating_votes_stay_bounded_after_warmup() {
+    let (high, low, close) = wiggly_trend(80, 0.3, 100.0);
+    let (oscillators_rating, ma_rating, all_rating) =
+        rating::technical_rating(&high, &low, &close, 50);
+
+    for series in [&oscillators_rating, &ma_rating, &all_rating] {
+        for &v in series.iter().skip(60) {
+            assert!((-1.0..=1.0).contains(&v), "vote out of range: {v}");
+        }
+    }
+}
+
+#[test]
+fn technical_rating_ma_rating_tracks_price_trend_direction() {
+    let (high, low, close) = wiggly_trend(80, 0.3, 100.0);
+    let (_, up_ma_rating, up_all_rating) = rating::technical_rating(&high, &low, &close, 50);
+    let last = close.len() - 1;
+    assert!(up_ma_rating[last] > 0.0);
+    assert!(up_all_rating[last] > 0.0);
+
+    let (high, low, close) = wiggly_trend(80, -0.3, 200.0);
+    let (_, down_ma_rating, down_all_rating) = rating::technical_rating(&high, &low, &close, 50);
+    let last = close.len() - 1;
+    assert!(down_ma_rating[last] < 0.0);
+    assert!(down_all_rating[last] < 0.0);
+}
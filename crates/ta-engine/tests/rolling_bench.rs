@@ -0,0 +1,37 @@
+//! Manual throughput check for the rolling-window kernels, run on demand with
+//! `cargo test --release -- --ignored rolling_bench` (not part of the
+//! regular suite since it's a timing measurement, not a correctness check).
+
+use std::time::Instant;
+
+use ta_engine::rolling::{rolling_max, rolling_mean, rolling_min, rolling_std};
+
+fn million_bar_series() -> Vec<f64> {
+    (0..1_000_000)
+        .map(|i| ((i as f64) * 0.0013).sin() * 50.0 + (i as f64) * 0.01)
+        .collect()
+}
+
+#[test]
+#[ignore]
+fn rolling_bench() {
+    let values = million_bar_series();
+    let period = 20;
+
+    for (name, f) in [
+        ("rolling_mean", rolling_mean as fn(&[f64], usize) -> Vec<f64>),
+        ("rolling_std", rolling_std),
+        ("rolling_min", rolling_min),
+        ("rolling_max", rolling_max),
+    ] {
+        let start = Instant::now();
+        let out = f(&values, period);
+        let elapsed = start.elapsed();
+        println!(
+            "{name}: {:?} for {} bars (period {period}), last = {:?}",
+            elapsed,
+            values.len(),
+            out.last()
+        );
+    }
+}
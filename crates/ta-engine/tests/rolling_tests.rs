@@ -1,5 +1,124 @@
 use ta_engine::rolling;
 
+/// Deterministic xorshift64-based series generator -- no external `rand`
+/// dependency, same `seed` always reproduces the same series.
+fn xorshift_series(len: usize, seed: u64) -> Vec<f64> {
+    let mut state = seed.max(1);
+    let mut next_unit = || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state >> 11) as f64 / (1u64 << 53) as f64
+    };
+    (0..len).map(|_| next_unit() * 100.0).collect()
+}
+
+/// Reference O(n*period) rescan, kept around only to check the monotonic
+/// deque against for equivalence.
+fn naive_rolling_extremum(values: &[f64], period: usize, combine: fn(f64, f64) -> f64) -> Vec<f64> {
+    let n = values.len();
+    let mut out = vec![f64::NAN; n];
+    if period == 0 || n == 0 || period > n {
+        return out;
+    }
+    for i in (period - 1)..n {
+        out[i] = values[i + 1 - period..=i]
+            .iter()
+            .copied()
+            .reduce(combine)
+            .unwrap();
+    }
+    out
+}
+
+fn assert_matches_naive(values: &[f64], period: usize) {
+    let naive_min = naive_rolling_extremum(values, period, f64::min);
+    let naive_max = naive_rolling_extremum(values, period, f64::max);
+    let min = rolling::rolling_min(values, period);
+    let max = rolling::rolling_max(values, period);
+    for i in 0..values.len() {
+        assert_eq!(min[i].is_nan(), naive_min[i].is_nan(), "min nan mismatch at {i}");
+        if !naive_min[i].is_nan() {
+            assert_eq!(min[i], naive_min[i], "min mismatch at {i}");
+        }
+        assert_eq!(max[i].is_nan(), naive_max[i].is_nan(), "max nan mismatch at {i}");
+        if !naive_max[i].is_nan() {
+            assert_eq!(max[i], naive_max[i], "max mismatch at {i}");
+        }
+    }
+}
+
+#[test]
+fn rolling_min_max_monotonic_deque_matches_naive_rescan_on_random_data() {
+    for seed in [1u64, 7, 42] {
+        assert_matches_naive(&xorshift_series(500, seed), 20);
+    }
+}
+
+#[test]
+fn rolling_min_max_monotonic_deque_matches_naive_rescan_on_plateaus() {
+    let mut values = vec![3.0; 50];
+    values[10] = 1.0;
+    values[30] = 5.0;
+    assert_matches_naive(&values, 7);
+}
+
+#[test]
+fn rolling_min_max_monotonic_deque_matches_naive_rescan_on_monotonic_runs() {
+    let ascending: Vec<f64> = (0..100).map(|i| i as f64).collect();
+    let descending: Vec<f64> = (0..100).map(|i| -(i as f64)).collect();
+    assert_matches_naive(&ascending, 10);
+    assert_matches_naive(&descending, 10);
+}
+
+#[test]
+fn rolling_min_max_matches_naive_rescan_with_a_nan_inside_the_window() {
+    let mut values = xorshift_series(50, 3);
+    // Place the NaN mid-window, not just before it -- a deque that never
+    // evicts a stale NaN candidate would only fail once the window has
+    // fully slid past it and a later, smaller finite value needs to win.
+    values[20] = f64::NAN;
+    assert_matches_naive(&values, 7);
+}
+
+#[test]
+fn rolling_min_evicts_a_nan_candidate_once_a_finite_value_follows_it() {
+    // Regression for a should_evict comparator that used `>=`/`<=`
+    // directly: NaN always compares false, so a NaN entry was never
+    // evicted and could be reported as the window's min/max even though
+    // it isn't extremal by f64::min/f64::max's ignore-NaN contract.
+    let out = rolling::rolling_min(&[5.0, f64::NAN, 2.0], 3);
+    assert_eq!(out[2], 2.0);
+
+    let out = rolling::rolling_max(&[5.0, f64::NAN, 2.0], 3);
+    assert_eq!(out[2], 5.0);
+}
+
+#[test]
+fn rolling_std_stays_accurate_for_large_magnitude_low_variance_windows() {
+    let window = [1.0e9 + 1.0, 1.0e9 + 2.0, 1.0e9 + 3.0];
+    let out = rolling::rolling_std(&window, 3);
+
+    let mean = window.iter().sum::<f64>() / window.len() as f64;
+    let reference = (window.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / window.len() as f64).sqrt();
+
+    assert!(
+        (out[2] - reference).abs() < 1e-6,
+        "got {}, expected close to two-pass reference {reference}",
+        out[2]
+    );
+}
+
+#[test]
+fn rolling_std_ddof_one_gives_sample_std_instead_of_population_std() {
+    let values = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+    let population = rolling::rolling_std(&values, values.len());
+    let sample = rolling::rolling_std_ddof(&values, values.len(), 1);
+
+    let last = values.len() - 1;
+    assert!(sample[last] > population[last]);
+}
+
 #[test]
 fn rolling_mean_basic() {
     let out = rolling::rolling_mean(&[1.0, 2.0, 3.0, 4.0], 3);
@@ -24,3 +143,21 @@ fn rolling_min_max_basic() {
     assert_eq!(max_out[2], 4.0);
     assert_eq!(max_out[3], 4.0);
 }
+
+#[test]
+fn wilder_smooth_seeds_with_average_then_recurs() {
+    let out = rolling::wilder_smooth(&[1.0, 2.0, 3.0, 4.0, 5.0], 3);
+    assert!(out[0].is_nan());
+    assert!(out[1].is_nan());
+    assert_eq!(out[2], 2.0);
+    assert_eq!(out[3], (2.0 * 2.0 + 4.0) / 3.0);
+    assert_eq!(out[4], (out[3] * 2.0 + 5.0) / 3.0);
+}
+
+#[test]
+fn wilder_smooth_non_negative_clamps_before_smoothing() {
+    let plain = rolling::wilder_smooth(&[1.0, -2.0, 3.0], 2);
+    let clamped = rolling::wilder_smooth_non_negative(&[1.0, -2.0, 3.0], 2);
+    assert_ne!(plain[2], clamped[2]);
+    assert_eq!(clamped[2], (0.5 * 1.0 + 3.0) / 2.0);
+}
@@ -0,0 +1,63 @@
+use serde_json::json;
+use ta_engine::{compute_batch, ComputeIndicatorRequest, OhlcvInput};
+
+fn sample_ohlcv() -> OhlcvInput {
+    let close: Vec<f64> = (1..=64).map(|v| v as f64 + 0.5).collect();
+    OhlcvInput {
+        timestamps: (1..=64).collect(),
+        open: (1..=64).map(|v| v as f64).collect(),
+        high: (1..=64).map(|v| v as f64 + 1.0).collect(),
+        low: (1..=64).map(|v| v as f64 - 1.0).collect(),
+        close,
+        volume: Some((1..=64).map(|v| 1000.0 + v as f64).collect()),
+    }
+}
+
+fn request(indicator_id: &str, params: serde_json::Value, instance_id: &str) -> ComputeIndicatorRequest {
+    ComputeIndicatorRequest {
+        indicator_id: indicator_id.to_string(),
+        params,
+        ohlcv: sample_ohlcv(),
+        instance_id: Some(instance_id.to_string()),
+        named_inputs: Default::default(),
+    }
+}
+
+#[test]
+fn preserves_request_order_and_instance_ids() {
+    let requests = vec![
+        request("rsi", json!({"period": 14}), "a"),
+        request("sma", json!({"period": 5}), "b"),
+        request("ema", json!({"period": 10}), "c"),
+    ];
+    let out = compute_batch(requests).expect("batch should compute");
+
+    assert_eq!(out.len(), 3);
+    assert_eq!(out[0].instance_id.as_deref(), Some("a"));
+    assert_eq!(out[1].instance_id.as_deref(), Some("b"));
+    assert_eq!(out[2].instance_id.as_deref(), Some("c"));
+    assert_eq!(out[0].runtime_binding, "rsi");
+    assert_eq!(out[1].runtime_binding, "sma");
+    assert_eq!(out[2].runtime_binding, "ema");
+}
+
+#[test]
+fn deduplicates_identical_kernel_requests() {
+    let requests = vec![
+        request("ema", json!({"period": 12}), "macd_fast"),
+        request("ema", json!({"period": 12}), "ema_cross_fast"),
+        request("ema", json!({"period": 26}), "macd_slow"),
+    ];
+    let out = compute_batch(requests).expect("batch should compute");
+
+    assert_eq!(out.len(), 3);
+    assert_eq!(out[0].outputs, out[1].outputs, "identical requests should share a computed result");
+    assert_ne!(out[0].outputs, out[2].outputs, "distinct periods must not collide");
+}
+
+#[test]
+fn rejects_unknown_indicator() {
+    let requests = vec![request("not_a_real_indicator", json!({}), "x")];
+    let err = compute_batch(requests).expect_err("unknown indicator should fail");
+    assert_eq!(err.code, "unknown_indicator");
+}
@@ -0,0 +1,56 @@
+use serde_json::json;
+use ta_engine::{compute_indicator_batch, OhlcvBatch};
+
+fn sample_batch(n_symbols: usize) -> OhlcvBatch {
+    let close: Vec<f64> = (1..=64).map(|v| v as f64 + 0.5).collect();
+    OhlcvBatch {
+        timestamps: (1..=64).collect(),
+        open: vec![(1..=64).map(|v| v as f64).collect(); n_symbols],
+        high: vec![(1..=64).map(|v| v as f64 + 1.0).collect(); n_symbols],
+        low: vec![(1..=64).map(|v| v as f64 - 1.0).collect(); n_symbols],
+        close: vec![close; n_symbols],
+        volume: Some(vec![(1..=64).map(|v| 1000.0 + v as f64).collect(); n_symbols]),
+    }
+}
+
+#[test]
+fn broadcasts_scalar_param_across_symbols() {
+    let batch = sample_batch(3);
+    let out = compute_indicator_batch("sma", &json!({"period": 5}), &batch, None)
+        .expect("sma batch should compute");
+
+    assert_eq!(out.outputs.len(), 1);
+    assert_eq!(out.outputs[0].values.len(), 3);
+    for column in &out.outputs[0].values {
+        assert_eq!(column.len(), 64);
+    }
+}
+
+#[test]
+fn applies_per_symbol_param_vector() {
+    let batch = sample_batch(2);
+    let out = compute_indicator_batch("sma", &json!({"period": [5, 10]}), &batch, None)
+        .expect("sma batch should compute with per-symbol periods");
+
+    assert_eq!(out.outputs[0].values.len(), 2);
+    let warmup_5 = out.outputs[0].values[0].iter().filter(|v| v.is_none()).count();
+    let warmup_10 = out.outputs[0].values[1].iter().filter(|v| v.is_none()).count();
+    assert!(warmup_10 > warmup_5, "longer period should warm up longer");
+}
+
+#[test]
+fn rejects_param_vector_with_wrong_length() {
+    let batch = sample_batch(3);
+    let err = compute_indicator_batch("sma", &json!({"period": [5, 10]}), &batch, None)
+        .expect_err("param vector length mismatch should fail");
+    assert_eq!(err.code, "shape_mismatch");
+}
+
+#[test]
+fn single_symbol_batch_matches_expected_output_shape() {
+    let batch = sample_batch(1);
+    let out = compute_indicator_batch("rsi", &json!({"period": 14}), &batch, None)
+        .expect("rsi batch should compute");
+    assert_eq!(out.outputs[0].values.len(), 1);
+    assert_eq!(out.outputs[0].values[0].len(), 64);
+}
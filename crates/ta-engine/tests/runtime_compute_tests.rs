@@ -1,6 +1,6 @@
 use serde_json::json;
 use ta_engine::metadata::indicator_catalog;
-use ta_engine::{compute_indicator, ComputeIndicatorRequest, OhlcvInput};
+use ta_engine::{compute_indicator, ComputeIndicatorRequest, OhlcvInput, ParamErrorKind};
 
 fn sample_ohlcv() -> OhlcvInput {
     OhlcvInput {
@@ -20,6 +20,7 @@ fn computes_alias_id_with_alias_param_and_resolves_canonical_id() {
         params: json!({"lookback": 5}),
         ohlcv: sample_ohlcv(),
         instance_id: Some("inst-1".to_string()),
+        ..Default::default()
     };
 
     let out = compute_indicator(req).expect("mean alias should resolve to sma");
@@ -38,6 +39,7 @@ fn computes_macd_with_metadata_output_order() {
         params: json!({"fast_period": 12, "slow_period": 26, "signal_period": 9}),
         ohlcv: sample_ohlcv(),
         instance_id: None,
+        ..Default::default()
     };
 
     let out = compute_indicator(req).expect("macd should compute");
@@ -45,6 +47,171 @@ fn computes_macd_with_metadata_output_order() {
     assert_eq!(names, vec!["macd", "signal", "histogram"]);
 }
 
+#[test]
+fn macd_signal_ma_type_independently_selects_the_signal_line_kernel() {
+    let base = json!({"fast_period": 12, "slow_period": 26, "signal_period": 9});
+
+    let ema_req = ComputeIndicatorRequest {
+        indicator_id: "macd".to_string(),
+        params: base.clone(),
+        ohlcv: sample_ohlcv(),
+        instance_id: None,
+        ..Default::default()
+    };
+    let mut wma_params = base;
+    wma_params["signal_ma_type"] = json!("WMA");
+    let wma_req = ComputeIndicatorRequest {
+        indicator_id: "macd".to_string(),
+        params: wma_params,
+        ohlcv: sample_ohlcv(),
+        instance_id: None,
+        ..Default::default()
+    };
+
+    let ema_out = compute_indicator(ema_req).expect("macd with default signal_ma_type");
+    let wma_out = compute_indicator(wma_req).expect("macd with signal_ma_type=WMA");
+    assert_ne!(
+        ema_out.outputs[1].values, wma_out.outputs[1].values,
+        "signal line should differ between EMA and WMA kernels"
+    );
+}
+
+#[test]
+fn williams_r_smooth_period_applies_the_selected_ma_kernel() {
+    let raw_req = ComputeIndicatorRequest {
+        indicator_id: "williams_r".to_string(),
+        params: json!({"period": 14}),
+        ohlcv: sample_ohlcv(),
+        instance_id: None,
+        ..Default::default()
+    };
+    let smoothed_req = ComputeIndicatorRequest {
+        indicator_id: "williams_r".to_string(),
+        params: json!({"period": 14, "smooth_period": 3, "ma_type": "SMA"}),
+        ohlcv: sample_ohlcv(),
+        instance_id: None,
+        ..Default::default()
+    };
+
+    let raw_out = compute_indicator(raw_req).expect("unsmoothed williams_r");
+    let smoothed_out = compute_indicator(smoothed_req).expect("smoothed williams_r");
+    assert_ne!(raw_out.outputs[0].values, smoothed_out.outputs[0].values);
+}
+
+#[test]
+fn computes_ac_and_chaikin_osc_with_defaults() {
+    for id in ["ac", "chaikin_osc"] {
+        let req = ComputeIndicatorRequest {
+            indicator_id: id.to_string(),
+            params: json!({}),
+            ohlcv: sample_ohlcv(),
+            instance_id: None,
+            ..Default::default()
+        };
+        let out = compute_indicator(req).unwrap_or_else(|err| panic!("{id} failed: {err:?}"));
+        assert_eq!(out.outputs[0].name, "result", "{id}");
+        assert!(
+            out.outputs[0].values.iter().any(Option::is_some),
+            "{id} produced no finite output"
+        );
+    }
+}
+
+#[test]
+fn computes_every_lagless_ma_family_member_with_defaults() {
+    for id in ["dema", "tema", "zlema", "t3"] {
+        let req = ComputeIndicatorRequest {
+            indicator_id: id.to_string(),
+            params: json!({}),
+            ohlcv: sample_ohlcv(),
+            instance_id: None,
+            ..Default::default()
+        };
+        let out = compute_indicator(req).unwrap_or_else(|err| panic!("{id} failed: {err:?}"));
+        assert_eq!(out.outputs[0].name, "result", "{id}");
+        assert!(
+            out.outputs[0].values.iter().any(Option::is_some),
+            "{id} produced no finite output"
+        );
+    }
+}
+
+#[test]
+fn computes_alma_with_defaults() {
+    let req = ComputeIndicatorRequest {
+        indicator_id: "alma".to_string(),
+        params: json!({}),
+        ohlcv: sample_ohlcv(),
+        instance_id: None,
+        ..Default::default()
+    };
+
+    let out = compute_indicator(req).expect("alma should compute");
+    assert_eq!(out.outputs[0].name, "result");
+    assert_eq!(out.normalized_params["window"], json!(9));
+    assert!(out.outputs[0].values[8..].iter().all(Option::is_some));
+}
+
+#[test]
+fn computes_vwma_and_trima_with_defaults() {
+    for id in ["vwma", "trima"] {
+        let req = ComputeIndicatorRequest {
+            indicator_id: id.to_string(),
+            params: json!({}),
+            ohlcv: sample_ohlcv(),
+            instance_id: None,
+            ..Default::default()
+        };
+        let out = compute_indicator(req).unwrap_or_else(|err| panic!("{id} failed: {err:?}"));
+        assert_eq!(out.outputs[0].name, "result", "{id}");
+        assert!(
+            out.outputs[0].values.iter().any(Option::is_some),
+            "{id} produced no finite output"
+        );
+    }
+}
+
+#[test]
+fn vwma_falls_back_to_a_plain_sma_when_volume_is_all_zero() {
+    let mut ohlcv = sample_ohlcv();
+    ohlcv.volume = Some(vec![0.0; 64]);
+    let req = ComputeIndicatorRequest {
+        indicator_id: "vwma".to_string(),
+        params: json!({"period": 5}),
+        ohlcv,
+        instance_id: None,
+        ..Default::default()
+    };
+
+    let out = compute_indicator(req).expect("vwma should compute with zero volume");
+    let sma = ta_engine::rolling::rolling_mean(
+        &(1..=64).map(|v| v as f64 + 0.5).collect::<Vec<_>>(),
+        5,
+    );
+    for (a, b) in out.outputs[0].values.iter().zip(sma.iter()) {
+        match a {
+            Some(v) => assert!((v - b).abs() < 1e-9),
+            None => assert!(b.is_nan()),
+        }
+    }
+}
+
+#[test]
+fn computes_kama_with_configurable_rates() {
+    let req = ComputeIndicatorRequest {
+        indicator_id: "kama".to_string(),
+        params: json!({"er_period": 10, "fast_period": 2, "slow_period": 30}),
+        ohlcv: sample_ohlcv(),
+        instance_id: None,
+        ..Default::default()
+    };
+
+    let out = compute_indicator(req).expect("kama should compute");
+    assert_eq!(out.outputs[0].name, "result");
+    assert_eq!(out.outputs[0].values.len(), 64);
+    assert!(out.outputs[0].values[10..].iter().all(Option::is_some));
+}
+
 #[test]
 fn returns_structured_error_for_invalid_param() {
     let req = ComputeIndicatorRequest {
@@ -52,10 +219,45 @@ fn returns_structured_error_for_invalid_param() {
         params: json!({"period": 0}),
         ohlcv: sample_ohlcv(),
         instance_id: None,
+        ..Default::default()
     };
 
     let err = compute_indicator(req).expect_err("period=0 should fail");
     assert_eq!(err.code, "invalid_param");
+    let param = err.param.expect("out-of-range period should carry param detail");
+    assert_eq!(param.param_name, "period");
+    assert_eq!(param.kind, ParamErrorKind::OutOfRange);
+}
+
+#[test]
+fn missing_required_param_reports_a_missing_param_detail() {
+    let req = ComputeIndicatorRequest {
+        indicator_id: "stoch_rsi".to_string(),
+        params: json!({"rsi_period": "not a number"}),
+        ohlcv: sample_ohlcv(),
+        instance_id: None,
+        ..Default::default()
+    };
+
+    let err = compute_indicator(req).expect_err("non-numeric rsi_period should fail");
+    let param = err.param.expect("wrong-type rsi_period should carry param detail");
+    assert_eq!(param.param_name, "rsi_period");
+    assert_eq!(param.kind, ParamErrorKind::WrongType);
+}
+
+#[test]
+fn macd_with_fast_period_gte_slow_period_reports_cross_field_constraint() {
+    let req = ComputeIndicatorRequest {
+        indicator_id: "macd".to_string(),
+        params: json!({"fast_period": 26, "slow_period": 12, "signal_period": 9}),
+        ohlcv: sample_ohlcv(),
+        instance_id: None,
+        ..Default::default()
+    };
+
+    let err = compute_indicator(req).expect_err("fast_period >= slow_period should fail");
+    let param = err.param.expect("constraint violation should carry param detail");
+    assert_eq!(param.kind, ParamErrorKind::CrossFieldConstraint);
 }
 
 #[test]
@@ -65,6 +267,7 @@ fn computes_event_signal_series() {
         params: json!({"a": "close", "b": "open"}),
         ohlcv: sample_ohlcv(),
         instance_id: None,
+        ..Default::default()
     };
 
     let out = compute_indicator(req).expect("cross should compute");
@@ -73,6 +276,108 @@ fn computes_event_signal_series() {
     assert_eq!(out.outputs[0].values.len(), 64);
 }
 
+#[test]
+fn computes_stoch_of_rsi_as_stochrsi() {
+    let req = ComputeIndicatorRequest {
+        indicator_id: "stoch_of".to_string(),
+        params: json!({"source": "rsi", "source_params": {"period": 14}, "k_period": 14, "d_period": 3, "smooth": 1}),
+        ohlcv: sample_ohlcv(),
+        instance_id: None,
+        ..Default::default()
+    };
+
+    let out = compute_indicator(req).expect("stoch_of should compute over rsi");
+    let names: Vec<&str> = out.outputs.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, vec!["k", "d"]);
+    assert_eq!(out.outputs[0].values.len(), 64);
+    assert_eq!(out.normalized_params["source"], json!("rsi"));
+
+    for value in out.outputs[0].values.iter().flatten() {
+        assert!((0.0..=100.0).contains(value), "k out of range: {value}");
+    }
+}
+
+#[test]
+fn computes_stoch_rsi_over_an_internally_resolved_rsi() {
+    let req = ComputeIndicatorRequest {
+        indicator_id: "stoch_rsi".to_string(),
+        params: json!({"rsi_period": 14, "stoch_period": 14, "smooth_k": 3, "smooth_d": 3}),
+        ohlcv: sample_ohlcv(),
+        instance_id: None,
+        ..Default::default()
+    };
+
+    let out = compute_indicator(req).expect("stoch_rsi should compute over an internal rsi");
+    let names: Vec<&str> = out.outputs.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, vec!["k", "d"]);
+    assert_eq!(out.outputs[0].values.len(), 64);
+    assert_eq!(out.normalized_params["rsi_period"], json!(14));
+
+    for value in out.outputs[0].values.iter().flatten() {
+        assert!((0.0..=100.0).contains(value), "k out of range: {value}");
+    }
+}
+
+#[test]
+fn computes_dt_oscillator_with_a_selectable_ma_type() {
+    let req = ComputeIndicatorRequest {
+        indicator_id: "dt_oscillator".to_string(),
+        params: json!({"ma_type": "EMA"}),
+        ohlcv: sample_ohlcv(),
+        instance_id: None,
+        ..Default::default()
+    };
+
+    let out = compute_indicator(req).expect("dt_oscillator should compute over an internal rsi");
+    let names: Vec<&str> = out.outputs.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, vec!["k", "d"]);
+    assert_eq!(out.outputs[0].values.len(), 64);
+    assert_eq!(out.normalized_params["rsi_period"], json!(13));
+    assert_eq!(out.normalized_params["ma_type"], json!("EMA"));
+}
+
+#[test]
+fn sma_over_hl2_matches_a_hand_computed_average_of_high_and_low() {
+    let ohlcv = sample_ohlcv();
+    let expected_hl2: Vec<f64> = ohlcv
+        .high
+        .iter()
+        .zip(&ohlcv.low)
+        .map(|(h, l)| (h + l) / 2.0)
+        .collect();
+
+    let req = ComputeIndicatorRequest {
+        indicator_id: "sma".to_string(),
+        params: json!({"period": 1, "source": "hl2"}),
+        ohlcv,
+        instance_id: None,
+        ..Default::default()
+    };
+
+    let out = compute_indicator(req).expect("sma over hl2 should compute");
+    assert_eq!(out.outputs[0].values.len(), expected_hl2.len());
+    for (actual, expected) in out.outputs[0].values.iter().zip(&expected_hl2) {
+        assert_eq!(actual.expect("period 1 has no warmup gap"), *expected);
+    }
+}
+
+#[test]
+fn sma_accepts_every_derived_price_source_name() {
+    for source in ["hlc3", "typical", "ohlc4", "hlcc4", "weighted"] {
+        let req = ComputeIndicatorRequest {
+            indicator_id: "sma".to_string(),
+            params: json!({"period": 5, "source": source}),
+            ohlcv: sample_ohlcv(),
+            instance_id: None,
+            ..Default::default()
+        };
+
+        let out = compute_indicator(req)
+            .unwrap_or_else(|err| panic!("source '{source}' failed with {}: {}", err.code, err.message));
+        assert_eq!(out.outputs[0].values.len(), 64);
+    }
+}
+
 #[test]
 fn computes_all_catalog_indicators_with_defaults() {
     for meta in indicator_catalog() {
@@ -81,6 +386,7 @@ fn computes_all_catalog_indicators_with_defaults() {
             params: json!({}),
             ohlcv: sample_ohlcv(),
             instance_id: None,
+            ..Default::default()
         };
         let out = compute_indicator(req).unwrap_or_else(|err| {
             panic!("{} failed with {}: {}", meta.id, err.code, err.message);
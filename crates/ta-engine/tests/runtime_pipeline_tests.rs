@@ -0,0 +1,143 @@
+use std::collections::BTreeMap;
+
+use serde_json::json;
+use ta_engine::{
+    evaluate_pipeline, BinOp, BinOpRhs, EventKind, OhlcvInput, Pipeline, PipelineNode,
+    PipelineNodeKind, PipelineValue,
+};
+
+fn sample_ohlcv() -> OhlcvInput {
+    OhlcvInput {
+        timestamps: (1..=64).collect(),
+        open: (1..=64).map(|v| v as f64).collect(),
+        high: (1..=64).map(|v| v as f64 + 1.0).collect(),
+        low: (1..=64).map(|v| v as f64 - 1.0).collect(),
+        close: (1..=64).map(|v| v as f64 + 0.5).collect(),
+        volume: Some((1..=64).map(|v| 1000.0 + v as f64).collect()),
+    }
+}
+
+#[test]
+fn reuses_a_shared_indicator_node_across_two_downstream_consumers() {
+    let pipeline = Pipeline {
+        ohlcv: sample_ohlcv(),
+        nodes: vec![
+            PipelineNode {
+                id: "close".to_string(),
+                kind: PipelineNodeKind::Source("close".to_string()),
+            },
+            PipelineNode {
+                id: "ema_fast".to_string(),
+                kind: PipelineNodeKind::Indicator {
+                    indicator_id: "ema".to_string(),
+                    params: json!({"period": 5}),
+                    inputs: BTreeMap::from([("source".to_string(), "close".to_string())]),
+                    output: None,
+                },
+            },
+            PipelineNode {
+                id: "above_ema".to_string(),
+                kind: PipelineNodeKind::BinOp {
+                    op: BinOp::Sub,
+                    lhs: "close".to_string(),
+                    rhs: BinOpRhs::Node("ema_fast".to_string()),
+                },
+            },
+            PipelineNode {
+                id: "crossed_ema".to_string(),
+                kind: PipelineNodeKind::Event {
+                    kind: EventKind::CrossUp,
+                    lhs: "close".to_string(),
+                    rhs: Some("ema_fast".to_string()),
+                },
+            },
+        ],
+    };
+
+    let out = evaluate_pipeline(&pipeline).expect("pipeline should evaluate");
+    assert_eq!(out.len(), 4);
+    match &out["above_ema"] {
+        PipelineValue::Numeric(values) => assert_eq!(values.len(), 64),
+        other => panic!("expected numeric output, got {other:?}"),
+    }
+    match &out["crossed_ema"] {
+        PipelineValue::Boolean(values) => assert_eq!(values.len(), 64),
+        other => panic!("expected boolean output, got {other:?}"),
+    }
+}
+
+#[test]
+fn binop_against_a_scalar_broadcasts_elementwise() {
+    let pipeline = Pipeline {
+        ohlcv: sample_ohlcv(),
+        nodes: vec![
+            PipelineNode {
+                id: "close".to_string(),
+                kind: PipelineNodeKind::Source("close".to_string()),
+            },
+            PipelineNode {
+                id: "close_plus_ten".to_string(),
+                kind: PipelineNodeKind::BinOp {
+                    op: BinOp::Add,
+                    lhs: "close".to_string(),
+                    rhs: BinOpRhs::Scalar(10.0),
+                },
+            },
+        ],
+    };
+
+    let out = evaluate_pipeline(&pipeline).expect("pipeline should evaluate");
+    let PipelineValue::Numeric(close) = &out["close"] else {
+        panic!("expected numeric close");
+    };
+    let PipelineValue::Numeric(shifted) = &out["close_plus_ten"] else {
+        panic!("expected numeric close_plus_ten");
+    };
+    assert_eq!(shifted[0], close[0] + 10.0);
+}
+
+#[test]
+fn reports_cycle_instead_of_looping() {
+    let pipeline = Pipeline {
+        ohlcv: sample_ohlcv(),
+        nodes: vec![
+            PipelineNode {
+                id: "a".to_string(),
+                kind: PipelineNodeKind::BinOp {
+                    op: BinOp::Add,
+                    lhs: "b".to_string(),
+                    rhs: BinOpRhs::Scalar(1.0),
+                },
+            },
+            PipelineNode {
+                id: "b".to_string(),
+                kind: PipelineNodeKind::BinOp {
+                    op: BinOp::Add,
+                    lhs: "a".to_string(),
+                    rhs: BinOpRhs::Scalar(1.0),
+                },
+            },
+        ],
+    };
+
+    let err = evaluate_pipeline(&pipeline).unwrap_err();
+    assert_eq!(err.code, "pipeline_cycle");
+}
+
+#[test]
+fn reports_unknown_node_reference_instead_of_panicking() {
+    let pipeline = Pipeline {
+        ohlcv: sample_ohlcv(),
+        nodes: vec![PipelineNode {
+            id: "a".to_string(),
+            kind: PipelineNodeKind::BinOp {
+                op: BinOp::Add,
+                lhs: "missing".to_string(),
+                rhs: BinOpRhs::Scalar(1.0),
+            },
+        }],
+    };
+
+    let err = evaluate_pipeline(&pipeline).unwrap_err();
+    assert_eq!(err.code, "unknown_pipeline_node");
+}
@@ -0,0 +1,162 @@
+use std::collections::BTreeMap;
+
+use serde_json::json;
+use ta_engine::{
+    compute_pipeline, execute_indicator_plan, IndicatorPlan, OhlcvInput, PipelineIndicatorSpec,
+    PlanInputSource, PlanNode,
+};
+
+fn sample_ohlcv() -> OhlcvInput {
+    OhlcvInput {
+        timestamps: (1..=64).collect(),
+        open: (1..=64).map(|v| v as f64).collect(),
+        high: (1..=64).map(|v| v as f64 + 1.0).collect(),
+        low: (1..=64).map(|v| v as f64 - 1.0).collect(),
+        close: (1..=64).map(|v| v as f64 + 0.5).collect(),
+        volume: Some((1..=64).map(|v| 1000.0 + v as f64).collect()),
+    }
+}
+
+#[test]
+fn chains_upstream_output_into_downstream_input() {
+    let plan = IndicatorPlan {
+        ohlcv: sample_ohlcv(),
+        nodes: vec![
+            PlanNode {
+                id: "sma_fast".to_string(),
+                indicator_id: "sma".to_string(),
+                params: json!({"period": 5}),
+                inputs: BTreeMap::new(),
+            },
+            PlanNode {
+                id: "rsi_of_sma".to_string(),
+                indicator_id: "rsi".to_string(),
+                params: json!({"period": 14}),
+                inputs: BTreeMap::from([(
+                    "source".to_string(),
+                    PlanInputSource::Node {
+                        node: "sma_fast".to_string(),
+                        output: "result".to_string(),
+                    },
+                )]),
+            },
+        ],
+    };
+
+    let out = execute_indicator_plan(&plan).expect("plan should execute");
+    assert_eq!(out.len(), 2);
+    let rsi = &out["rsi_of_sma"];
+    assert_eq!(rsi.indicator_id, "rsi");
+    assert_eq!(rsi.outputs[0].values.len(), 64);
+}
+
+#[test]
+fn reports_cycle_instead_of_looping() {
+    let plan = IndicatorPlan {
+        ohlcv: sample_ohlcv(),
+        nodes: vec![
+            PlanNode {
+                id: "a".to_string(),
+                indicator_id: "sma".to_string(),
+                params: json!({"period": 5}),
+                inputs: BTreeMap::from([(
+                    "source".to_string(),
+                    PlanInputSource::Node {
+                        node: "b".to_string(),
+                        output: "result".to_string(),
+                    },
+                )]),
+            },
+            PlanNode {
+                id: "b".to_string(),
+                indicator_id: "sma".to_string(),
+                params: json!({"period": 5}),
+                inputs: BTreeMap::from([(
+                    "source".to_string(),
+                    PlanInputSource::Node {
+                        node: "a".to_string(),
+                        output: "result".to_string(),
+                    },
+                )]),
+            },
+        ],
+    };
+
+    let err = execute_indicator_plan(&plan).expect_err("cycle should be rejected");
+    assert_eq!(err.code, "plan_cycle");
+}
+
+#[test]
+fn reports_unknown_upstream_node() {
+    let plan = IndicatorPlan {
+        ohlcv: sample_ohlcv(),
+        nodes: vec![PlanNode {
+            id: "rsi_of_missing".to_string(),
+            indicator_id: "rsi".to_string(),
+            params: json!({"period": 14}),
+            inputs: BTreeMap::from([(
+                "source".to_string(),
+                PlanInputSource::Node {
+                    node: "does_not_exist".to_string(),
+                    output: "result".to_string(),
+                },
+            )]),
+        }],
+    };
+
+    let err = execute_indicator_plan(&plan).expect_err("missing upstream should fail");
+    assert_eq!(err.code, "unknown_plan_node");
+}
+
+#[test]
+fn compute_pipeline_resolves_a_dotted_source_reference() {
+    let specs = vec![
+        PipelineIndicatorSpec {
+            instance_id: "sma_fast".to_string(),
+            indicator_id: "sma".to_string(),
+            params: json!({"period": 5}),
+        },
+        PipelineIndicatorSpec {
+            instance_id: "rsi_of_sma".to_string(),
+            indicator_id: "rsi".to_string(),
+            params: json!({"period": 14, "source": "sma_fast.result"}),
+        },
+    ];
+
+    let out = compute_pipeline(&specs, sample_ohlcv()).expect("pipeline should compute");
+    assert_eq!(out.len(), 2);
+    let rsi = &out["rsi_of_sma"];
+    assert_eq!(rsi.indicator_id, "rsi");
+    assert_eq!(rsi.outputs[0].values.len(), 64);
+}
+
+#[test]
+fn compute_pipeline_leaves_an_ordinary_string_param_alone() {
+    let specs = vec![PipelineIndicatorSpec {
+        instance_id: "rsi_default".to_string(),
+        indicator_id: "rsi".to_string(),
+        params: json!({"period": 14, "source": "close"}),
+    }];
+
+    let out = compute_pipeline(&specs, sample_ohlcv()).expect("pipeline should compute");
+    assert_eq!(out["rsi_default"].outputs[0].values.len(), 64);
+}
+
+#[test]
+fn compute_pipeline_reports_a_cycle_the_same_way_execute_indicator_plan_does() {
+    let specs = vec![
+        PipelineIndicatorSpec {
+            instance_id: "a".to_string(),
+            indicator_id: "sma".to_string(),
+            params: json!({"period": 5, "source": "b.result"}),
+        },
+        PipelineIndicatorSpec {
+            instance_id: "b".to_string(),
+            indicator_id: "sma".to_string(),
+            params: json!({"period": 5, "source": "a.result"}),
+        },
+    ];
+
+    let err = compute_pipeline(&specs, sample_ohlcv()).expect_err("cycle should be rejected");
+    assert_eq!(err.code, "plan_cycle");
+}
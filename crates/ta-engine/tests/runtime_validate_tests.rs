@@ -0,0 +1,289 @@
+use std::collections::BTreeMap;
+
+use ta_engine::metadata::{
+    IndicatorAliasMeta, IndicatorMeta, IndicatorOutputMeta, IndicatorPaneHint, IndicatorParamDefault,
+    IndicatorParamKind, IndicatorParamMeta, IndicatorScaleGroup, IndicatorSemanticsMeta,
+    IndicatorVisualMeta,
+};
+use ta_engine::{validate, validate_and_normalize, ParamRule, ParamValue};
+
+fn rsi_meta() -> IndicatorMeta {
+    IndicatorMeta {
+        id: "rsi",
+        display_name: "Relative Strength Index",
+        category: "momentum",
+        aliases: &[],
+        param_aliases: &[IndicatorAliasMeta {
+            alias: "lookback",
+            target: "period",
+        }],
+        params: &[IndicatorParamMeta {
+            name: "period",
+            kind: IndicatorParamKind::Integer,
+            required: false,
+            default: Some(IndicatorParamDefault::Integer(14)),
+            description: "Lookback period",
+            min: Some(1.0),
+            max: None,
+            allowed: None,
+        }],
+        outputs: &[IndicatorOutputMeta {
+            name: "result",
+            kind: "line",
+            description: "RSI value",
+        }],
+        semantics: IndicatorSemanticsMeta {
+            required_fields: &["close"],
+            optional_fields: &[],
+            lookback_params: &["period"],
+            default_lookback: None,
+            warmup_policy: "window",
+            source_param: None,
+        },
+        visual: IndicatorVisualMeta {
+            pane_hint: IndicatorPaneHint::SeparatePane,
+            scale_group: IndicatorScaleGroup::Oscillator,
+            output_visuals: &[],
+            style_slots: &[],
+        },
+        runtime_binding: "rsi",
+    }
+}
+
+fn bbands_meta() -> IndicatorMeta {
+    IndicatorMeta {
+        id: "bbands",
+        display_name: "Bollinger Bands",
+        category: "volatility",
+        aliases: &[],
+        param_aliases: &[],
+        params: &[
+            IndicatorParamMeta {
+                name: "period",
+                kind: IndicatorParamKind::Integer,
+                required: false,
+                default: Some(IndicatorParamDefault::Integer(20)),
+                description: "Lookback period",
+                min: Some(1.0),
+                max: None,
+                allowed: None,
+            },
+            IndicatorParamMeta {
+                name: "std_dev",
+                kind: IndicatorParamKind::Float,
+                required: true,
+                default: None,
+                description: "Standard deviation multiplier",
+                min: Some(0.0),
+                max: Some(10.0),
+                allowed: None,
+            },
+        ],
+        outputs: &[IndicatorOutputMeta {
+            name: "result",
+            kind: "line",
+            description: "Bollinger Bands value",
+        }],
+        semantics: IndicatorSemanticsMeta {
+            required_fields: &["close"],
+            optional_fields: &[],
+            lookback_params: &["period"],
+            default_lookback: None,
+            warmup_policy: "window",
+            source_param: None,
+        },
+        visual: IndicatorVisualMeta {
+            pane_hint: IndicatorPaneHint::PriceOverlay,
+            scale_group: IndicatorScaleGroup::Price,
+            output_visuals: &[],
+            style_slots: &[],
+        },
+        runtime_binding: "bbands",
+    }
+}
+
+fn stochastic_meta() -> IndicatorMeta {
+    IndicatorMeta {
+        id: "stochastic",
+        display_name: "Stochastic Oscillator",
+        category: "momentum",
+        aliases: &[],
+        param_aliases: &[],
+        params: &[IndicatorParamMeta {
+            name: "smoothing_method",
+            kind: IndicatorParamKind::String,
+            required: false,
+            default: Some(IndicatorParamDefault::String("MVA")),
+            description: "Smoothing method applied to %K/%D: MVA or EMA",
+            min: None,
+            max: None,
+            allowed: Some(&["MVA", "EMA"]),
+        }],
+        outputs: &[IndicatorOutputMeta {
+            name: "k",
+            kind: "osc_main",
+            description: "K line",
+        }],
+        semantics: IndicatorSemanticsMeta {
+            required_fields: &["high", "low", "close"],
+            optional_fields: &[],
+            lookback_params: &[],
+            default_lookback: None,
+            warmup_policy: "window",
+            source_param: None,
+        },
+        visual: IndicatorVisualMeta {
+            pane_hint: IndicatorPaneHint::SeparatePane,
+            scale_group: IndicatorScaleGroup::Oscillator,
+            output_visuals: &[],
+            style_slots: &[],
+        },
+        runtime_binding: "stochastic_kd",
+    }
+}
+
+#[test]
+fn accepts_a_value_in_the_allowed_list() {
+    let meta = stochastic_meta();
+    let supplied = BTreeMap::from([("smoothing_method", ParamValue::String("EMA".to_string()))]);
+    let validated = validate(&meta, &supplied).expect("should validate");
+    assert_eq!(
+        validated.get("smoothing_method"),
+        Some(&ParamValue::String("EMA".to_string()))
+    );
+}
+
+#[test]
+fn rejects_a_value_outside_the_allowed_list() {
+    let meta = stochastic_meta();
+    let supplied = BTreeMap::from([("smoothing_method", ParamValue::String("SMA".to_string()))]);
+    let errors = validate(&meta, &supplied).unwrap_err();
+    assert_eq!(errors[0].rule, ParamRule::NotAllowed(&["MVA", "EMA"]));
+}
+
+#[test]
+fn resolves_an_alias_to_its_canonical_name() {
+    let meta = rsi_meta();
+    let supplied = BTreeMap::from([("lookback", ParamValue::Integer(9))]);
+    let validated = validate(&meta, &supplied).expect("should validate");
+    assert_eq!(validated.get("period"), Some(&ParamValue::Integer(9)));
+}
+
+#[test]
+fn fills_the_catalog_default_when_nothing_is_supplied() {
+    let meta = rsi_meta();
+    let validated = validate(&meta, &BTreeMap::new()).expect("should validate");
+    assert_eq!(validated.get("period"), Some(&ParamValue::Integer(14)));
+}
+
+#[test]
+fn widens_an_integer_supplied_for_a_float_param() {
+    let meta = bbands_meta();
+    let supplied = BTreeMap::from([("std_dev", ParamValue::Integer(2))]);
+    let validated = validate(&meta, &supplied).expect("should validate");
+    assert_eq!(validated.get("std_dev"), Some(&ParamValue::Float(2.0)));
+}
+
+#[test]
+fn rejects_a_missing_required_param() {
+    let meta = bbands_meta();
+    let errors = validate(&meta, &BTreeMap::new()).unwrap_err();
+    assert_eq!(
+        errors,
+        vec![ta_engine::ParamError {
+            field: "std_dev".to_string(),
+            rule: ParamRule::MissingRequired,
+        }]
+    );
+}
+
+#[test]
+fn rejects_an_unknown_param() {
+    let meta = rsi_meta();
+    let supplied = BTreeMap::from([("not_a_param", ParamValue::Integer(1))]);
+    let errors = validate(&meta, &supplied).unwrap_err();
+    assert_eq!(errors[0].rule, ParamRule::Unknown);
+}
+
+#[test]
+fn rejects_a_value_below_min() {
+    let meta = rsi_meta();
+    let supplied = BTreeMap::from([("period", ParamValue::Integer(0))]);
+    let errors = validate(&meta, &supplied).unwrap_err();
+    assert_eq!(errors[0].rule, ParamRule::BelowMin(1.0));
+}
+
+#[test]
+fn rejects_a_value_above_max() {
+    let meta = bbands_meta();
+    let supplied = BTreeMap::from([("std_dev", ParamValue::Float(20.0))]);
+    let errors = validate(&meta, &supplied).unwrap_err();
+    assert!(errors
+        .iter()
+        .any(|e| e.field == "std_dev" && e.rule == ParamRule::AboveMax(10.0)));
+}
+
+#[test]
+fn rejects_a_non_finite_float() {
+    let meta = bbands_meta();
+    let supplied = BTreeMap::from([("std_dev", ParamValue::Float(f64::NAN))]);
+    let errors = validate(&meta, &supplied).unwrap_err();
+    assert!(errors
+        .iter()
+        .any(|e| e.field == "std_dev" && e.rule == ParamRule::NotFinite));
+}
+
+#[test]
+fn rejects_duplicate_assignment_through_an_alias() {
+    let meta = rsi_meta();
+    let supplied = BTreeMap::from([
+        ("period", ParamValue::Integer(10)),
+        ("lookback", ParamValue::Integer(20)),
+    ]);
+    let errors = validate(&meta, &supplied).unwrap_err();
+    assert!(errors
+        .iter()
+        .any(|e| e.field == "period" && e.rule == ParamRule::DuplicateAssignment));
+}
+
+#[test]
+fn validate_and_normalize_parses_strings_and_resolves_aliases_against_the_catalog() {
+    let params = BTreeMap::from([("lookback".to_string(), "9".to_string())]);
+    let validated = validate_and_normalize("rsi", &params).expect("should validate");
+    assert_eq!(validated.get("period"), Some(&ParamValue::Integer(9)));
+}
+
+#[test]
+fn validate_and_normalize_fills_catalog_defaults_for_a_real_indicator() {
+    let validated =
+        validate_and_normalize("bbands", &BTreeMap::new()).expect("should validate");
+    assert_eq!(validated.get("period"), Some(&ParamValue::Integer(20)));
+}
+
+#[test]
+fn validate_and_normalize_parses_a_float_string_for_a_float_param() {
+    let params = BTreeMap::from([("std_dev".to_string(), "2.5".to_string())]);
+    let validated = validate_and_normalize("bbands", &params).expect("should validate");
+    assert_eq!(validated.get("std_dev"), Some(&ParamValue::Float(2.5)));
+}
+
+#[test]
+fn validate_and_normalize_rejects_an_unparseable_value_for_its_kind() {
+    let params = BTreeMap::from([("period".to_string(), "not_a_number".to_string())]);
+    let errors = validate_and_normalize("rsi", &params).unwrap_err();
+    assert!(errors
+        .iter()
+        .any(|e| e.field == "period" && e.rule == ParamRule::WrongKind(IndicatorParamKind::Integer)));
+}
+
+#[test]
+fn validate_and_normalize_rejects_an_unknown_indicator_id() {
+    let errors = validate_and_normalize("not_a_real_indicator", &BTreeMap::new()).unwrap_err();
+    assert_eq!(
+        errors,
+        vec![ta_engine::ParamError {
+            field: "not_a_real_indicator".to_string(),
+            rule: ParamRule::UnknownIndicator,
+        }]
+    );
+}
@@ -0,0 +1,66 @@
+use ta_engine::statistics;
+
+fn sample_ohlc(n: usize) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let close: Vec<f64> = (0..n)
+        .map(|v| 100.0 + (v as f64 * 0.3).sin() * 4.0 + v as f64 * 0.15)
+        .collect();
+    let high: Vec<f64> = close.iter().map(|c| c + 1.0).collect();
+    let low: Vec<f64> = close.iter().map(|c| c - 1.0).collect();
+    (high, low, close)
+}
+
+#[test]
+fn normalized_atr_is_a_percentage_of_close() {
+    let (high, low, close) = sample_ohlc(40);
+    let natr = statistics::normalized_atr(&high, &low, &close, 14);
+
+    assert!(natr[..13].iter().all(|v| v.is_nan()));
+    for i in 13..close.len() {
+        assert!(natr[i].is_finite());
+        assert!(natr[i] > 0.0);
+    }
+}
+
+#[test]
+fn linreg_slope_is_positive_for_a_straight_uptrend() {
+    let close: Vec<f64> = (0..30).map(|v| 10.0 + v as f64 * 2.0).collect();
+    let (slope, intercept, r_squared) = statistics::linreg_slope(&close, 10);
+
+    let last = close.len() - 1;
+    assert!((slope[last] - 2.0).abs() < 1e-9);
+    assert!(r_squared[last] > 0.999);
+    assert!((intercept[last] + slope[last] * 9.0 - close[last]).abs() < 1e-9);
+}
+
+#[test]
+fn smma_matches_wilder_recurrence() {
+    let close = vec![10.0, 12.0, 11.0, 13.0, 14.0, 12.0, 15.0];
+    let period = 3usize;
+    let smma = ta_engine::moving_averages::rma(&close, period);
+
+    let mut expected = vec![close[0]];
+    for &x in &close[1..] {
+        let prev = *expected.last().unwrap();
+        expected.push(prev + (x - prev) / period as f64);
+    }
+
+    for (got, want) in smma.iter().zip(expected.iter()) {
+        assert!((got - want).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn hurst_exponent_is_high_for_a_persistent_trend_and_low_for_noise() {
+    let trending: Vec<f64> = (0..128).map(|v| 100.0 + v as f64).collect();
+    let trending_h = statistics::hurst_exponent(&trending, 100);
+    assert!(trending_h[127] > 0.6);
+
+    let mut noisy = Vec::with_capacity(128);
+    let mut state = 100.0;
+    for i in 0..128 {
+        state += if i % 2 == 0 { 1.0 } else { -1.0 };
+        noisy.push(state);
+    }
+    let noisy_h = statistics::hurst_exponent(&noisy, 100);
+    assert!(noisy_h[127] < trending_h[127]);
+}
@@ -0,0 +1,20 @@
+use ta_engine::indicators::streaming::{RollingMaxState, RollingMinState};
+
+#[test]
+fn rolling_min_state_evicts_a_nan_candidate_once_a_finite_value_follows_it() {
+    // Regression for a should_evict comparator that used `>=` directly:
+    // NaN always compares false, so a NaN tick was never evicted and could
+    // be reported as the window's min for the life of the state.
+    let mut state = RollingMinState::new(3);
+    assert_eq!(state.update(5.0), None);
+    assert_eq!(state.update(f64::NAN), None);
+    assert_eq!(state.update(2.0), Some(2.0));
+}
+
+#[test]
+fn rolling_max_state_evicts_a_nan_candidate_once_a_finite_value_follows_it() {
+    let mut state = RollingMaxState::new(3);
+    assert_eq!(state.update(5.0), None);
+    assert_eq!(state.update(f64::NAN), None);
+    assert_eq!(state.update(2.0), Some(5.0));
+}
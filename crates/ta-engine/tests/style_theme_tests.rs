@@ -0,0 +1,58 @@
+use ta_engine::metadata::{resolve_styles, SlotOverride, StyleError, StyleTheme};
+
+#[test]
+fn light_theme_reproduces_the_slots_compiled_defaults() {
+    let resolved = resolve_styles("rsi", &StyleTheme::light()).expect("rsi is in the catalog");
+    assert_eq!(resolved["primary_line"].color, "#38bdf8");
+}
+
+#[test]
+fn dark_theme_brightens_the_oscillator_line() {
+    let resolved = resolve_styles("rsi", &StyleTheme::dark()).expect("rsi is in the catalog");
+    assert_eq!(resolved["primary_line"].color, "#7dd3fc");
+}
+
+#[test]
+fn dark_theme_brightens_the_ichimoku_kumo_fill_without_touching_other_slots() {
+    let resolved =
+        resolve_styles("ichimoku", &StyleTheme::dark()).expect("ichimoku is in the catalog");
+    assert_eq!(resolved["kumo_fill"].color, "#94a3b8");
+    assert_eq!(resolved["kumo_fill"].opacity, Some(0.25));
+    assert_eq!(resolved["tenkan_line"].color, "#f97316");
+}
+
+#[test]
+fn a_further_override_layered_on_a_built_in_theme_wins() {
+    let theme = StyleTheme::dark().with_slot("primary_line", SlotOverride::default().color("#ffffff"));
+    let resolved = resolve_styles("rsi", &theme).expect("rsi is in the catalog");
+    assert_eq!(resolved["primary_line"].color, "#ffffff");
+}
+
+#[test]
+fn an_unset_override_field_falls_back_to_the_slot_default() {
+    let theme = StyleTheme::named("custom").with_slot("primary_line", SlotOverride::default().color("#ffffff"));
+    let resolved = resolve_styles("rsi", &theme).expect("rsi is in the catalog");
+    assert_eq!(resolved["primary_line"].width, Some(1.5));
+}
+
+#[test]
+fn rejects_an_unknown_indicator_id() {
+    let error = resolve_styles("not_a_real_indicator", &StyleTheme::light()).unwrap_err();
+    assert_eq!(
+        error,
+        StyleError::UnknownIndicator("not_a_real_indicator".to_string())
+    );
+}
+
+#[test]
+fn rejects_an_overlay_for_a_slot_the_indicator_does_not_declare() {
+    let theme = StyleTheme::named("custom").with_slot("kumo_fill", SlotOverride::default().color("#000000"));
+    let error = resolve_styles("rsi", &theme).unwrap_err();
+    assert_eq!(
+        error,
+        StyleError::UnknownSlot {
+            indicator: "rsi",
+            slot: "kumo_fill",
+        }
+    );
+}
@@ -0,0 +1,79 @@
+use ta_engine::contracts::{TaSeriesF64, TaStatusCode};
+
+fn series(values: &[f64], availability_mask: &[bool]) -> TaSeriesF64 {
+    TaSeriesF64::new(values.to_vec(), availability_mask.to_vec()).unwrap()
+}
+
+#[test]
+fn add_masks_output_available_only_where_both_inputs_are() {
+    let a = series(&[1.0, 2.0, 3.0], &[true, true, false]);
+    let b = series(&[10.0, 20.0, 30.0], &[true, false, false]);
+
+    let sum = a.add(&b).unwrap();
+
+    assert_eq!(sum.values, vec![11.0, 22.0, 33.0]);
+    assert_eq!(sum.availability_mask, vec![true, false, false]);
+}
+
+#[test]
+fn sub_mul_div_all_and_their_masks_the_same_way() {
+    let a = series(&[4.0, 9.0], &[true, false]);
+    let b = series(&[2.0, 3.0], &[true, true]);
+
+    assert_eq!(a.sub(&b).unwrap().availability_mask, vec![true, false]);
+    assert_eq!(a.mul(&b).unwrap().availability_mask, vec![true, false]);
+    assert_eq!(a.div(&b).unwrap().availability_mask, vec![true, false]);
+    assert_eq!(a.div(&b).unwrap().values, vec![2.0, 3.0]);
+}
+
+#[test]
+fn elementwise_ops_reject_length_mismatch() {
+    let a = series(&[1.0, 2.0], &[true, true]);
+    let b = series(&[1.0], &[true]);
+
+    assert_eq!(a.add(&b).unwrap_err(), TaStatusCode::ShapeMismatch);
+    assert_eq!(a.sub(&b).unwrap_err(), TaStatusCode::ShapeMismatch);
+    assert_eq!(a.mul(&b).unwrap_err(), TaStatusCode::ShapeMismatch);
+    assert_eq!(a.div(&b).unwrap_err(), TaStatusCode::ShapeMismatch);
+}
+
+#[test]
+fn scalar_ops_preserve_the_input_mask() {
+    let a = series(&[1.0, 2.0, 3.0], &[true, false, true]);
+
+    assert_eq!(a.add_scalar(1.0).availability_mask, a.availability_mask);
+    assert_eq!(a.sub_scalar(1.0).availability_mask, a.availability_mask);
+    assert_eq!(a.mul_scalar(2.0).availability_mask, a.availability_mask);
+    assert_eq!(a.div_scalar(2.0).availability_mask, a.availability_mask);
+    assert_eq!(a.add_scalar(1.0).values, vec![2.0, 3.0, 4.0]);
+}
+
+#[test]
+fn fill_forward_carries_the_last_available_value_into_gaps() {
+    let a = series(&[1.0, 0.0, 0.0, 5.0, 0.0], &[true, false, false, true, false]);
+
+    let filled = a.fill_forward();
+
+    assert_eq!(filled.values, vec![1.0, 1.0, 1.0, 5.0, 5.0]);
+    assert_eq!(filled.availability_mask, vec![true, true, true, true, true]);
+}
+
+#[test]
+fn fill_forward_leaves_a_leading_gap_with_nothing_to_carry() {
+    let a = series(&[0.0, 0.0, 3.0], &[false, false, true]);
+
+    let filled = a.fill_forward();
+
+    assert_eq!(filled.availability_mask, vec![false, false, true]);
+    assert_eq!(filled.values, vec![0.0, 0.0, 3.0]);
+}
+
+#[test]
+fn fill_value_replaces_only_unavailable_slots() {
+    let a = series(&[1.0, 0.0, 3.0], &[true, false, true]);
+
+    let filled = a.fill_value(-1.0);
+
+    assert_eq!(filled.values, vec![1.0, -1.0, 3.0]);
+    assert_eq!(filled.availability_mask, vec![true, true, true]);
+}
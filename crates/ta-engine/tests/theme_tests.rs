@@ -0,0 +1,40 @@
+use ta_engine::metadata::{indicator_catalog, resolve_style, ColorToken, Theme};
+
+fn rsi_visual() -> &'static ta_engine::metadata::IndicatorVisualMeta {
+    let rsi = indicator_catalog()
+        .iter()
+        .find(|meta| meta.id == "rsi")
+        .expect("catalog always has rsi");
+    &rsi.visual
+}
+
+#[test]
+fn default_theme_reproduces_the_slot_default_color() {
+    let resolved = resolve_style(rsi_visual(), &Theme::default_theme());
+    let primary_line = resolved
+        .iter()
+        .find(|slot| slot.slot == "primary_line")
+        .expect("rsi has a primary_line slot");
+    assert_eq!(primary_line.color, "#38bdf8");
+}
+
+#[test]
+fn custom_theme_override_takes_precedence_over_the_token_default() {
+    let theme = Theme::named("midnight").with_color(ColorToken::Primary, "#ffffff");
+    let resolved = resolve_style(rsi_visual(), &theme);
+    let primary_line = resolved
+        .iter()
+        .find(|slot| slot.slot == "primary_line")
+        .expect("rsi has a primary_line slot");
+    assert_eq!(primary_line.color, "#ffffff");
+}
+
+#[test]
+fn custom_theme_leaves_unrelated_tokens_at_their_default() {
+    let theme = Theme::named("midnight").with_color(ColorToken::Primary, "#ffffff");
+    let resolved = resolve_style(rsi_visual(), &theme);
+    assert_eq!(resolved.len(), 1);
+    assert_eq!(resolved[0].kind, ta_engine::metadata::StyleSlotType::Stroke);
+    assert_eq!(resolved[0].width, Some(1.5));
+    assert_eq!(resolved[0].opacity, None);
+}
@@ -0,0 +1,60 @@
+use ta_engine::trend;
+
+fn sample_ohlc() -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let close: Vec<f64> = (1..=40)
+        .map(|v| 100.0 + (v as f64 * 0.4).sin() * 5.0 + v as f64 * 0.2)
+        .collect();
+    let high: Vec<f64> = close.iter().map(|c| c + 1.0).collect();
+    let low: Vec<f64> = close.iter().map(|c| c - 1.0).collect();
+    (high, low, close)
+}
+
+#[test]
+fn supertrend_warms_up_then_tracks_one_active_band() {
+    let (high, low, close) = sample_ohlc();
+    let (line, direction) = trend::supertrend(&high, &low, &close, 10, 3.0);
+
+    assert!(line[..9].iter().all(|v| v.is_nan()));
+    assert!(direction[..9].iter().all(|v| v.is_nan()));
+
+    for i in 9..close.len() {
+        assert!(line[i].is_finite());
+        assert!(direction[i] == 1.0 || direction[i] == -1.0);
+        if direction[i] == 1.0 {
+            assert!(close[i] >= line[i] || i == 9);
+        }
+    }
+}
+
+#[test]
+fn stl_does_not_panic_on_a_nan_padded_series_long_enough_to_reach_median() {
+    // Long enough to clear stl's own degenerate-length guard (n >= 2 *
+    // period), but still NaN-padded up front -- exactly what a composed
+    // series looks like once it's downstream of another indicator's
+    // warmup. bisquare_weights' internal median used to panic on this via
+    // partial_cmp(...).unwrap().
+    let (_, _, close) = sample_ohlc();
+    let mut values = vec![f64::NAN; 5];
+    values.extend(close);
+
+    let (trend, seasonal, remainder) = trend::stl(&values, 7);
+
+    assert_eq!(trend.len(), values.len());
+    assert_eq!(seasonal.len(), values.len());
+    assert_eq!(remainder.len(), values.len());
+}
+
+#[test]
+fn supertrend_direction_flips_bearish_when_price_crashes_through_the_lower_band() {
+    let mut close: Vec<f64> = (1..=30).map(|v| 100.0 + v as f64).collect();
+    for v in close.iter_mut().skip(20) {
+        *v -= 40.0;
+    }
+    let high: Vec<f64> = close.iter().map(|c| c + 1.0).collect();
+    let low: Vec<f64> = close.iter().map(|c| c - 1.0).collect();
+
+    let (_line, direction) = trend::supertrend(&high, &low, &close, 5, 2.0);
+
+    assert_eq!(direction[19], 1.0);
+    assert_eq!(direction[29], -1.0);
+}
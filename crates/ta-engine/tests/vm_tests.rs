@@ -0,0 +1,174 @@
+use std::collections::BTreeMap;
+
+use ta_engine::vm::{eval_program, EvalProgramError, InputId, Inputs, Op, Program};
+
+fn sample_inputs() -> (Vec<f64>, Vec<f64>, Vec<f64>, Vec<f64>, Vec<f64>) {
+    let close = vec![
+        10.0, 10.5, 11.0, 10.8, 11.2, 11.5, 11.3, 11.8, 12.0, 12.4, 12.1, 12.6, 12.9, 13.1, 13.4,
+    ];
+    let high: Vec<f64> = close.iter().map(|c| c + 0.3).collect();
+    let low: Vec<f64> = close.iter().map(|c| c - 0.3).collect();
+    let open = close.clone();
+    let volume = vec![1.0; close.len()];
+    (open, high, low, close, volume)
+}
+
+#[test]
+fn evaluates_a_macd_minus_atr_style_composite() {
+    let (open, high, low, close, volume) = sample_inputs();
+    let len = close.len();
+    let inputs = Inputs {
+        open: &open,
+        high: &high,
+        low: &low,
+        close: &close,
+        volume: &volume,
+    };
+
+    let mut fast_params = BTreeMap::new();
+    fast_params.insert("period".to_string(), 3.0);
+    let mut slow_params = BTreeMap::new();
+    slow_params.insert("period".to_string(), 5.0);
+    let mut atr_params = BTreeMap::new();
+    atr_params.insert("period".to_string(), 3.0);
+
+    let program = Program {
+        ops: vec![
+            Op::CallIndicator {
+                dst: 0,
+                indicator_id: "ema".to_string(),
+                input: InputId::Close,
+                params: fast_params,
+            },
+            Op::CallIndicator {
+                dst: 1,
+                indicator_id: "ema".to_string(),
+                input: InputId::Close,
+                params: slow_params,
+            },
+            Op::Sub { dst: 2, a: 0, b: 1 },
+            Op::CallIndicator {
+                dst: 3,
+                indicator_id: "atr".to_string(),
+                input: InputId::Close,
+                params: atr_params,
+            },
+            Op::Div { dst: 4, a: 2, b: 3 },
+        ],
+        result_reg: 4,
+        register_count: 5,
+    };
+
+    let expected_fast = ta_engine::moving_averages::ema(&close, 3);
+    let expected_slow = ta_engine::moving_averages::ema(&close, 5);
+    let expected_atr = ta_engine::volatility::atr(&high, &low, &close, 3);
+
+    let result = eval_program(&program, &inputs, len).expect("program should evaluate");
+    assert_eq!(result.len(), len);
+    for i in 0..len {
+        let expected = (expected_fast[i] - expected_slow[i]) / expected_atr[i];
+        if expected.is_nan() {
+            assert!(result[i].is_nan(), "index {i} should still be warming up");
+            continue;
+        }
+        assert!((result[i] - expected).abs() < 1e-9, "index {i}");
+    }
+}
+
+#[test]
+fn if_pos_te_selects_elementwise() {
+    let (open, high, low, close, volume) = sample_inputs();
+    let len = close.len();
+    let inputs = Inputs {
+        open: &open,
+        high: &high,
+        low: &low,
+        close: &close,
+        volume: &volume,
+    };
+
+    let program = Program {
+        ops: vec![
+            Op::LoadSeries {
+                dst: 0,
+                input: InputId::Close,
+            },
+            Op::AddConst {
+                dst: 1,
+                src: 0,
+                value: -12.0,
+            },
+            Op::LoadSeries {
+                dst: 2,
+                input: InputId::High,
+            },
+            Op::LoadSeries {
+                dst: 3,
+                input: InputId::Low,
+            },
+            Op::IfPosTE {
+                dst: 4,
+                cond: 1,
+                a: 2,
+                b: 3,
+            },
+        ],
+        result_reg: 4,
+        register_count: 5,
+    };
+
+    let result = eval_program(&program, &inputs, len).expect("program should evaluate");
+    for i in 0..len {
+        let expected = if close[i] - 12.0 >= 0.0 { high[i] } else { low[i] };
+        assert_eq!(result[i], expected, "index {i}");
+    }
+}
+
+#[test]
+fn rejects_an_out_of_bounds_register() {
+    let (open, high, low, close, volume) = sample_inputs();
+    let len = close.len();
+    let inputs = Inputs {
+        open: &open,
+        high: &high,
+        low: &low,
+        close: &close,
+        volume: &volume,
+    };
+
+    let program = Program {
+        ops: vec![Op::Move { dst: 0, src: 5 }],
+        result_reg: 0,
+        register_count: 1,
+    };
+
+    let err = eval_program(&program, &inputs, len).expect_err("must reject");
+    assert_eq!(err, EvalProgramError::RegisterOutOfBounds(5, 1));
+}
+
+#[test]
+fn rejects_an_unknown_indicator() {
+    let (open, high, low, close, volume) = sample_inputs();
+    let len = close.len();
+    let inputs = Inputs {
+        open: &open,
+        high: &high,
+        low: &low,
+        close: &close,
+        volume: &volume,
+    };
+
+    let program = Program {
+        ops: vec![Op::CallIndicator {
+            dst: 0,
+            indicator_id: "vwma".to_string(),
+            input: InputId::Close,
+            params: BTreeMap::new(),
+        }],
+        result_reg: 0,
+        register_count: 1,
+    };
+
+    let err = eval_program(&program, &inputs, len).expect_err("must reject");
+    assert_eq!(err, EvalProgramError::UnknownIndicator("vwma".to_string()));
+}
@@ -0,0 +1,88 @@
+use ta_engine::volume::{vwap, vwap_anchored};
+
+#[test]
+fn matches_plain_vwap_when_no_resets_are_configured() {
+    let timestamps = [0i64, 60, 120, 180];
+    let high = [12.0, 13.0, 11.0, 14.0];
+    let low = [9.0, 10.0, 8.0, 11.0];
+    let close = [11.0, 12.0, 9.5, 13.0];
+    let volume = [100.0, 150.0, 120.0, 200.0];
+
+    let expected = vwap(&high, &low, &close, &volume);
+    let (actual, _, _) = vwap_anchored(&timestamps, &high, &low, &close, &volume, &[], 0, 2.0);
+
+    for (a, e) in actual.iter().zip(expected.iter()) {
+        assert!((a - e).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn explicit_anchors_reset_the_running_sums() {
+    let timestamps = [0i64, 60, 120, 180];
+    let high = [12.0, 13.0, 11.0, 14.0];
+    let low = [9.0, 10.0, 8.0, 11.0];
+    let close = [11.0, 12.0, 9.5, 13.0];
+    let volume = [100.0, 150.0, 120.0, 200.0];
+
+    let (vwap_out, _, _) =
+        vwap_anchored(&timestamps, &high, &low, &close, &volume, &[2], 0, 2.0);
+
+    let expected_after_reset = vwap(&high[2..], &low[2..], &close[2..], &volume[2..]);
+    assert!((vwap_out[2] - expected_after_reset[0]).abs() < 1e-9);
+    assert!((vwap_out[3] - expected_after_reset[1]).abs() < 1e-9);
+}
+
+#[test]
+fn session_boundary_derived_from_timestamps_resets_like_an_explicit_anchor() {
+    let timestamps = [0i64, 50, 100, 150];
+    let high = [12.0, 13.0, 11.0, 14.0];
+    let low = [9.0, 10.0, 8.0, 11.0];
+    let close = [11.0, 12.0, 9.5, 13.0];
+    let volume = [100.0, 150.0, 120.0, 200.0];
+
+    let (session_vwap, _, _) =
+        vwap_anchored(&timestamps, &high, &low, &close, &volume, &[], 100, 2.0);
+    let (anchor_vwap, _, _) =
+        vwap_anchored(&timestamps, &high, &low, &close, &volume, &[2], 0, 2.0);
+
+    for (a, e) in session_vwap.iter().zip(anchor_vwap.iter()) {
+        assert!((a - e).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn out_of_range_and_duplicate_anchors_are_ignored_without_panicking() {
+    let timestamps = [0i64, 60, 120, 180];
+    let high = [12.0, 13.0, 11.0, 14.0];
+    let low = [9.0, 10.0, 8.0, 11.0];
+    let close = [11.0, 12.0, 9.5, 13.0];
+    let volume = [100.0, 150.0, 120.0, 200.0];
+
+    let (with_stray_anchors, _, _) =
+        vwap_anchored(&timestamps, &high, &low, &close, &volume, &[2, 2, 99], 0, 2.0);
+    let (with_clean_anchor, _, _) =
+        vwap_anchored(&timestamps, &high, &low, &close, &volume, &[2], 0, 2.0);
+
+    for (a, e) in with_stray_anchors.iter().zip(with_clean_anchor.iter()) {
+        assert!((a - e).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn bands_widen_around_vwap_as_std_dev_multiplier_grows() {
+    let timestamps = [0i64, 60, 120, 180, 240];
+    let high = [12.0, 13.0, 11.0, 15.0, 9.0];
+    let low = [9.0, 10.0, 8.0, 11.0, 6.0];
+    let close = [11.0, 12.0, 9.5, 13.0, 7.5];
+    let volume = [100.0, 150.0, 120.0, 200.0, 90.0];
+
+    let (vwap_tight, upper_tight, lower_tight) =
+        vwap_anchored(&timestamps, &high, &low, &close, &volume, &[], 0, 1.0);
+    let (vwap_wide, upper_wide, lower_wide) =
+        vwap_anchored(&timestamps, &high, &low, &close, &volume, &[], 0, 3.0);
+
+    let last = timestamps.len() - 1;
+    assert!((vwap_tight[last] - vwap_wide[last]).abs() < 1e-9);
+    assert!(upper_wide[last] >= upper_tight[last]);
+    assert!(lower_wide[last] <= lower_tight[last]);
+}
@@ -1,3 +1,5 @@
+use std::collections::{BTreeMap, HashMap};
+
 use napi_derive::napi;
 
 fn period_from_u32(period: u32) -> napi::Result<usize> {
@@ -9,6 +11,21 @@ fn period_from_u32(period: u32) -> napi::Result<usize> {
     Ok(period as usize)
 }
 
+fn parse_ma_type(ma_type: &str) -> napi::Result<ta_engine::moving_averages::MovingAverageType> {
+    ta_engine::moving_averages::MovingAverageType::parse(ma_type).ok_or_else(|| {
+        napi::Error::from_reason(format!("ERR_MA_TYPE_INVALID: unsupported ma_type '{ma_type}'"))
+    })
+}
+
+/// Turns a warmup-NaN-filled series into explicit `null`s so JS callers don't
+/// have to guess how many leading values are undefined.
+fn nullable(values: Vec<f64>) -> Vec<Option<f64>> {
+    values
+        .into_iter()
+        .map(|v| if v.is_nan() { None } else { Some(v) })
+        .collect()
+}
+
 fn ensure_same_len(label: &str, lengths: &[usize]) -> napi::Result<()> {
     if lengths.is_empty() {
         return Ok(());
@@ -114,6 +131,34 @@ pub struct KlingerOutput {
     pub signal: Vec<f64>,
 }
 
+#[napi(object)]
+pub struct MacdOptOutput {
+    pub macd: Vec<Option<f64>>,
+    pub signal: Vec<Option<f64>>,
+    pub histogram: Vec<Option<f64>>,
+}
+
+#[napi(object)]
+pub struct AdxOptOutput {
+    pub adx: Vec<Option<f64>>,
+    pub plus_di: Vec<Option<f64>>,
+    pub minus_di: Vec<Option<f64>>,
+}
+
+#[napi(object)]
+pub struct MacdStepOutput {
+    pub macd: f64,
+    pub signal: f64,
+    pub histogram: f64,
+}
+
+#[napi(object)]
+pub struct StlOutput {
+    pub trend: Vec<f64>,
+    pub seasonal: Vec<f64>,
+    pub remainder: Vec<f64>,
+}
+
 #[napi]
 pub fn engine_version() -> String {
     ta_engine::engine_version().to_string()
@@ -125,6 +170,13 @@ pub fn sma(values: Vec<f64>, period: u32) -> napi::Result<Vec<f64>> {
     Ok(ta_engine::rolling::rolling_mean(&values, period))
 }
 
+/// Like [`sma`], but warmup values are `null` instead of `NaN`.
+#[napi]
+pub fn sma_opt(values: Vec<f64>, period: u32) -> napi::Result<Vec<Option<f64>>> {
+    let period = period_from_u32(period)?;
+    Ok(nullable(ta_engine::rolling::rolling_mean(&values, period)))
+}
+
 #[napi]
 pub fn ema(values: Vec<f64>, period: u32) -> napi::Result<Vec<f64>> {
     let period = period_from_u32(period)?;
@@ -155,6 +207,13 @@ pub fn rsi(values: Vec<f64>, period: u32) -> napi::Result<Vec<f64>> {
     Ok(ta_engine::momentum::rsi(&values, period))
 }
 
+/// Like [`rsi`], but warmup values are `null` instead of `NaN`.
+#[napi]
+pub fn rsi_opt(values: Vec<f64>, period: u32) -> napi::Result<Vec<Option<f64>>> {
+    let period = period_from_u32(period)?;
+    Ok(nullable(ta_engine::momentum::rsi(&values, period)))
+}
+
 #[napi]
 pub fn roc(values: Vec<f64>, period: u32) -> napi::Result<Vec<f64>> {
     let period = period_from_u32(period)?;
@@ -297,12 +356,14 @@ pub fn macd(
     fast_period: u32,
     slow_period: u32,
     signal_period: u32,
+    ma_type: Option<String>,
 ) -> napi::Result<MacdOutput> {
     let fast_period = period_from_u32(fast_period)?;
     let slow_period = period_from_u32(slow_period)?;
     let signal_period = period_from_u32(signal_period)?;
+    let ma_type = parse_ma_type(ma_type.as_deref().unwrap_or("EMA"))?;
     let (macd, signal, histogram) =
-        ta_engine::trend::macd(&values, fast_period, slow_period, signal_period);
+        ta_engine::trend::macd(&values, fast_period, slow_period, signal_period, ma_type);
     Ok(MacdOutput {
         macd,
         signal,
@@ -310,10 +371,38 @@ pub fn macd(
     })
 }
 
+/// Like [`macd`], but warmup values are `null` instead of `NaN`.
+#[napi]
+pub fn macd_opt(
+    values: Vec<f64>,
+    fast_period: u32,
+    slow_period: u32,
+    signal_period: u32,
+    ma_type: Option<String>,
+) -> napi::Result<MacdOptOutput> {
+    let fast_period = period_from_u32(fast_period)?;
+    let slow_period = period_from_u32(slow_period)?;
+    let signal_period = period_from_u32(signal_period)?;
+    let ma_type = parse_ma_type(ma_type.as_deref().unwrap_or("EMA"))?;
+    let (macd, signal, histogram) =
+        ta_engine::trend::macd(&values, fast_period, slow_period, signal_period, ma_type);
+    Ok(MacdOptOutput {
+        macd: nullable(macd),
+        signal: nullable(signal),
+        histogram: nullable(histogram),
+    })
+}
+
 #[napi]
-pub fn bbands(values: Vec<f64>, period: u32, std_dev: f64) -> napi::Result<BbandsOutput> {
+pub fn bbands(
+    values: Vec<f64>,
+    period: u32,
+    std_dev: f64,
+    ma_type: Option<String>,
+) -> napi::Result<BbandsOutput> {
     let period = period_from_u32(period)?;
-    let (upper, middle, lower) = ta_engine::volatility::bbands(&values, period, std_dev);
+    let ma_type = parse_ma_type(ma_type.as_deref().unwrap_or("SMA"))?;
+    let (upper, middle, lower) = ta_engine::volatility::bbands(&values, period, std_dev, ma_type);
     Ok(BbandsOutput {
         upper,
         middle,
@@ -351,6 +440,24 @@ pub fn adx(high: Vec<f64>, low: Vec<f64>, close: Vec<f64>, period: u32) -> napi:
     })
 }
 
+/// Like [`adx`], but warmup values are `null` instead of `NaN`.
+#[napi]
+pub fn adx_opt(
+    high: Vec<f64>,
+    low: Vec<f64>,
+    close: Vec<f64>,
+    period: u32,
+) -> napi::Result<AdxOptOutput> {
+    ensure_same_len("adx_opt", &[high.len(), low.len(), close.len()])?;
+    let period = period_from_u32(period)?;
+    let (adx, plus_di, minus_di) = ta_engine::trend::adx(&high, &low, &close, period);
+    Ok(AdxOptOutput {
+        adx: nullable(adx),
+        plus_di: nullable(plus_di),
+        minus_di: nullable(minus_di),
+    })
+}
+
 #[napi]
 #[allow(clippy::too_many_arguments)]
 pub fn ichimoku(
@@ -492,12 +599,15 @@ pub fn keltner(
     ema_period: u32,
     atr_period: u32,
     multiplier: f64,
+    ma_type: Option<String>,
 ) -> napi::Result<KeltnerOutput> {
     ensure_same_len("keltner", &[high.len(), low.len(), close.len()])?;
     let ema_period = period_from_u32(ema_period)?;
     let atr_period = period_from_u32(atr_period)?;
-    let (upper, middle, lower) =
-        ta_engine::volatility::keltner(&high, &low, &close, ema_period, atr_period, multiplier);
+    let ma_type = parse_ma_type(ma_type.as_deref().unwrap_or("EMA"))?;
+    let (upper, middle, lower) = ta_engine::volatility::keltner(
+        &high, &low, &close, ema_period, atr_period, multiplier, ma_type,
+    );
     Ok(KeltnerOutput {
         upper,
         middle,
@@ -535,6 +645,418 @@ pub fn klinger(
     Ok(KlingerOutput { klinger, signal })
 }
 
+#[napi]
+pub fn stl(values: Vec<f64>, period: u32) -> napi::Result<StlOutput> {
+    let period = period_from_u32(period)?;
+    if values.len() < 2 * period {
+        return Err(napi::Error::from_reason(
+            "ERR_PERIOD_INVALID: values must have at least 2 * period observations",
+        ));
+    }
+    let (trend, seasonal, remainder) = ta_engine::trend::stl(&values, period);
+    Ok(StlOutput {
+        trend,
+        seasonal,
+        remainder,
+    })
+}
+
+/// Streaming EMA: `update` folds in one value at a time in O(1) instead of
+/// recomputing the whole series, for live tick-by-tick loops.
+#[napi]
+pub struct EmaState {
+    inner: ta_engine::streaming::EmaState,
+}
+
+#[napi]
+impl EmaState {
+    #[napi(constructor)]
+    pub fn new(period: u32) -> napi::Result<Self> {
+        let period = period_from_u32(period)?;
+        Ok(Self {
+            inner: ta_engine::streaming::EmaState::new(period),
+        })
+    }
+
+    #[napi]
+    pub fn update(&mut self, value: f64) -> f64 {
+        self.inner.update(value)
+    }
+}
+
+/// Streaming Wilder RSI: `update` returns `null` until enough bars have
+/// arrived to seed the average gain/loss.
+#[napi]
+pub struct RsiState {
+    inner: ta_engine::streaming::RsiState,
+}
+
+#[napi]
+impl RsiState {
+    #[napi(constructor)]
+    pub fn new(period: u32) -> napi::Result<Self> {
+        let period = period_from_u32(period)?;
+        Ok(Self {
+            inner: ta_engine::streaming::RsiState::new(period),
+        })
+    }
+
+    #[napi]
+    pub fn update(&mut self, value: f64) -> Option<f64> {
+        self.inner.update(value)
+    }
+}
+
+/// Streaming Wilder ATR: `update_bar` returns `null` until `period` true
+/// ranges have been seeded.
+#[napi]
+pub struct AtrState {
+    inner: ta_engine::streaming::AtrState,
+}
+
+#[napi]
+impl AtrState {
+    #[napi(constructor)]
+    pub fn new(period: u32) -> napi::Result<Self> {
+        let period = period_from_u32(period)?;
+        Ok(Self {
+            inner: ta_engine::streaming::AtrState::new(period),
+        })
+    }
+
+    #[napi]
+    pub fn update_bar(&mut self, high: f64, low: f64, close: f64) -> Option<f64> {
+        self.inner.update(high, low, close)
+    }
+}
+
+/// Streaming MACD: maintains fast/slow/signal EMA state internally so
+/// `update` returns the latest `(macd, signal, histogram)` triple in O(1).
+#[napi]
+pub struct MacdState {
+    inner: ta_engine::streaming::MacdState,
+}
+
+#[napi]
+impl MacdState {
+    #[napi(constructor)]
+    pub fn new(fast_period: u32, slow_period: u32, signal_period: u32) -> napi::Result<Self> {
+        let fast_period = period_from_u32(fast_period)?;
+        let slow_period = period_from_u32(slow_period)?;
+        let signal_period = period_from_u32(signal_period)?;
+        Ok(Self {
+            inner: ta_engine::streaming::MacdState::new(fast_period, slow_period, signal_period),
+        })
+    }
+
+    #[napi]
+    pub fn update(&mut self, value: f64) -> MacdStepOutput {
+        let (macd, signal, histogram) = self.inner.update(value);
+        MacdStepOutput {
+            macd,
+            signal,
+            histogram,
+        }
+    }
+}
+
+/// One instruction of an `eval_program` bytecode program. `kind` selects
+/// which fields are read; unused fields are left `null` on the JS side.
+/// See `ta_engine::vm::Op` for the instruction set this mirrors.
+#[napi(object)]
+pub struct VmOp {
+    pub kind: String,
+    pub dst: Option<u32>,
+    pub src: Option<u32>,
+    pub a: Option<u32>,
+    pub b: Option<u32>,
+    pub cond: Option<u32>,
+    pub value: Option<f64>,
+    pub input: Option<String>,
+    pub indicator_id: Option<String>,
+    pub params: Option<HashMap<String, f64>>,
+}
+
+fn vm_op_error(index: usize, reason: &str) -> napi::Error {
+    napi::Error::from_reason(format!("ERR_VM_OP_INVALID: op {index}: {reason}"))
+}
+
+fn parse_input_id(index: usize, raw: &str) -> napi::Result<ta_engine::vm::InputId> {
+    match raw {
+        "open" => Ok(ta_engine::vm::InputId::Open),
+        "high" => Ok(ta_engine::vm::InputId::High),
+        "low" => Ok(ta_engine::vm::InputId::Low),
+        "close" => Ok(ta_engine::vm::InputId::Close),
+        "volume" => Ok(ta_engine::vm::InputId::Volume),
+        other => Err(vm_op_error(
+            index,
+            &format!("unknown input '{other}' (expected open/high/low/close/volume)"),
+        )),
+    }
+}
+
+fn require_field<T>(index: usize, name: &str, field: Option<T>) -> napi::Result<T> {
+    field.ok_or_else(|| vm_op_error(index, &format!("missing field '{name}' for this op kind")))
+}
+
+fn build_program(ops: Vec<VmOp>, result_reg: u32, register_count: u32) -> napi::Result<ta_engine::vm::Program> {
+    let mut parsed = Vec::with_capacity(ops.len());
+    for (index, op) in ops.into_iter().enumerate() {
+        let dst = || require_field(index, "dst", op.dst).map(|v| v as usize);
+        let src = || require_field(index, "src", op.src).map(|v| v as usize);
+        let a = || require_field(index, "a", op.a).map(|v| v as usize);
+        let b = || require_field(index, "b", op.b).map(|v| v as usize);
+        let cond = || require_field(index, "cond", op.cond).map(|v| v as usize);
+        let value = || require_field(index, "value", op.value);
+        let input = |index: usize, input: Option<String>| -> napi::Result<ta_engine::vm::InputId> {
+            parse_input_id(index, &require_field(index, "input", input)?)
+        };
+
+        let parsed_op = match op.kind.as_str() {
+            "LoadSeries" => ta_engine::vm::Op::LoadSeries {
+                dst: dst()?,
+                input: input(index, op.input)?,
+            },
+            "CallIndicator" => {
+                let params: BTreeMap<String, f64> = require_field(index, "params", op.params)?
+                    .into_iter()
+                    .collect();
+                ta_engine::vm::Op::CallIndicator {
+                    dst: dst()?,
+                    indicator_id: require_field(index, "indicator_id", op.indicator_id)?,
+                    input: input(index, op.input)?,
+                    params,
+                }
+            }
+            "Move" => ta_engine::vm::Op::Move {
+                dst: dst()?,
+                src: src()?,
+            },
+            "Add" => ta_engine::vm::Op::Add { dst: dst()?, a: a()?, b: b()? },
+            "Sub" => ta_engine::vm::Op::Sub { dst: dst()?, a: a()?, b: b()? },
+            "Mul" => ta_engine::vm::Op::Mul { dst: dst()?, a: a()?, b: b()? },
+            "Div" => ta_engine::vm::Op::Div { dst: dst()?, a: a()?, b: b()? },
+            "Min" => ta_engine::vm::Op::Min { dst: dst()?, a: a()?, b: b()? },
+            "Max" => ta_engine::vm::Op::Max { dst: dst()?, a: a()?, b: b()? },
+            "AddConst" => ta_engine::vm::Op::AddConst { dst: dst()?, src: src()?, value: value()? },
+            "MulConst" => ta_engine::vm::Op::MulConst { dst: dst()?, src: src()?, value: value()? },
+            "MinConst" => ta_engine::vm::Op::MinConst { dst: dst()?, src: src()?, value: value()? },
+            "MaxConst" => ta_engine::vm::Op::MaxConst { dst: dst()?, src: src()?, value: value()? },
+            "Abs" => ta_engine::vm::Op::Abs { dst: dst()?, src: src()? },
+            "Recip" => ta_engine::vm::Op::Recip { dst: dst()?, src: src()? },
+            "IfPosTE" => ta_engine::vm::Op::IfPosTE {
+                dst: dst()?,
+                cond: cond()?,
+                a: a()?,
+                b: b()?,
+            },
+            other => return Err(vm_op_error(index, &format!("unknown op kind '{other}'"))),
+        };
+        parsed.push(parsed_op);
+    }
+
+    Ok(ta_engine::vm::Program {
+        ops: parsed,
+        result_reg: result_reg as usize,
+        register_count: register_count as usize,
+    })
+}
+
+/// Evaluates a bytecode program built from `VmOp`s over `open`/`high`/`low`/
+/// `close`/`volume` in a single FFI crossing, so composite indicators like
+/// `(ema(close,12) - ema(close,26)) / atr(...)` don't require one round-trip
+/// per indicator call.
+#[napi]
+pub fn eval_program(
+    ops: Vec<VmOp>,
+    result_reg: u32,
+    register_count: u32,
+    open: Vec<f64>,
+    high: Vec<f64>,
+    low: Vec<f64>,
+    close: Vec<f64>,
+    volume: Vec<f64>,
+) -> napi::Result<Vec<f64>> {
+    ensure_same_len(
+        "eval_program",
+        &[open.len(), high.len(), low.len(), close.len(), volume.len()],
+    )?;
+    let len = close.len();
+    let program = build_program(ops, result_reg, register_count)?;
+    let inputs = ta_engine::vm::Inputs {
+        open: &open,
+        high: &high,
+        low: &low,
+        close: &close,
+        volume: &volume,
+    };
+    ta_engine::vm::eval_program(&program, &inputs, len)
+        .map_err(|err| napi::Error::from_reason(format!("ERR_VM_EVAL: {err}")))
+}
+
+#[napi(object)]
+pub struct IndicatorRequest {
+    pub id: String,
+    pub indicator: String,
+    pub params: HashMap<String, f64>,
+}
+
+#[napi(object)]
+pub struct BatchOutput {
+    pub series: HashMap<String, Vec<f64>>,
+}
+
+fn batch_param(params: &HashMap<String, f64>, indicator: &str, name: &str) -> napi::Result<u32> {
+    params.get(name).copied().map(|v| v as u32).ok_or_else(|| {
+        napi::Error::from_reason(format!(
+            "ERR_MISSING_PARAM: '{indicator}' request is missing param '{name}'"
+        ))
+    })
+}
+
+fn batch_param_f64(params: &HashMap<String, f64>, indicator: &str, name: &str) -> napi::Result<f64> {
+    params.get(name).copied().ok_or_else(|| {
+        napi::Error::from_reason(format!(
+            "ERR_MISSING_PARAM: '{indicator}' request is missing param '{name}'"
+        ))
+    })
+}
+
+/// Wilder's true range, the `O(1)`-per-bar reduction `atr` folds over. Pulled
+/// out so a batch with several `atr` requests (e.g. different periods feeding
+/// different indicators) computes it once and reuses the buffer instead of
+/// recomputing it per request.
+fn true_range(high: &[f64], low: &[f64], close: &[f64]) -> Vec<f64> {
+    let n = close.len();
+    let mut tr = vec![0.0; n];
+    if n == 0 {
+        return tr;
+    }
+    tr[0] = high[0] - low[0];
+    for i in 1..n {
+        tr[i] = (high[i] - low[i])
+            .max((high[i] - close[i - 1]).abs())
+            .max((low[i] - close[i - 1]).abs());
+    }
+    tr
+}
+
+/// Runs several indicator requests against one shared OHLCV table in a
+/// single FFI crossing, validating `high`/`low`/`close`/`volume` lengths once
+/// up front instead of once per indicator. Every `atr` request reuses a
+/// single true-range pass over the batch rather than recomputing it per
+/// request, since it's the shared building block behind every period.
+///
+/// Output series are keyed `"{request.id}.{output name}"` (e.g.
+/// `"fast_atr.atr"`), since a batch can request the same indicator more than
+/// once under different ids.
+#[napi]
+pub fn compute_batch(
+    high: Vec<f64>,
+    low: Vec<f64>,
+    close: Vec<f64>,
+    volume: Option<Vec<f64>>,
+    requests: Vec<IndicatorRequest>,
+) -> napi::Result<BatchOutput> {
+    let mut lengths = vec![high.len(), low.len(), close.len()];
+    if let Some(volume) = &volume {
+        lengths.push(volume.len());
+    }
+    ensure_same_len("compute_batch", &lengths)?;
+
+    let mut tr_cache: Option<Vec<f64>> = None;
+    let mut series = HashMap::with_capacity(requests.len());
+
+    for req in requests {
+        let key = |output: &str| format!("{}.{output}", req.id);
+        match req.indicator.as_str() {
+            "sma" => {
+                let period = period_from_u32(batch_param(&req.params, "sma", "period")?)?;
+                series.insert(key("sma"), ta_engine::rolling::rolling_mean(&close, period));
+            }
+            "ema" => {
+                let period = period_from_u32(batch_param(&req.params, "ema", "period")?)?;
+                series.insert(key("ema"), ta_engine::moving_averages::ema(&close, period));
+            }
+            "rsi" => {
+                let period = period_from_u32(batch_param(&req.params, "rsi", "period")?)?;
+                series.insert(key("rsi"), ta_engine::momentum::rsi(&close, period));
+            }
+            "atr" => {
+                let period = period_from_u32(batch_param(&req.params, "atr", "period")?)?;
+                let tr = tr_cache.get_or_insert_with(|| true_range(&high, &low, &close));
+                series.insert(key("atr"), ta_engine::volatility::atr_from_tr(tr, period));
+            }
+            "macd" => {
+                let fast = period_from_u32(batch_param(&req.params, "macd", "fast_period")?)?;
+                let slow = period_from_u32(batch_param(&req.params, "macd", "slow_period")?)?;
+                let signal = period_from_u32(batch_param(&req.params, "macd", "signal_period")?)?;
+                let (macd, signal_line, histogram) = ta_engine::trend::macd(
+                    &close,
+                    fast,
+                    slow,
+                    signal,
+                    ta_engine::moving_averages::MovingAverageType::Ema,
+                );
+                series.insert(key("macd"), macd);
+                series.insert(key("signal"), signal_line);
+                series.insert(key("histogram"), histogram);
+            }
+            "bbands" => {
+                let period = period_from_u32(batch_param(&req.params, "bbands", "period")?)?;
+                let std_dev = batch_param_f64(&req.params, "bbands", "std_dev")?;
+                let (upper, middle, lower) = ta_engine::volatility::bbands(
+                    &close,
+                    period,
+                    std_dev,
+                    ta_engine::moving_averages::MovingAverageType::Sma,
+                );
+                series.insert(key("upper"), upper);
+                series.insert(key("middle"), middle);
+                series.insert(key("lower"), lower);
+            }
+            "donchian" => {
+                let period = period_from_u32(batch_param(&req.params, "donchian", "period")?)?;
+                let (upper, lower, middle) = ta_engine::volatility::donchian(&high, &low, period);
+                series.insert(key("upper"), upper);
+                series.insert(key("lower"), lower);
+                series.insert(key("middle"), middle);
+            }
+            "keltner" => {
+                let ema_period = period_from_u32(batch_param(&req.params, "keltner", "ema_period")?)?;
+                let atr_period = period_from_u32(batch_param(&req.params, "keltner", "atr_period")?)?;
+                let multiplier = batch_param_f64(&req.params, "keltner", "multiplier")?;
+                let (upper, middle, lower) = ta_engine::volatility::keltner(
+                    &high,
+                    &low,
+                    &close,
+                    ema_period,
+                    atr_period,
+                    multiplier,
+                    ta_engine::moving_averages::MovingAverageType::Ema,
+                );
+                series.insert(key("upper"), upper);
+                series.insert(key("middle"), middle);
+                series.insert(key("lower"), lower);
+            }
+            "adx" => {
+                let period = period_from_u32(batch_param(&req.params, "adx", "period")?)?;
+                let (adx, plus_di, minus_di) = ta_engine::trend::adx(&high, &low, &close, period);
+                series.insert(key("adx"), adx);
+                series.insert(key("plus_di"), plus_di);
+                series.insert(key("minus_di"), minus_di);
+            }
+            other => {
+                return Err(napi::Error::from_reason(format!(
+                    "ERR_UNKNOWN_INDICATOR: '{other}' is not supported by compute_batch"
+                )))
+            }
+        }
+    }
+
+    Ok(BatchOutput { series })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -643,6 +1165,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn opt_variants_mark_warmup_as_null_instead_of_nan() {
+        let values = sample_series();
+        let (high, low, close, _volume) = sample_ohlcv();
+
+        let sma_opt = sma_opt(values.clone(), 3).expect("sma_opt");
+        assert_eq!(sma_opt.len(), values.len());
+        assert_eq!(sma_opt[0], None);
+        assert!(sma_opt[values.len() - 1].is_some());
+
+        let rsi_opt = rsi_opt(values.clone(), 3).expect("rsi_opt");
+        assert_eq!(rsi_opt.len(), values.len());
+        assert_eq!(rsi_opt[0], None);
+        assert!(rsi_opt[values.len() - 1].is_some());
+
+        let macd_opt = macd_opt(values.clone(), 2, 4, 3).expect("macd_opt");
+        assert_eq!(macd_opt.macd.len(), values.len());
+        assert_eq!(macd_opt.macd[0], None);
+
+        let adx_opt = adx_opt(high.clone(), low.clone(), close.clone(), 3).expect("adx_opt");
+        assert_eq!(adx_opt.adx.len(), high.len());
+        assert_eq!(adx_opt.adx[0], None);
+    }
+
     #[test]
     fn multi_output_indicators_preserve_input_length() {
         let values = sample_series();
@@ -714,4 +1260,207 @@ mod tests {
         assert_eq!(kling.klinger.len(), values.len());
         assert_eq!(kling.signal.len(), values.len());
     }
+
+    fn seasonal_series(cycles: usize, period: usize) -> Vec<f64> {
+        (0..cycles * period)
+            .map(|i| {
+                let trend = i as f64 * 0.1;
+                let season = ((i % period) as f64 - period as f64 / 2.0).abs();
+                trend + season
+            })
+            .collect()
+    }
+
+    #[test]
+    fn stl_preserves_input_length_and_reconstructs_the_series() {
+        let values = seasonal_series(6, 12);
+        let out = stl(values.clone(), 12).expect("stl");
+        assert_eq!(out.trend.len(), values.len());
+        assert_eq!(out.seasonal.len(), values.len());
+        assert_eq!(out.remainder.len(), values.len());
+
+        for i in 0..values.len() {
+            let reconstructed = out.trend[i] + out.seasonal[i] + out.remainder[i];
+            assert!((reconstructed - values[i]).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn stl_rejects_a_series_shorter_than_two_periods() {
+        let values = seasonal_series(1, 12);
+        let err = stl(values, 12).expect_err("must reject short series");
+        assert!(err.to_string().contains("ERR_PERIOD_INVALID"));
+    }
+
+    #[test]
+    fn stl_rejects_a_zero_period() {
+        let values = seasonal_series(6, 12);
+        let err = stl(values, 0).expect_err("must reject zero period");
+        assert!(err.to_string().contains("ERR_PERIOD_INVALID"));
+    }
+
+    #[test]
+    fn ema_state_seeds_with_the_first_value() {
+        let mut state = EmaState::new(3).expect("ema state");
+        assert_eq!(state.update(1.0), 1.0);
+        assert!(state.update(2.0) > 1.0);
+    }
+
+    #[test]
+    fn rsi_state_returns_none_during_warmup_then_a_value() {
+        let mut state = RsiState::new(3).expect("rsi state");
+        assert_eq!(state.update(1.0), None);
+        assert_eq!(state.update(2.0), None);
+        assert_eq!(state.update(3.0), None);
+        assert!(state.update(4.0).is_some());
+    }
+
+    #[test]
+    fn atr_state_returns_none_during_warmup_then_a_value() {
+        let mut state = AtrState::new(2).expect("atr state");
+        assert_eq!(state.update_bar(12.0, 8.0, 10.0), None);
+        assert!(state.update_bar(13.0, 9.0, 11.0).is_some());
+    }
+
+    #[test]
+    fn macd_state_reports_the_histogram_as_macd_minus_signal() {
+        let mut state = MacdState::new(2, 5, 3).expect("macd state");
+        let mut last = state.update(1.0);
+        for value in [2.0, 3.0, 4.0, 5.0, 6.0] {
+            last = state.update(value);
+        }
+        assert!((last.histogram - (last.macd - last.signal)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn state_constructors_reject_a_zero_period() {
+        assert!(EmaState::new(0).is_err());
+        assert!(RsiState::new(0).is_err());
+        assert!(AtrState::new(0).is_err());
+        assert!(MacdState::new(0, 5, 3).is_err());
+    }
+
+    fn vm_op(kind: &str) -> VmOp {
+        VmOp {
+            kind: kind.to_string(),
+            dst: None,
+            src: None,
+            a: None,
+            b: None,
+            cond: None,
+            value: None,
+            input: None,
+            indicator_id: None,
+            params: None,
+        }
+    }
+
+    #[test]
+    fn eval_program_evaluates_a_composite_indicator_expression() {
+        let values = sample_series();
+        let (high, low, close, volume) = sample_ohlcv();
+
+        let mut fast_params = HashMap::new();
+        fast_params.insert("period".to_string(), 2.0);
+        let mut slow_params = HashMap::new();
+        slow_params.insert("period".to_string(), 4.0);
+
+        let ops = vec![
+            VmOp {
+                dst: Some(0),
+                input: Some("close".to_string()),
+                indicator_id: Some("ema".to_string()),
+                params: Some(fast_params),
+                ..vm_op("CallIndicator")
+            },
+            VmOp {
+                dst: Some(1),
+                input: Some("close".to_string()),
+                indicator_id: Some("ema".to_string()),
+                params: Some(slow_params),
+                ..vm_op("CallIndicator")
+            },
+            VmOp {
+                dst: Some(2),
+                a: Some(0),
+                b: Some(1),
+                ..vm_op("Sub")
+            },
+        ];
+
+        let result = eval_program(ops, 2, 3, values.clone(), high, low, close, volume).expect("eval_program");
+        assert_eq!(result.len(), values.len());
+    }
+
+    #[test]
+    fn eval_program_rejects_a_missing_op_field() {
+        let values = sample_series();
+        let (high, low, close, volume) = sample_ohlcv();
+
+        let ops = vec![VmOp {
+            dst: Some(0),
+            a: Some(0),
+            ..vm_op("Add")
+        }];
+
+        let err = eval_program(ops, 0, 1, values.clone(), high, low, close, volume)
+            .expect_err("missing 'b' must fail");
+        assert!(err.to_string().contains("ERR_VM_OP_INVALID"));
+    }
+
+    #[test]
+    fn eval_program_rejects_an_unknown_op_kind() {
+        let values = sample_series();
+        let (high, low, close, volume) = sample_ohlcv();
+
+        let ops = vec![vm_op("Nope")];
+        let err = eval_program(ops, 0, 1, values.clone(), high, low, close, volume)
+            .expect_err("unknown op kind must fail");
+        assert!(err.to_string().contains("ERR_VM_OP_INVALID"));
+    }
+
+    fn indicator_request(id: &str, indicator: &str, params: &[(&str, f64)]) -> IndicatorRequest {
+        IndicatorRequest {
+            id: id.to_string(),
+            indicator: indicator.to_string(),
+            params: params.iter().map(|(k, v)| (k.to_string(), *v)).collect(),
+        }
+    }
+
+    #[test]
+    fn compute_batch_runs_several_indicators_over_one_shared_table() {
+        let (high, low, close, volume) = sample_ohlcv();
+
+        let requests = vec![
+            indicator_request("fast_sma", "sma", &[("period", 3.0)]),
+            indicator_request("fast_atr", "atr", &[("period", 3.0)]),
+            indicator_request("slow_atr", "atr", &[("period", 5.0)]),
+        ];
+
+        let result = compute_batch(high, low, close, Some(volume), requests).expect("compute_batch");
+        assert!(result.series.contains_key("fast_sma.sma"));
+        assert!(result.series.contains_key("fast_atr.atr"));
+        assert!(result.series.contains_key("slow_atr.atr"));
+    }
+
+    #[test]
+    fn compute_batch_rejects_mismatched_lengths() {
+        let (high, low, close, volume) = sample_ohlcv();
+        let mut short_volume = volume;
+        short_volume.pop();
+
+        let err = compute_batch(high, low, close, Some(short_volume), vec![])
+            .expect_err("mismatched volume length must fail");
+        assert!(err.to_string().contains("ERR_LENGTH_MISMATCH"));
+    }
+
+    #[test]
+    fn compute_batch_rejects_an_unknown_indicator() {
+        let (high, low, close, volume) = sample_ohlcv();
+        let requests = vec![indicator_request("x", "vwma", &[])];
+
+        let err = compute_batch(high, low, close, Some(volume), requests)
+            .expect_err("unknown indicator must fail");
+        assert!(err.to_string().contains("ERR_UNKNOWN_INDICATOR"));
+    }
 }
@@ -74,6 +74,7 @@ fn parity_multi_output_wrappers_match_engine() {
         f.periods.fast,
         f.periods.slow,
         f.periods.signal,
+        None,
     )
     .expect("macd");
     let (ref_macd, ref_signal, ref_hist) = ta_engine::trend::macd(
@@ -81,14 +82,20 @@ fn parity_multi_output_wrappers_match_engine() {
         f.periods.fast as usize,
         f.periods.slow as usize,
         f.periods.signal as usize,
+        ta_engine::moving_averages::MovingAverageType::Ema,
     );
     assert_series_close(&node_macd.macd, &ref_macd, 1e-12);
     assert_series_close(&node_macd.signal, &ref_signal, 1e-12);
     assert_series_close(&node_macd.histogram, &ref_hist, 1e-12);
 
-    let node_bbands = ta_node::bbands(f.series.clone(), f.periods.short, 2.0).expect("bbands");
-    let (ref_upper, ref_middle, ref_lower) =
-        ta_engine::volatility::bbands(&f.series, f.periods.short as usize, 2.0);
+    let node_bbands =
+        ta_node::bbands(f.series.clone(), f.periods.short, 2.0, None).expect("bbands");
+    let (ref_upper, ref_middle, ref_lower) = ta_engine::volatility::bbands(
+        &f.series,
+        f.periods.short as usize,
+        2.0,
+        ta_engine::moving_averages::MovingAverageType::Sma,
+    );
     assert_series_close(&node_bbands.upper, &ref_upper, 1e-12);
     assert_series_close(&node_bbands.middle, &ref_middle, 1e-12);
     assert_series_close(&node_bbands.lower, &ref_lower, 1e-12);
@@ -0,0 +1,157 @@
+//! `execute_plan_on_dataframe`: runs indicator calls over a Polars
+//! `DataFrame` with named OHLCV columns via the same name-dispatch
+//! `ta_engine::incremental::graph_exec::execute_plan_on_columns` uses for
+//! graph `call` nodes, instead of requiring callers to register a dataset
+//! partition first. Results land back on the frame as new columns, so bars
+//! pulled from the Polars/`yahoo_finance_api` ecosystem, a batch of
+//! indicators, and any downstream joins all stay in one frame.
+
+use polars::prelude::{DataFrame, Series};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use pyo3_polars::PyDataFrame;
+use ta_engine::dataset::OhlcvColumns;
+use ta_engine::incremental::graph_exec::{execute_plan_on_columns, ColumnCallSpec};
+
+use crate::conversions::extract_scalar_string;
+use crate::errors::map_execute_plan_error;
+
+const OPEN_ALIASES: &[&str] = &["open", "o"];
+const HIGH_ALIASES: &[&str] = &["high", "h"];
+const LOW_ALIASES: &[&str] = &["low", "l"];
+const CLOSE_ALIASES: &[&str] = &["close", "c", "adj_close", "price"];
+const VOLUME_ALIASES: &[&str] = &["volume", "v", "vol"];
+
+/// Finds the first column in `df` whose lowercased name is one of `aliases`,
+/// case-insensitively, and returns it cast to `f64`.
+fn find_column(df: &DataFrame, aliases: &[&str]) -> PyResult<Option<Vec<f64>>> {
+    for column in df.get_columns() {
+        if aliases.contains(&column.name().to_lowercase().as_str()) {
+            let values = column
+                .cast(&polars::prelude::DataType::Float64)
+                .map_err(|err| {
+                    pyo3::exceptions::PyValueError::new_err(format!(
+                        "column '{}' could not be coerced to f64: {err}",
+                        column.name()
+                    ))
+                })?
+                .f64()
+                .map_err(|err| pyo3::exceptions::PyValueError::new_err(err.to_string()))?
+                .into_iter()
+                .map(|v| v.unwrap_or(f64::NAN))
+                .collect();
+            return Ok(Some(values));
+        }
+    }
+    Ok(None)
+}
+
+/// Parses one `{"name": str, "params": dict}` spec dict -- `params` values
+/// are coerced to strings and stored under `kw_<key>` (the meta shape
+/// `dispatch_call_node`'s `get_usize`/`get_f64` helpers read), except
+/// `output`, which selects a multi-series component and is stored as-is.
+fn parse_spec(item: &Bound<'_, PyAny>) -> PyResult<ColumnCallSpec> {
+    let d = item.downcast::<PyDict>()?;
+    let name: String = d
+        .get_item("name")?
+        .ok_or_else(|| pyo3::exceptions::PyKeyError::new_err("missing spec 'name'"))?
+        .extract()?;
+    let mut params = std::collections::BTreeMap::new();
+    if let Some(params_dict) = d.get_item("params")? {
+        let params_dict = params_dict.downcast::<PyDict>()?;
+        for (k, v) in params_dict.iter() {
+            let key: String = k.extract()?;
+            let value = extract_scalar_string(&v)?;
+            if key == "output" {
+                params.insert("output".to_string(), value);
+            } else {
+                params.insert(format!("kw_{key}"), value);
+            }
+        }
+    }
+    Ok(ColumnCallSpec { name, params })
+}
+
+#[pyfunction]
+pub(crate) fn execute_plan_on_dataframe(
+    df: PyDataFrame,
+    specs: &Bound<'_, PyList>,
+) -> PyResult<PyDataFrame> {
+    let mut frame: DataFrame = df.into();
+    let rows = frame.height();
+
+    let open = find_column(&frame, OPEN_ALIASES)?;
+    let high = find_column(&frame, HIGH_ALIASES)?;
+    let low = find_column(&frame, LOW_ALIASES)?;
+    let close = find_column(&frame, CLOSE_ALIASES)?.ok_or_else(|| {
+        pyo3::exceptions::PyValueError::new_err(
+            "dataframe has no recognizable close column (expected one of: close, c, adj_close, price)",
+        )
+    })?;
+    let volume = find_column(&frame, VOLUME_ALIASES)?;
+
+    let ohlcv = match (open, high, low, volume) {
+        (Some(open), Some(high), Some(low), Some(volume)) => Some(OhlcvColumns {
+            timestamps: vec![0; rows],
+            open,
+            high,
+            low,
+            close: close.clone(),
+            volume,
+        }),
+        _ => None,
+    };
+
+    let parsed_specs = specs
+        .iter()
+        .map(|item| parse_spec(&item))
+        .collect::<PyResult<Vec<_>>>()?;
+
+    let results = execute_plan_on_columns(&close, ohlcv.as_ref(), &parsed_specs)
+        .map_err(map_execute_plan_error)?;
+
+    for (column_name, values) in results {
+        frame
+            .with_column(to_output_series(&column_name, &values))
+            .map_err(|err| pyo3::exceptions::PyValueError::new_err(err.to_string()))?;
+    }
+
+    Ok(PyDataFrame(frame))
+}
+
+/// Bool-valued call results (e.g. `crossup`) become a bool column; anything
+/// else becomes an `f64` column with `NaN` for the `Null`s a warm-up region
+/// produces.
+fn to_output_series(name: &str, values: &[ta_engine::incremental::contracts::IncrementalValue]) -> Series {
+    use ta_engine::incremental::contracts::IncrementalValue;
+
+    let all_bool_or_null = values
+        .iter()
+        .all(|v| matches!(v, IncrementalValue::Bool(_) | IncrementalValue::Null));
+    if all_bool_or_null {
+        let bools: Vec<Option<bool>> = values
+            .iter()
+            .map(|v| match v {
+                IncrementalValue::Bool(b) => Some(*b),
+                _ => None,
+            })
+            .collect();
+        Series::new(name.into(), bools)
+    } else {
+        let nums: Vec<f64> = values
+            .iter()
+            .map(|v| match v {
+                IncrementalValue::Number(n) => *n,
+                IncrementalValue::Bool(b) => {
+                    if *b {
+                        1.0
+                    } else {
+                        0.0
+                    }
+                }
+                _ => f64::NAN,
+            })
+            .collect();
+        Series::new(name.into(), nums)
+    }
+}
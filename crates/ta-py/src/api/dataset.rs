@@ -1,6 +1,7 @@
 use pyo3::prelude::*;
 use pyo3::types::PyDict;
 use ta_engine::dataset::{self, DatasetPartitionKey};
+use ta_engine::dataset_mmap::{self, MmapColumnSpec, MmapOhlcvLayout};
 
 use crate::conversions::indicator_meta_to_pydict;
 use crate::errors::{map_dataset_error, map_dataset_ops_error};
@@ -51,6 +52,60 @@ pub(crate) fn dataset_append_ohlcv(
     .map_err(map_dataset_error)
 }
 
+/// Memory-maps an on-disk fixed-width columnar OHLCV file and registers it
+/// as a partition without copying it through Python lists first. `layout`
+/// is a dict with `row_count` plus a `byte_offset` entry per column name
+/// (`timestamp`, `open`, `high`, `low`, `close`, `volume`) -- each column is
+/// 8-byte little-endian (`i64` for `timestamp`, `f64` for the rest),
+/// stored contiguously for `row_count` rows starting at its offset.
+#[pyfunction]
+pub(crate) fn dataset_load_mmap(
+    dataset_id: u64,
+    symbol: String,
+    timeframe: String,
+    source: String,
+    path: String,
+    layout: &Bound<'_, PyDict>,
+) -> PyResult<usize> {
+    let layout = parse_mmap_layout(layout)?;
+    dataset_mmap::load_mmap_partition(
+        dataset_id,
+        DatasetPartitionKey {
+            symbol,
+            timeframe,
+            source,
+        },
+        &path,
+        &layout,
+    )
+    .map_err(map_dataset_error)
+}
+
+fn parse_mmap_layout(layout: &Bound<'_, PyDict>) -> PyResult<MmapOhlcvLayout> {
+    let row_count: usize = layout
+        .get_item("row_count")?
+        .ok_or_else(|| pyo3::exceptions::PyKeyError::new_err("layout missing 'row_count'"))?
+        .extract()?;
+
+    let column = |name: &str| -> PyResult<MmapColumnSpec> {
+        let byte_offset: usize = layout
+            .get_item(name)?
+            .ok_or_else(|| pyo3::exceptions::PyKeyError::new_err(format!("layout missing '{name}'")))?
+            .extract()?;
+        Ok(MmapColumnSpec { byte_offset })
+    };
+
+    Ok(MmapOhlcvLayout {
+        row_count,
+        timestamp: column("timestamp")?,
+        open: column("open")?,
+        high: column("high")?,
+        low: column("low")?,
+        close: column("close")?,
+        volume: column("volume")?,
+    })
+}
+
 #[pyfunction]
 pub(crate) fn dataset_append_series(
     dataset_id: u64,
@@ -75,6 +130,30 @@ pub(crate) fn dataset_append_series(
     .map_err(map_dataset_error)
 }
 
+/// Lists every stored partition of `dataset_id` whose key matches `symbol`
+/// and `timeframe` as a leading prefix of the ordered `(symbol, timeframe,
+/// source)` key -- e.g. `symbol="BTCUSDT"` alone returns every timeframe and
+/// source stored for that symbol. `timeframe` without `symbol` is rejected,
+/// since it wouldn't be a contiguous range. Returns a list of
+/// `(symbol, timeframe, source)` tuples.
+#[pyfunction]
+#[pyo3(signature = (dataset_id, symbol=None, timeframe=None))]
+pub(crate) fn dataset_list_partitions(
+    py: Python<'_>,
+    dataset_id: u64,
+    symbol: Option<String>,
+    timeframe: Option<String>,
+) -> PyResult<PyObject> {
+    let keys = dataset::list_partitions(dataset_id, symbol.as_deref(), timeframe.as_deref())
+        .map_err(map_dataset_error)?;
+
+    let py_list = pyo3::types::PyList::empty(py);
+    for key in keys {
+        py_list.append((key.symbol, key.timeframe, key.source))?;
+    }
+    Ok(py_list.into_any().unbind())
+}
+
 #[pyfunction]
 pub(crate) fn dataset_info(py: Python<'_>, dataset_id: u64) -> PyResult<PyObject> {
     let info = dataset::dataset_info(dataset_id).map_err(map_dataset_error)?;
@@ -98,6 +177,66 @@ pub(crate) fn series_downsample(
         .map_err(map_dataset_ops_error)
 }
 
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn series_downsample_ohlcv(
+    timestamps: Vec<i64>,
+    open: Vec<f64>,
+    high: Vec<f64>,
+    low: Vec<f64>,
+    close: Vec<f64>,
+    volume: Vec<f64>,
+    factor: usize,
+) -> PyResult<(Vec<i64>, Vec<f64>, Vec<f64>, Vec<f64>, Vec<f64>, Vec<f64>)> {
+    ta_engine::dataset_ops::downsample_ohlcv(&timestamps, &open, &high, &low, &close, &volume, factor)
+        .map_err(map_dataset_ops_error)
+}
+
+/// Like `series_downsample_ohlcv`, but also returns a `vwap` column so a
+/// caller rolling 1m bars up to 5m/1h gets all seven aligned columns from
+/// one call instead of layering a separate `series_downsample` for vwap on
+/// top (and risking it drifting out of alignment with the OHLCV buckets).
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn series_resample_ohlcv(
+    timestamps: Vec<i64>,
+    open: Vec<f64>,
+    high: Vec<f64>,
+    low: Vec<f64>,
+    close: Vec<f64>,
+    volume: Vec<f64>,
+    factor: usize,
+) -> PyResult<(
+    Vec<i64>,
+    Vec<f64>,
+    Vec<f64>,
+    Vec<f64>,
+    Vec<f64>,
+    Vec<f64>,
+    Vec<f64>,
+)> {
+    ta_engine::dataset_ops::resample_ohlcv(&timestamps, &open, &high, &low, &close, &volume, factor)
+        .map_err(map_dataset_ops_error)
+}
+
+/// Like `series_downsample`, but buckets by a fixed calendar interval
+/// (`interval_ms`, anchored to the epoch) instead of a fixed sample count, so
+/// the output aligns to real bar boundaries even when the input is irregular
+/// or has gaps. `empty_policy` controls windows with no samples: `"skip"`
+/// omits them, `"zero"`/`"ffill"`/`"linear"` fill them the same way
+/// `series_sync_timeframe`'s matching `fill` modes do.
+#[pyfunction]
+pub(crate) fn series_downsample_interval(
+    timestamps: Vec<i64>,
+    values: Vec<f64>,
+    interval_ms: i64,
+    agg: String,
+    empty_policy: String,
+) -> PyResult<(Vec<i64>, Vec<f64>)> {
+    ta_engine::dataset_ops::downsample_interval(&timestamps, &values, interval_ms, &agg, &empty_policy)
+        .map_err(map_dataset_ops_error)
+}
+
 #[pyfunction]
 pub(crate) fn series_upsample_ffill(
     timestamps: Vec<i64>,
@@ -126,9 +265,23 @@ pub(crate) fn series_sync_timeframe(
 
 #[pyfunction]
 pub(crate) fn indicator_catalog(py: Python<'_>) -> PyResult<PyObject> {
+    let overlay_entries = crate::api::registry::overlay_catalog(py)?;
+    let overlay_ids: std::collections::HashSet<&str> =
+        overlay_entries.iter().map(|(id, _)| id.as_str()).collect();
+
     let py_list = pyo3::types::PyList::empty(py);
     for meta in ta_engine::metadata::indicator_catalog() {
-        py_list.append(indicator_meta_to_pydict(py, meta)?)?;
+        if overlay_ids.contains(meta.id) {
+            continue;
+        }
+        let entry = indicator_meta_to_pydict(py, meta)?;
+        entry.bind(py).downcast::<PyDict>()?.set_item("user_provided", false)?;
+        py_list.append(entry)?;
+    }
+    for (_, meta) in overlay_entries {
+        let entry = meta.bind(py).copy()?;
+        entry.set_item("user_provided", true)?;
+        py_list.append(entry)?;
     }
     Ok(py_list.into_any().unbind())
 }
@@ -143,10 +296,18 @@ pub(crate) fn indicator_catalog_contract(py: Python<'_>) -> PyResult<PyObject> {
 
 #[pyfunction]
 pub(crate) fn indicator_meta(py: Python<'_>, id: String) -> PyResult<PyObject> {
+    if let Some(overlay) = crate::api::registry::overlay_meta(py, &id)? {
+        let entry = overlay.bind(py).copy()?;
+        entry.set_item("user_provided", true)?;
+        return Ok(entry.into_any().unbind());
+    }
+
     let meta = ta_engine::metadata::find_indicator_meta(&id).ok_or_else(|| {
         pyo3::exceptions::PyKeyError::new_err(format!(
             "indicator metadata not found for id/alias '{id}'"
         ))
     })?;
-    indicator_meta_to_pydict(py, meta)
+    let entry = indicator_meta_to_pydict(py, meta)?;
+    entry.bind(py).downcast::<PyDict>()?.set_item("user_provided", false)?;
+    Ok(entry)
 }
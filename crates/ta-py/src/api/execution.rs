@@ -1,19 +1,21 @@
 use std::collections::BTreeMap;
 
+use numpy::PyReadonlyArray1;
 use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyList};
-use ta_engine::contracts::{RustExecutionGraph, RustExecutionPartition, RustExecutionPayload};
-use ta_engine::dataset::DatasetPartitionKey;
+use ta_engine::dataset::{self, DatasetPartitionKey};
 use ta_engine::incremental::backend::{self, ExecutePlanPayload, IncrementalBackend};
+use ta_engine::incremental::contracts::IncrementalValue;
 
+use crate::api::registry::overlay_callable;
 use crate::conversions::{
-    extract_node_id, extract_scalar_string, incremental_map_to_pydict,
-    incremental_series_map_to_pydict, parse_contract_requests, parse_events, parse_requests,
-    parse_tick,
+    events_from_ohlcv_arrays, incremental_map_to_pydict, incremental_series_map_to_pydict,
+    params_dict_to_json, parse_events, parse_execution_payload_dict, parse_requests, parse_tick,
 };
-use crate::errors::map_execute_plan_error;
+use crate::errors::{map_dataset_error, map_execute_plan_error};
 use crate::state::{
-    next_backend_id, next_snapshot_id, with_backends_mut, with_snapshots, with_snapshots_mut,
+    insert_backend, insert_snapshot, next_backend_id, next_snapshot_id, snapshot, snapshot_pydict,
+    with_backend_mut,
 };
 
 #[pyfunction]
@@ -21,75 +23,252 @@ pub(crate) fn incremental_initialize() -> PyResult<u64> {
     let mut backend = IncrementalBackend::default();
     backend.initialize();
     let id = next_backend_id();
-    with_backends_mut(|map| {
-        map.insert(id, backend);
-        id
-    })
+    insert_backend(id, backend)?;
+    Ok(id)
 }
 
+/// Bridges a catalog indicator to the incremental backend: looks up
+/// `indicator_id` in the Rust catalog, validates `params` against its
+/// metadata, and resolves its `runtime_binding` to a streaming kernel.
+/// Returns a freshly initialized backend id together with the request dict
+/// `incremental_step` expects in its `requests` list.
 #[pyfunction]
+pub(crate) fn incremental_build_request(
+    py: Python<'_>,
+    indicator_id: String,
+    node_id: u32,
+    params: &Bound<'_, PyDict>,
+) -> PyResult<(u64, PyObject)> {
+    let meta = ta_engine::metadata::find_indicator_meta(&indicator_id).ok_or_else(|| {
+        pyo3::exceptions::PyKeyError::new_err(format!("unknown indicator id: {indicator_id}"))
+    })?;
+    let params_json = params_dict_to_json(params)?;
+    let request =
+        ta_engine::incremental::factory::build_incremental(meta, node_id, &params_json)
+            .map_err(|err| pyo3::exceptions::PyValueError::new_err(err.to_string()))?;
+
+    let backend_id = next_backend_id();
+    let mut backend = IncrementalBackend::default();
+    backend.initialize();
+    insert_backend(backend_id, backend)?;
+
+    let request_dict = PyDict::new(py);
+    request_dict.set_item("node_id", request.node_id)?;
+    request_dict.set_item("kernel_id", request.kernel_id.as_str())?;
+    request_dict.set_item("input_field", request.input_field)?;
+    request_dict.set_item("kwargs", incremental_map_to_pydict(py, &request.kwargs)?)?;
+
+    Ok((backend_id, request_dict.into_any().unbind()))
+}
+
+/// `stream_id` identifies which event source this tick came from, so a
+/// backend fed overlapping or out-of-order ranges (e.g. crash recovery, or
+/// two partitions merged into one backend) can tell a replayed duplicate
+/// from genuinely new progress -- see `IncrementalBackend::step`. Callers
+/// with a single event source can leave it at its default.
+#[pyfunction]
+#[pyo3(signature = (backend_id, requests, tick, event_index, stream_id=0))]
 pub(crate) fn incremental_step(
     py: Python<'_>,
     backend_id: u64,
     requests: &Bound<'_, PyList>,
     tick: &Bound<'_, PyDict>,
     event_index: u64,
+    stream_id: u32,
 ) -> PyResult<PyObject> {
     let parsed_requests = parse_requests(requests)?;
     let parsed_tick = parse_tick(tick)?;
 
-    let out = with_backends_mut(|map| {
-        let backend = map.get_mut(&backend_id).ok_or_else(|| {
-            pyo3::exceptions::PyKeyError::new_err(format!("backend id {backend_id} not found"))
-        })?;
-        Ok::<_, PyErr>(backend.step(event_index, &parsed_requests, &parsed_tick))
-    })??;
+    let out = py.allow_threads(|| {
+        with_backend_mut(backend_id, |backend| {
+            backend.step(stream_id, event_index, &parsed_requests, &parsed_tick)
+        })
+    })?
+    .map_err(map_execute_plan_error)?;
 
     incremental_map_to_pydict(py, &out)
 }
 
 #[pyfunction]
 pub(crate) fn incremental_snapshot(backend_id: u64) -> PyResult<u64> {
-    let snapshot = with_backends_mut(|map| {
-        let backend = map.get_mut(&backend_id).ok_or_else(|| {
-            pyo3::exceptions::PyKeyError::new_err(format!("backend id {backend_id} not found"))
-        })?;
-        Ok::<_, PyErr>(backend.snapshot())
-    })??;
+    let snap = with_backend_mut(backend_id, |backend| backend.snapshot())?;
+
+    let snapshot_id = next_snapshot_id();
+    insert_snapshot(snapshot_id, snap)?;
+    Ok(snapshot_id)
+}
+
+/// Serializes a backend's snapshot (previously captured via
+/// `incremental_snapshot`) to bytes so it can be persisted across process
+/// restarts and handed back to `incremental_snapshot_load` later.
+#[pyfunction]
+pub(crate) fn incremental_snapshot_dump(snapshot_id: u64) -> PyResult<Vec<u8>> {
+    let snap = snapshot(snapshot_id)?;
+    Ok(ta_engine::incremental::snapshot_codec::encode_snapshot_to_bytes(&snap))
+}
+
+/// Restores a snapshot previously produced by `incremental_snapshot_dump`,
+/// migrating it to the current schema version, and registers it under a
+/// fresh snapshot id usable with `incremental_replay`.
+#[pyfunction]
+pub(crate) fn incremental_snapshot_load(bytes: Vec<u8>) -> PyResult<u64> {
+    let snap = ta_engine::incremental::snapshot_codec::decode_snapshot_from_bytes(&bytes)
+        .map_err(|err| pyo3::exceptions::PyValueError::new_err(err.to_string()))?;
 
     let snapshot_id = next_snapshot_id();
-    with_snapshots_mut(|snaps| {
-        snaps.insert(snapshot_id, snapshot);
-        snapshot_id
+    insert_snapshot(snapshot_id, snap)?;
+    Ok(snapshot_id)
+}
+
+/// Returns `snapshot_id`'s current state as a dict (`schema_version`,
+/// `nodes` keyed by node id, each with `ticks_processed`/`last_output`/
+/// `state_blob`) -- the same shape `snapshot_codec::encode_snapshot_to_json`
+/// produces. Since a stored snapshot is never mutated in place, the built
+/// dict is cached after the first call and a cheap `Py` clone is returned
+/// on every call after that, instead of re-walking the snapshot's node map.
+#[pyfunction]
+pub(crate) fn incremental_snapshot_inspect(py: Python<'_>, snapshot_id: u64) -> PyResult<PyObject> {
+    Ok(snapshot_pydict(py, snapshot_id)?.into_any())
+}
+
+/// Captures `backend_id`'s current snapshot and serializes it straight to
+/// bytes, skipping the `incremental_snapshot`/`incremental_snapshot_dump`
+/// indirection through the process-local snapshot-id map. The returned
+/// bytes are the same versioned, self-describing format `snapshot_codec`
+/// already produces, so they can be written to disk or shipped to another
+/// host and read back with `incremental_restore_bytes` there -- no shared
+/// in-process handle required.
+#[pyfunction]
+pub(crate) fn incremental_snapshot_bytes(py: Python<'_>, backend_id: u64) -> PyResult<Vec<u8>> {
+    let snapshot = with_backend_mut(backend_id, |backend| backend.snapshot())?;
+    Ok(py.allow_threads(|| {
+        ta_engine::incremental::snapshot_codec::encode_snapshot_to_bytes(&snapshot)
+    }))
+}
+
+/// Decodes bytes previously produced by `incremental_snapshot_bytes` (or
+/// `incremental_snapshot_dump`), migrating them to the current schema
+/// version, and restores `backend_id` in place. Unlike `incremental_replay`
+/// this does not also replay events -- it just leaves the backend resumed
+/// from the restored state, ready for further `incremental_step` calls.
+#[pyfunction]
+pub(crate) fn incremental_restore_bytes(
+    py: Python<'_>,
+    backend_id: u64,
+    bytes: Vec<u8>,
+) -> PyResult<()> {
+    let snapshot = py.allow_threads(|| {
+        ta_engine::incremental::snapshot_codec::decode_snapshot_from_bytes(&bytes)
     })
+    .map_err(|err| pyo3::exceptions::PyValueError::new_err(err.to_string()))?;
+
+    with_backend_mut(backend_id, |backend| {
+        backend
+            .restore(snapshot)
+            .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+    })?
+}
+
+/// Turns on per-node cost accounting for `backend_id` -- see
+/// `IncrementalBackend::enable_profiling`. Off by default; call this once
+/// before the `incremental_step`/`incremental_replay` calls you want to
+/// measure.
+#[pyfunction]
+pub(crate) fn incremental_enable_profiling(backend_id: u64) -> PyResult<()> {
+    with_backend_mut(backend_id, |backend| backend.enable_profiling())
 }
 
+/// Returns the per-node profile captured since `incremental_enable_profiling`
+/// was called on `backend_id`, keyed by node id, each a dict with
+/// `ticks_processed`, `total_nanos`, `peak_state_blob_bytes`, and
+/// `recompute_count`. Empty if profiling was never enabled for this backend.
 #[pyfunction]
+pub(crate) fn incremental_profile(py: Python<'_>, backend_id: u64) -> PyResult<PyObject> {
+    let profiles = with_backend_mut(backend_id, |backend| backend.profile().clone())?;
+
+    let out = PyDict::new(py);
+    for (node_id, profile) in profiles {
+        let record = PyDict::new(py);
+        record.set_item("ticks_processed", profile.ticks_processed)?;
+        record.set_item("total_nanos", profile.total_nanos)?;
+        record.set_item("peak_state_blob_bytes", profile.peak_state_blob_bytes)?;
+        record.set_item("recompute_count", profile.recompute_count)?;
+        out.set_item(node_id, record)?;
+    }
+    Ok(out.into_any().unbind())
+}
+
+#[pyfunction]
+#[pyo3(signature = (backend_id, snapshot_id, requests, events, stream_id=0, start_event_index=1))]
 pub(crate) fn incremental_replay(
     py: Python<'_>,
     backend_id: u64,
     snapshot_id: u64,
     requests: &Bound<'_, PyList>,
     events: &Bound<'_, PyList>,
+    stream_id: u32,
+    start_event_index: u64,
 ) -> PyResult<PyObject> {
-    let snapshot = with_snapshots(|snaps| {
-        snaps.get(&snapshot_id).cloned().ok_or_else(|| {
-            pyo3::exceptions::PyKeyError::new_err(format!("snapshot id {snapshot_id} not found"))
-        })
-    })??;
+    let snap = snapshot(snapshot_id)?;
 
     let parsed_requests = parse_requests(requests)?;
     let parsed_events = parse_events(events)?;
 
-    let replay_out = with_backends_mut(|map| {
-        let backend = map.get_mut(&backend_id).ok_or_else(|| {
-            pyo3::exceptions::PyKeyError::new_err(format!("backend id {backend_id} not found"))
-        })?;
+    let replay_out = py.allow_threads(|| {
+        with_backend_mut(backend_id, |backend| {
+            backend
+                .restore(snap)
+                .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+            backend
+                .replay(stream_id, start_event_index, &parsed_requests, &parsed_events)
+                .map_err(map_execute_plan_error)
+        })
+    })??;
 
-        backend
-            .restore(snapshot)
-            .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
-        Ok::<_, PyErr>(backend.replay(&parsed_requests, &parsed_events))
+    let py_list = PyList::empty(py);
+    for step in replay_out {
+        py_list.append(incremental_map_to_pydict(py, &step)?)?;
+    }
+    Ok(py_list.into_any().unbind())
+}
+
+/// Zero-copy counterpart of `incremental_replay`: builds its `events` from
+/// contiguous/strided NumPy OHLCV column arrays (see
+/// `events_from_ohlcv_arrays`) instead of a Python list of per-row dicts,
+/// avoiding one `extract::<f64>()` Python call per cell on large backtests.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (
+    backend_id, snapshot_id, requests, open, high, low, close, volume,
+    stream_id=0, start_event_index=1
+))]
+pub(crate) fn incremental_replay_np(
+    py: Python<'_>,
+    backend_id: u64,
+    snapshot_id: u64,
+    requests: &Bound<'_, PyList>,
+    open: PyReadonlyArray1<'_, f64>,
+    high: PyReadonlyArray1<'_, f64>,
+    low: PyReadonlyArray1<'_, f64>,
+    close: PyReadonlyArray1<'_, f64>,
+    volume: PyReadonlyArray1<'_, f64>,
+    stream_id: u32,
+    start_event_index: u64,
+) -> PyResult<PyObject> {
+    let snap = snapshot(snapshot_id)?;
+
+    let parsed_requests = parse_requests(requests)?;
+    let parsed_events = events_from_ohlcv_arrays(open, high, low, close, volume)?;
+
+    let replay_out = py.allow_threads(|| {
+        with_backend_mut(backend_id, |backend| {
+            backend
+                .restore(snap)
+                .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+            backend
+                .replay(stream_id, start_event_index, &parsed_requests, &parsed_events)
+                .map_err(map_execute_plan_error)
+        })
     })??;
 
     let py_list = PyList::empty(py);
@@ -99,6 +278,18 @@ pub(crate) fn incremental_replay(
     Ok(py_list.into_any().unbind())
 }
 
+/// Splits `requests` into native kernel-id dispatch (resolved as before via
+/// `KernelId::from_name`) and overlay dispatch: any `kernel_id` registered
+/// through `register_indicator` is pulled out and run against the
+/// registered Python callable instead, so a registration sharing an id with
+/// a built-in kernel transparently overrides it while unregistered ids fall
+/// straight through to `parse_requests`' native resolution. Returns
+/// `{"series": {node_id: series}, "cache_hits": N}` rather than a flat
+/// `node_id -> series` dict, since `cache_hits` (how many native requests
+/// were answered from `dedupe_requests`' content-addressed cache rather
+/// than recomputed) isn't itself a series -- mixing it into the series dict
+/// would break every consumer (`pd.DataFrame(result)`, `.values()` loops)
+/// that assumes a homogeneous `node_id -> series` map.
 #[pyfunction]
 pub(crate) fn execute_plan(
     py: Python<'_>,
@@ -108,18 +299,119 @@ pub(crate) fn execute_plan(
     source: String,
     requests: &Bound<'_, PyList>,
 ) -> PyResult<PyObject> {
-    let parsed_requests = parse_requests(requests)?;
-    let payload = ExecutePlanPayload {
-        dataset_id,
-        partition_key: DatasetPartitionKey {
-            symbol,
-            timeframe,
-            source,
-        },
-        requests: parsed_requests,
+    let partition_key = DatasetPartitionKey {
+        symbol,
+        timeframe,
+        source,
     };
-    let out = backend::execute_plan_payload(&payload).map_err(map_execute_plan_error)?;
-    incremental_series_map_to_pydict(py, &out)
+
+    let native_requests = PyList::empty(py);
+    let mut overlay_requests = Vec::new();
+    for item in requests.iter() {
+        let d = item.downcast::<PyDict>()?;
+        let kernel_id: String = d
+            .get_item("kernel_id")?
+            .ok_or_else(|| pyo3::exceptions::PyKeyError::new_err("missing kernel_id"))?
+            .extract()?;
+        match overlay_callable(py, &kernel_id)? {
+            Some(callable) => {
+                let node_id: u32 = d
+                    .get_item("node_id")?
+                    .ok_or_else(|| pyo3::exceptions::PyKeyError::new_err("missing node_id"))?
+                    .extract()?;
+                let kwargs: Py<PyDict> = d
+                    .get_item("kwargs")?
+                    .ok_or_else(|| pyo3::exceptions::PyKeyError::new_err("missing kwargs"))?
+                    .downcast_into::<PyDict>()?
+                    .unbind();
+                overlay_requests.push((node_id, callable, kwargs));
+            }
+            None => native_requests.append(d)?,
+        }
+    }
+
+    let (mut out, cache_hits) = if native_requests.len() == 0 {
+        (BTreeMap::new(), 0usize)
+    } else {
+        let parsed_requests = parse_requests(&native_requests)?;
+        let payload = ExecutePlanPayload {
+            dataset_id,
+            partition_key: partition_key.clone(),
+            requests: parsed_requests,
+        };
+        let (out, stats) = py
+            .allow_threads(|| backend::execute_plan_payload_with_stats(&payload))
+            .map_err(map_execute_plan_error)?;
+        (out, stats.cache_hits)
+    };
+
+    if !overlay_requests.is_empty() {
+        run_overlay_requests(py, dataset_id, &partition_key, overlay_requests, &mut out)?;
+    }
+
+    let series = incremental_series_map_to_pydict(py, &out)?;
+    let response = PyDict::new(py);
+    response.set_item("series", series)?;
+    response.set_item("cache_hits", cache_hits)?;
+    Ok(response.into_any().unbind())
+}
+
+/// Computes every overlay-registered `(node_id, callable, kwargs)` request
+/// against `dataset_id`'s `partition_key` OHLCV, calling each callable as
+/// `callable(ohlcv, kwargs)` where `ohlcv` is a dict of
+/// `timestamps`/`open`/`high`/`low`/`close`/`volume` lists -- the same
+/// columns native kernels read one tick at a time, handed over in one batch
+/// since a user callable has no incremental state to resume partway. Each
+/// callable must return a `list[float]` the same length as `timestamps`;
+/// its values land in `out` under `node_id` alongside the natively
+/// dispatched nodes.
+fn run_overlay_requests(
+    py: Python<'_>,
+    dataset_id: u64,
+    partition_key: &DatasetPartitionKey,
+    overlay_requests: Vec<(u32, Py<PyAny>, Py<PyDict>)>,
+    out: &mut BTreeMap<u32, Vec<IncrementalValue>>,
+) -> PyResult<()> {
+    let record = dataset::get_dataset(dataset_id).map_err(map_dataset_error)?;
+    let partition = record.partitions.get(partition_key).ok_or_else(|| {
+        pyo3::exceptions::PyKeyError::new_err(format!(
+            "dataset partition not found for symbol={} timeframe={} source={}",
+            partition_key.symbol, partition_key.timeframe, partition_key.source
+        ))
+    })?;
+    let ohlcv = partition.ohlcv.as_ref().ok_or_else(|| {
+        pyo3::exceptions::PyValueError::new_err(format!(
+            "ohlcv columns missing for symbol={} timeframe={} source={}",
+            partition_key.symbol, partition_key.timeframe, partition_key.source
+        ))
+    })?;
+
+    let ohlcv_dict = PyDict::new(py);
+    ohlcv_dict.set_item("timestamps", ohlcv.timestamps.clone())?;
+    ohlcv_dict.set_item("open", ohlcv.open.clone())?;
+    ohlcv_dict.set_item("high", ohlcv.high.clone())?;
+    ohlcv_dict.set_item("low", ohlcv.low.clone())?;
+    ohlcv_dict.set_item("close", ohlcv.close.clone())?;
+    ohlcv_dict.set_item("volume", ohlcv.volume.clone())?;
+    let rows = ohlcv.timestamps.len();
+
+    for (node_id, callable, kwargs) in overlay_requests {
+        let result: Vec<f64> = callable
+            .bind(py)
+            .call1((ohlcv_dict.clone(), kwargs.bind(py).clone()))?
+            .extract()?;
+        if result.len() != rows {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "overlay indicator for node_id {node_id} returned {} values, expected {rows}",
+                result.len()
+            )));
+        }
+        out.insert(
+            node_id,
+            result.into_iter().map(IncrementalValue::Number).collect(),
+        );
+    }
+    Ok(())
 }
 
 #[pyfunction]
@@ -127,86 +419,139 @@ pub(crate) fn execute_plan_payload(
     py: Python<'_>,
     payload: &Bound<'_, PyDict>,
 ) -> PyResult<PyObject> {
-    let dataset_id: u64 = payload
-        .get_item("dataset_id")?
-        .ok_or_else(|| pyo3::exceptions::PyKeyError::new_err("missing dataset_id"))?
-        .extract()?;
-    let partition = payload
-        .get_item("partition")?
-        .ok_or_else(|| pyo3::exceptions::PyKeyError::new_err("missing partition"))?
-        .downcast_into::<PyDict>()?;
-    let symbol: String = partition
-        .get_item("symbol")?
-        .ok_or_else(|| pyo3::exceptions::PyKeyError::new_err("missing partition.symbol"))?
-        .extract()?;
-    let timeframe: String = partition
-        .get_item("timeframe")?
-        .ok_or_else(|| pyo3::exceptions::PyKeyError::new_err("missing partition.timeframe"))?
-        .extract()?;
-    let source: String = partition
-        .get_item("source")?
-        .ok_or_else(|| pyo3::exceptions::PyKeyError::new_err("missing partition.source"))?
-        .extract()?;
-    let requests = payload
-        .get_item("requests")?
-        .ok_or_else(|| pyo3::exceptions::PyKeyError::new_err("missing requests"))?
-        .downcast_into::<PyList>()?;
-
-    let graph = payload
-        .get_item("graph")?
-        .ok_or_else(|| pyo3::exceptions::PyKeyError::new_err("missing graph"))?
-        .downcast_into::<PyDict>()?;
-    let root_id: u32 = graph
-        .get_item("root_id")?
-        .ok_or_else(|| pyo3::exceptions::PyKeyError::new_err("missing graph.root_id"))?
-        .extract()?;
-    let node_order: Vec<u32> = graph
-        .get_item("node_order")?
-        .ok_or_else(|| pyo3::exceptions::PyKeyError::new_err("missing graph.node_order"))?
-        .extract()?;
-    let nodes_dict = graph
-        .get_item("nodes")?
-        .ok_or_else(|| pyo3::exceptions::PyKeyError::new_err("missing graph.nodes"))?
-        .downcast_into::<PyDict>()?;
-    let edges_dict = graph
-        .get_item("edges")?
-        .ok_or_else(|| pyo3::exceptions::PyKeyError::new_err("missing graph.edges"))?
-        .downcast_into::<PyDict>()?;
-
-    let mut nodes: BTreeMap<u32, BTreeMap<String, String>> = BTreeMap::new();
-    for (k, v) in nodes_dict.iter() {
-        let node_id = extract_node_id(&k)?;
-        let details = v.downcast::<PyDict>()?;
-        let mut map = BTreeMap::new();
-        for (dk, dv) in details.iter() {
-            map.insert(dk.extract::<String>()?, extract_scalar_string(&dv)?);
-        }
-        nodes.insert(node_id, map);
-    }
+    let contract_payload = parse_execution_payload_dict(payload)?;
+    let out = py
+        .allow_threads(|| backend::execute_plan_graph_payload(&contract_payload))
+        .map_err(map_execute_plan_error)?;
+    incremental_series_map_to_pydict(py, &out)
+}
 
-    let mut edges = BTreeMap::new();
-    for (k, v) in edges_dict.iter() {
-        let node_id = extract_node_id(&k)?;
-        let child_ids: Vec<u32> = v.extract()?;
-        edges.insert(node_id, child_ids);
+/// Runs many independent `RustExecutionPayload`-shaped dicts (same shape as
+/// `execute_plan_payload`'s `payload` argument) in a single call, computing
+/// each partition's graph in parallel across rayon's thread pool with the
+/// GIL released for the whole batch. Returns a dict keyed by
+/// `(symbol, timeframe, source)` tuples mapping to each partition's series
+/// map, so a large universe of symbols/timeframes costs one Python->Rust
+/// round trip instead of one per partition.
+#[pyfunction]
+pub(crate) fn execute_plan_batch(
+    py: Python<'_>,
+    payloads: &Bound<'_, PyList>,
+) -> PyResult<PyObject> {
+    let parsed_payloads = payloads
+        .iter()
+        .map(|item| parse_execution_payload_dict(item.downcast::<PyDict>()?))
+        .collect::<PyResult<Vec<_>>>()?;
+
+    let results = py.allow_threads(|| backend::execute_plan_graph_batch(&parsed_payloads));
+
+    let out = PyDict::new(py);
+    for (partition, result) in results {
+        let key = (partition.symbol, partition.timeframe, partition.source);
+        let series = result.map_err(map_execute_plan_error)?;
+        out.set_item(key, incremental_series_map_to_pydict(py, &series)?)?;
     }
+    Ok(out.into_any().unbind())
+}
 
-    let contract_payload = RustExecutionPayload {
-        dataset_id,
-        partition: RustExecutionPartition {
-            symbol,
-            timeframe,
-            source,
-        },
-        graph: RustExecutionGraph {
-            root_id,
-            node_order,
-            nodes,
-            edges,
-        },
-        requests: parse_contract_requests(&requests)?,
+/// The kernel-list-shaped counterpart of `execute_plan_batch`/
+/// `execute_plan_prefix`, which both operate on the graph-shaped
+/// `RustExecutionPayload`. `requests` is the same plain kernel-request list
+/// `execute_plan`/`execute_plan_payload` take -- it is parsed and validated
+/// (including each `kernel_id` via `KernelId::from_name`, through
+/// `parse_requests`) exactly once up front, so a bad kernel name fails
+/// before any partition is touched. `partitions` is a list of
+/// `(symbol, timeframe, source)` tuples -- the same shape
+/// `dataset_list_partitions` returns -- or `None` to mean every partition
+/// currently registered for `dataset_id`. Partitions run in parallel across
+/// rayon's thread pool with the GIL released, and are re-acquired only once
+/// at the end to build the result dicts. `max_threads` caps the worker count
+/// used for this call (see `backend::execute_plan_batch`); left as `None`,
+/// rayon's default global pool is used. Results are inserted into the
+/// returned `results`/`errors` dicts in `partition_keys` order (or, when
+/// `partitions` is `None`, the order `dataset_list_partitions` returns) --
+/// this is deterministic regardless of which partition's rayon task happens
+/// to finish first. A partition that fails (e.g. `PartitionNotFound`) does
+/// not abort the batch: its error message is recorded in the returned
+/// `errors` sub-dict instead of `results`, both keyed by the partition's
+/// `(symbol, timeframe, source)` tuple.
+#[pyfunction]
+#[pyo3(signature = (dataset_id, requests, partitions=None, max_threads=None))]
+pub(crate) fn execute_plan_requests_batch(
+    py: Python<'_>,
+    dataset_id: u64,
+    requests: &Bound<'_, PyList>,
+    partitions: Option<&Bound<'_, PyList>>,
+    max_threads: Option<usize>,
+) -> PyResult<PyObject> {
+    let parsed_requests = parse_requests(requests)?;
+
+    let partition_keys: Vec<DatasetPartitionKey> = match partitions {
+        Some(items) => items
+            .iter()
+            .map(|item| {
+                let (symbol, timeframe, source): (String, String, String) = item.extract()?;
+                Ok(DatasetPartitionKey {
+                    symbol,
+                    timeframe,
+                    source,
+                })
+            })
+            .collect::<PyResult<Vec<_>>>()?,
+        None => dataset::list_partitions(dataset_id, None, None).map_err(map_dataset_error)?,
     };
-    let out =
-        backend::execute_plan_graph_payload(&contract_payload).map_err(map_execute_plan_error)?;
-    incremental_series_map_to_pydict(py, &out)
+
+    let results = py.allow_threads(|| {
+        backend::execute_plan_batch(dataset_id, &partition_keys, &parsed_requests, max_threads)
+    });
+
+    let out = PyDict::new(py);
+    let errors = PyDict::new(py);
+    for (key, result) in results {
+        let tuple_key = (key.symbol, key.timeframe, key.source);
+        match result {
+            Ok(series) => {
+                out.set_item(tuple_key, incremental_series_map_to_pydict(py, &series)?)?;
+            }
+            Err(err) => {
+                errors.set_item(tuple_key, err.to_string())?;
+            }
+        }
+    }
+
+    let response = PyDict::new(py);
+    response.set_item("results", out)?;
+    response.set_item("errors", errors)?;
+    Ok(response.into_any().unbind())
+}
+
+/// Runs a single `RustExecutionPayload`-shaped dict's graph against every
+/// partition of its `dataset_id` matching `symbol`/`timeframe` as a leading
+/// prefix (same filter rules as `dataset_list_partitions`), in parallel with
+/// the GIL released for the whole batch. The dict's own `partition` field is
+/// ignored -- one run per matched partition. Returns a dict keyed by
+/// `(symbol, timeframe, source)` tuples, the same shape `execute_plan_batch`
+/// returns.
+#[pyfunction]
+#[pyo3(signature = (payload, symbol=None, timeframe=None))]
+pub(crate) fn execute_plan_prefix(
+    py: Python<'_>,
+    payload: &Bound<'_, PyDict>,
+    symbol: Option<String>,
+    timeframe: Option<String>,
+) -> PyResult<PyObject> {
+    let contract_payload = parse_execution_payload_dict(payload)?;
+
+    let results = py.allow_threads(|| {
+        backend::execute_plan_graph_prefix(&contract_payload, symbol.as_deref(), timeframe.as_deref())
+    })
+    .map_err(map_execute_plan_error)?;
+
+    let out = PyDict::new(py);
+    for (partition, result) in results {
+        let key = (partition.symbol, partition.timeframe, partition.source);
+        let series = result.map_err(map_execute_plan_error)?;
+        out.set_item(key, incremental_series_map_to_pydict(py, &series)?)?;
+    }
+    Ok(out.into_any().unbind())
 }
@@ -11,6 +11,29 @@ fn validate_period(period: usize) -> PyResult<()> {
     Ok(())
 }
 
+fn parse_fill_policy(name: &str, constant: Option<f64>) -> PyResult<ta_engine::FillPolicy> {
+    match name.to_ascii_lowercase().as_str() {
+        "nan" => Ok(ta_engine::FillPolicy::Nan),
+        "zero" => Ok(ta_engine::FillPolicy::Zero),
+        "ffill" => Ok(ta_engine::FillPolicy::Ffill),
+        "drop" => Ok(ta_engine::FillPolicy::Drop),
+        "constant" => constant.map(ta_engine::FillPolicy::Constant).ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err(
+                "fill_policy 'constant' requires a fill_value",
+            )
+        }),
+        other => Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "unsupported fill_policy: {other}"
+        ))),
+    }
+}
+
+fn parse_ma_type(name: &str) -> PyResult<ta_engine::moving_averages::MovingAverageType> {
+    ta_engine::moving_averages::MovingAverageType::parse(name).ok_or_else(|| {
+        pyo3::exceptions::PyValueError::new_err(format!("unsupported ma_type: {name}"))
+    })
+}
+
 #[pyfunction]
 pub(crate) fn rolling_sum(values: Vec<f64>, period: usize) -> PyResult<Vec<f64>> {
     validate_period(period)?;
@@ -36,6 +59,13 @@ pub(crate) fn rolling_max(values: Vec<f64>, period: usize) -> PyResult<Vec<f64>>
     validate_period(period)?;
     Ok(ta_engine::rolling::rolling_max(&values, period))
 }
+/// Whether `rolling_sum`/`rolling_mean`/`rolling_std`/`rolling_min`/
+/// `rolling_max` are currently using the wider SIMD-friendly accumulator
+/// path on this CPU, versus the narrower scalar-fallback baseline.
+#[pyfunction]
+pub(crate) fn simd_enabled() -> bool {
+    ta_engine::rolling::simd_enabled()
+}
 #[pyfunction]
 pub(crate) fn rolling_ema(values: Vec<f64>, period: usize) -> PyResult<Vec<f64>> {
     validate_period(period)?;
@@ -61,6 +91,22 @@ pub(crate) fn rsi(values: Vec<f64>, period: usize) -> PyResult<Vec<f64>> {
     validate_period(period)?;
     Ok(ta_engine::momentum::rsi(&values, period))
 }
+/// Same as [`rsi`], but the no-loss-in-window warmup/degenerate point is
+/// resolved through `fill_policy` (`"nan"`/`"zero"`/`"ffill"`/`"constant"`/`"drop"`,
+/// `fill_value` is required for `"constant"`) instead of the hardcoded
+/// `50.0`/`100.0` sentinel. Returns `(values, drop_offset)`.
+#[pyfunction]
+#[pyo3(signature = (values, period, fill_policy, fill_value=None))]
+pub(crate) fn rsi_with_policy(
+    values: Vec<f64>,
+    period: usize,
+    fill_policy: String,
+    fill_value: Option<f64>,
+) -> PyResult<(Vec<f64>, usize)> {
+    validate_period(period)?;
+    let policy = parse_fill_policy(&fill_policy, fill_value)?;
+    Ok(ta_engine::momentum::rsi_with_policy(&values, period, policy))
+}
 #[pyfunction]
 pub(crate) fn roc(values: Vec<f64>, period: usize) -> PyResult<Vec<f64>> {
     validate_period(period)?;
@@ -114,6 +160,27 @@ pub(crate) fn mfi(
         &high, &low, &close, &volume, period,
     ))
 }
+/// Same as [`mfi`], but the no-negative-flow-in-window point is resolved
+/// through `fill_policy` instead of the hardcoded `100.0` sentinel. Returns
+/// `(values, drop_offset)`.
+#[pyfunction]
+#[pyo3(signature = (high, low, close, volume, period, fill_policy, fill_value=None))]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn mfi_with_policy(
+    high: Vec<f64>,
+    low: Vec<f64>,
+    close: Vec<f64>,
+    volume: Vec<f64>,
+    period: usize,
+    fill_policy: String,
+    fill_value: Option<f64>,
+) -> PyResult<(Vec<f64>, usize)> {
+    validate_period(period)?;
+    let policy = parse_fill_policy(&fill_policy, fill_value)?;
+    Ok(ta_engine::momentum::mfi_with_policy(
+        &high, &low, &close, &volume, period, policy,
+    ))
+}
 #[pyfunction]
 pub(crate) fn vortex(
     high: Vec<f64>,
@@ -126,13 +193,14 @@ pub(crate) fn vortex(
 }
 #[pyfunction]
 pub(crate) fn atr(
+    py: Python<'_>,
     high: Vec<f64>,
     low: Vec<f64>,
     close: Vec<f64>,
     period: usize,
 ) -> PyResult<Vec<f64>> {
     validate_period(period)?;
-    Ok(ta_engine::volatility::atr(&high, &low, &close, period))
+    Ok(py.allow_threads(|| ta_engine::volatility::atr(&high, &low, &close, period)))
 }
 #[pyfunction]
 pub(crate) fn atr_from_tr(values: Vec<f64>, period: usize) -> PyResult<Vec<f64>> {
@@ -160,30 +228,38 @@ pub(crate) fn obv(close: Vec<f64>, volume: Vec<f64>) -> PyResult<Vec<f64>> {
     Ok(ta_engine::volume::obv(&close, &volume))
 }
 #[pyfunction]
+#[pyo3(signature = (values, fast_period, slow_period, signal_period, ma_type="EMA".to_string()))]
 pub(crate) fn macd(
+    py: Python<'_>,
     values: Vec<f64>,
     fast_period: usize,
     slow_period: usize,
     signal_period: usize,
+    ma_type: String,
 ) -> PyResult<(Vec<f64>, Vec<f64>, Vec<f64>)> {
     validate_period(fast_period)?;
     validate_period(slow_period)?;
     validate_period(signal_period)?;
-    Ok(ta_engine::trend::macd(
-        &values,
-        fast_period,
-        slow_period,
-        signal_period,
-    ))
+    let ma_type = parse_ma_type(&ma_type)?;
+    Ok(py.allow_threads(|| {
+        ta_engine::trend::macd(&values, fast_period, slow_period, signal_period, ma_type)
+    }))
 }
 #[pyfunction]
+#[pyo3(signature = (values, period, std_dev, ma_type="SMA".to_string()))]
 pub(crate) fn bbands(
     values: Vec<f64>,
     period: usize,
     std_dev: f64,
+    ma_type: String,
 ) -> PyResult<(Vec<f64>, Vec<f64>, Vec<f64>)> {
     validate_period(period)?;
-    Ok(ta_engine::volatility::bbands(&values, period, std_dev))
+    Ok(ta_engine::volatility::bbands(
+        &values,
+        period,
+        std_dev,
+        parse_ma_type(&ma_type)?,
+    ))
 }
 #[pyfunction]
 pub(crate) fn donchian(
@@ -195,6 +271,7 @@ pub(crate) fn donchian(
     Ok(ta_engine::volatility::donchian(&high, &low, period))
 }
 #[pyfunction]
+#[pyo3(signature = (high, low, close, ema_period, atr_period, multiplier, ma_type="EMA".to_string()))]
 pub(crate) fn keltner(
     high: Vec<f64>,
     low: Vec<f64>,
@@ -202,15 +279,23 @@ pub(crate) fn keltner(
     ema_period: usize,
     atr_period: usize,
     multiplier: f64,
+    ma_type: String,
 ) -> PyResult<(Vec<f64>, Vec<f64>, Vec<f64>)> {
     validate_period(ema_period)?;
     validate_period(atr_period)?;
     Ok(ta_engine::volatility::keltner(
-        &high, &low, &close, ema_period, atr_period, multiplier,
+        &high,
+        &low,
+        &close,
+        ema_period,
+        atr_period,
+        multiplier,
+        parse_ma_type(&ma_type)?,
     ))
 }
 #[pyfunction]
 pub(crate) fn ichimoku(
+    py: Python<'_>,
     high: Vec<f64>,
     low: Vec<f64>,
     close: Vec<f64>,
@@ -223,15 +308,17 @@ pub(crate) fn ichimoku(
     validate_period(kijun_period)?;
     validate_period(span_b_period)?;
     validate_period(displacement)?;
-    Ok(ta_engine::trend::ichimoku(
-        &high,
-        &low,
-        &close,
-        tenkan_period,
-        kijun_period,
-        span_b_period,
-        displacement,
-    ))
+    Ok(py.allow_threads(|| {
+        ta_engine::trend::ichimoku(
+            &high,
+            &low,
+            &close,
+            tenkan_period,
+            kijun_period,
+            span_b_period,
+            displacement,
+        )
+    }))
 }
 #[pyfunction]
 pub(crate) fn fisher(
@@ -262,6 +349,7 @@ pub(crate) fn psar(
 }
 #[pyfunction]
 pub(crate) fn supertrend(
+    py: Python<'_>,
     high: Vec<f64>,
     low: Vec<f64>,
     close: Vec<f64>,
@@ -269,19 +357,20 @@ pub(crate) fn supertrend(
     multiplier: f64,
 ) -> PyResult<(Vec<f64>, Vec<f64>)> {
     validate_period(period)?;
-    Ok(ta_engine::trend::supertrend(
-        &high, &low, &close, period, multiplier,
-    ))
+    Ok(py.allow_threads(|| {
+        ta_engine::trend::supertrend(&high, &low, &close, period, multiplier)
+    }))
 }
 #[pyfunction]
 pub(crate) fn adx(
+    py: Python<'_>,
     high: Vec<f64>,
     low: Vec<f64>,
     close: Vec<f64>,
     period: usize,
 ) -> PyResult<(Vec<f64>, Vec<f64>, Vec<f64>)> {
     validate_period(period)?;
-    Ok(ta_engine::trend::adx(&high, &low, &close, period))
+    Ok(py.allow_threads(|| ta_engine::trend::adx(&high, &low, &close, period)))
 }
 #[pyfunction]
 pub(crate) fn swing_points_raw(
@@ -308,6 +397,25 @@ pub(crate) fn cci(
 ) -> PyResult<Vec<f64>> {
     Ok(ta_engine::momentum::cci(&high, &low, &close, period))
 }
+/// Same as [`cci`], but the flat-window point is resolved through
+/// `fill_policy` instead of the hardcoded `0.0` sentinel. Returns
+/// `(values, drop_offset)`.
+#[pyfunction]
+#[pyo3(signature = (high, low, close, period, fill_policy, fill_value=None))]
+pub(crate) fn cci_with_policy(
+    high: Vec<f64>,
+    low: Vec<f64>,
+    close: Vec<f64>,
+    period: usize,
+    fill_policy: String,
+    fill_value: Option<f64>,
+) -> PyResult<(Vec<f64>, usize)> {
+    validate_period(period)?;
+    let policy = parse_fill_policy(&fill_policy, fill_value)?;
+    Ok(ta_engine::momentum::cci_with_policy(
+        &high, &low, &close, period, policy,
+    ))
+}
 #[pyfunction]
 pub(crate) fn elder_ray(
     high: Vec<f64>,
@@ -328,6 +436,25 @@ pub(crate) fn williams_r(
     validate_period(period)?;
     Ok(ta_engine::momentum::williams_r(&high, &low, &close, period))
 }
+/// Same as [`williams_r`], but the flat-range point is resolved through
+/// `fill_policy` instead of the hardcoded `0.0` sentinel. Returns
+/// `(values, drop_offset)`.
+#[pyfunction]
+#[pyo3(signature = (high, low, close, period, fill_policy, fill_value=None))]
+pub(crate) fn williams_r_with_policy(
+    high: Vec<f64>,
+    low: Vec<f64>,
+    close: Vec<f64>,
+    period: usize,
+    fill_policy: String,
+    fill_value: Option<f64>,
+) -> PyResult<(Vec<f64>, usize)> {
+    validate_period(period)?;
+    let policy = parse_fill_policy(&fill_policy, fill_value)?;
+    Ok(ta_engine::momentum::williams_r_with_policy(
+        &high, &low, &close, period, policy,
+    ))
+}
 #[pyfunction]
 pub(crate) fn crossup(a: Vec<f64>, b: Vec<f64>) -> PyResult<Vec<bool>> {
     Ok(ta_engine::events::crossup(&a, &b))
@@ -436,3 +563,26 @@ pub(crate) fn cmf(
     validate_period(period)?;
     Ok(ta_engine::volume::cmf(&high, &low, &close, &volume, period))
 }
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn vwap_anchored(
+    timestamps: Vec<i64>,
+    high: Vec<f64>,
+    low: Vec<f64>,
+    close: Vec<f64>,
+    volume: Vec<f64>,
+    anchors: Vec<usize>,
+    session_seconds: i64,
+    std_dev: f64,
+) -> PyResult<(Vec<f64>, Vec<f64>, Vec<f64>)> {
+    Ok(ta_engine::volume::vwap_anchored(
+        &timestamps,
+        &high,
+        &low,
+        &close,
+        &volume,
+        &anchors,
+        session_seconds,
+        std_dev,
+    ))
+}
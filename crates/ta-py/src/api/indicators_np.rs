@@ -0,0 +1,390 @@
+//! NumPy-native counterparts of the hot-path functions in [`super::indicators`].
+//!
+//! Every function here reads straight from the caller's NumPy buffer via
+//! [`PyReadonlyArray1`] instead of copying it into a `Vec<f64>` first, and
+//! hands the result back as a freshly allocated [`PyArray1`] instead of a
+//! `Vec` that PyO3 would otherwise convert into a Python list element by
+//! element. Names match [`super::indicators`] with an `_np` suffix so
+//! callers can opt in per call site without a breaking signature change --
+//! [`super::indicators`]'s plain `Vec<f64>` overloads stay exactly as they
+//! are for pandas/list callers.
+//!
+//! Non-contiguous views (a sliced or transposed array) can't be borrowed as
+//! a flat `&[f64]`, so [`as_cow_slice`] copies only that case via
+//! `to_vec()`; a C-contiguous array never pays that cost. Passing an `f32`
+//! or integer array isn't silently truncated -- PyO3's `f64` extraction
+//! rejects the mismatched dtype with a `TypeError` before the function body
+//! ever runs.
+//!
+//! Every function also accepts an optional `out` buffer (or one `out_*` per
+//! output, for multi-series results): when given, [`into_output`] writes the
+//! computed values straight into it instead of allocating a fresh
+//! [`PyArray1`], which matters for tight streaming loops built on
+//! `incremental_step` that call one of these every tick against a reused
+//! scratch buffer. `out` must be a contiguous `float64` array the same
+//! length as the input -- a length mismatch or non-contiguous buffer raises
+//! `PyValueError` rather than silently truncating or copying.
+
+use std::borrow::Cow;
+
+use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1};
+use pyo3::prelude::*;
+
+use crate::conversions::IchimokuTuple;
+
+type Array1<'py> = Bound<'py, PyArray1<f64>>;
+type ArrayTriple<'py> = (Array1<'py>, Array1<'py>, Array1<'py>);
+type ArrayQuintuple<'py> = (Array1<'py>, Array1<'py>, Array1<'py>, Array1<'py>, Array1<'py>);
+
+fn validate_period(period: usize) -> PyResult<()> {
+    if period == 0 {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "period must be positive",
+        ));
+    }
+    Ok(())
+}
+
+fn parse_ma_type(name: &str) -> PyResult<ta_engine::moving_averages::MovingAverageType> {
+    ta_engine::moving_averages::MovingAverageType::parse(name).ok_or_else(|| {
+        pyo3::exceptions::PyValueError::new_err(format!("unsupported ma_type: {name}"))
+    })
+}
+
+/// Borrows `arr`'s backing buffer directly when it's a contiguous flat
+/// array, falling back to a single `to_vec()` copy (via the strided
+/// `ndarray` view) only when it isn't.
+fn as_cow_slice<'a>(arr: &'a PyReadonlyArray1<'_, f64>) -> Cow<'a, [f64]> {
+    match arr.as_slice() {
+        Ok(slice) => Cow::Borrowed(slice),
+        Err(_) => Cow::Owned(arr.as_array().to_vec()),
+    }
+}
+
+/// Writes `computed` into `out` in place when the caller supplied a
+/// preallocated output buffer, avoiding the allocation and copy
+/// `into_pyarray` would otherwise pay for; falls back to allocating a fresh
+/// array when `out` is `None`.
+fn into_output<'py>(
+    py: Python<'py>,
+    computed: Vec<f64>,
+    out: Option<Array1<'py>>,
+) -> PyResult<Array1<'py>> {
+    match out {
+        Some(buf) => {
+            if buf.len() != computed.len() {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "out buffer has length {}, expected {}",
+                    buf.len(),
+                    computed.len()
+                )));
+            }
+            // SAFETY: no other Rust or Python code can be touching `buf`
+            // while we hold the GIL here, and the length check above
+            // guarantees `slice` covers exactly `computed`'s elements.
+            let slice = unsafe { buf.as_slice_mut() }.map_err(|_| {
+                pyo3::exceptions::PyValueError::new_err(
+                    "out buffer must be a contiguous float64 array",
+                )
+            })?;
+            slice.copy_from_slice(&computed);
+            Ok(buf)
+        }
+        None => Ok(computed.into_pyarray(py)),
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (values, period, out=None))]
+pub(crate) fn rolling_sum_np<'py>(
+    py: Python<'py>,
+    values: PyReadonlyArray1<'py, f64>,
+    period: usize,
+    out: Option<Array1<'py>>,
+) -> PyResult<Array1<'py>> {
+    validate_period(period)?;
+    let computed = ta_engine::rolling::rolling_sum(as_cow_slice(&values).as_ref(), period);
+    into_output(py, computed, out)
+}
+
+#[pyfunction]
+#[pyo3(signature = (values, period, out=None))]
+pub(crate) fn rolling_mean_np<'py>(
+    py: Python<'py>,
+    values: PyReadonlyArray1<'py, f64>,
+    period: usize,
+    out: Option<Array1<'py>>,
+) -> PyResult<Array1<'py>> {
+    validate_period(period)?;
+    let computed = ta_engine::rolling::rolling_mean(as_cow_slice(&values).as_ref(), period);
+    into_output(py, computed, out)
+}
+
+#[pyfunction]
+#[pyo3(signature = (values, period, out=None))]
+pub(crate) fn rolling_std_np<'py>(
+    py: Python<'py>,
+    values: PyReadonlyArray1<'py, f64>,
+    period: usize,
+    out: Option<Array1<'py>>,
+) -> PyResult<Array1<'py>> {
+    validate_period(period)?;
+    let computed = ta_engine::rolling::rolling_std(as_cow_slice(&values).as_ref(), period);
+    into_output(py, computed, out)
+}
+
+#[pyfunction]
+#[pyo3(signature = (values, period, out=None))]
+pub(crate) fn rolling_min_np<'py>(
+    py: Python<'py>,
+    values: PyReadonlyArray1<'py, f64>,
+    period: usize,
+    out: Option<Array1<'py>>,
+) -> PyResult<Array1<'py>> {
+    validate_period(period)?;
+    let computed = ta_engine::rolling::rolling_min(as_cow_slice(&values).as_ref(), period);
+    into_output(py, computed, out)
+}
+
+#[pyfunction]
+#[pyo3(signature = (values, period, out=None))]
+pub(crate) fn rolling_max_np<'py>(
+    py: Python<'py>,
+    values: PyReadonlyArray1<'py, f64>,
+    period: usize,
+    out: Option<Array1<'py>>,
+) -> PyResult<Array1<'py>> {
+    validate_period(period)?;
+    let computed = ta_engine::rolling::rolling_max(as_cow_slice(&values).as_ref(), period);
+    into_output(py, computed, out)
+}
+
+#[pyfunction]
+#[pyo3(signature = (values, period, out=None))]
+pub(crate) fn rsi_np<'py>(
+    py: Python<'py>,
+    values: PyReadonlyArray1<'py, f64>,
+    period: usize,
+    out: Option<Array1<'py>>,
+) -> PyResult<Array1<'py>> {
+    validate_period(period)?;
+    let computed = ta_engine::momentum::rsi(as_cow_slice(&values).as_ref(), period);
+    into_output(py, computed, out)
+}
+
+#[pyfunction]
+#[pyo3(signature = (high, low, close, period, out=None))]
+pub(crate) fn atr_np<'py>(
+    py: Python<'py>,
+    high: PyReadonlyArray1<'py, f64>,
+    low: PyReadonlyArray1<'py, f64>,
+    close: PyReadonlyArray1<'py, f64>,
+    period: usize,
+    out: Option<Array1<'py>>,
+) -> PyResult<Array1<'py>> {
+    validate_period(period)?;
+    let computed = ta_engine::volatility::atr(
+        as_cow_slice(&high).as_ref(),
+        as_cow_slice(&low).as_ref(),
+        as_cow_slice(&close).as_ref(),
+        period,
+    );
+    into_output(py, computed, out)
+}
+
+#[pyfunction]
+#[pyo3(signature = (high, low, close, period, out=None))]
+pub(crate) fn cci_np<'py>(
+    py: Python<'py>,
+    high: PyReadonlyArray1<'py, f64>,
+    low: PyReadonlyArray1<'py, f64>,
+    close: PyReadonlyArray1<'py, f64>,
+    period: usize,
+    out: Option<Array1<'py>>,
+) -> PyResult<Array1<'py>> {
+    validate_period(period)?;
+    let computed = ta_engine::momentum::cci(
+        as_cow_slice(&high).as_ref(),
+        as_cow_slice(&low).as_ref(),
+        as_cow_slice(&close).as_ref(),
+        period,
+    );
+    into_output(py, computed, out)
+}
+
+#[pyfunction]
+#[pyo3(signature = (high, low, close, period, out=None))]
+pub(crate) fn williams_r_np<'py>(
+    py: Python<'py>,
+    high: PyReadonlyArray1<'py, f64>,
+    low: PyReadonlyArray1<'py, f64>,
+    close: PyReadonlyArray1<'py, f64>,
+    period: usize,
+    out: Option<Array1<'py>>,
+) -> PyResult<Array1<'py>> {
+    validate_period(period)?;
+    let computed = ta_engine::momentum::williams_r(
+        as_cow_slice(&high).as_ref(),
+        as_cow_slice(&low).as_ref(),
+        as_cow_slice(&close).as_ref(),
+        period,
+    );
+    into_output(py, computed, out)
+}
+
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (high, low, close, k_period, d_period, smooth, out_k=None, out_d=None))]
+pub(crate) fn stochastic_kd_np<'py>(
+    py: Python<'py>,
+    high: PyReadonlyArray1<'py, f64>,
+    low: PyReadonlyArray1<'py, f64>,
+    close: PyReadonlyArray1<'py, f64>,
+    k_period: usize,
+    d_period: usize,
+    smooth: usize,
+    out_k: Option<Array1<'py>>,
+    out_d: Option<Array1<'py>>,
+) -> PyResult<(Array1<'py>, Array1<'py>)> {
+    validate_period(k_period)?;
+    validate_period(d_period)?;
+    validate_period(smooth)?;
+    let (k, d) = ta_engine::momentum::stochastic_kd(
+        as_cow_slice(&high).as_ref(),
+        as_cow_slice(&low).as_ref(),
+        as_cow_slice(&close).as_ref(),
+        k_period,
+        d_period,
+        smooth,
+    );
+    Ok((into_output(py, k, out_k)?, into_output(py, d, out_d)?))
+}
+
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (values, fast_period, slow_period, signal_period, ma_type="EMA".to_string(), out_macd=None, out_signal=None, out_histogram=None))]
+pub(crate) fn macd_np<'py>(
+    py: Python<'py>,
+    values: PyReadonlyArray1<'py, f64>,
+    fast_period: usize,
+    slow_period: usize,
+    signal_period: usize,
+    ma_type: String,
+    out_macd: Option<Array1<'py>>,
+    out_signal: Option<Array1<'py>>,
+    out_histogram: Option<Array1<'py>>,
+) -> PyResult<ArrayTriple<'py>> {
+    validate_period(fast_period)?;
+    validate_period(slow_period)?;
+    validate_period(signal_period)?;
+    let (macd, signal, histogram) = ta_engine::trend::macd(
+        as_cow_slice(&values).as_ref(),
+        fast_period,
+        slow_period,
+        signal_period,
+        parse_ma_type(&ma_type)?,
+    );
+    Ok((
+        into_output(py, macd, out_macd)?,
+        into_output(py, signal, out_signal)?,
+        into_output(py, histogram, out_histogram)?,
+    ))
+}
+
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (values, period, std_dev, ma_type="SMA".to_string(), out_upper=None, out_middle=None, out_lower=None))]
+pub(crate) fn bbands_np<'py>(
+    py: Python<'py>,
+    values: PyReadonlyArray1<'py, f64>,
+    period: usize,
+    std_dev: f64,
+    ma_type: String,
+    out_upper: Option<Array1<'py>>,
+    out_middle: Option<Array1<'py>>,
+    out_lower: Option<Array1<'py>>,
+) -> PyResult<ArrayTriple<'py>> {
+    validate_period(period)?;
+    let (upper, middle, lower) = ta_engine::volatility::bbands(
+        as_cow_slice(&values).as_ref(),
+        period,
+        std_dev,
+        parse_ma_type(&ma_type)?,
+    );
+    Ok((
+        into_output(py, upper, out_upper)?,
+        into_output(py, middle, out_middle)?,
+        into_output(py, lower, out_lower)?,
+    ))
+}
+
+#[pyfunction]
+#[pyo3(signature = (high, low, close, period, out_adx=None, out_plus_di=None, out_minus_di=None))]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn adx_np<'py>(
+    py: Python<'py>,
+    high: PyReadonlyArray1<'py, f64>,
+    low: PyReadonlyArray1<'py, f64>,
+    close: PyReadonlyArray1<'py, f64>,
+    period: usize,
+    out_adx: Option<Array1<'py>>,
+    out_plus_di: Option<Array1<'py>>,
+    out_minus_di: Option<Array1<'py>>,
+) -> PyResult<ArrayTriple<'py>> {
+    validate_period(period)?;
+    let (adx, plus_di, minus_di) = ta_engine::trend::adx(
+        as_cow_slice(&high).as_ref(),
+        as_cow_slice(&low).as_ref(),
+        as_cow_slice(&close).as_ref(),
+        period,
+    );
+    Ok((
+        into_output(py, adx, out_adx)?,
+        into_output(py, plus_di, out_plus_di)?,
+        into_output(py, minus_di, out_minus_di)?,
+    ))
+}
+
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (
+    high, low, close, tenkan_period, kijun_period, span_b_period, displacement,
+    out_tenkan=None, out_kijun=None, out_span_a=None, out_span_b=None, out_chikou=None,
+))]
+pub(crate) fn ichimoku_np<'py>(
+    py: Python<'py>,
+    high: PyReadonlyArray1<'py, f64>,
+    low: PyReadonlyArray1<'py, f64>,
+    close: PyReadonlyArray1<'py, f64>,
+    tenkan_period: usize,
+    kijun_period: usize,
+    span_b_period: usize,
+    displacement: usize,
+    out_tenkan: Option<Array1<'py>>,
+    out_kijun: Option<Array1<'py>>,
+    out_span_a: Option<Array1<'py>>,
+    out_span_b: Option<Array1<'py>>,
+    out_chikou: Option<Array1<'py>>,
+) -> PyResult<ArrayQuintuple<'py>> {
+    validate_period(tenkan_period)?;
+    validate_period(kijun_period)?;
+    validate_period(span_b_period)?;
+    validate_period(displacement)?;
+    let tuple: IchimokuTuple = ta_engine::trend::ichimoku(
+        as_cow_slice(&high).as_ref(),
+        as_cow_slice(&low).as_ref(),
+        as_cow_slice(&close).as_ref(),
+        tenkan_period,
+        kijun_period,
+        span_b_period,
+        displacement,
+    );
+    let (tenkan_sen, kijun_sen, senkou_span_a, senkou_span_b, chikou_span) = tuple;
+    Ok((
+        into_output(py, tenkan_sen, out_tenkan)?,
+        into_output(py, kijun_sen, out_kijun)?,
+        into_output(py, senkou_span_a, out_span_a)?,
+        into_output(py, senkou_span_b, out_span_b)?,
+        into_output(py, chikou_span, out_chikou)?,
+    ))
+}
@@ -0,0 +1,184 @@
+//! A `#[pyclass]` builder over `ta_engine::runtime`'s fused multi-indicator
+//! pipeline: callers declare a DAG of named nodes (source columns,
+//! indicators, element-wise binary ops, event checks) and `evaluate` runs
+//! the whole graph in one Rust pass, instead of one Python-boundary call
+//! per primitive.
+
+use std::collections::BTreeMap;
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use ta_engine::{BinOp, BinOpRhs, EventKind, OhlcvInput, PipelineNode, PipelineNodeKind, PipelineValue};
+
+use crate::conversions::params_dict_to_json;
+use crate::errors::map_compute_error;
+
+fn parse_bin_op(op: &str) -> PyResult<BinOp> {
+    match op.to_ascii_lowercase().as_str() {
+        "min" => Ok(BinOp::Min),
+        "max" => Ok(BinOp::Max),
+        "add" => Ok(BinOp::Add),
+        "mul" => Ok(BinOp::Mul),
+        "sub" => Ok(BinOp::Sub),
+        "subf" => Ok(BinOp::SubF),
+        other => Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "unsupported binop: {other}"
+        ))),
+    }
+}
+
+fn parse_event_kind(kind: &str) -> PyResult<EventKind> {
+    match kind.to_ascii_lowercase().as_str() {
+        "crossup" => Ok(EventKind::CrossUp),
+        "crossdown" => Ok(EventKind::CrossDown),
+        "cross" => Ok(EventKind::Cross),
+        "rising" => Ok(EventKind::Rising),
+        "falling" => Ok(EventKind::Falling),
+        other => Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "unsupported event kind: {other}"
+        ))),
+    }
+}
+
+fn inputs_dict_to_map(inputs: &Bound<'_, PyDict>) -> PyResult<BTreeMap<String, String>> {
+    let mut out = BTreeMap::new();
+    for (k, v) in inputs.iter() {
+        let key: String = k.extract()?;
+        let node: String = v.extract()?;
+        out.insert(key, node);
+    }
+    Ok(out)
+}
+
+#[pyclass]
+pub(crate) struct Pipeline {
+    nodes: Vec<PipelineNode>,
+}
+
+#[pymethods]
+impl Pipeline {
+    #[new]
+    fn new() -> Self {
+        Self { nodes: Vec::new() }
+    }
+
+    /// Declares a raw OHLCV column (`open`/`high`/`low`/`close`/`volume`)
+    /// as node `id`.
+    fn add_source(&mut self, id: String, field: String) {
+        self.nodes.push(PipelineNode {
+            id,
+            kind: PipelineNodeKind::Source(field),
+        });
+    }
+
+    /// Declares a catalog indicator as node `id`. `inputs` maps an
+    /// indicator param name (e.g. `"source"`) to an earlier node's id;
+    /// `output` selects which of the indicator's named outputs becomes
+    /// this node's value (the first output if omitted).
+    #[pyo3(signature = (id, indicator_id, params, inputs, output=None))]
+    fn add_indicator(
+        &mut self,
+        id: String,
+        indicator_id: String,
+        params: &Bound<'_, PyDict>,
+        inputs: &Bound<'_, PyDict>,
+        output: Option<String>,
+    ) -> PyResult<()> {
+        self.nodes.push(PipelineNode {
+            id,
+            kind: PipelineNodeKind::Indicator {
+                indicator_id,
+                params: params_dict_to_json(params)?,
+                inputs: inputs_dict_to_map(inputs)?,
+                output,
+            },
+        });
+        Ok(())
+    }
+
+    /// Declares an element-wise binary op as node `id`. Exactly one of
+    /// `rhs_node` / `rhs_scalar` must be provided: the former compares two
+    /// nodes, the latter broadcasts a scalar against `lhs`.
+    #[pyo3(signature = (id, op, lhs, rhs_node=None, rhs_scalar=None))]
+    fn add_binop(
+        &mut self,
+        id: String,
+        op: String,
+        lhs: String,
+        rhs_node: Option<String>,
+        rhs_scalar: Option<f64>,
+    ) -> PyResult<()> {
+        let rhs = match (rhs_node, rhs_scalar) {
+            (Some(node), None) => BinOpRhs::Node(node),
+            (None, Some(scalar)) => BinOpRhs::Scalar(scalar),
+            _ => {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "exactly one of rhs_node or rhs_scalar must be provided",
+                ))
+            }
+        };
+        self.nodes.push(PipelineNode {
+            id,
+            kind: PipelineNodeKind::BinOp {
+                op: parse_bin_op(&op)?,
+                lhs,
+                rhs,
+            },
+        });
+        Ok(())
+    }
+
+    /// Declares an event node as node `id`. `crossup`/`crossdown`/`cross`
+    /// require `rhs`; `rising`/`falling` ignore it.
+    #[pyo3(signature = (id, kind, lhs, rhs=None))]
+    fn add_event(&mut self, id: String, kind: String, lhs: String, rhs: Option<String>) -> PyResult<()> {
+        self.nodes.push(PipelineNode {
+            id,
+            kind: PipelineNodeKind::Event {
+                kind: parse_event_kind(&kind)?,
+                lhs,
+                rhs,
+            },
+        });
+        Ok(())
+    }
+
+    /// Evaluates every declared node in one Rust traversal, returning a
+    /// dict keyed by node id: numeric nodes map to `list[float]`, event
+    /// nodes map to `list[bool]`.
+    #[pyo3(signature = (timestamps, open, high, low, close, volume=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn evaluate(
+        &self,
+        py: Python<'_>,
+        timestamps: Vec<i64>,
+        open: Vec<f64>,
+        high: Vec<f64>,
+        low: Vec<f64>,
+        close: Vec<f64>,
+        volume: Option<Vec<f64>>,
+    ) -> PyResult<PyObject> {
+        let pipeline = ta_engine::Pipeline {
+            nodes: self.nodes.clone(),
+            ohlcv: OhlcvInput {
+                timestamps,
+                open,
+                high,
+                low,
+                close,
+                volume,
+            },
+        };
+
+        let results = ta_engine::evaluate_pipeline(&pipeline).map_err(map_compute_error)?;
+
+        let out = PyDict::new(py);
+        for (id, value) in results {
+            match value {
+                PipelineValue::Numeric(values) => out.set_item(id, values)?,
+                PipelineValue::Boolean(values) => out.set_item(id, values)?,
+            }
+        }
+        Ok(out.into_any().unbind())
+    }
+}
@@ -0,0 +1,149 @@
+//! Process-level overlay registry for user-defined indicators and
+//! aggregators.
+//!
+//! `indicator_meta`, `indicator_catalog`, and `execute_plan` each consult
+//! this registry before falling back to the built-in catalog/kernel
+//! dispatch -- a registration sharing an id with a built-in indicator
+//! transparently overrides it, the same "overlay over defaults" shape
+//! `theme.rs`'s style resolution uses for per-slot overrides.
+//!
+//! `series_register_aggregator` is the same idea one layer down: it wraps a
+//! Python callable in a [`PyBucketAggregator`] and installs it into
+//! `ta_engine::dataset_ops`'s own registry, so `series_downsample` resolves
+//! it exactly like a built-in `"mean"`/`"sum"`/etc.
+
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+use pyo3::exceptions::PyRuntimeError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use ta_engine::dataset_ops::{self, BucketAggregator, BucketState};
+
+struct OverlayIndicator {
+    meta: Py<PyDict>,
+    callable: Py<PyAny>,
+}
+
+static OVERLAY: OnceLock<RwLock<HashMap<String, OverlayIndicator>>> = OnceLock::new();
+
+fn overlay() -> &'static RwLock<HashMap<String, OverlayIndicator>> {
+    OVERLAY.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Registers `callable` under `id`, storing a copy of `meta` (the same
+/// dict shape `indicator_meta_to_pydict` produces) in the overlay. `id` is
+/// written into the stored copy's `"id"` entry, overriding whatever `meta`
+/// itself carries there, so the registration id is always authoritative.
+/// Registering an id that is already registered replaces it.
+#[pyfunction]
+pub(crate) fn register_indicator(
+    id: String,
+    meta: &Bound<'_, PyDict>,
+    callable: Bound<'_, PyAny>,
+) -> PyResult<()> {
+    let meta_copy = meta.copy()?;
+    meta_copy.set_item("id", &id)?;
+
+    let mut registry = overlay()
+        .write()
+        .map_err(|_| PyRuntimeError::new_err("failed to lock indicator overlay registry"))?;
+    registry.insert(
+        id,
+        OverlayIndicator {
+            meta: meta_copy.unbind(),
+            callable: callable.unbind(),
+        },
+    );
+    Ok(())
+}
+
+/// Removes `id` from the overlay registry. A no-op when `id` was never
+/// registered.
+#[pyfunction]
+pub(crate) fn deregister_indicator(id: String) -> PyResult<()> {
+    let mut registry = overlay()
+        .write()
+        .map_err(|_| PyRuntimeError::new_err("failed to lock indicator overlay registry"))?;
+    registry.remove(&id);
+    Ok(())
+}
+
+/// Returns a fresh copy of `id`'s overlay metadata dict, if registered.
+pub(crate) fn overlay_meta(py: Python<'_>, id: &str) -> PyResult<Option<Py<PyDict>>> {
+    let registry = overlay()
+        .read()
+        .map_err(|_| PyRuntimeError::new_err("failed to lock indicator overlay registry"))?;
+    Ok(registry.get(id).map(|entry| entry.meta.clone_ref(py)))
+}
+
+/// Returns every overlay entry's `(id, metadata dict)`, for `indicator_catalog`
+/// to merge alongside the built-in catalog.
+pub(crate) fn overlay_catalog(py: Python<'_>) -> PyResult<Vec<(String, Py<PyDict>)>> {
+    let registry = overlay()
+        .read()
+        .map_err(|_| PyRuntimeError::new_err("failed to lock indicator overlay registry"))?;
+    Ok(registry
+        .iter()
+        .map(|(id, entry)| (id.clone(), entry.meta.clone_ref(py)))
+        .collect())
+}
+
+/// Returns `id`'s registered callable, if any, for `execute_plan` to invoke
+/// in place of native kernel dispatch.
+pub(crate) fn overlay_callable(py: Python<'_>, id: &str) -> PyResult<Option<Py<PyAny>>> {
+    let registry = overlay()
+        .read()
+        .map_err(|_| PyRuntimeError::new_err("failed to lock indicator overlay registry"))?;
+    Ok(registry.get(id).map(|entry| entry.callable.clone_ref(py)))
+}
+
+/// Bridges a Python callable into `dataset_ops`'s native [`BucketAggregator`]
+/// trait: every value in a bucket is collected, then `finalize` hands the
+/// whole bucket to `callable` as a `list[float]` and takes its return value
+/// as the bucket's output.
+struct PyBucketAggregator {
+    callable: Py<PyAny>,
+}
+
+impl BucketAggregator for PyBucketAggregator {
+    fn accumulate(&self, state: &mut BucketState, value: f64, _weight: f64) {
+        state.values.push(value);
+    }
+
+    fn finalize(&self, state: &BucketState) -> f64 {
+        Python::with_gil(|py| {
+            let bucket = PyList::empty(py);
+            for &value in &state.values {
+                let _ = bucket.append(value);
+            }
+            match self.callable.bind(py).call1((bucket,)) {
+                Ok(result) => result.extract::<f64>().unwrap_or(f64::NAN),
+                Err(_) => f64::NAN,
+            }
+        })
+    }
+}
+
+/// Registers `callable` as a `series_downsample` aggregation named `name`:
+/// `callable` is called once per bucket with that bucket's values as a
+/// `list[float]` and must return a `float`. Registering a name already
+/// registered (built-in or not) replaces it.
+#[pyfunction]
+pub(crate) fn series_register_aggregator(name: String, callable: Bound<'_, PyAny>) -> PyResult<()> {
+    dataset_ops::register_aggregator(
+        name,
+        Box::new(PyBucketAggregator {
+            callable: callable.unbind(),
+        }),
+    );
+    Ok(())
+}
+
+/// Removes `name` from the aggregator registry, including a built-in. A
+/// no-op when `name` was never registered.
+#[pyfunction]
+pub(crate) fn series_deregister_aggregator(name: String) -> PyResult<()> {
+    dataset_ops::deregister_aggregator(&name);
+    Ok(())
+}
@@ -0,0 +1,220 @@
+use numpy::ndarray::Axis;
+use numpy::{IntoPyArray, PyReadonlyArray2};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use ta_engine::{OhlcvBatch, OhlcvInput};
+
+use crate::conversions::{
+    compute_indicator_response_to_pydict, parse_compute_indicator_request_dict,
+    parse_pipeline_spec_dict, params_dict_to_json,
+};
+use crate::errors::map_compute_error;
+
+/// Vectorized multi-symbol indicator compute. `open`/`high`/`low`/`close`
+/// (and optional `volume`) are column-major: one inner list per symbol, each
+/// the same length as `timestamps`. `params` values that are Python lists are
+/// broadcast one-per-symbol (and must have `len(columns)` entries); any other
+/// value is broadcast as a scalar to every symbol. A single-column batch
+/// (`n_symbols == 1`) computes the same result as the non-batch API.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn compute_indicator_batch(
+    py: Python<'_>,
+    indicator_id: String,
+    params: &Bound<'_, PyDict>,
+    timestamps: Vec<i64>,
+    open: Vec<Vec<f64>>,
+    high: Vec<Vec<f64>>,
+    low: Vec<Vec<f64>>,
+    close: Vec<Vec<f64>>,
+    volume: Option<Vec<Vec<f64>>>,
+    instance_id: Option<String>,
+) -> PyResult<PyObject> {
+    let batch = OhlcvBatch {
+        timestamps,
+        open,
+        high,
+        low,
+        close,
+        volume,
+    };
+    let params_json = params_dict_to_json(params)?;
+
+    let response =
+        ta_engine::compute_indicator_batch(&indicator_id, &params_json, &batch, instance_id)
+            .map_err(map_compute_error)?;
+
+    let out = PyDict::new(py);
+    out.set_item("indicator_id", response.indicator_id)?;
+    out.set_item("runtime_binding", response.runtime_binding)?;
+    out.set_item("instance_id", response.instance_id)?;
+
+    let outputs = PyList::empty(py);
+    for series in response.outputs {
+        let entry = PyDict::new(py);
+        entry.set_item("name", series.name)?;
+        entry.set_item("values", series.values)?;
+        outputs.append(entry)?;
+    }
+    out.set_item("outputs", outputs)?;
+    Ok(out.into_any().unbind())
+}
+
+/// NumPy-native counterpart of [`compute_indicator_batch`]: `open`/`high`/
+/// `low`/`close` (and optional `volume`) are 2D arrays shaped
+/// `(n_symbols, n_bars)` instead of a list of lists, read row by row via
+/// [`PyReadonlyArray2::as_array`] so both C- and Fortran-contiguous arrays
+/// work. `params` broadcasts the same way as the list-based API (a Python
+/// list is per-symbol, anything else is a scalar applied to every row).
+/// Each output series comes back as one `(n_symbols, n_bars)` array with
+/// `NaN` standing in for a symbol's warmup gaps, instead of a list of lists
+/// of `Option<f64>`.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (indicator_id, params, timestamps, open, high, low, close, volume=None, instance_id=None))]
+pub(crate) fn compute_indicator_batch_np<'py>(
+    py: Python<'py>,
+    indicator_id: String,
+    params: &Bound<'py, PyDict>,
+    timestamps: Vec<i64>,
+    open: PyReadonlyArray2<'py, f64>,
+    high: PyReadonlyArray2<'py, f64>,
+    low: PyReadonlyArray2<'py, f64>,
+    close: PyReadonlyArray2<'py, f64>,
+    volume: Option<PyReadonlyArray2<'py, f64>>,
+    instance_id: Option<String>,
+) -> PyResult<PyObject> {
+    let batch = OhlcvBatch {
+        timestamps,
+        open: rows_to_columns(&open),
+        high: rows_to_columns(&high),
+        low: rows_to_columns(&low),
+        close: rows_to_columns(&close),
+        volume: volume.as_ref().map(rows_to_columns),
+    };
+    let params_json = params_dict_to_json(params)?;
+
+    let response = py.allow_threads(|| {
+        ta_engine::compute_indicator_batch(&indicator_id, &params_json, &batch, instance_id)
+    })
+    .map_err(map_compute_error)?;
+
+    let n_symbols = batch.n_symbols();
+    let n_bars = batch.n_bars();
+
+    let out = PyDict::new(py);
+    out.set_item("indicator_id", response.indicator_id)?;
+    out.set_item("runtime_binding", response.runtime_binding)?;
+    out.set_item("instance_id", response.instance_id)?;
+
+    let outputs = PyList::empty(py);
+    for series in response.outputs {
+        let mut flat = Vec::with_capacity(n_symbols * n_bars);
+        for row in series.values {
+            flat.extend(row.into_iter().map(|v| v.unwrap_or(f64::NAN)));
+        }
+        let array = numpy::ndarray::Array2::from_shape_vec((n_symbols, n_bars), flat)
+            .expect("row lengths were validated by OhlcvBatch::validate");
+
+        let entry = PyDict::new(py);
+        entry.set_item("name", series.name)?;
+        entry.set_item("values", array.into_pyarray(py))?;
+        outputs.append(entry)?;
+    }
+    out.set_item("outputs", outputs)?;
+    Ok(out.into_any().unbind())
+}
+
+fn rows_to_columns(array: &PyReadonlyArray2<'_, f64>) -> Vec<Vec<f64>> {
+    array
+        .as_array()
+        .axis_iter(Axis(0))
+        .map(|row| row.to_vec())
+        .collect()
+}
+
+/// Runs a DAG of indicators against one shared OHLCV frame in a single Rust
+/// pass. `specs` is a list of `{instance_id, indicator_id, params}` dicts;
+/// any string param value shaped `"<instance_id>.<output_name>"`, where
+/// `<instance_id>` names another spec, is rewired to read that spec's
+/// output instead of a raw OHLCV field (see `ta_engine::compute_pipeline`).
+/// Returns a dict keyed by `instance_id`, each value the same
+/// `{indicator_id, runtime_binding, instance_id, outputs}` shape
+/// [`compute_indicator_batch`] returns per symbol.
+#[pyfunction]
+#[pyo3(signature = (specs, timestamps, open, high, low, close, volume=None))]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn compute_pipeline(
+    py: Python<'_>,
+    specs: &Bound<'_, PyList>,
+    timestamps: Vec<i64>,
+    open: Vec<f64>,
+    high: Vec<f64>,
+    low: Vec<f64>,
+    close: Vec<f64>,
+    volume: Option<Vec<f64>>,
+) -> PyResult<PyObject> {
+    let specs: Vec<_> = specs
+        .iter()
+        .map(|item| parse_pipeline_spec_dict(item.downcast::<PyDict>()?))
+        .collect::<PyResult<_>>()?;
+    let ohlcv = OhlcvInput {
+        timestamps,
+        open,
+        high,
+        low,
+        close,
+        volume,
+    };
+
+    let responses =
+        ta_engine::compute_pipeline(&specs, ohlcv).map_err(map_compute_error)?;
+
+    let out = PyDict::new(py);
+    for (instance_id, response) in responses {
+        out.set_item(instance_id, compute_indicator_response_to_pydict(py, &response)?)?;
+    }
+    Ok(out.into_any().unbind())
+}
+
+/// Computes many, possibly heterogeneous, indicator requests against one
+/// shared OHLCV frame, deduping requests that reduce to the same
+/// `(runtime_binding, normalized_params, named_inputs)` down to one
+/// computation (see `ta_engine::compute_batch`). `requests` is a list of
+/// `{indicator_id, params, instance_id=None, named_inputs={}}` dicts;
+/// results preserve request order.
+#[pyfunction]
+#[pyo3(signature = (requests, timestamps, open, high, low, close, volume=None))]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn compute_batch(
+    py: Python<'_>,
+    requests: &Bound<'_, PyList>,
+    timestamps: Vec<i64>,
+    open: Vec<f64>,
+    high: Vec<f64>,
+    low: Vec<f64>,
+    close: Vec<f64>,
+    volume: Option<Vec<f64>>,
+) -> PyResult<PyObject> {
+    let ohlcv = OhlcvInput {
+        timestamps,
+        open,
+        high,
+        low,
+        close,
+        volume,
+    };
+    let requests: Vec<_> = requests
+        .iter()
+        .map(|item| parse_compute_indicator_request_dict(item.downcast::<PyDict>()?, &ohlcv))
+        .collect::<PyResult<_>>()?;
+
+    let responses = ta_engine::compute_batch(requests).map_err(map_compute_error)?;
+
+    let out = PyList::empty(py);
+    for response in &responses {
+        out.append(compute_indicator_response_to_pydict(py, response)?)?;
+    }
+    Ok(out.into_any().unbind())
+}
+
@@ -0,0 +1,296 @@
+//! PyO3 classes over [`ta_engine::streaming`]'s O(1)-per-tick state, for
+//! callers that feed one bar at a time instead of recomputing a whole
+//! series on every update (e.g. an event-driven trading loop).
+
+use pyo3::prelude::*;
+
+fn validate_period(period: usize) -> PyResult<()> {
+    if period == 0 {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "period must be positive",
+        ));
+    }
+    Ok(())
+}
+
+/// Streaming EMA: `update` never warms up, it seeds from the first value.
+#[pyclass]
+pub(crate) struct EmaState {
+    inner: ta_engine::streaming::EmaState,
+}
+
+#[pymethods]
+impl EmaState {
+    #[new]
+    fn new(period: usize) -> PyResult<Self> {
+        validate_period(period)?;
+        Ok(Self {
+            inner: ta_engine::streaming::EmaState::new(period),
+        })
+    }
+
+    fn update(&mut self, value: f64) -> f64 {
+        self.inner.update(value)
+    }
+}
+
+/// Streaming Wilder RSI: `value()` is `None` until `period` diffs have
+/// been seeded.
+#[pyclass]
+pub(crate) struct RsiState {
+    inner: ta_engine::streaming::RsiState,
+    last: Option<f64>,
+}
+
+#[pymethods]
+impl RsiState {
+    #[new]
+    fn new(period: usize) -> PyResult<Self> {
+        validate_period(period)?;
+        Ok(Self {
+            inner: ta_engine::streaming::RsiState::new(period),
+            last: None,
+        })
+    }
+
+    fn update(&mut self, value: f64) -> Option<f64> {
+        self.last = self.inner.update(value);
+        self.last
+    }
+
+    fn value(&self) -> Option<f64> {
+        self.last
+    }
+
+    fn warmup_complete(&self) -> bool {
+        self.last.is_some()
+    }
+}
+
+/// Streaming Wilder ATR: `value()` is `None` until `period` true ranges
+/// have been seeded.
+#[pyclass]
+pub(crate) struct AtrState {
+    inner: ta_engine::streaming::AtrState,
+    last: Option<f64>,
+}
+
+#[pymethods]
+impl AtrState {
+    #[new]
+    fn new(period: usize) -> PyResult<Self> {
+        validate_period(period)?;
+        Ok(Self {
+            inner: ta_engine::streaming::AtrState::new(period),
+            last: None,
+        })
+    }
+
+    fn update(&mut self, high: f64, low: f64, close: f64) -> Option<f64> {
+        self.last = self.inner.update(high, low, close);
+        self.last
+    }
+
+    fn value(&self) -> Option<f64> {
+        self.last
+    }
+
+    fn warmup_complete(&self) -> bool {
+        self.last.is_some()
+    }
+}
+
+/// Streaming MACD: `update` returns the latest `(macd, signal, histogram)`
+/// triple in O(1), built from three internal `EmaState`s.
+#[pyclass]
+pub(crate) struct MacdState {
+    inner: ta_engine::streaming::MacdState,
+    last: (f64, f64, f64),
+}
+
+#[pymethods]
+impl MacdState {
+    #[new]
+    fn new(fast_period: usize, slow_period: usize, signal_period: usize) -> PyResult<Self> {
+        validate_period(fast_period)?;
+        validate_period(slow_period)?;
+        validate_period(signal_period)?;
+        Ok(Self {
+            inner: ta_engine::streaming::MacdState::new(fast_period, slow_period, signal_period),
+            last: (0.0, 0.0, 0.0),
+        })
+    }
+
+    fn update(&mut self, value: f64) -> (f64, f64, f64) {
+        self.last = self.inner.update(value);
+        self.last
+    }
+
+    fn value(&self) -> (f64, f64, f64) {
+        self.last
+    }
+}
+
+/// Streaming Wilder ADX: `value()` is `None` until the `+DI`/`-DI` smoothing
+/// and the ADX average itself have both seeded over `period` bars.
+#[pyclass]
+pub(crate) struct AdxState {
+    inner: ta_engine::streaming::AdxState,
+    last: Option<f64>,
+}
+
+#[pymethods]
+impl AdxState {
+    #[new]
+    fn new(period: usize) -> PyResult<Self> {
+        validate_period(period)?;
+        Ok(Self {
+            inner: ta_engine::streaming::AdxState::new(period),
+            last: None,
+        })
+    }
+
+    fn update(&mut self, high: f64, low: f64, close: f64) -> Option<f64> {
+        self.last = self.inner.update(high, low, close);
+        self.last
+    }
+
+    fn value(&self) -> Option<f64> {
+        self.last
+    }
+
+    fn warmup_complete(&self) -> bool {
+        self.last.is_some()
+    }
+}
+
+/// Streaming Parabolic SAR: `value()` is `None` until the first two bars
+/// have seeded a direction.
+#[pyclass]
+pub(crate) struct PsarState {
+    inner: ta_engine::streaming::PsarState,
+    last: Option<f64>,
+}
+
+#[pymethods]
+impl PsarState {
+    #[new]
+    #[pyo3(signature = (af_step=0.02, af_max=0.2))]
+    fn new(af_step: f64, af_max: f64) -> Self {
+        Self {
+            inner: ta_engine::streaming::PsarState::new(af_step, af_max),
+            last: None,
+        }
+    }
+
+    fn update(&mut self, high: f64, low: f64) -> Option<f64> {
+        self.last = self.inner.update(high, low);
+        self.last
+    }
+
+    fn value(&self) -> Option<f64> {
+        self.last
+    }
+
+    fn warmup_complete(&self) -> bool {
+        self.last.is_some()
+    }
+}
+
+/// Streaming Supertrend: `value()` is `None` until the internal ATR has
+/// warmed up; once it has, returns `(supertrend, direction)`.
+#[pyclass]
+pub(crate) struct SupertrendState {
+    inner: ta_engine::streaming::SupertrendState,
+    last: Option<(f64, f64)>,
+}
+
+#[pymethods]
+impl SupertrendState {
+    #[new]
+    fn new(period: usize, multiplier: f64) -> PyResult<Self> {
+        validate_period(period)?;
+        Ok(Self {
+            inner: ta_engine::streaming::SupertrendState::new(period, multiplier),
+            last: None,
+        })
+    }
+
+    fn update(&mut self, high: f64, low: f64, close: f64) -> Option<(f64, f64)> {
+        self.last = self.inner.update(high, low, close);
+        self.last
+    }
+
+    fn value(&self) -> Option<(f64, f64)> {
+        self.last
+    }
+
+    fn warmup_complete(&self) -> bool {
+        self.last.is_some()
+    }
+}
+
+/// Streaming rolling minimum over a monotonic deque: `value()` is `None`
+/// until `period` values have arrived.
+#[pyclass]
+pub(crate) struct RollingMinState {
+    inner: ta_engine::streaming::RollingMinState,
+    last: Option<f64>,
+}
+
+#[pymethods]
+impl RollingMinState {
+    #[new]
+    fn new(period: usize) -> PyResult<Self> {
+        validate_period(period)?;
+        Ok(Self {
+            inner: ta_engine::streaming::RollingMinState::new(period),
+            last: None,
+        })
+    }
+
+    fn update(&mut self, value: f64) -> Option<f64> {
+        self.last = self.inner.update(value);
+        self.last
+    }
+
+    fn value(&self) -> Option<f64> {
+        self.last
+    }
+
+    fn warmup_complete(&self) -> bool {
+        self.inner.warmup_complete()
+    }
+}
+
+/// Streaming rolling maximum, the mirror image of [`RollingMinState`].
+#[pyclass]
+pub(crate) struct RollingMaxState {
+    inner: ta_engine::streaming::RollingMaxState,
+    last: Option<f64>,
+}
+
+#[pymethods]
+impl RollingMaxState {
+    #[new]
+    fn new(period: usize) -> PyResult<Self> {
+        validate_period(period)?;
+        Ok(Self {
+            inner: ta_engine::streaming::RollingMaxState::new(period),
+            last: None,
+        })
+    }
+
+    fn update(&mut self, value: f64) -> Option<f64> {
+        self.last = self.inner.update(value);
+        self.last
+    }
+
+    fn value(&self) -> Option<f64> {
+        self.last
+    }
+
+    fn warmup_complete(&self) -> bool {
+        self.inner.warmup_complete()
+    }
+}
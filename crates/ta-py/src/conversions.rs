@@ -1,11 +1,16 @@
 use std::collections::BTreeMap;
 
+use numpy::{IntoPyArray, PyReadonlyArray1};
 use pyo3::prelude::*;
-use pyo3::types::{PyAny, PyDict, PyList};
-use ta_engine::contracts::RustExecutionRequest;
+use pyo3::types::{PyAny, PyBool, PyBytes, PyDict, PyFloat, PyInt, PyList, PyString, PyTuple};
+use serde_json::Value;
+use ta_engine::contracts::{
+    RustExecutionGraph, RustExecutionPartition, RustExecutionPayload, RustExecutionRequest,
+};
 use ta_engine::incremental::backend::KernelStepRequest;
 use ta_engine::incremental::contracts::IncrementalValue;
 use ta_engine::incremental::kernel_registry::KernelId;
+use ta_engine::{ComputeIndicatorRequest, ComputeIndicatorResponse, OhlcvInput, PipelineIndicatorSpec};
 
 pub(crate) type IchimokuTuple = (Vec<f64>, Vec<f64>, Vec<f64>, Vec<f64>, Vec<f64>);
 
@@ -76,6 +81,182 @@ pub(crate) fn parse_contract_requests(
     Ok(out)
 }
 
+/// Parses one `RustExecutionPayload`-shaped dict (`dataset_id`, `partition`,
+/// `graph`, `requests`), the same shape `execute_plan_payload` and
+/// `execute_plan_batch` both accept.
+pub(crate) fn parse_execution_payload_dict(
+    payload: &Bound<'_, PyDict>,
+) -> PyResult<RustExecutionPayload> {
+    let dataset_id: u64 = payload
+        .get_item("dataset_id")?
+        .ok_or_else(|| pyo3::exceptions::PyKeyError::new_err("missing dataset_id"))?
+        .extract()?;
+    let partition = payload
+        .get_item("partition")?
+        .ok_or_else(|| pyo3::exceptions::PyKeyError::new_err("missing partition"))?
+        .downcast_into::<PyDict>()?;
+    let symbol: String = partition
+        .get_item("symbol")?
+        .ok_or_else(|| pyo3::exceptions::PyKeyError::new_err("missing partition.symbol"))?
+        .extract()?;
+    let timeframe: String = partition
+        .get_item("timeframe")?
+        .ok_or_else(|| pyo3::exceptions::PyKeyError::new_err("missing partition.timeframe"))?
+        .extract()?;
+    let source: String = partition
+        .get_item("source")?
+        .ok_or_else(|| pyo3::exceptions::PyKeyError::new_err("missing partition.source"))?
+        .extract()?;
+    let requests = payload
+        .get_item("requests")?
+        .ok_or_else(|| pyo3::exceptions::PyKeyError::new_err("missing requests"))?
+        .downcast_into::<PyList>()?;
+
+    let graph = payload
+        .get_item("graph")?
+        .ok_or_else(|| pyo3::exceptions::PyKeyError::new_err("missing graph"))?
+        .downcast_into::<PyDict>()?;
+    let root_id: u32 = graph
+        .get_item("root_id")?
+        .ok_or_else(|| pyo3::exceptions::PyKeyError::new_err("missing graph.root_id"))?
+        .extract()?;
+    let node_order: Vec<u32> = graph
+        .get_item("node_order")?
+        .ok_or_else(|| pyo3::exceptions::PyKeyError::new_err("missing graph.node_order"))?
+        .extract()?;
+    let nodes_dict = graph
+        .get_item("nodes")?
+        .ok_or_else(|| pyo3::exceptions::PyKeyError::new_err("missing graph.nodes"))?
+        .downcast_into::<PyDict>()?;
+    let edges_dict = graph
+        .get_item("edges")?
+        .ok_or_else(|| pyo3::exceptions::PyKeyError::new_err("missing graph.edges"))?
+        .downcast_into::<PyDict>()?;
+
+    let mut nodes: BTreeMap<u32, BTreeMap<String, String>> = BTreeMap::new();
+    for (k, v) in nodes_dict.iter() {
+        let node_id = extract_node_id(&k)?;
+        let details = v.downcast::<PyDict>()?;
+        let mut map = BTreeMap::new();
+        for (dk, dv) in details.iter() {
+            map.insert(dk.extract::<String>()?, extract_scalar_string(&dv)?);
+        }
+        nodes.insert(node_id, map);
+    }
+
+    let mut edges = BTreeMap::new();
+    for (k, v) in edges_dict.iter() {
+        let node_id = extract_node_id(&k)?;
+        let child_ids: Vec<u32> = v.extract()?;
+        edges.insert(node_id, child_ids);
+    }
+
+    Ok(RustExecutionPayload {
+        dataset_id,
+        partition: RustExecutionPartition {
+            symbol,
+            timeframe,
+            source,
+        },
+        partitions: Vec::new(),
+        graph: RustExecutionGraph {
+            root_id,
+            node_order,
+            nodes,
+            edges,
+        },
+        requests: parse_contract_requests(&requests)?,
+    })
+}
+
+/// Parses one `{instance_id, indicator_id, params}` dict, the shape
+/// `compute_pipeline`'s `specs` list takes.
+pub(crate) fn parse_pipeline_spec_dict(
+    spec: &Bound<'_, PyDict>,
+) -> PyResult<PipelineIndicatorSpec> {
+    let instance_id: String = spec
+        .get_item("instance_id")?
+        .ok_or_else(|| pyo3::exceptions::PyKeyError::new_err("missing instance_id"))?
+        .extract()?;
+    let indicator_id: String = spec
+        .get_item("indicator_id")?
+        .ok_or_else(|| pyo3::exceptions::PyKeyError::new_err("missing indicator_id"))?
+        .extract()?;
+    let params = match spec.get_item("params")? {
+        Some(params) => params_dict_to_json(params.downcast::<PyDict>()?)?,
+        None => Value::Object(serde_json::Map::new()),
+    };
+    Ok(PipelineIndicatorSpec {
+        instance_id,
+        indicator_id,
+        params,
+    })
+}
+
+/// Parses one `{indicator_id, params, instance_id=None, named_inputs={}}`
+/// dict, the shape `compute_batch`'s `requests` list takes, pairing it with
+/// `ohlcv` shared across the whole batch.
+pub(crate) fn parse_compute_indicator_request_dict(
+    request: &Bound<'_, PyDict>,
+    ohlcv: &OhlcvInput,
+) -> PyResult<ComputeIndicatorRequest> {
+    let indicator_id: String = request
+        .get_item("indicator_id")?
+        .ok_or_else(|| pyo3::exceptions::PyKeyError::new_err("missing indicator_id"))?
+        .extract()?;
+    let params = match request.get_item("params")? {
+        Some(params) => params_dict_to_json(params.downcast::<PyDict>()?)?,
+        None => Value::Object(serde_json::Map::new()),
+    };
+    let instance_id: Option<String> = match request.get_item("instance_id")? {
+        Some(value) if !value.is_none() => Some(value.extract()?),
+        _ => None,
+    };
+    let named_inputs = match request.get_item("named_inputs")? {
+        Some(value) => {
+            let dict = value.downcast::<PyDict>()?;
+            let mut out = BTreeMap::new();
+            for (k, v) in dict.iter() {
+                out.insert(k.extract::<String>()?, v.extract::<Vec<f64>>()?);
+            }
+            out
+        }
+        None => BTreeMap::new(),
+    };
+    Ok(ComputeIndicatorRequest {
+        indicator_id,
+        params,
+        ohlcv: ohlcv.clone(),
+        instance_id,
+        named_inputs,
+    })
+}
+
+/// Converts one `ComputeIndicatorResponse` into the same
+/// `{indicator_id, runtime_binding, instance_id, outputs: [{name, values}]}`
+/// shape [`crate::api::runtime::compute_indicator_batch`] returns, minus the
+/// per-symbol dimension this isn't vectorized over.
+pub(crate) fn compute_indicator_response_to_pydict(
+    py: Python<'_>,
+    response: &ComputeIndicatorResponse,
+) -> PyResult<PyObject> {
+    let out = PyDict::new(py);
+    out.set_item("indicator_id", &response.indicator_id)?;
+    out.set_item("runtime_binding", &response.runtime_binding)?;
+    out.set_item("instance_id", &response.instance_id)?;
+
+    let outputs = PyList::empty(py);
+    for series in &response.outputs {
+        let entry = PyDict::new(py);
+        entry.set_item("name", &series.name)?;
+        let values: Vec<f64> = series.values.iter().map(|v| v.unwrap_or(f64::NAN)).collect();
+        entry.set_item("values", values)?;
+        outputs.append(entry)?;
+    }
+    out.set_item("outputs", outputs)?;
+    Ok(out.into_any().unbind())
+}
+
 pub(crate) fn parse_events(
     events: &Bound<'_, PyList>,
 ) -> PyResult<Vec<BTreeMap<String, IncrementalValue>>> {
@@ -87,6 +268,49 @@ pub(crate) fn parse_events(
     Ok(out)
 }
 
+/// Builds one event (tick) per row straight from NumPy OHLCV column
+/// arrays, the buffer-protocol counterpart of calling [`parse_tick`] once
+/// per row of a Python list of dicts. Reads each column through
+/// [`PyReadonlyArray1::as_array`], which honors whatever strides the caller
+/// passed (a `.as_slice()` fast path is used when a column happens to be
+/// C-contiguous) so a sliced/transposed view doesn't need to be copied
+/// into a fresh contiguous buffer before this call.
+pub(crate) fn events_from_ohlcv_arrays(
+    open: PyReadonlyArray1<'_, f64>,
+    high: PyReadonlyArray1<'_, f64>,
+    low: PyReadonlyArray1<'_, f64>,
+    close: PyReadonlyArray1<'_, f64>,
+    volume: PyReadonlyArray1<'_, f64>,
+) -> PyResult<Vec<BTreeMap<String, IncrementalValue>>> {
+    let rows = close.len()?;
+    for (name, arr) in [("open", &open), ("high", &high), ("low", &low), ("volume", &volume)] {
+        if arr.len()? != rows {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "column '{name}' has length {} but 'close' has length {rows}",
+                arr.len()?
+            )));
+        }
+    }
+
+    let open = open.as_array();
+    let high = high.as_array();
+    let low = low.as_array();
+    let close = close.as_array();
+    let volume = volume.as_array();
+
+    let mut out = Vec::with_capacity(rows);
+    for i in 0..rows {
+        let mut tick = BTreeMap::new();
+        tick.insert("open".to_string(), IncrementalValue::Number(open[i]));
+        tick.insert("high".to_string(), IncrementalValue::Number(high[i]));
+        tick.insert("low".to_string(), IncrementalValue::Number(low[i]));
+        tick.insert("close".to_string(), IncrementalValue::Number(close[i]));
+        tick.insert("volume".to_string(), IncrementalValue::Number(volume[i]));
+        out.push(tick);
+    }
+    Ok(out)
+}
+
 pub(crate) fn parse_tick(tick: &Bound<'_, PyDict>) -> PyResult<BTreeMap<String, IncrementalValue>> {
     let mut out = BTreeMap::new();
     for (k, v) in tick.iter() {
@@ -97,6 +321,8 @@ pub(crate) fn parse_tick(tick: &Bound<'_, PyDict>) -> PyResult<BTreeMap<String,
             IncrementalValue::Bool(b)
         } else if let Ok(s) = v.extract::<String>() {
             IncrementalValue::Text(s)
+        } else if let Ok(fields) = v.extract::<Vec<f64>>() {
+            IncrementalValue::Fields(fields)
         } else {
             IncrementalValue::Null
         };
@@ -135,6 +361,87 @@ pub(crate) fn extract_scalar_string(value: &Bound<'_, PyAny>) -> PyResult<String
     Ok(format!("{value:?}"))
 }
 
+/// Converts a Python indicator-params dict into a `serde_json::Value` object,
+/// the shape `ta_engine::runtime` expects. List values pass through as JSON
+/// arrays so per-symbol batch params (one entry per symbol) round-trip.
+pub(crate) fn params_dict_to_json(params: &Bound<'_, PyDict>) -> PyResult<Value> {
+    let mut out = serde_json::Map::with_capacity(params.len());
+    for (k, v) in params.iter() {
+        let key: String = k.extract()?;
+        out.insert(key, pyany_to_json_value(&v)?);
+    }
+    Ok(Value::Object(out))
+}
+
+fn pyany_to_json_value(value: &Bound<'_, PyAny>) -> PyResult<Value> {
+    if value.is_none() {
+        return Ok(Value::Null);
+    }
+    if let Ok(v) = value.extract::<bool>() {
+        return Ok(Value::Bool(v));
+    }
+    if let Ok(v) = value.extract::<i64>() {
+        return Ok(Value::from(v));
+    }
+    if let Ok(v) = value.extract::<f64>() {
+        return Ok(Value::from(v));
+    }
+    if let Ok(v) = value.extract::<String>() {
+        return Ok(Value::String(v));
+    }
+    if let Ok(list) = value.downcast::<PyList>() {
+        let mut items = Vec::with_capacity(list.len());
+        for item in list.iter() {
+            items.push(pyany_to_json_value(&item)?);
+        }
+        return Ok(Value::Array(items));
+    }
+    Err(pyo3::exceptions::PyValueError::new_err(format!(
+        "unsupported param value type: {value:?}"
+    )))
+}
+
+/// Converts an arbitrary `serde_json::Value` into its Python equivalent --
+/// the inverse of [`pyany_to_json_value`]. Used to build a snapshot's
+/// inspection dict from `snapshot_codec::encode_snapshot_to_json`.
+fn json_value_to_py<'py>(py: Python<'py>, value: &Value) -> PyResult<Bound<'py, PyAny>> {
+    Ok(match value {
+        Value::Null => py.None().into_bound(py),
+        Value::Bool(b) => PyBool::new(py, *b).to_owned().into_any(),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => PyInt::new(py, i).into_any(),
+            None => PyFloat::new(py, n.as_f64().unwrap_or(f64::NAN)).into_any(),
+        },
+        Value::String(s) => PyString::new(py, s).into_any(),
+        Value::Array(items) => {
+            let list = PyList::empty(py);
+            for item in items {
+                list.append(json_value_to_py(py, item)?)?;
+            }
+            list.into_any()
+        }
+        Value::Object(_) => json_value_to_pydict(py, value)?.into_any(),
+    })
+}
+
+/// Converts a JSON object into a `PyDict`. Errors if `value` isn't a JSON
+/// object (this is the counterpart of `params_dict_to_json`, not a general
+/// `json_value_to_py` entry point -- callers that may see a bare scalar or
+/// array should call `json_value_to_py` directly).
+pub(crate) fn json_value_to_pydict<'py>(
+    py: Python<'py>,
+    value: &Value,
+) -> PyResult<Bound<'py, PyDict>> {
+    let object = value
+        .as_object()
+        .ok_or_else(|| pyo3::exceptions::PyTypeError::new_err("expected a JSON object"))?;
+    let dict = PyDict::new(py);
+    for (k, v) in object {
+        dict.set_item(k, json_value_to_py(py, v)?)?;
+    }
+    Ok(dict)
+}
+
 pub(crate) fn incremental_map_to_pydict(
     py: Python<'_>,
     values: &BTreeMap<u32, IncrementalValue>,
@@ -145,24 +452,39 @@ pub(crate) fn incremental_map_to_pydict(
             IncrementalValue::Number(n) => d.set_item(k, n)?,
             IncrementalValue::Bool(b) => d.set_item(k, b)?,
             IncrementalValue::Text(s) => d.set_item(k, s)?,
+            IncrementalValue::Fields(f) => d.set_item(k, PyTuple::new(py, f)?)?,
+            IncrementalValue::Bytes(b) => d.set_item(k, PyBytes::new(py, b))?,
             IncrementalValue::Null => d.set_item(k, py.None())?,
         }
     }
     Ok(d.into_any().unbind())
 }
 
+/// Converts one node's tick-by-tick history into a Python value. A series
+/// made up entirely of `IncrementalValue::Number` (the common case for a
+/// numeric kernel like RSI or ATR) comes back as a `PyArray1<f64>` instead
+/// of a list, so callers can hand it straight to NumPy/pandas without a
+/// per-element unboxing pass. Any other series (mixed types, or a kernel
+/// that emits `Bool`/`Text`/`Fields`/`Bytes`/`Null`) falls back to the
+/// existing element-by-element `PyList`.
 pub(crate) fn incremental_series_map_to_pydict(
     py: Python<'_>,
     values: &BTreeMap<u32, Vec<IncrementalValue>>,
 ) -> PyResult<PyObject> {
     let d = PyDict::new(py);
     for (k, series) in values {
+        if let Some(numbers) = all_numbers(series) {
+            d.set_item(k, numbers.into_pyarray(py))?;
+            continue;
+        }
         let py_list = PyList::empty(py);
         for v in series {
             match v {
                 IncrementalValue::Number(n) => py_list.append(*n)?,
                 IncrementalValue::Bool(b) => py_list.append(*b)?,
                 IncrementalValue::Text(s) => py_list.append(s)?,
+                IncrementalValue::Fields(f) => py_list.append(PyTuple::new(py, f)?)?,
+                IncrementalValue::Bytes(b) => py_list.append(PyBytes::new(py, b))?,
                 IncrementalValue::Null => py_list.append(py.None())?,
             }
         }
@@ -171,6 +493,18 @@ pub(crate) fn incremental_series_map_to_pydict(
     Ok(d.into_any().unbind())
 }
 
+/// Returns the series as a plain `Vec<f64>` when every element is
+/// `IncrementalValue::Number`, `None` otherwise.
+fn all_numbers(series: &[IncrementalValue]) -> Option<Vec<f64>> {
+    series
+        .iter()
+        .map(|v| match v {
+            IncrementalValue::Number(n) => Some(*n),
+            _ => None,
+        })
+        .collect()
+}
+
 pub(crate) fn indicator_meta_to_pydict(
     py: Python<'_>,
     meta: &ta_engine::metadata::IndicatorMeta,
@@ -202,13 +536,38 @@ pub(crate) fn indicator_meta_to_pydict(
             ta_engine::metadata::IndicatorParamKind::Float => "float",
             ta_engine::metadata::IndicatorParamKind::Boolean => "bool",
             ta_engine::metadata::IndicatorParamKind::String => "string",
+            ta_engine::metadata::IndicatorParamKind::MaType => "string",
         };
         p.set_item("kind", kind)?;
         p.set_item("required", param.required)?;
-        p.set_item("default", param.default)?;
+        match param.default {
+            Some(ta_engine::metadata::IndicatorParamDefault::Integer(n)) => {
+                p.set_item("default", n)?
+            }
+            Some(ta_engine::metadata::IndicatorParamDefault::Float(n)) => {
+                p.set_item("default", n)?
+            }
+            Some(ta_engine::metadata::IndicatorParamDefault::Boolean(flag)) => {
+                p.set_item("default", flag)?
+            }
+            Some(ta_engine::metadata::IndicatorParamDefault::String(s)) => {
+                p.set_item("default", s)?
+            }
+            None => p.set_item("default", py.None())?,
+        }
         p.set_item("description", param.description)?;
         p.set_item("min", param.min)?;
         p.set_item("max", param.max)?;
+        match param.allowed {
+            Some(choices) => {
+                let allowed = PyList::empty(py);
+                for choice in choices {
+                    allowed.append(choice)?;
+                }
+                p.set_item("allowed", allowed)?
+            }
+            None => p.set_item("allowed", py.None())?,
+        }
         params.append(p)?;
     }
     d.set_item("params", params)?;
@@ -2,6 +2,33 @@ use pyo3::PyErr;
 use ta_engine::dataset::DatasetRegistryError;
 use ta_engine::dataset_ops::DatasetOpsError;
 use ta_engine::incremental::backend::ExecutePlanError;
+use ta_engine::{ComputeRuntimeError, ParamErrorKind};
+
+/// Maps a `ComputeRuntimeError` to a `PyValueError`, folding in the
+/// structured `param` detail (when present) so a caller parsing the
+/// message can tell which parameter failed and why, instead of just
+/// getting `code: message`.
+pub(crate) fn map_compute_error(err: ComputeRuntimeError) -> PyErr {
+    let Some(param) = err.param else {
+        return pyo3::exceptions::PyValueError::new_err(format!("{}: {}", err.code, err.message));
+    };
+
+    let kind = match param.kind {
+        ParamErrorKind::Missing => "missing",
+        ParamErrorKind::WrongType => "wrong_type",
+        ParamErrorKind::OutOfRange => "out_of_range",
+        ParamErrorKind::CrossFieldConstraint => "cross_field_constraint",
+    };
+    let mut detail = format!("{}: {} (param={}, kind={kind}", err.code, err.message, param.param_name);
+    if let Some(expected) = param.expected {
+        detail.push_str(&format!(", expected={expected}"));
+    }
+    if let Some(got) = param.got {
+        detail.push_str(&format!(", got={got}"));
+    }
+    detail.push(')');
+    pyo3::exceptions::PyValueError::new_err(detail)
+}
 
 pub(crate) fn map_execute_plan_error(err: ExecutePlanError) -> PyErr {
     match err {
@@ -26,6 +53,11 @@ pub(crate) fn map_execute_plan_error(err: ExecutePlanError) -> PyErr {
         ExecutePlanError::UnsupportedKernelId(kernel_id) => {
             pyo3::exceptions::PyValueError::new_err(format!("unsupported kernel_id in payload: {kernel_id}"))
         }
+        ExecutePlanError::QuotaExceeded { kind, limit } => {
+            pyo3::exceptions::PyRuntimeError::new_err(format!(
+                "backend quota exceeded: {kind:?} limit of {limit} reached"
+            ))
+        }
     }
 }
 
@@ -66,5 +98,11 @@ pub(crate) fn map_dataset_error(err: DatasetRegistryError) -> PyErr {
         DatasetRegistryError::EmptyField { field } => {
             pyo3::exceptions::PyValueError::new_err(format!("empty field not allowed: {field}"))
         }
+        DatasetRegistryError::InvalidPartitionFilter { message } => {
+            pyo3::exceptions::PyValueError::new_err(message)
+        }
+        DatasetRegistryError::MmapLayout { message } => {
+            pyo3::exceptions::PyValueError::new_err(message)
+        }
     }
 }
@@ -12,27 +12,39 @@ fn ta_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(api::dataset::dataset_drop, m)?)?;
     m.add_function(wrap_pyfunction!(api::dataset::dataset_append_ohlcv, m)?)?;
     m.add_function(wrap_pyfunction!(api::dataset::dataset_append_series, m)?)?;
+    m.add_function(wrap_pyfunction!(api::dataset::dataset_load_mmap, m)?)?;
     m.add_function(wrap_pyfunction!(api::dataset::dataset_info, m)?)?;
     m.add_function(wrap_pyfunction!(api::dataset::series_downsample, m)?)?;
+    m.add_function(wrap_pyfunction!(api::dataset::series_downsample_ohlcv, m)?)?;
+    m.add_function(wrap_pyfunction!(api::dataset::series_resample_ohlcv, m)?)?;
+    m.add_function(wrap_pyfunction!(api::dataset::series_downsample_interval, m)?)?;
     m.add_function(wrap_pyfunction!(api::dataset::series_upsample_ffill, m)?)?;
     m.add_function(wrap_pyfunction!(api::dataset::series_sync_timeframe, m)?)?;
     m.add_function(wrap_pyfunction!(api::dataset::indicator_catalog, m)?)?;
     m.add_function(wrap_pyfunction!(api::dataset::indicator_meta, m)?)?;
+    m.add_function(wrap_pyfunction!(api::dataset::dataset_list_partitions, m)?)?;
+    m.add_function(wrap_pyfunction!(api::registry::register_indicator, m)?)?;
+    m.add_function(wrap_pyfunction!(api::registry::deregister_indicator, m)?)?;
+    m.add_function(wrap_pyfunction!(api::registry::series_register_aggregator, m)?)?;
+    m.add_function(wrap_pyfunction!(api::registry::series_deregister_aggregator, m)?)?;
 
     m.add_function(wrap_pyfunction!(api::indicators::rolling_sum, m)?)?;
     m.add_function(wrap_pyfunction!(api::indicators::rolling_mean, m)?)?;
     m.add_function(wrap_pyfunction!(api::indicators::rolling_std, m)?)?;
     m.add_function(wrap_pyfunction!(api::indicators::rolling_min, m)?)?;
     m.add_function(wrap_pyfunction!(api::indicators::rolling_max, m)?)?;
+    m.add_function(wrap_pyfunction!(api::indicators::simd_enabled, m)?)?;
     m.add_function(wrap_pyfunction!(api::indicators::rolling_ema, m)?)?;
     m.add_function(wrap_pyfunction!(api::indicators::rolling_rma, m)?)?;
     m.add_function(wrap_pyfunction!(api::indicators::rolling_wma, m)?)?;
     m.add_function(wrap_pyfunction!(api::indicators::rsi, m)?)?;
+    m.add_function(wrap_pyfunction!(api::indicators::rsi_with_policy, m)?)?;
     m.add_function(wrap_pyfunction!(api::indicators::roc, m)?)?;
     m.add_function(wrap_pyfunction!(api::indicators::cmo, m)?)?;
     m.add_function(wrap_pyfunction!(api::indicators::ao, m)?)?;
     m.add_function(wrap_pyfunction!(api::indicators::coppock, m)?)?;
     m.add_function(wrap_pyfunction!(api::indicators::mfi, m)?)?;
+    m.add_function(wrap_pyfunction!(api::indicators::mfi_with_policy, m)?)?;
     m.add_function(wrap_pyfunction!(api::indicators::vortex, m)?)?;
     m.add_function(wrap_pyfunction!(api::indicators::atr, m)?)?;
     m.add_function(wrap_pyfunction!(api::indicators::atr_from_tr, m)?)?;
@@ -48,7 +60,9 @@ fn ta_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(api::indicators::adx, m)?)?;
     m.add_function(wrap_pyfunction!(api::indicators::swing_points_raw, m)?)?;
     m.add_function(wrap_pyfunction!(api::indicators::cci, m)?)?;
+    m.add_function(wrap_pyfunction!(api::indicators::cci_with_policy, m)?)?;
     m.add_function(wrap_pyfunction!(api::indicators::williams_r, m)?)?;
+    m.add_function(wrap_pyfunction!(api::indicators::williams_r_with_policy, m)?)?;
     m.add_function(wrap_pyfunction!(api::indicators::elder_ray, m)?)?;
     m.add_function(wrap_pyfunction!(api::indicators::crossup, m)?)?;
     m.add_function(wrap_pyfunction!(api::indicators::crossdown, m)?)?;
@@ -66,12 +80,58 @@ fn ta_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(api::indicators::klinger_vf, m)?)?;
     m.add_function(wrap_pyfunction!(api::indicators::klinger, m)?)?;
     m.add_function(wrap_pyfunction!(api::indicators::cmf, m)?)?;
+    m.add_function(wrap_pyfunction!(api::indicators::vwap_anchored, m)?)?;
+
+    m.add_function(wrap_pyfunction!(api::indicators_np::rolling_sum_np, m)?)?;
+    m.add_function(wrap_pyfunction!(api::indicators_np::rolling_mean_np, m)?)?;
+    m.add_function(wrap_pyfunction!(api::indicators_np::rolling_std_np, m)?)?;
+    m.add_function(wrap_pyfunction!(api::indicators_np::rolling_min_np, m)?)?;
+    m.add_function(wrap_pyfunction!(api::indicators_np::rolling_max_np, m)?)?;
+    m.add_function(wrap_pyfunction!(api::indicators_np::rsi_np, m)?)?;
+    m.add_function(wrap_pyfunction!(api::indicators_np::atr_np, m)?)?;
+    m.add_function(wrap_pyfunction!(api::indicators_np::cci_np, m)?)?;
+    m.add_function(wrap_pyfunction!(api::indicators_np::williams_r_np, m)?)?;
+    m.add_function(wrap_pyfunction!(api::indicators_np::stochastic_kd_np, m)?)?;
+    m.add_function(wrap_pyfunction!(api::indicators_np::macd_np, m)?)?;
+    m.add_function(wrap_pyfunction!(api::indicators_np::bbands_np, m)?)?;
+    m.add_function(wrap_pyfunction!(api::indicators_np::adx_np, m)?)?;
+    m.add_function(wrap_pyfunction!(api::indicators_np::ichimoku_np, m)?)?;
 
     m.add_function(wrap_pyfunction!(api::execution::incremental_initialize, m)?)?;
+    m.add_function(wrap_pyfunction!(api::execution::incremental_build_request, m)?)?;
     m.add_function(wrap_pyfunction!(api::execution::incremental_step, m)?)?;
     m.add_function(wrap_pyfunction!(api::execution::incremental_snapshot, m)?)?;
+    m.add_function(wrap_pyfunction!(api::execution::incremental_snapshot_dump, m)?)?;
+    m.add_function(wrap_pyfunction!(api::execution::incremental_snapshot_load, m)?)?;
+    m.add_function(wrap_pyfunction!(api::execution::incremental_snapshot_inspect, m)?)?;
+    m.add_function(wrap_pyfunction!(api::execution::incremental_snapshot_bytes, m)?)?;
+    m.add_function(wrap_pyfunction!(api::execution::incremental_restore_bytes, m)?)?;
+    m.add_function(wrap_pyfunction!(api::execution::incremental_enable_profiling, m)?)?;
+    m.add_function(wrap_pyfunction!(api::execution::incremental_profile, m)?)?;
     m.add_function(wrap_pyfunction!(api::execution::incremental_replay, m)?)?;
+    m.add_function(wrap_pyfunction!(api::execution::incremental_replay_np, m)?)?;
     m.add_function(wrap_pyfunction!(api::execution::execute_plan, m)?)?;
     m.add_function(wrap_pyfunction!(api::execution::execute_plan_payload, m)?)?;
+    m.add_function(wrap_pyfunction!(api::execution::execute_plan_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(api::execution::execute_plan_prefix, m)?)?;
+    m.add_function(wrap_pyfunction!(api::execution::execute_plan_requests_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(api::dataframe::execute_plan_on_dataframe, m)?)?;
+
+    m.add_function(wrap_pyfunction!(api::runtime::compute_indicator_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(api::runtime::compute_indicator_batch_np, m)?)?;
+    m.add_function(wrap_pyfunction!(api::runtime::compute_pipeline, m)?)?;
+    m.add_function(wrap_pyfunction!(api::runtime::compute_batch, m)?)?;
+
+    m.add_class::<api::streaming::EmaState>()?;
+    m.add_class::<api::streaming::RsiState>()?;
+    m.add_class::<api::streaming::AtrState>()?;
+    m.add_class::<api::streaming::MacdState>()?;
+    m.add_class::<api::streaming::AdxState>()?;
+    m.add_class::<api::streaming::PsarState>()?;
+    m.add_class::<api::streaming::SupertrendState>()?;
+    m.add_class::<api::streaming::RollingMinState>()?;
+    m.add_class::<api::streaming::RollingMaxState>()?;
+
+    m.add_class::<api::pipeline::Pipeline>()?;
     Ok(())
 }
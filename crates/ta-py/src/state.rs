@@ -1,16 +1,38 @@
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::{Mutex, OnceLock};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
 
-use pyo3::exceptions::PyRuntimeError;
-use pyo3::prelude::PyResult;
+use pyo3::exceptions::{PyKeyError, PyRuntimeError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
 use ta_engine::incremental::backend::IncrementalBackend;
 use ta_engine::incremental::contracts::RuntimeSnapshot;
 
+use crate::conversions::json_value_to_pydict;
+
 static BACKEND_ID: AtomicU64 = AtomicU64::new(1);
 static SNAPSHOT_ID: AtomicU64 = AtomicU64::new(1);
-static BACKENDS: OnceLock<Mutex<HashMap<u64, IncrementalBackend>>> = OnceLock::new();
-static SNAPSHOTS: OnceLock<Mutex<HashMap<u64, RuntimeSnapshot>>> = OnceLock::new();
+
+/// Each backend gets its own `Mutex` behind the registry's `Arc`, so taking a
+/// handle only needs to hold the registry lock long enough to clone the
+/// `Arc` out. The registry lock is never held across a kernel call.
+type BackendHandle = Arc<Mutex<IncrementalBackend>>;
+
+/// A stored snapshot plus its lazily-built Python dict representation.
+/// `RuntimeSnapshot` is write-once (nothing ever mutates a stored snapshot
+/// in place), so the cached `Py<PyDict>` never needs invalidating -- it's
+/// just dropped along with the entry when the snapshot id is removed.
+struct SnapshotEntry {
+    snapshot: RuntimeSnapshot,
+    pydict_cache: OnceLock<Py<PyDict>>,
+}
+
+/// Both registries use `RwLock` rather than `Mutex`: `incremental_snapshot`
+/// /`dataset_info`-style read-only lookups (snapshot fetch, inspection) can
+/// then run concurrently across threads, with a write guard reserved for
+/// `incremental_initialize`/`incremental_step`-driven inserts.
+static BACKENDS: OnceLock<RwLock<HashMap<u64, BackendHandle>>> = OnceLock::new();
+static SNAPSHOTS: OnceLock<RwLock<HashMap<u64, SnapshotEntry>>> = OnceLock::new();
 
 pub(crate) fn next_backend_id() -> u64 {
     BACKEND_ID.fetch_add(1, Ordering::SeqCst)
@@ -20,32 +42,93 @@ pub(crate) fn next_snapshot_id() -> u64 {
     SNAPSHOT_ID.fetch_add(1, Ordering::SeqCst)
 }
 
-pub(crate) fn with_backends_mut<T>(
-    f: impl FnOnce(&mut HashMap<u64, IncrementalBackend>) -> T,
-) -> PyResult<T> {
-    let mut map = BACKENDS
-        .get_or_init(|| Mutex::new(HashMap::new()))
-        .lock()
+fn backends() -> &'static RwLock<HashMap<u64, BackendHandle>> {
+    BACKENDS.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+fn snapshots() -> &'static RwLock<HashMap<u64, SnapshotEntry>> {
+    SNAPSHOTS.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Registers a freshly built backend under a new id.
+pub(crate) fn insert_backend(id: u64, backend: IncrementalBackend) -> PyResult<()> {
+    let mut map = backends()
+        .write()
         .map_err(|_| PyRuntimeError::new_err("failed to lock backend registry"))?;
-    Ok(f(&mut map))
+    map.insert(id, Arc::new(Mutex::new(backend)));
+    Ok(())
 }
 
-pub(crate) fn with_snapshots_mut<T>(
-    f: impl FnOnce(&mut HashMap<u64, RuntimeSnapshot>) -> T,
+fn backend_handle(id: u64) -> PyResult<BackendHandle> {
+    let map = backends()
+        .read()
+        .map_err(|_| PyRuntimeError::new_err("failed to lock backend registry"))?;
+    map.get(&id)
+        .cloned()
+        .ok_or_else(|| PyKeyError::new_err(format!("backend id {id} not found")))
+}
+
+/// Runs `f` against the backend registered under `id`. The registry-wide
+/// lock is released before `f` runs -- only `id`'s own `Mutex` is held for
+/// the duration of the call, so `incremental_step`/`incremental_replay`
+/// calls against *distinct* backend ids (e.g. from a Python thread pool
+/// with the GIL released) run fully in parallel. A single backend id is
+/// still single-writer: concurrent calls against the same id serialize on
+/// its `Mutex`, same as before this was split out from the registry lock.
+pub(crate) fn with_backend_mut<T>(
+    id: u64,
+    f: impl FnOnce(&mut IncrementalBackend) -> T,
 ) -> PyResult<T> {
-    let mut map = SNAPSHOTS
-        .get_or_init(|| Mutex::new(HashMap::new()))
+    let handle = backend_handle(id)?;
+    let mut backend = handle
         .lock()
+        .map_err(|_| PyRuntimeError::new_err(format!("backend id {id} lock poisoned")))?;
+    Ok(f(&mut backend))
+}
+
+pub(crate) fn insert_snapshot(id: u64, snapshot: RuntimeSnapshot) -> PyResult<()> {
+    let mut map = snapshots()
+        .write()
         .map_err(|_| PyRuntimeError::new_err("failed to lock snapshot registry"))?;
-    Ok(f(&mut map))
+    map.insert(
+        id,
+        SnapshotEntry {
+            snapshot,
+            pydict_cache: OnceLock::new(),
+        },
+    );
+    Ok(())
 }
 
-pub(crate) fn with_snapshots<T>(
-    f: impl FnOnce(&HashMap<u64, RuntimeSnapshot>) -> T,
-) -> PyResult<T> {
-    let map = SNAPSHOTS
-        .get_or_init(|| Mutex::new(HashMap::new()))
-        .lock()
+/// Clones the stored snapshot for `id` out from behind a read guard.
+pub(crate) fn snapshot(id: u64) -> PyResult<RuntimeSnapshot> {
+    let map = snapshots()
+        .read()
         .map_err(|_| PyRuntimeError::new_err("failed to lock snapshot registry"))?;
-    Ok(f(&map))
+    map.get(&id)
+        .map(|entry| entry.snapshot.clone())
+        .ok_or_else(|| PyKeyError::new_err(format!("snapshot id {id} not found")))
+}
+
+/// Returns `id`'s JSON-shaped dict representation (`schema_version`,
+/// `nodes`), building it on first call and cloning the cached `Py` handle
+/// on every call after that instead of re-walking the snapshot's
+/// `BTreeMap`s and re-allocating Python objects each time.
+pub(crate) fn snapshot_pydict(py: Python<'_>, id: u64) -> PyResult<Py<PyDict>> {
+    let map = snapshots()
+        .read()
+        .map_err(|_| PyRuntimeError::new_err("failed to lock snapshot registry"))?;
+    let entry = map
+        .get(&id)
+        .ok_or_else(|| PyKeyError::new_err(format!("snapshot id {id} not found")))?;
+
+    if let Some(cached) = entry.pydict_cache.get() {
+        return Ok(cached.clone_ref(py));
+    }
+
+    let json = ta_engine::incremental::snapshot_codec::encode_snapshot_to_json(&entry.snapshot);
+    let dict = json_value_to_pydict(py, &json)?;
+    let handle: Py<PyDict> = dict.unbind();
+    let cached = entry.pydict_cache.get_or_init(|| handle.clone_ref(py));
+    Ok(cached.clone_ref(py))
 }